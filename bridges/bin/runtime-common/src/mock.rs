@@ -226,6 +226,7 @@ impl pallet_bridge_parachains::Config for TestRuntime {
 		SingleParaStoredHeaderDataBuilder<BridgedUnderlyingParachain>;
 	type HeadsToKeep = ConstU32<8>;
 	type MaxParaHeadDataSize = ConstU32<1024>;
+	type ParaHeadsRootVerifier = ();
 	type WeightInfo = pallet_bridge_parachains::weights::BridgeWeight<TestRuntime>;
 }
 
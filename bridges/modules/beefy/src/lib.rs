@@ -0,0 +1,926 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY bridge pallet.
+//!
+//! This pallet is an on-chain BEEFY light client for Substrate-based chains that are using the
+//! following pallets bundle: `pallet-mmr`, `pallet-beefy` and `pallet-beefy-mmr`.
+//!
+//! The pallet is able to verify MMR leaf proofs and BEEFY commitments, so it has access
+//! to the following data of the bridged chain:
+//!
+//! - header hashes;
+//! - changes of BEEFY authorities;
+//! - extra data of MMR leafs (e.g. parachains heads when bridged with relay chain and properly
+//!   configured).
+//!
+//! Given the header hash, other pallets are able to verify header-based proofs
+//! (e.g. storage proofs, transaction inclusion proofs, etc.). Given the MMR root, other
+//! pallets are able to verify that some MMR leaf has been committed by the bridged chain.
+//! E.g. the [`BeefyParaHeadsRootVerifier`] may be used by the `pallet-bridge-parachains`
+//! to prove parachain heads without importing relay chain headers.
+//!
+//! Right now the pallet verifies all signatures of the submitted commitment. Verifying a
+//! random subset of signatures (sampling) may be added later.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use bp_beefy::{ChainWithBeefy, InitializationData};
+use sp_std::{boxed::Box, prelude::*};
+
+// Re-export in crate namespace for `construct_runtime!`
+pub use pallet::*;
+pub use para_heads::{BeefyParaHeadsRootProof, BeefyParaHeadsRootVerifier};
+pub use weights::WeightInfo;
+
+mod para_heads;
+mod utils;
+
+/// Module, containing weights for this pallet.
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod mock_chain;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-beefy";
+
+/// Configured bridged chain.
+pub type BridgedChain<T, I> = <T as Config<I>>::BridgedChain;
+/// Block number, used by configured bridged chain.
+pub type BridgedBlockNumber<T, I> = bp_runtime::BlockNumberOf<BridgedChain<T, I>>;
+/// Block hash, used by configured bridged chain.
+pub type BridgedBlockHash<T, I> = bp_runtime::HashOf<BridgedChain<T, I>>;
+
+/// Pallet initialization data.
+pub type InitializationDataOf<T, I> =
+	InitializationData<BridgedBlockNumber<T, I>, bp_beefy::MmrHashOf<BridgedChain<T, I>>>;
+/// BEEFY commitment hasher, used by configured bridged chain.
+pub type BridgedBeefyCommitmentHasher<T, I> = bp_beefy::BeefyCommitmentHasher<BridgedChain<T, I>>;
+/// BEEFY validator id, used by configured bridged chain.
+pub type BridgedBeefyAuthorityId<T, I> = bp_beefy::BeefyAuthorityIdOf<BridgedChain<T, I>>;
+/// BEEFY validator set, used by configured bridged chain.
+pub type BridgedBeefyAuthoritySet<T, I> = bp_beefy::BeefyAuthoritySetOf<BridgedChain<T, I>>;
+/// BEEFY authority set, used by configured bridged chain.
+pub type BridgedBeefyAuthoritySetInfo<T, I> = bp_beefy::BeefyAuthoritySetInfoOf<BridgedChain<T, I>>;
+/// BEEFY signed commitment, used by configured bridged chain.
+pub type BridgedBeefySignedCommitment<T, I> = bp_beefy::BeefySignedCommitmentOf<BridgedChain<T, I>>;
+/// MMR hashing, used by configured bridged chain.
+pub type BridgedMmrHashing<T, I> = bp_beefy::MmrHashingOf<BridgedChain<T, I>>;
+/// MMR hash, used by configured bridged chain.
+pub type BridgedMmrHash<T, I> = bp_beefy::MmrHashOf<BridgedChain<T, I>>;
+/// The type of the MMR leaf extra data used by the configured bridged chain.
+pub type BridgedBeefyMmrLeafExtra<T, I> = bp_beefy::BeefyMmrLeafExtraOf<BridgedChain<T, I>>;
+/// BEEFY MMR proof type used by the pallet.
+pub type BridgedMmrProof<T, I> = bp_beefy::MmrProofOf<BridgedChain<T, I>>;
+/// MMR leaf type, used by configured bridged chain.
+pub type BridgedBeefyMmrLeaf<T, I> = bp_beefy::BeefyMmrLeafOf<BridgedChain<T, I>>;
+/// Imported commitment data, stored by the pallet.
+pub type ImportedCommitment<T, I> = bp_beefy::ImportedCommitment<
+	BridgedBlockNumber<T, I>,
+	BridgedBlockHash<T, I>,
+	BridgedMmrHash<T, I>,
+>;
+
+/// Some high level info about the imported commitments.
+#[derive(codec::Encode, codec::Decode, scale_info::TypeInfo)]
+pub struct ImportedCommitmentsInfoData<BlockNumber> {
+	/// Best known block number, provided in a BEEFY commitment. However this is not
+	/// the best proven block. The best proven block is this block's parent.
+	pub best_block_number: BlockNumber,
+	/// The head of the `ImportedBlockNumbers` ring buffer.
+	pub next_block_number_index: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use bp_runtime::{BasicOperatingMode, OwnedBridgeModule};
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The chain we are bridging to here.
+		type BridgedChain: ChainWithBeefy;
+
+		/// Maximal number of imported commitments to keep in the storage.
+		///
+		/// The setting is there to prevent growing the on-chain state indefinitely. Note
+		/// the setting does not relate to block numbers - we will simply keep as much items
+		/// in the storage, so it doesn't guarantee any fixed timeframe for imported commitments.
+		///
+		/// Incautious change of this constant may lead to orphan entries in the runtime storage.
+		#[pallet::constant]
+		type CommitmentsToKeep: Get<u32>;
+
+		/// Weights of the pallet calls.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn integrity_test() {
+			assert!(
+				T::CommitmentsToKeep::get() > 0,
+				"CommitmentsToKeep must be greater than zero!"
+			);
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> OwnedBridgeModule<T> for Pallet<T, I> {
+		const LOG_TARGET: &'static str = LOG_TARGET;
+		type OwnerStorage = PalletOwner<T, I>;
+		type OperatingMode = BasicOperatingMode;
+		type OperatingModeStorage = PalletOperatingMode<T, I>;
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Submit a commitment generated by BEEFY authority set.
+		///
+		/// The commitment must be signed by the current or by the next BEEFY authority set,
+		/// known to the pallet. The `validator_set` must be the full set of validators that
+		/// is committed to by the corresponding `BeefyAuthoritySet::keyset_commitment`. The
+		/// `mmr_leaf` must be the latest leaf of the MMR, whose root is found in the commitment
+		/// payload, and the `mmr_proof` must prove that it is a part of this MMR.
+		///
+		/// If the commitment is signed by the next authority set, the pallet enacts the
+		/// authority set change: the next set becomes current and the next set is read
+		/// from the `mmr_leaf`.
+		///
+		/// The call fails if:
+		///
+		/// - the pallet is halted;
+		///
+		/// - the pallet knows better commitment than the submitted one;
+		///
+		/// - the commitment or the MMR leaf proof is invalid.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit_commitment(
+			commitment.signatures.len() as u32,
+			mmr_proof.items.len() as u32,
+		))]
+		pub fn submit_commitment(
+			origin: OriginFor<T>,
+			commitment: BridgedBeefySignedCommitment<T, I>,
+			validator_set: Box<BridgedBeefyAuthoritySet<T, I>>,
+			mmr_leaf: Box<BridgedBeefyMmrLeaf<T, I>>,
+			mmr_proof: BridgedMmrProof<T, I>,
+		) -> DispatchResult {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			let mut imported_commitments_info =
+				ImportedCommitmentsInfo::<T, I>::get().ok_or(Error::<T, I>::NotInitialized)?;
+			let block_number = commitment.commitment.block_number;
+			ensure!(
+				block_number > imported_commitments_info.best_block_number,
+				Error::<T, I>::OldCommitment
+			);
+
+			// select the authority set that has signed the commitment
+			let current_authority_set_info = CurrentAuthoritySetInfo::<T, I>::get();
+			let next_authority_set_info = NextAuthoritySetInfo::<T, I>::get();
+			let commitment_set_id = commitment.commitment.validator_set_id;
+			let is_signed_by_next_set = commitment_set_id != current_authority_set_info.id &&
+				commitment_set_id == next_authority_set_info.id;
+			let authority_set_info = if is_signed_by_next_set {
+				&next_authority_set_info
+			} else {
+				&current_authority_set_info
+			};
+
+			// verify commitment and the MMR leaf
+			let mmr_root =
+				utils::verify_commitment::<T, I>(&commitment, authority_set_info, &validator_set)?;
+			utils::verify_latest_beefy_mmr_leaf::<T, I>(
+				block_number,
+				&mmr_leaf,
+				mmr_proof,
+				mmr_root,
+			)?;
+
+			// enact authority set change, if required
+			if is_signed_by_next_set {
+				let new_next_authority_set_info = mmr_leaf.beefy_next_authority_set.clone();
+				ensure!(
+					new_next_authority_set_info.id == next_authority_set_info.id + 1,
+					Error::<T, I>::InvalidNextAuthoritySet
+				);
+
+				log::info!(
+					target: LOG_TARGET,
+					"Transitioned from BEEFY authority set {} to {}",
+					current_authority_set_info.id,
+					next_authority_set_info.id,
+				);
+
+				CurrentAuthoritySetInfo::<T, I>::put(next_authority_set_info);
+				NextAuthoritySetInfo::<T, I>::put(new_next_authority_set_info);
+			}
+
+			// import the commitment
+			let block_number_index = imported_commitments_info.next_block_number_index;
+			let to_prune = ImportedBlockNumbers::<T, I>::try_get(block_number_index);
+			ImportedCommitments::<T, I>::insert(
+				block_number,
+				ImportedCommitment::<T, I> {
+					parent_number_and_hash: mmr_leaf.parent_number_and_hash,
+					mmr_root,
+				},
+			);
+			ImportedBlockNumbers::<T, I>::insert(block_number_index, block_number);
+			imported_commitments_info.best_block_number = block_number;
+			imported_commitments_info.next_block_number_index = (imported_commitments_info
+				.next_block_number_index +
+				1) % T::CommitmentsToKeep::get();
+			ImportedCommitmentsInfo::<T, I>::put(imported_commitments_info);
+			if let Ok(old_block_number) = to_prune {
+				log::debug!(
+					target: LOG_TARGET,
+					"Pruning commitment for old block: {:?}.",
+					old_block_number
+				);
+				ImportedCommitments::<T, I>::remove(old_block_number);
+			}
+
+			log::info!(
+				target: LOG_TARGET,
+				"Successfully imported commitment for block {:?}",
+				block_number,
+			);
+
+			Self::deposit_event(Event::CommitmentImported { number: block_number, mmr_root });
+
+			Ok(())
+		}
+
+		/// Bootstrap the bridge pallet with an initial block number and authority sets from
+		/// which to sync.
+		///
+		/// This function is only allowed to be called from a trusted origin and writes to storage
+		/// with practically no checks in terms of the validity of the data. It is important that
+		/// you ensure that valid data is being passed in.
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 4), DispatchClass::Operational))]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			init_data: InitializationDataOf<T, I>,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+
+			let is_initialized = <ImportedCommitmentsInfo<T, I>>::exists();
+			ensure!(!is_initialized, <Error<T, I>>::AlreadyInitialized);
+
+			log::info!(target: LOG_TARGET, "Initializing bridge BEEFY pallet: {:?}", init_data);
+			Ok(initialize::<T, I>(init_data)?)
+		}
+
+		/// Change `PalletOwner`.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(2)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_owner(origin: OriginFor<T>, new_owner: Option<T::AccountId>) -> DispatchResult {
+			<Self as OwnedBridgeModule<_>>::set_owner(origin, new_owner)
+		}
+
+		/// Halt or resume all pallet operations.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(3)]
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_operating_mode(
+			origin: OriginFor<T>,
+			operating_mode: BasicOperatingMode,
+		) -> DispatchResult {
+			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
+		}
+	}
+
+	/// High level info about the imported commitments.
+	///
+	/// Contains the following info:
+	/// - best known block number of the bridged chain, finalized by BEEFY;
+	/// - the head of the `ImportedBlockNumbers` ring buffer.
+	#[pallet::storage]
+	pub type ImportedCommitmentsInfo<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ImportedCommitmentsInfoData<BridgedBlockNumber<T, I>>>;
+
+	/// A ring buffer containing the block numbers of the commitments that we have imported,
+	/// ordered by the insertion time.
+	#[pallet::storage]
+	pub(super) type ImportedBlockNumbers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, u32, BridgedBlockNumber<T, I>>;
+
+	/// All the commitments that we have imported and haven't been pruned yet.
+	#[pallet::storage]
+	pub type ImportedCommitments<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, BridgedBlockNumber<T, I>, ImportedCommitment<T, I>>;
+
+	/// The current BEEFY authority set at the bridged chain.
+	#[pallet::storage]
+	pub type CurrentAuthoritySetInfo<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgedBeefyAuthoritySetInfo<T, I>, ValueQuery>;
+
+	/// The next BEEFY authority set at the bridged chain.
+	#[pallet::storage]
+	pub type NextAuthoritySetInfo<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgedBeefyAuthoritySetInfo<T, I>, ValueQuery>;
+
+	/// Optional pallet owner.
+	///
+	/// Pallet owner has the right to halt all pallet operations and then resume it. If it is
+	/// `None`, then there are no direct ways to halt/resume pallet operations, but other
+	/// runtime methods may still be used to do that (i.e. `democracy::referendum` to update halt
+	/// flag directly or calling `set_operating_mode`).
+	#[pallet::storage]
+	pub type PalletOwner<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The current operating mode of the pallet.
+	///
+	/// Depending on the mode either all, or no transactions will be allowed.
+	#[pallet::storage]
+	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BasicOperatingMode, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		/// Optional module owner account.
+		pub owner: Option<T::AccountId>,
+		/// Optional module initialization data.
+		pub init_data: Option<InitializationDataOf<T, I>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
+		fn build(&self) {
+			if let Some(ref owner) = self.owner {
+				<PalletOwner<T, I>>::put(owner);
+			}
+
+			if let Some(init_data) = self.init_data.clone() {
+				initialize::<T, I>(init_data).expect("invalid initialization data of BEEFY bridge");
+			} else {
+				// Since the bridge hasn't been initialized we shouldn't allow anyone to perform
+				// transactions.
+				<PalletOperatingMode<T, I>>::put(BasicOperatingMode::Halted);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// BEEFY commitment for the given bridged chain block has been imported.
+		CommitmentImported {
+			/// Number of the bridged chain block that has been finalized by the commitment.
+			number: BridgedBlockNumber<T, I>,
+			/// MMR root at the `number` block.
+			mmr_root: BridgedMmrHash<T, I>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The pallet has not been initialized yet.
+		NotInitialized,
+		/// The pallet has already been initialized.
+		AlreadyInitialized,
+		/// Invalid initial authority set.
+		InvalidInitialAuthoritySet,
+		/// The submitted commitment is older than the best commitment known to the pallet.
+		OldCommitment,
+		/// The commitment is signed by an unknown validator set.
+		InvalidCommitmentValidatorSetId,
+		/// The id of the provided validator set is invalid.
+		InvalidValidatorSetId,
+		/// The number of validators in the provided validator set is invalid.
+		InvalidValidatorSetLen,
+		/// The provided validator set doesn't match the expected keyset commitment.
+		InvalidValidatorSetRoot,
+		/// The number of signatures in the commitment is invalid.
+		InvalidCommitmentSignaturesLen,
+		/// There are not enough correct signatures in the commitment to finalize the block.
+		NotEnoughCorrectSignatures,
+		/// MMR root is missing from the commitment.
+		MmrRootMissingFromCommitment,
+		/// The provided MMR leaf is not the latest leaf of the committed MMR.
+		InvalidMmrLeaf,
+		/// MMR proof verification has failed.
+		MmrProofVerificationFailed,
+		/// The authority set in the MMR leaf, signed by the next authority set, is invalid.
+		InvalidNextAuthoritySet,
+		/// Error generated by the `OwnedBridgeModule` trait.
+		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+	}
+
+	/// Initialize pallet with given parameters.
+	pub(super) fn initialize<T: Config<I>, I: 'static>(
+		init_data: InitializationDataOf<T, I>,
+	) -> Result<(), Error<T, I>> {
+		if init_data.authority_set.len == 0 || init_data.next_authority_set.len == 0 {
+			return Err(Error::<T, I>::InvalidInitialAuthoritySet)
+		}
+		if init_data.next_authority_set.id != init_data.authority_set.id + 1 {
+			return Err(Error::<T, I>::InvalidInitialAuthoritySet)
+		}
+		CurrentAuthoritySetInfo::<T, I>::put(init_data.authority_set);
+		NextAuthoritySetInfo::<T, I>::put(init_data.next_authority_set);
+
+		<PalletOperatingMode<T, I>>::put(init_data.operating_mode);
+		ImportedCommitmentsInfo::<T, I>::put(ImportedCommitmentsInfoData {
+			best_block_number: init_data.best_block_number,
+			next_block_number_index: 0,
+		});
+
+		Ok(())
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns number of the best block, finalized by the imported BEEFY commitment.
+		pub fn best_block_number() -> Option<BridgedBlockNumber<T, I>> {
+			ImportedCommitmentsInfo::<T, I>::get().map(|info| info.best_block_number)
+		}
+
+		/// Returns the MMR root, committed by the BEEFY commitment for the given block.
+		pub fn imported_mmr_root(number: BridgedBlockNumber<T, I>) -> Option<BridgedMmrHash<T, I>> {
+			ImportedCommitments::<T, I>::get(number).map(|commitment| commitment.mmr_root)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, mock_chain::*};
+	use bp_beefy::{BeefyPayload, ValidatorSet};
+	use bp_parachains::VerifyParaHeadsRoot;
+	use bp_runtime::{BasicOperatingMode, HeaderId, OwnedBridgeModuleError};
+	use bp_test_utils::generate_owned_bridge_module_tests;
+	use codec::Encode;
+	use frame_support::{assert_noop, assert_ok, traits::Get};
+	use sp_runtime::{traits::Header as HeaderT, DispatchError};
+
+	fn import_header_chain(headers: Vec<HeaderAndCommitment>) {
+		for header in headers {
+			if header.commitment.is_some() {
+				assert_ok!(import_commitment(header));
+			}
+		}
+	}
+
+	#[test]
+	fn fails_to_initialize_if_not_owner_and_root() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(1);
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(
+					RuntimeOrigin::signed(10),
+					InitializationData {
+						operating_mode: BasicOperatingMode::Normal,
+						best_block_number: 0,
+						authority_set: authority_set_info(0, &validator_pairs(0, 1)),
+						next_authority_set: authority_set_info(1, &validator_pairs(1, 1)),
+					}
+				),
+				DispatchError::BadOrigin,
+			);
+		})
+	}
+
+	#[test]
+	fn root_is_able_to_initialize_pallet() {
+		run_test_with_initialize(8, || {
+			assert_eq!(Pallet::<TestRuntime>::best_block_number(), Some(0));
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get().id, 0);
+			assert_eq!(NextAuthoritySetInfo::<TestRuntime>::get().id, 1);
+		})
+	}
+
+	#[test]
+	fn owner_is_able_to_initialize_pallet() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(1);
+			assert_ok!(Pallet::<TestRuntime>::initialize(
+				RuntimeOrigin::signed(1),
+				InitializationData {
+					operating_mode: BasicOperatingMode::Normal,
+					best_block_number: 0,
+					authority_set: authority_set_info(0, &validator_pairs(0, 1)),
+					next_authority_set: authority_set_info(1, &validator_pairs(1, 1)),
+				}
+			));
+			assert!(ImportedCommitmentsInfo::<TestRuntime>::exists());
+		})
+	}
+
+	#[test]
+	fn fails_to_initialize_if_already_initialized() {
+		run_test_with_initialize(32, || {
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(
+					RuntimeOrigin::root(),
+					InitializationData {
+						operating_mode: BasicOperatingMode::Normal,
+						best_block_number: 0,
+						authority_set: authority_set_info(0, &validator_pairs(0, 1)),
+						next_authority_set: authority_set_info(1, &validator_pairs(1, 1)),
+					}
+				),
+				Error::<TestRuntime, ()>::AlreadyInitialized,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_initialize_if_authority_set_is_empty() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(
+					RuntimeOrigin::root(),
+					InitializationData {
+						operating_mode: BasicOperatingMode::Normal,
+						best_block_number: 0,
+						authority_set: authority_set_info(0, &[]),
+						next_authority_set: authority_set_info(1, &validator_pairs(1, 1)),
+					}
+				),
+				Error::<TestRuntime, ()>::InvalidInitialAuthoritySet,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_initialize_if_next_authority_set_id_is_invalid() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(
+					RuntimeOrigin::root(),
+					InitializationData {
+						operating_mode: BasicOperatingMode::Normal,
+						best_block_number: 0,
+						authority_set: authority_set_info(0, &validator_pairs(0, 1)),
+						next_authority_set: authority_set_info(2, &validator_pairs(2, 1)),
+					}
+				),
+				Error::<TestRuntime, ()>::InvalidInitialAuthoritySet,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_if_halted() {
+		run_test_with_initialize(1, || {
+			assert_ok!(Pallet::<TestRuntime>::set_operating_mode(
+				RuntimeOrigin::root(),
+				BasicOperatingMode::Halted
+			));
+			assert_noop!(
+				import_commitment(ChainBuilder::new(1).append_finalized_header().header(1)),
+				Error::<TestRuntime, ()>::BridgeModule(OwnedBridgeModuleError::Halted),
+			);
+		})
+	}
+
+	#[test]
+	fn fails_to_import_commitment_if_not_initialized() {
+		run_test(|| {
+			assert_noop!(
+				import_commitment(ChainBuilder::new(1).append_finalized_header().header(1)),
+				Error::<TestRuntime, ()>::NotInitialized,
+			);
+		})
+	}
+
+	#[test]
+	fn importing_commitment_works() {
+		run_test_with_initialize(8, || {
+			System::set_block_number(1);
+
+			let chain = ChainBuilder::new(8).append_finalized_header();
+			let header = chain.header(1);
+			assert_ok!(import_commitment(header.clone()));
+
+			assert_eq!(Pallet::<TestRuntime>::best_block_number(), Some(1));
+			assert_eq!(Pallet::<TestRuntime>::imported_mmr_root(1), Some(header.mmr_root));
+			assert_eq!(
+				ImportedCommitments::<TestRuntime>::get(1).map(|c| c.parent_number_and_hash),
+				Some((0, *header.header.parent_hash())),
+			);
+			assert_eq!(
+				System::events().last().map(|event| event.event.clone()),
+				Some(RuntimeEvent::Beefy(Event::CommitmentImported {
+					number: 1,
+					mmr_root: header.mmr_root,
+				})),
+			);
+		});
+	}
+
+	#[test]
+	fn importing_commitment_with_skipped_headers_works() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8).append_default_headers(4).append_finalized_header();
+			assert_ok!(import_commitment(chain.header(5)));
+			assert_eq!(Pallet::<TestRuntime>::best_block_number(), Some(5));
+		});
+	}
+
+	#[test]
+	fn fails_to_import_old_commitment() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8).append_finalized_headers(2);
+			assert_ok!(import_commitment(chain.header(2)));
+			assert_noop!(
+				import_commitment(chain.header(1)),
+				Error::<TestRuntime, ()>::OldCommitment,
+			);
+			assert_noop!(
+				import_commitment(chain.header(2)),
+				Error::<TestRuntime, ()>::OldCommitment,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_signed_by_unknown_validator_set() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.customize_commitment(
+				|commitment| commitment.validator_set_id = 42,
+				&validator_pairs(0, 8),
+				8,
+			);
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::InvalidCommitmentValidatorSetId,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_invalid_validator_set() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.validator_set =
+				ValidatorSet::new(validator_ids(&validator_pairs(42, 8)), 0).unwrap();
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::InvalidValidatorSetRoot,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_invalid_validator_set_len() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.validator_set =
+				ValidatorSet::new(validator_ids(&validator_pairs(0, 4)), 0).unwrap();
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::InvalidValidatorSetLen,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_invalid_signatures_len() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.customize_signatures(|signatures| {
+				signatures.pop();
+			});
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::InvalidCommitmentSignaturesLen,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_not_enough_signatures() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.customize_commitment(|_| {}, &validator_pairs(0, 8), 5);
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::NotEnoughCorrectSignatures,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_invalid_signatures() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			// signatures of another validator set are not accepted
+			let other_validators = validator_pairs(42, 8);
+			let other_signatures = sign_commitment(
+				header.commitment.clone().unwrap().commitment,
+				&other_validators,
+				8,
+			)
+			.signatures;
+			header.customize_signatures(|signatures| {
+				signatures[0] = other_signatures[0].clone();
+				signatures[1] = other_signatures[1].clone();
+			});
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::NotEnoughCorrectSignatures,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_without_mmr_root() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.customize_commitment(
+				|commitment| commitment.payload = BeefyPayload::from_single_entry(*b"xy", vec![]),
+				&validator_pairs(0, 8),
+				8,
+			);
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::MmrRootMissingFromCommitment,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_not_latest_mmr_leaf() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8).append_finalized_headers(2);
+			let mut header = chain.header(2);
+			header.leaf = chain.header(1).leaf;
+			assert_noop!(import_commitment(header), Error::<TestRuntime, ()>::InvalidMmrLeaf);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_invalid_mmr_proof() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8).append_finalized_headers(4);
+			let mut header = chain.header(4);
+			header.leaf_proof.items.pop();
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::MmrProofVerificationFailed,
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_commitment_with_modified_mmr_leaf() {
+		run_test_with_initialize(8, || {
+			let mut header = ChainBuilder::new(8).append_finalized_header().header(1);
+			header.leaf.leaf_extra = Default::default();
+			header.leaf.leaf_extra.0[0] = 42;
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::MmrProofVerificationFailed,
+			);
+		});
+	}
+
+	#[test]
+	fn importing_commitment_signed_by_next_set_enacts_handoff() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8)
+				.append_finalized_header()
+				.append_handoff_header(16)
+				.append_finalized_header();
+			import_header_chain(vec![chain.header(1), chain.header(2), chain.header(3)]);
+
+			assert_eq!(Pallet::<TestRuntime>::best_block_number(), Some(3));
+			assert_eq!(
+				CurrentAuthoritySetInfo::<TestRuntime>::get(),
+				authority_set_info(1, &validator_pairs(1, 8)),
+			);
+			assert_eq!(
+				NextAuthoritySetInfo::<TestRuntime>::get(),
+				authority_set_info(2, &validator_pairs(2, 16)),
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_import_handoff_commitment_with_modified_next_authority_set() {
+		run_test_with_initialize(8, || {
+			let chain = ChainBuilder::new(8).append_handoff_header(8);
+			let mut header = chain.header(1);
+			header.leaf.beefy_next_authority_set.id = 42;
+			assert_noop!(
+				import_commitment(header),
+				Error::<TestRuntime, ()>::MmrProofVerificationFailed,
+			);
+		});
+	}
+
+	#[test]
+	fn old_commitments_are_pruned() {
+		run_test_with_initialize(8, || {
+			let commitments_to_keep = <TestRuntime as Config<()>>::CommitmentsToKeep::get();
+			let commitments_to_import = commitments_to_keep as usize * 2;
+			let chain = ChainBuilder::new(8).append_finalized_headers(commitments_to_import);
+
+			for number in 1..=commitments_to_import as TestBridgedBlockNumber {
+				assert_ok!(import_commitment(chain.header(number)));
+
+				let pruned_number = number.checked_sub(commitments_to_keep);
+				if let Some(pruned_number) = pruned_number.filter(|n| *n > 0) {
+					assert!(!ImportedCommitments::<TestRuntime>::contains_key(pruned_number));
+				}
+				let oldest_kept = pruned_number.unwrap_or(0) + 1;
+				for kept_number in oldest_kept..=number {
+					assert!(ImportedCommitments::<TestRuntime>::contains_key(kept_number));
+				}
+			}
+		});
+	}
+
+	#[test]
+	fn para_heads_root_is_verified() {
+		run_test_with_initialize(8, || {
+			let para_heads = vec![(1000, vec![1, 2, 3]), (2000, vec![4, 5, 6])];
+			let chain = ChainBuilder::new(8)
+				.set_para_heads(para_heads.clone())
+				.append_finalized_headers(2)
+				.append_default_headers(2)
+				.append_finalized_header();
+			import_header_chain(vec![chain.header(2), chain.header(5)]);
+
+			// leaf of the non-finalized block is proved using the MMR root of the best commitment
+			let leaf = chain.header(3).leaf;
+			let expected_root = bp_beefy::merkle_root::<sp_runtime::traits::Keccak256, _>(
+				para_heads.iter().map(|(para_id, head)| (para_id, head).encode()),
+			);
+			assert_eq!(
+				BeefyParaHeadsRootVerifier::<TestRuntime, ()>::verify_para_heads_root(
+					BeefyParaHeadsRootProof {
+						commitment_block_number: 5,
+						mmr_leaf: Box::new(leaf.clone()),
+						mmr_proof: chain.leaf_proof_at_best(3),
+					}
+				),
+				Some((HeaderId(2, chain.header(2).header.hash()), expected_root)),
+			);
+
+			// proof is rejected if commitment is unknown
+			assert_eq!(
+				BeefyParaHeadsRootVerifier::<TestRuntime, ()>::verify_para_heads_root(
+					BeefyParaHeadsRootProof {
+						commitment_block_number: 4,
+						mmr_leaf: Box::new(leaf.clone()),
+						mmr_proof: chain.leaf_proof_at_best(3),
+					}
+				),
+				None,
+			);
+
+			// proof is rejected if it is generated for another MMR root
+			assert_eq!(
+				BeefyParaHeadsRootVerifier::<TestRuntime, ()>::verify_para_heads_root(
+					BeefyParaHeadsRootProof {
+						commitment_block_number: 2,
+						mmr_leaf: Box::new(leaf),
+						mmr_proof: chain.leaf_proof_at_best(3),
+					}
+				),
+				None,
+			);
+		});
+	}
+
+	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
+}
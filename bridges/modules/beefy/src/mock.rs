@@ -0,0 +1,199 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+// From construct_runtime macro
+#![allow(clippy::from_over_into)]
+
+use crate as beefy;
+use crate::{
+	utils::get_authorities_mmr_root, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
+	BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedMmrHash, BridgedMmrProof,
+};
+
+use bp_beefy::{
+	BeefyValidatorSignatureOf, ChainWithBeefy, EcdsaValidatorId, InitializationData, MmrDataOrHash,
+};
+use bp_runtime::{BasicOperatingMode, Chain};
+use codec::Encode;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::Weight,
+};
+use sp_core::{ecdsa, sr25519::Signature, Pair};
+use sp_runtime::{
+	testing::H256,
+	traits::{BlakeTwo256, IdentityLookup, Keccak256},
+	DispatchResult, Perbill,
+};
+
+pub type TestAccountId = u64;
+pub type TestBridgedBlockNumber = u32;
+pub type TestBridgedBlockHash = H256;
+pub type TestBridgedHeader = sp_runtime::generic::Header<TestBridgedBlockNumber, BlakeTwo256>;
+pub type TestBridgedAuthoritySetInfo = BridgedBeefyAuthoritySetInfo<TestRuntime, ()>;
+pub type TestBridgedValidatorSet = BridgedBeefyAuthoritySet<TestRuntime, ()>;
+pub type TestBridgedCommitment = BridgedBeefySignedCommitment<TestRuntime, ()>;
+pub type TestBridgedValidatorSignature = BeefyValidatorSignatureOf<TestBridgedChain>;
+pub type TestBridgedMmrHash = BridgedMmrHash<TestRuntime, ()>;
+pub type TestBridgedMmrProof = BridgedMmrProof<TestRuntime, ()>;
+pub type TestBridgedRawMmrLeaf = BridgedBeefyMmrLeaf<TestRuntime, ()>;
+pub type TestBridgedMmrNode = MmrDataOrHash<Keccak256, TestBridgedRawMmrLeaf>;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Beefy: beefy::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const MaximumBlockWeight: Weight = Weight::from_parts(1024, 0);
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for TestRuntime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Nonce = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = TestAccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl beefy::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type BridgedChain = TestBridgedChain;
+	type CommitmentsToKeep = ConstU32<16>;
+	type WeightInfo = ();
+}
+
+#[derive(Debug)]
+pub struct TestBridgedChain;
+
+impl Chain for TestBridgedChain {
+	type BlockNumber = TestBridgedBlockNumber;
+	type Hash = H256;
+	type Hasher = BlakeTwo256;
+	type Header = TestBridgedHeader;
+
+	type AccountId = TestAccountId;
+	type Balance = u64;
+	type Nonce = u64;
+	type Signature = Signature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+impl ChainWithBeefy for TestBridgedChain {
+	type CommitmentHasher = Keccak256;
+	type MmrHashing = Keccak256;
+	type MmrHash = H256;
+	type BeefyMmrLeafExtra = H256;
+	type AuthorityId = EcdsaValidatorId;
+	type AuthorityIdToMerkleLeaf = bp_beefy::BeefyEcdsaToEthereum;
+}
+
+/// Run test within test runtime.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+}
+
+/// Initialize pallet and run test.
+pub fn run_test_with_initialize<T>(initial_validators_count: u32, test: impl FnOnce() -> T) -> T {
+	run_test(|| {
+		let validators = validator_pairs(0, initial_validators_count);
+		let next_validators = validator_pairs(1, initial_validators_count);
+		let authority_set = authority_set_info(0, &validators);
+		let next_authority_set = authority_set_info(1, &next_validators);
+
+		crate::Pallet::<TestRuntime>::initialize(
+			RuntimeOrigin::root(),
+			InitializationData {
+				operating_mode: BasicOperatingMode::Normal,
+				best_block_number: 0,
+				authority_set,
+				next_authority_set,
+			},
+		)
+		.expect("initialization data is correct");
+
+		test()
+	})
+}
+
+/// Import given commitment.
+pub fn import_commitment(header: crate::mock_chain::HeaderAndCommitment) -> DispatchResult {
+	crate::Pallet::<TestRuntime>::submit_commitment(
+		RuntimeOrigin::signed(1),
+		header
+			.commitment
+			.expect("thou shall not call import_commitment on header without commitment"),
+		Box::new(header.validator_set),
+		Box::new(header.leaf),
+		header.leaf_proof,
+	)
+}
+
+/// Return `count` validator keys of the validator set with given id.
+pub fn validator_pairs(set_id: u64, count: u32) -> Vec<ecdsa::Pair> {
+	(0..count)
+		.map(|index| {
+			let mut seed = [1u8; 32];
+			seed[0..8].copy_from_slice(&set_id.encode());
+			seed[8..12].copy_from_slice(&index.encode());
+			ecdsa::Pair::from_seed(&seed)
+		})
+		.collect()
+}
+
+/// Return identifiers of given validators.
+pub fn validator_ids(validators: &[ecdsa::Pair]) -> Vec<EcdsaValidatorId> {
+	validators.iter().map(|pair| EcdsaValidatorId::from(pair.public())).collect()
+}
+
+/// Return authority set info of given validators.
+pub fn authority_set_info(id: u64, validators: &[ecdsa::Pair]) -> TestBridgedAuthoritySetInfo {
+	let merkle_root = get_authorities_mmr_root::<TestRuntime, ()>(&validator_ids(validators));
+
+	TestBridgedAuthoritySetInfo { id, len: validators.len() as u32, keyset_commitment: merkle_root }
+}
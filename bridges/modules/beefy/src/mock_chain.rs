@@ -0,0 +1,274 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Utilities to build bridged chain and BEEFY+MMR structures.
+
+use crate::mock::*;
+
+use bp_beefy::{
+	merkle_root, signatures_required, BeefyPayload, Commitment, MmrLeafVersion, MmrProof,
+	SignedCommitment, ValidatorSet, ValidatorSetId, MMR_ROOT_PAYLOAD_ID,
+};
+use codec::Encode;
+use sp_core::{ecdsa, keccak_256};
+use sp_runtime::traits::{Hash, Header as HeaderT, Keccak256};
+
+/// Merge strategy, used by the bridged chain MMR. Must be the same as in the `pallet-mmr`.
+pub struct BridgedMmrHashMerge;
+
+impl mmr_lib::Merge for BridgedMmrHashMerge {
+	type Item = TestBridgedMmrNode;
+
+	fn merge(left: &Self::Item, right: &Self::Item) -> mmr_lib::Result<Self::Item> {
+		let mut concat = left.hash().as_ref().to_vec();
+		concat.extend_from_slice(right.hash().as_ref());
+
+		Ok(TestBridgedMmrNode::Hash(Keccak256::hash(&concat)))
+	}
+}
+
+/// Bridged chain header with all structures that are required to import it.
+#[derive(Debug, Clone)]
+pub struct HeaderAndCommitment {
+	/// Header itself.
+	pub header: TestBridgedHeader,
+	/// Signed commitment, if the header is finalized by BEEFY.
+	pub commitment: Option<TestBridgedCommitment>,
+	/// Validator set that has signed the commitment.
+	pub validator_set: TestBridgedValidatorSet,
+	/// MMR leaf that has been added to the MMR at this header.
+	pub leaf: TestBridgedRawMmrLeaf,
+	/// Proof of the `leaf` inclusion into the MMR at this header.
+	pub leaf_proof: TestBridgedMmrProof,
+	/// MMR root at this header.
+	pub mmr_root: TestBridgedMmrHash,
+}
+
+impl HeaderAndCommitment {
+	/// Update signatures of the commitment.
+	pub fn customize_signatures(
+		&mut self,
+		f: impl FnOnce(&mut Vec<Option<TestBridgedValidatorSignature>>),
+	) {
+		if let Some(commitment) = &mut self.commitment {
+			f(&mut commitment.signatures);
+		}
+	}
+
+	/// Update the commitment and re-sign it with given validators.
+	pub fn customize_commitment(
+		&mut self,
+		f: impl FnOnce(&mut Commitment<TestBridgedBlockNumber>),
+		validator_pairs: &[ecdsa::Pair],
+		signature_count: usize,
+	) {
+		if let Some(mut commitment) = self.commitment.take() {
+			f(&mut commitment.commitment);
+			self.commitment =
+				Some(sign_commitment(commitment.commitment, validator_pairs, signature_count));
+		}
+	}
+}
+
+/// Bridged chain builder.
+pub struct ChainBuilder {
+	headers: Vec<HeaderAndCommitment>,
+	validator_set_id: ValidatorSetId,
+	validator_keys: Vec<ecdsa::Pair>,
+	next_validator_keys: Vec<ecdsa::Pair>,
+	para_heads: Vec<(u32, Vec<u8>)>,
+	mmr: mmr_lib::util::MemMMR<TestBridgedMmrNode, BridgedMmrHashMerge>,
+}
+
+impl ChainBuilder {
+	/// Creates new chain builder with given validators count.
+	pub fn new(initial_validators_count: u32) -> Self {
+		ChainBuilder {
+			headers: Vec::new(),
+			validator_set_id: 0,
+			validator_keys: validator_pairs(0, initial_validators_count),
+			next_validator_keys: validator_pairs(1, initial_validators_count),
+			para_heads: Vec::new(),
+			mmr: Default::default(),
+		}
+	}
+
+	/// Get keys of the current validator set.
+	pub fn validator_keys(&self) -> &[ecdsa::Pair] {
+		&self.validator_keys
+	}
+
+	/// Get header with given number.
+	pub fn header(&self, number: TestBridgedBlockNumber) -> HeaderAndCommitment {
+		self.headers[number as usize - 1].clone()
+	}
+
+	/// Returns proof of inclusion of the leaf, inserted at given header, into the latest MMR.
+	pub fn leaf_proof_at_best(&self, number: TestBridgedBlockNumber) -> TestBridgedMmrProof {
+		let leaf_index = number as u64 - 1;
+		let leaf_count = self.headers.len() as u64;
+		let proof = self
+			.mmr
+			.gen_proof(vec![mmr_lib::leaf_index_to_pos(leaf_index)])
+			.expect("leaf is in the MMR");
+
+		MmrProof {
+			leaf_indices: vec![leaf_index],
+			leaf_count,
+			items: proof.proof_items().iter().map(|node| node.hash()).collect(),
+		}
+	}
+
+	/// Set parachain heads that are committed to by the next appended headers.
+	pub fn set_para_heads(mut self, para_heads: Vec<(u32, Vec<u8>)>) -> Self {
+		self.para_heads = para_heads;
+		self
+	}
+
+	/// Appends header, that has been finalized by BEEFY (so it has a linked signed commitment).
+	pub fn append_finalized_header(self) -> Self {
+		self.append_header(true, None)
+	}
+
+	/// Append multiple finalized headers at once.
+	pub fn append_finalized_headers(mut self, count: usize) -> Self {
+		for _ in 0..count {
+			self = self.append_finalized_header();
+		}
+		self
+	}
+
+	/// Appends header, that is not finalized by BEEFY.
+	pub fn append_default_headers(mut self, count: usize) -> Self {
+		for _ in 0..count {
+			self = self.append_header(false, None);
+		}
+		self
+	}
+
+	/// Appends header, that enacts the next validator set. The commitment for this header is
+	/// signed by the next validator set.
+	pub fn append_handoff_header(self, next_validators_count: u32) -> Self {
+		self.append_header(true, Some(next_validators_count))
+	}
+
+	fn append_header(mut self, is_finalized: bool, handoff: Option<u32>) -> Self {
+		let number = self.headers.len() as TestBridgedBlockNumber + 1;
+		let parent_hash = match self.headers.last() {
+			Some(parent) => parent.header.hash(),
+			None => test_header(0).hash(),
+		};
+		let header = test_header_with_parent(number, parent_hash);
+
+		if let Some(next_validators_count) = handoff {
+			self.validator_set_id += 1;
+			self.validator_keys = std::mem::replace(
+				&mut self.next_validator_keys,
+				validator_pairs(self.validator_set_id + 1, next_validators_count),
+			);
+		}
+
+		// leaf of the block `number` is computed at its parent state
+		let leaf = TestBridgedRawMmrLeaf {
+			version: MmrLeafVersion::new(1, 0),
+			parent_number_and_hash: (number - 1, parent_hash),
+			beefy_next_authority_set: authority_set_info(
+				self.validator_set_id + 1,
+				&self.next_validator_keys,
+			),
+			leaf_extra: merkle_root::<Keccak256, _>(
+				self.para_heads.iter().map(|(para_id, head)| (para_id, head).encode()),
+			),
+		};
+		let leaf_index = number as u64 - 1;
+		self.mmr
+			.push(TestBridgedMmrNode::Data(leaf.clone()))
+			.expect("push to MemMMR never fails");
+		let mmr_root = self.mmr.get_root().expect("MMR is not empty").hash();
+		let proof = self
+			.mmr
+			.gen_proof(vec![mmr_lib::leaf_index_to_pos(leaf_index)])
+			.expect("leaf has just been pushed");
+		let leaf_proof = MmrProof {
+			leaf_indices: vec![leaf_index],
+			leaf_count: leaf_index + 1,
+			items: proof.proof_items().iter().map(|node| node.hash()).collect(),
+		};
+
+		let validator_set =
+			ValidatorSet::new(validator_ids(&self.validator_keys), self.validator_set_id)
+				.expect("validator set is not empty");
+		let commitment = is_finalized.then(|| {
+			sign_commitment(
+				Commitment {
+					payload: BeefyPayload::from_single_entry(
+						MMR_ROOT_PAYLOAD_ID,
+						mmr_root.encode(),
+					),
+					block_number: number,
+					validator_set_id: self.validator_set_id,
+				},
+				&self.validator_keys,
+				signatures_required(self.validator_keys.len()),
+			)
+		});
+
+		self.headers.push(HeaderAndCommitment {
+			header,
+			commitment,
+			validator_set,
+			leaf,
+			leaf_proof,
+			mmr_root,
+		});
+		self
+	}
+}
+
+/// Return test header with given number.
+pub fn test_header(number: TestBridgedBlockNumber) -> TestBridgedHeader {
+	test_header_with_parent(number, Default::default())
+}
+
+/// Return test header with given number and parent hash.
+pub fn test_header_with_parent(
+	number: TestBridgedBlockNumber,
+	parent_hash: TestBridgedBlockHash,
+) -> TestBridgedHeader {
+	TestBridgedHeader::new(
+		number,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		Default::default(),
+	)
+}
+
+/// Sign BEEFY commitment by first `signature_count` validators.
+pub fn sign_commitment(
+	commitment: Commitment<TestBridgedBlockNumber>,
+	validator_pairs: &[ecdsa::Pair],
+	signature_count: usize,
+) -> TestBridgedCommitment {
+	let hash = keccak_256(&commitment.encode());
+	let signatures = validator_pairs
+		.iter()
+		.enumerate()
+		.map(|(index, pair)| (index < signature_count).then(|| pair.sign_prehashed(&hash).into()))
+		.collect();
+
+	SignedCommitment { commitment, signatures }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of parachain heads roots, committed by the bridged relay chain to its BEEFY MMR.
+
+use crate::{
+	utils, BridgedBeefyMmrLeaf, BridgedBlockNumber, BridgedChain, BridgedMmrProof, Config,
+	ImportedCommitments, WeightInfo, LOG_TARGET,
+};
+
+use bp_beefy::ChainWithBeefy;
+use bp_parachains::VerifyParaHeadsRoot;
+use bp_polkadot_core::{
+	parachains::ParaHash, BlockNumber as RelayBlockNumber, Hash as RelayBlockHash,
+};
+use bp_runtime::HeaderId;
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Keccak256, RuntimeDebug};
+use sp_std::{boxed::Box, marker::PhantomData};
+
+/// Proof of the parachain heads root, stored in the `leaf_extra` field of the BEEFY MMR leaf.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BeefyParaHeadsRootProof<BlockNumber, MmrLeaf, MmrProof> {
+	/// Number of the bridged relay chain block with imported BEEFY commitment. The MMR root
+	/// from this commitment is used to verify the `mmr_proof`.
+	pub commitment_block_number: BlockNumber,
+	/// MMR leaf with the parachain heads root.
+	pub mmr_leaf: Box<MmrLeaf>,
+	/// Proof of the `mmr_leaf` inclusion into the MMR.
+	pub mmr_proof: MmrProof,
+}
+
+/// Parachain heads root proof, accepted by the given pallet instance.
+pub type BeefyParaHeadsRootProofOf<T, I> = BeefyParaHeadsRootProof<
+	BridgedBlockNumber<T, I>,
+	BridgedBeefyMmrLeaf<T, I>,
+	BridgedMmrProof<T, I>,
+>;
+
+/// Parachain heads root verifier, backed by the commitments, imported by the BEEFY pallet.
+///
+/// It assumes that the bridged relay chain puts the Keccak-256 merkle root of its parachain
+/// heads into the `leaf_extra` field of the MMR leaves, like Polkadot and Kusama do. The root
+/// in the leaf of block `N` is computed over parachain heads at the block `N - 1`, which is
+/// the parent block, referenced by the leaf.
+pub struct BeefyParaHeadsRootVerifier<T, I>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> VerifyParaHeadsRoot for BeefyParaHeadsRootVerifier<T, I>
+where
+	BridgedChain<T, I>: ChainWithBeefy<
+		BlockNumber = RelayBlockNumber,
+		Hash = RelayBlockHash,
+		BeefyMmrLeafExtra = ParaHash,
+	>,
+{
+	type Hasher = Keccak256;
+	type Proof = BeefyParaHeadsRootProofOf<T, I>;
+
+	fn verify_para_heads_root(
+		proof: Self::Proof,
+	) -> Option<(HeaderId<RelayBlockHash, RelayBlockNumber>, ParaHash)> {
+		let commitment_block_number = proof.commitment_block_number;
+		let mmr_root = match ImportedCommitments::<T, I>::get(commitment_block_number) {
+			Some(imported_commitment) => imported_commitment.mmr_root,
+			None => {
+				log::trace!(
+					target: LOG_TARGET,
+					"Commitment for block {} is unknown, can't verify parachain heads root",
+					commitment_block_number,
+				);
+				return None
+			},
+		};
+
+		utils::verify_beefy_mmr_leaf::<T, I>(&proof.mmr_leaf, proof.mmr_proof, mmr_root).ok()?;
+
+		let (relay_block_number, relay_block_hash) = proof.mmr_leaf.parent_number_and_hash;
+		Some((HeaderId(relay_block_number, relay_block_hash), proof.mmr_leaf.leaf_extra))
+	}
+
+	fn verify_para_heads_root_weight(proof: &Self::Proof) -> Weight {
+		T::WeightInfo::verify_para_heads_root(proof.mmr_proof.items.len() as u32)
+	}
+}
@@ -0,0 +1,193 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	BridgedBeefyAuthorityId, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
+	BridgedBeefyCommitmentHasher, BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment,
+	BridgedBlockNumber, BridgedChain, BridgedMmrHash, BridgedMmrHashing, BridgedMmrProof, Config,
+	Error, LOG_TARGET,
+};
+use bp_beefy::{
+	merkle_root, signatures_required, verify_mmr_leaves_proof, BeefyAuthorityId,
+	BeefyAuthorityIdToMerkleLeafOf, MmrDataOrHash, MMR_ROOT_PAYLOAD_ID,
+};
+use codec::Encode;
+use frame_support::ensure;
+use sp_runtime::traits::{Convert, Hash, One, Saturating};
+use sp_std::{vec, vec::Vec};
+
+type BridgedMmrDataOrHash<T, I> = MmrDataOrHash<BridgedMmrHashing<T, I>, BridgedBeefyMmrLeaf<T, I>>;
+
+/// Compute the merkle root of the BEEFY authorities.
+///
+/// This is the same value, that is committed to by the `BeefyAuthoritySet::keyset_commitment`.
+pub(crate) fn get_authorities_mmr_root<T: Config<I>, I: 'static>(
+	authorities: &[BridgedBeefyAuthorityId<T, I>],
+) -> BridgedMmrHash<T, I> {
+	let merkle_leafs = authorities
+		.iter()
+		.cloned()
+		.map(BeefyAuthorityIdToMerkleLeafOf::<BridgedChain<T, I>>::convert)
+		.collect::<Vec<_>>();
+	merkle_root::<BridgedMmrHashing<T, I>, _>(merkle_leafs)
+}
+
+/// Verify that the `authority_set` matches the `authority_set_info`.
+fn verify_authority_set<T: Config<I>, I: 'static>(
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(authority_set.id() == authority_set_info.id, Error::<T, I>::InvalidValidatorSetId);
+	ensure!(
+		authority_set.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidValidatorSetLen
+	);
+
+	// Ensure that the authority set that signed the commitment is the expected one.
+	let root = get_authorities_mmr_root::<T, I>(authority_set.validators());
+	ensure!(root == authority_set_info.keyset_commitment, Error::<T, I>::InvalidValidatorSetRoot);
+
+	Ok(())
+}
+
+/// Verify that the commitment is signed by enough validators of the `authority_set`.
+fn verify_signatures<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		commitment.signatures.len() == authority_set.len(),
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	// Ensure that the commitment was signed by enough authorities.
+	let msg = commitment.commitment.encode();
+	let mut missing_signatures = signatures_required(authority_set.len());
+	for (idx, (authority, maybe_sig)) in
+		authority_set.validators().iter().zip(commitment.signatures.iter()).enumerate()
+	{
+		if let Some(sig) = maybe_sig {
+			if <BridgedBeefyAuthorityId<T, I> as BeefyAuthorityId<
+				BridgedBeefyCommitmentHasher<T, I>,
+			>>::verify(authority, sig, &msg)
+			{
+				missing_signatures = missing_signatures.saturating_sub(1);
+				if missing_signatures == 0 {
+					break
+				}
+			} else {
+				log::debug!(
+					target: LOG_TARGET,
+					"Signed commitment contains incorrect signature of validator {} ({:?}): {:?}",
+					idx,
+					authority,
+					sig,
+				);
+			}
+		}
+	}
+	ensure!(missing_signatures == 0, Error::<T, I>::NotEnoughCorrectSignatures);
+
+	Ok(())
+}
+
+/// Extract MMR root from commitment payload.
+fn extract_mmr_root<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	commitment
+		.commitment
+		.payload
+		.get_decoded(&MMR_ROOT_PAYLOAD_ID)
+		.ok_or(Error::MmrRootMissingFromCommitment)
+}
+
+/// Verify the commitment, signed by the given authority set and return the MMR root from
+/// its payload.
+pub(crate) fn verify_commitment<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	// Ensure that the commitment is signed by the expected BEEFY validator set.
+	ensure!(
+		commitment.commitment.validator_set_id == authority_set_info.id,
+		Error::<T, I>::InvalidCommitmentValidatorSetId
+	);
+	ensure!(
+		commitment.signatures.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	verify_authority_set(authority_set_info, authority_set)?;
+	verify_signatures(commitment, authority_set)?;
+
+	extract_mmr_root(commitment)
+}
+
+/// Verify MMR proof of given leaf.
+pub(crate) fn verify_beefy_mmr_leaf<T: Config<I>, I: 'static>(
+	mmr_leaf: &BridgedBeefyMmrLeaf<T, I>,
+	mmr_proof: BridgedMmrProof<T, I>,
+	mmr_root: BridgedMmrHash<T, I>,
+) -> Result<(), Error<T, I>> {
+	let mmr_proof_leaf_count = mmr_proof.leaf_count;
+	let mmr_proof_length = mmr_proof.items.len();
+
+	// Verify the mmr proof for the provided leaf.
+	let mmr_leaf_hash = <BridgedMmrHashing<T, I> as Hash>::hash(&mmr_leaf.encode());
+	verify_mmr_leaves_proof(
+		mmr_root,
+		vec![BridgedMmrDataOrHash::<T, I>::Hash(mmr_leaf_hash)],
+		mmr_proof,
+	)
+	.map_err(|e| {
+		log::error!(
+			target: LOG_TARGET,
+			"MMR proof of leaf {:?} (root: {:?}, leaf count: {}, len: {}) \
+				verification has failed with error: {:?}",
+			mmr_leaf_hash,
+			mmr_root,
+			mmr_proof_leaf_count,
+			mmr_proof_length,
+			e,
+		);
+
+		Error::<T, I>::MmrProofVerificationFailed
+	})
+}
+
+/// Verify MMR proof of the leaf that has been added to the MMR at the commitment block.
+///
+/// This leaf is the latest leaf in the MMR, whose root is committed to by the commitment.
+pub(crate) fn verify_latest_beefy_mmr_leaf<T: Config<I>, I: 'static>(
+	commitment_block_number: BridgedBlockNumber<T, I>,
+	mmr_leaf: &BridgedBeefyMmrLeaf<T, I>,
+	mmr_proof: BridgedMmrProof<T, I>,
+	mmr_root: BridgedMmrHash<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		mmr_leaf.parent_number_and_hash.0.saturating_add(One::one()) == commitment_block_number,
+		Error::<T, I>::InvalidMmrLeaf
+	);
+	ensure!(
+		mmr_proof.leaf_indices.len() == 1 &&
+			mmr_proof.leaf_indices[0].saturating_add(1) == mmr_proof.leaf_count,
+		Error::<T, I>::InvalidMmrLeaf
+	);
+
+	verify_beefy_mmr_leaf::<T, I>(mmr_leaf, mmr_proof, mmr_root)
+}
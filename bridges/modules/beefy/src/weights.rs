@@ -0,0 +1,61 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for the BEEFY bridge pallet.
+//!
+//! The pallet has no benchmarks yet, so the `()` implementation is using estimations, based
+//! on the cost of the most expensive operations: ECDSA public key recovery, MMR proof hashing
+//! and database access.
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_bridge_beefy.
+pub trait WeightInfo {
+	/// Weight of the `submit_commitment` call with `s` signatures and `p` MMR proof items.
+	fn submit_commitment(s: u32, p: u32) -> Weight;
+	/// Weight of the parachain heads root verification with `p` MMR proof items.
+	fn verify_para_heads_root(p: u32) -> Weight;
+}
+
+/// Estimated weight of single ECDSA public key recovery.
+const ECDSA_RECOVER_WEIGHT: Weight = Weight::from_parts(60_000_000, 0);
+/// Estimated weight of single MMR proof item processing (mostly hashing).
+const MMR_PROOF_ITEM_WEIGHT: Weight = Weight::from_parts(2_000_000, 0);
+
+impl WeightInfo for () {
+	fn submit_commitment(s: u32, p: u32) -> Weight {
+		// reads: `PalletOperatingMode`, `ImportedCommitmentsInfo`, `CurrentAuthoritySetInfo`,
+		// `NextAuthoritySetInfo`, `ImportedBlockNumbers`
+		//
+		// writes: `ImportedCommitments` (insert + prune), `ImportedBlockNumbers`,
+		// `ImportedCommitmentsInfo`, `CurrentAuthoritySetInfo`, `NextAuthoritySetInfo`
+		Weight::from_parts(50_000_000, 2048)
+			.saturating_add(ECDSA_RECOVER_WEIGHT.saturating_mul(s as u64))
+			.saturating_add(MMR_PROOF_ITEM_WEIGHT.saturating_mul(p as u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	fn verify_para_heads_root(p: u32) -> Weight {
+		// reads: `ImportedCommitments`
+		Weight::from_parts(20_000_000, 1024)
+			.saturating_add(MMR_PROOF_ITEM_WEIGHT.saturating_mul(p as u64))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
@@ -20,6 +20,12 @@
 //! chain blocks. The main entry point of this module is `submit_parachain_heads`, which
 //! accepts storage proof of some parachain `Heads` entries from bridged relay chain.
 //! It requires corresponding relay headers to be already synced.
+//!
+//! Alternatively, parachain heads may be submitted using the
+//! `submit_parachain_heads_with_root_proof` call. Then they are proved against the root of
+//! all parachain heads, committed to by the bridged relay chain, which is verified using
+//! the configured `ParaHeadsRootVerifier` (e.g. against the MMR root, imported by the BEEFY
+//! pallet).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -27,7 +33,10 @@ pub use weights::WeightInfo;
 pub use weights_ext::WeightInfoExt;
 
 use bp_header_chain::{HeaderChain, HeaderChainError};
-use bp_parachains::{parachain_head_storage_key_at_source, ParaInfo, ParaStoredHeaderData};
+use bp_parachains::{
+	parachain_head_storage_key_at_source, ParaHeadMerkleProof, ParaInfo, ParaStoredHeaderData,
+	VerifyParaHeadsRoot,
+};
 use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaHeadsProof, ParaId};
 use bp_runtime::{Chain, HashOf, HeaderId, HeaderIdOf, Parachain, StorageProofError};
 use frame_support::{dispatch::PostDispatchInfo, DefaultNoBound};
@@ -91,6 +100,9 @@ pub mod pallet {
 		BoundedStorageValue<<T as Config<I>>::MaxParaHeadDataSize, ParaStoredHeaderData>;
 	/// Weight info of the given parachains pallet.
 	pub type WeightInfoOf<T, I> = <T as Config<I>>::WeightInfo;
+	/// Proof of the parachain heads root, accepted by the given parachains pallet.
+	pub type ParaHeadsRootProofOf<T, I> =
+		<<T as Config<I>>::ParaHeadsRootVerifier as VerifyParaHeadsRoot>::Proof;
 	type GrandpaPalletOf<T, I> =
 		pallet_bridge_grandpa::Pallet<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
 
@@ -129,6 +141,8 @@ pub mod pallet {
 		InvalidRelayChainBlockNumber,
 		/// Parachain heads storage proof is invalid.
 		HeaderChainStorageProof(HeaderChainError),
+		/// Proof of the parachain heads root is invalid.
+		InvalidParaHeadsRootProof,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
 	}
@@ -212,6 +226,12 @@ pub mod pallet {
 		/// that exceeds this bound.
 		#[pallet::constant]
 		type MaxParaHeadDataSize: Get<u32>;
+
+		/// Verifier of the parachain heads root, committed to by the bridged relay chain.
+		///
+		/// It is used by the `submit_parachain_heads_with_root_proof` call. If this call
+		/// is not needed, use `()` here.
+		type ParaHeadsRootVerifier: VerifyParaHeadsRoot;
 	}
 
 	/// Optional pallet owner.
@@ -389,49 +409,13 @@ pub mod pallet {
 					continue
 				}
 
-				// convert from parachain head into stored parachain head data
-				let parachain_head_data =
-					match T::ParaStoredHeaderDataBuilder::try_build(parachain, &parachain_head) {
-						Some(parachain_head_data) => parachain_head_data,
-						None => {
-							log::trace!(
-								target: LOG_TARGET,
-								"The head of parachain {:?} has been provided, but it is not tracked by the pallet",
-								parachain,
-							);
-							Self::deposit_event(Event::UntrackedParachainRejected { parachain });
-							continue
-						},
-					};
-
-				let update_result: Result<_, ()> =
-					ParasInfo::<T, I>::try_mutate(parachain, |stored_best_head| {
-						let artifacts = Pallet::<T, I>::update_parachain_head(
-							parachain,
-							stored_best_head.take(),
-							relay_block_number,
-							parachain_head_data,
-							parachain_head_hash,
-						)?;
-						*stored_best_head = Some(artifacts.best_head);
-						Ok(artifacts.prune_happened)
-					});
-
-				// we're refunding weight if update has not happened and if pruning has not happened
-				let is_update_happened = update_result.is_ok();
-				if !is_update_happened {
-					actual_weight = actual_weight.saturating_sub(
-						WeightInfoOf::<T, I>::parachain_head_storage_write_weight(
-							T::DbWeight::get(),
-						),
-					);
-				}
-				let is_prune_happened = matches!(update_result, Ok(true));
-				if !is_prune_happened {
-					actual_weight = actual_weight.saturating_sub(
-						WeightInfoOf::<T, I>::parachain_head_pruning_weight(T::DbWeight::get()),
-					);
-				}
+				let refund_weight = Self::import_parachain_head(
+					relay_block_number,
+					parachain,
+					parachain_head_hash,
+					&parachain_head,
+				);
+				actual_weight = actual_weight.saturating_sub(refund_weight);
 			}
 
 			// even though we may have accepted some parachain heads, we can't allow relayers to
@@ -466,6 +450,78 @@ pub mod pallet {
 		) -> DispatchResult {
 			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
 		}
+
+		/// Submit one or several parachain heads, proved against the parachain heads root.
+		///
+		/// This is an alternative to the `submit_parachain_heads` call, which doesn't require
+		/// relay chain headers to be imported by the GRANDPA pallet. Instead, the root of the
+		/// merkle tree of all parachain heads (as committed to by the bridged relay chain) is
+		/// verified using the `ParaHeadsRootVerifier` - e.g. against the MMR root, imported
+		/// by the BEEFY pallet. Every head then must be proved against this root.
+		///
+		/// The call fails if:
+		///
+		/// - the pallet is halted;
+		///
+		/// - the `para_heads_root_proof` is invalid.
+		///
+		/// The call may succeed, but some heads may not be updated e.g. because the head
+		/// merkle proof is invalid, pallet knows better head or it isn't tracked by the pallet.
+		#[pallet::call_index(3)]
+		#[pallet::weight(WeightInfoOf::<T, I>::submit_parachain_heads_with_root_proof_weight(
+			T::DbWeight::get(),
+			parachains,
+			T::ParaHeadsRootVerifier::verify_para_heads_root_weight(para_heads_root_proof),
+		))]
+		pub fn submit_parachain_heads_with_root_proof(
+			origin: OriginFor<T>,
+			para_heads_root_proof: ParaHeadsRootProofOf<T, I>,
+			parachains: Vec<ParaHeadMerkleProof>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			let mut actual_weight =
+				WeightInfoOf::<T, I>::submit_parachain_heads_with_root_proof_weight(
+					T::DbWeight::get(),
+					&parachains,
+					T::ParaHeadsRootVerifier::verify_para_heads_root_weight(&para_heads_root_proof),
+				);
+
+			// verify the root of parachain heads, committed to by the relay chain
+			let (relay_block, para_heads_root) =
+				T::ParaHeadsRootVerifier::verify_para_heads_root(para_heads_root_proof)
+					.ok_or(Error::<T, I>::InvalidParaHeadsRootProof)?;
+			let relay_block_number = relay_block.number();
+
+			for parachain_head_proof in parachains {
+				let parachain = parachain_head_proof.parachain;
+				if !parachain_head_proof
+					.verify::<<T::ParaHeadsRootVerifier as VerifyParaHeadsRoot>::Hasher>(
+						&para_heads_root,
+					) {
+					log::trace!(
+						target: LOG_TARGET,
+						"The merkle proof of parachain {:?} head is invalid at relay block {:?}",
+						parachain,
+						relay_block,
+					);
+					Self::deposit_event(Event::MissingParachainHead { parachain });
+					continue
+				}
+
+				let parachain_head = parachain_head_proof.parachain_head;
+				let refund_weight = Self::import_parachain_head(
+					relay_block_number,
+					parachain,
+					parachain_head.hash(),
+					&parachain_head,
+				);
+				actual_weight = actual_weight.saturating_sub(refund_weight);
+			}
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -517,6 +573,61 @@ pub mod pallet {
 			storage.read_and_decode_value(parachain_head_key.0.as_ref())
 		}
 
+		/// Import parachain head, that has been read from the proof.
+		///
+		/// Returns weight that needs to be refunded, because the head has not been imported or
+		/// because no pruning has happened.
+		fn import_parachain_head(
+			relay_block_number: RelayBlockNumber,
+			parachain: ParaId,
+			parachain_head_hash: ParaHash,
+			parachain_head: &ParaHead,
+		) -> Weight {
+			// convert from parachain head into stored parachain head data
+			let parachain_head_data =
+				match T::ParaStoredHeaderDataBuilder::try_build(parachain, parachain_head) {
+					Some(parachain_head_data) => parachain_head_data,
+					None => {
+						log::trace!(
+							target: LOG_TARGET,
+							"The head of parachain {:?} has been provided, but it is not tracked by the pallet",
+							parachain,
+						);
+						Self::deposit_event(Event::UntrackedParachainRejected { parachain });
+						return Weight::zero()
+					},
+				};
+
+			let update_result: Result<_, ()> =
+				ParasInfo::<T, I>::try_mutate(parachain, |stored_best_head| {
+					let artifacts = Pallet::<T, I>::update_parachain_head(
+						parachain,
+						stored_best_head.take(),
+						relay_block_number,
+						parachain_head_data,
+						parachain_head_hash,
+					)?;
+					*stored_best_head = Some(artifacts.best_head);
+					Ok(artifacts.prune_happened)
+				});
+
+			// we're refunding weight if update has not happened and if pruning has not happened
+			let mut refund_weight = Weight::zero();
+			let is_update_happened = update_result.is_ok();
+			if !is_update_happened {
+				refund_weight = refund_weight.saturating_add(
+					WeightInfoOf::<T, I>::parachain_head_storage_write_weight(T::DbWeight::get()),
+				);
+			}
+			let is_prune_happened = matches!(update_result, Ok(true));
+			if !is_prune_happened {
+				refund_weight = refund_weight.saturating_add(
+					WeightInfoOf::<T, I>::parachain_head_pruning_weight(T::DbWeight::get()),
+				);
+			}
+			refund_weight
+		}
+
 		/// Try to update parachain head.
 		pub(super) fn update_parachain_head(
 			parachain: ParaId,
@@ -1563,6 +1674,152 @@ pub(crate) mod tests {
 		});
 	}
 
+	fn prepare_parachain_heads_merkle_proofs(
+		heads: Vec<(u32, ParaHead)>,
+	) -> (ParaHash, Vec<ParaHeadMerkleProof>) {
+		let leaves = heads.iter().map(|(parachain, head)| (parachain, &head.0).encode());
+		let root =
+			binary_merkle_tree::merkle_root::<sp_runtime::traits::Keccak256, _>(leaves.clone());
+		let proofs = heads
+			.iter()
+			.enumerate()
+			.map(|(index, (parachain, head))| {
+				let proof = binary_merkle_tree::merkle_proof::<sp_runtime::traits::Keccak256, _, _>(
+					leaves.clone(),
+					index,
+				);
+				ParaHeadMerkleProof {
+					parachain: ParaId(*parachain),
+					parachain_head: head.clone(),
+					leaf_index: proof.leaf_index as u32,
+					number_of_leaves: proof.number_of_leaves as u32,
+					proof: proof.proof,
+				}
+			})
+			.collect();
+		(root, proofs)
+	}
+
+	#[test]
+	fn imports_parachain_heads_with_root_proof() {
+		let (root, proofs) = prepare_parachain_heads_merkle_proofs(vec![
+			(1, head_data(1, 5)),
+			(2, head_data(2, 7)),
+			(3, head_data(3, 9)),
+		]);
+		run_test(|| {
+			// only parachains 1 and 3 are submitted
+			let proofs = vec![proofs[0].clone(), proofs[2].clone()];
+			let expected_weight = WeightInfo::submit_parachain_heads_with_root_proof_weight(
+				DbWeight::get(),
+				&proofs,
+				Weight::zero(),
+			)
+			.saturating_sub(WeightInfo::parachain_head_pruning_weight(DbWeight::get()) * 2);
+			let result = Pallet::<TestRuntime>::submit_parachain_heads_with_root_proof(
+				RuntimeOrigin::signed(1),
+				Some((10, Default::default(), root)),
+				proofs,
+			);
+			assert_ok!(result);
+			assert_eq!(result.expect("checked above").actual_weight, Some(expected_weight));
+
+			assert_eq!(
+				ParasInfo::<TestRuntime>::get(ParaId(1)),
+				Some(ParaInfo {
+					best_head_hash: BestParaHeadHash {
+						at_relay_block_number: 10,
+						head_hash: head_data(1, 5).hash()
+					},
+					next_imported_hash_position: 1,
+				})
+			);
+			assert_eq!(ParasInfo::<TestRuntime>::get(ParaId(2)), None);
+			assert_eq!(
+				ImportedParaHeads::<TestRuntime>::get(ParaId(3), head_data(3, 9).hash())
+					.map(|h| h.into_inner()),
+				Some(stored_head_data(3, 9))
+			);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::UpdatedParachainHead {
+							parachain: ParaId(1),
+							parachain_head_hash: head_data(1, 5).hash(),
+						}),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::UpdatedParachainHead {
+							parachain: ParaId(3),
+							parachain_head_hash: head_data(3, 9).hash(),
+						}),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn submit_parachain_heads_with_root_proof_fails_if_root_proof_is_invalid() {
+		let (_, proofs) = prepare_parachain_heads_merkle_proofs(vec![(1, head_data(1, 5))]);
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_parachain_heads_with_root_proof(
+					RuntimeOrigin::signed(1),
+					None,
+					proofs,
+				),
+				Error::<TestRuntime>::InvalidParaHeadsRootProof,
+			);
+		});
+	}
+
+	#[test]
+	fn submit_parachain_heads_with_root_proof_checks_operating_mode() {
+		let (root, proofs) = prepare_parachain_heads_merkle_proofs(vec![(1, head_data(1, 5))]);
+		run_test(|| {
+			PalletOperatingMode::<TestRuntime>::put(BasicOperatingMode::Halted);
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_parachain_heads_with_root_proof(
+					RuntimeOrigin::signed(1),
+					Some((10, Default::default(), root)),
+					proofs,
+				),
+				Error::<TestRuntime>::BridgeModule(OwnedBridgeModuleError::Halted)
+			);
+		});
+	}
+
+	#[test]
+	fn submit_parachain_heads_with_root_proof_ignores_head_with_invalid_merkle_proof() {
+		let (root, mut proofs) =
+			prepare_parachain_heads_merkle_proofs(vec![(1, head_data(1, 5)), (2, head_data(2, 7))]);
+		run_test(|| {
+			proofs[0].parachain_head = head_data(1, 6);
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads_with_root_proof(
+				RuntimeOrigin::signed(1),
+				Some((10, Default::default(), root)),
+				vec![proofs[0].clone()],
+			));
+			assert_eq!(ParasInfo::<TestRuntime>::get(ParaId(1)), None);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Parachains(Event::MissingParachainHead {
+						parachain: ParaId(1),
+					}),
+					topics: vec![],
+				}],
+			);
+		});
+	}
+
 	#[test]
 	fn test_bridge_parachain_call_is_correctly_defined() {
 		let (state_root, proof, _) =
@@ -15,12 +15,13 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use bp_header_chain::ChainWithGrandpa;
-use bp_polkadot_core::parachains::ParaId;
-use bp_runtime::{Chain, Parachain};
+use bp_parachains::VerifyParaHeadsRoot;
+use bp_polkadot_core::parachains::{ParaHash, ParaId};
+use bp_runtime::{Chain, HeaderId, Parachain};
 use frame_support::{construct_runtime, parameter_types, traits::ConstU32, weights::Weight};
 use sp_runtime::{
 	testing::H256,
-	traits::{BlakeTwo256, Header as HeaderT, IdentityLookup},
+	traits::{BlakeTwo256, Header as HeaderT, IdentityLookup, Keccak256},
 	MultiSignature, Perbill,
 };
 
@@ -221,6 +222,25 @@ impl pallet_bridge_parachains::Config for TestRuntime {
 	type ParaStoredHeaderDataBuilder = (Parachain1, Parachain2, Parachain3, BigParachain);
 	type HeadsToKeep = HeadsToKeep;
 	type MaxParaHeadDataSize = ConstU32<MAXIMAL_PARACHAIN_HEAD_DATA_SIZE>;
+	type ParaHeadsRootVerifier = TestParaHeadsRootVerifier;
+}
+
+/// Parachain heads root verifier, that trusts the proof if it is `Some(_)`.
+pub struct TestParaHeadsRootVerifier;
+
+impl VerifyParaHeadsRoot for TestParaHeadsRootVerifier {
+	type Hasher = Keccak256;
+	type Proof = Option<(crate::RelayBlockNumber, crate::RelayBlockHash, ParaHash)>;
+
+	fn verify_para_heads_root(
+		proof: Self::Proof,
+	) -> Option<(HeaderId<crate::RelayBlockHash, crate::RelayBlockNumber>, ParaHash)> {
+		proof.map(|(number, hash, root)| (HeaderId(number, hash), root))
+	}
+
+	fn verify_para_heads_root_weight(_proof: &Self::Proof) -> Weight {
+		Weight::zero()
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
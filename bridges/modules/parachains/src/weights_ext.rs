@@ -18,6 +18,7 @@
 
 use crate::weights::{BridgeWeight, WeightInfo};
 
+use bp_parachains::ParaHeadMerkleProof;
 use bp_runtime::Size;
 use frame_support::weights::{RuntimeDbWeight, Weight};
 
@@ -68,6 +69,44 @@ pub trait WeightInfoExt: WeightInfo {
 		base_weight.saturating_add(proof_size_overhead).saturating_add(pruning_weight)
 	}
 
+	/// Weight of the parachain heads delivery extrinsic, where heads are proved against the
+	/// parachain heads root.
+	///
+	/// The `para_heads_root_proof_weight` is the weight of the parachain heads root proof
+	/// verification.
+	fn submit_parachain_heads_with_root_proof_weight(
+		db_weight: RuntimeDbWeight,
+		parachains: &[ParaHeadMerkleProof],
+		para_heads_root_proof_weight: Weight,
+	) -> Weight {
+		// we are using the same benchmark as for the `submit_parachain_heads` call. It includes
+		// storage proof verification, which isn't happening here, but the merkle proof
+		// verification is comparable to it
+		let parachains_count = parachains.len() as u32;
+		let base_weight = Self::submit_parachain_heads_with_n_parachains(parachains_count);
+
+		// overhead because of extra proof bytes
+		let expected_proof_size = parachains_count
+			.saturating_mul(DEFAULT_PARACHAIN_HEAD_SIZE)
+			.saturating_add(Self::expected_extra_storage_proof_size());
+		let actual_proof_size = parachains.iter().fold(0u32, |size, parachain| {
+			size.saturating_add(parachain.parachain_head.0.len() as u32)
+				.saturating_add((parachain.proof.len() as u32).saturating_mul(32))
+		});
+		let proof_size_overhead = Self::storage_proof_size_overhead(
+			actual_proof_size.saturating_sub(expected_proof_size),
+		);
+
+		// potential pruning weight (refunded if hasn't happened)
+		let pruning_weight =
+			Self::parachain_head_pruning_weight(db_weight).saturating_mul(parachains_count as u64);
+
+		base_weight
+			.saturating_add(proof_size_overhead)
+			.saturating_add(pruning_weight)
+			.saturating_add(para_heads_root_proof_weight)
+	}
+
 	/// Returns weight of single parachain head storage update.
 	///
 	/// This weight only includes db write operations that happens if parachain head is actually
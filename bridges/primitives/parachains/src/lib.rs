@@ -25,13 +25,13 @@ use bp_polkadot_core::{
 	BlockNumber as RelayBlockNumber, Hash as RelayBlockHash,
 };
 use bp_runtime::{
-	BlockNumberOf, Chain, HashOf, HeaderOf, Parachain, StorageDoubleMapKeyProvider,
+	BlockNumberOf, Chain, HashOf, HeaderId, HeaderOf, Parachain, StorageDoubleMapKeyProvider,
 	StorageMapKeyProvider,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{Blake2_128Concat, Twox64Concat};
+use frame_support::{weights::Weight, Blake2_128Concat, Parameter, Twox64Concat};
 use scale_info::TypeInfo;
-use sp_core::storage::StorageKey;
+use sp_core::{storage::StorageKey, Hasher};
 use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -166,6 +166,77 @@ impl ParaStoredHeaderDataBuilder for C {
 	}
 }
 
+/// Proof of parachain head inclusion into the parachain heads merkle root.
+///
+/// Relay chains may commit to the merkle root of all (sorted by parachain id) parachain heads,
+/// built from the SCALE-encoded `(ParaId, ParaHead)` leaves. E.g. Polkadot and Kusama put this
+/// root into the `leaf_extra` field of the BEEFY MMR leaves.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaHeadMerkleProof {
+	/// Parachain identifier.
+	pub parachain: ParaId,
+	/// Parachain head.
+	pub parachain_head: ParaHead,
+	/// Index of the parachain head leaf in the merkle tree.
+	pub leaf_index: u32,
+	/// Number of leaves in the merkle tree.
+	pub number_of_leaves: u32,
+	/// Merkle proof items.
+	pub proof: Vec<ParaHash>,
+}
+
+impl ParaHeadMerkleProof {
+	/// Returns true if the proof is valid with regard to given parachain heads root.
+	pub fn verify<H: Hasher<Out = ParaHash>>(&self, para_heads_root: &ParaHash) -> bool {
+		let leaf = (self.parachain.0, &self.parachain_head.0).encode();
+		binary_merkle_tree::verify_proof::<H, _, _>(
+			para_heads_root,
+			self.proof.iter().cloned(),
+			self.number_of_leaves as usize,
+			self.leaf_index as usize,
+			&leaf,
+		)
+	}
+}
+
+/// Something that is able to verify parachain heads roots, committed by the bridged relay chain.
+///
+/// It is an alternative to the regular way of proving parachain heads, where we need a relay
+/// chain header, finalized by the GRANDPA light client, and a storage proof of the parachain
+/// heads, generated at this header.
+pub trait VerifyParaHeadsRoot {
+	/// Hasher that is used to build the parachain heads merkle tree.
+	type Hasher: Hasher<Out = ParaHash>;
+	/// Proof of the parachain heads root.
+	type Proof: Parameter;
+
+	/// Verify given proof and return the relay chain block at which parachain heads
+	/// have been read, and the parachain heads root itself.
+	fn verify_para_heads_root(
+		proof: Self::Proof,
+	) -> Option<(HeaderId<RelayBlockHash, RelayBlockNumber>, ParaHash)>;
+
+	/// Returns weight of the `verify_para_heads_root` call with given proof.
+	fn verify_para_heads_root_weight(proof: &Self::Proof) -> Weight;
+}
+
+/// Implementation that rejects all proofs. It shall be used if the bridged relay chain
+/// parachain heads are only proved using storage proofs.
+impl VerifyParaHeadsRoot for () {
+	type Hasher = sp_runtime::traits::Keccak256;
+	type Proof = ();
+
+	fn verify_para_heads_root(
+		_proof: Self::Proof,
+	) -> Option<(HeaderId<RelayBlockHash, RelayBlockNumber>, ParaHash)> {
+		None
+	}
+
+	fn verify_para_heads_root_weight(_proof: &Self::Proof) -> Weight {
+		Weight::zero()
+	}
+}
+
 /// A minimized version of `pallet-bridge-parachains::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
 #[allow(non_camel_case_types)]
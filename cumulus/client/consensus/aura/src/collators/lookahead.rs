@@ -30,6 +30,10 @@
 //! building the block, as there is some buffer before it can get posted to the relay-chain.
 //! The main limitation is block propagation time - i.e. the new blocks created by an author
 //! must be propagated to the next author before their turn.
+//!
+//! When the para is assigned to several cores at the relay parent (elastic scaling), this
+//! builds a chain of blocks and submits one of them to each of the cores, so that they can be
+//! backed in parallel.
 
 use codec::{Codec, Encode};
 use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
@@ -49,7 +53,7 @@ use polkadot_node_subsystem::messages::{
 	CollationGenerationMessage, RuntimeApiMessage, RuntimeApiRequest,
 };
 use polkadot_overseer::Handle as OverseerHandle;
-use polkadot_primitives::{CollatorPair, Id as ParaId, OccupiedCoreAssumption};
+use polkadot_primitives::{CollatorPair, CoreIndex, Id as ParaId, OccupiedCoreAssumption};
 
 use futures::{channel::oneshot, prelude::*};
 use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
@@ -183,7 +187,9 @@ where
 		while let Some(relay_parent_header) = import_notifications.next().await {
 			let relay_parent = relay_parent_header.hash();
 
-			if !is_para_scheduled(relay_parent, params.para_id, &mut params.overseer_handle).await {
+			let scheduled_cores =
+				scheduled_cores(relay_parent, params.para_id, &mut params.overseer_handle).await;
+			if scheduled_cores.is_empty() {
 				tracing::trace!(
 					target: crate::LOG_TARGET,
 					?relay_parent,
@@ -295,9 +301,8 @@ where
 			let mut parent_header = initial_parent.header;
 			let overseer_handle = &mut params.overseer_handle;
 
-			// This needs to change to support elastic scaling, but for continuously
-			// scheduled chains this ensures that the backlog will grow steadily.
-			for n_built in 0..2 {
+			// Build a block for each of the cores assigned to the para.
+			for (n_built, core_index) in scheduled_cores.iter().copied().enumerate() {
 				let slot_claim = match can_build_upon(parent_hash).await {
 					None => break,
 					Some(c) => c,
@@ -306,6 +311,7 @@ where
 				tracing::debug!(
 					target: crate::LOG_TARGET,
 					?relay_parent,
+					?core_index,
 					unincluded_segment_len = initial_parent.depth + n_built,
 					"Slot claimed. Building"
 				);
@@ -378,6 +384,7 @@ where
 										parent_head: parent_header.encode().into(),
 										validation_code_hash,
 										result_sender: None,
+										core_index: Some(core_index),
 									},
 								),
 								"SubmitCollation",
@@ -458,14 +465,14 @@ async fn max_ancestry_lookback(
 	}
 }
 
-// Checks if there exists a scheduled core for the para at the provided relay parent.
+// Returns the indices of the cores scheduled for the para at the provided relay parent.
 //
-// Falls back to `false` in case of an error.
-async fn is_para_scheduled(
+// Falls back to no cores in case of an error.
+async fn scheduled_cores(
 	relay_parent: PHash,
 	para_id: ParaId,
 	overseer_handle: &mut OverseerHandle,
-) -> bool {
+) -> Vec<CoreIndex> {
 	let (tx, rx) = oneshot::channel();
 	let request = RuntimeApiRequest::AvailabilityCores(tx);
 	overseer_handle
@@ -481,7 +488,7 @@ async fn is_para_scheduled(
 				?relay_parent,
 				"Failed to query availability cores runtime API",
 			);
-			return false
+			return Vec::new()
 		},
 		Err(oneshot::Canceled) => {
 			tracing::error!(
@@ -489,9 +496,14 @@ async fn is_para_scheduled(
 				?relay_parent,
 				"Sender for availability cores runtime request dropped",
 			);
-			return false
+			return Vec::new()
 		},
 	};
 
-	cores.iter().any(|core| core.para_id() == Some(para_id))
+	cores
+		.iter()
+		.enumerate()
+		.filter(|(_, core)| core.para_id() == Some(para_id))
+		.map(|(idx, _)| CoreIndex(idx as u32))
+		.collect()
 }
@@ -52,12 +52,23 @@ use sp_core::{traits::SpawnNamed, Decode};
 use sp_runtime::traits::{Block as BlockT, BlockIdTo};
 use std::{sync::Arc, time::Duration};
 
+pub use cumulus_primitives_proof_size_hostfunction::storage_proof_size;
+
 // Given the sporadic nature of the explicit recovery operation and the
 // possibility to retry infinite times this value is more than enough.
 // In practice here we expect no more than one queued messages.
 const RECOVERY_CHAN_SIZE: usize = 8;
 const LOG_TARGET_SYNC: &str = "sync::cumulus";
 
+/// Host functions that should be used in parachain nodes.
+///
+/// Contains the standard substrate host functions, as well as a
+/// host function to enable PoV-reclaim on parachain nodes.
+pub type ParachainHostFunctions = (
+	cumulus_primitives_proof_size_hostfunction::storage_proof_size::HostFunctions,
+	sp_io::SubstrateHostFunctions,
+);
+
 /// A hint about how long the node should wait before attempting to recover missing block data
 /// from the data availability layer.
 pub enum DARecoveryProfile {
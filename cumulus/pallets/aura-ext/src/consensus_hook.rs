@@ -32,6 +32,9 @@ use sp_std::{marker::PhantomData, num::NonZeroU32};
 /// A consensus hook for a fixed block processing velocity and unincluded segment capacity.
 ///
 /// Relay chain slot duration must be provided in milliseconds.
+///
+/// A para that is assigned multiple cores builds one block per core on the same relay parent,
+/// so the velocity `V` should be at least the number of cores it intends to use.
 pub struct FixedVelocityConsensusHook<
 	T,
 	const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32,
@@ -35,11 +35,23 @@
 //!
 //! 1. [`Invulnerables`]: a set of collators appointed by governance. These accounts will always be
 //!    collators.
-//! 2. [`Candidates`]: these are *candidates to the collation task* and may or may not be elected as
-//!    a final collator.
+//! 2. [`CandidateList`]: these are *candidates to the collation task* and may or may not be elected
+//!    as a final collator.
 //!
-//! The current implementation resolves congestion of [`Candidates`] in a first-come-first-serve
-//! manner.
+//! The current implementation resolves congestion of [`CandidateList`] through a simple auction
+//! mechanism. Candidates bid for the collator slots and at the end of the session, the auction ends
+//! and the top candidates are selected to become collators. The number of selected candidates is
+//! determined by the value of `DesiredCandidates`.
+//!
+//! Before the list reaches full capacity, candidates can register by placing the minimum bond
+//! through `register_as_candidate`. Then, if an account wants to participate in the collator slot
+//! auction, they have to replace an existing candidate by placing a greater deposit through
+//! `take_candidate_slot`. Existing candidates can increase their bids through `update_bond`.
+//!
+//! At any point, an account can take the place of another account in the candidate list if they
+//! put up a greater deposit than the target. While new joiners would like to deposit as little as
+//! possible to participate in the auction, the replacement threat incentivizes candidates to bid as
+//! close to their budget as possible in order to avoid being replaced.
 //!
 //! Candidates will not be allowed to get kicked or `leave_intent` if the total number of collators
 //! would fall below `MinEligibleCollators`. This is to ensure that some collators will always
@@ -99,7 +111,7 @@ pub mod pallet {
 	use sp_std::vec::Vec;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
@@ -180,11 +192,14 @@ pub mod pallet {
 	pub type Invulnerables<T: Config> =
 		StorageValue<_, BoundedVec<T::AccountId, T::MaxInvulnerables>, ValueQuery>;
 
-	/// The (community, limited) collation candidates. `Candidates` and `Invulnerables` should be
+	/// The (community, limited) collation candidates. `CandidateList` and `Invulnerables` should be
 	/// mutually exclusive.
+	///
+	/// This list is sorted in ascending order by deposit and when the deposits are equal, the least
+	/// recently updated is considered greater.
 	#[pallet::storage]
-	#[pallet::getter(fn candidates)]
-	pub type Candidates<T: Config> = StorageValue<
+	#[pallet::getter(fn candidate_list)]
+	pub type CandidateList<T: Config> = StorageValue<
 		_,
 		BoundedVec<CandidateInfo<T::AccountId, BalanceOf<T>>, T::MaxCandidates>,
 		ValueQuery,
@@ -203,7 +218,7 @@ pub mod pallet {
 	#[pallet::getter(fn desired_candidates)]
 	pub type DesiredCandidates<T> = StorageValue<_, u32, ValueQuery>;
 
-	/// Fixed amount to deposit to become a collator.
+	/// Minimum amount to deposit to become a collator.
 	///
 	/// When a collator calls `leave_intent` they immediately receive the deposit back.
 	#[pallet::storage]
@@ -261,8 +276,12 @@ pub mod pallet {
 		NewCandidacyBond { bond_amount: BalanceOf<T> },
 		/// A new candidate joined.
 		CandidateAdded { account_id: T::AccountId, deposit: BalanceOf<T> },
+		/// Bond of a candidate updated.
+		CandidateBondUpdated { account_id: T::AccountId, deposit: BalanceOf<T> },
 		/// A candidate was removed.
 		CandidateRemoved { account_id: T::AccountId },
+		/// An account was replaced in the candidate list by another one.
+		CandidateReplaced { old: T::AccountId, new: T::AccountId, deposit: BalanceOf<T> },
 		/// An account was unable to be added to the Invulnerables because they did not have keys
 		/// registered. Other Invulnerables may have been set.
 		InvalidInvulnerableSkipped { account_id: T::AccountId },
@@ -288,6 +307,18 @@ pub mod pallet {
 		NoAssociatedValidatorId,
 		/// Validator ID is not yet registered.
 		ValidatorNotRegistered,
+		/// Could not insert in the candidate list.
+		InsertToCandidateListFailed,
+		/// New deposit amount would be below the minimum candidacy bond.
+		DepositTooLow,
+		/// Deposit amount is too low to take the target's slot in the candidate list.
+		InsufficientBond,
+		/// The target account to be replaced in the candidate list is not a candidate.
+		TargetIsNotCandidate,
+		/// The updated deposit amount is equal to the amount already reserved.
+		IdenticalDeposit,
+		/// Cannot lower candidacy bond while occupying a future collator slot in the list.
+		InvalidUnreserve,
 	}
 
 	#[pallet::hooks]
@@ -306,10 +337,11 @@ pub mod pallet {
 		/// it is non-atomic; the caller accepts all `AccountId`s passed in `new` _individually_ as
 		/// acceptable Invulnerables, and is not proposing a _set_ of new Invulnerables.
 		///
-		/// This call does not maintain mutual exclusivity of `Invulnerables` and `Candidates`. It
-		/// is recommended to use a batch of `add_invulnerable` and `remove_invulnerable` instead.
-		/// A `batch_all` can also be used to enforce atomicity. If any candidates are included in
-		/// `new`, they should be removed with `remove_invulnerable_candidate` after execution.
+		/// This call does not maintain mutual exclusivity of `Invulnerables` and `CandidateList`.
+		/// It is recommended to use a batch of `add_invulnerable` and `remove_invulnerable`
+		/// instead. A `batch_all` can also be used to enforce atomicity. If any candidates are
+		/// included in `new`, they should be removed with `remove_invulnerable_candidate` after
+		/// execution.
 		///
 		/// Must be called by the `UpdateOrigin`.
 		#[pallet::call_index(0)]
@@ -320,7 +352,7 @@ pub mod pallet {
 			// don't wipe out the collator set
 			if new.is_empty() {
 				ensure!(
-					Candidates::<T>::decode_len().unwrap_or_default() >=
+					CandidateList::<T>::decode_len().unwrap_or_default() >=
 						T::MinEligibleCollators::get() as usize,
 					Error::<T>::TooFewEligibleCollators
 				);
@@ -380,7 +412,7 @@ pub mod pallet {
 
 		/// Set the ideal number of non-invulnerable collators. If lowering this number, then the
 		/// number of running collators could be higher than this figure. Aside from that edge case,
-		/// there should be no other way to have more candidates than the desired number.
+		/// there should be no other way to have more collators than the desired number.
 		///
 		/// The origin for this call must be the `UpdateOrigin`.
 		#[pallet::call_index(1)]
@@ -417,15 +449,19 @@ pub mod pallet {
 		/// Register this account as a collator candidate. The account must (a) already have
 		/// registered session keys and (b) be able to reserve the `CandidacyBond`.
 		///
-		/// This call is not available to `Invulnerable` collators.
+		/// This call is not available to `Invulnerable` collators. Once the candidate list is
+		/// full, new candidates must use `take_candidate_slot` instead.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
 		pub fn register_as_candidate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			// ensure we are below limit.
-			let length = <Candidates<T>>::decode_len().unwrap_or_default();
-			ensure!((length as u32) < Self::desired_candidates(), Error::<T>::TooManyCandidates);
+			let length: u32 = <CandidateList<T>>::decode_len()
+				.unwrap_or_default()
+				.try_into()
+				.unwrap_or_default();
+			ensure!(length < T::MaxCandidates::get(), Error::<T>::TooManyCandidates);
 			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
 
 			let validator_key = T::ValidatorIdOf::convert(who.clone())
@@ -440,12 +476,20 @@ pub mod pallet {
 			let incoming = CandidateInfo { who: who.clone(), deposit };
 
 			let current_count =
-				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+				<CandidateList<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
 					if candidates.iter().any(|candidate| candidate.who == who) {
 						Err(Error::<T>::AlreadyCandidate)?
 					} else {
 						T::Currency::reserve(&who, deposit)?;
-						candidates.try_push(incoming).map_err(|_| Error::<T>::TooManyCandidates)?;
+						// Keep the list sorted: the newcomer goes below everyone with an equal
+						// or greater deposit, as they are the most recently updated.
+						let pos = candidates
+							.iter()
+							.position(|candidate| candidate.deposit >= deposit)
+							.unwrap_or(candidates.len());
+						candidates
+							.try_insert(pos, incoming)
+							.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
 						<LastAuthoredBlock<T>>::insert(
 							who.clone(),
 							frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
@@ -521,7 +565,7 @@ pub mod pallet {
 					.unwrap_or_default()
 					.try_into()
 					.unwrap_or(T::MaxInvulnerables::get().saturating_sub(1)),
-				Candidates::<T>::decode_len()
+				CandidateList::<T>::decode_len()
 					.unwrap_or_default()
 					.try_into()
 					.unwrap_or(T::MaxCandidates::get()),
@@ -554,6 +598,155 @@ pub mod pallet {
 			Self::deposit_event(Event::InvulnerableRemoved { account_id: who });
 			Ok(())
 		}
+
+		/// Update the candidacy bond of collator candidate `origin` to a new amount `new_deposit`.
+		///
+		/// Setting a `new_deposit` that is lower than the current deposit while `origin` is
+		/// occupying a top-`DesiredCandidates` slot is not allowed.
+		///
+		/// This call will fail if `origin` is not a collator candidate, the updated bond is lower
+		/// than the minimum candidacy bond, and/or the amount cannot be reserved.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::update_bond(T::MaxCandidates::get()))]
+		pub fn update_bond(
+			origin: OriginFor<T>,
+			new_deposit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(new_deposit >= <CandidacyBond<T>>::get(), Error::<T>::DepositTooLow);
+			// The function below will try to mutate the `CandidateList` entry for the caller to
+			// update their deposit to the new value of `new_deposit`. The return value is the
+			// length of the candidate list, used for the post dispatch weight.
+			let length =
+				<CandidateList<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					let idx = candidates
+						.iter()
+						.position(|candidate_info| candidate_info.who == who)
+						.ok_or(Error::<T>::NotCandidate)?;
+					let candidate_count = candidates.len();
+					// Remove the candidate from the list.
+					let mut info = candidates.remove(idx);
+					let old_deposit = info.deposit;
+					if new_deposit > old_deposit {
+						T::Currency::reserve(&who, new_deposit - old_deposit)?;
+					} else if new_deposit < old_deposit {
+						// Candidates which are in the top `DesiredCandidates` (the end of the
+						// list) are not allowed to lower their bond.
+						ensure!(
+							idx.saturating_add(<DesiredCandidates<T>>::get() as usize) <
+								candidate_count,
+							Error::<T>::InvalidUnreserve
+						);
+						T::Currency::unreserve(&who, old_deposit - new_deposit);
+					} else {
+						return Err(Error::<T>::IdenticalDeposit.into())
+					}
+
+					// Update the deposit and insert the candidate in the correct spot in the list.
+					info.deposit = new_deposit;
+					let new_pos = candidates
+						.iter()
+						.position(|candidate| candidate.deposit >= new_deposit)
+						.unwrap_or(candidates.len());
+					candidates
+						.try_insert(new_pos, info)
+						.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
+
+					Ok(candidate_count)
+				})?;
+
+			Self::deposit_event(Event::CandidateBondUpdated {
+				account_id: who,
+				deposit: new_deposit,
+			});
+			Ok(Some(T::WeightInfo::update_bond(length as u32)).into())
+		}
+
+		/// The caller `origin` replaces a candidate `target` in the collator candidate list by
+		/// reserving `deposit`. The amount `deposit` reserved by the caller must be greater than
+		/// the existing bond of the target it is trying to replace.
+		///
+		/// This call will fail if the caller is already a collator candidate or invulnerable, the
+		/// caller does not have registered session keys, the target is not a collator candidate,
+		/// and/or the `deposit` amount cannot be reserved.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::take_candidate_slot(T::MaxCandidates::get()))]
+		pub fn take_candidate_slot(
+			origin: OriginFor<T>,
+			deposit: BalanceOf<T>,
+			target: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
+			ensure!(deposit >= Self::candidacy_bond(), Error::<T>::InsufficientBond);
+
+			let validator_key = T::ValidatorIdOf::convert(who.clone())
+				.ok_or(Error::<T>::NoAssociatedValidatorId)?;
+			ensure!(
+				T::ValidatorRegistration::is_registered(&validator_key),
+				Error::<T>::ValidatorNotRegistered
+			);
+
+			let length = <CandidateList<T>>::decode_len().unwrap_or_default();
+			// The closure below iterates through all elements of the candidate list to ensure that
+			// the caller isn't already a candidate and to find the target it's trying to replace in
+			// the list. The return value is a tuple of the position of the candidate to be replaced
+			// in the list along with its candidate information.
+			let target_info = <CandidateList<T>>::try_mutate(
+				|candidates| -> Result<CandidateInfo<T::AccountId, BalanceOf<T>>, DispatchError> {
+					// Find the position in the list of the candidate that is being replaced.
+					let mut target_info_idx = None;
+					let mut new_info_idx = None;
+					for (idx, candidate_info) in candidates.iter().enumerate() {
+						// While iterating through the candidates trying to find the target,
+						// also ensure on the same pass that our caller isn't already a
+						// candidate.
+						ensure!(candidate_info.who != who, Error::<T>::AlreadyCandidate);
+						// If we find our target, update the position but do not stop the
+						// iteration since we're also checking that the caller isn't already a
+						// candidate.
+						if candidate_info.who == target {
+							target_info_idx = Some(idx);
+						}
+						// Find the spot where the new candidate would be inserted in the current
+						// version of the list.
+						if new_info_idx.is_none() && candidate_info.deposit >= deposit {
+							new_info_idx = Some(idx);
+						}
+					}
+					let target_info_idx =
+						target_info_idx.ok_or(Error::<T>::TargetIsNotCandidate)?;
+
+					// Remove the old candidate from the list.
+					let target_info = candidates.remove(target_info_idx);
+					ensure!(deposit > target_info.deposit, Error::<T>::InsufficientBond);
+					T::Currency::reserve(&who, deposit)?;
+
+					// We have removed one element before `new_info_idx`, so the position we have to
+					// insert to is reduced by 1.
+					let new_pos = new_info_idx
+						.map(|i| i.saturating_sub(1))
+						.unwrap_or_else(|| candidates.len());
+					let new_info = CandidateInfo { who: who.clone(), deposit };
+					// Insert the new candidate in the correct spot in the list.
+					candidates
+						.try_insert(new_pos, new_info)
+						.expect("candidate count previously decremented; qed");
+
+					Ok(target_info)
+				},
+			)?;
+			T::Currency::unreserve(&target_info.who, target_info.deposit);
+			<LastAuthoredBlock<T>>::remove(target_info.who.clone());
+			<LastAuthoredBlock<T>>::insert(
+				who.clone(),
+				frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
+			);
+
+			Self::deposit_event(Event::CandidateReplaced { old: target, new: who, deposit });
+			Ok(Some(T::WeightInfo::take_candidate_slot(length as u32)).into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -565,7 +758,7 @@ pub mod pallet {
 		/// Return the total number of accounts that are eligible collators (candidates and
 		/// invulnerables).
 		fn eligible_collators() -> usize {
-			Candidates::<T>::decode_len()
+			CandidateList::<T>::decode_len()
 				.unwrap_or_default()
 				.saturating_add(Invulnerables::<T>::decode_len().unwrap_or_default())
 		}
@@ -576,7 +769,7 @@ pub mod pallet {
 			remove_last_authored: bool,
 		) -> Result<usize, DispatchError> {
 			let current_count =
-				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+				<CandidateList<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
 					let index = candidates
 						.iter()
 						.position(|candidate| candidate.who == *who)
@@ -594,53 +787,65 @@ pub mod pallet {
 
 		/// Assemble the current set of candidates and invulnerables into the next collator set.
 		///
+		/// The candidates with the highest deposits, up to `DesiredCandidates`, are selected
+		/// after the invulnerables.
+		///
 		/// This is done on the fly, as frequent as we are told to do so, as the session manager.
-		pub fn assemble_collators(
-			candidates: BoundedVec<T::AccountId, T::MaxCandidates>,
-		) -> Vec<T::AccountId> {
+		pub fn assemble_collators() -> Vec<T::AccountId> {
+			// Casting `u32` to `usize` should be safe on all machines running this.
+			let desired_candidates = <DesiredCandidates<T>>::get() as usize;
 			let mut collators = Self::invulnerables().to_vec();
-			collators.extend(candidates);
+			collators.extend(
+				<CandidateList<T>>::get()
+					.iter()
+					.rev()
+					.cloned()
+					.take(desired_candidates)
+					.map(|candidate_info| candidate_info.who),
+			);
 			collators
 		}
 
 		/// Kicks out candidates that did not produce a block in the kick threshold and refunds
 		/// their deposits.
-		pub fn kick_stale_candidates(
-			candidates: BoundedVec<CandidateInfo<T::AccountId, BalanceOf<T>>, T::MaxCandidates>,
-		) -> BoundedVec<T::AccountId, T::MaxCandidates> {
+		///
+		/// Return value is the number of candidates left in the list.
+		pub fn kick_stale_candidates(candidates: impl IntoIterator<Item = T::AccountId>) -> u32 {
 			let now = frame_system::Pallet::<T>::block_number();
 			let kick_threshold = T::KickThreshold::get();
 			let min_collators = T::MinEligibleCollators::get();
 			candidates
 				.into_iter()
 				.filter_map(|c| {
-					let last_block = <LastAuthoredBlock<T>>::get(c.who.clone());
+					let last_block = <LastAuthoredBlock<T>>::get(c.clone());
 					let since_last = now.saturating_sub(last_block);
 
-					let is_invulnerable = Self::invulnerables().contains(&c.who);
+					let is_invulnerable = Self::invulnerables().contains(&c);
 					let is_lazy = since_last >= kick_threshold;
 
 					if is_invulnerable {
-						// They are invulnerable. No reason for them to be in Candidates also.
+						// They are invulnerable. No reason for them to be in `CandidateList` also.
 						// We don't even care about the min collators here, because an Account
 						// should not be a collator twice.
-						let _ = Self::try_remove_candidate(&c.who, false);
+						let _ = Self::try_remove_candidate(&c, false);
 						None
 					} else {
 						if Self::eligible_collators() <= min_collators as usize || !is_lazy {
 							// Either this is a good collator (not lazy) or we are at the minimum
 							// that the system needs. They get to stay.
-							Some(c.who)
+							Some(c)
 						} else {
 							// This collator has not produced a block recently enough. Bye bye.
-							let _ = Self::try_remove_candidate(&c.who, true);
+							let _ = Self::try_remove_candidate(&c, true);
 							None
 						}
 					}
 				})
-				.collect::<Vec<_>>()
+				.count()
 				.try_into()
-				.expect("filter_map operation can't result in a bounded vec larger than its original; qed")
+				.expect(
+					"filter_map operation can't result in more elements than the bounded list; qed",
+				)
 		}
 	}
 
@@ -677,14 +882,23 @@ pub mod pallet {
 				<frame_system::Pallet<T>>::block_number(),
 			);
 
-			let candidates = Self::candidates();
-			let candidates_len_before = candidates.len();
-			let active_candidates = Self::kick_stale_candidates(candidates);
-			let removed = candidates_len_before - active_candidates.len();
-			let result = Self::assemble_collators(active_candidates);
+			// The `expect` below is safe because the list is a `BoundedVec` with a max size of
+			// `T::MaxCandidates`, which is a `u32`. When `decode_len` returns `Some(len)`, `len`
+			// must be valid and at most `u32::MAX`, which must always be able to convert to `u32`.
+			let candidates_len_before: u32 = <CandidateList<T>>::decode_len()
+				.unwrap_or_default()
+				.try_into()
+				.expect("length is at most `T::MaxCandidates`, so it must fit in `u32`; qed");
+			let active_candidates_count = Self::kick_stale_candidates(
+				<CandidateList<T>>::get()
+					.iter()
+					.map(|candidate_info| candidate_info.who.clone()),
+			);
+			let removed = candidates_len_before.saturating_sub(active_candidates_count);
+			let result = Self::assemble_collators();
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::new_session(candidates_len_before as u32, removed as u32),
+				T::WeightInfo::new_session(candidates_len_before, removed),
 				DispatchClass::Mandatory,
 			);
 			Some(result)
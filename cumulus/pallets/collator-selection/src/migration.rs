@@ -90,3 +90,96 @@ pub mod v1 {
 		}
 	}
 }
+
+/// Version 2 Migration
+/// This migration moves the candidates from the unsorted `Candidates` storage value into the
+/// `CandidateList` storage value, sorted in ascending order by deposit.
+pub mod v2 {
+	use super::*;
+	use frame_support::{pallet_prelude::*, storage_alias, traits::Currency};
+	#[cfg(feature = "try-runtime")]
+	use sp_std::prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The unsorted candidates of version 1.
+	#[storage_alias]
+	pub type Candidates<T: Config> = StorageValue<
+		Pallet<T>,
+		BoundedVec<
+			CandidateInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+			<T as Config>::MaxCandidates,
+		>,
+		ValueQuery,
+	>;
+
+	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			if onchain_version == 1 {
+				let mut candidates = Candidates::<T>::take().into_inner();
+				let candidates_len = candidates.len();
+				// The old list is in registration order. Among equal deposits, the least recently
+				// registered must end up the greatest, so reverse before the (stable) sort.
+				candidates.reverse();
+				candidates.sort_by(|a, b| a.deposit.cmp(&b.deposit));
+				<CandidateList<T>>::put(BoundedVec::<_, T::MaxCandidates>::truncate_from(
+					candidates,
+				));
+
+				StorageVersion::new(2).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"Moved {} candidates to the sorted candidate list, upgraded storage to version 2",
+					candidates_len,
+				);
+				// 1 read for `onchain_version`, 1 read/write to take the old candidates, 1 write
+				// for the new list and 1 write to put the version
+				T::DbWeight::get().reads_writes(2, 3)
+			} else {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let number_of_candidates = Candidates::<T>::get().to_vec().len();
+			Ok((number_of_candidates as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(number_of_candidates: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			frame_support::ensure!(
+				!Candidates::<T>::exists(),
+				"after migration, the old candidates should be removed"
+			);
+
+			let candidate_list = CandidateList::<T>::get();
+			frame_support::ensure!(
+				candidate_list.windows(2).all(|w| w[0].deposit <= w[1].deposit),
+				"after migration, the candidate list should be sorted by deposit"
+			);
+
+			let number_of_candidates: u32 = Decode::decode(&mut number_of_candidates.as_slice())
+				.expect(
+					"the state parameter should be something that was generated by pre_upgrade",
+				);
+			assert_eq!(
+				number_of_candidates,
+				candidate_list.len() as u32,
+				"after migration, there should be the same number of candidates"
+			);
+
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			frame_support::ensure!(onchain_version >= 2, "must_upgrade");
+
+			Ok(())
+		}
+	}
+}
@@ -28,7 +28,7 @@ fn basic_setup_works() {
 		assert_eq!(CollatorSelection::desired_candidates(), 2);
 		assert_eq!(CollatorSelection::candidacy_bond(), 10);
 
-		assert!(CollatorSelection::candidates().is_empty());
+		assert!(CollatorSelection::candidate_list().is_empty());
 		// genesis should sort input
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
 	});
@@ -202,7 +202,7 @@ fn candidate_to_invulnerable_works() {
 		initialize_to_block(1);
 		assert_eq!(CollatorSelection::desired_candidates(), 2);
 		assert_eq!(CollatorSelection::candidacy_bond(), 10);
-		assert_eq!(CollatorSelection::candidates(), Vec::new());
+		assert_eq!(CollatorSelection::candidate_list(), Vec::new());
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
 
 		assert_eq!(Balances::free_balance(3), 100);
@@ -226,7 +226,7 @@ fn candidate_to_invulnerable_works() {
 		));
 		assert!(CollatorSelection::invulnerables().to_vec().contains(&3));
 		assert_eq!(Balances::free_balance(3), 100);
-		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert_eq!(CollatorSelection::candidate_list().len(), 1);
 
 		assert_ok!(CollatorSelection::add_invulnerable(
 			RuntimeOrigin::signed(RootAccount::get()),
@@ -240,7 +240,7 @@ fn candidate_to_invulnerable_works() {
 		));
 		assert!(CollatorSelection::invulnerables().to_vec().contains(&4));
 		assert_eq!(Balances::free_balance(4), 100);
-		assert_eq!(CollatorSelection::candidates().len(), 0);
+		assert_eq!(CollatorSelection::candidate_list().len(), 0);
 	});
 }
 
@@ -286,31 +286,36 @@ fn set_candidacy_bond() {
 #[test]
 fn cannot_register_candidate_if_too_many() {
 	new_test_ext().execute_with(|| {
-		// reset desired candidates:
+		// the desired candidates do not bound the candidate list.
 		<crate::DesiredCandidates<Test>>::put(0);
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 
-		// can't accept anyone anymore.
-		assert_noop!(
-			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)),
-			Error::<Test>::TooManyCandidates,
-		);
-
-		// reset desired candidates:
-		<crate::DesiredCandidates<Test>>::put(1);
-		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
-
-		// but no more
-		assert_noop!(
-			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)),
-			Error::<Test>::TooManyCandidates,
-		);
+		// MaxCandidates: u32 = 20
+		for ii in 4..=23 {
+			// only keys were registered in mock for 1 to 5
+			if ii > 5 {
+				Balances::make_free_balance_be(&ii, 100);
+				let key = MockSessionKeys { aura: UintAuthorityId(ii) };
+				Session::set_keys(RuntimeOrigin::signed(ii).into(), key, Vec::new()).unwrap();
+			}
+			if ii < 23 {
+				assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(ii)));
+			} else {
+				// but no more
+				assert_noop!(
+					CollatorSelection::register_as_candidate(RuntimeOrigin::signed(ii)),
+					Error::<Test>::TooManyCandidates,
+				);
+			}
+		}
+		assert_eq!(CollatorSelection::candidate_list().len(), 20);
 	})
 }
 
 #[test]
 fn cannot_unregister_candidate_if_too_few() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(CollatorSelection::candidates(), Vec::new());
+		assert_eq!(CollatorSelection::candidate_list(), Vec::new());
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
 		assert_ok!(CollatorSelection::remove_invulnerable(
 			RuntimeOrigin::signed(RootAccount::get()),
@@ -369,7 +374,7 @@ fn cannot_register_dupe_candidate() {
 		// can add 3 as candidate
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		let addition = CandidateInfo { who: 3, deposit: 10 };
-		assert_eq!(CollatorSelection::candidates(), vec![addition]);
+		assert_eq!(CollatorSelection::candidate_list(), vec![addition]);
 		assert_eq!(CollatorSelection::last_authored_block(3), 10);
 		assert_eq!(Balances::free_balance(3), 90);
 
@@ -404,7 +409,7 @@ fn register_as_candidate_works() {
 		// given
 		assert_eq!(CollatorSelection::desired_candidates(), 2);
 		assert_eq!(CollatorSelection::candidacy_bond(), 10);
-		assert_eq!(CollatorSelection::candidates(), Vec::new());
+		assert_eq!(CollatorSelection::candidate_list(), Vec::new());
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
 
 		// take two endowed, non-invulnerables accounts.
@@ -417,7 +422,258 @@ fn register_as_candidate_works() {
 		assert_eq!(Balances::free_balance(3), 90);
 		assert_eq!(Balances::free_balance(4), 90);
 
-		assert_eq!(CollatorSelection::candidates().len(), 2);
+		assert_eq!(CollatorSelection::candidate_list().len(), 2);
+	});
+}
+
+#[test]
+fn register_as_candidate_keeps_list_sorted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 20));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)));
+
+		// equal deposits: the most recently registered is the lowest.
+		let expected = vec![
+			CandidateInfo { who: 5, deposit: 10 },
+			CandidateInfo { who: 4, deposit: 10 },
+			CandidateInfo { who: 3, deposit: 20 },
+		];
+		assert_eq!(CollatorSelection::candidate_list(), expected);
+	});
+}
+
+#[test]
+fn update_bond_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_eq!(Balances::free_balance(3), 90);
+
+		// raising the bond reserves the difference and moves the candidate up the list.
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 30));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::CandidateBondUpdated { account_id: 4, deposit: 30 },
+		));
+		assert_eq!(Balances::free_balance(4), 70);
+		assert_eq!(Balances::reserved_balance(4), 30);
+		assert_eq!(
+			CollatorSelection::candidate_list(),
+			vec![CandidateInfo { who: 3, deposit: 10 }, CandidateInfo { who: 4, deposit: 30 }]
+		);
+
+		// overtaking an equal bond puts the updated candidate below, being the most recent.
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 30));
+		assert_eq!(
+			CollatorSelection::candidate_list(),
+			vec![CandidateInfo { who: 3, deposit: 30 }, CandidateInfo { who: 4, deposit: 30 }]
+		);
+	});
+}
+
+#[test]
+fn update_bond_fails_for_invalid_amounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+
+		// cannot go below the candidacy bond.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 9),
+			Error::<Test>::DepositTooLow
+		);
+		// nothing to update.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 10),
+			Error::<Test>::IdenticalDeposit
+		);
+		// cannot reserve more than the free balance.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 200),
+			BalancesError::<Test>::InsufficientBalance
+		);
+		// not a candidate.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20),
+			Error::<Test>::NotCandidate
+		);
+	});
+}
+
+#[test]
+fn update_bond_lowering_only_outside_desired_candidates() {
+	new_test_ext().execute_with(|| {
+		// DesiredCandidates: u32 = 2
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 50));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 40));
+
+		// both are in the top 2 and cannot lower their bond.
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 20),
+			Error::<Test>::InvalidUnreserve
+		);
+		assert_noop!(
+			CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20),
+			Error::<Test>::InvalidUnreserve
+		);
+
+		// 5 joins with a higher bond, pushing 4 out of the top 2.
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(5), 60));
+
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20));
+		assert_eq!(Balances::reserved_balance(4), 20);
+		assert_eq!(
+			CollatorSelection::candidate_list(),
+			vec![
+				CandidateInfo { who: 4, deposit: 20 },
+				CandidateInfo { who: 3, deposit: 50 },
+				CandidateInfo { who: 5, deposit: 60 },
+			]
+		);
+	});
+}
+
+#[test]
+fn take_candidate_slot_works() {
+	new_test_ext().execute_with(|| {
+		// fill the candidate list up to `MaxCandidates`.
+		for ii in 3..=22 {
+			if ii > 5 {
+				Balances::make_free_balance_be(&ii, 100);
+				let key = MockSessionKeys { aura: UintAuthorityId(ii) };
+				Session::set_keys(RuntimeOrigin::signed(ii).into(), key, Vec::new()).unwrap();
+			}
+			assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(ii)));
+		}
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(3), 30));
+
+		let newcomer = 23;
+		Balances::make_free_balance_be(&newcomer, 100);
+		let key = MockSessionKeys { aura: UintAuthorityId(newcomer) };
+		Session::set_keys(RuntimeOrigin::signed(newcomer).into(), key, Vec::new()).unwrap();
+
+		assert_noop!(
+			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(newcomer)),
+			Error::<Test>::TooManyCandidates
+		);
+
+		// the newcomer outbids 4.
+		assert_ok!(CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(newcomer), 20, 4));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::CandidateReplaced { old: 4, new: newcomer, deposit: 20 },
+		));
+
+		// 4 got its deposit back and the newcomer reserved theirs.
+		assert_eq!(Balances::free_balance(4), 100);
+		assert_eq!(CollatorSelection::last_authored_block(4), 0);
+		assert_eq!(Balances::reserved_balance(newcomer), 20);
+		assert_eq!(CollatorSelection::last_authored_block(newcomer), 10);
+
+		// the list is still full and sorted, with the newcomer right below 3.
+		let candidates = CollatorSelection::candidate_list();
+		assert_eq!(candidates.len(), 20);
+		assert!(candidates.iter().all(|c| c.who != 4));
+		assert_eq!(candidates[18], CandidateInfo { who: newcomer, deposit: 20 });
+		assert_eq!(candidates[19], CandidateInfo { who: 3, deposit: 30 });
+	});
+}
+
+#[test]
+fn take_candidate_slot_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+
+		// invulnerables cannot take a slot.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(1), 20, 3),
+			Error::<Test>::AlreadyInvulnerable
+		);
+		// deposit below the candidacy bond.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 5, 3),
+			Error::<Test>::InsufficientBond
+		);
+		// deposit not greater than the target's.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 10, 3),
+			Error::<Test>::InsufficientBond
+		);
+		// keys not registered.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(42), 20, 3),
+			Error::<Test>::ValidatorNotRegistered
+		);
+		// already a candidate.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(4), 20, 3),
+			Error::<Test>::AlreadyCandidate
+		);
+		// target is not a candidate.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 20, 2),
+			Error::<Test>::TargetIsNotCandidate
+		);
+		// cannot reserve the deposit.
+		assert_noop!(
+			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(5), 200, 3),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn assemble_collators_favours_higher_bonds() {
+	new_test_ext().execute_with(|| {
+		// DesiredCandidates: u32 = 2
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 20));
+		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(5), 30));
+
+		// invulnerables first, then the highest bonds.
+		assert_eq!(CollatorSelection::assemble_collators(), vec![1, 2, 5, 4]);
+
+		initialize_to_block(10);
+		let queued = Session::queued_keys().iter().map(|(who, _)| *who).collect::<Vec<_>>();
+		assert_eq!(queued, vec![1, 2, 5, 4]);
+		// 3 is not queued as a collator, but is still a candidate.
+		assert_eq!(CollatorSelection::candidate_list().len(), 3);
+	});
+}
+
+#[test]
+fn migrate_to_v2_sorts_candidates() {
+	use crate::migration::v2;
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<CollatorSelection>();
+		let old = vec![
+			CandidateInfo { who: 3, deposit: 30 },
+			CandidateInfo { who: 4, deposit: 10 },
+			CandidateInfo { who: 5, deposit: 20 },
+			CandidateInfo { who: 6, deposit: 10 },
+		];
+		v2::Candidates::<Test>::put(frame_support::BoundedVec::try_from(old).unwrap());
+
+		v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert!(!v2::Candidates::<Test>::exists());
+		assert_eq!(
+			CollatorSelection::candidate_list(),
+			vec![
+				CandidateInfo { who: 6, deposit: 10 },
+				CandidateInfo { who: 4, deposit: 10 },
+				CandidateInfo { who: 5, deposit: 20 },
+				CandidateInfo { who: 3, deposit: 30 },
+			]
+		);
+		assert_eq!(StorageVersion::get::<CollatorSelection>(), 2);
 	});
 }
 
@@ -459,7 +715,7 @@ fn authorship_event_handler() {
 
 		let collator = CandidateInfo { who: 4, deposit: 10 };
 
-		assert_eq!(CollatorSelection::candidates(), vec![collator]);
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator]);
 		assert_eq!(CollatorSelection::last_authored_block(4), 0);
 
 		// half of the pot goes to the collator who's the author (4 in tests).
@@ -484,7 +740,7 @@ fn fees_edgecases() {
 
 		let collator = CandidateInfo { who: 4, deposit: 10 };
 
-		assert_eq!(CollatorSelection::candidates(), vec![collator]);
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator]);
 		assert_eq!(CollatorSelection::last_authored_block(4), 0);
 		// Nothing received
 		assert_eq!(Balances::free_balance(4), 90);
@@ -512,7 +768,7 @@ fn session_management_works() {
 		// session won't see this.
 		assert_eq!(SessionHandlerCollators::get(), vec![1, 2]);
 		// but we have a new candidate.
-		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert_eq!(CollatorSelection::candidate_list().len(), 1);
 
 		initialize_to_block(10);
 		assert_eq!(SessionChangeBlock::get(), 10);
@@ -537,15 +793,15 @@ fn kick_mechanism() {
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
 		initialize_to_block(10);
-		assert_eq!(CollatorSelection::candidates().len(), 2);
+		assert_eq!(CollatorSelection::candidate_list().len(), 2);
 		initialize_to_block(20);
 		assert_eq!(SessionChangeBlock::get(), 20);
 		// 4 authored this block, gets to stay 3 was kicked
-		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert_eq!(CollatorSelection::candidate_list().len(), 1);
 		// 3 will be kicked after 1 session delay
 		assert_eq!(SessionHandlerCollators::get(), vec![1, 2, 3, 4]);
 		let collator = CandidateInfo { who: 4, deposit: 10 };
-		assert_eq!(CollatorSelection::candidates(), vec![collator]);
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator]);
 		assert_eq!(CollatorSelection::last_authored_block(4), 20);
 		initialize_to_block(30);
 		// 3 gets kicked after 1 session delay
@@ -559,7 +815,7 @@ fn kick_mechanism() {
 fn should_not_kick_mechanism_too_few() {
 	new_test_ext().execute_with(|| {
 		// remove the invulnerables and add new collators 3 and 5
-		assert_eq!(CollatorSelection::candidates(), Vec::new());
+		assert_eq!(CollatorSelection::candidate_list(), Vec::new());
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
 		assert_ok!(CollatorSelection::remove_invulnerable(
 			RuntimeOrigin::signed(RootAccount::get()),
@@ -573,30 +829,31 @@ fn should_not_kick_mechanism_too_few() {
 		));
 
 		initialize_to_block(10);
-		assert_eq!(CollatorSelection::candidates().len(), 2);
+		assert_eq!(CollatorSelection::candidate_list().len(), 2);
 
 		initialize_to_block(20);
 		assert_eq!(SessionChangeBlock::get(), 20);
-		// 4 authored this block, 3 is kicked, 5 stays because of too few collators
-		assert_eq!(CollatorSelection::candidates().len(), 1);
-		// 3 will be kicked after 1 session delay
+		// 4 authored this block. 5 registered last, so it has the lowest bond and is kicked
+		// first. 3 stays because of too few collators.
+		assert_eq!(CollatorSelection::candidate_list().len(), 1);
+		// 5 will be kicked after 1 session delay
 		assert_eq!(SessionHandlerCollators::get(), vec![3, 5]);
-		let collator = CandidateInfo { who: 5, deposit: 10 };
-		assert_eq!(CollatorSelection::candidates(), vec![collator]);
+		let collator = CandidateInfo { who: 3, deposit: 10 };
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator]);
 		assert_eq!(CollatorSelection::last_authored_block(4), 20);
 
 		initialize_to_block(30);
-		// 3 gets kicked after 1 session delay
-		assert_eq!(SessionHandlerCollators::get(), vec![5]);
+		// 5 gets kicked after 1 session delay
+		assert_eq!(SessionHandlerCollators::get(), vec![3]);
 		// kicked collator gets funds back
-		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Balances::free_balance(5), 100);
 	});
 }
 
 #[test]
 fn should_kick_invulnerables_from_candidates_on_session_change() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(CollatorSelection::candidates(), Vec::new());
+		assert_eq!(CollatorSelection::candidate_list(), Vec::new());
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
 		assert_eq!(Balances::free_balance(3), 90);
@@ -609,13 +866,14 @@ fn should_kick_invulnerables_from_candidates_on_session_change() {
 		let collator_3 = CandidateInfo { who: 3, deposit: 10 };
 		let collator_4 = CandidateInfo { who: 4, deposit: 10 };
 
-		assert_eq!(CollatorSelection::candidates(), vec![collator_3, collator_4.clone()]);
+		// equal deposits, so the most recently registered is the lowest.
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator_4.clone(), collator_3]);
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2, 3]);
 
 		// session change
 		initialize_to_block(10);
 		// 3 is removed from candidates
-		assert_eq!(CollatorSelection::candidates(), vec![collator_4]);
+		assert_eq!(CollatorSelection::candidate_list(), vec![collator_4]);
 		// but not from invulnerables
 		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2, 3]);
 		// and it got its deposit back
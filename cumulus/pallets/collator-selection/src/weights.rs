@@ -35,6 +35,8 @@ pub trait WeightInfo {
 	fn leave_intent(_c: u32) -> Weight;
 	fn note_author() -> Weight;
 	fn new_session(_c: u32, _r: u32) -> Weight;
+	fn update_bond(_c: u32) -> Weight;
+	fn take_candidate_slot(_c: u32) -> Weight;
 }
 
 /// Weights for pallet_collator_selection using the Substrate node and recommended hardware.
@@ -86,9 +88,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
 	/// Storage: CollatorSelection Invulnerables (r:1 w:1)
 	/// Proof: CollatorSelection Invulnerables (max_values: Some(1), max_size: Some(641), added:
-	/// 1136, mode: MaxEncodedLen) Storage: CollatorSelection Candidates (r:1 w:1)
-	/// Proof: CollatorSelection Candidates (max_values: Some(1), max_size: Some(4802), added: 5297,
-	/// mode: MaxEncodedLen) Storage: System Account (r:1 w:1)
+	/// 1136, mode: MaxEncodedLen) Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
 	/// MaxEncodedLen) The range of component `b` is `[1, 19]`.
 	/// The range of component `c` is `[1, 99]`.
@@ -123,6 +125,48 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: CollatorSelection CandidacyBond (r:1 w:0)
+	/// Proof: CollatorSelection CandidacyBond (max_values: Some(1), max_size: Some(16), added: 511,
+	/// mode: MaxEncodedLen) Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: CollatorSelection DesiredCandidates (r:1 w:0)
+	/// Proof: CollatorSelection DesiredCandidates (max_values: Some(1), max_size: Some(4), added:
+	/// 499, mode: MaxEncodedLen) The range of component `c` is `[3, 100]`.
+	fn update_bond(c: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `306 + c * (50 ±0)`
+		//  Estimated: `6287`
+		// Minimum execution time: 34_814_000 picoseconds.
+		Weight::from_parts(36_371_520, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			// Standard Error: 2_391
+			.saturating_add(Weight::from_parts(201_700, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: CollatorSelection Invulnerables (r:1 w:0)
+	/// Proof: CollatorSelection Invulnerables (max_values: Some(1), max_size: Some(3202), added:
+	/// 3697, mode: MaxEncodedLen) Storage: CollatorSelection CandidacyBond (r:1 w:0)
+	/// Proof: CollatorSelection CandidacyBond (max_values: Some(1), max_size: Some(16), added: 511,
+	/// mode: MaxEncodedLen) Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	/// Storage: CollatorSelection LastAuthoredBlock (r:0 w:2)
+	/// Proof: CollatorSelection LastAuthoredBlock (max_values: None, max_size: Some(44), added:
+	/// 2519, mode: MaxEncodedLen) The range of component `c` is `[4, 100]`.
+	fn take_candidate_slot(c: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `306 + c * (50 ±0)`
+		//  Estimated: `6287`
+		// Minimum execution time: 51_209_000 picoseconds.
+		Weight::from_parts(52_924_452, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			// Standard Error: 2_917
+			.saturating_add(Weight::from_parts(204_511, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
 
 // For backwards compatibility and tests
@@ -173,9 +217,9 @@ impl WeightInfo for () {
 	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
 	/// Storage: CollatorSelection Invulnerables (r:1 w:1)
 	/// Proof: CollatorSelection Invulnerables (max_values: Some(1), max_size: Some(641), added:
-	/// 1136, mode: MaxEncodedLen) Storage: CollatorSelection Candidates (r:1 w:1)
-	/// Proof: CollatorSelection Candidates (max_values: Some(1), max_size: Some(4802), added: 5297,
-	/// mode: MaxEncodedLen) Storage: System Account (r:1 w:1)
+	/// 1136, mode: MaxEncodedLen) Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
 	/// MaxEncodedLen) The range of component `b` is `[1, 19]`.
 	/// The range of component `c` is `[1, 99]`.
@@ -210,4 +254,46 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+	/// Storage: CollatorSelection CandidacyBond (r:1 w:0)
+	/// Proof: CollatorSelection CandidacyBond (max_values: Some(1), max_size: Some(16), added: 511,
+	/// mode: MaxEncodedLen) Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: CollatorSelection DesiredCandidates (r:1 w:0)
+	/// Proof: CollatorSelection DesiredCandidates (max_values: Some(1), max_size: Some(4), added:
+	/// 499, mode: MaxEncodedLen) The range of component `c` is `[3, 100]`.
+	fn update_bond(c: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `306 + c * (50 ±0)`
+		//  Estimated: `6287`
+		// Minimum execution time: 34_814_000 picoseconds.
+		Weight::from_parts(36_371_520, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			// Standard Error: 2_391
+			.saturating_add(Weight::from_parts(201_700, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: CollatorSelection CandidateList (r:1 w:1)
+	/// Proof: CollatorSelection CandidateList (max_values: Some(1), max_size: Some(4802), added:
+	/// 5297, mode: MaxEncodedLen) Storage: CollatorSelection Invulnerables (r:1 w:0)
+	/// Proof: CollatorSelection Invulnerables (max_values: Some(1), max_size: Some(3202), added:
+	/// 3697, mode: MaxEncodedLen) Storage: CollatorSelection CandidacyBond (r:1 w:0)
+	/// Proof: CollatorSelection CandidacyBond (max_values: Some(1), max_size: Some(16), added: 511,
+	/// mode: MaxEncodedLen) Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	/// Storage: CollatorSelection LastAuthoredBlock (r:0 w:2)
+	/// Proof: CollatorSelection LastAuthoredBlock (max_values: None, max_size: Some(44), added:
+	/// 2519, mode: MaxEncodedLen) The range of component `c` is `[4, 100]`.
+	fn take_candidate_slot(c: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `306 + c * (50 ±0)`
+		//  Estimated: `6287`
+		// Minimum execution time: 51_209_000 picoseconds.
+		Weight::from_parts(52_924_452, 0)
+			.saturating_add(Weight::from_parts(0, 6287))
+			// Standard Error: 2_917
+			.saturating_add(Weight::from_parts(204_511, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
 }
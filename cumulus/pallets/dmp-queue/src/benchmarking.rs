@@ -0,0 +1,46 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarking setup for cumulus-pallet-dmp-queue
+
+use crate::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::Get;
+
+/// The size of the page that is migrated by `lazy_migrate_page`.
+const MIGRATED_PAGE_SIZE: usize = 64 * 1024;
+
+benchmarks! {
+	enqueue_dmp_message {
+		let msg = vec![0u8; MaxDmpMessageLenOf::<T>::get() as usize];
+	}: {
+		Pallet::<T>::handle_message(&msg[..]);
+	}
+
+	lazy_migrate_page {
+		// Many small messages are the worst case since each is enqueued on its own.
+		let msg = vec![0u8; 4];
+		let page = vec![(0, msg.clone()); MIGRATED_PAGE_SIZE / (msg.encoded_size() + 4)];
+		Pages::<T>::insert(0, page);
+		PageIndex::<T>::put(PageIndexData { begin_used: 0, end_used: 1, overweight_count: 0 });
+	}: {
+		assert!(migration::lazy_migrate_queue::<T>());
+	} verify {
+		assert!(!Pages::<T>::contains_key(0));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
@@ -16,7 +16,7 @@
 
 //! A module that is responsible for migration of storage.
 
-use crate::{Config, Configuration, Overweight, Pallet, DEFAULT_POV_SIZE};
+use crate::{Config, Configuration, Event, Overweight, PageIndex, Pages, Pallet};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{OnRuntimeUpgrade, StorageVersion},
@@ -26,6 +26,8 @@ use frame_support::{
 /// The current storage version.
 pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
+const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
+
 /// Migrates the pallet storage to the most recent version.
 pub struct Migration<T: Config>(PhantomData<T>);
 
@@ -97,6 +99,50 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 	T::DbWeight::get().reads_writes(overweight_messages, 1)
 }
 
+/// Hand one page of the old queue or, once those are all gone, one of the old overweight
+/// messages over to the `DmpSink`. The remaining storage of the old queue is cleared at the end.
+///
+/// Returns `false` once there is nothing left to migrate.
+pub fn lazy_migrate_queue<T: Config>() -> bool {
+	let mut page_index = PageIndex::<T>::get();
+	if page_index.begin_used < page_index.end_used {
+		for (_sent_at, data) in Pages::<T>::take(page_index.begin_used) {
+			Pallet::<T>::handle_message(&data[..]);
+		}
+		page_index.begin_used += 1;
+		if page_index.begin_used == page_index.end_used {
+			// Reset if there's no pages left.
+			page_index.begin_used = 0;
+			page_index.end_used = 0;
+		}
+		PageIndex::<T>::put(page_index);
+		return true
+	}
+
+	if let Some((index, (_sent_at, data))) = Overweight::<T>::iter().next() {
+		Overweight::<T>::remove(index);
+		Pallet::<T>::handle_message(&data[..]);
+		return true
+	}
+
+	if PageIndex::<T>::exists() || Configuration::<T>::exists() {
+		PageIndex::<T>::kill();
+		Configuration::<T>::kill();
+		Pallet::<T>::deposit_event(Event::MigrationCompleted);
+		return true
+	}
+
+	false
+}
+
+/// Whether anything of the old queue is left for [`lazy_migrate_queue`].
+pub fn has_pending_queue<T: Config>() -> bool {
+	PageIndex::<T>::exists() || Configuration::<T>::exists() || Overweight::<T>::count() > 0
+}
+
+/// The number of storage reads done by [`has_pending_queue`].
+pub const PENDING_CHECK_READS: u64 = 3;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
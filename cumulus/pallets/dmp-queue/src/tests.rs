@@ -0,0 +1,213 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate as dmp_queue;
+
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Footprint, OnIdle},
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use xcm::{latest::prelude::*, VersionedXcm};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		DmpQueue: dmp_queue::{Pallet, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	/// The messages that were handed to [`RecordingSink`].
+	pub static Handled: Vec<Vec<u8>> = Vec::new();
+}
+
+type AccountId = u64;
+
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type BlockLength = ();
+	type BlockWeights = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// A `HandleMessage` implementation that records all messages in thread-local storage.
+pub struct RecordingSink;
+impl HandleMessage for RecordingSink {
+	type MaxMessageLen = ConstU32<64>;
+
+	fn handle_message(message: BoundedSlice<u8, Self::MaxMessageLen>) {
+		Handled::mutate(|h| h.push(message.to_vec()));
+	}
+
+	fn handle_messages<'a>(
+		messages: impl Iterator<Item = BoundedSlice<'a, u8, Self::MaxMessageLen>>,
+	) {
+		messages.for_each(Self::handle_message);
+	}
+
+	fn sweep_queue() {
+		Handled::take();
+	}
+
+	fn footprint() -> Footprint {
+		let handled = Handled::get();
+		Footprint::from_parts(handled.len(), handled.iter().map(Vec::len).sum())
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type DmpSink = RecordingSink;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn msg(n: u8) -> Vec<u8> {
+	VersionedXcm::<()>::from(Xcm::<()>(vec![Trap(n as u64)])).encode()
+}
+
+fn enqueue_old(messages: &[Vec<u8>]) {
+	let mut index = PageIndex::<Test>::get();
+	Pages::<Test>::insert(
+		index.end_used,
+		messages.iter().map(|m| (0, m.clone())).collect::<Vec<_>>(),
+	);
+	index.end_used += 1;
+	PageIndex::<Test>::put(index);
+}
+
+#[test]
+fn messages_are_handed_to_the_sink() {
+	new_test_ext().execute_with(|| {
+		let incoming = vec![(0, msg(1)), (1, msg(2))];
+		DmpQueue::handle_dmp_messages(incoming.into_iter(), Weight::zero());
+
+		assert_eq!(Handled::get(), vec![msg(1), msg(2)]);
+		assert_eq!(PageIndex::<Test>::get(), PageIndexData::default());
+	});
+}
+
+#[test]
+fn too_big_messages_are_dropped() {
+	new_test_ext().execute_with(|| {
+		let big = vec![0u8; 65];
+		let incoming = vec![(0, big.clone()), (0, msg(1))];
+		DmpQueue::handle_dmp_messages(incoming.into_iter(), Weight::zero());
+
+		assert_eq!(Handled::get(), vec![msg(1)]);
+		System::assert_has_event(
+			Event::MessageTooBig { message_hash: sp_io::hashing::blake2_256(&big) }.into(),
+		);
+	});
+}
+
+#[test]
+fn messages_are_appended_to_the_old_queue_until_it_is_drained() {
+	new_test_ext().execute_with(|| {
+		enqueue_old(&[msg(1), msg(2)]);
+		DmpQueue::handle_dmp_messages(vec![(1, msg(3))].into_iter(), Weight::zero());
+		assert!(Handled::get().is_empty());
+
+		assert!(migration::lazy_migrate_queue::<Test>());
+		assert!(migration::lazy_migrate_queue::<Test>());
+		assert_eq!(Handled::get(), vec![msg(1), msg(2), msg(3)]);
+
+		// The old queue is drained; new messages go to the sink directly.
+		DmpQueue::handle_dmp_messages(vec![(2, msg(4))].into_iter(), Weight::zero());
+		assert_eq!(Handled::get(), vec![msg(1), msg(2), msg(3), msg(4)]);
+	});
+}
+
+#[test]
+fn lazy_migration_drains_pages_and_overweight() {
+	new_test_ext().execute_with(|| {
+		enqueue_old(&[msg(1)]);
+		Overweight::<Test>::insert(0, (0, msg(2)));
+		Configuration::<Test>::put(ConfigData { max_individual: Weight::from_parts(1, 1) });
+
+		// One page, one overweight message and the cleanup.
+		for _ in 0..3 {
+			assert!(migration::lazy_migrate_queue::<Test>());
+		}
+		assert!(!migration::lazy_migrate_queue::<Test>());
+
+		assert_eq!(Handled::get(), vec![msg(1), msg(2)]);
+		assert_eq!(Overweight::<Test>::count(), 0);
+		assert!(!PageIndex::<Test>::exists());
+		assert!(!Configuration::<Test>::exists());
+		System::assert_last_event(Event::MigrationCompleted.into());
+	});
+}
+
+#[test]
+fn on_idle_migrates_within_weight_limit() {
+	new_test_ext().execute_with(|| {
+		enqueue_old(&[msg(1)]);
+		enqueue_old(&[msg(2)]);
+		let page_weight = <() as WeightInfo>::lazy_migrate_page();
+
+		// Not enough weight for a single page.
+		let used = DmpQueue::on_idle(1, page_weight - Weight::from_parts(1, 0));
+		assert_eq!(used, Weight::zero());
+		assert!(Handled::get().is_empty());
+
+		let used = DmpQueue::on_idle(1, page_weight);
+		assert_eq!(used, page_weight);
+		assert_eq!(Handled::get(), vec![msg(1)]);
+
+		DmpQueue::on_idle(1, Weight::MAX);
+		assert_eq!(Handled::get(), vec![msg(1), msg(2)]);
+		assert_eq!(PageIndex::<Test>::get(), PageIndexData::default());
+
+		// Nothing is charged once the old queue is gone.
+		assert!(!migration::has_pending_queue::<Test>());
+		assert_eq!(DmpQueue::on_idle(2, Weight::MAX), Weight::zero());
+	});
+}
@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+// Implemented by autogenerated benchmarking code.
+pub trait WeightInfo {
+	fn enqueue_dmp_message() -> Weight;
+	fn lazy_migrate_page() -> Weight;
+}
+
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn enqueue_dmp_message() -> Weight {
+		Weight::from_parts(15_118_000_u64, 3_517)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+
+	// Storage: DmpQueue PageIndex (r:1 w:1)
+	// Storage: DmpQueue Pages (r:1 w:1)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn lazy_migrate_page() -> Weight {
+		Weight::from_parts(71_302_000_u64, 69_047)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn enqueue_dmp_message() -> Weight {
+		Weight::from_parts(15_118_000_u64, 3_517)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+
+	// Storage: DmpQueue PageIndex (r:1 w:1)
+	// Storage: DmpQueue Pages (r:1 w:1)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn lazy_migrate_page() -> Weight {
+		Weight::from_parts(71_302_000_u64, 69_047)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+}
@@ -150,6 +150,11 @@ impl CheckAssociatedRelayNumber for AnyRelayNumber {
 ///
 /// It will ensure that the associated relay block number monotonically increases between Parachain
 /// blocks. This should be used when asynchronous backing is enabled.
+///
+/// It also allows a chain of Parachain blocks to be built on the same relay parent, which is
+/// required for a Parachain to make use of multiple cores at once (elastic scaling). The size of
+/// such a chain is bounded by the unincluded segment capacity returned by
+/// [`Config::ConsensusHook`].
 pub struct RelayNumberMonotonicallyIncreases;
 
 impl CheckAssociatedRelayNumber for RelayNumberMonotonicallyIncreases {
@@ -524,6 +524,130 @@ fn unincluded_segment_works() {
 		);
 }
 
+#[test]
+fn unincluded_segment_accepts_blocks_on_same_relay_parent() {
+	CONSENSUS_HOOK.with(|c| {
+		*c.borrow_mut() = Box::new(|_| (Weight::zero(), NonZeroU32::new(4).unwrap().into()))
+	});
+
+	// Two parachain blocks are built on each relay parent, as with a para assigned
+	// to two cores.
+	BlockTests::new()
+		.with_inclusion_delay(2)
+		.with_relay_block_number(|block_number| 10 + (*block_number as RelayChainBlockNumber) / 2)
+		.add_with_post_test(
+			122,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 1);
+			},
+		)
+		.add_with_post_test(
+			123,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 2);
+			},
+		)
+		.add_with_post_test(
+			124,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 3);
+			},
+		)
+		.add_with_post_test(
+			125,
+			|| {},
+			|| {
+				// Block 122 was included and popped from the segment.
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 3);
+			},
+		);
+}
+
+#[test]
+fn unincluded_segment_accepts_chains_of_candidates_on_multiple_cores() {
+	CONSENSUS_HOOK.with(|c| {
+		*c.borrow_mut() = Box::new(|_| (Weight::zero(), NonZeroU32::new(6).unwrap().into()))
+	});
+
+	// Three parachain blocks are built on each relay parent, as with a para assigned to
+	// three cores, and a chain of three candidates is pending availability at any time.
+	BlockTests::new()
+		.with_inclusion_delay(3)
+		.with_relay_block_number(|block_number| 10 + (*block_number as RelayChainBlockNumber) / 3)
+		.add_with_post_test(
+			120,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 1);
+			},
+		)
+		.add_with_post_test(
+			121,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 2);
+			},
+		)
+		.add_with_post_test(
+			122,
+			|| {},
+			|| {
+				// The whole chain built on the first relay parent is in the segment.
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 3);
+				assert_eq!(LastRelayChainBlockNumber::<Test>::get(), 50);
+			},
+		)
+		.add_with_post_test(
+			123,
+			|| {},
+			|| {
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 4);
+				assert_eq!(LastRelayChainBlockNumber::<Test>::get(), 51);
+			},
+		)
+		.add_with_post_test(
+			124,
+			|| {},
+			|| {
+				// Block 120 was included and popped from the segment.
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 4);
+			},
+		)
+		.add_with_post_test(
+			125,
+			|| {},
+			|| {
+				// Block 121 was included and popped from the segment.
+				let segment = <UnincludedSegment<Test>>::get();
+				assert_eq!(segment.len(), 4);
+			},
+		);
+}
+
+#[test]
+fn relay_number_monotonically_increases_accepts_same_relay_parent() {
+	RelayNumberMonotonicallyIncreases::check_associated_relay_number(10, 10);
+	RelayNumberMonotonicallyIncreases::check_associated_relay_number(11, 10);
+}
+
+#[test]
+#[should_panic = "Relay chain block number needs to monotonically increase between Parachain blocks!"]
+fn relay_number_monotonically_increases_rejects_older_relay_parent() {
+	RelayNumberMonotonicallyIncreases::check_associated_relay_number(9, 10);
+}
+
 #[test]
 #[should_panic = "no space left for the block in the unincluded segment"]
 fn unincluded_segment_is_limited() {
@@ -16,7 +16,7 @@
 
 //! The actual implementation of the validate block functionality.
 
-use super::{trie_cache, MemoryOptimizedValidationParams};
+use super::{trie_cache, trie_recorder, MemoryOptimizedValidationParams};
 use cumulus_primitives_core::{
 	relay_chain::Hash as RHash, ParachainBlockData, PersistedValidationData,
 };
@@ -34,7 +34,7 @@ use sp_externalities::{set_and_run_with_externalities, Externalities};
 use sp_io::KillStorageResult;
 use sp_runtime::traits::{Block as BlockT, Extrinsic, HashingFor, Header as HeaderT};
 use sp_std::prelude::*;
-use sp_trie::MemoryDB;
+use sp_trie::{MemoryDB, ProofSizeProvider};
 
 type TrieBackend<B> = sp_state_machine::TrieBackend<
 	MemoryDB<HashingFor<B>>,
@@ -44,6 +44,8 @@ type TrieBackend<B> = sp_state_machine::TrieBackend<
 
 type Ext<'a, B> = sp_state_machine::Ext<'a, HashingFor<B>, TrieBackend<B>>;
 
+environmental::environmental!(recorder: trait ProofSizeProvider);
+
 fn with_externalities<F: FnOnce(&mut dyn Externalities) -> R, R>(f: F) -> R {
 	sp_externalities::with_externalities(f).expect("Environmental externalities not set.")
 }
@@ -65,7 +67,8 @@ fn with_externalities<F: FnOnce(&mut dyn Externalities) -> R, R>(f: F) -> R {
 /// This means instead of calling into the host, we will stay inside the wasm execution. This is
 /// very important as the relay chain validator hasn't the state required to verify the block. But
 /// we have the in-memory database that contains all the values from the state of the parachain
-/// that we require to verify the block.
+/// that we require to verify the block. The size of the storage proof, which is also provided by
+/// the host, is computed from the trie nodes the runtime accessed while executing the block.
 ///
 /// 5. We are going to run `check_inherents`. This is important to check stuff like the timestamp
 /// matching the real world time.
@@ -120,7 +123,8 @@ where
 
 	sp_std::mem::drop(storage_proof);
 
-	let cache_provider = trie_cache::CacheProvider::new();
+	let mut size_recorder = trie_recorder::SizeOnlyRecorderProvider::default();
+	let cache_provider = trie_cache::CacheProvider::new(size_recorder.clone());
 	// We use the storage root of the `parent_head` to ensure that it is the correct root.
 	// This is already being done above while creating the in-memory db, but let's be paranoid!!
 	let backend = sp_state_machine::TrieBackendBuilder::new_with_cache(
@@ -167,6 +171,8 @@ where
 			.replace_implementation(host_default_child_storage_next_key),
 		sp_io::offchain_index::host_set.replace_implementation(host_offchain_index_set),
 		sp_io::offchain_index::host_clear.replace_implementation(host_offchain_index_clear),
+		cumulus_primitives_proof_size_hostfunction::storage_proof_size::host_storage_proof_size
+			.replace_implementation(host_storage_proof_size),
 	);
 
 	run_with_externalities::<B, _, _>(&backend, || {
@@ -193,7 +199,10 @@ where
 	run_with_externalities::<B, _, _>(&backend, || {
 		let head_data = HeadData(block.header().encode());
 
-		E::execute_block(block);
+		// The proof size reported to the runtime has to match the one of the collator, which only
+		// recorded the execution of the block.
+		size_recorder.reset();
+		recorder::using(&mut size_recorder, || E::execute_block(block));
 
 		let new_validation_code = crate::NewValidationCode::<PSC>::get();
 		let upward_messages = crate::UpwardMessages::<PSC>::get().try_into().expect(
@@ -276,6 +285,11 @@ fn run_with_externalities<B: BlockT, R, F: FnOnce() -> R>(
 	set_and_run_with_externalities(&mut ext, || execute())
 }
 
+fn host_storage_proof_size() -> u64 {
+	recorder::with(|rec| rec.estimate_encoded_size() as u64)
+		.unwrap_or(cumulus_primitives_proof_size_hostfunction::PROOF_RECORDING_DISABLED)
+}
+
 fn host_storage_read(key: &[u8], value_out: &mut [u8], value_offset: u32) -> Option<u32> {
 	match with_externalities(|ext| ext.storage(key)) {
 		Some(value) => {
@@ -15,6 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::trie_recorder::SizeOnlyRecorderProvider;
 use sp_state_machine::TrieCacheProvider;
 use sp_std::{
 	boxed::Box,
@@ -22,7 +23,7 @@ use sp_std::{
 	collections::btree_map::{BTreeMap, Entry},
 };
 use sp_trie::NodeCodec;
-use trie_db::{node::NodeOwned, Hasher};
+use trie_db::{node::NodeOwned, Hasher, TrieRecorder};
 
 /// Special purpose trie cache implementation that is able to cache an unlimited number
 /// of values. To be used in `validate_block` to serve values and nodes that
@@ -65,16 +66,19 @@ impl<'a, H: Hasher> trie_db::TrieCache<NodeCodec<H>> for TrieCache<'a, H> {
 }
 
 /// Provider of [`TrieCache`] instances.
+///
+/// It also provides the recorders which keep track of the size of the storage proof.
 pub(crate) struct CacheProvider<H: Hasher> {
 	node_cache: RefCell<BTreeMap<H::Out, NodeOwned<H::Out>>>,
 	value_cache: RefCell<BTreeMap<Box<[u8]>, trie_db::CachedValue<H::Out>>>,
+	recorder: SizeOnlyRecorderProvider<H>,
 }
 
 impl<H: Hasher> CacheProvider<H> {
 	/// Constructs a new instance of [`CacheProvider`] with an uninitialized state
-	/// and empty node and value caches.
-	pub fn new() -> Self {
-		CacheProvider { node_cache: Default::default(), value_cache: Default::default() }
+	/// and empty node and value caches, which records the trie accesses with `recorder`.
+	pub fn new(recorder: SizeOnlyRecorderProvider<H>) -> Self {
+		CacheProvider { node_cache: Default::default(), value_cache: Default::default(), recorder }
 	}
 }
 
@@ -97,6 +101,13 @@ impl<H: Hasher> TrieCacheProvider<H> for CacheProvider<H> {
 	}
 
 	fn merge<'a>(&'a self, _other: Self::Cache<'a>, _new_root: <H as Hasher>::Out) {}
+
+	fn as_trie_db_recorder(
+		&self,
+		storage_root: <H as Hasher>::Out,
+	) -> Option<Box<dyn TrieRecorder<H::Out> + '_>> {
+		Some(Box::new(self.recorder.as_trie_recorder(storage_root)))
+	}
 }
 
 // This is safe here since we are single-threaded in WASM
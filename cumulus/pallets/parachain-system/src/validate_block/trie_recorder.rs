@@ -0,0 +1,260 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trie recorder that only keeps track of the size of the storage proof.
+//!
+//! The nodes accessed in `validate_block` are already part of the storage proof of the block, so
+//! they don't need to be recorded again. Only the size of the proof recorded so far is required,
+//! to return it from the `storage_proof_size` host function.
+
+use codec::Encode;
+use sp_std::{
+	boxed::Box,
+	cell::{RefCell, RefMut},
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	rc::Rc,
+};
+use sp_trie::{NodeCodec, ProofSizeProvider};
+use trie_db::{Hasher, RecordedForKey, TrieAccess};
+
+/// The state shared by all recorders of a [`SizeOnlyRecorderProvider`].
+struct RecorderInner<H: Hasher> {
+	/// The hashes of the nodes and values which are already part of the proof.
+	seen_nodes: BTreeSet<H::Out>,
+	/// The estimated encoded size of the proof.
+	encoded_size: usize,
+	/// Mapping: `StorageRoot -> (Key -> RecordedForKey)`.
+	recorded_keys: BTreeMap<H::Out, BTreeMap<Box<[u8]>, RecordedForKey>>,
+}
+
+impl<H: Hasher> Default for RecorderInner<H> {
+	fn default() -> Self {
+		Self { seen_nodes: Default::default(), encoded_size: 0, recorded_keys: Default::default() }
+	}
+}
+
+/// A trie recorder that only keeps track of the proof size.
+///
+/// The size is computed in the same way as by [`sp_trie::recorder::Recorder`], so it matches the
+/// proof size the collator reported to the runtime while building the block.
+pub(crate) struct SizeOnlyRecorder<'a, H: Hasher> {
+	inner: RefMut<'a, RecorderInner<H>>,
+	storage_root: H::Out,
+}
+
+impl<'a, H: Hasher> SizeOnlyRecorder<'a, H> {
+	/// Update the recorded keys entry for the given `full_key`.
+	fn update_recorded_keys(&mut self, full_key: &[u8], access: RecordedForKey) {
+		let entry = self
+			.inner
+			.recorded_keys
+			.entry(self.storage_root)
+			.or_default()
+			.entry(full_key.into())
+			.or_insert(access);
+
+		// Only a `Value` access can be an upgrade from `Hash`.
+		if matches!(access, RecordedForKey::Value) {
+			*entry = access;
+		}
+	}
+
+	/// Account for the node or value with the given `hash`, if it isn't part of the proof yet.
+	fn record_node(&mut self, hash: H::Out, encoded_size: impl FnOnce() -> usize) {
+		if self.inner.seen_nodes.insert(hash) {
+			self.inner.encoded_size += encoded_size();
+		}
+	}
+}
+
+impl<'a, H: Hasher> trie_db::TrieRecorder<H::Out> for SizeOnlyRecorder<'a, H> {
+	fn record(&mut self, access: TrieAccess<'_, H::Out>) {
+		match access {
+			TrieAccess::NodeOwned { hash, node_owned } =>
+				self.record_node(hash, || node_owned.to_encoded::<NodeCodec<H>>().encoded_size()),
+			TrieAccess::EncodedNode { hash, encoded_node } =>
+				self.record_node(hash, || encoded_node.encoded_size()),
+			TrieAccess::Value { hash, value, full_key } => {
+				self.record_node(hash, || value.encoded_size());
+				self.update_recorded_keys(full_key, RecordedForKey::Value);
+			},
+			TrieAccess::Hash { full_key } => {
+				self.update_recorded_keys(full_key, RecordedForKey::Hash);
+			},
+			TrieAccess::NonExisting { full_key } | TrieAccess::InlineValue { full_key } => {
+				self.update_recorded_keys(full_key, RecordedForKey::Value);
+			},
+		}
+	}
+
+	fn trie_nodes_recorded_for_key(&self, key: &[u8]) -> RecordedForKey {
+		self.inner
+			.recorded_keys
+			.get(&self.storage_root)
+			.and_then(|keys| keys.get(key).copied())
+			.unwrap_or(RecordedForKey::None)
+	}
+}
+
+/// Provider of [`SizeOnlyRecorder`] instances which share their state.
+///
+/// Clones of the provider share the state as well.
+pub(crate) struct SizeOnlyRecorderProvider<H: Hasher> {
+	inner: Rc<RefCell<RecorderInner<H>>>,
+}
+
+impl<H: Hasher> Default for SizeOnlyRecorderProvider<H> {
+	fn default() -> Self {
+		Self { inner: Default::default() }
+	}
+}
+
+impl<H: Hasher> Clone for SizeOnlyRecorderProvider<H> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner.clone() }
+	}
+}
+
+impl<H: Hasher> SizeOnlyRecorderProvider<H> {
+	/// Returns the recorder for the trie with the given `storage_root`.
+	pub fn as_trie_recorder(&self, storage_root: H::Out) -> SizeOnlyRecorder<'_, H> {
+		SizeOnlyRecorder { inner: self.inner.borrow_mut(), storage_root }
+	}
+
+	/// Discard everything recorded so far.
+	pub fn reset(&self) {
+		*self.inner.borrow_mut() = Default::default();
+	}
+}
+
+impl<H: Hasher> ProofSizeProvider for SizeOnlyRecorderProvider<H> {
+	fn estimate_encoded_size(&self) -> usize {
+		self.inner.borrow().encoded_size
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_trie::{
+		cache::{CacheSize, SharedTrieCache},
+		MemoryDB,
+	};
+	use trie_db::{Trie, TrieDBBuilder, TrieDBMutBuilder, TrieHash, TrieMut};
+
+	type Recorder = sp_trie::recorder::Recorder<sp_core::Blake2Hasher>;
+	type Layout = sp_trie::LayoutV1<sp_core::Blake2Hasher>;
+
+	fn create_trie() -> (MemoryDB<sp_core::Blake2Hasher>, TrieHash<Layout>, Vec<(Vec<u8>, Vec<u8>)>)
+	{
+		let mut db = MemoryDB::default();
+		let mut root = Default::default();
+
+		let mut seed = [0u8; 32];
+		let mut entries = Vec::new();
+		for i in 0..100u32 {
+			seed[..4].copy_from_slice(&i.to_le_bytes());
+			let key = sp_core::blake2_256(&seed).to_vec();
+			// Some values are stored inline in the nodes, others in the db.
+			let value = vec![i as u8; (i * 3) as usize];
+			entries.push((key, value));
+		}
+
+		{
+			let mut trie = TrieDBMutBuilder::<Layout>::new(&mut db, &mut root).build();
+			for (key, value) in &entries {
+				trie.insert(key, value).expect("Inserts data");
+			}
+		}
+
+		(db, root, entries)
+	}
+
+	#[test]
+	fn recorder_equivalence_with_cache() {
+		let (db, root, entries) = create_trie();
+
+		let reference_recorder = Recorder::default();
+		let size_only_recorder_provider = SizeOnlyRecorderProvider::default();
+		let shared_cache = SharedTrieCache::<sp_core::Blake2Hasher>::new(CacheSize::unlimited());
+		let local_cache = shared_cache.local_cache();
+
+		for i in 0..200 {
+			let (key, value) = &entries[(i * 37) % entries.len()];
+			let mut cache = local_cache.as_trie_db_cache(root);
+
+			{
+				let mut reference_trie_recorder = reference_recorder.as_trie_recorder(root);
+				let trie = TrieDBBuilder::<Layout>::new(&db, &root)
+					.with_recorder(&mut reference_trie_recorder)
+					.with_cache(&mut cache)
+					.build();
+				assert_eq!(trie.get(key).unwrap().as_ref(), Some(value));
+			}
+
+			{
+				let mut trie_recorder = size_only_recorder_provider.as_trie_recorder(root);
+				let trie = TrieDBBuilder::<Layout>::new(&db, &root)
+					.with_recorder(&mut trie_recorder)
+					.with_cache(&mut cache)
+					.build();
+				assert_eq!(trie.get(key).unwrap().as_ref(), Some(value));
+			}
+
+			assert_eq!(
+				reference_recorder.estimate_encoded_size(),
+				size_only_recorder_provider.estimate_encoded_size()
+			);
+		}
+	}
+
+	#[test]
+	fn recorder_equivalence_without_cache() {
+		let (db, root, entries) = create_trie();
+
+		let reference_recorder = Recorder::default();
+		let size_only_recorder_provider = SizeOnlyRecorderProvider::default();
+
+		for i in 0..200 {
+			let (key, value) = &entries[(i * 37) % entries.len()];
+
+			{
+				let mut reference_trie_recorder = reference_recorder.as_trie_recorder(root);
+				let trie = TrieDBBuilder::<Layout>::new(&db, &root)
+					.with_recorder(&mut reference_trie_recorder)
+					.build();
+				assert_eq!(trie.get(key).unwrap().as_ref(), Some(value));
+			}
+
+			{
+				let mut trie_recorder = size_only_recorder_provider.as_trie_recorder(root);
+				let trie = TrieDBBuilder::<Layout>::new(&db, &root)
+					.with_recorder(&mut trie_recorder)
+					.build();
+				assert_eq!(trie.get(key).unwrap().as_ref(), Some(value));
+			}
+
+			assert_eq!(
+				reference_recorder.estimate_encoded_size(),
+				size_only_recorder_provider.estimate_encoded_size()
+			);
+		}
+
+		size_only_recorder_provider.reset();
+		assert_eq!(size_only_recorder_provider.estimate_encoded_size(), 0);
+	}
+}
@@ -20,9 +20,63 @@ use crate::*;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
 
+/// The size of the page that is migrated by `lazy_migrate_inbound_page`.
+const MIGRATED_PAGE_SIZE: usize = 64 * 1024;
+
 benchmarks! {
 	set_config_with_u32 {}: update_resume_threshold(RawOrigin::Root, 100)
-	set_config_with_weight {}: update_weight_restrict_decay(RawOrigin::Root, Weight::from_parts(3_000_000, 0))
+
+	enqueue_xcmp_message {
+		let msg = vec![0u8; MaxXcmpMessageLenOf::<T>::get() as usize];
+		let msg = BoundedSlice::try_from(&msg[..]).unwrap();
+	}: {
+		T::XcmpQueue::enqueue_message(msg, 1000.into());
+	}
+
+	suspend_channel {
+		let para = 1000.into();
+	}: {
+		Pallet::<T>::suspend_channel(para);
+	} verify {
+		assert!(OutboundXcmpStatus::<T>::get()
+			.iter()
+			.any(|c| c.recipient == para && c.state == OutboundState::Suspended));
+	}
+
+	resume_channel {
+		let para = 1000.into();
+		Pallet::<T>::suspend_channel(para);
+	}: {
+		Pallet::<T>::resume_channel(para);
+	} verify {
+		assert!(OutboundXcmpStatus::<T>::get().iter().all(|c| c.recipient != para));
+	}
+
+	take_first_concatenated_xcm {
+		let xcm = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin; 100])).encode();
+		let mut data = &xcm[..];
+	}: {
+		Pallet::<T>::take_first_concatenated_xcm(&mut data).unwrap();
+	} verify {
+		assert!(data.is_empty());
+	}
+
+	lazy_migrate_inbound_page {
+		let sender = ParaId::from(1000);
+		let xcm = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin])).encode();
+		let page = xcm.repeat(MIGRATED_PAGE_SIZE / xcm.len());
+		migration::v3::InboundXcmpStatus::<T>::put(vec![migration::v3::InboundChannelDetails {
+			sender,
+			state: migration::v3::InboundState::Suspended,
+			message_metadata: vec![(1, XcmpMessageFormat::ConcatenatedVersionedXcm)],
+		}]);
+		migration::v3::InboundXcmpMessages::<T>::insert(sender, 1, page);
+	}: {
+		assert!(migration::v3::lazy_migrate_inbound_queue::<T>());
+	} verify {
+		assert!(!migration::v3::InboundXcmpStatus::<T>::exists());
+		assert!(!migration::v3::InboundXcmpMessages::<T>::contains_key(sender, 1));
+	}
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
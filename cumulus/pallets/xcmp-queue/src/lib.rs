@@ -20,8 +20,14 @@
 //! * `XcmpMessageSource`
 //!
 //! Also provides an implementation of `SendXcm` which can be placed in a router tuple for relaying
-//! XCM over XCMP if the destination is `Parent/Parachain`. It requires an implementation of
-//! `XcmExecutor` for dispatching incoming XCM messages.
+//! XCM over XCMP if the destination is `Parent/Parachain`.
+//!
+//! Inbound XCMP pages are split into their individual XCMs which are then enqueued into the
+//! `XcmpQueue` (usually `pallet-message-queue`) with one queue per sibling. Executing them,
+//! including the servicing of overweight messages, is left to that queue. The number of messages
+//! queued per sibling drives the `Suspend` and `Resume` signals sent back over the channel. The
+//! `XcmpQueue` must report changes of a queue through [`OnQueueChanged`] and consult
+//! [`QueuePausedQuery`] for this to work.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -44,17 +50,16 @@ use cumulus_primitives_core::{
 	ParaId, XcmpMessageFormat, XcmpMessageHandler, XcmpMessageSource,
 };
 use frame_support::{
-	traits::{EnsureOrigin, Get},
-	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, Weight},
+	defensive,
+	traits::{EnqueueMessage, EnsureOrigin, Get, QueuePausedQuery},
+	weights::{Weight, WeightMeter},
+	BoundedSlice,
 };
+use pallet_message_queue::OnQueueChanged;
 use polkadot_runtime_common::xcm_sender::PriceForParachainDelivery;
-use rand_chacha::{
-	rand_core::{RngCore, SeedableRng},
-	ChaChaRng,
-};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_std::prelude::*;
 use xcm::{latest::prelude::*, VersionedXcm, WrapVersion, MAX_XCM_DECODE_DEPTH};
 use xcm_executor::traits::ConvertOrigin;
 
@@ -63,19 +68,16 @@ pub use pallet::*;
 /// Index used to identify overweight XCMs.
 pub type OverweightIndex = u64;
 
-const LOG_TARGET: &str = "xcmp_queue";
-const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
+/// The maximal length of an XCMP message that can be enqueued into the `XcmpQueue`.
+pub type MaxXcmpMessageLenOf<T> =
+	<<T as Config>::XcmpQueue as EnqueueMessage<ParaId>>::MaxMessageLen;
 
-// Maximum amount of messages to process per block. This is a temporary measure until we properly
-// account for proof size weights.
-const MAX_MESSAGES_PER_BLOCK: u8 = 10;
-// Maximum amount of messages that can exist in the overweight queue at any given time.
-const MAX_OVERWEIGHT_MESSAGES: u32 = 1000;
+const LOG_TARGET: &str = "xcmp_queue";
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, BoundedBTreeSet};
 	use frame_system::pallet_prelude::*;
 
 	#[pallet::pallet]
@@ -87,17 +89,24 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		/// Something to execute an XCM message. We need this to service the XCMoXCMP queue.
-		type XcmExecutor: ExecuteXcm<Self::RuntimeCall>;
-
 		/// Information on the avaialble XCMP channels.
 		type ChannelInfo: GetChannelInfo;
 
 		/// Means of converting an `Xcm` into a `VersionedXcm`.
 		type VersionWrapper: WrapVersion;
 
-		/// The origin that is allowed to execute overweight messages.
-		type ExecuteOverweightOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Enqueue an inbound horizontal message for later processing.
+		///
+		/// This defines the maximal message length via [`crate::MaxXcmpMessageLenOf`]. The pallet
+		/// assumes that this hook will eventually process all the pushed messages.
+		type XcmpQueue: EnqueueMessage<ParaId>;
+
+		/// The maximum number of inbound XCMP channels that can be suspended simultaneously.
+		///
+		/// Any further channel suspensions will fail and messages may get dropped without further
+		/// notice. Choosing a high value (1000) is okay.
+		#[pallet::constant]
+		type MaxInboundSuspended: Get<u32>;
 
 		/// The origin that is allowed to resume or suspend the XCMP queue.
 		type ControllerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -116,49 +125,28 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_idle(_now: BlockNumberFor<T>, max_weight: Weight) -> Weight {
-			// on_idle processes additional messages with any remaining block weight.
-			Self::service_xcmp_queue(max_weight)
+			// Use any remaining block weight to move the messages that were queued before the
+			// upgrade to v4 into the `XcmpQueue`.
+			let mut meter = WeightMeter::with_limit(max_weight);
+			// Once the old queue is gone, only the check for leftovers is paid for.
+			let check_weight = T::DbWeight::get().reads(migration::v3::PENDING_CHECK_READS);
+			if meter.try_consume(check_weight).is_err() ||
+				!migration::v3::has_pending_inbound_queue::<T>()
+			{
+				return meter.consumed()
+			}
+			let page_weight = T::WeightInfo::lazy_migrate_inbound_page();
+			while meter.try_consume(page_weight).is_ok() {
+				if !migration::v3::lazy_migrate_inbound_queue::<T>() {
+					break
+				}
+			}
+			meter.consumed()
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Services a single overweight XCM.
-		///
-		/// - `origin`: Must pass `ExecuteOverweightOrigin`.
-		/// - `index`: The index of the overweight XCM to service
-		/// - `weight_limit`: The amount of weight that XCM execution may take.
-		///
-		/// Errors:
-		/// - `BadOverweightIndex`: XCM under `index` is not found in the `Overweight` storage map.
-		/// - `BadXcm`: XCM under `index` cannot be properly decoded into a valid XCM format.
-		/// - `WeightOverLimit`: XCM execution may use greater `weight_limit`.
-		///
-		/// Events:
-		/// - `OverweightServiced`: On success.
-		#[pallet::call_index(0)]
-		#[pallet::weight((weight_limit.saturating_add(Weight::from_parts(1_000_000, 0)), DispatchClass::Operational))]
-		pub fn service_overweight(
-			origin: OriginFor<T>,
-			index: OverweightIndex,
-			weight_limit: Weight,
-		) -> DispatchResultWithPostInfo {
-			T::ExecuteOverweightOrigin::ensure_origin(origin)?;
-
-			let (sender, sent_at, data) =
-				Overweight::<T>::get(index).ok_or(Error::<T>::BadOverweightIndex)?;
-			let xcm = VersionedXcm::<T::RuntimeCall>::decode_all_with_depth_limit(
-				MAX_XCM_DECODE_DEPTH,
-				&mut data.as_slice(),
-			)
-			.map_err(|_| Error::<T>::BadXcm)?;
-			let used = Self::handle_xcm_message(sender, sent_at, xcm, weight_limit)
-				.map_err(|_| Error::<T>::WeightOverLimit)?;
-			Overweight::<T>::remove(index);
-			Self::deposit_event(Event::OverweightServiced { index, used });
-			Ok(Some(used.saturating_add(Weight::from_parts(1_000_000, 0))).into())
-		}
-
 		/// Suspends all XCM executions for the XCMP queue, regardless of the sender's origin.
 		///
 		/// - `origin`: Must pass `ControllerOrigin`.
@@ -187,8 +175,8 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Overwrites the number of pages of messages which must be in the queue for the other side
-		/// to be told to suspend their sending.
+		/// Overwrites the number of queued messages which must be reached for the other side to
+		/// be told to suspend their sending.
 		///
 		/// - `origin`: Must pass `Root`.
 		/// - `new`: Desired value for `QueueConfigData.suspend_value`
@@ -201,8 +189,8 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Overwrites the number of pages of messages which must be in the queue after which we
-		/// drop any further messages from the channel.
+		/// Overwrites the number of queued messages after which we drop any further messages
+		/// from the channel.
 		///
 		/// - `origin`: Must pass `Root`.
 		/// - `new`: Desired value for `QueueConfigData.drop_threshold`
@@ -215,7 +203,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Overwrites the number of pages of messages which the queue must be reduced to before it
+		/// Overwrites the number of queued messages which the queue must be reduced to before it
 		/// signals that message sending may recommence after it has been suspended.
 		///
 		/// - `origin`: Must pass `Root`.
@@ -228,76 +216,13 @@ pub mod pallet {
 
 			Ok(())
 		}
-
-		/// Overwrites the amount of remaining weight under which we stop processing messages.
-		///
-		/// - `origin`: Must pass `Root`.
-		/// - `new`: Desired value for `QueueConfigData.threshold_weight`
-		#[pallet::call_index(6)]
-		#[pallet::weight((T::WeightInfo::set_config_with_weight(), DispatchClass::Operational,))]
-		pub fn update_threshold_weight(origin: OriginFor<T>, new: Weight) -> DispatchResult {
-			ensure_root(origin)?;
-			QueueConfig::<T>::mutate(|data| data.threshold_weight = new);
-
-			Ok(())
-		}
-
-		/// Overwrites the speed to which the available weight approaches the maximum weight.
-		/// A lower number results in a faster progression. A value of 1 makes the entire weight
-		/// available initially.
-		///
-		/// - `origin`: Must pass `Root`.
-		/// - `new`: Desired value for `QueueConfigData.weight_restrict_decay`.
-		#[pallet::call_index(7)]
-		#[pallet::weight((T::WeightInfo::set_config_with_weight(), DispatchClass::Operational,))]
-		pub fn update_weight_restrict_decay(origin: OriginFor<T>, new: Weight) -> DispatchResult {
-			ensure_root(origin)?;
-			QueueConfig::<T>::mutate(|data| data.weight_restrict_decay = new);
-
-			Ok(())
-		}
-
-		/// Overwrite the maximum amount of weight any individual message may consume.
-		/// Messages above this weight go into the overweight queue and may only be serviced
-		/// explicitly.
-		///
-		/// - `origin`: Must pass `Root`.
-		/// - `new`: Desired value for `QueueConfigData.xcmp_max_individual_weight`.
-		#[pallet::call_index(8)]
-		#[pallet::weight((T::WeightInfo::set_config_with_weight(), DispatchClass::Operational,))]
-		pub fn update_xcmp_max_individual_weight(
-			origin: OriginFor<T>,
-			new: Weight,
-		) -> DispatchResult {
-			ensure_root(origin)?;
-			QueueConfig::<T>::mutate(|data| data.xcmp_max_individual_weight = new);
-
-			Ok(())
-		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Some XCM was executed ok.
-		Success { message_hash: XcmHash, message_id: XcmHash, weight: Weight },
-		/// Some XCM failed.
-		Fail { message_hash: XcmHash, message_id: XcmHash, error: XcmError, weight: Weight },
-		/// Bad XCM version used.
-		BadVersion { message_hash: XcmHash },
-		/// Bad XCM format used.
-		BadFormat { message_hash: XcmHash },
 		/// An HRMP message was sent to a sibling parachain.
 		XcmpMessageSent { message_hash: XcmHash },
-		/// An XCM exceeded the individual message weight budget.
-		OverweightEnqueued {
-			sender: ParaId,
-			sent_at: RelayBlockNumber,
-			index: OverweightIndex,
-			required: Weight,
-		},
-		/// An XCM from the overweight queue was executed with the given actual weight used.
-		OverweightServiced { index: OverweightIndex, used: Weight },
 	}
 
 	#[pallet::error]
@@ -308,28 +233,16 @@ pub mod pallet {
 		BadXcmOrigin,
 		/// Bad XCM data.
 		BadXcm,
-		/// Bad overweight index.
-		BadOverweightIndex,
-		/// Provided weight is possibly not enough to execute the message.
-		WeightOverLimit,
 	}
 
-	/// Status of the inbound XCMP channels.
-	#[pallet::storage]
-	pub(super) type InboundXcmpStatus<T: Config> =
-		StorageValue<_, Vec<InboundChannelDetails>, ValueQuery>;
-
-	/// Inbound aggregate XCMP messages. It can only be one per ParaId/block.
+	/// The suspended inbound XCMP channels. All others are not suspended.
+	///
+	/// This is a `StorageValue` instead of a `StorageMap` since we expect multiple reads per block
+	/// to different keys with a one byte payload. The access to `BoundedBTreeSet` will be cached
+	/// within the block and therefore only included once in the proof size.
 	#[pallet::storage]
-	pub(super) type InboundXcmpMessages<T: Config> = StorageDoubleMap<
-		_,
-		Blake2_128Concat,
-		ParaId,
-		Twox64Concat,
-		RelayBlockNumber,
-		Vec<u8>,
-		ValueQuery,
-	>;
+	pub(super) type InboundXcmpSuspended<T: Config> =
+		StorageValue<_, BoundedBTreeSet<ParaId, T::MaxInboundSuspended>, ValueQuery>;
 
 	/// The non-empty XCMP channels in order of becoming non-empty, and the index of the first
 	/// and last outbound message. If the two indices are equal, then it indicates an empty
@@ -356,50 +269,17 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type QueueConfig<T: Config> = StorageValue<_, QueueConfigData, ValueQuery>;
 
-	/// The messages that exceeded max individual message weight budget.
-	///
-	/// These message stay in this storage map until they are manually dispatched via
-	/// `service_overweight`.
-	#[pallet::storage]
-	pub(super) type Overweight<T: Config> =
-		CountedStorageMap<_, Twox64Concat, OverweightIndex, (ParaId, RelayBlockNumber, Vec<u8>)>;
-
-	/// The number of overweight messages ever recorded in `Overweight`. Also doubles as the next
-	/// available free overweight index.
-	#[pallet::storage]
-	pub(super) type OverweightCount<T: Config> = StorageValue<_, OverweightIndex, ValueQuery>;
-
 	/// Whether or not the XCMP queue is suspended from executing incoming XCMs or not.
 	#[pallet::storage]
 	pub(super) type QueueSuspended<T: Config> = StorageValue<_, bool, ValueQuery>;
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub enum InboundState {
-	Ok,
-	Suspended,
-}
-
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum OutboundState {
 	Ok,
 	Suspended,
 }
 
-/// Struct containing detailed information about the inbound channel.
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, TypeInfo)]
-pub struct InboundChannelDetails {
-	/// The `ParaId` of the parachain that this channel is connected with.
-	sender: ParaId,
-	/// The state of the channel.
-	state: InboundState,
-	/// The ordered metadata of each inbound message.
-	///
-	/// Contains info about the relay block number that the message was sent at, and the format
-	/// of the incoming message.
-	message_metadata: Vec<(RelayBlockNumber, XcmpMessageFormat)>,
-}
-
 /// Struct containing detailed information about the outbound channel.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub struct OutboundChannelDetails {
@@ -436,41 +316,22 @@ impl OutboundChannelDetails {
 		self
 	}
 }
-
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct QueueConfigData {
-	/// The number of pages of messages which must be in the queue for the other side to be told to
+	/// The number of messages which must be queued for a sibling for the other side to be told to
 	/// suspend their sending.
 	suspend_threshold: u32,
-	/// The number of pages of messages which must be in the queue after which we drop any further
+	/// The number of messages which must be queued for a sibling after which we drop any further
 	/// messages from the channel.
 	drop_threshold: u32,
-	/// The number of pages of messages which the queue must be reduced to before it signals that
-	/// message sending may recommence after it has been suspended.
+	/// The number of messages which the queue of a sibling must be reduced to before it signals
+	/// that message sending may recommence after it has been suspended.
 	resume_threshold: u32,
-	/// The amount of remaining weight under which we stop processing messages.
-	threshold_weight: Weight,
-	/// The speed to which the available weight approaches the maximum weight. A lower number
-	/// results in a faster progression. A value of 1 makes the entire weight available initially.
-	weight_restrict_decay: Weight,
-	/// The maximum amount of weight any individual message may consume. Messages above this weight
-	/// go into the overweight queue and may only be serviced explicitly.
-	xcmp_max_individual_weight: Weight,
 }
 
 impl Default for QueueConfigData {
 	fn default() -> Self {
-		Self {
-			suspend_threshold: 2,
-			drop_threshold: 5,
-			resume_threshold: 1,
-			threshold_weight: Weight::from_parts(100_000, 0),
-			weight_restrict_decay: Weight::from_parts(2, 0),
-			xcmp_max_individual_weight: Weight::from_parts(
-				20u64 * WEIGHT_REF_TIME_PER_MILLIS,
-				DEFAULT_POV_SIZE,
-			),
-		}
+		Self { suspend_threshold: 256, drop_threshold: 512, resume_threshold: 64 }
 	}
 }
 
@@ -585,336 +446,89 @@ impl<T: Config> Pallet<T> {
 		Self::send_fragment(recipient, XcmpMessageFormat::ConcatenatedVersionedXcm, xcm)
 	}
 
-	fn create_shuffle(len: usize) -> Vec<usize> {
-		// Create a shuffled order for use to iterate through.
-		// Not a great random seed, but good enough for our purposes.
-		let seed = frame_system::Pallet::<T>::parent_hash();
-		let seed =
-			<[u8; 32]>::decode(&mut sp_runtime::traits::TrailingZeroInput::new(seed.as_ref()))
-				.expect("input is padded with zeroes; qed");
-		let mut rng = ChaChaRng::from_seed(seed);
-		let mut shuffled = (0..len).collect::<Vec<_>>();
-		for i in 0..len {
-			let j = (rng.next_u32() as usize) % len;
-			shuffled.as_mut_slice().swap(i, j);
-		}
-		shuffled
-	}
-
-	fn handle_blob_message(
-		_sender: ParaId,
-		_sent_at: RelayBlockNumber,
-		_blob: Vec<u8>,
-		_weight_limit: Weight,
-	) -> Result<Weight, bool> {
-		debug_assert!(false, "Blob messages not handled.");
-		Err(false)
-	}
-
-	fn handle_xcm_message(
-		sender: ParaId,
-		_sent_at: RelayBlockNumber,
-		xcm: VersionedXcm<T::RuntimeCall>,
-		max_weight: Weight,
-	) -> Result<Weight, XcmError> {
-		let message_hash = xcm.using_encoded(sp_io::hashing::blake2_256);
-		log::debug!("Processing XCMP-XCM: {:?}", &message_hash);
-		let (result, event) = match Xcm::<T::RuntimeCall>::try_from(xcm) {
-			Ok(xcm) => {
-				let location = (Parent, Parachain(sender.into()));
-				let mut message_id = message_hash;
-
-				match T::XcmExecutor::prepare_and_execute(
-					location,
-					xcm,
-					&mut message_id,
-					max_weight,
-					Weight::zero(),
-				) {
-					Outcome::Error(error) => (
-						Err(error),
-						Event::Fail { message_hash, message_id, error, weight: Weight::zero() },
-					),
-					Outcome::Complete(weight) =>
-						(Ok(weight), Event::Success { message_hash, message_id, weight }),
-					// As far as the caller is concerned, this was dispatched without error, so
-					// we just report the weight used.
-					Outcome::Incomplete(weight, error) =>
-						(Ok(weight), Event::Fail { message_hash, message_id, error, weight }),
-				}
-			},
-			Err(()) => (Err(XcmError::UnhandledXcmVersion), Event::BadVersion { message_hash }),
-		};
-		Self::deposit_event(event);
-		result
-	}
-
-	fn process_xcmp_message(
-		sender: ParaId,
-		(sent_at, format): (RelayBlockNumber, XcmpMessageFormat),
-		messages_processed: &mut u8,
-		max_weight: Weight,
-		max_individual_weight: Weight,
-	) -> (Weight, bool) {
-		let data = <InboundXcmpMessages<T>>::get(sender, sent_at);
-		let mut last_remaining_fragments;
-		let mut remaining_fragments = &data[..];
-		let mut weight_used = Weight::zero();
-		match format {
-			XcmpMessageFormat::ConcatenatedVersionedXcm => {
-				while !remaining_fragments.is_empty() &&
-					*messages_processed < MAX_MESSAGES_PER_BLOCK
-				{
-					last_remaining_fragments = remaining_fragments;
-					if let Ok(xcm) = VersionedXcm::<T::RuntimeCall>::decode_with_depth_limit(
-						MAX_XCM_DECODE_DEPTH,
-						&mut remaining_fragments,
-					) {
-						let weight = max_weight - weight_used;
-						*messages_processed += 1;
-						match Self::handle_xcm_message(sender, sent_at, xcm, weight) {
-							Ok(used) => weight_used = weight_used.saturating_add(used),
-							Err(XcmError::WeightLimitReached(required))
-								if required.any_gt(max_individual_weight) =>
-							{
-								let is_under_limit =
-									Overweight::<T>::count() < MAX_OVERWEIGHT_MESSAGES;
-								weight_used.saturating_accrue(T::DbWeight::get().reads(1));
-								if is_under_limit {
-									// overweight - add to overweight queue and continue with
-									// message execution consuming the message.
-									let msg_len = last_remaining_fragments
-										.len()
-										.saturating_sub(remaining_fragments.len());
-									let overweight_xcm =
-										last_remaining_fragments[..msg_len].to_vec();
-									let index =
-										Self::stash_overweight(sender, sent_at, overweight_xcm);
-									let e = Event::OverweightEnqueued {
-										sender,
-										sent_at,
-										index,
-										required,
-									};
-									Self::deposit_event(e);
-								}
-							},
-							Err(XcmError::WeightLimitReached(required))
-								if required.all_lte(max_weight) =>
-							{
-								// That message didn't get processed this time because of being
-								// too heavy. We leave it around for next time and bail.
-								remaining_fragments = last_remaining_fragments;
-								break
-							},
-							Err(error) => {
-								log::error!(
-									"Failed to process XCMP-XCM message, caused by {:?}",
-									error
-								);
-								// Message looks invalid; don't attempt to retry
-							},
-						}
-					} else {
-						debug_assert!(false, "Invalid incoming XCMP message data");
-						remaining_fragments = &b""[..];
-					}
-				}
-			},
-			XcmpMessageFormat::ConcatenatedEncodedBlob => {
-				while !remaining_fragments.is_empty() {
-					last_remaining_fragments = remaining_fragments;
-
-					if let Ok(blob) = <Vec<u8>>::decode(&mut remaining_fragments) {
-						let weight = max_weight - weight_used;
-						*messages_processed += 1;
-						match Self::handle_blob_message(sender, sent_at, blob, weight) {
-							Ok(used) => weight_used = weight_used.saturating_add(used),
-							Err(true) => {
-								// That message didn't get processed this time because of being
-								// too heavy. We leave it around for next time and bail.
-								remaining_fragments = last_remaining_fragments;
-								break
-							},
-							Err(false) => {
-								// Message invalid; don't attempt to retry
-							},
-						}
-					} else {
-						debug_assert!(false, "Invalid incoming blob message data");
-						remaining_fragments = &b""[..];
-					}
-				}
-			},
-			XcmpMessageFormat::Signals => {
-				debug_assert!(false, "All signals are handled immediately; qed");
-				remaining_fragments = &b""[..];
-			},
-		}
-		let is_empty = remaining_fragments.is_empty();
-		if is_empty {
-			<InboundXcmpMessages<T>>::remove(sender, sent_at);
-		} else {
-			<InboundXcmpMessages<T>>::insert(sender, sent_at, remaining_fragments);
-		}
-		(weight_used, is_empty)
-	}
-
-	/// Puts a given XCM into the list of overweight messages, allowing it to be executed later.
-	fn stash_overweight(
-		sender: ParaId,
-		sent_at: RelayBlockNumber,
-		xcm: Vec<u8>,
-	) -> OverweightIndex {
-		let index = OverweightCount::<T>::mutate(|count| {
-			let index = *count;
-			*count += 1;
-			index
-		});
-
-		Overweight::<T>::insert(index, (sender, sent_at, xcm));
-		index
+	/// Split off the first XCM of a `ConcatenatedVersionedXcm` page and return its encoding.
+	///
+	/// `data` is advanced past the message. Fails if the remaining data does not start with a
+	/// valid `VersionedXcm`, in which case the rest of the page cannot be split any further.
+	pub(crate) fn take_first_concatenated_xcm<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], ()> {
+		let input = *data;
+		VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, data).map_err(|_| ())?;
+		Ok(&input[..input.len() - data.len()])
 	}
 
-	/// Service the incoming XCMP message queue attempting to execute up to `max_weight` execution
-	/// weight of messages.
-	///
-	/// Channels are first shuffled and then processed in this random one page at a time, order over
-	/// and over until either `max_weight` is exhausted or no channel has messages that can be
-	/// processed any more.
-	///
-	/// There are two obvious "modes" that we could apportion `max_weight`: one would be to attempt
-	/// to spend it all on the first channel's first page, then use the leftover (if any) for the
-	/// second channel's first page and so on until finally we cycle back and the process messages
-	/// on the first channel's second page &c. The other mode would be to apportion only `1/N` of
-	/// `max_weight` for the first page (where `N` could be, perhaps, the number of channels to
-	/// service, using the remainder plus the next `1/N` for the next channel's page &c.
+	/// Enqueue all XCMs of a `ConcatenatedVersionedXcm` page into the queue of `sender`.
 	///
-	/// Both modes have good qualities, the first ensures that a channel with a large message (over
-	/// `1/N` does not get indefinitely blocked if other channels have continuous, light traffic.
-	/// The second is fairer, and ensures that channels with continuous light messages don't suffer
-	/// high latency.
-	///
-	/// The following code is a hybrid solution; we have a concept of `weight_available` which
-	/// incrementally approaches `max_weight` as more channels are attempted to be processed. We use
-	/// the parameter `weight_restrict_decay` to control the speed with which `weight_available`
-	/// approaches `max_weight`, with `0` being strictly equivalent to the first aforementioned
-	/// mode, and `N` approximating the second. A reasonable parameter may be `1`, which makes
-	/// half of the `max_weight` available for the first page, then a quarter plus the remainder
-	/// for the second &c. though empirical and or practical factors may give rise to adjusting it
-	/// further.
-	fn service_xcmp_queue(max_weight: Weight) -> Weight {
-		let suspended = QueueSuspended::<T>::get();
-		let mut messages_processed = 0;
-
-		let mut status = <InboundXcmpStatus<T>>::get(); // <- sorted.
-		if status.is_empty() {
-			return Weight::zero()
-		}
-
-		let QueueConfigData {
-			resume_threshold,
-			threshold_weight,
-			weight_restrict_decay,
-			xcmp_max_individual_weight,
-			..
-		} = <QueueConfig<T>>::get();
-
-		let mut shuffled = Self::create_shuffle(status.len());
-		let mut weight_used = Weight::zero();
-		let mut weight_available = Weight::zero();
-
-		// We don't want the possibility of a chain sending a series of really heavy messages and
-		// tying up the block's execution time from other chains. Therefore we execute any remaining
-		// messages in a random order.
-		// Order within a single channel will always be preserved, however this does mean that
-		// relative order between channels may not. The result is that chains which tend to send
-		// fewer, lighter messages will generally have a lower latency than chains which tend to
-		// send more, heavier messages.
-
-		let mut shuffle_index = 0;
-		while shuffle_index < shuffled.len() &&
-			max_weight.saturating_sub(weight_used).all_gte(threshold_weight) &&
-			messages_processed < MAX_MESSAGES_PER_BLOCK
-		{
-			let index = shuffled[shuffle_index];
-			let sender = status[index].sender;
-			let sender_origin = T::ControllerOriginConverter::convert_origin(
-				(Parent, Parachain(sender.into())),
-				OriginKind::Superuser,
-			);
-			let is_controller = sender_origin
-				.map_or(false, |origin| T::ControllerOrigin::try_origin(origin).is_ok());
-
-			if suspended && !is_controller {
-				shuffle_index += 1;
-				continue
-			}
-
-			if weight_available != max_weight {
-				// Get incrementally closer to freeing up max_weight for message execution over the
-				// first round. For the second round we unlock all weight. If we come close enough
-				// on the first round to unlocking everything, then we do so.
-				if shuffle_index < status.len() {
-					weight_available +=
-						(max_weight - weight_available) / (weight_restrict_decay.ref_time() + 1);
-					if (weight_available + threshold_weight).any_gt(max_weight) {
-						weight_available = max_weight;
-					}
-				} else {
-					weight_available = max_weight;
-				}
-			}
+	/// Tells the sender to suspend its channel once `suspend_threshold` messages are queued and
+	/// drops all messages that arrive after `drop_threshold` was reached.
+	pub(crate) fn enqueue_xcmp_page(sender: ParaId, mut data: &[u8]) -> Weight {
+		let QueueConfigData { suspend_threshold, drop_threshold, .. } = <QueueConfig<T>>::get();
+		let mut queued = T::XcmpQueue::footprint(sender).count;
+		let mut weight = T::DbWeight::get().reads(2);
+		let mut suspend_requested = false;
+
+		while !data.is_empty() {
+			weight.saturating_accrue(T::WeightInfo::take_first_concatenated_xcm());
+			let Ok(xcm) = Self::take_first_concatenated_xcm(&mut data) else {
+				defensive!("Invalid incoming XCMP message data; dropping the rest of the page");
+				break
+			};
 
-			let weight_processed = if status[index].message_metadata.is_empty() {
-				debug_assert!(false, "channel exists in status; there must be messages; qed");
-				Weight::zero()
-			} else {
-				// Process up to one block's worth for now.
-				let weight_remaining = weight_available.saturating_sub(weight_used);
-				let (weight_processed, is_empty) = Self::process_xcmp_message(
+			if queued >= drop_threshold as u64 {
+				log::error!(
+					target: LOG_TARGET,
+					"XCMP queue for sibling {:?} is full; dropping messages",
 					sender,
-					status[index].message_metadata[0],
-					&mut messages_processed,
-					weight_remaining,
-					xcmp_max_individual_weight,
 				);
-				if is_empty {
-					status[index].message_metadata.remove(0);
-				}
-				weight_processed
-			};
-			weight_used += weight_processed;
-
-			if status[index].message_metadata.len() as u32 <= resume_threshold &&
-				status[index].state == InboundState::Suspended
-			{
-				// Resume
-				let r = Self::send_signal(sender, ChannelSignal::Resume);
-				debug_assert!(r.is_ok(), "WARNING: Failed sending resume into suspended channel");
-				status[index].state = InboundState::Ok;
+				break
+			}
+			if queued >= suspend_threshold as u64 && !suspend_requested {
+				suspend_requested = true;
+				weight.saturating_accrue(Self::suspend_inbound_channel(sender));
 			}
 
-			// If there are more and we're making progress, we process them after we've given the
-			// other channels a look in. If we've still not unlocked all weight, then we set them
-			// up for processing a second time anyway.
-			if !status[index].message_metadata.is_empty() &&
-				(weight_processed.any_gt(Weight::zero()) || weight_available != max_weight)
-			{
-				if shuffle_index + 1 == shuffled.len() {
-					// Only this queue left. Just run around this loop once more.
-					continue
-				}
-				shuffled.push(index);
+			match BoundedSlice::<u8, MaxXcmpMessageLenOf<T>>::try_from(xcm) {
+				Ok(xcm) => {
+					T::XcmpQueue::enqueue_message(xcm, sender);
+					weight.saturating_accrue(T::WeightInfo::enqueue_xcmp_message());
+					queued += 1;
+				},
+				Err(_) => log::error!(
+					target: LOG_TARGET,
+					"Dropping XCMP message of {} bytes from sibling {:?}: too big to be enqueued",
+					xcm.len(),
+					sender,
+				),
 			}
-			shuffle_index += 1;
 		}
 
-		// Only retain the senders that have non-empty queues.
-		status.retain(|item| !item.message_metadata.is_empty());
+		weight
+	}
 
-		<InboundXcmpStatus<T>>::put(status);
-		weight_used
+	/// Record the inbound channel of `sender` as suspended and signal this to the sender.
+	fn suspend_inbound_channel(sender: ParaId) -> Weight {
+		let mut suspended = <InboundXcmpSuspended<T>>::get();
+		match suspended.try_insert(sender) {
+			Ok(true) => {
+				<InboundXcmpSuspended<T>>::put(suspended);
+				let r = Self::send_signal(sender, ChannelSignal::Suspend);
+				if r.is_err() {
+					log::warn!(
+						target: LOG_TARGET,
+						"Attempt to suspend channel failed. Messages may be dropped."
+					);
+				}
+				T::DbWeight::get().reads_writes(2, 3)
+			},
+			Ok(false) => T::DbWeight::get().reads(1),
+			Err(_) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Too many suspended inbound channels; cannot suspend {:?}. Messages may be dropped.",
+					sender,
+				);
+				T::DbWeight::get().reads(1)
+			},
+		}
 	}
 
 	fn suspend_channel(target: ParaId) {
@@ -952,13 +566,13 @@ impl<T: Config> Pallet<T> {
 impl<T: Config> XcmpMessageHandler for Pallet<T> {
 	fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
 		iter: I,
-		max_weight: Weight,
+		_max_weight: Weight,
 	) -> Weight {
-		let mut status = <InboundXcmpStatus<T>>::get();
-
-		let QueueConfigData { suspend_threshold, drop_threshold, .. } = <QueueConfig<T>>::get();
+		// The messages were already taken from the relay chain and must not be rejected, hence the
+		// weight limit is not enforced here. Executing them is metered by the `XcmpQueue`.
+		let mut weight_used = Weight::zero();
 
-		for (sender, sent_at, data) in iter {
+		for (sender, _sent_at, data) in iter {
 			// Figure out the message format.
 			let mut data_ref = data;
 			let format = match XcmpMessageFormat::decode_with_depth_limit(
@@ -967,60 +581,71 @@ impl<T: Config> XcmpMessageHandler for Pallet<T> {
 			) {
 				Ok(f) => f,
 				Err(_) => {
-					debug_assert!(false, "Unknown XCMP message format. Silently dropping message");
+					defensive!("Unknown XCMP message format. Silently dropping message");
 					continue
 				},
 			};
-			if format == XcmpMessageFormat::Signals {
-				while !data_ref.is_empty() {
-					use ChannelSignal::*;
-					match ChannelSignal::decode(&mut data_ref) {
-						Ok(Suspend) => Self::suspend_channel(sender),
-						Ok(Resume) => Self::resume_channel(sender),
-						Err(_) => break,
-					}
-				}
-			} else {
-				// Record the fact we received it.
-				match status.binary_search_by_key(&sender, |item| item.sender) {
-					Ok(i) => {
-						let count = status[i].message_metadata.len();
-						if count as u32 >= suspend_threshold && status[i].state == InboundState::Ok
-						{
-							status[i].state = InboundState::Suspended;
-							let r = Self::send_signal(sender, ChannelSignal::Suspend);
-							if r.is_err() {
-								log::warn!(
-									"Attempt to suspend channel failed. Messages may be dropped."
-								);
-							}
-						}
-						if (count as u32) < drop_threshold {
-							status[i].message_metadata.push((sent_at, format));
-						} else {
-							debug_assert!(
-								false,
-								"XCMP channel queue full. Silently dropping message"
-							);
+
+			match format {
+				XcmpMessageFormat::Signals =>
+					while !data_ref.is_empty() {
+						use ChannelSignal::*;
+						match ChannelSignal::decode(&mut data_ref) {
+							Ok(Suspend) => {
+								Self::suspend_channel(sender);
+								weight_used.saturating_accrue(T::WeightInfo::suspend_channel());
+							},
+							Ok(Resume) => {
+								Self::resume_channel(sender);
+								weight_used.saturating_accrue(T::WeightInfo::resume_channel());
+							},
+							Err(_) => break,
 						}
 					},
-					Err(_) => status.push(InboundChannelDetails {
-						sender,
-						state: InboundState::Ok,
-						message_metadata: vec![(sent_at, format)],
-					}),
-				}
-				// Queue the payload for later execution.
-				<InboundXcmpMessages<T>>::insert(sender, sent_at, data_ref);
+				XcmpMessageFormat::ConcatenatedVersionedXcm =>
+					weight_used.saturating_accrue(Self::enqueue_xcmp_page(sender, data_ref)),
+				XcmpMessageFormat::ConcatenatedEncodedBlob => {
+					defensive!("Blob messages are not supported. Silently dropping message");
+				},
 			}
+		}
 
-			// Optimization note; it would make sense to execute messages immediately if
-			// `status.is_empty()` here.
+		weight_used
+	}
+}
+
+impl<T: Config> OnQueueChanged<ParaId> for Pallet<T> {
+	// Resume the channel once its queue drained down to the `resume_threshold`.
+	fn on_queue_changed(para: ParaId, items_count: u64, _items_size: u64) {
+		let QueueConfigData { resume_threshold, .. } = <QueueConfig<T>>::get();
+		if items_count > resume_threshold as u64 {
+			return
 		}
-		status.sort();
-		<InboundXcmpStatus<T>>::put(status);
 
-		Self::service_xcmp_queue(max_weight)
+		let mut suspended = <InboundXcmpSuspended<T>>::get();
+		if suspended.remove(&para) {
+			<InboundXcmpSuspended<T>>::put(suspended);
+			let r = Self::send_signal(para, ChannelSignal::Resume);
+			debug_assert!(r.is_ok(), "WARNING: Failed sending resume into suspended channel");
+		}
+	}
+}
+
+impl<T: Config> QueuePausedQuery<ParaId> for Pallet<T> {
+	fn is_paused(para: &ParaId) -> bool {
+		if !QueueSuspended::<T>::get() {
+			return false
+		}
+
+		// Only the controller may still have its messages executed while the queue is suspended.
+		let sender_origin = T::ControllerOriginConverter::convert_origin(
+			(Parent, Parachain((*para).into())),
+			OriginKind::Superuser,
+		);
+		let is_controller =
+			sender_origin.map_or(false, |origin| T::ControllerOrigin::try_origin(origin).is_ok());
+
+		!is_controller
 	}
 }
 
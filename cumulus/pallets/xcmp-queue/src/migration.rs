@@ -16,7 +16,7 @@
 
 //! A module that is responsible for migration of storage.
 
-use crate::{Config, Overweight, Pallet, QueueConfig, DEFAULT_POV_SIZE};
+use crate::{Config, OverweightIndex, Pallet, QueueConfig, QueueConfigData};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{OnRuntimeUpgrade, StorageVersion},
@@ -24,7 +24,9 @@ use frame_support::{
 };
 
 /// The current storage version.
-pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
+pub(crate) const DEFAULT_POV_SIZE: u64 = 64 * 1024; // 64 KB
 
 /// Migrates the pallet storage to the most recent version.
 pub struct Migration<T: Config>(PhantomData<T>);
@@ -45,8 +47,36 @@ impl<T: Config> OnRuntimeUpgrade for Migration<T> {
 			weight.saturating_accrue(T::DbWeight::get().writes(1));
 		}
 
+		if StorageVersion::get::<Pallet<T>>() == 3 {
+			weight.saturating_accrue(migrate_to_v4::<T>());
+			StorageVersion::new(4).put::<Pallet<T>>();
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+
 		weight
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<sp_std::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(StorageVersion::get::<Pallet<T>>().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: sp_std::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let pre_version: StorageVersion = Decode::decode(&mut &state[..]).map_err(|_| {
+			"the state parameter should be something that was generated by pre_upgrade"
+		})?;
+
+		ensure!(StorageVersion::get::<Pallet<T>>() == STORAGE_VERSION, "wrong storage version");
+		if pre_version < 4 {
+			// A v3 config that was not migrated decodes into bogus thresholds and halts XCMP.
+			ensure!(
+				QueueConfig::<T>::get() == QueueConfigData::default(),
+				"the thresholds were not reset to the v4 defaults"
+			);
+		}
+		Ok(())
+	}
 }
 
 mod v1 {
@@ -77,14 +107,190 @@ mod v1 {
 	}
 }
 
+/// The storage layout of `QueueConfig` in v2 and v3.
+pub mod v2 {
+	use super::*;
+	use codec::{Decode, Encode};
+
+	#[derive(Encode, Decode, Debug)]
+	pub struct QueueConfigData {
+		pub suspend_threshold: u32,
+		pub drop_threshold: u32,
+		pub resume_threshold: u32,
+		pub threshold_weight: Weight,
+		pub weight_restrict_decay: Weight,
+		pub xcmp_max_individual_weight: Weight,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type QueueConfig<T: Config> = StorageValue<Pallet<T>, QueueConfigData>;
+}
+
+/// The inbound queue of v3, which is drained lazily into the `XcmpQueue` since v4.
+pub mod v3 {
+	use super::*;
+	use crate::{MaxXcmpMessageLenOf, LOG_TARGET};
+	use cumulus_primitives_core::{
+		relay_chain::BlockNumber as RelayBlockNumber, ParaId, XcmpMessageFormat,
+	};
+	use frame_support::{traits::EnqueueMessage, BoundedSlice};
+	use sp_runtime::RuntimeDebug;
+	use sp_std::prelude::*;
+
+	/// Status of the inbound XCMP channels.
+	#[frame_support::storage_alias]
+	pub(crate) type InboundXcmpStatus<T: Config> =
+		StorageValue<Pallet<T>, Vec<InboundChannelDetails>, ValueQuery>;
+
+	/// Inbound aggregate XCMP messages. It can only be one per ParaId/block.
+	#[frame_support::storage_alias]
+	pub(crate) type InboundXcmpMessages<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		ParaId,
+		Twox64Concat,
+		RelayBlockNumber,
+		Vec<u8>,
+		ValueQuery,
+	>;
+
+	/// The messages that exceeded max individual message weight budget.
+	#[frame_support::storage_alias]
+	pub(crate) type Overweight<T: Config> = CountedStorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		OverweightIndex,
+		(ParaId, RelayBlockNumber, Vec<u8>),
+	>;
+
+	/// The number of overweight messages ever recorded in `Overweight`.
+	#[frame_support::storage_alias]
+	pub(crate) type OverweightCount<T: Config> =
+		StorageValue<Pallet<T>, OverweightIndex, ValueQuery>;
+
+	#[derive(
+		Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo,
+	)]
+	pub enum InboundState {
+		Ok,
+		Suspended,
+	}
+
+	/// Struct containing detailed information about the inbound channel.
+	#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, TypeInfo)]
+	pub struct InboundChannelDetails {
+		/// The `ParaId` of the parachain that this channel is connected with.
+		pub sender: ParaId,
+		/// The state of the channel.
+		pub state: InboundState,
+		/// The ordered metadata of each inbound message.
+		///
+		/// Contains info about the relay block number that the message was sent at, and the
+		/// format of the incoming message.
+		pub message_metadata: Vec<(RelayBlockNumber, XcmpMessageFormat)>,
+	}
+
+	/// Move one page of the v3 `InboundXcmpMessages` or, once those are all gone, one entry of the
+	/// v3 `Overweight` storage into the `XcmpQueue` of its sender.
+	///
+	/// The thresholds of the `QueueConfig` are not applied, so that no message is lost. Messages
+	/// which arrived after the upgrade may still be processed before the migrated ones.
+	///
+	/// Returns `false` once there is nothing left to migrate.
+	pub fn lazy_migrate_inbound_queue<T: Config>() -> bool {
+		let mut status = InboundXcmpStatus::<T>::get();
+		if let Some(channel) = status.first_mut() {
+			let sender = channel.sender;
+			if channel.state == InboundState::Suspended {
+				// The sender still considers its channel as suspended; make sure that it will be
+				// resumed once its queue got serviced.
+				channel.state = InboundState::Ok;
+				let _ = crate::InboundXcmpSuspended::<T>::try_mutate(|s| s.try_insert(sender));
+			}
+			let page =
+				(!channel.message_metadata.is_empty()).then(|| channel.message_metadata.remove(0));
+			if channel.message_metadata.is_empty() {
+				status.remove(0);
+			}
+			if status.is_empty() {
+				InboundXcmpStatus::<T>::kill();
+			} else {
+				InboundXcmpStatus::<T>::put(status);
+			}
+
+			if let Some((sent_at, format)) = page {
+				let data = InboundXcmpMessages::<T>::take(sender, sent_at);
+				if format == XcmpMessageFormat::ConcatenatedVersionedXcm {
+					enqueue_page::<T>(sender, &data);
+				} else {
+					log::warn!(
+						target: LOG_TARGET,
+						"Dropping a page of format {:?} from sibling {:?} during migration",
+						format,
+						sender,
+					);
+				}
+			}
+			return true
+		}
+
+		if let Some((index, (sender, _sent_at, data))) = Overweight::<T>::iter().next() {
+			Overweight::<T>::remove(index);
+			enqueue::<T>(sender, &data);
+			if Overweight::<T>::count() == 0 {
+				OverweightCount::<T>::kill();
+			}
+			return true
+		}
+
+		false
+	}
+
+	/// Whether any messages of the v3 inbound queue are left for [`lazy_migrate_inbound_queue`].
+	pub fn has_pending_inbound_queue<T: Config>() -> bool {
+		InboundXcmpStatus::<T>::exists() || Overweight::<T>::count() > 0
+	}
+
+	/// The number of storage reads done by [`has_pending_inbound_queue`].
+	pub const PENDING_CHECK_READS: u64 = 2;
+
+	fn enqueue_page<T: Config>(sender: ParaId, mut data: &[u8]) {
+		while !data.is_empty() {
+			match Pallet::<T>::take_first_concatenated_xcm(&mut data) {
+				Ok(xcm) => enqueue::<T>(sender, xcm),
+				Err(()) => {
+					log::error!(
+						target: LOG_TARGET,
+						"Dropping the undecodable rest of a page from sibling {:?} during migration",
+						sender,
+					);
+					break
+				},
+			}
+		}
+	}
+
+	fn enqueue<T: Config>(sender: ParaId, xcm: &[u8]) {
+		match BoundedSlice::<u8, MaxXcmpMessageLenOf<T>>::try_from(xcm) {
+			Ok(xcm) => T::XcmpQueue::enqueue_message(xcm, sender),
+			Err(_) => log::error!(
+				target: LOG_TARGET,
+				"Dropping XCMP message of {} bytes from sibling {:?}: too big to be enqueued",
+				xcm.len(),
+				sender,
+			),
+		}
+	}
+}
+
 /// Migrates `QueueConfigData` from v1 (using only reference time weights) to v2 (with
 /// 2D weights).
 ///
 /// NOTE: Only use this function if you know what you're doing. Default to using
 /// `migrate_to_latest`.
 pub fn migrate_to_v2<T: Config>() -> Weight {
-	let translate = |pre: v1::QueueConfigData| -> super::QueueConfigData {
-		super::QueueConfigData {
+	let translate = |pre: v1::QueueConfigData| -> v2::QueueConfigData {
+		v2::QueueConfigData {
 			suspend_threshold: pre.suspend_threshold,
 			drop_threshold: pre.drop_threshold,
 			resume_threshold: pre.resume_threshold,
@@ -97,7 +303,7 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 		}
 	};
 
-	if QueueConfig::<T>::translate(|pre| pre.map(translate)).is_err() {
+	if v2::QueueConfig::<T>::translate(|pre| pre.map(translate)).is_err() {
 		log::error!(
 			target: super::LOG_TARGET,
 			"unexpected error when performing translation of the QueueConfig type during storage upgrade to v2"
@@ -108,15 +314,30 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 }
 
 pub fn migrate_to_v3<T: Config>() -> Weight {
-	let overweight_messages = Overweight::<T>::initialize_counter() as u64;
+	let overweight_messages = v3::Overweight::<T>::initialize_counter() as u64;
 
 	T::DbWeight::get().reads_writes(overweight_messages, 1)
 }
 
+/// Migrates `QueueConfigData` from v3 to v4, which drops the weight related fields.
+///
+/// The thresholds changed their unit from pages to messages and are therefore reset to their
+/// defaults. The inbound messages of v3 are migrated lazily in `on_idle`.
+pub fn migrate_to_v4<T: Config>() -> Weight {
+	if v2::QueueConfig::<T>::exists() {
+		QueueConfig::<T>::put(QueueConfigData::default());
+	}
+
+	T::DbWeight::get().reads_writes(1, 1)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{new_test_ext, Test};
+	use crate::mock::{new_test_ext, EnqueuedMessages, Test};
+	use codec::Encode;
+	use cumulus_primitives_core::{ParaId, XcmpMessageFormat};
+	use xcm::{latest::prelude::*, VersionedXcm};
 
 	#[test]
 	fn test_migration_to_v2() {
@@ -137,7 +358,7 @@ mod tests {
 
 			migrate_to_v2::<Test>();
 
-			let v2 = crate::QueueConfig::<Test>::get();
+			let v2 = v2::QueueConfig::<Test>::get().unwrap();
 
 			assert_eq!(v1.suspend_threshold, v2.suspend_threshold);
 			assert_eq!(v1.drop_threshold, v2.drop_threshold);
@@ -147,4 +368,85 @@ mod tests {
 			assert_eq!(v1.xcmp_max_individual_weight, v2.xcmp_max_individual_weight.ref_time());
 		});
 	}
+
+	#[test]
+	fn test_migration_to_v4() {
+		let v3 = v2::QueueConfigData {
+			suspend_threshold: 5,
+			drop_threshold: 12,
+			resume_threshold: 3,
+			threshold_weight: Weight::from_parts(333_333, 0),
+			weight_restrict_decay: Weight::from_parts(1, 0),
+			xcmp_max_individual_weight: Weight::from_parts(10_000_000_000, DEFAULT_POV_SIZE),
+		};
+
+		new_test_ext().execute_with(|| {
+			v2::QueueConfig::<Test>::put(v3);
+			StorageVersion::new(3).put::<Pallet<Test>>();
+
+			Migration::<Test>::on_runtime_upgrade();
+
+			assert_eq!(QueueConfig::<Test>::get(), QueueConfigData::default());
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 4);
+		});
+	}
+
+	#[test]
+	fn on_idle_only_pays_for_the_check_once_migrated() {
+		use frame_support::traits::Hooks;
+
+		new_test_ext().execute_with(|| {
+			v3::Overweight::<Test>::insert(0, (ParaId::from(2000), 1, vec![]));
+			assert!(v3::has_pending_inbound_queue::<Test>());
+			let page_weight = <() as crate::WeightInfo>::lazy_migrate_inbound_page();
+			assert_eq!(crate::Pallet::<Test>::on_idle(1, Weight::MAX), page_weight * 2);
+
+			assert!(!v3::has_pending_inbound_queue::<Test>());
+			assert_eq!(crate::Pallet::<Test>::on_idle(2, Weight::MAX), Weight::zero());
+		});
+	}
+
+	#[test]
+	fn lazy_migration_drains_pages_and_overweight() {
+		new_test_ext().execute_with(|| {
+			let xcm = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin])).encode();
+			let mut page = xcm.clone();
+			page.extend(xcm.clone());
+
+			v3::InboundXcmpStatus::<Test>::put(vec![v3::InboundChannelDetails {
+				sender: 1000.into(),
+				state: v3::InboundState::Suspended,
+				message_metadata: vec![
+					(1, XcmpMessageFormat::ConcatenatedVersionedXcm),
+					(2, XcmpMessageFormat::ConcatenatedVersionedXcm),
+				],
+			}]);
+			v3::InboundXcmpMessages::<Test>::insert(ParaId::from(1000), 1, page.clone());
+			v3::InboundXcmpMessages::<Test>::insert(ParaId::from(1000), 2, xcm.clone());
+			v3::Overweight::<Test>::insert(0, (ParaId::from(2000), 1, xcm.clone()));
+			v3::OverweightCount::<Test>::put(1);
+
+			assert!(v3::lazy_migrate_inbound_queue::<Test>());
+			assert_eq!(EnqueuedMessages::get().len(), 2);
+			assert!(crate::InboundXcmpSuspended::<Test>::get().contains(&1000.into()));
+
+			assert!(v3::lazy_migrate_inbound_queue::<Test>());
+			assert!(v3::lazy_migrate_inbound_queue::<Test>());
+			assert!(!v3::lazy_migrate_inbound_queue::<Test>());
+
+			assert_eq!(
+				EnqueuedMessages::get(),
+				vec![
+					(1000.into(), xcm.clone()),
+					(1000.into(), xcm.clone()),
+					(1000.into(), xcm.clone()),
+					(2000.into(), xcm),
+				]
+			);
+			assert!(!v3::InboundXcmpStatus::<Test>::exists());
+			assert_eq!(v3::InboundXcmpMessages::<Test>::iter().count(), 0);
+			assert_eq!(v3::Overweight::<Test>::count(), 0);
+			assert!(!v3::OverweightCount::<Test>::exists());
+		});
+	}
 }
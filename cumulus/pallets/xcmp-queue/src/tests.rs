@@ -16,9 +16,17 @@
 use super::*;
 use cumulus_primitives_core::{ParaId, XcmpMessageHandler};
 use frame_support::{assert_noop, assert_ok};
-use mock::{new_test_ext, ParachainSystem, RuntimeCall, RuntimeOrigin, Test, XcmpQueue};
+use mock::{new_test_ext, EnqueuedMessages, ParachainSystem, RuntimeOrigin, Test, XcmpQueue};
 use sp_runtime::traits::BadOrigin;
 
+/// A page with `n` concatenated copies of a `ClearOrigin` XCM and the encoding of that XCM.
+fn xcm_page(n: usize) -> (Vec<u8>, Vec<u8>) {
+	let xcm = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin])).encode();
+	let mut page = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+	page.extend(xcm.repeat(n));
+	(page, xcm)
+}
+
 #[test]
 fn one_message_does_not_panic() {
 	new_test_ext().execute_with(|| {
@@ -31,7 +39,19 @@ fn one_message_does_not_panic() {
 }
 
 #[test]
-#[should_panic = "Invalid incoming blob message data"]
+fn xcm_messages_are_enqueued_individually() {
+	new_test_ext().execute_with(|| {
+		let (page, xcm) = xcm_page(3);
+		let messages = vec![(ParaId::from(1000), 1u32, page.as_slice())];
+
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_eq!(EnqueuedMessages::get(), vec![(1000.into(), xcm); 3]);
+	})
+}
+
+#[test]
+#[should_panic = "Defensive failure has been triggered!"]
 #[cfg(debug_assertions)]
 fn bad_message_is_handled() {
 	new_test_ext().execute_with(|| {
@@ -40,80 +60,68 @@ fn bad_message_is_handled() {
 			0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 0, 0, 0, 0, 0, 0, 0, 37, 0,
 			0, 0, 0, 0, 0, 0, 16, 0, 127, 147,
 		];
-		InboundXcmpMessages::<Test>::insert(ParaId::from(1000), 1, bad_data);
-		let format = XcmpMessageFormat::ConcatenatedEncodedBlob;
+		let mut page = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+		page.extend(bad_data);
+		let messages = vec![(ParaId::from(1000), 1u32, page.as_slice())];
+
 		// This should exit with an error.
-		XcmpQueue::process_xcmp_message(
-			1000.into(),
-			(1, format),
-			&mut 0,
-			Weight::from_parts(10_000_000_000, 0),
-			Weight::from_parts(10_000_000_000, 0),
-		);
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
 	});
 }
 
 /// Tests that a blob message is handled. Currently this isn't implemented and panics when debug
 /// assertions are enabled. When this feature is enabled, this test should be rewritten properly.
 #[test]
-#[should_panic = "Blob messages not handled."]
+#[should_panic = "Defensive failure has been triggered!"]
 #[cfg(debug_assertions)]
 fn handle_blob_message() {
 	new_test_ext().execute_with(|| {
-		let bad_data = vec![
-			1, 1, 1, 1, 3, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 64, 239,
-			139, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 0, 0, 0, 0, 0, 0, 0,
-			37, 0, 0, 0, 0, 0, 0, 0, 16, 0, 127, 147,
-		];
-		InboundXcmpMessages::<Test>::insert(ParaId::from(1000), 1, bad_data);
-		let format = XcmpMessageFormat::ConcatenatedEncodedBlob;
-		XcmpQueue::process_xcmp_message(
-			1000.into(),
-			(1, format),
-			&mut 0,
-			Weight::from_parts(10_000_000_000, 0),
-			Weight::from_parts(10_000_000_000, 0),
-		);
-	});
-}
+		let mut page = XcmpMessageFormat::ConcatenatedEncodedBlob.encode();
+		page.extend(vec![1u8, 2, 3].encode());
+		let messages = vec![(ParaId::from(1000), 1u32, page.as_slice())];
 
-#[test]
-#[should_panic = "Invalid incoming XCMP message data"]
-#[cfg(debug_assertions)]
-fn handle_invalid_data() {
-	new_test_ext().execute_with(|| {
-		let data = Xcm::<Test>(vec![]).encode();
-		InboundXcmpMessages::<Test>::insert(ParaId::from(1000), 1, data);
-		let format = XcmpMessageFormat::ConcatenatedVersionedXcm;
-		XcmpQueue::process_xcmp_message(
-			1000.into(),
-			(1, format),
-			&mut 0,
-			Weight::from_parts(10_000_000_000, 0),
-			Weight::from_parts(10_000_000_000, 0),
-		);
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
 	});
 }
 
 #[test]
-fn service_overweight_unknown() {
+fn xcm_messages_beyond_drop_threshold_are_dropped() {
 	new_test_ext().execute_with(|| {
-		assert_noop!(
-			XcmpQueue::service_overweight(RuntimeOrigin::root(), 0, Weight::from_parts(1000, 1000)),
-			Error::<Test>::BadOverweightIndex,
+		assert_ok!(XcmpQueue::update_suspend_threshold(RuntimeOrigin::root(), 2));
+		assert_ok!(XcmpQueue::update_drop_threshold(RuntimeOrigin::root(), 4));
+		let (page, _) = xcm_page(5);
+		let messages = vec![(ParaId::from(1000), 1u32, page.as_slice())];
+
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+
+		assert_eq!(EnqueuedMessages::get().len(), 4);
+		assert!(InboundXcmpSuspended::<Test>::get().contains(&1000.into()));
+		assert_eq!(
+			SignalMessages::<Test>::get(ParaId::from(1000)),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend).encode()
 		);
 	});
 }
 
 #[test]
-fn service_overweight_bad_xcm_format() {
+fn suspended_channel_is_resumed_once_queue_drained() {
 	new_test_ext().execute_with(|| {
-		let bad_xcm = vec![255];
-		Overweight::<Test>::insert(0, (ParaId::from(1000), 0, bad_xcm));
+		assert_ok!(XcmpQueue::update_suspend_threshold(RuntimeOrigin::root(), 2));
+		assert_ok!(XcmpQueue::update_resume_threshold(RuntimeOrigin::root(), 1));
+		let (page, _) = xcm_page(3);
+		let messages = vec![(ParaId::from(1000), 1u32, page.as_slice())];
+		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
+		assert!(InboundXcmpSuspended::<Test>::get().contains(&1000.into()));
 
-		assert_noop!(
-			XcmpQueue::service_overweight(RuntimeOrigin::root(), 0, Weight::from_parts(1000, 1000)),
-			Error::<Test>::BadXcm
+		// Still above the resume threshold.
+		XcmpQueue::on_queue_changed(1000.into(), 2, 0);
+		assert!(InboundXcmpSuspended::<Test>::get().contains(&1000.into()));
+
+		XcmpQueue::on_queue_changed(1000.into(), 1, 0);
+		assert!(InboundXcmpSuspended::<Test>::get().is_empty());
+		assert_eq!(
+			SignalMessages::<Test>::get(ParaId::from(1000)),
+			(XcmpMessageFormat::Signals, ChannelSignal::Suspend, ChannelSignal::Resume).encode()
 		);
 	});
 }
@@ -121,28 +129,12 @@ fn service_overweight_bad_xcm_format() {
 #[test]
 fn suspend_xcm_execution_works() {
 	new_test_ext().execute_with(|| {
+		assert!(!XcmpQueue::is_paused(&2000.into()));
 		QueueSuspended::<Test>::put(true);
 
-		let xcm =
-			VersionedXcm::from(Xcm::<RuntimeCall>(vec![Instruction::<RuntimeCall>::ClearOrigin]))
-				.encode();
-		let mut message_format = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
-		message_format.extend(xcm.clone());
-		let messages = vec![(ParaId::from(999), 1u32, message_format.as_slice())];
-
-		// This should have executed the incoming XCM, because it came from a system parachain
-		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
-
-		let queued_xcm = InboundXcmpMessages::<Test>::get(ParaId::from(999), 1u32);
-		assert!(queued_xcm.is_empty());
-
-		let messages = vec![(ParaId::from(2000), 1u32, message_format.as_slice())];
-
-		// This shouldn't have executed the incoming XCM
-		XcmpQueue::handle_xcmp_messages(messages.into_iter(), Weight::MAX);
-
-		let queued_xcm = InboundXcmpMessages::<Test>::get(ParaId::from(2000), 1u32);
-		assert_eq!(queued_xcm, xcm);
+		// System parachains can still send their messages since they are the controller.
+		assert!(!XcmpQueue::is_paused(&999.into()));
+		assert!(XcmpQueue::is_paused(&2000.into()));
 	});
 }
 
@@ -150,7 +142,7 @@ fn suspend_xcm_execution_works() {
 fn update_suspend_threshold_works() {
 	new_test_ext().execute_with(|| {
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(data.suspend_threshold, 2);
+		assert_eq!(data.suspend_threshold, 256);
 		assert_ok!(XcmpQueue::update_suspend_threshold(RuntimeOrigin::root(), 3));
 		assert_noop!(XcmpQueue::update_suspend_threshold(RuntimeOrigin::signed(2), 5), BadOrigin);
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
@@ -163,7 +155,7 @@ fn update_suspend_threshold_works() {
 fn update_drop_threshold_works() {
 	new_test_ext().execute_with(|| {
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(data.drop_threshold, 5);
+		assert_eq!(data.drop_threshold, 512);
 		assert_ok!(XcmpQueue::update_drop_threshold(RuntimeOrigin::root(), 6));
 		assert_noop!(XcmpQueue::update_drop_threshold(RuntimeOrigin::signed(2), 7), BadOrigin);
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
@@ -176,7 +168,7 @@ fn update_drop_threshold_works() {
 fn update_resume_threshold_works() {
 	new_test_ext().execute_with(|| {
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(data.resume_threshold, 1);
+		assert_eq!(data.resume_threshold, 64);
 		assert_ok!(XcmpQueue::update_resume_threshold(RuntimeOrigin::root(), 2));
 		assert_noop!(XcmpQueue::update_resume_threshold(RuntimeOrigin::signed(7), 3), BadOrigin);
 		let data: QueueConfigData = <QueueConfig<Test>>::get();
@@ -185,78 +177,6 @@ fn update_resume_threshold_works() {
 	});
 }
 
-#[test]
-fn update_threshold_weight_works() {
-	new_test_ext().execute_with(|| {
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(data.threshold_weight, Weight::from_parts(100_000, 0));
-		assert_ok!(XcmpQueue::update_threshold_weight(
-			RuntimeOrigin::root(),
-			Weight::from_parts(10_000, 0)
-		));
-		assert_noop!(
-			XcmpQueue::update_threshold_weight(
-				RuntimeOrigin::signed(5),
-				Weight::from_parts(10_000_000, 0),
-			),
-			BadOrigin
-		);
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-
-		assert_eq!(data.threshold_weight, Weight::from_parts(10_000, 0));
-	});
-}
-
-#[test]
-fn update_weight_restrict_decay_works() {
-	new_test_ext().execute_with(|| {
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(data.weight_restrict_decay, Weight::from_parts(2, 0));
-		assert_ok!(XcmpQueue::update_weight_restrict_decay(
-			RuntimeOrigin::root(),
-			Weight::from_parts(5, 0)
-		));
-		assert_noop!(
-			XcmpQueue::update_weight_restrict_decay(
-				RuntimeOrigin::signed(6),
-				Weight::from_parts(4, 0),
-			),
-			BadOrigin
-		);
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-
-		assert_eq!(data.weight_restrict_decay, Weight::from_parts(5, 0));
-	});
-}
-
-#[test]
-fn update_xcmp_max_individual_weight() {
-	new_test_ext().execute_with(|| {
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-		assert_eq!(
-			data.xcmp_max_individual_weight,
-			Weight::from_parts(20u64 * WEIGHT_REF_TIME_PER_MILLIS, DEFAULT_POV_SIZE),
-		);
-		assert_ok!(XcmpQueue::update_xcmp_max_individual_weight(
-			RuntimeOrigin::root(),
-			Weight::from_parts(30u64 * WEIGHT_REF_TIME_PER_MILLIS, 0)
-		));
-		assert_noop!(
-			XcmpQueue::update_xcmp_max_individual_weight(
-				RuntimeOrigin::signed(3),
-				Weight::from_parts(10u64 * WEIGHT_REF_TIME_PER_MILLIS, 0)
-			),
-			BadOrigin
-		);
-		let data: QueueConfigData = <QueueConfig<Test>>::get();
-
-		assert_eq!(
-			data.xcmp_max_individual_weight,
-			Weight::from_parts(30u64 * WEIGHT_REF_TIME_PER_MILLIS, 0)
-		);
-	});
-}
-
 /// Validates [`validate`] for required Some(destination) and Some(message)
 struct OkFixedXcmHashWithAssertingRequiredInputsSender;
 impl OkFixedXcmHashWithAssertingRequiredInputsSender {
@@ -25,7 +25,11 @@ use sp_std::marker::PhantomData;
 // Implemented by autogenerated benchmarking code.
 pub trait WeightInfo {
 	fn set_config_with_u32() -> Weight;
-	fn set_config_with_weight() -> Weight;
+	fn enqueue_xcmp_message() -> Weight;
+	fn suspend_channel() -> Weight;
+	fn resume_channel() -> Weight;
+	fn take_first_concatenated_xcm() -> Weight;
+	fn lazy_migrate_inbound_page() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -38,12 +42,43 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 
-	// Storage: XcmpQueue QueueConfig (r:1 w:1)
-	fn set_config_with_weight() -> Weight {
-		Weight::from_parts(2_717_000_u64, 0)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn enqueue_xcmp_message() -> Weight {
+		Weight::from_parts(15_118_000_u64, 3_517)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	fn suspend_channel() -> Weight {
+		Weight::from_parts(2_094_000_u64, 1_497)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	fn resume_channel() -> Weight {
+		Weight::from_parts(1_853_000_u64, 1_497)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+
+	fn take_first_concatenated_xcm() -> Weight {
+		Weight::from_parts(2_840_000_u64, 0)
+	}
+
+	// Storage: XcmpQueue InboundXcmpStatus (r:1 w:1)
+	// Storage: XcmpQueue InboundXcmpMessages (r:1 w:1)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn lazy_migrate_inbound_page() -> Weight {
+		Weight::from_parts(73_442_000_u64, 69_047)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 }
 
 impl WeightInfo for () {
@@ -54,10 +89,41 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 
-	// Storage: XcmpQueue QueueConfig (r:1 w:1)
-	fn set_config_with_weight() -> Weight {
-		Weight::from_parts(2_717_000_u64, 0)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn enqueue_xcmp_message() -> Weight {
+		Weight::from_parts(15_118_000_u64, 3_517)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	fn suspend_channel() -> Weight {
+		Weight::from_parts(2_094_000_u64, 1_497)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	fn resume_channel() -> Weight {
+		Weight::from_parts(1_853_000_u64, 1_497)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+
+	fn take_first_concatenated_xcm() -> Weight {
+		Weight::from_parts(2_840_000_u64, 0)
+	}
+
+	// Storage: XcmpQueue InboundXcmpStatus (r:1 w:1)
+	// Storage: XcmpQueue InboundXcmpMessages (r:1 w:1)
+	// Storage: MessageQueue BookStateFor (r:1 w:1)
+	// Storage: MessageQueue ServiceHead (r:1 w:1)
+	// Storage: MessageQueue Pages (r:0 w:1)
+	fn lazy_migrate_inbound_page() -> Weight {
+		Weight::from_parts(73_442_000_u64, 69_047)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
 }
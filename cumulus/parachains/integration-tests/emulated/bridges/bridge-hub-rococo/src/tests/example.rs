@@ -60,8 +60,8 @@ fn example() {
 		assert_expected_events!(
 			BridgeHubRococo,
 			vec![
-				RuntimeEvent::DmpQueue(cumulus_pallet_dmp_queue::Event::ExecutedDownward {
-					outcome: Outcome::Complete(_),
+				RuntimeEvent::MessageQueue(pallet_message_queue::Event::Processed {
+					success: true,
 					..
 				}) => {},
 				RuntimeEvent::BridgeWococoMessages(pallet_bridge_messages::Event::MessageAccepted {
@@ -91,7 +91,7 @@ fn example() {
 		assert_expected_events!(
 			AssetHubWococo,
 			vec![
-				RuntimeEvent::XcmpQueue(cumulus_pallet_xcmp_queue::Event::Fail { .. }) => {},
+				RuntimeEvent::MessageQueue(pallet_message_queue::Event::ProcessingFailed { .. }) => {},
 			]
 		);
 	});
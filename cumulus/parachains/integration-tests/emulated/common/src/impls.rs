@@ -38,7 +38,8 @@ pub use cumulus_pallet_dmp_queue;
 pub use cumulus_pallet_parachain_system;
 pub use cumulus_pallet_xcmp_queue;
 pub use cumulus_primitives_core::{
-	relay_chain::HrmpChannelId, DmpMessageHandler, ParaId, XcmpMessageHandler,
+	relay_chain::HrmpChannelId, AggregateMessageOrigin as CumulusAggregateMessageOrigin,
+	DmpMessageHandler, ParaId, XcmpMessageHandler,
 };
 use pallet_bridge_messages::{Config, Instance1, Instance2, OutboundLanes, Pallet};
 pub use parachains_common::{AccountId, Balance};
@@ -468,13 +469,16 @@ macro_rules! impl_assert_events_helpers_for_parachain {
 					$crate::impls::assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::DmpQueue($crate::impls::cumulus_pallet_dmp_queue::Event::ExecutedDownward {
-								outcome: $crate::impls::Outcome::Complete(weight), ..
+							[<$chain RuntimeEvent>]::MessageQueue($crate::impls::pallet_message_queue::Event::Processed {
+								origin: $crate::impls::CumulusAggregateMessageOrigin::Parent,
+								weight_used,
+								success: true,
+								..
 							}) => {
-								weight: $crate::impls::weight_within_threshold(
+								weight_used: $crate::impls::weight_within_threshold(
 									($crate::impls::REF_TIME_THRESHOLD, $crate::impls::PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
 							},
 						]
@@ -482,22 +486,21 @@ macro_rules! impl_assert_events_helpers_for_parachain {
 				}
 
 				/// Asserts a XCM from Relay Chain is incompletely executed
-				pub fn assert_dmp_queue_incomplete(
-					expected_weight: Option<$crate::impls::Weight>,
-					expected_error: Option<$crate::impls::Error>,
-				) {
+				pub fn assert_dmp_queue_incomplete(expected_weight: Option<$crate::impls::Weight>) {
 					$crate::impls::assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::DmpQueue($crate::impls::cumulus_pallet_dmp_queue::Event::ExecutedDownward {
-								outcome: $crate::impls::Outcome::Incomplete(weight, error), ..
+							[<$chain RuntimeEvent>]::MessageQueue($crate::impls::pallet_message_queue::Event::Processed {
+								origin: $crate::impls::CumulusAggregateMessageOrigin::Parent,
+								weight_used,
+								success: false,
+								..
 							}) => {
-								weight: $crate::impls::weight_within_threshold(
+								weight_used: $crate::impls::weight_within_threshold(
 									($crate::impls::REF_TIME_THRESHOLD, $crate::impls::PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
-								error: *error == expected_error.unwrap_or(*error),
 							},
 						]
 					);
@@ -508,13 +511,16 @@ macro_rules! impl_assert_events_helpers_for_parachain {
 					$crate::impls::assert_expected_events!(
 						Self,
 						vec![
-							[<$chain RuntimeEvent>]::XcmpQueue(
-								$crate::impls::cumulus_pallet_xcmp_queue::Event::Success { weight, .. }
-							) => {
-								weight: $crate::impls::weight_within_threshold(
+							[<$chain RuntimeEvent>]::MessageQueue($crate::impls::pallet_message_queue::Event::Processed {
+								origin: $crate::impls::CumulusAggregateMessageOrigin::Sibling(_),
+								weight_used,
+								success: true,
+								..
+							}) => {
+								weight_used: $crate::impls::weight_within_threshold(
 									($crate::impls::REF_TIME_THRESHOLD, $crate::impls::PROOF_SIZE_THRESHOLD),
-									expected_weight.unwrap_or(*weight),
-									*weight
+									expected_weight.unwrap_or(*weight_used),
+									*weight_used
 								),
 							},
 						]
@@ -101,6 +101,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: ForeignAssets Account (max_values: None, max_size: Some(732), added: 3207, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1000 w:1000)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: `ForeignAssets::Holds` (r:0 w:1000)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:0 w:1000)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	/// The range of component `c` is `[0, 1000]`.
 	/// The range of component `c` is `[0, 1000]`.
 	fn destroy_accounts(c: u32, ) -> Weight {
@@ -115,7 +119,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
 			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(c.into())))
 			.saturating_add(Weight::from_parts(0, 3207).saturating_mul(c.into()))
 	}
 	/// Storage: ForeignAssets Asset (r:1 w:1)
@@ -171,6 +175,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Account` (r:1 w:1)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn burn() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -178,7 +186,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 33_503_000 picoseconds.
 		Weight::from_parts(33_860_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -187,6 +195,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -194,7 +206,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 45_065_000 picoseconds.
 		Weight::from_parts(45_856_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -203,6 +215,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer_keep_alive() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -210,7 +226,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 39_913_000 picoseconds.
 		Weight::from_parts(40_791_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -219,6 +235,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn force_transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -226,7 +246,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 45_337_000 picoseconds.
 		Weight::from_parts(45_980_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
@@ -407,6 +427,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer_approved() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `520`
@@ -414,7 +438,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 63_205_000 picoseconds.
 		Weight::from_parts(64_078_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(5))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -493,6 +517,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn refund() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `471`
@@ -500,13 +528,17 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 31_728_000 picoseconds.
 		Weight::from_parts(32_012_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `ForeignAssets::Account` (r:1 w:1)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn refund_other() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `401`
@@ -514,7 +546,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 29_432_000 picoseconds.
 		Weight::from_parts(29_968_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
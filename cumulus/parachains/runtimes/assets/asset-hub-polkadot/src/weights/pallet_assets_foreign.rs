@@ -96,6 +96,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1000 w:1000)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:0 w:1000)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:0 w:1000)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	/// The range of component `c` is `[0, 1000]`.
 	/// The range of component `c` is `[0, 1000]`.
 	fn destroy_accounts(c: u32, ) -> Weight {
@@ -110,7 +114,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
 			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(c.into())))
 			.saturating_add(Weight::from_parts(0, 3207).saturating_mul(c.into()))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -166,6 +170,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Account` (r:1 w:1)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn burn() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -173,7 +181,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 33_918_000 picoseconds.
 		Weight::from_parts(34_624_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -182,6 +190,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -189,7 +201,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 45_863_000 picoseconds.
 		Weight::from_parts(46_674_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -198,6 +210,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer_keep_alive() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -205,7 +221,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 40_592_000 picoseconds.
 		Weight::from_parts(41_582_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -214,6 +230,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn force_transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `350`
@@ -221,7 +241,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 46_170_000 picoseconds.
 		Weight::from_parts(46_880_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
@@ -406,6 +426,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn transfer_approved() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `520`
@@ -413,7 +437,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 65_431_000 picoseconds.
 		Weight::from_parts(66_502_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(5))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -492,6 +516,10 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn refund() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `471`
@@ -499,13 +527,17 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 31_148_000 picoseconds.
 		Weight::from_parts(31_751_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `ForeignAssets::Account` (r:1 w:1)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Holds` (r:1 w:0)
+	/// Proof: `ForeignAssets::Holds` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Freezes` (r:1 w:0)
+	/// Proof: `ForeignAssets::Freezes` (`max_values`: None, `max_size`: Some(667), added: 3142, mode: `MaxEncodedLen`)
 	fn refund_other() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `401`
@@ -513,7 +545,7 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 29_127_000 picoseconds.
 		Weight::from_parts(29_922_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
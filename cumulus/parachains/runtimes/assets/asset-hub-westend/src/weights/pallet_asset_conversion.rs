@@ -78,6 +78,10 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 	}
 	/// Storage: `AssetConversion::Pools` (r:1 w:0)
 	/// Proof: `AssetConversion::Pools` (`max_values`: None, `max_size`: Some(1224), added: 3699, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceAccumulators` (r:1 w:1)
+	/// Proof: `AssetConversion::PriceAccumulators` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceObservations` (r:1 w:1)
+	/// Proof: `AssetConversion::PriceObservations` (`max_values`: None, `max_size`: Some(2085), added: 4560, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -93,13 +97,17 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 		//  Measured:  `1117`
 		//  Estimated: `7404`
 		// Minimum execution time: 153_484_000 picoseconds.
-		Weight::from_parts(155_465_000, 0)
+		Weight::from_parts(158_465_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(8))
-			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(9))
 	}
 	/// Storage: `AssetConversion::Pools` (r:1 w:0)
 	/// Proof: `AssetConversion::Pools` (`max_values`: None, `max_size`: Some(1224), added: 3699, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceAccumulators` (r:1 w:1)
+	/// Proof: `AssetConversion::PriceAccumulators` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceObservations` (r:1 w:1)
+	/// Proof: `AssetConversion::PriceObservations` (`max_values`: None, `max_size`: Some(2085), added: 4560, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
@@ -117,41 +125,82 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 		//  Measured:  `1106`
 		//  Estimated: `7404`
 		// Minimum execution time: 141_326_000 picoseconds.
-		Weight::from_parts(143_882_000, 0)
+		Weight::from_parts(146_882_000, 0)
 			.saturating_add(Weight::from_parts(0, 7404))
-			.saturating_add(T::DbWeight::get().reads(8))
-			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(8))
 	}
+	/// Storage: `AssetConversion::Pools` (r:2 w:0)
+	/// Proof: `AssetConversion::Pools` (`max_values`: None, `max_size`: Some(1224), added: 3699, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceAccumulators` (r:2 w:2)
+	/// Proof: `AssetConversion::PriceAccumulators` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceObservations` (r:2 w:2)
+	/// Proof: `AssetConversion::PriceObservations` (`max_values`: None, `max_size`: Some(2085), added: 4560, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:2 w:2)
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Account` (r:4 w:4)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	fn swap_exact_tokens_for_tokens() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1148`
-		//  Estimated: `13818`
-		// Minimum execution time: 168_556_000 picoseconds.
-		Weight::from_parts(170_313_000, 0)
-			.saturating_add(Weight::from_parts(0, 13818))
-			.saturating_add(T::DbWeight::get().reads(8))
-			.saturating_add(T::DbWeight::get().writes(8))
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight {
+		Weight::from_parts(54_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 990))
+			.saturating_add(Weight::from_parts(41_800_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6414).saturating_mul(n.into()))
 	}
-	/// Storage: `System::Account` (r:2 w:2)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::Pools` (r:2 w:0)
+	/// Proof: `AssetConversion::Pools` (`max_values`: None, `max_size`: Some(1224), added: 3699, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceAccumulators` (r:2 w:2)
+	/// Proof: `AssetConversion::PriceAccumulators` (`max_values`: None, `max_size`: Some(1256), added: 3731, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceObservations` (r:2 w:2)
+	/// Proof: `AssetConversion::PriceObservations` (`max_values`: None, `max_size`: Some(2085), added: 4560, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:2 w:2)
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Account` (r:4 w:4)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
-	fn swap_tokens_for_exact_tokens() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1148`
-		//  Estimated: `13818`
-		// Minimum execution time: 167_704_000 picoseconds.
-		Weight::from_parts(170_034_000, 0)
-			.saturating_add(Weight::from_parts(0, 13818))
-			.saturating_add(T::DbWeight::get().reads(8))
-			.saturating_add(T::DbWeight::get().writes(8))
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight {
+		Weight::from_parts(54_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 990))
+			.saturating_add(Weight::from_parts(41_800_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6414).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetConversion::FeeTiers` (r:1 w:0)
+	/// Proof: `AssetConversion::FeeTiers` (`max_values`: Some(1), `max_size`: Some(41), added: 536, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::Pools` (r:1 w:1)
+	/// Proof: `AssetConversion::Pools` (`max_values`: None, `max_size`: Some(1224), added: 3699, mode: `MaxEncodedLen`)
+	/// Storage: UNKNOWN KEY `0x76a2c49709deec21d9c05f96c1f47351` (r:1 w:0)
+	/// Proof: UNKNOWN KEY `0x76a2c49709deec21d9c05f96c1f47351` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:1)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::NextPoolAssetId` (r:1 w:1)
+	/// Proof: `AssetConversion::NextPoolAssetId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:1)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	fn create_custom_pool() -> Weight {
+		Weight::from_parts(92_372_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: `AssetConversion::FeeTiers` (r:0 w:1)
+	/// Proof: `AssetConversion::FeeTiers` (`max_values`: Some(1), `max_size`: Some(41), added: 536, mode: `MaxEncodedLen`)
+	fn set_fee_tiers() -> Weight {
+		Weight::from_parts(7_563_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
 	}
 }
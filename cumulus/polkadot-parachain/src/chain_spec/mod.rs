@@ -43,6 +43,10 @@ pub struct Extensions {
 	pub para_id: u32,
 }
 
+/// Generic chain spec for runtimes without a dedicated chain spec type, e.g. the ones run in omni
+/// mode.
+pub type GenericChainSpec = sc_service::GenericChainSpec<(), Extensions>;
+
 impl Extensions {
 	/// Try to get the extension from the given `ChainSpec`.
 	pub fn try_get(chain_spec: &dyn sc_service::ChainSpec) -> Option<&Self> {
@@ -94,6 +94,14 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// Run the chain as an omni node.
+	///
+	/// Any parachain runtime without a dedicated service in this node can be run this way, with
+	/// the consensus detected from its genesis runtime. `--chain` must be the path to its JSON
+	/// chain spec.
+	#[arg(long)]
+	pub omni_node: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relaychain_args: Vec<String>,
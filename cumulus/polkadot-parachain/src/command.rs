@@ -19,8 +19,11 @@ use crate::{
 	cli::{Cli, RelayChainCli, Subcommand},
 	service::{new_partial, Block},
 };
-use cumulus_primitives_core::ParaId;
+use codec::Decode;
+use cumulus_client_service::ParachainHostFunctions;
+use cumulus_primitives_core::{CollectCollationInfo, ParaId};
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
 use log::info;
 use parachains_common::{AssetHubPolkadotAuraId, AuraId};
 use sc_cli::{
@@ -28,7 +31,13 @@ use sc_cli::{
 	NetworkParams, Result, SharedParams, SubstrateCli,
 };
 use sc_service::config::{BasePath, PrometheusConfig};
-use sp_runtime::traits::AccountIdConversion;
+use sp_api::RuntimeApiInfo;
+use sp_consensus_aura::AuraApi;
+use sp_core::{
+	traits::{CallContext, CodeExecutor, ReadRuntimeVersion, RuntimeCode, WrappedRuntimeCode},
+	OpaqueMetadata,
+};
+use sp_runtime::{traits::AccountIdConversion, BuildStorage};
 use std::{net::SocketAddr, path::PathBuf};
 
 /// Helper enum that is used for better distinction of different parachain/runtime configuration
@@ -49,6 +58,22 @@ enum Runtime {
 	CollectivesWestend,
 	Glutton,
 	BridgeHub(chain_spec::bridge_hubs::BridgeHubRuntimeType),
+	/// Any runtime without a dedicated service in this node, requested with `--omni-node`.
+	///
+	/// It is run with the generic [`crate::fake_runtime_api::aura::RuntimeApi`] and the consensus
+	/// detected from the genesis runtime, see [`detect_consensus`].
+	Omni,
+}
+
+/// The consensus used to run a [`Runtime::Omni`] chain.
+#[derive(Debug, PartialEq)]
+enum Consensus {
+	/// Aura with `sr25519` authorities.
+	AuraSr25519,
+	/// Aura with `ed25519` authorities, as used by Asset Hub Polkadot.
+	AuraEd25519,
+	/// Free-for-all relay chain consensus, as used by runtimes that do not implement `AuraApi`.
+	Relay,
 }
 
 trait RuntimeResolver {
@@ -113,7 +138,94 @@ fn runtime(id: &str) -> Runtime {
 	}
 }
 
-fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+/// The runtime to run `chain_spec` with.
+///
+/// Running as an omni node is an explicit opt-in, so that a chain spec with an unrecognized id
+/// keeps running with [`Runtime::default()`] otherwise.
+fn resolve_runtime(cli: &Cli, chain_spec: &dyn ChainSpec) -> Runtime {
+	if cli.omni_node {
+		Runtime::Omni
+	} else {
+		chain_spec.runtime()
+	}
+}
+
+/// Detect the consensus needed by the genesis runtime of `chain_spec`.
+///
+/// The runtime version embedded in the genesis code lists the runtime APIs it implements. A
+/// runtime that can't be matched to one of the [`Consensus`] variants is an error.
+fn detect_consensus(chain_spec: &dyn ChainSpec) -> std::result::Result<Consensus, String> {
+	let storage = chain_spec.build_storage()?;
+	let code = storage
+		.top
+		.get(sp_core::storage::well_known_keys::CODE)
+		.ok_or("Could not find the runtime code in the genesis storage of the chain spec")?;
+
+	// Only the version and metadata of the runtime are read, so host functions the node doesn't
+	// know about must not stop it from being instantiated.
+	let executor = sc_executor::WasmExecutor::<ParachainHostFunctions>::builder()
+		.with_allow_missing_host_functions(true)
+		.build();
+	let mut ext = sp_state_machine::BasicExternalities::new_empty();
+	let version = executor
+		.read_runtime_version(code, &mut ext)
+		.map_err(|e| format!("Failed to read the genesis runtime version: {}", e))?;
+	let version = sp_version::RuntimeVersion::decode(&mut &version[..])
+		.map_err(|e| format!("Failed to decode the genesis runtime version: {}", e))?;
+
+	// The id of a runtime API doesn't depend on its generic parameters, so this matches
+	// `AuraApi` with any authority type.
+	if version.has_api_with(&<dyn AuraApi<Block, AuraId>>::ID, |_| true) {
+		detect_aura_authority(&executor, code, &mut ext)
+	} else if version.has_api_with(&<dyn CollectCollationInfo<Block>>::ID, |_| true) {
+		Ok(Consensus::Relay)
+	} else {
+		Err("The genesis runtime implements neither `AuraApi` nor `CollectCollationInfo`, \
+			so its consensus could not be detected"
+			.into())
+	}
+}
+
+/// Detect the Aura authority type of the runtime `code` from the types in its metadata.
+fn detect_aura_authority(
+	executor: &sc_executor::WasmExecutor<ParachainHostFunctions>,
+	code: &[u8],
+	ext: &mut sp_state_machine::BasicExternalities,
+) -> std::result::Result<Consensus, String> {
+	const SR25519: [&str; 4] = ["sp_consensus_aura", "sr25519", "app_sr25519", "Public"];
+	const ED25519: [&str; 4] = ["sp_consensus_aura", "ed25519", "app_ed25519", "Public"];
+
+	let runtime_code = RuntimeCode {
+		code_fetcher: &WrappedRuntimeCode(code.into()),
+		heap_pages: None,
+		hash: sp_core::blake2_256(code).to_vec(),
+	};
+	let metadata = executor
+		.call(ext, &runtime_code, "Metadata_metadata", &[], false, CallContext::Offchain)
+		.0
+		.map_err(|e| format!("Failed to read the genesis runtime metadata: {}", e))?;
+	let metadata = OpaqueMetadata::decode(&mut &metadata[..])
+		.and_then(|metadata| RuntimeMetadataPrefixed::decode(&mut &metadata[..]))
+		.map_err(|e| format!("Failed to decode the genesis runtime metadata: {}", e))?;
+	let registry = match metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata.types,
+		RuntimeMetadata::V15(metadata) => metadata.types,
+		_ => return Err("Unsupported metadata version of the genesis runtime".into()),
+	};
+	let has_type = |path: &[&str]| registry.types.iter().any(|ty| ty.ty.path.segments == path);
+
+	match (has_type(&SR25519), has_type(&ED25519)) {
+		(true, false) => Ok(Consensus::AuraSr25519),
+		(false, true) => Ok(Consensus::AuraEd25519),
+		_ => Err("Could not detect the Aura authority type of the genesis runtime".into()),
+	}
+}
+
+/// Load the chain spec `id`, either one of the built in ones or the path to a JSON file.
+///
+/// A JSON file is loaded as a [`chain_spec::GenericChainSpec`] for the omni node, since its
+/// runtime is only known from the genesis code.
+fn load_spec(id: &str, omni_node: bool) -> std::result::Result<Box<dyn ChainSpec>, String> {
 	let (id, _, para_id) = extract_parachain_id(id);
 	Ok(match id {
 		// - Defaul-like
@@ -243,7 +355,8 @@ fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
 		// -- Loading a specific spec from disk
 		path => {
 			let path: PathBuf = path.into();
-			match path.runtime() {
+			let runtime = if omni_node { Runtime::Omni } else { path.runtime() };
+			match runtime {
 				Runtime::AssetHubPolkadot => Box::new(
 					chain_spec::asset_hubs::AssetHubPolkadotChainSpec::from_json_file(path)?,
 				),
@@ -270,6 +383,7 @@ fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
 				Runtime::Default => Box::new(
 					chain_spec::rococo_parachain::RococoParachainChainSpec::from_json_file(path)?,
 				),
+				Runtime::Omni => Box::new(chain_spec::GenericChainSpec::from_json_file(path)?),
 			}
 		},
 	})
@@ -340,7 +454,7 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
-		load_spec(id)
+		load_spec(id, self.omni_node)
 	}
 }
 
@@ -382,8 +496,8 @@ impl SubstrateCli for RelayChainCli {
 
 /// Creates partial components for the runtimes that are supported by the benchmarks.
 macro_rules! construct_partials {
-	($config:expr, |$partials:ident| $code:expr) => {
-		match $config.chain_spec.runtime() {
+	($cli:expr, $config:expr, |$partials:ident| $code:expr) => {
+		match resolve_runtime(&$cli, &*$config.chain_spec) {
 			Runtime::AssetHubKusama => {
 				let $partials = new_partial::<asset_hub_kusama_runtime::RuntimeApi, _>(
 					&$config,
@@ -491,6 +605,30 @@ macro_rules! construct_partials {
 				)?;
 				$code
 			},
+			Runtime::Omni => match detect_consensus(&*$config.chain_spec)? {
+				Consensus::AuraSr25519 => {
+					let $partials = new_partial::<crate::fake_runtime_api::aura::RuntimeApi, _>(
+						&$config,
+						crate::service::aura_build_import_queue::<_, AuraId>,
+					)?;
+					$code
+				},
+				Consensus::AuraEd25519 => {
+					let $partials =
+						new_partial::<crate::fake_runtime_api::aura_ed25519::RuntimeApi, _>(
+							&$config,
+							crate::service::aura_build_import_queue::<_, AssetHubPolkadotAuraId>,
+						)?;
+					$code
+				},
+				Consensus::Relay => {
+					let $partials = new_partial::<crate::fake_runtime_api::aura::RuntimeApi, _>(
+						&$config,
+						crate::service::shell_build_import_queue,
+					)?;
+					$code
+				},
+			},
 		}
 	};
 }
@@ -498,7 +636,7 @@ macro_rules! construct_partials {
 macro_rules! construct_async_run {
 	(|$components:ident, $cli:ident, $cmd:ident, $config:ident| $( $code:tt )* ) => {{
 		let runner = $cli.create_runner($cmd)?;
-		match runner.config().chain_spec.runtime() {
+		match resolve_runtime(&$cli, &*runner.config().chain_spec) {
 			Runtime::AssetHubWestend => {
 				runner.async_run(|$config| {
 					let $components = new_partial::<asset_hub_westend_runtime::RuntimeApi, _>(
@@ -657,7 +795,42 @@ macro_rules! construct_async_run {
 					let task_manager = $components.task_manager;
 					{ $( $code )* }.map(|v| (v, task_manager))
 				})
-			}
+			},
+			Runtime::Omni => match detect_consensus(&*runner.config().chain_spec)? {
+				Consensus::AuraSr25519 => {
+					runner.async_run(|$config| {
+						let $components = new_partial::<crate::fake_runtime_api::aura::RuntimeApi, _>(
+							&$config,
+							crate::service::aura_build_import_queue::<_, AuraId>,
+						)?;
+						let task_manager = $components.task_manager;
+						{ $( $code )* }.map(|v| (v, task_manager))
+					})
+				},
+				Consensus::AuraEd25519 => {
+					runner.async_run(|$config| {
+						let $components = new_partial::<
+							crate::fake_runtime_api::aura_ed25519::RuntimeApi,
+							_,
+						>(
+							&$config,
+							crate::service::aura_build_import_queue::<_, AssetHubPolkadotAuraId>,
+						)?;
+						let task_manager = $components.task_manager;
+						{ $( $code )* }.map(|v| (v, task_manager))
+					})
+				},
+				Consensus::Relay => {
+					runner.async_run(|$config| {
+						let $components = new_partial::<crate::fake_runtime_api::aura::RuntimeApi, _>(
+							&$config,
+							crate::service::shell_build_import_queue,
+						)?;
+						let task_manager = $components.task_manager;
+						{ $( $code )* }.map(|v| (v, task_manager))
+					})
+				},
+			},
 		}
 	}}
 }
@@ -716,7 +889,7 @@ pub fn run() -> Result<()> {
 		Some(Subcommand::ExportGenesisState(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| {
-				construct_partials!(config, |partials| cmd.run(&*config.chain_spec, &*partials.client))
+				construct_partials!(cli, config, |partials| cmd.run(&*config.chain_spec, &*partials.client))
 			})
 		},
 		Some(Subcommand::ExportGenesisWasm(cmd)) => {
@@ -740,7 +913,7 @@ pub fn run() -> Result<()> {
 							.into())
 					},
 				BenchmarkCmd::Block(cmd) => runner.sync_run(|config| {
-					construct_partials!(config, |partials| cmd.run(partials.client))
+					construct_partials!(cli, config, |partials| cmd.run(partials.client))
 				}),
 				#[cfg(not(feature = "runtime-benchmarks"))]
 				BenchmarkCmd::Storage(_) =>
@@ -752,7 +925,7 @@ pub fn run() -> Result<()> {
 					.into()),
 				#[cfg(feature = "runtime-benchmarks")]
 				BenchmarkCmd::Storage(cmd) => runner.sync_run(|config| {
-					construct_partials!(config, |partials| {
+					construct_partials!(cli, config, |partials| {
 						let db = partials.backend.expose_db();
 						let storage = partials.backend.expose_storage();
 
@@ -835,7 +1008,7 @@ pub fn run() -> Result<()> {
 				info!("Parachain Account: {}", parachain_account);
 				info!("Is collating: {}", if config.role.is_authority() { "yes" } else { "no" });
 
-				match config.chain_spec.runtime() {
+				match resolve_runtime(&cli, &*config.chain_spec) {
 					Runtime::AssetHubPolkadot => crate::service::start_generic_aura_node::<
 						asset_hub_polkadot_runtime::RuntimeApi,
 						AssetHubPolkadotAuraId,
@@ -959,6 +1132,31 @@ pub fn run() -> Result<()> {
 						.await
 						.map(|r| r.0)
 						.map_err(Into::into),
+					Runtime::Omni => {
+						let consensus = detect_consensus(&*config.chain_spec)?;
+						info!("Running as omni node with {:?} consensus", consensus);
+
+						match consensus {
+							Consensus::AuraSr25519 => crate::service::start_generic_aura_node::<
+								crate::fake_runtime_api::aura::RuntimeApi,
+								AuraId,
+							>(config, polkadot_config, collator_options, id, hwbench)
+							.await
+							.map(|r| r.0),
+							Consensus::AuraEd25519 => crate::service::start_generic_aura_node::<
+								crate::fake_runtime_api::aura_ed25519::RuntimeApi,
+								AssetHubPolkadotAuraId,
+							>(config, polkadot_config, collator_options, id, hwbench)
+							.await
+							.map(|r| r.0),
+							Consensus::Relay => crate::service::start_shell_node::<
+								crate::fake_runtime_api::aura::RuntimeApi,
+							>(config, polkadot_config, collator_options, id, hwbench)
+							.await
+							.map(|r| r.0),
+						}
+						.map_err(Into::into)
+					},
 				}
 			})
 		},
@@ -1094,7 +1292,10 @@ impl CliConfiguration<Self> for RelayChainCli {
 mod tests {
 	use crate::{
 		chain_spec::{get_account_id_from_seed, get_from_seed},
-		command::{Runtime, RuntimeResolver},
+		cli::Cli,
+		command::{
+			detect_consensus, load_spec, resolve_runtime, Consensus, Runtime, RuntimeResolver,
+		},
 	};
 	use sc_chain_spec::{ChainSpec, ChainSpecExtension, ChainSpecGroup, ChainType, Extension};
 	use serde::{Deserialize, Serialize};
@@ -1202,5 +1403,73 @@ mod tests {
 			Box::new(crate::chain_spec::contracts::contracts_rococo_local_config()),
 		);
 		assert_eq!(Runtime::ContractsRococo, path.runtime());
+
+		let path = store_configuration(
+			&temp_dir,
+			Box::new(create_default_with_extensions("my-parachain", Extensions1::default())),
+		);
+		assert_eq!(Runtime::Default, path.runtime());
+	}
+
+	#[test]
+	fn test_omni_node_is_opt_in() {
+		let spec = create_default_with_extensions("my-parachain", Extensions1::default());
+
+		let cli = <Cli as clap::Parser>::parse_from(["polkadot-parachain"]);
+		assert_eq!(Runtime::Default, resolve_runtime(&cli, &spec));
+
+		let cli = <Cli as clap::Parser>::parse_from(["polkadot-parachain", "--omni-node"]);
+		assert_eq!(Runtime::Omni, resolve_runtime(&cli, &spec));
+	}
+
+	#[test]
+	fn test_detect_consensus_from_genesis_runtime() {
+		let spec = crate::chain_spec::rococo_parachain::rococo_parachain_local_config();
+		assert_eq!(Ok(Consensus::AuraSr25519), detect_consensus(&spec));
+
+		let spec = crate::chain_spec::asset_hubs::asset_hub_polkadot_local_config();
+		assert_eq!(Ok(Consensus::AuraEd25519), detect_consensus(&spec));
+
+		let spec = crate::chain_spec::shell::get_shell_chain_spec();
+		assert_eq!(Ok(Consensus::Relay), detect_consensus(&spec));
+	}
+
+	#[test]
+	fn test_detect_consensus_of_the_parachain_template() {
+		// the template reclaims storage weight, so its runtime imports the proof size host function.
+		let spec = sc_service::GenericChainSpec::<_, Extensions1>::from_genesis(
+			"Template local testnet",
+			"template-local",
+			ChainType::Local,
+			|| parachain_template_runtime::RuntimeGenesisConfig {
+				system: parachain_template_runtime::SystemConfig {
+					code: parachain_template_runtime::WASM_BINARY
+						.expect("WASM binary was not built, please build it!")
+						.to_vec(),
+					..Default::default()
+				},
+				..Default::default()
+			},
+			Vec::new(),
+			None,
+			None,
+			None,
+			None,
+			Extensions1::default(),
+		);
+		assert_eq!(Ok(Consensus::AuraSr25519), detect_consensus(&spec));
+	}
+
+	#[test]
+	fn test_omni_node_loads_any_chain_spec_file() {
+		let temp_dir = tempfile::tempdir().expect("Failed to access tempdir");
+		let path = store_configuration(
+			&temp_dir,
+			Box::new(crate::chain_spec::asset_hubs::asset_hub_polkadot_local_config()),
+		);
+		let path = path.to_str().expect("Valid UTF-8 path");
+
+		let spec = load_spec(path, true).expect("Chain spec is loaded");
+		assert_eq!(Ok(Consensus::AuraEd25519), detect_consensus(&*spec));
 	}
 }
@@ -24,6 +24,7 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod fake_runtime_api;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
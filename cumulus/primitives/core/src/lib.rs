@@ -18,7 +18,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_parachain_primitives::primitives::HeadData;
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
@@ -78,6 +78,49 @@ impl From<MessageSendError> for &'static str {
 	}
 }
 
+/// Origin of a message that is enqueued into the `MessageQueue` pallet of a parachain.
+///
+/// Every variant maps to its own queue. This is stored in storage, so any change to the existing
+/// variants requires a migration.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub enum AggregateMessageOrigin {
+	/// The message came from the para-chain itself.
+	#[codec(index = 0)]
+	Here,
+	/// The message came from the relay-chain.
+	///
+	/// This is used by the DMP queue.
+	#[codec(index = 1)]
+	Parent,
+	/// The message came from a sibling para-chain.
+	///
+	/// This is used by the HRMP queue.
+	#[codec(index = 2)]
+	Sibling(ParaId),
+}
+
+impl From<AggregateMessageOrigin> for MultiLocation {
+	fn from(origin: AggregateMessageOrigin) -> Self {
+		match origin {
+			AggregateMessageOrigin::Here => MultiLocation::here(),
+			AggregateMessageOrigin::Parent => MultiLocation::parent(),
+			AggregateMessageOrigin::Sibling(id) =>
+				MultiLocation::new(1, Junction::Parachain(id.into())),
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl From<u32> for AggregateMessageOrigin {
+	fn from(x: u32) -> Self {
+		match x {
+			0 => Self::Here,
+			1 => Self::Parent,
+			p => Self::Sibling(ParaId::from(p)),
+		}
+	}
+}
+
 /// Information about an XCMP channel.
 pub struct ChannelInfo {
 	/// The maximum number of messages that can be pending in the channel at once.
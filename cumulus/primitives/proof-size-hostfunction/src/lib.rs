@@ -0,0 +1,99 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tools for reclaiming PoV weight in parachain runtimes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+use sp_externalities::ExternalitiesExt;
+use sp_runtime_interface::runtime_interface;
+#[cfg(feature = "std")]
+use sp_trie::proof_size_extension::ProofSizeExt;
+
+/// Value returned by [`storage_proof_size::storage_proof_size`] if proof recording is disabled.
+pub const PROOF_RECORDING_DISABLED: u64 = u64::MAX;
+
+/// Interface that provides access to the current storage proof size.
+///
+/// Should return the current storage proof size if [`ProofSizeExt`] is registered. Otherwise, needs
+/// to return [`PROOF_RECORDING_DISABLED`].
+#[runtime_interface]
+pub trait StorageProofSize {
+	/// Returns the current storage proof size.
+	fn storage_proof_size(&mut self) -> u64 {
+		match self.extension::<ProofSizeExt>() {
+			Some(ext) => ext.storage_proof_size(),
+			None => PROOF_RECORDING_DISABLED,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::Blake2Hasher;
+	use sp_externalities::Extensions;
+	use sp_state_machine::{Ext, OverlayedChanges, TestExternalities, TrieBackendBuilder};
+	use sp_trie::{proof_size_extension::ProofSizeExt, recorder::Recorder};
+
+	use crate::{storage_proof_size, PROOF_RECORDING_DISABLED};
+
+	const TEST_DATA: &[(&[u8], &[u8])] = &[(b"key1", &[1; 64]), (b"key2", &[2; 64])];
+
+	fn prepared_test_externalities() -> TestExternalities<Blake2Hasher> {
+		let mut ext = TestExternalities::new_empty();
+		TEST_DATA.iter().for_each(|(k, v)| ext.insert(k.to_vec(), v.to_vec()));
+		ext.commit_all().expect("Commits the test data");
+		ext
+	}
+
+	#[test]
+	fn host_function_returns_size_from_recorder() {
+		let ext = prepared_test_externalities();
+		let recorder = Recorder::<Blake2Hasher>::default();
+		let backend =
+			TrieBackendBuilder::wrap(&ext.backend).with_recorder(recorder.clone()).build();
+
+		let mut overlay = OverlayedChanges::default();
+		let mut extensions = Extensions::new();
+		extensions.register(ProofSizeExt::new(recorder));
+		let mut ext = Ext::new(&mut overlay, &backend, Some(&mut extensions));
+
+		sp_externalities::set_and_run_with_externalities(&mut ext, || {
+			assert_eq!(storage_proof_size::storage_proof_size(), 0);
+			sp_io::storage::get(b"key1");
+			let after_first_read = storage_proof_size::storage_proof_size();
+			assert!(after_first_read > 0);
+			sp_io::storage::get(b"key2");
+			let after_second_read = storage_proof_size::storage_proof_size();
+			assert!(after_second_read > after_first_read);
+			// Reading the same key again doesn't add to the proof.
+			sp_io::storage::get(b"key2");
+			assert_eq!(storage_proof_size::storage_proof_size(), after_second_read);
+		});
+	}
+
+	#[test]
+	fn host_function_returns_max_without_extension() {
+		let mut ext = prepared_test_externalities();
+
+		ext.execute_with(|| {
+			assert_eq!(storage_proof_size::storage_proof_size(), PROOF_RECORDING_DISABLED);
+			sp_io::storage::get(b"key1");
+			assert_eq!(storage_proof_size::storage_proof_size(), PROOF_RECORDING_DISABLED);
+		});
+	}
+}
@@ -0,0 +1,159 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reclaims the proof size weight of extrinsics which they didn't use.
+//!
+//! The proof size weight of an extrinsic is benchmarked for the worst case, so blocks of a
+//! parachain are considered full long before their PoV reaches its limit. The
+//! [`StorageWeightReclaim`] signed extension reads the size of the storage proof recorded by the
+//! node before and after the dispatch of an extrinsic, and refunds the difference to the
+//! benchmarked proof size in [`frame_system::BlockWeight`].
+//!
+//! The node has to record the storage proof while building and while importing blocks, otherwise
+//! nothing is reclaimed. The runtime has to be executed with the host functions of
+//! [`cumulus_primitives_proof_size_hostfunction::storage_proof_size`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use cumulus_primitives_proof_size_hostfunction::{
+	storage_proof_size::storage_proof_size, PROOF_RECORDING_DISABLED,
+};
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	weights::Weight,
+};
+use frame_system::Config;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::TransactionValidityError,
+	DispatchResult,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "runtime::storage_reclaim";
+
+/// Returns the current size of the storage proof recorded by the node.
+///
+/// Returns `None` if the node doesn't record the storage proof.
+pub fn get_proof_size() -> Option<u64> {
+	let proof_size = storage_proof_size();
+	(proof_size != PROOF_RECORDING_DISABLED).then_some(proof_size)
+}
+
+/// Reclaims the unused proof size weight of an extrinsic.
+///
+/// # Transaction Validity
+///
+/// This extension does not influence any fields of `TransactionValidity` in case the
+/// transaction is valid.
+///
+/// It should be the last signed extension of the runtime, so the weight it reclaims includes the
+/// storage accessed by the other extensions after the dispatch.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct StorageWeightReclaim<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> StorageWeightReclaim<T> {
+	/// Create a new `StorageWeightReclaim` instance.
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for StorageWeightReclaim<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "StorageWeightReclaim")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for StorageWeightReclaim<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	/// The proof size before the dispatch, if the node records the storage proof.
+	type Pre = Option<u64>;
+	const IDENTIFIER: &'static str = "StorageWeightReclaim";
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(get_proof_size())
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let Some(Some(pre_dispatch_proof_size)) = pre else { return Ok(()) };
+
+		let Some(post_dispatch_proof_size) = get_proof_size() else {
+			log::debug!(
+				target: LOG_TARGET,
+				"Proof recording was enabled before the dispatch, but not after it."
+			);
+			return Ok(())
+		};
+
+		let benchmarked = post_info.calc_actual_weight(info).proof_size();
+		let consumed = post_dispatch_proof_size.saturating_sub(pre_dispatch_proof_size);
+
+		// `CheckWeight` only accounts for the actual weight of the extrinsic in the block weight,
+		// so only the difference to it has to be corrected.
+		frame_system::BlockWeight::<T>::mutate(|current_weight| {
+			if consumed > benchmarked {
+				log::error!(
+					target: LOG_TARGET,
+					"Benchmarked proof size is smaller than the consumed one. \
+					 benchmarked: {benchmarked}, consumed: {consumed}",
+				);
+				current_weight.accrue(Weight::from_parts(0, consumed - benchmarked), info.class);
+			} else {
+				log::trace!(
+					target: LOG_TARGET,
+					"Reclaiming proof size. benchmarked: {benchmarked}, consumed: {consumed}",
+				);
+				current_weight.reduce(Weight::from_parts(0, benchmarked - consumed), info.class);
+			}
+		});
+
+		Ok(())
+	}
+}
@@ -0,0 +1,266 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	assert_ok,
+	dispatch::{DispatchClass, PerDispatchClass},
+	traits::{ConstU16, ConstU64},
+};
+use frame_system::{BlockWeight, CheckWeight};
+use sp_core::H256;
+use sp_io::TestExternalities;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use sp_trie::{proof_size_extension::ProofSizeExt, ProofSizeProvider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+const CALL: &RuntimeCall = &RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 0u64 });
+const ALICE: u64 = 1;
+const LEN: usize = 0;
+
+/// Returns the given proof sizes, one after another.
+struct TestRecorder {
+	return_values: Box<[usize]>,
+	counter: AtomicUsize,
+}
+
+impl TestRecorder {
+	fn new(values: &[usize]) -> Self {
+		TestRecorder { return_values: values.into(), counter: Default::default() }
+	}
+}
+
+impl ProofSizeProvider for TestRecorder {
+	fn estimate_encoded_size(&self) -> usize {
+		let counter = self.counter.fetch_add(1, Ordering::Relaxed);
+		self.return_values[counter]
+	}
+}
+
+fn new_test_ext() -> TestExternalities {
+	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+}
+
+/// Test externalities whose proof size is reported as the given values, one per read.
+fn setup_test_externalities(proof_values: &[usize]) -> TestExternalities {
+	let mut ext = new_test_ext();
+	ext.register_extension(ProofSizeExt::new(TestRecorder::new(proof_values)));
+	ext
+}
+
+fn set_current_storage_weight(new_weight: u64) {
+	BlockWeight::<Test>::mutate(|current_weight| {
+		current_weight.set(Weight::from_parts(0, new_weight), DispatchClass::Normal);
+	});
+}
+
+fn normal_proof_size() -> u64 {
+	BlockWeight::<Test>::get().get(DispatchClass::Normal).proof_size()
+}
+
+fn info_with_proof_size(proof_size: u64) -> DispatchInfo {
+	DispatchInfo { weight: Weight::from_parts(0, proof_size), ..Default::default() }
+}
+
+fn post_info_with_proof_size(proof_size: Option<u64>) -> PostDispatchInfo {
+	PostDispatchInfo {
+		actual_weight: proof_size.map(|proof_size| Weight::from_parts(0, proof_size)),
+		pays_fee: Default::default(),
+	}
+}
+
+#[test]
+fn basic_refund() {
+	// The real cost will be 100 bytes of storage size.
+	let mut test_ext = setup_test_externalities(&[0, 100]);
+
+	test_ext.execute_with(|| {
+		set_current_storage_weight(1000);
+
+		// Benchmarked storage weight: 500.
+		let info = info_with_proof_size(500);
+		let post_info = post_info_with_proof_size(None);
+
+		let pre = StorageWeightReclaim::<Test>::new()
+			.pre_dispatch(&ALICE, CALL, &info, LEN)
+			.unwrap();
+		assert_eq!(pre, Some(0));
+
+		assert_ok!(CheckWeight::<Test>::post_dispatch(None, &info, &post_info, 0, &Ok(())));
+		// We expect a refund of 400.
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		assert_eq!(normal_proof_size(), 600);
+	})
+}
+
+#[test]
+fn does_nothing_without_extension() {
+	let mut test_ext = new_test_ext();
+
+	test_ext.execute_with(|| {
+		set_current_storage_weight(1000);
+
+		let info = info_with_proof_size(500);
+		let post_info = post_info_with_proof_size(None);
+
+		let pre = StorageWeightReclaim::<Test>::new()
+			.pre_dispatch(&ALICE, CALL, &info, LEN)
+			.unwrap();
+		assert_eq!(pre, None);
+
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		assert_eq!(normal_proof_size(), 1000);
+	})
+}
+
+#[test]
+fn negative_refund_is_added_to_weight() {
+	let mut test_ext = setup_test_externalities(&[100, 300]);
+
+	test_ext.execute_with(|| {
+		set_current_storage_weight(1000);
+
+		// Benchmarked storage weight: 100, but the extrinsic consumed 200.
+		let info = info_with_proof_size(100);
+		let post_info = post_info_with_proof_size(None);
+
+		let pre = StorageWeightReclaim::<Test>::new()
+			.pre_dispatch(&ALICE, CALL, &info, LEN)
+			.unwrap();
+		assert_eq!(pre, Some(100));
+
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		assert_eq!(normal_proof_size(), 1100);
+	})
+}
+
+#[test]
+fn refund_is_based_on_actual_weight() {
+	let mut test_ext = setup_test_externalities(&[100, 200]);
+
+	test_ext.execute_with(|| {
+		set_current_storage_weight(1000);
+
+		// Benchmarked storage weight: 500, the actual weight of the dispatch is 300 and it
+		// consumed 100.
+		let info = info_with_proof_size(500);
+		let post_info = post_info_with_proof_size(Some(300));
+
+		let pre = StorageWeightReclaim::<Test>::new()
+			.pre_dispatch(&ALICE, CALL, &info, LEN)
+			.unwrap();
+		assert_eq!(pre, Some(100));
+
+		// `CheckWeight` refunds 200 of the benchmarked weight.
+		assert_ok!(CheckWeight::<Test>::post_dispatch(None, &info, &post_info, 0, &Ok(())));
+		assert_eq!(normal_proof_size(), 800);
+
+		// We expect a refund of the remaining 200.
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		assert_eq!(normal_proof_size(), 600);
+	})
+}
+
+#[test]
+fn weight_is_not_reduced_below_zero() {
+	let mut test_ext = setup_test_externalities(&[0, 100]);
+
+	test_ext.execute_with(|| {
+		set_current_storage_weight(0);
+
+		let info = info_with_proof_size(500);
+		let post_info = post_info_with_proof_size(None);
+
+		let pre = StorageWeightReclaim::<Test>::new()
+			.pre_dispatch(&ALICE, CALL, &info, LEN)
+			.unwrap();
+
+		assert_ok!(StorageWeightReclaim::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			LEN,
+			&Ok(())
+		));
+
+		assert_eq!(BlockWeight::<Test>::get(), PerDispatchClass::default());
+	})
+}
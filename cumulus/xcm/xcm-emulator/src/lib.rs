@@ -244,6 +244,8 @@ pub trait RelayChain: Chain {
 pub trait Parachain: Chain {
 	type XcmpMessageHandler: XcmpMessageHandler;
 	type DmpMessageHandler: DmpMessageHandler;
+	/// Services the messages enqueued by the XCMP and DMP handlers.
+	type MessageProcessor: ServiceQueues;
 	type LocationToAccountId: ConvertLocation<AccountId>;
 	type ParachainInfo: Get<ParaId>;
 	type ParachainSystem;
@@ -605,6 +607,7 @@ macro_rules! decl_test_parachains {
 			impl $crate::Parachain for $name {
 				type XcmpMessageHandler = $xcmp_message_handler;
 				type DmpMessageHandler = $dmp_message_handler;
+				type MessageProcessor = $crate::MessageQueuePallet<<Self as $crate::Chain>::Runtime>;
 				type LocationToAccountId = $location_to_account;
 				type ParachainSystem = $crate::ParachainSystemPallet<<Self as $crate::Chain>::Runtime>;
 				type ParachainInfo = $parachain_info;
@@ -966,7 +969,7 @@ macro_rules! decl_test_networks {
 				}
 
 				fn process_downward_messages() {
-					use $crate::{DmpMessageHandler, Bounded, Parachain, RelayChainBlockNumber, TestExt};
+					use $crate::{DmpMessageHandler, Bounded, Parachain, RelayChainBlockNumber, ServiceQueues, TestExt};
 
 					while let Some((to_para_id, messages))
 						= $crate::DOWNWARD_MESSAGES.with(|b| b.borrow_mut().get_mut(Self::name()).unwrap().pop_front()) {
@@ -986,6 +989,7 @@ macro_rules! decl_test_networks {
 								if msgs.len() != 0 {
 									<$parachain>::ext_wrapper(|| {
 										<$parachain as Parachain>::DmpMessageHandler::handle_dmp_messages(msgs.clone().into_iter(), $crate::Weight::max_value());
+										<$parachain as Parachain>::MessageProcessor::service_queues($crate::Weight::max_value());
 									});
 									$crate::log::debug!(target: concat!("dmp::", stringify!($name)) , "DMP messages processed {:?} to para_id {:?}", msgs.clone(), &to_para_id);
 									for m in msgs {
@@ -998,7 +1002,7 @@ macro_rules! decl_test_networks {
 				}
 
 				fn process_horizontal_messages() {
-					use $crate::{XcmpMessageHandler, Bounded, Parachain, TestExt};
+					use $crate::{XcmpMessageHandler, Bounded, Parachain, ServiceQueues, TestExt};
 
 					while let Some((to_para_id, messages))
 						= $crate::HORIZONTAL_MESSAGES.with(|b| b.borrow_mut().get_mut(Self::name()).unwrap().pop_front()) {
@@ -1009,6 +1013,7 @@ macro_rules! decl_test_networks {
 							if $crate::PARA_IDS.with(|b| b.borrow_mut().get_mut(Self::name()).unwrap().contains(&to_para_id)) && para_id == to_para_id {
 								<$parachain>::ext_wrapper(|| {
 									<$parachain as Parachain>::XcmpMessageHandler::handle_xcmp_messages(iter.clone(), $crate::Weight::max_value());
+									<$parachain as Parachain>::MessageProcessor::service_queues($crate::Weight::max_value());
 								});
 								$crate::log::debug!(target: concat!("hrmp::", stringify!($name)) , "HRMP messages processed {:?} to para_id {:?}", &messages, &to_para_id);
 							}
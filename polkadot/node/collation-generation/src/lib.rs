@@ -27,7 +27,8 @@
 //!   * Use the Runtime API subsystem to fetch the full validation data.
 //!   * Invoke the `collator`, and use its outputs to produce a [`CandidateReceipt`], signed with
 //!     the configuration's `key`.
-//!   * Dispatch a [`CollatorProtocolMessage::DistributeCollation`]`(receipt, pov)`.
+//!   * Dispatch a [`CollatorProtocolMessage::DistributeCollation`] for the core the collation was
+//!     built for.
 
 #![deny(missing_docs)]
 
@@ -49,8 +50,8 @@ use polkadot_node_subsystem_util::{
 };
 use polkadot_primitives::{
 	collator_signature_payload, CandidateCommitments, CandidateDescriptor, CandidateReceipt,
-	CollatorPair, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData,
-	ValidationCodeHash,
+	CollatorPair, CoreIndex, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption,
+	PersistedValidationData, ValidationCodeHash,
 };
 use sp_core::crypto::Pair;
 use std::sync::Arc;
@@ -349,6 +350,7 @@ async fn handle_new_activations<Context>(
 							validation_data,
 							validation_code_hash,
 							n_validators,
+							core_index: Some(CoreIndex(core_idx as u32)),
 						},
 						task_config.key.clone(),
 						&mut task_sender,
@@ -379,6 +381,7 @@ async fn handle_submit_collation<Context>(
 		parent_head,
 		validation_code_hash,
 		result_sender,
+		core_index,
 	} = params;
 
 	let validators = request_validators(relay_parent, ctx.sender()).await.await??;
@@ -415,6 +418,7 @@ async fn handle_submit_collation<Context>(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	};
 
 	construct_and_distribute_receipt(
@@ -436,6 +440,7 @@ struct PreparedCollation {
 	validation_data: PersistedValidationData,
 	validation_code_hash: ValidationCodeHash,
 	n_validators: usize,
+	core_index: Option<CoreIndex>,
 }
 
 /// Takes a prepared collation, along with its context, and produces a candidate receipt
@@ -454,6 +459,7 @@ async fn construct_and_distribute_receipt(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	} = collation;
 
 	let persisted_validation_data_hash = validation_data.hash();
@@ -542,12 +548,13 @@ async fn construct_and_distribute_receipt(
 	metrics.on_collation_generated();
 
 	sender
-		.send_message(CollatorProtocolMessage::DistributeCollation(
-			ccr,
+		.send_message(CollatorProtocolMessage::DistributeCollation {
+			candidate_receipt: ccr,
 			parent_head_data_hash,
 			pov,
 			result_sender,
-		))
+			core_index,
+		})
 		.await;
 }
 
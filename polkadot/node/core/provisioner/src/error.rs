@@ -53,6 +53,9 @@ pub enum Error {
 	#[error("failed to get backable candidate from prospective parachains")]
 	CanceledBackableCandidate(#[source] oneshot::Canceled),
 
+	#[error("failed to get fragment tree membership from prospective parachains")]
+	CanceledTreeMembership(#[source] oneshot::Canceled),
+
 	#[error(transparent)]
 	ChainApi(#[from] ChainApiError),
 
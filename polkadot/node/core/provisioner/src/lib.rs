@@ -640,6 +640,10 @@ async fn select_candidate_hashes_from_tracked(
 /// Requests backable candidates from Prospective Parachains subsystem
 /// based on core states.
 ///
+/// A para may be assigned to more than one core, in which case a chain of
+/// candidates is requested and assigned to its cores in ascending order,
+/// which is the order in which the runtime occupies them.
+///
 /// Should be called when prospective parachains are enabled.
 async fn request_backable_candidates(
 	availability_cores: &[CoreState],
@@ -649,60 +653,198 @@ async fn request_backable_candidates(
 ) -> Result<Vec<(CandidateHash, Hash)>, Error> {
 	let block_number = get_block_number_under_construction(relay_parent, sender).await?;
 
-	let mut selected_candidates = Vec::with_capacity(availability_cores.len());
+	// Free cores per para, and the candidates pending availability per para along with their
+	// cores.
+	let mut free_cores: BTreeMap<ParaId, Vec<usize>> = BTreeMap::new();
+	let mut pending: BTreeMap<ParaId, Vec<(usize, CandidateHash, PendingCore)>> = BTreeMap::new();
 
 	for (core_idx, core) in availability_cores.iter().enumerate() {
-		let (para_id, required_path) = match core {
+		match core {
 			CoreState::Scheduled(scheduled_core) => {
 				// The core is free, pick the first eligible candidate from
 				// the fragment tree.
-				(scheduled_core.para_id, Vec::new())
+				free_cores.entry(scheduled_core.para_id).or_default().push(core_idx);
 			},
 			CoreState::Occupied(occupied_core) => {
-				if bitfields_indicate_availability(core_idx, bitfields, &occupied_core.availability)
-				{
-					if let Some(ref scheduled_core) = occupied_core.next_up_on_available {
-						// The candidate occupying the core is available, choose its
-						// child in the fragment tree.
-						//
-						// TODO: doesn't work for on-demand parachains. We lean hard on the
-						// assumption that cores are fixed to specific parachains within a session.
-						// https://github.com/paritytech/polkadot/issues/5492
-						(scheduled_core.para_id, vec![occupied_core.candidate_hash])
-					} else {
-						continue
-					}
-				} else {
-					if occupied_core.time_out_at != block_number {
-						continue
+				let state = if bitfields_indicate_availability(
+					core_idx,
+					bitfields,
+					&occupied_core.availability,
+				) {
+					// The candidate occupying the core is available, choose its
+					// child in the fragment tree.
+					//
+					// TODO: doesn't work for on-demand parachains. We lean hard on the
+					// assumption that cores are fixed to specific parachains within a session.
+					// https://github.com/paritytech/polkadot/issues/5492
+					let para_id = occupied_core.para_id();
+					match occupied_core.next_up_on_available {
+						Some(ref scheduled_core) if scheduled_core.para_id != para_id => {
+							// The core is handed over to another para, which has nothing to
+							// build upon.
+							free_cores.entry(scheduled_core.para_id).or_default().push(core_idx);
+							PendingCore::Available { frees_core: false }
+						},
+						ref next_up => PendingCore::Available { frees_core: next_up.is_some() },
 					}
+				} else if occupied_core.time_out_at == block_number {
 					if let Some(ref scheduled_core) = occupied_core.next_up_on_time_out {
 						// Candidate's availability timed out, practically same as scheduled.
-						(scheduled_core.para_id, Vec::new())
-					} else {
-						continue
+						free_cores.entry(scheduled_core.para_id).or_default().push(core_idx);
 					}
-				}
+					PendingCore::TimedOut
+				} else {
+					PendingCore::Pending
+				};
+				pending.entry(occupied_core.para_id()).or_default().push((
+					core_idx,
+					occupied_core.candidate_hash,
+					state,
+				));
 			},
 			CoreState::Free => continue,
+		}
+	}
+
+	// Free cores and the path in the fragment tree to build on, per para.
+	let mut requests: BTreeMap<ParaId, (Vec<usize>, Vec<CandidateHash>)> = free_cores
+		.into_iter()
+		.map(|(para_id, cores)| (para_id, (cores, Vec::new())))
+		.collect();
+
+	for (para_id, pending) in pending {
+		let pending = if pending.len() > 1 {
+			order_by_chain_position(relay_parent, para_id, pending, sender).await?
+		} else {
+			pending
 		};
 
-		let response = get_backable_candidate(relay_parent, para_id, required_path, sender).await?;
+		// The new candidates have to build upon every candidate pending availability, not just
+		// upon the available ones, as the runtime only accepts candidates extending the chain of
+		// candidates pending availability of their para. The runtime only frees the cores of a
+		// prefix of the chain which is available, and drops the chain from a timed out candidate
+		// onwards.
+		let mut cores = Vec::new();
+		let mut required_path = Vec::new();
+		let mut available_prefix = true;
+		for (core_idx, candidate_hash, state) in pending {
+			match state {
+				PendingCore::TimedOut => break,
+				PendingCore::Available { frees_core } if available_prefix =>
+					if frees_core {
+						cores.push(core_idx);
+					},
+				PendingCore::Available { .. } | PendingCore::Pending => available_prefix = false,
+			}
+			required_path.push(candidate_hash);
+		}
+
+		// There is nothing to request for a para without a free core.
+		if cores.is_empty() && !requests.contains_key(&para_id) {
+			continue
+		}
+
+		let (request_cores, request_path) = requests.entry(para_id).or_default();
+		request_cores.extend(cores);
+		request_cores.sort();
+		*request_path = required_path;
+	}
+
+	let mut selected_candidates = Vec::with_capacity(availability_cores.len());
+
+	for (para_id, (cores, required_path)) in requests {
+		let response = get_backable_candidates(
+			relay_parent,
+			para_id,
+			required_path,
+			cores.len() as u32,
+			sender,
+		)
+		.await?;
+
+		if response.len() < cores.len() {
+			gum::debug!(
+				target: LOG_TARGET,
+				leaf_hash = ?relay_parent,
+				?para_id,
+				cores = ?cores,
+				n_candidates = response.len(),
+				"Not enough backable candidates returned by prospective parachains",
+			);
+		}
+
+		selected_candidates.extend(cores.into_iter().zip(response));
+	}
+
+	// Keep the candidates in ascending order by core index.
+	selected_candidates.sort_by_key(|(core_idx, _)| *core_idx);
+
+	Ok(selected_candidates.into_iter().map(|(_, candidate)| candidate).collect())
+}
 
-		match response {
-			Some((hash, relay_parent)) => selected_candidates.push((hash, relay_parent)),
+/// What happens to a core occupied by a candidate pending availability in the block under
+/// construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingCore {
+	/// The candidate is available. The core is freed for the para if the candidate is part of
+	/// the available prefix of the chain of candidates pending availability and `frees_core`.
+	Available { frees_core: bool },
+	/// The candidate stays pending availability.
+	Pending,
+	/// The availability of the candidate times out.
+	TimedOut,
+}
+
+/// Orders the candidates pending availability of a para by their position in the chain of
+/// candidates pending availability, which need not match the order of their cores.
+///
+/// Only the candidates forming a prefix of the chain are returned, since the new candidates
+/// can only build upon those.
+async fn order_by_chain_position(
+	relay_parent: Hash,
+	para_id: ParaId,
+	pending: Vec<(usize, CandidateHash, PendingCore)>,
+	sender: &mut impl overseer::ProvisionerSenderTrait,
+) -> Result<Vec<(usize, CandidateHash, PendingCore)>, Error> {
+	let mut by_depth = BTreeMap::new();
+	for (core_idx, candidate_hash, state) in pending {
+		let (tx, rx) = oneshot::channel();
+		sender
+			.send_message(ProspectiveParachainsMessage::GetTreeMembership(
+				para_id,
+				candidate_hash,
+				tx,
+			))
+			.await;
+
+		let membership = rx.await.map_err(Error::CanceledTreeMembership)?;
+		let depth = membership
+			.into_iter()
+			.find(|(leaf, _)| leaf == &relay_parent)
+			.and_then(|(_, depths)| depths.into_iter().min());
+
+		match depth {
+			Some(depth) => {
+				by_depth.insert(depth, (core_idx, candidate_hash, state));
+			},
 			None => {
 				gum::debug!(
 					target: LOG_TARGET,
 					leaf_hash = ?relay_parent,
-					core = core_idx,
-					"No backable candidate returned by prospective parachains",
+					?para_id,
+					?candidate_hash,
+					"Candidate pending availability is not in the fragment tree",
 				);
 			},
 		}
 	}
 
-	Ok(selected_candidates)
+	Ok(by_depth
+		.into_iter()
+		.enumerate()
+		.take_while(|(position, (depth, _))| position == depth)
+		.map(|(_, (_, candidate))| candidate)
+		.collect())
 }
 
 /// Determine which cores are free, and then to the degree possible, pick a candidate appropriate to
@@ -802,19 +944,21 @@ async fn get_block_number_under_construction(
 	}
 }
 
-/// Requests backable candidate from Prospective Parachains based on
+/// Requests up to `count` backable candidates from Prospective Parachains based on
 /// the given path in the fragment tree.
-async fn get_backable_candidate(
+async fn get_backable_candidates(
 	relay_parent: Hash,
 	para_id: ParaId,
 	required_path: Vec<CandidateHash>,
+	count: u32,
 	sender: &mut impl overseer::ProvisionerSenderTrait,
-) -> Result<Option<(CandidateHash, Hash)>, Error> {
+) -> Result<Vec<(CandidateHash, Hash)>, Error> {
 	let (tx, rx) = oneshot::channel();
 	sender
-		.send_message(ProspectiveParachainsMessage::GetBackableCandidate(
+		.send_message(ProspectiveParachainsMessage::GetBackableCandidates(
 			relay_parent,
 			para_id,
+			count,
 			required_path,
 			tx,
 		))
@@ -22,6 +22,9 @@ use polkadot_primitives::{OccupiedCore, ScheduledCore};
 const MOCK_GROUP_SIZE: usize = 5;
 
 pub fn occupied_core(para_id: u32) -> CoreState {
+	let mut candidate_descriptor = dummy_candidate_descriptor(dummy_hash());
+	candidate_descriptor.para_id = para_id.into();
+
 	CoreState::Occupied(OccupiedCore {
 		group_responsible: para_id.into(),
 		next_up_on_available: None,
@@ -29,7 +32,7 @@ pub fn occupied_core(para_id: u32) -> CoreState {
 		time_out_at: 200_u32,
 		next_up_on_time_out: None,
 		availability: bitvec![u8, bitvec::order::Lsb0; 0; 32],
-		candidate_descriptor: dummy_candidate_descriptor(dummy_hash()),
+		candidate_descriptor,
 		candidate_hash: Default::default(),
 	})
 }
@@ -373,10 +376,10 @@ mod select_candidates {
 					let _ = sender.send(response);
 				},
 				AllMessages::ProspectiveParachains(
-					ProspectiveParachainsMessage::GetBackableCandidate(.., tx),
+					ProspectiveParachainsMessage::GetBackableCandidates(_, _, count, _, tx),
 				) => match prospective_parachains_mode {
 					ProspectiveParachainsMode::Enabled { .. } => {
-						let _ = tx.send(candidates_iter.next());
+						let _ = tx.send(candidates_iter.by_ref().take(count as usize).collect());
 					},
 					ProspectiveParachainsMode::Disabled =>
 						panic!("unexpected prospective parachains request"),
@@ -700,4 +703,253 @@ mod select_candidates {
 			},
 		)
 	}
+
+	#[test]
+	fn selects_chain_for_para_with_multiple_cores() {
+		// Para 1 is assigned to cores 0 and 1, para 2 to core 2.
+		let mock_cores = vec![
+			CoreState::Scheduled(scheduled_core(1)),
+			CoreState::Scheduled(scheduled_core(1)),
+			CoreState::Scheduled(scheduled_core(2)),
+		];
+
+		let mut descriptor_template = dummy_candidate_descriptor(dummy_hash());
+		descriptor_template.persisted_validation_data_hash =
+			PersistedValidationData::<Hash, BlockNumber>::default().hash();
+		let candidate_template = CandidateReceipt {
+			descriptor: descriptor_template,
+			commitments_hash: CandidateCommitments::default().hash(),
+		};
+
+		// Para 1 gets a chain of two candidates in a single request.
+		let candidates: Vec<_> = [1u32, 1, 2]
+			.iter()
+			.enumerate()
+			.map(|(idx, para_id)| {
+				let mut candidate = candidate_template.clone();
+				candidate.descriptor.para_id = (*para_id).into();
+				candidate.descriptor.relay_parent = Hash::repeat_byte(idx as u8);
+				candidate
+			})
+			.collect();
+
+		let prospective_parachains_mode =
+			ProspectiveParachainsMode::Enabled { max_candidate_depth: 2, allowed_ancestry_len: 0 };
+
+		let to_backed = |c: &CandidateReceipt| BackedCandidate {
+			candidate: CommittedCandidateReceipt {
+				descriptor: c.descriptor.clone(),
+				commitments: Default::default(),
+			},
+			validity_votes: Vec::new(),
+			validator_indices: default_bitvec(MOCK_GROUP_SIZE),
+		};
+		let expected_backed: Vec<_> = candidates.iter().map(to_backed).collect();
+		let expected_hashes: Vec<_> = expected_backed.iter().map(|c| c.hash()).collect();
+
+		test_harness(
+			|r| mock_overseer(r, expected_backed, prospective_parachains_mode),
+			|mut tx: TestSubsystemSender| async move {
+				let result = select_candidates(
+					&mock_cores,
+					&[],
+					&[],
+					prospective_parachains_mode,
+					Default::default(),
+					&mut tx,
+				)
+				.await
+				.unwrap();
+
+				assert_eq!(result.iter().map(|c| c.hash()).collect::<Vec<_>>(), expected_hashes);
+			},
+		)
+	}
+
+	#[test]
+	fn orders_required_path_by_chain_position() {
+		use std::ops::Not;
+
+		let relay_parent = Hash::repeat_byte(0xff);
+		let candidate_hash = |byte| CandidateHash(Hash::repeat_byte(byte));
+
+		let available_core = |para_id: u32, hash: CandidateHash| {
+			build_occupied_core(para_id, |core| {
+				core.next_up_on_available = Some(scheduled_core(para_id));
+				core.availability = core.availability.clone().not();
+				core.candidate_hash = hash;
+			})
+		};
+
+		// Para 1 has a chain of two available candidates pending availability, the second of
+		// which occupies the lower core, and an additional free core.
+		// Para 2 has the second and third candidates of its chain available, but not the first.
+		let mock_cores = vec![
+			available_core(1, candidate_hash(2)),
+			available_core(1, candidate_hash(1)),
+			CoreState::Scheduled(scheduled_core(1)),
+			available_core(2, candidate_hash(5)),
+			build_occupied_core(2, |core| {
+				core.next_up_on_available = Some(scheduled_core(2));
+				core.candidate_hash = candidate_hash(3);
+			}),
+			available_core(2, candidate_hash(4)),
+		];
+		let depths: HashMap<_, _> = [
+			(candidate_hash(1), 0),
+			(candidate_hash(2), 1),
+			(candidate_hash(3), 0),
+			(candidate_hash(4), 1),
+			(candidate_hash(5), 2),
+		]
+		.into_iter()
+		.collect();
+
+		let backable: Vec<_> =
+			(10..13).map(|byte| (candidate_hash(byte), Hash::repeat_byte(byte))).collect();
+		let expected = backable.clone();
+
+		test_harness(
+			|mut receiver: mpsc::UnboundedReceiver<AllMessages>| async move {
+				while let Some(from_job) = receiver.next().await {
+					match from_job {
+						AllMessages::ChainApi(ChainApiMessage::BlockNumber(_, tx)) =>
+							tx.send(Ok(Some(BLOCK_UNDER_PRODUCTION - 1))).unwrap(),
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetTreeMembership(_, hash, tx),
+						) => {
+							let _ = tx.send(vec![(relay_parent, vec![depths[&hash]])]);
+						},
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetBackableCandidates(
+								_,
+								para_id,
+								count,
+								required_path,
+								tx,
+							),
+						) => {
+							// Para 2 frees no core, since the first candidate of its chain is
+							// not available.
+							assert_eq!(para_id, 1.into());
+							assert_eq!(count, 3);
+							assert_eq!(required_path, vec![candidate_hash(1), candidate_hash(2)]);
+							let _ = tx.send(backable.clone());
+						},
+						_ => panic!("Unexpected message: {:?}", from_job),
+					}
+				}
+			},
+			|mut tx: TestSubsystemSender| async move {
+				let result = request_backable_candidates(&mock_cores, &[], relay_parent, &mut tx)
+					.await
+					.unwrap();
+
+				assert_eq!(result, expected);
+			},
+		)
+	}
+
+	#[test]
+	fn required_path_includes_unavailable_candidates_pending_availability() {
+		use std::ops::Not;
+
+		let relay_parent = Hash::repeat_byte(0xff);
+		let candidate_hash = |byte| CandidateHash(Hash::repeat_byte(byte));
+
+		// Para 1 has a chain of three candidates pending availability, of which only the first
+		// one is available, and an additional free core. Para 2 has a candidate pending
+		// availability which times out, and para 3 one which stays pending without a free core.
+		let mock_cores = vec![
+			build_occupied_core(1, |core| {
+				core.next_up_on_available = Some(scheduled_core(1));
+				core.candidate_hash = candidate_hash(3);
+			}),
+			build_occupied_core(1, |core| {
+				core.next_up_on_available = Some(scheduled_core(1));
+				core.availability = core.availability.clone().not();
+				core.candidate_hash = candidate_hash(1);
+			}),
+			build_occupied_core(1, |core| {
+				core.next_up_on_available = Some(scheduled_core(1));
+				core.candidate_hash = candidate_hash(2);
+			}),
+			CoreState::Scheduled(scheduled_core(1)),
+			build_occupied_core(2, |core| {
+				core.next_up_on_time_out = Some(scheduled_core(2));
+				core.time_out_at = BLOCK_UNDER_PRODUCTION;
+				core.candidate_hash = candidate_hash(4);
+			}),
+			build_occupied_core(3, |core| {
+				core.next_up_on_available = Some(scheduled_core(3));
+				core.candidate_hash = candidate_hash(5);
+			}),
+		];
+		let depths: HashMap<_, _> =
+			[(candidate_hash(1), 0), (candidate_hash(2), 1), (candidate_hash(3), 2)]
+				.into_iter()
+				.collect();
+
+		let backable_1: Vec<_> =
+			(10..12).map(|byte| (candidate_hash(byte), Hash::repeat_byte(byte))).collect();
+		let backable_2 = vec![(candidate_hash(12), Hash::repeat_byte(12))];
+		let expected = vec![backable_1[0], backable_1[1], backable_2[0]];
+
+		test_harness(
+			|mut receiver: mpsc::UnboundedReceiver<AllMessages>| async move {
+				while let Some(from_job) = receiver.next().await {
+					match from_job {
+						AllMessages::ChainApi(ChainApiMessage::BlockNumber(_, tx)) =>
+							tx.send(Ok(Some(BLOCK_UNDER_PRODUCTION - 1))).unwrap(),
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetTreeMembership(_, hash, tx),
+						) => {
+							let _ = tx.send(vec![(relay_parent, vec![depths[&hash]])]);
+						},
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetBackableCandidates(
+								_,
+								para_id,
+								count,
+								required_path,
+								tx,
+							),
+						) if para_id == 1.into() => {
+							// The core of the available candidate and the free core, building
+							// upon the whole chain.
+							assert_eq!(count, 2);
+							assert_eq!(
+								required_path,
+								vec![candidate_hash(1), candidate_hash(2), candidate_hash(3)]
+							);
+							let _ = tx.send(backable_1.clone());
+						},
+						AllMessages::ProspectiveParachains(
+							ProspectiveParachainsMessage::GetBackableCandidates(
+								_,
+								para_id,
+								count,
+								required_path,
+								tx,
+							),
+						) => {
+							// The timed out candidate is dropped, para 3 has no free core.
+							assert_eq!(para_id, 2.into());
+							assert_eq!(count, 1);
+							assert!(required_path.is_empty());
+							let _ = tx.send(backable_2.clone());
+						},
+						_ => panic!("Unexpected message: {:?}", from_job),
+					}
+				}
+			},
+			|mut tx: TestSubsystemSender| async move {
+				let result = request_backable_candidates(&mock_cores, &[], relay_parent, &mut tx)
+					.await
+					.unwrap();
+
+				assert_eq!(result, expected);
+			},
+		)
+	}
 }
@@ -319,6 +319,8 @@ impl State {
 /// Distribute a collation.
 ///
 /// Figure out the core our para is assigned to and the relevant validators.
+/// If the collation was built for a specific core (elastic scaling), that core
+/// is used as long as it is assigned to our para at the relay-parent.
 /// Issue a connection request to these validators.
 /// If the para is not scheduled or next up on any core, at the relay-parent,
 /// or the relay-parent isn't in the active-leaves set, we ignore the message
@@ -334,6 +336,7 @@ async fn distribute_collation<Context>(
 	parent_head_data_hash: Hash,
 	pov: PoV,
 	result_sender: Option<oneshot::Sender<CollationSecondedSignal>>,
+	core_index: Option<CoreIndex>,
 ) -> Result<()> {
 	let candidate_relay_parent = receipt.descriptor.relay_parent;
 	let candidate_hash = receipt.hash();
@@ -353,22 +356,6 @@ async fn distribute_collation<Context>(
 	};
 	let relay_parent_mode = per_relay_parent.prospective_parachains_mode;
 
-	let collations_limit = match relay_parent_mode {
-		ProspectiveParachainsMode::Disabled => 1,
-		ProspectiveParachainsMode::Enabled { max_candidate_depth, .. } => max_candidate_depth + 1,
-	};
-
-	if per_relay_parent.collations.len() >= collations_limit {
-		gum::debug!(
-			target: LOG_TARGET,
-			?candidate_relay_parent,
-			?relay_parent_mode,
-			"The limit of {} collations per relay parent is already reached",
-			collations_limit,
-		);
-		return Ok(())
-	}
-
 	// We have already seen collation for this relay parent.
 	if per_relay_parent.collations.contains_key(&candidate_hash) {
 		gum::debug!(
@@ -380,22 +367,56 @@ async fn distribute_collation<Context>(
 		return Ok(())
 	}
 
-	// Determine which core the para collated-on is assigned to.
+	// Determine which cores the para collated-on is assigned to.
 	// If it is not scheduled then ignore the message.
-	let (our_core, num_cores) =
-		match determine_core(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await? {
-			Some(core) => core,
-			None => {
-				gum::warn!(
-					target: LOG_TARGET,
-					para_id = %id,
-					"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
-				);
+	let (assigned_cores, num_cores) =
+		determine_cores(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await?;
 
-				return Ok(())
-			},
+	let our_core = match (core_index, assigned_cores.first()) {
+		(_, None) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				para_id = %id,
+				"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
+			);
+
+			return Ok(())
+		},
+		(Some(core_index), Some(_)) if !assigned_cores.contains(&core_index) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				para_id = %id,
+				?core_index,
+				?assigned_cores,
+				"core {:?} is not assigned to {} at {}", core_index, id, candidate_relay_parent,
+			);
+
+			return Ok(())
+		},
+		(Some(core_index), Some(_)) => core_index,
+		(None, Some(first)) => *first,
+	};
+
+	// Every core assigned to the para may back its own chain of candidates
+	// built on top of this relay parent.
+	let collations_limit = assigned_cores.len() *
+		match relay_parent_mode {
+			ProspectiveParachainsMode::Disabled => 1,
+			ProspectiveParachainsMode::Enabled { max_candidate_depth, .. } =>
+				max_candidate_depth + 1,
 		};
 
+	if per_relay_parent.collations.len() >= collations_limit {
+		gum::debug!(
+			target: LOG_TARGET,
+			?candidate_relay_parent,
+			?relay_parent_mode,
+			"The limit of {} collations per relay parent is already reached",
+			collations_limit,
+		);
+		return Ok(())
+	}
+
 	// Determine the group on that core.
 	//
 	// When prospective parachains are disabled, candidate relay parent here is
@@ -492,15 +513,16 @@ async fn distribute_collation<Context>(
 	Ok(())
 }
 
-/// Get the Id of the Core that is assigned to the para being collated on if any
+/// Get the Ids of all the Cores that are assigned to the para being collated on
 /// and the total number of cores.
-async fn determine_core(
+async fn determine_cores(
 	sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	para_id: ParaId,
 	relay_parent: Hash,
 	relay_parent_mode: ProspectiveParachainsMode,
-) -> Result<Option<(CoreIndex, usize)>> {
+) -> Result<(Vec<CoreIndex>, usize)> {
 	let cores = get_availability_cores(sender, relay_parent).await?;
+	let mut assigned = Vec::new();
 
 	for (idx, core) in cores.iter().enumerate() {
 		let core_para_id = match core {
@@ -517,11 +539,11 @@ async fn determine_core(
 		};
 
 		if core_para_id == Some(para_id) {
-			return Ok(Some(((idx as u32).into(), cores.len())))
+			assigned.push((idx as u32).into());
 		}
 	}
 
-	Ok(None)
+	Ok((assigned, cores.len()))
 }
 
 /// Validators of a particular group index.
@@ -757,7 +779,13 @@ async fn process_msg<Context>(
 		CollateOn(id) => {
 			state.collating_on = Some(id);
 		},
-		DistributeCollation(receipt, parent_head_data_hash, pov, result_sender) => {
+		DistributeCollation {
+			candidate_receipt: receipt,
+			parent_head_data_hash,
+			pov,
+			result_sender,
+			core_index,
+		} => {
 			let _span1 = state
 				.span_per_relay_parent
 				.get(&receipt.descriptor.relay_parent)
@@ -786,6 +814,7 @@ async fn process_msg<Context>(
 						parent_head_data_hash,
 						pov,
 						result_sender,
+						core_index,
 					)
 					.await?;
 				},
@@ -272,12 +272,13 @@ fn distribute_collation_from_implicit_view() {
 			.build();
 			overseer_send(
 				virtual_overseer,
-				CollatorProtocolMessage::DistributeCollation(
-					candidate.clone(),
+				CollatorProtocolMessage::DistributeCollation {
+					candidate_receipt: candidate.clone(),
 					parent_head_data_hash,
-					pov.clone(),
-					None,
-				),
+					pov: pov.clone(),
+					result_sender: None,
+					core_index: None,
+				},
 			)
 			.await;
 
@@ -352,15 +353,28 @@ fn distribute_collation_up_to_limit() {
 			.build();
 			overseer_send(
 				virtual_overseer,
-				CollatorProtocolMessage::DistributeCollation(
-					candidate.clone(),
+				CollatorProtocolMessage::DistributeCollation {
+					candidate_receipt: candidate.clone(),
 					parent_head_data_hash,
-					pov.clone(),
-					None,
-				),
+					pov: pov.clone(),
+					result_sender: None,
+					core_index: None,
+				},
 			)
 			.await;
 
+			// The limit depends on the number of cores assigned to the para.
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_relay_parent,
+					RuntimeApiRequest::AvailabilityCores(tx)
+				)) => {
+					assert_eq!(head_b, _relay_parent);
+					tx.send(Ok(test_state.availability_cores.clone())).unwrap();
+				}
+			);
+
 			// Limit has been reached.
 			assert!(overseer_recv_with_timeout(virtual_overseer, Duration::from_millis(100))
 				.await
@@ -371,6 +385,72 @@ fn distribute_collation_up_to_limit() {
 	)
 }
 
+/// Tests that collator ignores a collation built for a core not assigned to the para.
+#[test]
+fn distribute_collation_for_unassigned_core() {
+	let test_state = TestState::default();
+
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			let head_a = Hash::from_low_u64_be(128);
+			let head_a_num: u32 = 64;
+
+			// Set collating para id.
+			overseer_send(virtual_overseer, CollatorProtocolMessage::CollateOn(test_state.para_id))
+				.await;
+			update_view(virtual_overseer, &test_state, vec![(head_a, head_a_num)], 1).await;
+
+			let pov = PoV { block_data: BlockData(vec![1, 2, 3]) };
+			let parent_head_data_hash = Hash::repeat_byte(0xAA);
+			let candidate = TestCandidateBuilder {
+				para_id: test_state.para_id,
+				relay_parent: head_a,
+				pov_hash: pov.hash(),
+				..Default::default()
+			}
+			.build();
+			// The second core is free.
+			overseer_send(
+				virtual_overseer,
+				CollatorProtocolMessage::DistributeCollation {
+					candidate_receipt: candidate.clone(),
+					parent_head_data_hash,
+					pov: pov.clone(),
+					result_sender: None,
+					core_index: Some(CoreIndex(1)),
+				},
+			)
+			.await;
+
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_relay_parent,
+					RuntimeApiRequest::AvailabilityCores(tx)
+				)) => {
+					assert_eq!(head_a, _relay_parent);
+					tx.send(Ok(test_state.availability_cores.clone())).unwrap();
+				}
+			);
+
+			// The collation is dropped.
+			assert!(overseer_recv_with_timeout(virtual_overseer, Duration::from_millis(100))
+				.await
+				.is_none());
+
+			test_harness
+		},
+	)
+}
+
 /// Tests that collator correctly handles peer V2 requests.
 #[test]
 fn advertise_and_send_collation_by_hash() {
@@ -1457,7 +1457,7 @@ async fn process_msg<Context>(
 				"CollateOn message is not expected on the validator side of the protocol",
 			);
 		},
-		DistributeCollation(..) => {
+		DistributeCollation { .. } => {
 			gum::warn!(
 				target: LOG_TARGET,
 				"DistributeCollation message is not expected on the validator side of the protocol",
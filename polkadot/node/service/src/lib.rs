@@ -497,8 +497,13 @@ fn new_partial<ChainSelection>(
 where
 	ChainSelection: 'static + SelectChain<Block>,
 {
+	let mut transaction_pool_options = config.transaction_pool.clone();
+	transaction_pool_options.sender_of = Some(sc_transaction_pool::SenderOf::unchecked_extrinsic::<
+		sp_runtime::MultiAddress<polkadot_primitives::AccountId, ()>,
+	>());
+
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
-		config.transaction_pool.clone(),
+		transaction_pool_options,
 		config.role.is_authority().into(),
 		config.prometheus_registry(),
 		task_manager.spawn_essential_handle(),
@@ -191,16 +191,20 @@ pub enum CollatorProtocolMessage {
 	/// This should be sent before any `DistributeCollation` message.
 	CollateOn(ParaId),
 	/// Provide a collation to distribute to validators with an optional result sender.
-	/// The second argument is the parent head-data hash.
-	///
-	/// The result sender should be informed when at least one parachain validator seconded the
-	/// collation. It is also completely okay to just drop the sender.
-	DistributeCollation(
-		CandidateReceipt,
-		Hash,
-		PoV,
-		Option<oneshot::Sender<CollationSecondedSignal>>,
-	),
+	DistributeCollation {
+		/// The receipt of the candidate.
+		candidate_receipt: CandidateReceipt,
+		/// The hash of the parent head-data.
+		parent_head_data_hash: Hash,
+		/// Proof of validity.
+		pov: PoV,
+		/// The result sender should be informed when at least one parachain validator seconded
+		/// the collation. It is also completely okay to just drop the sender.
+		result_sender: Option<oneshot::Sender<CollationSecondedSignal>>,
+		/// The core the collation is meant to be backed on. If `None`, the first core assigned
+		/// to the para at the candidate's relay parent is used.
+		core_index: Option<CoreIndex>,
+	},
 	/// Report a collator as having provided an invalid collation. This should lead to disconnect
 	/// and blacklist of the collator.
 	ReportCollator(CollatorId),
@@ -1096,14 +1100,19 @@ pub enum ProspectiveParachainsMessage {
 	/// has been backed. This requires that the candidate was successfully introduced in
 	/// the past.
 	CandidateBacked(ParaId, CandidateHash),
-	/// Get a backable candidate hash along with its relay parent for the given parachain,
-	/// under the given relay-parent hash, which is a descendant of the given candidate hashes.
-	/// Returns `None` on the channel if no such candidate exists.
-	GetBackableCandidate(
+	/// Get up to `count` backable candidate hashes along with their relay parents for the given
+	/// parachain, under the given relay-parent hash, which form a chain descending from the given
+	/// candidate hashes. The candidates are returned in order, each one building on the previous.
+	///
+	/// Requesting more than one candidate allows a parachain assigned to multiple cores to have
+	/// a chain of candidates backed in parallel. Returns an empty vector on the channel if no such
+	/// candidate exists.
+	GetBackableCandidates(
 		Hash,
 		ParaId,
+		u32,
 		Vec<CandidateHash>,
-		oneshot::Sender<Option<(CandidateHash, Hash)>>,
+		oneshot::Sender<Vec<(CandidateHash, Hash)>>,
 	),
 	/// Get the hypothetical frontier membership of candidates with the given properties
 	/// under the specified active leaves' fragment trees.
@@ -383,6 +383,19 @@ pub mod pallet {
 	pub(crate) type PendingAvailabilityCommitments<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, CandidateCommitments>;
 
+	/// Candidates pending availability which were backed on top of the candidate in
+	/// `PendingAvailability` of the same para, in chain order and with their commitments.
+	///
+	/// Only paras that are scheduled on multiple cores at once have entries here. They are
+	/// enacted after the candidate in `PendingAvailability` and in order.
+	#[pallet::storage]
+	pub(crate) type PendingAvailabilityChain<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ParaId,
+		Vec<(CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>, CandidateCommitments)>,
+	>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
 }
@@ -472,6 +485,7 @@ impl<T: Config> Pallet<T> {
 		// and require consumption.
 		for _ in <PendingAvailabilityCommitments<T>>::drain() {}
 		for _ in <PendingAvailability<T>>::drain() {}
+		for _ in <PendingAvailabilityChain<T>>::drain() {}
 		for _ in <AvailabilityBitfields<T>>::drain() {}
 
 		Self::cleanup_outgoing_ump_dispatch_queues(outgoing_paras);
@@ -491,7 +505,8 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Bitfields are expected to have been sanitized already. E.g. via `sanitize_bitfields`!
 	///
-	/// Updates storage items `PendingAvailability` and `AvailabilityBitfields`.
+	/// Updates storage items `PendingAvailability`, `PendingAvailabilityChain` and
+	/// `AvailabilityBitfields`.
 	///
 	/// Returns a `Vec` of `CandidateHash`es and their respective `AvailabilityCore`s that became
 	/// available, and cores free.
@@ -504,13 +519,32 @@ impl<T: Config> Pallet<T> {
 	where
 		F: Fn(CoreIndex) -> Option<ParaId>,
 	{
-		let mut assigned_paras_record = (0..expected_bits)
+		let assigned_paras = (0..expected_bits)
 			.map(|bit_index| core_lookup(CoreIndex::from(bit_index as u32)))
-			.map(|opt_para_id| {
-				opt_para_id.map(|para_id| (para_id, PendingAvailability::<T>::get(&para_id)))
-			})
 			.collect::<Vec<_>>();
 
+		// The candidates pending availability of each of the assigned paras, in chain order.
+		let mut paras_record = BTreeMap::new();
+		for para_id in assigned_paras.iter().flatten() {
+			if paras_record.contains_key(para_id) {
+				continue
+			}
+			let Some(pending_availability) = PendingAvailability::<T>::get(para_id) else {
+				continue
+			};
+			let Some(commitments) = PendingAvailabilityCommitments::<T>::get(para_id) else {
+				log::warn!(
+					target: LOG_TARGET,
+					"Inclusion::process_bitfields: PendingAvailability and PendingAvailabilityCommitments
+					are out of sync, did someone mess with the storage?",
+				);
+				continue
+			};
+			let mut chain = vec![(pending_availability, commitments)];
+			chain.extend(PendingAvailabilityChain::<T>::get(para_id).unwrap_or_default());
+			paras_record.insert(*para_id, chain);
+		}
+
 		let now = <frame_system::Pallet<T>>::block_number();
 		for (checked_bitfield, validator_index) in
 			signed_bitfields.into_iter().map(|signed_bitfield| {
@@ -519,10 +553,11 @@ impl<T: Config> Pallet<T> {
 				(checked_bitfield, validator_idx)
 			}) {
 			for (bit_idx, _) in checked_bitfield.0.iter().enumerate().filter(|(_, is_av)| **is_av) {
-				let pending_availability = if let Some((_, pending_availability)) =
-					assigned_paras_record[bit_idx].as_mut()
+				let core_index = CoreIndex::from(bit_idx as u32);
+				let pending_availability = if let Some(chain) =
+					assigned_paras[bit_idx].and_then(|para_id| paras_record.get_mut(&para_id))
 				{
-					pending_availability
+					chain.iter_mut().find(|(pending, _)| pending.core == core_index)
 				} else {
 					// For honest validators, this happens in case of unoccupied cores,
 					// which in turn happens in case of a disputed candidate.
@@ -535,7 +570,7 @@ impl<T: Config> Pallet<T> {
 				// record, which is always `Some` if the core is occupied - that's why we're here.
 				let validator_index = validator_index.0 as usize;
 				if let Some(mut bit) =
-					pending_availability.as_mut().and_then(|candidate_pending_availability| {
+					pending_availability.and_then(|(candidate_pending_availability, _)| {
 						candidate_pending_availability.availability_votes.get_mut(validator_index)
 					}) {
 					*bit = true;
@@ -551,25 +586,17 @@ impl<T: Config> Pallet<T> {
 		let threshold = availability_threshold(validators.len());
 
 		let mut freed_cores = Vec::with_capacity(expected_bits);
-		for (para_id, pending_availability) in assigned_paras_record
-			.into_iter()
-			.flatten()
-			.filter_map(|(id, p)| p.map(|p| (id, p)))
-		{
-			if pending_availability.availability_votes.count_ones() >= threshold {
-				<PendingAvailability<T>>::remove(&para_id);
-				let commitments = match PendingAvailabilityCommitments::<T>::take(&para_id) {
-					Some(commitments) => commitments,
-					None => {
-						log::warn!(
-							target: LOG_TARGET,
-							"Inclusion::process_bitfields: PendingAvailability and PendingAvailabilityCommitments
-							are out of sync, did someone mess with the storage?",
-						);
-						continue
-					},
-				};
+		for (para_id, chain) in paras_record {
+			// The candidates of a chain can only be enacted in order, so a candidate which became
+			// available before its predecessors keeps waiting for them.
+			let n_available = chain
+				.iter()
+				.take_while(|(pending, _)| pending.availability_votes.count_ones() >= threshold)
+				.count();
+			let had_chained = chain.len() > 1;
 
+			let mut chain = chain.into_iter();
+			for (pending_availability, commitments) in chain.by_ref().take(n_available) {
 				let receipt = CommittedCandidateReceipt {
 					descriptor: pending_availability.descriptor,
 					commitments,
@@ -584,8 +611,25 @@ impl<T: Config> Pallet<T> {
 				);
 
 				freed_cores.push((pending_availability.core, pending_availability.hash));
-			} else {
-				<PendingAvailability<T>>::insert(&para_id, &pending_availability);
+			}
+
+			match chain.next() {
+				Some((pending_availability, commitments)) => {
+					<PendingAvailability<T>>::insert(&para_id, &pending_availability);
+					if n_available > 0 {
+						<PendingAvailabilityCommitments<T>>::insert(&para_id, commitments);
+					}
+				},
+				None => {
+					<PendingAvailability<T>>::remove(&para_id);
+					<PendingAvailabilityCommitments<T>>::remove(&para_id);
+				},
+			}
+			let chained = chain.collect::<Vec<_>>();
+			if !chained.is_empty() {
+				<PendingAvailabilityChain<T>>::insert(&para_id, chained);
+			} else if had_chained {
+				<PendingAvailabilityChain<T>>::remove(&para_id);
 			}
 		}
 
@@ -597,10 +641,15 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Both should be sorted ascending by core index, and the candidates should be a subset of
 	/// scheduled cores. If these conditions are not met, the execution of the function fails.
+	///
+	/// A para that is scheduled on several cores may have a chain of candidates, each building
+	/// on the head of the previous one. They are assigned to the cores of the para in ascending
+	/// order. The first one builds on the last candidate pending availability of the para, if
+	/// there is any.
 	pub(crate) fn process_candidates<GV>(
 		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
 		candidates: Vec<BackedCandidate<T::Hash>>,
-		scheduled: &BTreeMap<ParaId, CoreIndex>,
+		scheduled: &BTreeMap<ParaId, BTreeSet<CoreIndex>>,
 		group_validators: GV,
 	) -> Result<ProcessedCandidates<T::Hash>, DispatchError>
 	where
@@ -608,7 +657,10 @@ impl<T: Config> Pallet<T> {
 	{
 		let now = <frame_system::Pallet<T>>::block_number();
 
-		ensure!(candidates.len() <= scheduled.len(), Error::<T>::UnscheduledCandidate);
+		ensure!(
+			candidates.len() <= scheduled.values().map(BTreeSet::len).sum(),
+			Error::<T>::UnscheduledCandidate
+		);
 
 		if scheduled.is_empty() {
 			return Ok(ProcessedCandidates::default())
@@ -625,6 +677,8 @@ impl<T: Config> Pallet<T> {
 		let core_indices_and_backers = {
 			let mut core_indices_and_backers = Vec::with_capacity(candidates.len());
 			let mut last_core = None;
+			// The number of candidates of each para so far and the head of the last one.
+			let mut para_chains = BTreeMap::<ParaId, (usize, HeadData)>::new();
 
 			let mut check_assignment_in_order = |core_idx| -> DispatchResult {
 				ensure!(
@@ -658,10 +712,18 @@ impl<T: Config> Pallet<T> {
 					session_index: shared::Pallet::<T>::session_index(),
 				};
 
+				// The first candidate of a para builds upon its last candidate pending
+				// availability, if any.
+				let (chain_len, chained_upon) = match para_chains.get(&para_id) {
+					Some((len, head)) => (*len, Some(head.clone())),
+					None => (0, Self::pending_availability_head(para_id)),
+				};
+
 				let relay_parent_number = match check_ctx.verify_backed_candidate(
 					&allowed_relay_parents,
 					candidate_idx,
 					backed_candidate,
+					chained_upon.as_ref(),
 				)? {
 					Err(FailedToCreatePVD) => {
 						log::debug!(
@@ -680,11 +742,17 @@ impl<T: Config> Pallet<T> {
 				let para_id = backed_candidate.descriptor().para_id;
 				let mut backers = bitvec::bitvec![u8, BitOrderLsb0; 0; validators.len()];
 
-				let core_idx = *scheduled.get(&para_id).ok_or(Error::<T>::UnscheduledCandidate)?;
+				// Each candidate of a chain occupies a distinct core of its para.
+				let core_idx = scheduled
+					.get(&para_id)
+					.and_then(|cores| cores.iter().nth(chain_len))
+					.copied()
+					.ok_or(Error::<T>::UnscheduledCandidate)?;
 				check_assignment_in_order(core_idx)?;
 				ensure!(
-					<PendingAvailability<T>>::get(&para_id).is_none() &&
-						<PendingAvailabilityCommitments<T>>::get(&para_id).is_none(),
+					chained_upon.is_some() ||
+						(<PendingAvailability<T>>::get(&para_id).is_none() &&
+							<PendingAvailabilityCommitments<T>>::get(&para_id).is_none()),
 					Error::<T>::CandidateScheduledBeforeParaFree,
 				);
 
@@ -762,7 +830,12 @@ impl<T: Config> Pallet<T> {
 					backers,
 					group_idx,
 					relay_parent_number,
+					chained_upon.is_some(),
 				));
+				para_chains.insert(
+					para_id,
+					(chain_len + 1, backed_candidate.candidate.commitments.head_data.clone()),
+				);
 			}
 
 			core_indices_and_backers
@@ -770,7 +843,8 @@ impl<T: Config> Pallet<T> {
 
 		// one more sweep for actually writing to storage.
 		let core_indices = core_indices_and_backers.iter().map(|(c, ..)| *c).collect();
-		for (candidate, (core, backers, group, relay_parent_number)) in
+		let mut chained = BTreeMap::<ParaId, Vec<_>>::new();
+		for (candidate, (core, backers, group, relay_parent_number, is_chained)) in
 			candidates.into_iter().zip(core_indices_and_backers)
 		{
 			let para_id = candidate.descriptor().para_id;
@@ -791,20 +865,27 @@ impl<T: Config> Pallet<T> {
 			let (descriptor, commitments) =
 				(candidate.candidate.descriptor, candidate.candidate.commitments);
 
-			<PendingAvailability<T>>::insert(
-				&para_id,
-				CandidatePendingAvailability {
-					core: core.0,
-					hash: candidate_hash,
-					descriptor,
-					availability_votes,
-					relay_parent_number,
-					backers: backers.to_bitvec(),
-					backed_in_number: now,
-					backing_group: group,
-				},
-			);
-			<PendingAvailabilityCommitments<T>>::insert(&para_id, commitments);
+			let pending_availability = CandidatePendingAvailability {
+				core: core.0,
+				hash: candidate_hash,
+				descriptor,
+				availability_votes,
+				relay_parent_number,
+				backers: backers.to_bitvec(),
+				backed_in_number: now,
+				backing_group: group,
+			};
+			if is_chained {
+				chained.entry(para_id).or_default().push((pending_availability, commitments));
+			} else {
+				<PendingAvailability<T>>::insert(&para_id, pending_availability);
+				<PendingAvailabilityCommitments<T>>::insert(&para_id, commitments);
+			}
+		}
+		for (para_id, chain) in chained {
+			<PendingAvailabilityChain<T>>::mutate(&para_id, |pending| {
+				pending.get_or_insert_with(Vec::new).extend(chain)
+			});
 		}
 
 		Ok(ProcessedCandidates::<T::Hash> {
@@ -813,6 +894,22 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Returns the head data of the last candidate pending availability of the para, if any.
+	///
+	/// Candidates backed for a para with candidates pending availability have to build upon it.
+	pub(crate) fn pending_availability_head(para_id: ParaId) -> Option<HeadData> {
+		if !<PendingAvailability<T>>::contains_key(&para_id) {
+			return None
+		}
+
+		<PendingAvailabilityChain<T>>::get(&para_id)
+			.and_then(|chain| chain.last().map(|(_, commitments)| commitments.head_data.clone()))
+			.or_else(|| {
+				<PendingAvailabilityCommitments<T>>::get(&para_id)
+					.map(|commitments| commitments.head_data)
+			})
+	}
+
 	/// Run the acceptance criteria checks on the given candidate commitments.
 	pub(crate) fn check_validation_outputs_for_runtime_api(
 		para_id: ParaId,
@@ -1038,9 +1135,12 @@ impl<T: Config> Pallet<T> {
 		for para_id in cleaned_up_ids {
 			let pending = <PendingAvailability<T>>::take(&para_id);
 			let commitments = <PendingAvailabilityCommitments<T>>::take(&para_id);
+			// The candidates chained upon a timed out one can't be enacted anymore either.
+			let chained = <PendingAvailabilityChain<T>>::take(&para_id).unwrap_or_default();
+			cleaned_up_cores.extend(chained.iter().map(|(pending, _)| pending.core));
 
-			if let (Some(pending), Some(commitments)) = (pending, commitments) {
-				// defensive: this should always be true.
+			// defensive: the zip should always yield the head of the chain.
+			for (pending, commitments) in pending.zip(commitments).into_iter().chain(chained) {
 				let candidate = CandidateReceipt {
 					descriptor: pending.descriptor,
 					commitments_hash: commitments.hash(),
@@ -1074,6 +1174,25 @@ impl<T: Config> Pallet<T> {
 		for para_id in cleaned_up_ids {
 			let _ = <PendingAvailability<T>>::take(&para_id);
 			let _ = <PendingAvailabilityCommitments<T>>::take(&para_id);
+			// The candidates chained upon a disputed one are dropped as well.
+			let chained = <PendingAvailabilityChain<T>>::take(&para_id).unwrap_or_default();
+			cleaned_up_cores.extend(chained.into_iter().map(|(pending, _)| pending.core));
+		}
+
+		// A disputed candidate further down a chain is dropped together with its successors.
+		let truncated = <PendingAvailabilityChain<T>>::iter()
+			.filter_map(|(para_id, chained)| {
+				let pos = chained.iter().position(|(pending, _)| disputed.contains(&pending.hash))?;
+				Some((para_id, chained, pos))
+			})
+			.collect::<Vec<_>>();
+		for (para_id, mut chained, pos) in truncated {
+			cleaned_up_cores.extend(chained.drain(pos..).map(|(pending, _)| pending.core));
+			if chained.is_empty() {
+				<PendingAvailabilityChain<T>>::remove(&para_id);
+			} else {
+				<PendingAvailabilityChain<T>>::insert(&para_id, chained);
+			}
 		}
 
 		cleaned_up_cores
@@ -1089,6 +1208,17 @@ impl<T: Config> Pallet<T> {
 		let pending = <PendingAvailability<T>>::take(&para);
 		let commitments = <PendingAvailabilityCommitments<T>>::take(&para);
 
+		// The next candidate of a chain, if any, is now the one pending availability.
+		let mut chained = <PendingAvailabilityChain<T>>::take(&para).unwrap_or_default();
+		if !chained.is_empty() {
+			let (next, next_commitments) = chained.remove(0);
+			<PendingAvailability<T>>::insert(&para, next);
+			<PendingAvailabilityCommitments<T>>::insert(&para, next_commitments);
+			if !chained.is_empty() {
+				<PendingAvailabilityChain<T>>::insert(&para, chained);
+			}
+		}
+
 		if let (Some(pending), Some(commitments)) = (pending, commitments) {
 			let candidate =
 				CommittedCandidateReceipt { descriptor: pending.descriptor, commitments };
@@ -1121,6 +1251,23 @@ impl<T: Config> Pallet<T> {
 	) -> Option<CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>> {
 		<PendingAvailability<T>>::get(&para)
 	}
+
+	/// Returns the metadata around the candidate of the para provided which is pending
+	/// availability on the given core, if any.
+	///
+	/// Unlike [`Self::pending_availability`], this also covers the candidates that were chained
+	/// upon the one pending availability.
+	pub(crate) fn pending_availability_on_core(
+		para: ParaId,
+		core: CoreIndex,
+	) -> Option<CandidatePendingAvailability<T::Hash, BlockNumberFor<T>>> {
+		<PendingAvailability<T>>::get(&para).filter(|pending| pending.core == core).or_else(|| {
+			<PendingAvailabilityChain<T>>::get(&para)?
+				.into_iter()
+				.map(|(pending, _)| pending)
+				.find(|pending| pending.core == core)
+		})
+	}
 }
 
 const fn availability_threshold(n_validators: usize) -> usize {
@@ -1186,12 +1333,16 @@ impl<T: Config> CandidateCheckContext<T> {
 	///  * code hash of commitments matches current code hash
 	///  * para head in the descriptor and commitments match
 	///
+	/// The candidate is expected to build on the current head of the para, or on `chained_upon`
+	/// if it is part of a chain of candidates backed in the same block.
+	///
 	/// Returns the relay-parent block number.
 	pub(crate) fn verify_backed_candidate(
 		&self,
 		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
 		candidate_idx: usize,
 		backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>,
+		chained_upon: Option<&HeadData>,
 	) -> Result<Result<BlockNumberFor<T>, FailedToCreatePVD>, Error<T>> {
 		let para_id = backed_candidate.descriptor().para_id;
 		let relay_parent = backed_candidate.descriptor().relay_parent;
@@ -1205,16 +1356,20 @@ impl<T: Config> CandidateCheckContext<T> {
 		};
 
 		{
-			let persisted_validation_data = match crate::util::make_persisted_validation_data::<T>(
-				para_id,
-				relay_parent_number,
-				relay_parent_storage_root,
-			)
-			.defensive_proof("the para is registered")
-			{
-				Some(l) => l,
-				None => return Ok(Err(FailedToCreatePVD)),
-			};
+			let mut persisted_validation_data =
+				match crate::util::make_persisted_validation_data::<T>(
+					para_id,
+					relay_parent_number,
+					relay_parent_storage_root,
+				)
+				.defensive_proof("the para is registered")
+				{
+					Some(l) => l,
+					None => return Ok(Err(FailedToCreatePVD)),
+				};
+			if let Some(parent_head) = chained_upon {
+				persisted_validation_data.parent_head = parent_head.clone();
+			}
 
 			let expected = persisted_validation_data.hash();
 
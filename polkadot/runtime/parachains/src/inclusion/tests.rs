@@ -333,6 +333,17 @@ fn make_vdata_hash_with_block_number(
 	Some(persisted_validation_data.hash())
 }
 
+/// The cores scheduled for each para, given `(para, core)` assignments.
+fn scheduled_cores(
+	assignments: impl IntoIterator<Item = (ParaId, CoreIndex)>,
+) -> BTreeMap<ParaId, BTreeSet<CoreIndex>> {
+	let mut scheduled = BTreeMap::<_, BTreeSet<_>>::new();
+	for (para_id, core) in assignments {
+		scheduled.entry(para_id).or_default().insert(core);
+	}
+	scheduled
+}
+
 /// Wrapper around `sanitize_bitfields` with less parameters.
 fn simple_sanitize_bitfields(
 	unchecked_bitfields: UncheckedSignedAvailabilityBitfields,
@@ -945,7 +956,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_b_assignment].into_iter().collect(),
+					&scheduled_cores([chain_b_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::UnscheduledCandidate
@@ -1000,7 +1011,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed_b, backed_a],
-					&[chain_a_assignment, chain_b_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment, chain_b_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::ScheduledOutOfOrder
@@ -1033,7 +1044,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::InsufficientBacking
@@ -1090,7 +1101,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed_b, backed_a],
-					&[chain_a_assignment, chain_b_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment, chain_b_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::DisallowedRelayParent
@@ -1128,14 +1139,14 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[thread_a_assignment].into_iter().collect(),
+					&scheduled_cores([thread_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::NotCollatorSigned
 			);
 		}
 
-		// para occupied - reject.
+		// para occupied, candidate not building upon the candidate pending availability - reject.
 		{
 			let mut candidate = TestCandidateBuilder {
 				para_id: chain_a,
@@ -1158,7 +1169,9 @@ fn candidate_checks() {
 				BackingKind::Threshold,
 			);
 
-			let candidate = TestCandidateBuilder::default().build();
+			let candidate =
+				TestCandidateBuilder { head_data: vec![1, 2, 3].into(), ..Default::default() }
+					.build();
 			<PendingAvailability<Test>>::insert(
 				&chain_a,
 				CandidatePendingAvailability {
@@ -1178,10 +1191,10 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
-				Error::<Test>::CandidateScheduledBeforeParaFree
+				Error::<Test>::ValidationDataHashMismatch
 			);
 
 			<PendingAvailability<Test>>::remove(&chain_a);
@@ -1218,7 +1231,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::CandidateScheduledBeforeParaFree
@@ -1262,7 +1275,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::PrematureCodeUpgrade
@@ -1296,7 +1309,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Err(Error::<Test>::ValidationDataHashMismatch.into()),
@@ -1331,7 +1344,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::InvalidValidationCodeHash
@@ -1366,7 +1379,7 @@ fn candidate_checks() {
 				ParaInclusion::process_candidates(
 					&allowed_relay_parents,
 					vec![backed],
-					&[chain_a_assignment].into_iter().collect(),
+					&scheduled_cores([chain_a_assignment]),
 					&group_validators,
 				),
 				Error::<Test>::ParaHeadMismatch
@@ -1526,9 +1539,7 @@ fn backing_works() {
 		} = ParaInclusion::process_candidates(
 			&allowed_relay_parents,
 			backed_candidates.clone(),
-			&[chain_a_assignment, chain_b_assignment, thread_a_assignment]
-				.into_iter()
-				.collect(),
+			&scheduled_cores([chain_a_assignment, chain_b_assignment, thread_a_assignment]),
 			&group_validators,
 		)
 		.expect("candidates scheduled, in order, and backed");
@@ -1660,6 +1671,309 @@ fn backing_works() {
 	});
 }
 
+#[test]
+fn chained_candidates_are_backed_and_included_in_order() {
+	let chain_a = ParaId::from(1_u32);
+	let chain_b = ParaId::from(2_u32);
+	let thread_a = ParaId::from(3_u32);
+
+	// The block number of the relay-parent for testing.
+	const RELAY_PARENT_NUM: BlockNumber = 4;
+
+	let paras = vec![
+		(chain_a, ParaKind::Parachain),
+		(chain_b, ParaKind::Parachain),
+		(thread_a, ParaKind::Parathread),
+	];
+	let validators = vec![
+		Sr25519Keyring::Alice,
+		Sr25519Keyring::Bob,
+		Sr25519Keyring::Charlie,
+		Sr25519Keyring::Dave,
+		Sr25519Keyring::Ferdie,
+	];
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	for validator in validators.iter() {
+		Keystore::sr25519_generate_new(
+			&*keystore,
+			PARACHAIN_KEY_TYPE_ID,
+			Some(&validator.to_seed()),
+		)
+		.unwrap();
+	}
+	let validator_public = validator_pubkeys(&validators);
+
+	new_test_ext(genesis_config(paras)).execute_with(|| {
+		shared::Pallet::<Test>::set_active_validators_ascending(validator_public.clone());
+		shared::Pallet::<Test>::set_session_index(5);
+
+		run_to_block(5, |_| None);
+
+		let signing_context =
+			SigningContext { parent_hash: System::parent_hash(), session_index: 5 };
+
+		let group_validators = |group_index: GroupIndex| {
+			match group_index {
+				group_index if group_index == GroupIndex::from(0) => Some(vec![0, 1]),
+				group_index if group_index == GroupIndex::from(1) => Some(vec![2, 3]),
+				group_index if group_index == GroupIndex::from(2) => Some(vec![4]),
+				_ => panic!("Group index out of bounds for 2 parachains and 1 parathread core"),
+			}
+			.map(|vs| vs.into_iter().map(ValidatorIndex).collect::<Vec<_>>())
+		};
+
+		// When processing candidates, we compute the group index from scheduler.
+		let validator_groups = vec![
+			vec![ValidatorIndex(0), ValidatorIndex(1)],
+			vec![ValidatorIndex(2), ValidatorIndex(3)],
+			vec![ValidatorIndex(4)],
+		];
+		Scheduler::set_validator_groups(validator_groups);
+
+		let allowed_relay_parents = default_allowed_relay_parent_tracker();
+
+		// Chain A is scheduled on the first two cores.
+		let scheduled =
+			scheduled_cores([(chain_a, CoreIndex::from(0)), (chain_a, CoreIndex::from(1))]);
+
+		let mut candidate_a_1 = TestCandidateBuilder {
+			para_id: chain_a,
+			head_data: vec![1].into(),
+			relay_parent: System::parent_hash(),
+			pov_hash: Hash::repeat_byte(1),
+			persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
+			hrmp_watermark: RELAY_PARENT_NUM,
+			..Default::default()
+		}
+		.build();
+		collator_sign_candidate(Sr25519Keyring::One, &mut candidate_a_1);
+
+		// The second candidate builds on the head of the first one.
+		let mut candidate_a_2 = TestCandidateBuilder {
+			para_id: chain_a,
+			head_data: vec![2].into(),
+			relay_parent: System::parent_hash(),
+			pov_hash: Hash::repeat_byte(2),
+			persisted_validation_data_hash: {
+				let mut persisted_validation_data = crate::util::make_persisted_validation_data::<
+					Test,
+				>(
+					chain_a, RELAY_PARENT_NUM, Default::default()
+				)
+				.unwrap();
+				persisted_validation_data.parent_head = vec![1].into();
+				persisted_validation_data.hash()
+			},
+			hrmp_watermark: RELAY_PARENT_NUM,
+			..Default::default()
+		}
+		.build();
+		collator_sign_candidate(Sr25519Keyring::One, &mut candidate_a_2);
+
+		let backed_a_1 = back_candidate(
+			candidate_a_1.clone(),
+			&validators,
+			group_validators(GroupIndex::from(0)).unwrap().as_ref(),
+			&keystore,
+			&signing_context,
+			BackingKind::Threshold,
+		);
+		let backed_a_2 = back_candidate(
+			candidate_a_2.clone(),
+			&validators,
+			group_validators(GroupIndex::from(1)).unwrap().as_ref(),
+			&keystore,
+			&signing_context,
+			BackingKind::Threshold,
+		);
+
+		// The chain must start at the current head of the para.
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![backed_a_2.clone(), backed_a_1.clone()],
+				&scheduled,
+				&group_validators,
+			),
+			Error::<Test>::ValidationDataHashMismatch
+		);
+
+		// Each candidate of the chain needs a core of its own.
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![backed_a_1.clone(), backed_a_2.clone()],
+				&scheduled_cores([(chain_a, CoreIndex::from(0))]),
+				&group_validators,
+			),
+			Error::<Test>::UnscheduledCandidate
+		);
+
+		let ProcessedCandidates { core_indices: occupied_cores, .. } =
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![backed_a_1, backed_a_2],
+				&scheduled,
+				&group_validators,
+			)
+			.expect("candidates form a chain and are scheduled on distinct cores");
+
+		assert_eq!(
+			occupied_cores,
+			vec![(CoreIndex::from(0), chain_a), (CoreIndex::from(1), chain_a)]
+		);
+		assert_eq!(
+			<PendingAvailability<Test>>::get(&chain_a).map(|pending| pending.hash),
+			Some(candidate_a_1.hash())
+		);
+		assert_eq!(
+			<PendingAvailabilityChain<Test>>::get(&chain_a)
+				.unwrap()
+				.into_iter()
+				.map(|(pending, commitments)| (pending.core, pending.hash, commitments))
+				.collect::<Vec<_>>(),
+			vec![(CoreIndex::from(1), candidate_a_2.hash(), candidate_a_2.commitments.clone())]
+		);
+
+		// A candidate backed later on has to build upon the last candidate pending availability
+		// rather than upon the head of the para.
+		let make_candidate_a_3 = |parent_head: HeadData, pov_byte| {
+			let mut candidate = TestCandidateBuilder {
+				para_id: chain_a,
+				head_data: vec![3].into(),
+				relay_parent: System::parent_hash(),
+				pov_hash: Hash::repeat_byte(pov_byte),
+				persisted_validation_data_hash: {
+					let mut persisted_validation_data =
+						crate::util::make_persisted_validation_data::<Test>(
+							chain_a,
+							RELAY_PARENT_NUM,
+							Default::default(),
+						)
+						.unwrap();
+					persisted_validation_data.parent_head = parent_head;
+					persisted_validation_data.hash()
+				},
+				hrmp_watermark: RELAY_PARENT_NUM,
+				..Default::default()
+			}
+			.build();
+			collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
+			candidate
+		};
+		let candidate_a_3 = make_candidate_a_3(vec![2].into(), 3);
+		let back_a_3 = |candidate| {
+			back_candidate(
+				candidate,
+				&validators,
+				group_validators(GroupIndex::from(2)).unwrap().as_ref(),
+				&keystore,
+				&signing_context,
+				BackingKind::Threshold,
+			)
+		};
+
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![back_a_3(make_candidate_a_3(Vec::new().into(), 4))],
+				&scheduled_cores([(chain_a, CoreIndex::from(2))]),
+				&group_validators,
+			),
+			Error::<Test>::ValidationDataHashMismatch
+		);
+
+		let ProcessedCandidates { core_indices: occupied_cores, .. } =
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![back_a_3(candidate_a_3.clone())],
+				&scheduled_cores([(chain_a, CoreIndex::from(2))]),
+				&group_validators,
+			)
+			.expect("candidate builds upon the last candidate pending availability");
+
+		assert_eq!(occupied_cores, vec![(CoreIndex::from(2), chain_a)]);
+		assert_eq!(
+			<PendingAvailability<Test>>::get(&chain_a).map(|pending| pending.hash),
+			Some(candidate_a_1.hash())
+		);
+		assert_eq!(
+			<PendingAvailabilityChain<Test>>::get(&chain_a)
+				.unwrap()
+				.into_iter()
+				.map(|(pending, _)| (pending.core, pending.hash))
+				.collect::<Vec<_>>(),
+			vec![
+				(CoreIndex::from(1), candidate_a_2.hash()),
+				(CoreIndex::from(2), candidate_a_3.hash())
+			]
+		);
+
+		let core_lookup = |core| match core {
+			core if core == CoreIndex::from(0) ||
+				core == CoreIndex::from(1) ||
+				core == CoreIndex::from(2) =>
+				Some(chain_a),
+			_ => None,
+		};
+		let signed_bitfields = |core: usize| {
+			let mut bitfield = default_bitfield();
+			*bitfield.0.get_mut(core).unwrap() = true;
+			let signed_bitfields = validators
+				.iter()
+				.enumerate()
+				.map(|(i, key)| {
+					sign_bitfield(
+						&keystore,
+						key,
+						ValidatorIndex(i as _),
+						bitfield.clone(),
+						&signing_context,
+					)
+					.into()
+				})
+				.collect::<Vec<_>>();
+			simple_sanitize_bitfields(
+				signed_bitfields,
+				DisputedBitfield::zeros(expected_bits()),
+				expected_bits(),
+			)
+		};
+
+		// The second candidate becoming available first has to wait for the first one.
+		assert!(process_bitfields(expected_bits(), signed_bitfields(1), core_lookup).is_empty());
+		assert_eq!(Paras::para_head(&chain_a), Some(Vec::new().into()));
+		assert_eq!(
+			<PendingAvailabilityChain<Test>>::get(&chain_a).unwrap()[0]
+				.0
+				.availability_votes
+				.count_ones(),
+			validators.len()
+		);
+
+		// Once the first one is available, both are enacted in order. The third one stays pending
+		// availability.
+		assert_eq!(
+			process_bitfields(expected_bits(), signed_bitfields(0), core_lookup),
+			vec![
+				(CoreIndex::from(0), candidate_a_1.hash()),
+				(CoreIndex::from(1), candidate_a_2.hash())
+			]
+		);
+		assert_eq!(Paras::para_head(&chain_a), Some(vec![2].into()));
+		assert_eq!(
+			<PendingAvailability<Test>>::get(&chain_a).map(|pending| pending.hash),
+			Some(candidate_a_3.hash())
+		);
+		assert_eq!(
+			<PendingAvailabilityCommitments<Test>>::get(&chain_a),
+			Some(candidate_a_3.commitments.clone())
+		);
+		assert!(<PendingAvailabilityChain<Test>>::get(&chain_a).is_none());
+		assert_eq!(ParaInclusion::pending_availability_head(chain_a), Some(vec![3].into()));
+	});
+}
+
 #[test]
 fn can_include_candidate_with_ok_code_upgrade() {
 	let chain_a = ParaId::from(1_u32);
@@ -1740,7 +2054,7 @@ fn can_include_candidate_with_ok_code_upgrade() {
 			ParaInclusion::process_candidates(
 				&allowed_relay_parents,
 				vec![backed_a],
-				&[chain_a_assignment].into_iter().collect(),
+				&scheduled_cores([chain_a_assignment]),
 				&group_validators,
 			)
 			.expect("candidates scheduled, in order, and backed");
@@ -1951,9 +2265,7 @@ fn check_allowed_relay_parents() {
 		ParaInclusion::process_candidates(
 			&allowed_relay_parents,
 			backed_candidates.clone(),
-			&[chain_a_assignment, chain_b_assignment, thread_a_assignment]
-				.into_iter()
-				.collect(),
+			&scheduled_cores([chain_a_assignment, chain_b_assignment, thread_a_assignment]),
 			&group_validators,
 		)
 		.expect("candidates scheduled, in order, and backed");
@@ -2189,7 +2501,7 @@ fn para_upgrade_delay_scheduled_from_inclusion() {
 			ParaInclusion::process_candidates(
 				&allowed_relay_parents,
 				vec![backed_a],
-				&[chain_a_assignment].into_iter().collect(),
+				&scheduled_cores([chain_a_assignment]),
 				&group_validators,
 			)
 			.expect("candidates scheduled, in order, and backed");
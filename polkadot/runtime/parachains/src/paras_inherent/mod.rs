@@ -43,7 +43,7 @@ use frame_system::pallet_prelude::*;
 use pallet_babe::{self, ParentBlockRandomness};
 use primitives::{
 	BackedCandidate, CandidateHash, CandidateReceipt, CheckedDisputeStatementSet,
-	CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatementSet,
+	CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatementSet, HeadData,
 	InherentData as ParachainsInherentData, MultiDisputeStatementSet, ScrapedOnChainVotes,
 	SessionIndex, SignedAvailabilityBitfields, SigningContext, UncheckedSignedAvailabilityBitfield,
 	UncheckedSignedAvailabilityBitfields, ValidatorId, ValidatorIndex, ValidityAttestation,
@@ -581,16 +581,18 @@ impl<T: Config> Pallet<T> {
 		let freed = collect_all_freed_cores::<T, _>(freed_concluded.iter().cloned());
 
 		<scheduler::Pallet<T>>::update_claimqueue(freed, now);
-		let scheduled = <scheduler::Pallet<T>>::scheduled_paras()
-			.map(|(core_idx, para_id)| (para_id, core_idx))
-			.collect();
+		let mut scheduled = BTreeMap::<ParaId, BTreeSet<CoreIndex>>::new();
+		for (core_idx, para_id) in <scheduler::Pallet<T>>::scheduled_paras() {
+			scheduled.entry(para_id).or_default().insert(core_idx);
+		}
 
 		METRICS.on_candidates_processed_total(backed_candidates.len() as u64);
 
-		let backed_candidates = sanitize_backed_candidates::<T, _>(
+		let backed_candidates = sanitize_backed_candidates::<T, _, _>(
 			backed_candidates,
 			|candidate_idx: usize,
-			 backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>|
+			 backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>,
+			 chained_upon: Option<&HeadData>|
 			 -> bool {
 				let para_id = backed_candidate.descriptor().para_id;
 				let prev_context = <paras::Pallet<T>>::para_most_recent_context(para_id);
@@ -605,9 +607,15 @@ impl<T: Config> Pallet<T> {
 					//
 					// NOTE: this is the only place where we check the relay-parent.
 					check_ctx
-						.verify_backed_candidate(&allowed_relay_parents, candidate_idx, backed_candidate)
+						.verify_backed_candidate(
+							&allowed_relay_parents,
+							candidate_idx,
+							backed_candidate,
+							chained_upon,
+						)
 						.is_err()
 			},
+			<inclusion::Pallet<T>>::pending_availability_head,
 			&scheduled,
 		);
 
@@ -908,32 +916,65 @@ pub(crate) fn sanitize_bitfields<T: crate::inclusion::Config>(
 /// state.
 ///
 /// `candidate_has_concluded_invalid_dispute` must return `true` if the candidate
-/// is disputed, false otherwise. The passed `usize` is the candidate index.
+/// is disputed, false otherwise. The passed `usize` is the candidate index. The passed
+/// `HeadData` is the head the candidate has to build upon if it is chained upon another
+/// candidate of the same para, rather than upon the para's current head.
+///
+/// `pending_availability_head` returns the head of the last candidate pending availability of a
+/// para, if any. The first candidate backed for such a para is chained upon it.
+///
+/// A para that is scheduled on several cores may have a chain of candidates backed, one for each
+/// of its cores. A candidate that does not build upon the previous one of its para, or upon the
+/// last candidate pending availability of its para, or for which no core is left, is filtered
+/// out.
 ///
 /// The returned `Vec` is sorted according to the occupied core index.
 fn sanitize_backed_candidates<
 	T: crate::inclusion::Config,
-	F: FnMut(usize, &BackedCandidate<T::Hash>) -> bool,
+	F: FnMut(usize, &BackedCandidate<T::Hash>, Option<&HeadData>) -> bool,
+	P: Fn(ParaId) -> Option<HeadData>,
 >(
 	mut backed_candidates: Vec<BackedCandidate<T::Hash>>,
 	mut candidate_has_concluded_invalid_dispute_or_is_invalid: F,
-	scheduled: &BTreeMap<ParaId, CoreIndex>,
+	pending_availability_head: P,
+	scheduled: &BTreeMap<ParaId, BTreeSet<CoreIndex>>,
 ) -> Vec<BackedCandidate<T::Hash>> {
-	// Remove any candidates that were concluded invalid.
-	// This does not assume sorting.
-	backed_candidates.indexed_retain(move |candidate_idx, backed_candidate| {
-		!candidate_has_concluded_invalid_dispute_or_is_invalid(candidate_idx, backed_candidate)
-	});
+	// The number of candidates kept for each para so far, and the head of the last one.
+	let mut para_chains = BTreeMap::<ParaId, (usize, HeadData)>::new();
 
-	// Assure the backed candidate's `ParaId`'s core is free.
+	// Remove any candidates that were concluded invalid, or that don't extend the chain of
+	// candidates of their para, including its candidates pending availability.
+	// This does not assume sorting.
+	//
+	// Assure the backed candidate's `ParaId` has a free core left.
 	// This holds under the assumption that `Scheduler::schedule` is called _before_.
 	// We don't check the relay-parent because this is done in the closure when
 	// constructing the inherent and during actual processing otherwise.
+	backed_candidates.indexed_retain(move |candidate_idx, backed_candidate| {
+		let para_id = backed_candidate.descriptor().para_id;
+		let n_cores = scheduled.get(&para_id).map_or(0, BTreeSet::len);
+		if n_cores == 0 {
+			return false
+		}
+		let (chain_len, chained_upon) = match para_chains.get(&para_id) {
+			Some((len, head)) => (*len, Some(head.clone())),
+			None => (0, pending_availability_head(para_id)),
+		};
 
-	backed_candidates.retain(|backed_candidate| {
-		let desc = backed_candidate.descriptor();
+		if chain_len >= n_cores ||
+			candidate_has_concluded_invalid_dispute_or_is_invalid(
+				candidate_idx,
+				backed_candidate,
+				chained_upon.as_ref(),
+			) {
+			return false
+		}
 
-		scheduled.get(&desc.para_id).is_some()
+		para_chains.insert(
+			para_id,
+			(chain_len + 1, backed_candidate.candidate.commitments.head_data.clone()),
+		);
+		true
 	});
 
 	// Sort the `Vec` last, once there is a guarantee that these
@@ -941,12 +982,32 @@ fn sanitize_backed_candidates<
 	// but more importantly are scheduled for a free core.
 	// This both avoids extra work for obviously invalid candidates,
 	// but also allows this to be done in place.
-	backed_candidates.sort_by(|x, y| {
-		// Never panics, since we filtered all panic arguments out in the previous `fn retain`.
-		scheduled[&x.descriptor().para_id].cmp(&scheduled[&y.descriptor().para_id])
-	});
+	//
+	// The candidates of a chain occupy the cores of their para in ascending order, so that
+	// sorting by core keeps them in chain order.
+	let mut chain_positions = BTreeMap::<ParaId, usize>::new();
+	let mut candidates_with_core = backed_candidates
+		.into_iter()
+		.map(|backed_candidate| {
+			let para_id = backed_candidate.descriptor().para_id;
+			let position = chain_positions.entry(para_id).or_default();
+			// Never panics, since we filtered all candidates without a core left in the previous
+			// `fn retain`.
+			let core_idx = scheduled[&para_id]
+				.iter()
+				.nth(*position)
+				.copied()
+				.expect("a core is left for each retained candidate; qed");
+			*position += 1;
+			(core_idx, backed_candidate)
+		})
+		.collect::<Vec<_>>();
+	candidates_with_core.sort_by_key(|(core_idx, _)| *core_idx);
 
-	backed_candidates
+	candidates_with_core
+		.into_iter()
+		.map(|(_, backed_candidate)| backed_candidate)
+		.collect()
 }
 
 /// Derive entropy from babe provided per block randomness.
@@ -1234,11 +1234,17 @@ mod sanitizers {
 			}
 
 			let has_concluded_invalid =
-				|_idx: usize, _backed_candidate: &BackedCandidate| -> bool { false };
+				|_idx: usize, _candidate: &BackedCandidate, _head: Option<&HeadData>| false;
+			let no_pending_availability = |_para_id: ParaId| -> Option<HeadData> { None };
 
 			let scheduled = (0_usize..2)
 				.into_iter()
-				.map(|idx| (ParaId::from(1_u32 + idx as u32), CoreIndex::from(idx as u32)))
+				.map(|idx| {
+					(
+						ParaId::from(1_u32 + idx as u32),
+						[CoreIndex::from(idx as u32)].into_iter().collect::<BTreeSet<_>>(),
+					)
+				})
 				.collect::<BTreeMap<_, _>>();
 
 			let group_validators = |group_index: GroupIndex| {
@@ -1280,9 +1286,21 @@ mod sanitizers {
 
 			// happy path
 			assert_eq!(
-				sanitize_backed_candidates::<Test, _>(
+				sanitize_backed_candidates::<Test, _, _>(
 					backed_candidates.clone(),
 					has_concluded_invalid,
+					no_pending_availability,
+					&scheduled
+				),
+				backed_candidates
+			);
+
+			// candidates for which no core of their para is left are dropped
+			assert_eq!(
+				sanitize_backed_candidates::<Test, _, _>(
+					backed_candidates.iter().chain(backed_candidates.iter()).cloned().collect(),
+					has_concluded_invalid,
+					no_pending_availability,
 					&scheduled
 				),
 				backed_candidates
@@ -1290,10 +1308,11 @@ mod sanitizers {
 
 			// nothing is scheduled, so no paraids match, thus all backed candidates are skipped
 			{
-				let scheduled = &BTreeMap::new();
-				assert!(sanitize_backed_candidates::<Test, _>(
+				let scheduled = &BTreeMap::<ParaId, BTreeSet<CoreIndex>>::new();
+				assert!(sanitize_backed_candidates::<Test, _, _>(
 					backed_candidates.clone(),
 					has_concluded_invalid,
+					no_pending_availability,
 					&scheduled
 				)
 				.is_empty());
@@ -1312,17 +1331,124 @@ mod sanitizers {
 					set
 				};
 				let has_concluded_invalid =
-					|_idx: usize, candidate: &BackedCandidate| set.contains(&candidate.hash());
+					|_idx: usize, candidate: &BackedCandidate, _chained_upon: Option<&HeadData>| {
+						set.contains(&candidate.hash())
+					};
 				assert_eq!(
-					sanitize_backed_candidates::<Test, _>(
+					sanitize_backed_candidates::<Test, _, _>(
 						backed_candidates.clone(),
 						has_concluded_invalid,
+						no_pending_availability,
 						&scheduled
 					)
 					.len(),
 					backed_candidates.len() / 2
 				);
 			}
+
+			// a para scheduled on several cores may have a chain of candidates backed
+			{
+				let scheduled = [
+					(ParaId::from(1_u32), [CoreIndex::from(0), CoreIndex::from(2)]),
+					(ParaId::from(2_u32), [CoreIndex::from(1), CoreIndex::from(3)]),
+				]
+				.into_iter()
+				.map(|(para_id, cores)| (para_id, cores.into_iter().collect::<BTreeSet<_>>()))
+				.collect::<BTreeMap<_, _>>();
+
+				let chained_candidate = {
+					let mut candidate = TestCandidateBuilder {
+						para_id: ParaId::from(1_u32),
+						relay_parent,
+						pov_hash: Hash::repeat_byte(3),
+						persisted_validation_data_hash: [43u8; 32].into(),
+						hrmp_watermark: RELAY_PARENT_NUM,
+						head_data: vec![3].into(),
+						..Default::default()
+					}
+					.build();
+					collator_sign_candidate(Sr25519Keyring::One, &mut candidate);
+					back_candidate(
+						candidate,
+						&validators,
+						group_validators(GroupIndex::from(0)).unwrap().as_ref(),
+						&keystore,
+						&signing_context,
+						BackingKind::Threshold,
+					)
+				};
+
+				let mut chained_upon = Vec::new();
+				let has_concluded_invalid =
+					|_idx: usize, candidate: &BackedCandidate, head: Option<&HeadData>| {
+						chained_upon.push((candidate.hash(), head.cloned()));
+						false
+					};
+
+				// the chained candidate occupies the second core of its para, so it's sorted last
+				assert_eq!(
+					sanitize_backed_candidates::<Test, _, _>(
+						vec![
+							backed_candidates[0].clone(),
+							chained_candidate.clone(),
+							backed_candidates[1].clone(),
+						],
+						has_concluded_invalid,
+						no_pending_availability,
+						&scheduled
+					),
+					vec![
+						backed_candidates[0].clone(),
+						backed_candidates[1].clone(),
+						chained_candidate.clone(),
+					]
+				);
+				assert_eq!(
+					chained_upon,
+					vec![
+						(backed_candidates[0].hash(), None),
+						(
+							chained_candidate.hash(),
+							Some(backed_candidates[0].candidate.commitments.head_data.clone())
+						),
+						(backed_candidates[1].hash(), None),
+					]
+				);
+			}
+
+			// the first candidate of a para with candidates pending availability is chained upon
+			// the last of them, and filtered out if it doesn't build upon it
+			{
+				let pending_head: HeadData = vec![7].into();
+				let pending_availability_head = |para_id: ParaId| {
+					(para_id == ParaId::from(1_u32)).then(|| pending_head.clone())
+				};
+
+				let mut chained_upon = Vec::new();
+				// none of the candidates builds upon the candidate pending availability
+				let has_concluded_invalid =
+					|_idx: usize, candidate: &BackedCandidate, head: Option<&HeadData>| {
+						chained_upon.push((candidate.hash(), head.cloned()));
+						head.is_some()
+					};
+
+				assert_eq!(
+					sanitize_backed_candidates::<Test, _, _>(
+						backed_candidates.clone(),
+						has_concluded_invalid,
+						pending_availability_head,
+						&scheduled
+					),
+					vec![backed_candidates[1].clone()]
+				);
+				assert_eq!(
+					chained_upon,
+					vec![
+						(backed_candidates[0].hash(), Some(pending_head.clone())),
+						(backed_candidates[1].hash(), None),
+					]
+				);
+			}
 		});
 	}
 }
@@ -88,16 +88,18 @@ pub fn backing_state<T: initializer::Config>(
 	};
 
 	let pending_availability = {
-		// Note: the API deals with a `Vec` as it is future-proof for cases
-		// where there may be multiple candidates pending availability at a time.
-		// But at the moment only one candidate can be pending availability per
-		// parachain.
+		// Note: a para that is scheduled on multiple cores may have a chain of candidates
+		// pending availability, which follow the first one in order.
 		crate::inclusion::PendingAvailability::<T>::get(&para_id)
 			.and_then(|pending| {
 				let commitments =
 					crate::inclusion::PendingAvailabilityCommitments::<T>::get(&para_id);
 				commitments.map(move |c| (pending, c))
 			})
+			.into_iter()
+			.chain(
+				crate::inclusion::PendingAvailabilityChain::<T>::get(&para_id).unwrap_or_default(),
+			)
 			.map(|(pending, commitments)| {
 				CandidatePendingAvailability {
 					candidate_hash: pending.candidate_hash(),
@@ -107,7 +109,6 @@ pub fn backing_state<T: initializer::Config>(
 					max_pov_size: constraints.max_pov_size, // assume always same in session.
 				}
 			})
-			.into_iter()
 			.collect()
 	};
 
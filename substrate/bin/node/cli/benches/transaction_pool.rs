@@ -58,6 +58,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		transaction_pool: TransactionPoolOptions {
 			ready: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
 			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			ready_per_sender: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			future_per_sender: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			sender_of: None,
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(30 * 60),
 		},
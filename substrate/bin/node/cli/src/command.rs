@@ -107,7 +107,10 @@ pub fn run() -> Result<()> {
 							)
 						}
 
-						cmd.run::<Block, sp_statement_store::runtime_api::HostFunctions>(config)
+						cmd.run::<Block, (
+							sp_statement_store::runtime_api::HostFunctions,
+							sp_crypto_ec_utils::elliptic_curves::HostFunctions,
+						)>(config)
 					},
 					BenchmarkCmd::Block(cmd) => {
 						// ensure that we keep the task manager alive
@@ -186,8 +186,12 @@ pub fn new_partial(
 
 	let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
+	let mut transaction_pool_options = config.transaction_pool.clone();
+	transaction_pool_options.sender_of =
+		Some(sc_transaction_pool::SenderOf::unchecked_extrinsic::<kitchensink_runtime::Address>());
+
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
-		config.transaction_pool.clone(),
+		transaction_pool_options,
 		config.role.is_authority().into(),
 		config.prometheus_registry(),
 		task_manager.spawn_essential_handle(),
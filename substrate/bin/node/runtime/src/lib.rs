@@ -38,9 +38,9 @@ use frame_support::{
 		fungible::{Balanced, Credit, HoldConsideration, ItemOf},
 		tokens::{nonfungibles_v2::Inspect, GetSalary, PayFromAccount},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, Contains, Currency,
-		EitherOfDiverse, EqualPrivilegeOnly, Imbalance, InsideBoth, InstanceFilter,
-		KeyOwnerProofSystem, LinearStoragePrice, LockIdentifier, Nothing, OnUnbalanced,
-		WithdrawReasons,
+		EitherOfDiverse, EnsureOrigin, EnsureOriginWithArg, EqualPrivilegeOnly, Imbalance,
+		InsideBoth, InstanceFilter, KeyOwnerProofSystem, LinearStoragePrice, LockIdentifier,
+		Nothing, OnUnbalanced, WithdrawReasons,
 	},
 	weights::{
 		constants::{
@@ -109,6 +109,7 @@ use impls::{AllianceProposalProvider, Author, CreditToBlockAuthor};
 /// Constant values used within the runtime.
 pub mod constants;
 use constants::{currency::*, time::*};
+use dynamic_params::{RuntimeParameters, RuntimeParametersKey};
 use sp_runtime::generic::Era;
 
 /// Generated voter bag information.
@@ -1004,6 +1005,90 @@ impl pallet_ranked_collective::Config for Runtime {
 	type VoteWeight = pallet_ranked_collective::Geometric;
 }
 
+parameter_types! {
+	pub const Groth16MaxPublicInputs: u32 = 32;
+	pub const Groth16MaxKeyLen: u32 = 4 * 1024;
+}
+
+impl pallet_groth16_verifier::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type KeyOrigin = EnsureRoot<AccountId>;
+	type MaxPublicInputs = Groth16MaxPublicInputs;
+	type MaxKeyLen = Groth16MaxKeyLen;
+	type WeightInfo = pallet_groth16_verifier::weights::SubstrateWeight<Runtime>;
+}
+
+/// Parameters of the runtime which can be changed without a runtime upgrade.
+pub mod dynamic_params {
+	use super::*;
+
+	frame_support::dynamic_params! {
+		/// The parameters of the runtime, stored in the parameters pallet.
+		pub enum RuntimeParameters stored in pallet_parameters::Pallet<Runtime> {
+			/// Parameters of the treasury.
+			pub mod treasury: Treasury {
+				/// The portion of the funds of the treasury burnt every spend period.
+				pub static BurnPortion: Permill = Permill::from_percent(50);
+			}
+			/// Parameters of the contracts pallet.
+			pub mod contracts: Contracts {
+				/// The deposit per storage item of a contract.
+				pub static DepositPerItem: Balance = deposit(1, 0);
+				/// The deposit per byte of storage of a contract.
+				pub static DepositPerByte: Balance = deposit(0, 1);
+			}
+		}
+	}
+}
+
+/// Root or half the council for the treasury parameters, root for all others.
+pub struct DynamicParametersAdminOrigin;
+impl EnsureOriginWithArg<RuntimeOrigin, RuntimeParametersKey> for DynamicParametersAdminOrigin {
+	type Success = ();
+
+	fn try_origin(
+		origin: RuntimeOrigin,
+		key: &RuntimeParametersKey,
+	) -> Result<Self::Success, RuntimeOrigin> {
+		match key {
+			RuntimeParametersKey::Treasury(_) =>
+				EnsureRootOrHalfCouncil::try_origin(origin).map(|_| ()),
+			RuntimeParametersKey::Contracts(_) => EnsureRoot::<AccountId>::try_origin(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(key: &RuntimeParametersKey) -> Result<RuntimeOrigin, ()> {
+		match key {
+			RuntimeParametersKey::Treasury(_) => EnsureRootOrHalfCouncil::try_successful_origin(),
+			RuntimeParametersKey::Contracts(_) => EnsureRoot::<AccountId>::try_successful_origin(),
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ParametersBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_parameters::BenchmarkHelper<RuntimeParameters> for ParametersBenchmarkHelper {
+	fn key_value() -> RuntimeParameters {
+		// the balances are the largest values.
+		dynamic_params::contracts::Parameters::DepositPerByte(
+			dynamic_params::contracts::DepositPerByte,
+			Some(Balance::MAX),
+		)
+		.into()
+	}
+}
+
+impl pallet_parameters::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeParameters = RuntimeParameters;
+	type AdminOrigin = DynamicParametersAdminOrigin;
+	type WeightInfo = pallet_parameters::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ParametersBenchmarkHelper;
+}
+
 impl pallet_remark::Config for Runtime {
 	type WeightInfo = pallet_remark::weights::SubstrateWeight<Self>;
 	type RuntimeEvent = RuntimeEvent;
@@ -1175,7 +1260,6 @@ parameter_types! {
 	pub const ProposalBond: Permill = Permill::from_percent(5);
 	pub const ProposalBondMinimum: Balance = 1 * DOLLARS;
 	pub const SpendPeriod: BlockNumber = 1 * DAYS;
-	pub const Burn: Permill = Permill::from_percent(50);
 	pub const TipCountdown: BlockNumber = 1 * DAYS;
 	pub const TipFindersFee: Percent = Percent::from_percent(20);
 	pub const TipReportDepositBase: Balance = 1 * DOLLARS;
@@ -1203,7 +1287,7 @@ impl pallet_treasury::Config for Runtime {
 	type ProposalBondMinimum = ProposalBondMinimum;
 	type ProposalBondMaximum = ();
 	type SpendPeriod = SpendPeriod;
-	type Burn = Burn;
+	type Burn = dynamic_params::treasury::BurnPortion;
 	type BurnDestination = ();
 	type SpendFunds = Bounties;
 	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
@@ -1292,8 +1376,6 @@ impl pallet_tips::Config for Runtime {
 }
 
 parameter_types! {
-	pub const DepositPerItem: Balance = deposit(1, 0);
-	pub const DepositPerByte: Balance = deposit(0, 1);
 	pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
 	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
@@ -1312,8 +1394,8 @@ impl pallet_contracts::Config for Runtime {
 	/// change because that would break already deployed contracts. The `Call` structure itself
 	/// is not allowed to change the indices of existing pallets, too.
 	type CallFilter = Nothing;
-	type DepositPerItem = DepositPerItem;
-	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = dynamic_params::contracts::DepositPerItem;
+	type DepositPerByte = dynamic_params::contracts::DepositPerByte;
 	type DefaultDepositLimit = DefaultDepositLimit;
 	type CallStack = [pallet_contracts::Frame<Self>; 5];
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
@@ -1583,6 +1665,10 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
 	type Freezer = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type MaxHolds = ConstU32<0>;
+	type MaxFreezes = ConstU32<0>;
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
@@ -1610,6 +1696,10 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
 	type Freezer = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type MaxHolds = ConstU32<0>;
+	type MaxFreezes = ConstU32<0>;
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
@@ -1624,6 +1714,7 @@ parameter_types! {
 	pub const PoolSetupFee: Balance = 1 * DOLLARS; // should be more or equal to the existential deposit
 	pub const MintMinLiquidity: Balance = 100;  // 100 is good enough when the main currency has 10-12 decimals.
 	pub const LiquidityWithdrawalFee: Permill = Permill::from_percent(0);  // should be non-zero if AllowMultiAssetPools is true, otherwise can be zero.
+	pub const PriceObservationPeriod: BlockNumber = HOURS;
 }
 
 impl pallet_asset_conversion::Config for Runtime {
@@ -1639,6 +1730,10 @@ impl pallet_asset_conversion::Config for Runtime {
 	type PoolAssetId = <Self as pallet_assets::Config<Instance2>>::AssetId;
 	type PalletId = AssetConversionPalletId;
 	type LPFee = ConstU32<3>; // means 0.3%
+	type FeeTiersOrigin = EnsureRoot<AccountId>;
+	type MaxFeeTiers = ConstU32<10>;
+	type MaxPriceObservations = ConstU32<24>;
+	type PriceObservationPeriod = PriceObservationPeriod;
 	type PoolSetupFee = PoolSetupFee;
 	type PoolSetupFeeReceiver = AssetConversionOrigin;
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
@@ -2092,6 +2187,8 @@ construct_runtime!(
 		SafeMode: pallet_safe_mode,
 		Statement: pallet_statement,
 		Broker: pallet_broker,
+		Groth16Verifier: pallet_groth16_verifier,
+		Parameters: pallet_parameters,
 	}
 );
 
@@ -2144,6 +2241,7 @@ type Migrations = (
 	pallet_nomination_pools::migration::v2::MigrateToV2<Runtime>,
 	pallet_alliance::migration::Migration<Runtime>,
 	pallet_contracts::Migration<Runtime>,
+	pallet_asset_conversion::migration::v1::MigrateToV1<Runtime>,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -2205,6 +2303,8 @@ mod benches {
 		[pallet_salary, Salary]
 		[pallet_scheduler, Scheduler]
 		[pallet_glutton, Glutton]
+		[pallet_groth16_verifier, Groth16Verifier]
+		[pallet_parameters, Parameters]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_society, Society]
 		[pallet_staking, Staking]
@@ -2520,6 +2620,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -2538,6 +2639,10 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: NativeOrAssetId<u32>, asset2: NativeOrAssetId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(&asset1, &asset2).ok()
 		}
+
+		fn time_weighted_average_price(base: NativeOrAssetId<u32>, quote: NativeOrAssetId<u32>, window: BlockNumber) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::time_weighted_average_price(base, quote, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
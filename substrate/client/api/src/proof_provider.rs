@@ -51,6 +51,29 @@ pub trait ProofProvider<Block: BlockT> {
 		call_data: &[u8],
 	) -> sp_blockchain::Result<(Vec<u8>, StorageProof)>;
 
+	/// Reads the storage values of at most `count` keys matching `prefix`, starting at `start_at`
+	/// inclusively, in the top trie or in the child trie `child_info` at a given block, returning
+	/// the read proof and the number of keys it proves.
+	/// The proof is built until `size_limit` is reached and always includes at least one key.
+	fn read_range_proof(
+		&self,
+		hash: Block::Hash,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		start_at: Option<&[u8]>,
+		count: u32,
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)>;
+
+	/// Reads the closest merkle value of `key` in the top trie or in the child trie `child_info`
+	/// at a given block, returning the read proof.
+	fn closest_merkle_value_proof(
+		&self,
+		hash: Block::Hash,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> sp_blockchain::Result<StorageProof>;
+
 	/// Given a `Hash` iterate over all storage values starting at `start_keys`.
 	/// Last `start_keys` element contains last accessed key value.
 	/// With multiple `start_keys`, first `start_keys` element is
@@ -417,7 +417,10 @@ where
 		let mut skipped = 0;
 		let mut unqueue_invalid = Vec::new();
 
-		let mut t1 = self.transaction_pool.ready_at(self.parent_number).fuse();
+		let mut t1 = self
+			.transaction_pool
+			.ready_at_block(self.parent_hash, self.parent_number)
+			.fuse();
 		let mut t2 =
 			futures_timer::Delay::new(deadline.saturating_duration_since((self.now)()) / 8).fuse();
 
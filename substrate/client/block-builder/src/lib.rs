@@ -38,6 +38,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Hash, HashingFor, Header as HeaderT, NumberFor, One},
 	Digest,
 };
+use sp_trie::proof_size_extension::ProofSizeExt;
 
 use sc_client_api::backend;
 pub use sp_block_builder::BlockBuilder as BlockBuilderApi;
@@ -173,6 +174,10 @@ where
 
 		if record_proof.yes() {
 			api.record_proof();
+			let recorder = api
+				.proof_recorder()
+				.expect("Proof recording is enabled in the line above; qed.");
+			api.register_extension(ProofSizeExt::new(recorder));
 		}
 
 		api.set_call_context(CallContext::Onchain);
@@ -20,7 +20,8 @@ use crate::{arg_enums::SyncMode, params::node_key_params::NodeKeyParams};
 use clap::Args;
 use sc_network::{
 	config::{
-		NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, SetConfig, TransportConfig,
+		BandwidthQuota, BandwidthQuotas, NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode,
+		ProtocolName, SetConfig, TransportConfig,
 	},
 	multiaddr::Protocol,
 };
@@ -149,6 +150,50 @@ pub struct NetworkParams {
 	/// and observe block requests timing out.
 	#[arg(long, value_name = "COUNT", default_value_t = 64)]
 	pub max_blocks_per_request: u32,
+
+	/// Maximum number of bytes per second each peer may send to this node on each protocol.
+	///
+	/// Notifications and requests of a peer exceeding this rate are dropped, and the peer's
+	/// reputation is lowered. Unlimited by default.
+	#[arg(long, value_name = "BYTES_PER_SEC")]
+	pub peer_inbound_bandwidth: Option<u64>,
+
+	/// Maximum number of bytes per second this node may send to each peer on each protocol.
+	///
+	/// Notifications exceeding this rate are held back until the rate allows sending them, and
+	/// requests exceeding this rate are not sent. Unlimited by default.
+	#[arg(long, value_name = "BYTES_PER_SEC")]
+	pub peer_outbound_bandwidth: Option<u64>,
+
+	/// Bandwidth limits of a specific protocol, overriding `--peer-inbound-bandwidth` and
+	/// `--peer-outbound-bandwidth`.
+	///
+	/// Expected format is 'PROTOCOL=INBOUND:OUTBOUND', in bytes per second, where an empty limit
+	/// means unlimited, e.g. `--protocol-bandwidth /foo/transactions/1=100000:`.
+	/// This flag can be passed multiple times.
+	#[arg(long, value_name = "PROTOCOL=INBOUND:OUTBOUND", value_parser = parse_protocol_bandwidth)]
+	pub protocol_bandwidth: Vec<(ProtocolName, BandwidthQuota)>,
+}
+
+fn parse_protocol_bandwidth(s: &str) -> Result<(ProtocolName, BandwidthQuota), String> {
+	let (protocol, limits) = s
+		.rsplit_once('=')
+		.ok_or_else(|| "Expected 'PROTOCOL=INBOUND:OUTBOUND'".to_string())?;
+	let (inbound, outbound) = limits
+		.split_once(':')
+		.ok_or_else(|| "Expected 'INBOUND:OUTBOUND'".to_string())?;
+	let parse_limit = |limit: &str| -> Result<Option<u64>, String> {
+		match limit {
+			"" => Ok(None),
+			limit =>
+				limit.parse().map(Some).map_err(|e| format!("Invalid limit '{}': {}", limit, e)),
+		}
+	};
+
+	Ok((
+		protocol.to_string().into(),
+		BandwidthQuota { inbound: parse_limit(inbound)?, outbound: parse_limit(outbound)? },
+	))
 }
 
 impl NetworkParams {
@@ -242,6 +287,13 @@ impl NetworkParams {
 			kademlia_disjoint_query_paths: self.kademlia_disjoint_query_paths,
 			kademlia_replication_factor: self.kademlia_replication_factor,
 			yamux_window_size: None,
+			bandwidth_quotas: BandwidthQuotas {
+				default: BandwidthQuota {
+					inbound: self.peer_inbound_bandwidth,
+					outbound: self.peer_outbound_bandwidth,
+				},
+				protocols: self.protocol_bandwidth.iter().cloned().collect(),
+			},
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
 		}
@@ -292,6 +344,34 @@ mod tests {
 		assert_eq!(expected, params.network_params.reserved_nodes);
 	}
 
+	#[test]
+	fn protocol_bandwidth_multiple_occurrences() {
+		let params = Cli::try_parse_from([
+			"",
+			"--protocol-bandwidth",
+			"/foo/transactions/1=100:",
+			"--protocol-bandwidth",
+			"/foo/block-announces/1=:2000",
+		])
+		.expect("Parses network params");
+
+		assert_eq!(
+			vec![
+				(
+					ProtocolName::from("/foo/transactions/1"),
+					BandwidthQuota { inbound: Some(100), outbound: None },
+				),
+				(
+					ProtocolName::from("/foo/block-announces/1"),
+					BandwidthQuota { inbound: None, outbound: Some(2000) },
+				),
+			],
+			params.network_params.protocol_bandwidth,
+		);
+		assert!(Cli::try_parse_from(["", "--protocol-bandwidth", "/foo/1=100"]).is_err());
+		assert!(Cli::try_parse_from(["", "--protocol-bandwidth", "/foo/1=a:"]).is_err());
+	}
+
 	#[test]
 	fn sync_ingores_case() {
 		let params = Cli::try_parse_from(["", "--sync", "wArP"]).expect("Parses network params");
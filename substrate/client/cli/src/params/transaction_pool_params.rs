@@ -30,6 +30,22 @@ pub struct TransactionPoolParams {
 	#[arg(long, value_name = "COUNT", default_value_t = 20480)]
 	pub pool_kbytes: usize,
 
+	/// Maximum number of ready transactions of a single sender in the transaction pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 1024)]
+	pub pool_sender_limit: usize,
+
+	/// Maximum number of kilobytes of the ready transactions of a single sender in the pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 2048)]
+	pub pool_sender_kbytes: usize,
+
+	/// Maximum number of future transactions of a single sender in the transaction pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 64)]
+	pub pool_sender_future_limit: usize,
+
+	/// Maximum number of kilobytes of the future transactions of a single sender in the pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 256)]
+	pub pool_sender_future_kbytes: usize,
+
 	/// How long a transaction is banned for, if it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
@@ -49,6 +65,12 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		// per sender
+		opts.ready_per_sender.count = self.pool_sender_limit;
+		opts.ready_per_sender.total_bytes = self.pool_sender_kbytes * 1024;
+		opts.future_per_sender.count = self.pool_sender_future_limit;
+		opts.future_per_sender.total_bytes = self.pool_sender_future_kbytes * 1024;
+
 		opts.ban_time = if let Some(ban_seconds) = self.tx_ban_seconds {
 			std::time::Duration::from_secs(ban_seconds)
 		} else if is_dev {
@@ -47,6 +47,13 @@ const LOG_TARGET: &str = "light-client-request-handler";
 /// handling in production systems, this value is chosen to match the block request limit.
 const MAX_LIGHT_REQUEST_QUEUE: usize = 20;
 
+/// Maximum number of keys proven in response to a range read request.
+const MAX_READ_RANGE_KEYS: u32 = 1024;
+
+/// Maximum size of the proof sent in response to a range read request. The proof always includes
+/// at least one key, so it can still be larger if a single value is.
+const MAX_READ_RANGE_PROOF_SIZE: usize = 2 * 1024 * 1024;
+
 /// Handler for incoming light client requests from a remote peer.
 pub struct LightClientRequestHandler<B, Client> {
 	request_receiver: async_channel::Receiver<IncomingRequest>,
@@ -155,6 +162,10 @@ where
 				self.on_remote_read_request(&peer, r)?,
 			Some(schema::v1::light::request::Request::RemoteReadChildRequest(r)) =>
 				self.on_remote_read_child_request(&peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteReadRangeRequest(r)) =>
+				self.on_remote_read_range_request(&peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteClosestMerkleValueRequest(r)) =>
+				self.on_remote_closest_merkle_value_request(&peer, r)?,
 			None =>
 				return Err(HandleRequestError::BadRequest("Remote request without request data.")),
 		};
@@ -252,12 +263,7 @@ where
 
 		let block = Decode::decode(&mut request.block.as_ref())?;
 
-		let prefixed_key = PrefixedStorageKey::new_ref(&request.storage_key);
-		let child_info = match ChildType::from_prefixed_key(prefixed_key) {
-			Some((ChildType::ParentKeyId, storage_key)) => Ok(ChildInfo::new_default(storage_key)),
-			None => Err(sp_blockchain::Error::InvalidChildStorageKey),
-		};
-		let response = match child_info.and_then(|child_info| {
+		let response = match child_info(&request.storage_key).and_then(|child_info| {
 			self.client.read_child_proof(
 				block,
 				&child_info,
@@ -282,6 +288,106 @@ where
 			response: Some(schema::v1::light::response::Response::RemoteReadResponse(response)),
 		})
 	}
+
+	fn on_remote_read_range_request(
+		&mut self,
+		peer: &PeerId,
+		request: &schema::v1::light::RemoteReadRangeRequest,
+	) -> Result<schema::v1::light::Response, HandleRequestError> {
+		if request.limit == 0 {
+			debug!("Invalid remote read range request sent by {}.", peer);
+			return Err(HandleRequestError::BadRequest("Remote read range request without limit."))
+		}
+
+		trace!(
+			"Remote read range request from {} ({:?} {:?} {:?} x{} at {:?}).",
+			peer,
+			request.storage_key.as_ref().map(HexDisplay::from),
+			request.prefix.as_ref().map(HexDisplay::from),
+			request.start_key.as_ref().map(HexDisplay::from),
+			request.limit,
+			request.block,
+		);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+
+		let child_info = request.storage_key.as_deref().map(child_info).transpose();
+		let response = match child_info.and_then(|child_info| {
+			self.client.read_range_proof(
+				block,
+				child_info.as_ref(),
+				request.prefix.as_deref(),
+				request.start_key.as_deref(),
+				request.limit.min(MAX_READ_RANGE_KEYS),
+				MAX_READ_RANGE_PROOF_SIZE,
+			)
+		}) {
+			Ok((proof, count)) => schema::v1::light::RemoteReadRangeResponse {
+				proof: Some(proof.encode()),
+				count: Some(count),
+			},
+			Err(error) => {
+				trace!(
+					"remote read range request from {} (at {:?}) failed with: {}",
+					peer,
+					request.block,
+					error,
+				);
+				schema::v1::light::RemoteReadRangeResponse { proof: None, count: None }
+			},
+		};
+
+		Ok(schema::v1::light::Response {
+			response: Some(schema::v1::light::response::Response::RemoteReadRangeResponse(
+				response,
+			)),
+		})
+	}
+
+	fn on_remote_closest_merkle_value_request(
+		&mut self,
+		peer: &PeerId,
+		request: &schema::v1::light::RemoteClosestMerkleValueRequest,
+	) -> Result<schema::v1::light::Response, HandleRequestError> {
+		trace!(
+			"Remote closest merkle value request from {} ({:?} {} at {:?}).",
+			peer,
+			request.storage_key.as_ref().map(HexDisplay::from),
+			HexDisplay::from(&request.key),
+			request.block,
+		);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+
+		let child_info = request.storage_key.as_deref().map(child_info).transpose();
+		let response = match child_info.and_then(|child_info| {
+			self.client.closest_merkle_value_proof(block, child_info.as_ref(), &request.key)
+		}) {
+			Ok(proof) => schema::v1::light::RemoteReadResponse { proof: Some(proof.encode()) },
+			Err(error) => {
+				trace!(
+					"remote closest merkle value request from {} ({} at {:?}) failed with: {}",
+					peer,
+					HexDisplay::from(&request.key),
+					request.block,
+					error,
+				);
+				schema::v1::light::RemoteReadResponse { proof: None }
+			},
+		};
+
+		Ok(schema::v1::light::Response {
+			response: Some(schema::v1::light::response::Response::RemoteReadResponse(response)),
+		})
+	}
+}
+
+fn child_info(storage_key: &[u8]) -> Result<ChildInfo, sp_blockchain::Error> {
+	let prefixed_key = PrefixedStorageKey::new_ref(storage_key);
+	match ChildType::from_prefixed_key(prefixed_key) {
+		Some((ChildType::ParentKeyId, storage_key)) => Ok(ChildInfo::new_default(storage_key)),
+		None => Err(sp_blockchain::Error::InvalidChildStorageKey),
+	}
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,402 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-peer bandwidth quotas.
+//!
+//! A [`BandwidthQuota`] limits the byte rate at which a single peer can use a single notification
+//! or request-response protocol, independently in each direction. Quotas are enforced with a token
+//! bucket per peer, protocol and direction, which allows bursts of up to [`BURST_DURATION`] worth
+//! of traffic.
+//!
+//! Inbound notifications and requests that don't fit in the quota of the remote are dropped and
+//! the remote gets a reputation penalty, so that a peer that keeps overusing its quota ends up
+//! banned. Outbound requests that don't fit in the quota are refused locally, while outbound
+//! notifications are held back until the quota is refilled, which applies back-pressure to the
+//! senders of the protocol.
+//! Responses are never dropped, but they count towards the inbound quota of the peer which
+//! requested them, so that a peer can't exceed its quota with small requests for large responses:
+//! its next requests are refused until the debt is paid back.
+
+use crate::{types::ProtocolName, ReputationChange};
+
+use libp2p::PeerId;
+use parking_lot::Mutex;
+use prometheus_endpoint::{self as prometheus, CounterVec, Opts, PrometheusError, Registry, U64};
+
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// Amount of time worth of quota a peer can use at once.
+pub const BURST_DURATION: Duration = Duration::from_secs(5);
+
+/// Reputation change for a peer that sent a message exceeding its quota.
+pub(crate) const QUOTA_EXCEEDED: ReputationChange =
+	ReputationChange::new(-(1 << 10), "Bandwidth quota exceeded");
+
+/// Byte-rate budget of a single peer on a single protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BandwidthQuota {
+	/// Bytes per second a peer may send to the local node. `None` means unlimited.
+	pub inbound: Option<u64>,
+	/// Bytes per second the local node may send to a peer. `None` means unlimited.
+	pub outbound: Option<u64>,
+}
+
+impl BandwidthQuota {
+	fn rate(&self, direction: Direction) -> Option<u64> {
+		match direction {
+			Direction::Inbound => self.inbound,
+			Direction::Outbound => self.outbound,
+		}
+	}
+}
+
+/// Bandwidth quotas of all protocols.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BandwidthQuotas {
+	/// Quota of the protocols that have no entry in `protocols`.
+	pub default: BandwidthQuota,
+	/// Quotas of specific protocols.
+	pub protocols: HashMap<ProtocolName, BandwidthQuota>,
+}
+
+impl BandwidthQuotas {
+	/// Returns the quota of `protocol`.
+	pub fn quota(&self, protocol: &ProtocolName) -> BandwidthQuota {
+		self.protocols.get(protocol).copied().unwrap_or(self.default)
+	}
+}
+
+/// Direction of the traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+	/// From the remote to the local node.
+	Inbound,
+	/// From the local node to the remote.
+	Outbound,
+}
+
+impl Direction {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Direction::Inbound => "in",
+			Direction::Outbound => "out",
+		}
+	}
+}
+
+/// Token bucket of a peer, protocol and direction.
+struct Bucket {
+	/// Bytes that can be transferred. Becomes negative when a message larger than the remaining
+	/// budget is accepted.
+	available: f64,
+	/// Last time `available` was refilled.
+	last_refill: Instant,
+}
+
+impl Bucket {
+	fn new(rate: u64, now: Instant) -> Self {
+		Self { available: capacity(rate), last_refill: now }
+	}
+
+	/// Consumes `bytes` from the bucket if it isn't depleted.
+	///
+	/// A message is accepted as long as the budget isn't negative, whatever its size. Otherwise,
+	/// messages larger than the burst size could never be transferred.
+	fn try_consume(&mut self, rate: u64, bytes: usize, now: Instant) -> bool {
+		self.refill(rate, now);
+
+		if self.available < 0.0 {
+			return false
+		}
+		self.available -= bytes as f64;
+		true
+	}
+
+	fn refill(&mut self, rate: u64, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.last_refill);
+		self.available = (self.available + rate as f64 * elapsed.as_secs_f64()).min(capacity(rate));
+		self.last_refill = now;
+	}
+
+	/// Returns the time after which the bucket is no longer depleted, if it is.
+	fn depleted_for(&mut self, rate: u64, now: Instant) -> Option<Duration> {
+		self.refill(rate, now);
+
+		if self.available >= 0.0 {
+			return None
+		}
+		Some(Duration::from_secs_f64(-self.available / rate.max(1) as f64))
+	}
+}
+
+fn capacity(rate: u64) -> f64 {
+	rate as f64 * BURST_DURATION.as_secs_f64()
+}
+
+/// Prometheus metrics of the quotas.
+struct Metrics {
+	bytes_total: CounterVec<U64>,
+	exceeded_total: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			bytes_total: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_libp2p_bandwidth_quota_bytes_total",
+						"Total number of bytes accounted against the per-peer bandwidth quotas",
+					),
+					&["direction", "protocol"],
+				)?,
+				registry,
+			)?,
+			exceeded_total: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_libp2p_bandwidth_quota_exceeded_total",
+						"Total number of messages refused because of the per-peer bandwidth quotas",
+					),
+					&["direction", "protocol"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+struct Inner {
+	quotas: BandwidthQuotas,
+	buckets: HashMap<(PeerId, ProtocolName, Direction), Bucket>,
+	metrics: Option<Metrics>,
+}
+
+/// Enforces the [`BandwidthQuotas`].
+///
+/// Cloning the limiter creates another handle to the same buckets.
+#[derive(Clone)]
+pub struct BandwidthLimiter {
+	inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for BandwidthLimiter {
+	fn default() -> Self {
+		Self::with_metrics(BandwidthQuotas::default(), None)
+	}
+}
+
+impl BandwidthLimiter {
+	/// Creates a new limiter, registering its metrics in `registry`, if any.
+	pub fn new(
+		quotas: BandwidthQuotas,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		let metrics = registry.map(Metrics::register).transpose()?;
+		Ok(Self::with_metrics(quotas, metrics))
+	}
+
+	fn with_metrics(quotas: BandwidthQuotas, metrics: Option<Metrics>) -> Self {
+		Self { inner: Arc::new(Mutex::new(Inner { quotas, buckets: HashMap::new(), metrics })) }
+	}
+
+	/// Accounts `bytes` transferred with `peer` on `protocol`.
+	///
+	/// Returns `false` if the quota of the peer is exhausted, in which case the message must not
+	/// be transferred.
+	pub fn try_consume(
+		&self,
+		peer: PeerId,
+		protocol: &ProtocolName,
+		direction: Direction,
+		bytes: usize,
+	) -> bool {
+		self.consume_at(peer, protocol, direction, bytes, Instant::now())
+	}
+
+	/// Accounts `bytes` of a message that is transferred regardless of the quota.
+	pub fn force_consume(
+		&self,
+		peer: PeerId,
+		protocol: &ProtocolName,
+		direction: Direction,
+		bytes: usize,
+	) {
+		let mut inner = self.inner.lock();
+		let Some(rate) = inner.quotas.quota(protocol).rate(direction) else { return };
+		let now = Instant::now();
+		let bucket = inner
+			.buckets
+			.entry((peer, protocol.clone(), direction))
+			.or_insert_with(|| Bucket::new(rate, now));
+		// Refills the bucket before going into debt.
+		bucket.try_consume(rate, 0, now);
+		bucket.available -= bytes as f64;
+		if let Some(metrics) = &inner.metrics {
+			metrics
+				.bytes_total
+				.with_label_values(&[direction.as_str(), &**protocol])
+				.inc_by(bytes as u64);
+		}
+	}
+
+	fn consume_at(
+		&self,
+		peer: PeerId,
+		protocol: &ProtocolName,
+		direction: Direction,
+		bytes: usize,
+		now: Instant,
+	) -> bool {
+		let mut inner = self.inner.lock();
+		let Some(rate) = inner.quotas.quota(protocol).rate(direction) else { return true };
+		let accepted = inner
+			.buckets
+			.entry((peer, protocol.clone(), direction))
+			.or_insert_with(|| Bucket::new(rate, now))
+			.try_consume(rate, bytes, now);
+
+		if let Some(metrics) = &inner.metrics {
+			let labels = [direction.as_str(), &**protocol];
+			if accepted {
+				metrics.bytes_total.with_label_values(&labels).inc_by(bytes as u64);
+			} else {
+				metrics.exceeded_total.with_label_values(&labels).inc();
+			}
+		}
+		if !accepted {
+			log::debug!(
+				target: "sub-libp2p",
+				"Bandwidth quota of {} on {} ({}) exceeded, refusing {} bytes",
+				peer,
+				protocol,
+				direction.as_str(),
+				bytes,
+			);
+		}
+		accepted
+	}
+
+	/// Returns how long to wait before messages with `peer` on `protocol` fit in the quota again,
+	/// or `None` if they already do.
+	///
+	/// Used to hold back outbound notifications rather than dropping them.
+	pub fn exhausted_for(
+		&self,
+		peer: PeerId,
+		protocol: &ProtocolName,
+		direction: Direction,
+	) -> Option<Duration> {
+		self.exhausted_for_at(peer, protocol, direction, Instant::now())
+	}
+
+	fn exhausted_for_at(
+		&self,
+		peer: PeerId,
+		protocol: &ProtocolName,
+		direction: Direction,
+		now: Instant,
+	) -> Option<Duration> {
+		let mut inner = self.inner.lock();
+		let rate = inner.quotas.quota(protocol).rate(direction)?;
+		inner
+			.buckets
+			.get_mut(&(peer, protocol.clone(), direction))?
+			.depleted_for(rate, now)
+	}
+
+	/// Forgets the buckets of `peer`.
+	pub fn peer_disconnected(&self, peer: &PeerId) {
+		self.inner.lock().buckets.retain(|(p, _, _), _| p != peer);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limiter(inbound: u64) -> (BandwidthLimiter, ProtocolName) {
+		let protocol = ProtocolName::from("/test/1");
+		let quotas = BandwidthQuotas {
+			default: BandwidthQuota::default(),
+			protocols: [(
+				protocol.clone(),
+				BandwidthQuota { inbound: Some(inbound), outbound: None },
+			)]
+			.into_iter()
+			.collect(),
+		};
+		(BandwidthLimiter::new(quotas, None).unwrap(), protocol)
+	}
+
+	#[test]
+	fn quota_is_refilled_over_time() {
+		let (limiter, protocol) = limiter(100);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		// The whole burst can be used at once, even by an oversized message.
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 600, now));
+		assert!(!limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+		// The debt of 100 bytes is paid back after one second.
+		let now = now + Duration::from_secs(1);
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 50, now));
+		assert!(!limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+		// The bucket never holds more than the burst.
+		let now = now + Duration::from_secs(60);
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 550, now));
+		assert!(!limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+	}
+
+	#[test]
+	fn quotas_are_per_peer_protocol_and_direction() {
+		let (limiter, protocol) = limiter(100);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 1000, now));
+		assert!(!limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+		assert!(limiter.consume_at(PeerId::random(), &protocol, Direction::Inbound, 1, now));
+		assert!(limiter.consume_at(peer, &protocol, Direction::Outbound, 10_000, now));
+		assert!(limiter.consume_at(peer, &"/other/1".into(), Direction::Inbound, 10_000, now));
+
+		limiter.peer_disconnected(&peer);
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+	}
+
+	#[test]
+	fn exhausted_quota_reports_when_it_is_refilled() {
+		let (limiter, protocol) = limiter(100);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert_eq!(limiter.exhausted_for_at(peer, &protocol, Direction::Inbound, now), None);
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 700, now));
+		assert_eq!(
+			limiter.exhausted_for_at(peer, &protocol, Direction::Inbound, now),
+			Some(Duration::from_secs(2)),
+		);
+		let now = now + Duration::from_secs(2);
+		assert_eq!(limiter.exhausted_for_at(peer, &protocol, Direction::Inbound, now), None);
+		assert!(limiter.consume_at(peer, &protocol, Direction::Inbound, 1, now));
+	}
+}
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	bandwidth_quota::BandwidthLimiter,
 	discovery::{DiscoveryBehaviour, DiscoveryConfig, DiscoveryOut},
 	event::DhtEvent,
 	peer_info,
@@ -37,7 +38,11 @@ use libp2p::{
 use parking_lot::Mutex;
 use sc_network_common::role::{ObservedRole, Roles};
 use sp_runtime::traits::Block as BlockT;
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+	time::Duration,
+};
 
 pub use crate::request_responses::{InboundFailure, OutboundFailure, RequestId, ResponseFailure};
 
@@ -172,6 +177,7 @@ impl<B: BlockT> Behaviour<B> {
 		disco_config: DiscoveryConfig,
 		request_response_protocols: Vec<ProtocolConfig>,
 		peer_store_handle: PeerStoreHandle,
+		bandwidth_limiter: BandwidthLimiter,
 		external_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
 	) -> Result<Self, request_responses::RegisterError> {
 		Ok(Self {
@@ -185,6 +191,7 @@ impl<B: BlockT> Behaviour<B> {
 			request_responses: request_responses::RequestResponsesBehaviour::new(
 				request_response_protocols.into_iter(),
 				Box::new(peer_store_handle),
+				bandwidth_limiter,
 			)?,
 		})
 	}
@@ -194,6 +201,11 @@ impl<B: BlockT> Behaviour<B> {
 		self.discovery.known_peers()
 	}
 
+	/// Returns the addresses of the nodes we discovered in the network.
+	pub fn known_addresses(&mut self) -> HashMap<PeerId, Vec<Multiaddr>> {
+		self.discovery.known_addresses()
+	}
+
 	/// Adds a hard-coded address for the given peer, that never expires.
 	pub fn add_known_address(&mut self, peer_id: PeerId, addr: Multiaddr) {
 		self.discovery.add_known_address(peer_id, addr)
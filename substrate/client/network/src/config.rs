@@ -22,6 +22,7 @@
 //! See the documentation of [`Params`].
 
 pub use crate::{
+	bandwidth_quota::{BandwidthQuota, BandwidthQuotas},
 	discovery::DEFAULT_KADEMLIA_REPLICATION_FACTOR,
 	protocol::NotificationsSink,
 	request_responses::{
@@ -608,6 +609,9 @@ pub struct NetworkConfiguration {
 	/// a modification of the way the implementation works. Different nodes with different
 	/// configured values remain compatible with each other.
 	pub yamux_window_size: Option<u32>,
+
+	/// Per-peer bandwidth quotas of the notification and request-response protocols.
+	pub bandwidth_quotas: BandwidthQuotas,
 }
 
 impl NetworkConfiguration {
@@ -639,6 +643,7 @@ impl NetworkConfiguration {
 			kademlia_replication_factor: NonZeroUsize::new(DEFAULT_KADEMLIA_REPLICATION_FACTOR)
 				.expect("value is a constant; constant is non-zero; qed."),
 			yamux_window_size: None,
+			bandwidth_quotas: Default::default(),
 			ipfs_server: false,
 		}
 	}
@@ -744,6 +749,11 @@ impl FullNetworkConfiguration {
 	pub fn add_request_response_protocol(&mut self, config: RequestResponseConfig) {
 		self.request_response_protocols.push(config);
 	}
+
+	/// Get reference to installed request-response protocols.
+	pub fn request_response_protocols(&self) -> &Vec<RequestResponseConfig> {
+		&self.request_response_protocols
+	}
 }
 
 #[cfg(test)]
@@ -326,6 +326,27 @@ impl DiscoveryBehaviour {
 		peers
 	}
 
+	/// Returns the addresses of the nodes found in the Kademlia k-buckets and of the ones added
+	/// with [`Self::add_known_address`].
+	///
+	/// Permanent addresses are not included, as they are part of the configuration.
+	pub fn known_addresses(&mut self) -> HashMap<PeerId, Vec<Multiaddr>> {
+		let mut addresses = self.ephemeral_addresses.clone();
+		if let Some(k) = self.kademlia.as_mut() {
+			for b in k.kbuckets() {
+				for e in b.iter() {
+					let list = addresses.entry(*e.node.key.preimage()).or_default();
+					for addr in e.node.value.iter() {
+						if !list.contains(addr) {
+							list.push(addr.clone());
+						}
+					}
+				}
+			}
+		}
+		addresses
+	}
+
 	/// Adds a hard-coded address for the given peer, that never expires.
 	///
 	/// This adds an entry to the parameter that was passed to `new`.
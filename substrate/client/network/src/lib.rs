@@ -249,11 +249,13 @@ mod service;
 #[cfg(test)]
 mod mock;
 
+pub mod bandwidth_quota;
 pub mod config;
 pub mod discovery;
 pub mod error;
 pub mod event;
 pub mod network_state;
+pub mod peer_cache;
 pub mod peer_info;
 pub mod peer_store;
 pub mod protocol_controller;
@@ -277,10 +279,11 @@ pub use sc_network_common::{
 pub use service::{
 	signature::Signature,
 	traits::{
-		KademliaKey, NetworkBlock, NetworkDHTProvider, NetworkEventStream, NetworkNotification,
-		NetworkPeers, NetworkRequest, NetworkSigner, NetworkStateInfo, NetworkStatus,
-		NetworkStatusProvider, NetworkSyncForkRequest, NotificationSender as NotificationSenderT,
-		NotificationSenderError, NotificationSenderReady,
+		KademliaKey, NetworkBackend, NetworkBlock, NetworkDHTProvider, NetworkEventStream,
+		NetworkNotification, NetworkPeers, NetworkRequest, NetworkService as NetworkServiceT,
+		NetworkSigner, NetworkStateInfo, NetworkStatus, NetworkStatusProvider,
+		NetworkSyncForkRequest, NotificationSender as NotificationSenderT, NotificationSenderError,
+		NotificationSenderReady,
 	},
 	DecodingError, Keypair, NetworkService, NetworkWorker, NotificationSender, NotificationsSink,
 	OutboundFailure, PublicKey,
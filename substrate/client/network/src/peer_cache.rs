@@ -0,0 +1,239 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk cache of the peers known to the node.
+//!
+//! Reputations kept by the [`PeerStore`](crate::peer_store::PeerStore) and addresses found by the
+//! [discovery mechanism](crate::discovery) only live in memory. The network worker saves them,
+//! along with bans and the last time each peer was seen, to [`PEER_CACHE_FILE`] in the network
+//! configuration directory and restores them on startup. This way a restarted node doesn't have
+//! to go through the bootnodes again and keeps ignoring the peers it banned before.
+
+use libp2p::{Multiaddr, PeerId};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::{
+	fs, io,
+	path::Path,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Log target for this file.
+const LOG_TARGET: &str = "sub-libp2p::peer-cache";
+
+/// Name of the peer cache file in the network configuration directory.
+pub const PEER_CACHE_FILE: &str = "peers.json";
+
+/// Interval at which the peer cache is saved.
+pub(crate) const SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Maximum number of addresses saved per peer.
+pub(crate) const MAX_ADDRESSES_PER_PEER: usize = 8;
+
+/// A peer as stored in the [`PeerCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPeer {
+	/// Identity of the peer.
+	pub peer_id: PeerId,
+	/// Known addresses of the peer.
+	pub addresses: Vec<Multiaddr>,
+	/// Reputation of the peer.
+	pub reputation: i32,
+	/// Last time the reputation of the peer was updated or the peer was discovered.
+	pub last_seen: SystemTime,
+	/// Whether the peer is banned.
+	///
+	/// A banned peer keeps a reputation under
+	/// [`BANNED_THRESHOLD`](crate::peer_store::BANNED_THRESHOLD) once loaded, whatever the
+	/// reputation in the cache is. This allows banning peers by editing the file.
+	pub banned: bool,
+}
+
+/// Peers known to the node, as persisted between restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerCache {
+	/// The cached peers.
+	pub peers: Vec<CachedPeer>,
+}
+
+/// Serialized form of a [`CachedPeer`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerEntry {
+	peer_id: String,
+	addresses: Vec<String>,
+	reputation: i32,
+	/// Seconds since the UNIX epoch.
+	last_seen: u64,
+	banned: bool,
+}
+
+/// Serialized form of a [`PeerCache`].
+#[derive(Serialize, Deserialize, Default)]
+struct PeerCacheFile {
+	peers: Vec<PeerEntry>,
+}
+
+impl From<&CachedPeer> for PeerEntry {
+	fn from(peer: &CachedPeer) -> Self {
+		PeerEntry {
+			peer_id: peer.peer_id.to_base58(),
+			addresses: peer.addresses.iter().map(ToString::to_string).collect(),
+			reputation: peer.reputation,
+			last_seen: peer
+				.last_seen
+				.duration_since(UNIX_EPOCH)
+				.map_or(0, |duration| duration.as_secs()),
+			banned: peer.banned,
+		}
+	}
+}
+
+impl TryFrom<PeerEntry> for CachedPeer {
+	type Error = String;
+
+	fn try_from(entry: PeerEntry) -> Result<Self, Self::Error> {
+		let peer_id = entry
+			.peer_id
+			.parse::<PeerId>()
+			.map_err(|e| format!("invalid peer id {}: {}", entry.peer_id, e))?;
+		let addresses = entry
+			.addresses
+			.iter()
+			.filter_map(|address| match address.parse::<Multiaddr>() {
+				Ok(address) => Some(address),
+				Err(e) => {
+					debug!(target: LOG_TARGET, "Ignoring invalid address {} of {}: {}", address, peer_id, e);
+					None
+				},
+			})
+			.collect();
+
+		Ok(CachedPeer {
+			peer_id,
+			addresses,
+			reputation: entry.reputation,
+			last_seen: UNIX_EPOCH + Duration::from_secs(entry.last_seen),
+			banned: entry.banned,
+		})
+	}
+}
+
+impl PeerCache {
+	/// Loads the cache from `path`.
+	///
+	/// A missing file results in an empty cache. Invalid entries are skipped.
+	pub fn load(path: &Path) -> io::Result<Self> {
+		let content = match fs::read(path) {
+			Ok(content) => content,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+			Err(e) => return Err(e),
+		};
+		let file: PeerCacheFile = serde_json::from_slice(&content)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+		let peers = file
+			.peers
+			.into_iter()
+			.filter_map(|entry| match CachedPeer::try_from(entry) {
+				Ok(peer) => Some(peer),
+				Err(e) => {
+					debug!(target: LOG_TARGET, "Ignoring cached peer: {}", e);
+					None
+				},
+			})
+			.collect();
+
+		Ok(Self { peers })
+	}
+
+	/// Saves the cache to `path`.
+	///
+	/// The cache is written to a temporary file first, which then replaces the one at `path`, so
+	/// that an interrupted write doesn't corrupt the cache.
+	pub fn save(&self, path: &Path) -> io::Result<()> {
+		let file = PeerCacheFile { peers: self.peers.iter().map(Into::into).collect() };
+		let content = serde_json::to_vec_pretty(&file)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+		let tmp_path = path.with_extension("json.tmp");
+		fs::write(&tmp_path, content)?;
+		fs::rename(tmp_path, path)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn peer(reputation: i32, banned: bool) -> CachedPeer {
+		CachedPeer {
+			peer_id: PeerId::random(),
+			addresses: vec!["/ip4/127.0.0.1/tcp/30333".parse().unwrap()],
+			reputation,
+			last_seen: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+			banned,
+		}
+	}
+
+	#[test]
+	fn missing_file_yields_empty_cache() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = PeerCache::load(&dir.path().join(PEER_CACHE_FILE)).unwrap();
+		assert!(cache.peers.is_empty());
+	}
+
+	#[test]
+	fn cache_roundtrips() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_CACHE_FILE);
+		let cache = PeerCache { peers: vec![peer(100, false), peer(i32::MIN, true)] };
+
+		cache.save(&path).unwrap();
+		assert_eq!(PeerCache::load(&path).unwrap(), cache);
+		assert!(!path.with_extension("json.tmp").exists());
+	}
+
+	#[test]
+	fn invalid_entries_are_skipped() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_CACHE_FILE);
+		let valid = peer(-50, false);
+		let content = serde_json::json!({
+			"peers": [
+				{
+					"peerId": "not a peer id",
+					"addresses": [],
+					"reputation": 0,
+					"lastSeen": 0,
+					"banned": false,
+				},
+				{
+					"peerId": valid.peer_id.to_base58(),
+					"addresses": ["/ip4/127.0.0.1/tcp/30333", "not an address"],
+					"reputation": -50,
+					"lastSeen": 1_700_000_000u64,
+					"banned": false,
+				},
+			]
+		});
+		fs::write(&path, content.to_string()).unwrap();
+
+		assert_eq!(PeerCache::load(&path).unwrap(), PeerCache { peers: vec![valid] });
+	}
+}
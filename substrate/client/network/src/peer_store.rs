@@ -29,11 +29,11 @@ use std::{
 	collections::{hash_map::Entry, HashMap, HashSet},
 	fmt::Debug,
 	sync::Arc,
-	time::{Duration, Instant},
+	time::{Duration, Instant, SystemTime},
 };
 use wasm_timer::Delay;
 
-use crate::protocol_controller::ProtocolHandle;
+use crate::{peer_cache::CachedPeer, protocol_controller::ProtocolHandle};
 
 /// Log target for this file.
 pub const LOG_TARGET: &str = "peerset";
@@ -118,6 +118,20 @@ impl PeerStoreHandle {
 	pub fn add_known_peer(&mut self, peer_id: PeerId) {
 		self.inner.lock().add_known_peer(peer_id);
 	}
+
+	/// Get the known peers with their reputations, to be saved in the
+	/// [`PeerCache`](crate::peer_cache::PeerCache).
+	///
+	/// The addresses of the returned peers are left empty.
+	pub fn cached_peers(&self) -> Vec<CachedPeer> {
+		self.inner.lock().cached_peers()
+	}
+
+	/// Restore the reputations of peers loaded from the
+	/// [`PeerCache`](crate::peer_cache::PeerCache).
+	pub fn restore_cached_peers(&mut self, peers: &[CachedPeer]) {
+		self.inner.lock().restore_cached_peers(peers);
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -292,6 +306,48 @@ impl PeerStoreInner {
 			},
 		}
 	}
+
+	fn cached_peers(&self) -> Vec<CachedPeer> {
+		let now = Instant::now();
+		let system_now = SystemTime::now();
+
+		self.peers
+			.iter()
+			.map(|(peer_id, info)| CachedPeer {
+				peer_id: *peer_id,
+				addresses: Vec::new(),
+				reputation: info.reputation,
+				last_seen: system_now
+					.checked_sub(now.saturating_duration_since(info.last_updated))
+					.unwrap_or(system_now),
+				banned: info.is_banned(),
+			})
+			.collect()
+	}
+
+	fn restore_cached_peers(&mut self, peers: &[CachedPeer]) {
+		let now = Instant::now();
+		let system_now = SystemTime::now();
+
+		for peer in peers {
+			// Reputations don't decay while the node is offline, so that bans survive restarts.
+			let reputation = if peer.banned {
+				peer.reputation.min(BANNED_THRESHOLD - 1)
+			} else {
+				peer.reputation
+			};
+			let elapsed = system_now.duration_since(peer.last_seen).unwrap_or_default();
+			let last_updated = now.checked_sub(elapsed).unwrap_or(now);
+
+			trace!(
+				target: LOG_TARGET,
+				"Restoring cached peer {}, reputation: {}.",
+				peer.peer_id,
+				reputation,
+			);
+			self.peers.insert(peer.peer_id, PeerInfo { reputation, last_updated });
+		}
+	}
 }
 
 /// Worker part of [`PeerStoreHandle`]
@@ -343,7 +399,10 @@ impl PeerStore {
 
 #[cfg(test)]
 mod tests {
-	use super::PeerInfo;
+	use super::{PeerInfo, PeerStore, PeerStoreProvider, BANNED_THRESHOLD};
+	use crate::peer_cache::CachedPeer;
+	use libp2p::PeerId;
+	use std::time::SystemTime;
 
 	#[test]
 	fn decaying_zero_reputation_yields_zero() {
@@ -410,4 +469,41 @@ mod tests {
 		peer_info.decay_reputation(SECONDS / 2);
 		assert_eq!(peer_info.reputation, 0);
 	}
+
+	#[test]
+	fn cached_peers_are_restored() {
+		let good = PeerId::random();
+		let banned = PeerId::random();
+		let cached = [
+			CachedPeer {
+				peer_id: good,
+				addresses: Vec::new(),
+				reputation: 100,
+				last_seen: SystemTime::now(),
+				banned: false,
+			},
+			CachedPeer {
+				peer_id: banned,
+				addresses: Vec::new(),
+				reputation: 0,
+				last_seen: SystemTime::now(),
+				banned: true,
+			},
+		];
+
+		let mut handle = PeerStore::new(Vec::new()).handle();
+		handle.restore_cached_peers(&cached);
+
+		assert_eq!(handle.peer_reputation(&good), 100);
+		assert!(handle.is_banned(&banned));
+
+		let mut exported = handle.cached_peers();
+		exported.sort_by_key(|peer| peer.reputation);
+		assert_eq!(exported.len(), 2);
+		assert_eq!(exported[0].peer_id, banned);
+		assert!(exported[0].banned);
+		assert!(exported[0].reputation < BANNED_THRESHOLD);
+		assert_eq!(exported[1].peer_id, good);
+		assert!(!exported[1].banned);
+	}
 }
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	bandwidth_quota::{self, BandwidthLimiter, Direction},
 	config, error,
 	peer_store::{PeerStoreHandle, PeerStoreProvider},
 	protocol_controller::{self, SetId},
@@ -29,8 +30,9 @@ use futures::{channel::oneshot, stream::FuturesUnordered, StreamExt};
 use libp2p::{
 	core::Endpoint,
 	swarm::{
-		behaviour::FromSwarm, ConnectionDenied, ConnectionId, NetworkBehaviour, PollParameters,
-		THandler, THandlerInEvent, THandlerOutEvent, ToSwarm,
+		behaviour::{ConnectionClosed, FromSwarm},
+		ConnectionDenied, ConnectionId, NetworkBehaviour, PollParameters, THandler,
+		THandlerInEvent, THandlerOutEvent, ToSwarm,
 	},
 	Multiaddr, PeerId,
 };
@@ -77,6 +79,8 @@ type PendingSyncSubstreamValidation =
 pub struct Protocol<B: BlockT> {
 	/// Used to report reputation changes.
 	peer_store_handle: PeerStoreHandle,
+	/// Enforces the bandwidth quotas of inbound notifications.
+	bandwidth_limiter: BandwidthLimiter,
 	/// Handles opening the unique substream and sending and receiving raw messages.
 	behaviour: Notifications,
 	/// List of notifications protocols that have been registered.
@@ -102,6 +106,7 @@ impl<B: BlockT> Protocol<B> {
 		notification_protocols: Vec<config::NonDefaultSetConfig>,
 		block_announces_protocol: config::NonDefaultSetConfig,
 		peer_store_handle: PeerStoreHandle,
+		bandwidth_limiter: BandwidthLimiter,
 		protocol_controller_handles: Vec<protocol_controller::ProtocolHandle>,
 		from_protocol_controllers: TracingUnboundedReceiver<protocol_controller::Message>,
 		tx: TracingUnboundedSender<crate::event::SyncEvent<B>>,
@@ -125,11 +130,13 @@ impl<B: BlockT> Protocol<B> {
 					handshake: s.handshake.as_ref().map_or(roles.encode(), |h| (*h).to_vec()),
 					max_notification_size: s.max_notification_size,
 				})),
+				bandwidth_limiter.clone(),
 			)
 		};
 
 		let protocol = Self {
 			peer_store_handle,
+			bandwidth_limiter,
 			behaviour,
 			notification_protocols: iter::once(block_announces_protocol.notifications_protocol)
 				.chain(notification_protocols.iter().map(|s| s.notifications_protocol.clone()))
@@ -257,6 +264,14 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 	}
 
 	fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>) {
+		if let FromSwarm::ConnectionClosed(ConnectionClosed {
+			peer_id,
+			remaining_established: 0,
+			..
+		}) = &event
+		{
+			self.bandwidth_limiter.peer_disconnected(peer_id);
+		}
 		self.behaviour.on_swarm_event(event);
 	}
 
@@ -481,6 +496,14 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 			NotificationsOut::Notification { peer_id, set_id, message } => {
 				if self.bad_handshake_substreams.contains(&(peer_id, set_id)) {
 					CustomMessageOutcome::None
+				} else if !self.bandwidth_limiter.try_consume(
+					peer_id,
+					&self.notification_protocols[usize::from(set_id)],
+					Direction::Inbound,
+					message.len(),
+				) {
+					self.peer_store_handle.report_peer(peer_id, bandwidth_quota::QUOTA_EXCEEDED);
+					CustomMessageOutcome::None
 				} else if set_id == HARDCODED_PEERSETS_SYNC {
 					let _ = self.tx.unbounded_send(crate::SyncEvent::NotificationsReceived {
 						remote: peer_id,
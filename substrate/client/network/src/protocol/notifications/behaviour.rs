@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	bandwidth_quota::BandwidthLimiter,
 	protocol::notifications::handler::{
 		self, NotificationsSink, NotifsHandler, NotifsHandlerIn, NotifsHandlerOut,
 	},
@@ -106,6 +107,9 @@ pub struct Notifications {
 	/// Notification protocols. Entries never change after initialization.
 	notif_protocols: Vec<handler::ProtocolConfig>,
 
+	/// Enforces the bandwidth quotas of outbound notifications, shared with the handlers.
+	bandwidth_limiter: BandwidthLimiter,
+
 	/// Protocol controllers are responsible for peer connections management.
 	protocol_controller_handles: Vec<protocol_controller::ProtocolHandle>,
 
@@ -368,6 +372,7 @@ impl Notifications {
 		protocol_controller_handles: Vec<protocol_controller::ProtocolHandle>,
 		from_protocol_controllers: TracingUnboundedReceiver<Message>,
 		notif_protocols: impl Iterator<Item = ProtocolConfig>,
+		bandwidth_limiter: BandwidthLimiter,
 	) -> Self {
 		let notif_protocols = notif_protocols
 			.map(|cfg| handler::ProtocolConfig {
@@ -382,6 +387,7 @@ impl Notifications {
 
 		Self {
 			notif_protocols,
+			bandwidth_limiter,
 			protocol_controller_handles,
 			from_protocol_controllers,
 			peers: FnvHashMap::default(),
@@ -1021,6 +1027,7 @@ impl NetworkBehaviour for Notifications {
 				send_back_addr: remote_addr.clone(),
 			},
 			self.notif_protocols.clone(),
+			self.bandwidth_limiter.clone(),
 		))
 	}
 
@@ -1035,6 +1042,7 @@ impl NetworkBehaviour for Notifications {
 			peer,
 			ConnectedPoint::Dialer { address: addr.clone(), role_override },
 			self.notif_protocols.clone(),
+			self.bandwidth_limiter.clone(),
 		))
 	}
 
@@ -2180,6 +2188,7 @@ mod tests {
 					handshake: vec![1, 2, 3, 4],
 					max_notification_size: u64::MAX,
 				}),
+				Default::default(),
 			),
 			controller,
 		)
@@ -2394,7 +2403,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -2588,7 +2597,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -2735,7 +2744,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -2776,7 +2785,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -2843,7 +2852,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -2985,7 +2994,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn1,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3111,7 +3120,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3185,7 +3194,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3248,7 +3257,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn1,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected.clone(), vec![]),
+				handler: NotifsHandler::new(peer, connected.clone(), vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3262,7 +3271,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn2,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3313,7 +3322,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3368,7 +3377,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn2,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3426,7 +3435,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn1,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3495,7 +3504,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn1,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3536,7 +3545,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3653,7 +3662,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -3961,7 +3970,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4309,7 +4318,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: ConnectionId::new_unchecked(1337),
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4344,7 +4353,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: ConnectionId::new_unchecked(1337),
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4395,7 +4404,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: ConnectionId::new_unchecked(1337),
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4439,7 +4448,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4486,7 +4495,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: ConnectionId::new_unchecked(1337),
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4529,7 +4538,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected.clone(), vec![]),
+				handler: NotifsHandler::new(peer, connected.clone(), vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
@@ -4540,7 +4549,7 @@ mod tests {
 				peer_id: peer,
 				connection_id: conn,
 				endpoint: &connected.clone(),
-				handler: NotifsHandler::new(peer, connected, vec![]),
+				handler: NotifsHandler::new(peer, connected, vec![], Default::default()),
 				remaining_established: 0usize,
 			},
 		));
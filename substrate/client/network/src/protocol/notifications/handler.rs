@@ -58,6 +58,7 @@
 //! [`NotifsHandlerIn::Open`] has gotten an answer.
 
 use crate::{
+	bandwidth_quota::{BandwidthLimiter, Direction},
 	protocol::notifications::upgrade::{
 		NotificationsIn, NotificationsInSubstream, NotificationsOut, NotificationsOutSubstream,
 		UpgradeCollec,
@@ -122,6 +123,9 @@ pub struct NotifsHandler {
 	/// Remote we are connected to.
 	peer_id: PeerId,
 
+	/// Enforces the bandwidth quotas of outbound notifications.
+	bandwidth_limiter: BandwidthLimiter,
+
 	/// Events to return in priority from `poll`.
 	events_queue: VecDeque<
 		ConnectionHandlerEvent<NotificationsOut, usize, NotifsHandlerOut, NotifsHandlerError>,
@@ -130,7 +134,12 @@ pub struct NotifsHandler {
 
 impl NotifsHandler {
 	/// Creates new [`NotifsHandler`].
-	pub fn new(peer_id: PeerId, endpoint: ConnectedPoint, protocols: Vec<ProtocolConfig>) -> Self {
+	pub fn new(
+		peer_id: PeerId,
+		endpoint: ConnectedPoint,
+		protocols: Vec<ProtocolConfig>,
+		bandwidth_limiter: BandwidthLimiter,
+	) -> Self {
 		Self {
 			protocols: protocols
 				.into_iter()
@@ -141,11 +150,17 @@ impl NotifsHandler {
 						config.max_notification_size,
 					);
 
-					Protocol { config, in_upgrade, state: State::Closed { pending_opening: false } }
+					Protocol {
+						config,
+						in_upgrade,
+						state: State::Closed { pending_opening: false },
+						quota_delay: None,
+					}
 				})
 				.collect(),
 			peer_id,
 			endpoint,
+			bandwidth_limiter,
 			when_connection_open: Instant::now(),
 			events_queue: VecDeque::with_capacity(16),
 		}
@@ -175,6 +190,9 @@ struct Protocol {
 
 	/// Current state of the substreams for this protocol.
 	state: State,
+
+	/// Fires when the outbound bandwidth quota of the remote allows sending notifications again.
+	quota_delay: Option<futures_timer::Delay>,
 }
 
 /// See the module-level documentation to learn about the meaning of these variants.
@@ -701,9 +719,10 @@ impl ConnectionHandler for NotifsHandler {
 		// For each open substream, try send messages from `notifications_sink_rx` to the
 		// substream.
 		for protocol_index in 0..self.protocols.len() {
+			let Protocol { config, state, quota_delay, .. } = &mut self.protocols[protocol_index];
 			if let State::Open {
 				notifications_sink_rx, out_substream: Some(out_substream), ..
-			} = &mut self.protocols[protocol_index].state
+			} = state
 			{
 				loop {
 					// Only proceed with `out_substream.poll_ready_unpin` if there is an element
@@ -718,6 +737,26 @@ impl ConnectionHandler for NotifsHandler {
 						Poll::Ready(None) | Poll::Pending => break,
 					}
 
+					// Notifications exceeding the quota of the remote are kept in
+					// `notifications_sink_rx` until the quota is refilled. Once the channel is full,
+					// this applies back-pressure to the senders, like a slow remote does.
+					if let Some(delay) = self.bandwidth_limiter.exhausted_for(
+						self.peer_id,
+						&config.name,
+						Direction::Outbound,
+					) {
+						match quota_delay
+							.get_or_insert_with(|| futures_timer::Delay::new(delay))
+							.poll_unpin(cx)
+						{
+							Poll::Ready(()) => {
+								*quota_delay = None;
+								continue
+							},
+							Poll::Pending => break,
+						}
+					}
+
 					// Before we extract the element from `notifications_sink_rx`, check that the
 					// substream is ready to accept a message.
 					match out_substream.poll_ready_unpin(cx) {
@@ -738,6 +777,12 @@ impl ConnectionHandler for NotifsHandler {
 						},
 					};
 
+					self.bandwidth_limiter.force_consume(
+						self.peer_id,
+						&config.name,
+						Direction::Outbound,
+						message.len(),
+					);
 					let _ = out_substream.start_send_unpin(message);
 					// Note that flushing is performed later down this function.
 				}
@@ -825,8 +870,11 @@ impl ConnectionHandler for NotifsHandler {
 #[cfg(test)]
 pub mod tests {
 	use super::*;
-	use crate::protocol::notifications::upgrade::{
-		NotificationsInOpen, NotificationsInSubstreamHandshake, NotificationsOutOpen,
+	use crate::{
+		bandwidth_quota::{BandwidthQuota, BandwidthQuotas},
+		protocol::notifications::upgrade::{
+			NotificationsInOpen, NotificationsInSubstreamHandshake, NotificationsOutOpen,
+		},
 	};
 	use asynchronous_codec::Framed;
 	use libp2p::{
@@ -1029,6 +1077,7 @@ pub mod tests {
 			},
 			in_upgrade: NotificationsIn::new("/foo", Vec::new(), u64::MAX),
 			state: State::Closed { pending_opening: false },
+			quota_delay: None,
 		};
 
 		NotifsHandler {
@@ -1039,6 +1088,7 @@ pub mod tests {
 				send_back_addr: Multiaddr::empty(),
 			},
 			peer_id: PeerId::random(),
+			bandwidth_limiter: BandwidthLimiter::default(),
 			events_queue: VecDeque::new(),
 		}
 	}
@@ -1570,6 +1620,61 @@ pub mod tests {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn notifications_exceeding_the_quota_are_held_back() {
+		let mut handler = notifs_handler();
+		handler.bandwidth_limiter = BandwidthLimiter::new(
+			BandwidthQuotas {
+				default: BandwidthQuota { inbound: None, outbound: Some(1) },
+				protocols: HashMap::new(),
+			},
+			None,
+		)
+		.unwrap();
+		let (io, _io2) = MockSubstream::negotiated().await;
+		let codec = UviBytes::default();
+
+		let (async_tx, async_rx) = futures::channel::mpsc::channel(ASYNC_NOTIFICATIONS_BUFFER_SIZE);
+		let (sync_tx, sync_rx) = futures::channel::mpsc::channel(SYNC_NOTIFICATIONS_BUFFER_SIZE);
+		let notifications_sink = NotificationsSink {
+			inner: Arc::new(NotificationsSinkInner {
+				peer_id: PeerId::random(),
+				async_channel: FuturesMutex::new(async_tx),
+				sync_channel: Mutex::new(Some(sync_tx)),
+			}),
+		};
+
+		handler.protocols[0].state = State::Open {
+			notifications_sink_rx: stream::select(async_rx.fuse(), sync_rx.fuse()).peekable(),
+			out_substream: Some(NotificationsOutSubstream::new(Framed::new(io, codec))),
+			in_substream: None,
+		};
+
+		// The first notification uses the whole burst of 5 bytes, the second one has to wait.
+		notifications_sink.send_sync_notification(vec![0; 10]);
+		notifications_sink.send_sync_notification(vec![1, 3, 3, 7]);
+
+		futures::future::poll_fn(|cx| {
+			assert!(handler.poll(cx).is_pending());
+			Poll::Ready(())
+		})
+		.await;
+		assert!(handler.protocols[0].quota_delay.is_some());
+
+		let State::Open { notifications_sink_rx, .. } = &mut handler.protocols[0].state else {
+			panic!("invalid state");
+		};
+		futures::future::poll_fn(|cx| {
+			assert!(std::matches!(
+				Pin::new(&mut *notifications_sink_rx).poll_peek(cx),
+				Poll::Ready(Some(NotificationsSinkMessage::Notification { message }))
+					if message == &vec![1, 3, 3, 7]
+			));
+			Poll::Ready(())
+		})
+		.await;
+	}
+
 	#[tokio::test]
 	async fn close_desired_by_remote() {
 		let mut handler = notifs_handler();
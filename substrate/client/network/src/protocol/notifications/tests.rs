@@ -101,6 +101,7 @@ fn build_nodes() -> (Swarm<CustomProtoWithAddr>, Swarm<CustomProtoWithAddr>) {
 					handshake: Vec::new(),
 					max_notification_size: 1024 * 1024,
 				}),
+				Default::default(),
 			),
 			peer_store_future: peer_store.run().boxed(),
 			protocol_controller_future: controller.run().boxed(),
@@ -35,6 +35,7 @@
 //! is used to handle incoming requests.
 
 use crate::{
+	bandwidth_quota::{self, BandwidthLimiter, Direction},
 	peer_store::{PeerStoreProvider, BANNED_THRESHOLD},
 	types::ProtocolName,
 	ReputationChange,
@@ -75,6 +76,8 @@ pub enum RequestFailure {
 	Refused,
 	#[error("The remote replied, but the local node is no longer interested in the response.")]
 	Obsolete,
+	#[error("The outbound bandwidth quota of the requested peer is exhausted.")]
+	QuotaExceeded,
 	#[error("Problem on the network: {0}")]
 	Network(OutboundFailure),
 }
@@ -282,6 +285,9 @@ pub struct RequestResponsesBehaviour {
 
 	/// Primarily used to get a reputation of a node.
 	peer_store: Box<dyn PeerStoreProvider>,
+
+	/// Enforces the bandwidth quotas of requests and accounts for responses.
+	bandwidth_limiter: BandwidthLimiter,
 }
 
 /// Generated by the response builder and waiting to be processed.
@@ -299,6 +305,7 @@ impl RequestResponsesBehaviour {
 	pub fn new(
 		list: impl Iterator<Item = ProtocolConfig>,
 		peer_store: Box<dyn PeerStoreProvider>,
+		bandwidth_limiter: BandwidthLimiter,
 	) -> Result<Self, RegisterError> {
 		let mut protocols = HashMap::new();
 		for protocol in list {
@@ -336,6 +343,7 @@ impl RequestResponsesBehaviour {
 			pending_responses_arrival_time: Default::default(),
 			send_feedback: Default::default(),
 			peer_store,
+			bandwidth_limiter,
 		})
 	}
 
@@ -356,10 +364,25 @@ impl RequestResponsesBehaviour {
 		log::trace!(target: "sub-libp2p", "send request to {target} ({protocol_name:?}), {} bytes", request.len());
 
 		if let Some((protocol, _)) = self.protocols.get_mut(protocol_name) {
-			if protocol.is_connected(target) || connect.should_connect() {
+			let protocol_name = ProtocolName::from(protocol_name.to_string());
+			if !self.bandwidth_limiter.try_consume(
+				*target,
+				&protocol_name,
+				Direction::Outbound,
+				request.len(),
+			) {
+				if pending_response.send(Err(RequestFailure::QuotaExceeded)).is_err() {
+					log::debug!(
+						target: "sub-libp2p",
+						"Bandwidth quota of {:?} exceeded. At the same time local \
+						 node is no longer interested in the result.",
+						target,
+					);
+				}
+			} else if protocol.is_connected(target) || connect.should_connect() {
 				let request_id = protocol.send_request(target, request);
 				let prev_req_id = self.pending_requests.insert(
-					(protocol_name.to_string().into(), request_id).into(),
+					(protocol_name, request_id).into(),
 					(Instant::now(), pending_response),
 				);
 				debug_assert!(prev_req_id.is_none(), "Expect request id to be unique.");
@@ -569,6 +592,14 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 
 				if let Ok(payload) = result {
 					if let Some((protocol, _)) = self.protocols.get_mut(&*protocol_name) {
+						// The response is charged to the inbound quota of the peer which requested
+						// it, so that small requests for large responses can't exceed the quota.
+						self.bandwidth_limiter.force_consume(
+							peer,
+							&protocol_name,
+							Direction::Inbound,
+							payload.len(),
+						);
 						log::trace!(target: "sub-libp2p", "send response to {peer} ({protocol_name:?}), {} bytes", payload.len());
 
 						if protocol.send_response(inner_channel, Ok(payload)).is_err() {
@@ -646,6 +677,16 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								continue 'poll_protocol
 							}
 
+							if !self.bandwidth_limiter.try_consume(
+								peer,
+								protocol,
+								Direction::Inbound,
+								request.len(),
+							) {
+								self.peer_store.report_peer(peer, bandwidth_quota::QUOTA_EXCEEDED);
+								continue 'poll_protocol
+							}
+
 							let (tx, rx) = oneshot::channel();
 
 							// Submit the request to the "response builder" passed by the user at
@@ -975,7 +1016,10 @@ impl Codec for GenericCodec {
 mod tests {
 	use super::*;
 
-	use crate::mock::MockPeerStore;
+	use crate::{
+		bandwidth_quota::{BandwidthQuota, BandwidthQuotas},
+		mock::MockPeerStore,
+	};
 	use futures::{channel::oneshot, executor::LocalPool, task::Spawn};
 	use libp2p::{
 		core::{
@@ -998,6 +1042,13 @@ mod tests {
 
 	fn build_swarm(
 		list: impl Iterator<Item = ProtocolConfig>,
+	) -> (Swarm<RequestResponsesBehaviour>, Multiaddr) {
+		build_swarm_with_limiter(list, BandwidthLimiter::default())
+	}
+
+	fn build_swarm_with_limiter(
+		list: impl Iterator<Item = ProtocolConfig>,
+		bandwidth_limiter: BandwidthLimiter,
 	) -> (Swarm<RequestResponsesBehaviour>, Multiaddr) {
 		let keypair = Keypair::generate_ed25519();
 
@@ -1007,7 +1058,9 @@ mod tests {
 			.multiplex(libp2p::yamux::Config::default())
 			.boxed();
 
-		let behaviour = RequestResponsesBehaviour::new(list, Box::new(MockPeerStore {})).unwrap();
+		let behaviour =
+			RequestResponsesBehaviour::new(list, Box::new(MockPeerStore {}), bandwidth_limiter)
+				.unwrap();
 
 		let runtime = tokio::runtime::Runtime::new().unwrap();
 		let mut swarm = SwarmBuilder::with_executor(
@@ -1224,6 +1277,94 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn responses_are_charged_to_the_inbound_quota_of_the_requester() {
+		let protocol_name = "/test/req-resp/1";
+		let mut pool = LocalPool::new();
+
+		let (tx, mut rx) = async_channel::bounded::<IncomingRequest>(64);
+		pool.spawner()
+			.spawn_obj(
+				async move {
+					while let Some(rq) = rx.next().await {
+						let _ = rq.pending_response.send(super::OutgoingResponse {
+							result: Ok(vec![0; 1000]),
+							reputation_changes: Vec::new(),
+							sent_feedback: None,
+						});
+					}
+				}
+				.boxed()
+				.into(),
+			)
+			.unwrap();
+		let protocol_config = |inbound_queue| ProtocolConfig {
+			name: From::from(protocol_name),
+			fallback_names: Vec::new(),
+			max_request_size: 1024,
+			max_response_size: 1024 * 1024,
+			request_timeout: Duration::from_secs(30),
+			inbound_queue,
+		};
+
+		// The burst of the responder only covers a single response.
+		let quotas = BandwidthQuotas {
+			default: BandwidthQuota { inbound: Some(100), outbound: None },
+			protocols: Default::default(),
+		};
+		let (mut responder, listen_addr) = build_swarm_with_limiter(
+			iter::once(protocol_config(Some(tx))),
+			BandwidthLimiter::new(quotas, None).unwrap(),
+		);
+		let (mut requester, _) = build_swarm(iter::once(protocol_config(None)));
+		Swarm::dial(&mut requester, listen_addr).unwrap();
+
+		pool.spawner()
+			.spawn_obj(
+				async move {
+					loop {
+						let _ = responder.select_next_some().await;
+					}
+				}
+				.boxed()
+				.into(),
+			)
+			.unwrap();
+
+		pool.run_until(async move {
+			let send_request = |swarm: &mut Swarm<RequestResponsesBehaviour>, peer_id| {
+				let (sender, receiver) = oneshot::channel();
+				swarm.behaviour_mut().send_request(
+					&peer_id,
+					protocol_name,
+					b"small request".to_vec(),
+					sender,
+					IfDisconnected::ImmediateError,
+				);
+				receiver
+			};
+			let mut responses = Vec::new();
+
+			loop {
+				match requester.select_next_some().await {
+					SwarmEvent::ConnectionEstablished { peer_id, .. } =>
+						responses.push(send_request(&mut requester, peer_id)),
+					SwarmEvent::Behaviour(Event::RequestFinished { peer, .. }) => {
+						if responses.len() == 2 {
+							break
+						}
+						responses.push(send_request(&mut requester, peer));
+					},
+					_ => {},
+				}
+			}
+
+			assert_eq!(responses.remove(0).await.unwrap().unwrap().len(), 1000);
+			// The first response exhausted the quota, the second request is refused.
+			assert!(responses.remove(0).await.unwrap().is_err());
+		});
+	}
+
 	/// A [`RequestId`] is a unique identifier among either all inbound or all outbound requests for
 	/// a single [`RequestResponsesBehaviour`] behaviour. It is not guaranteed to be unique across
 	/// multiple [`RequestResponsesBehaviour`] behaviours. Thus when handling [`RequestId`] in the
@@ -28,29 +28,37 @@
 //! which is then processed by [`NetworkWorker::next_action`].
 
 use crate::{
+	bandwidth_quota::BandwidthLimiter,
 	behaviour::{self, Behaviour, BehaviourOut},
-	config::{parse_addr, FullNetworkConfiguration, MultiaddrWithPeerId, Params, TransportConfig},
+	config::{
+		parse_addr, FullNetworkConfiguration, MultiaddrWithPeerId, NonDefaultSetConfig,
+		NotificationHandshake, Params, SetConfig, TransportConfig,
+	},
 	discovery::DiscoveryConfig,
 	error::Error,
 	event::{DhtEvent, Event},
 	network_state::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
-	peer_store::{PeerStoreHandle, PeerStoreProvider},
+	peer_cache::{self, CachedPeer, PeerCache},
+	peer_store::{PeerStore, PeerStoreHandle, PeerStoreProvider},
 	protocol::{self, NotifsHandlerError, Protocol, Ready},
 	protocol_controller::{self, ProtoSetConfig, ProtocolController, SetId},
-	request_responses::{IfDisconnected, RequestFailure},
+	request_responses::{
+		IfDisconnected, IncomingRequest, ProtocolConfig as RequestResponseConfig, RequestFailure,
+	},
 	service::{
 		signature::{Signature, SigningError},
 		traits::{
-			NetworkDHTProvider, NetworkEventStream, NetworkNotification, NetworkPeers,
-			NetworkRequest, NetworkSigner, NetworkStateInfo, NetworkStatus, NetworkStatusProvider,
-			NotificationSender as NotificationSenderT, NotificationSenderError,
-			NotificationSenderReady as NotificationSenderReadyT,
+			NetworkBackend, NetworkDHTProvider, NetworkEventStream, NetworkNotification,
+			NetworkPeers, NetworkRequest, NetworkSigner, NetworkStateInfo, NetworkStatus,
+			NetworkStatusProvider, NotificationSender as NotificationSenderT,
+			NotificationSenderError, NotificationSenderReady as NotificationSenderReadyT,
 		},
 	},
 	transport,
 	types::ProtocolName,
+	utils::interval,
 	ReputationChange,
 };
 
@@ -84,12 +92,14 @@ use std::{
 	fs, iter,
 	marker::PhantomData,
 	num::NonZeroUsize,
+	path::PathBuf,
 	pin::Pin,
 	str,
 	sync::{
 		atomic::{AtomicUsize, Ordering},
 		Arc,
 	},
+	time::Duration,
 };
 
 pub use behaviour::{InboundFailure, OutboundFailure, ResponseFailure};
@@ -214,6 +224,18 @@ where
 			fs::create_dir_all(path)?;
 		}
 
+		let peer_cache_path = network_config
+			.net_config_path
+			.as_ref()
+			.map(|path| path.join(peer_cache::PEER_CACHE_FILE));
+		let peer_cache = match &peer_cache_path {
+			Some(path) => PeerCache::load(path).unwrap_or_else(|e| {
+				warn!(target: "sub-libp2p", "Failed to load peer cache from {}: {}", path.display(), e);
+				PeerCache::default()
+			}),
+			None => PeerCache::default(),
+		};
+
 		info!(
 			target: "sub-libp2p",
 			"🏷  Local node identity is: {}",
@@ -317,11 +339,17 @@ where
 				})
 				.collect();
 
+		let bandwidth_limiter = BandwidthLimiter::new(
+			network_config.bandwidth_quotas.clone(),
+			params.metrics_registry.as_ref(),
+		)?;
+
 		let protocol = Protocol::new(
 			From::from(&params.role),
 			notification_protocols.clone(),
 			params.block_announce_config,
 			params.peer_store.clone(),
+			bandwidth_limiter.clone(),
 			protocol_handles.clone(),
 			from_protocol_controllers,
 			params.tx,
@@ -436,6 +464,7 @@ where
 					discovery_config,
 					request_response_protocols,
 					params.peer_store.clone(),
+					bandwidth_limiter,
 					external_addresses.clone(),
 				);
 
@@ -507,6 +536,23 @@ where
 			);
 		}
 
+		// Restore the peers known before the restart, before any bootnode is dialed.
+		let cached_peers = peer_cache
+			.peers
+			.into_iter()
+			.filter(|peer| peer.peer_id != local_peer_id)
+			.collect::<Vec<_>>();
+		if !cached_peers.is_empty() {
+			debug!(target: "sub-libp2p", "Restoring {} cached peers", cached_peers.len());
+		}
+		let mut peer_store_handle = params.peer_store;
+		peer_store_handle.restore_cached_peers(&cached_peers);
+		for peer in cached_peers {
+			for addr in peer.addresses {
+				swarm.behaviour_mut().add_known_address(peer.peer_id, addr);
+			}
+		}
+
 		let listen_addresses = Arc::new(Mutex::new(HashSet::new()));
 		let peers_notifications_sinks = Arc::new(Mutex::new(HashMap::new()));
 
@@ -540,7 +586,11 @@ where
 			boot_node_ids,
 			reported_invalid_boot_nodes: Default::default(),
 			peers_notifications_sinks,
-			peer_store_handle: params.peer_store,
+			peer_store_handle,
+			peer_cache_path,
+			peer_cache_interval: (Box::pin(interval(peer_cache::SAVE_INTERVAL))
+				as Pin<Box<dyn Stream<Item = ()> + Send>>)
+				.fuse(),
 			_marker: Default::default(),
 			_block: Default::default(),
 		})
@@ -717,38 +767,6 @@ where
 }
 
 impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
-	/// Get network state.
-	///
-	/// **Note**: Use this only for debugging. This API is unstable. There are warnings literally
-	/// everywhere about this. Please don't use this function to retrieve actual information.
-	///
-	/// Returns an error if the `NetworkWorker` is no longer running.
-	pub async fn network_state(&self) -> Result<NetworkState, ()> {
-		let (tx, rx) = oneshot::channel();
-
-		let _ = self
-			.to_worker
-			.unbounded_send(ServiceToWorkerMsg::NetworkState { pending_response: tx });
-
-		match rx.await {
-			Ok(v) => v.map_err(|_| ()),
-			// The channel can only be closed if the network worker no longer exists.
-			Err(_) => Err(()),
-		}
-	}
-
-	/// Get the list of reserved peers.
-	///
-	/// Returns an error if the `NetworkWorker` is no longer running.
-	pub async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
-		let (tx, rx) = oneshot::channel();
-
-		self.sync_protocol_handle.reserved_peers(tx);
-
-		// The channel can only be closed if `ProtocolController` no longer exists.
-		rx.await.map_err(|_| ())
-	}
-
 	/// Utility function to extract `PeerId` from each `Multiaddr` for peer set updates.
 	///
 	/// Returns an `Err` if one of the given addresses is invalid or contains an
@@ -850,6 +868,29 @@ where
 			Err(_) => Err(()),
 		}
 	}
+
+	async fn network_state(&self) -> Result<NetworkState, ()> {
+		let (tx, rx) = oneshot::channel();
+
+		let _ = self
+			.to_worker
+			.unbounded_send(ServiceToWorkerMsg::NetworkState { pending_response: tx });
+
+		match rx.await {
+			Ok(v) => v.map_err(|_| ()),
+			// The channel can only be closed if the network worker no longer exists.
+			Err(_) => Err(()),
+		}
+	}
+
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
+		let (tx, rx) = oneshot::channel();
+
+		self.sync_protocol_handle.reserved_peers(tx);
+
+		// The channel can only be closed if `ProtocolController` no longer exists.
+		rx.await.map_err(|_| ())
+	}
 }
 
 impl<B, H> NetworkPeers for NetworkService<B, H>
@@ -1244,6 +1285,10 @@ where
 	peers_notifications_sinks: Arc<Mutex<HashMap<(PeerId, ProtocolName), NotificationsSink>>>,
 	/// Peer reputation store handle.
 	peer_store_handle: PeerStoreHandle,
+	/// Path of the peer cache file, if the peer cache is enabled.
+	peer_cache_path: Option<PathBuf>,
+	/// Interval at which the peer cache is saved.
+	peer_cache_interval: stream::Fuse<Pin<Box<dyn Stream<Item = ()> + Send>>>,
 	/// Marker to pin the `H` generic. Serves no purpose except to not break backwards
 	/// compatibility.
 	_marker: PhantomData<H>,
@@ -1279,6 +1324,9 @@ where
 			event = self.network_service.select_next_some() => {
 				self.handle_swarm_event(event);
 			},
+			_ = self.peer_cache_interval.next() => {
+				self.save_peer_cache();
+			},
 		};
 
 		// Update the `num_connected` count shared with the `NetworkService`.
@@ -1315,6 +1363,34 @@ where
 		true
 	}
 
+	/// Save the known peers to the peer cache, if enabled.
+	fn save_peer_cache(&mut self) {
+		let Some(path) = self.peer_cache_path.as_ref() else { return };
+
+		let mut addresses = self.network_service.behaviour_mut().known_addresses();
+		let peers = self
+			.peer_store_handle
+			.cached_peers()
+			.into_iter()
+			.map(|peer| CachedPeer {
+				addresses: addresses
+					.remove(&peer.peer_id)
+					.unwrap_or_default()
+					.into_iter()
+					.take(peer_cache::MAX_ADDRESSES_PER_PEER)
+					.collect(),
+				..peer
+			})
+			.collect::<Vec<_>>();
+
+		match (PeerCache { peers }).save(path) {
+			Ok(()) => trace!(target: "sub-libp2p", "Saved peer cache to {}", path.display()),
+			Err(e) => {
+				warn!(target: "sub-libp2p", "Failed to save peer cache to {}: {}", path.display(), e)
+			},
+		}
+	}
+
 	/// Process the next message coming from the `NetworkService`.
 	fn handle_worker_message(&mut self, msg: ServiceToWorkerMsg) {
 		match msg {
@@ -1419,6 +1495,7 @@ where
 								RequestFailure::UnknownProtocol => "unknown-protocol",
 								RequestFailure::Refused => "refused",
 								RequestFailure::Obsolete => "obsolete",
+								RequestFailure::QuotaExceeded => "quota-exceeded",
 								RequestFailure::Network(OutboundFailure::DialFailure) =>
 									"dial-failure",
 								RequestFailure::Network(OutboundFailure::Timeout) => "timeout",
@@ -1790,6 +1867,69 @@ where
 	}
 }
 
+#[async_trait::async_trait]
+impl<B, H> NetworkBackend<B, H> for NetworkWorker<B, H>
+where
+	B: BlockT + 'static,
+	H: ExHashT,
+{
+	type NetworkService = Arc<NetworkService<B, H>>;
+
+	fn new(params: Params<B>) -> Result<Self, Error> {
+		NetworkWorker::new(params)
+	}
+
+	fn peer_store(bootnodes: Vec<PeerId>) -> PeerStore {
+		PeerStore::new(bootnodes)
+	}
+
+	fn notification_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_notification_size: u64,
+		handshake: Option<NotificationHandshake>,
+		set_config: SetConfig,
+	) -> NonDefaultSetConfig {
+		NonDefaultSetConfig {
+			notifications_protocol: protocol_name,
+			fallback_names,
+			max_notification_size,
+			handshake,
+			set_config,
+		}
+	}
+
+	fn request_response_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_request_size: u64,
+		max_response_size: u64,
+		request_timeout: Duration,
+		inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
+	) -> RequestResponseConfig {
+		RequestResponseConfig {
+			name: protocol_name,
+			fallback_names,
+			max_request_size,
+			max_response_size,
+			request_timeout,
+			inbound_queue,
+		}
+	}
+
+	fn network_service(&self) -> Self::NetworkService {
+		self.service.clone()
+	}
+
+	fn add_known_address(&mut self, peer_id: PeerId, addr: Multiaddr) {
+		NetworkWorker::add_known_address(self, peer_id, addr)
+	}
+
+	async fn run(mut self) {
+		NetworkWorker::run(self).await
+	}
+}
+
 impl<B, H> Unpin for NetworkWorker<B, H>
 where
 	B: BlockT + 'static,
@@ -1797,6 +1937,16 @@ where
 {
 }
 
+impl<B, H> Drop for NetworkWorker<B, H>
+where
+	B: BlockT + 'static,
+	H: ExHashT,
+{
+	fn drop(&mut self) {
+		self.save_peer_cache();
+	}
+}
+
 fn ensure_addresses_consistent_with_transport<'a>(
 	addresses: impl Iterator<Item = &'a Multiaddr>,
 	transport: &TransportConfig,
@@ -19,9 +19,12 @@
 // If you read this, you are very thorough, congratulations.
 
 use crate::{
-	config::MultiaddrWithPeerId,
+	config::{MultiaddrWithPeerId, NonDefaultSetConfig, NotificationHandshake, Params, SetConfig},
+	error::Error,
 	event::Event,
-	request_responses::{IfDisconnected, RequestFailure},
+	network_state::NetworkState,
+	peer_store::PeerStore,
+	request_responses::{IfDisconnected, IncomingRequest, ProtocolConfig, RequestFailure},
 	service::signature::Signature,
 	types::ProtocolName,
 	ReputationChange,
@@ -29,8 +32,10 @@ use crate::{
 
 use futures::{channel::oneshot, Stream};
 use libp2p::{Multiaddr, PeerId};
+use sc_network_common::ExHashT;
+use sp_runtime::traits::Block as BlockT;
 
-use std::{collections::HashSet, future::Future, pin::Pin, sync::Arc};
+use std::{collections::HashSet, future::Future, pin::Pin, sync::Arc, time::Duration};
 
 pub use libp2p::{identity::SigningError, kad::record::Key as KademliaKey};
 
@@ -112,6 +117,19 @@ pub trait NetworkStatusProvider {
 	///
 	/// Returns an error if the `NetworkWorker` is no longer running.
 	async fn status(&self) -> Result<NetworkStatus, ()>;
+
+	/// Get network state.
+	///
+	/// **Note**: Use this only for debugging. This API is unstable. There are warnings literally
+	/// everywhere about this. Please don't use this function to retrieve actual information.
+	///
+	/// Returns an error if the `NetworkWorker` is no longer running.
+	async fn network_state(&self) -> Result<NetworkState, ()>;
+
+	/// Get the list of reserved peers.
+	///
+	/// Returns an error if the `NetworkWorker` is no longer running.
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()>;
 }
 
 // Manual implementation to avoid extra boxing here
@@ -129,6 +147,26 @@ where
 	{
 		T::status(self)
 	}
+
+	fn network_state<'life0, 'async_trait>(
+		&'life0 self,
+	) -> Pin<Box<dyn Future<Output = Result<NetworkState, ()>> + Send + 'async_trait>>
+	where
+		'life0: 'async_trait,
+		Self: 'async_trait,
+	{
+		T::network_state(self)
+	}
+
+	fn reserved_peers<'life0, 'async_trait>(
+		&'life0 self,
+	) -> Pin<Box<dyn Future<Output = Result<Vec<PeerId>, ()>> + Send + 'async_trait>>
+	where
+		'life0: 'async_trait,
+		Self: 'async_trait,
+	{
+		T::reserved_peers(self)
+	}
 }
 
 /// Provides low-level API for manipulating network peers.
@@ -611,3 +649,103 @@ where
 		T::new_best_block_imported(self, hash, number)
 	}
 }
+
+/// Handle to a [`NetworkBackend`], combining all the network service traits.
+///
+/// Components that talk to the network through this trait, rather than through the libp2p
+/// [`NetworkService`](crate::NetworkService), work with any backend.
+pub trait NetworkService:
+	NetworkSigner
+	+ NetworkDHTProvider
+	+ NetworkStatusProvider
+	+ NetworkPeers
+	+ NetworkEventStream
+	+ NetworkNotification
+	+ NetworkRequest
+	+ NetworkStateInfo
+	+ Send
+	+ Sync
+	+ 'static
+{
+}
+
+impl<T> NetworkService for T where
+	T: NetworkSigner
+		+ NetworkDHTProvider
+		+ NetworkStatusProvider
+		+ NetworkPeers
+		+ NetworkEventStream
+		+ NetworkNotification
+		+ NetworkRequest
+		+ NetworkStateInfo
+		+ Send
+		+ Sync
+		+ 'static
+{
+}
+
+/// Networking backend.
+///
+/// The backend owns the connections to other peers. It negotiates the notification and
+/// request-response protocols of [`Params::network_config`], fills the peer sets from the
+/// [`PeerStore`](crate::peer_store::PeerStore) and discovers new peers. The rest of the node only
+/// interacts with it through the handle returned by [`NetworkBackend::network_service`].
+///
+/// The peer store and the protocol configurations passed in [`Params`] are created through
+/// [`NetworkBackend::peer_store`], [`NetworkBackend::notification_config`] and
+/// [`NetworkBackend::request_response_config`], so that protocols don't depend on a specific
+/// backend.
+///
+/// [`NetworkWorker`](crate::NetworkWorker) is the libp2p implementation.
+#[async_trait::async_trait]
+pub trait NetworkBackend<B: BlockT + 'static, H: ExHashT>: Send + 'static {
+	/// Handle to the backend.
+	type NetworkService: NetworkService + Clone;
+
+	/// Create the backend.
+	///
+	/// The backend doesn't make any progress until [`NetworkBackend::run`] is polled.
+	fn new(params: Params<B>) -> Result<Self, Error>
+	where
+		Self: Sized;
+
+	/// Create the peer store from which the backend fills its peer sets, initialized with the
+	/// peer ids of the bootnodes.
+	///
+	/// The peer store must be run in the background and its handle passed in
+	/// [`Params::peer_store`].
+	fn peer_store(bootnodes: Vec<PeerId>) -> PeerStore;
+
+	/// Create the configuration of a notification protocol, to be added to
+	/// [`Params::network_config`].
+	fn notification_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_notification_size: u64,
+		handshake: Option<NotificationHandshake>,
+		set_config: SetConfig,
+	) -> NonDefaultSetConfig;
+
+	/// Create the configuration of a request-response protocol, to be added to
+	/// [`Params::network_config`].
+	///
+	/// Incoming requests are pushed on `inbound_queue`, the protocol is outbound only if it is
+	/// `None`.
+	fn request_response_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_request_size: u64,
+		max_response_size: u64,
+		request_timeout: Duration,
+		inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
+	) -> ProtocolConfig;
+
+	/// Returns a handle to the backend that can be shared through the codebase.
+	fn network_service(&self) -> Self::NetworkService;
+
+	/// Add an address of `peer_id` to the discovery mechanism.
+	fn add_known_address(&mut self, peer_id: PeerId, addr: Multiaddr);
+
+	/// Drive the backend. Returns once the backend has shut down.
+	async fn run(mut self);
+}
@@ -98,6 +98,9 @@ const INACTIVITY_EVICT_THRESHOLD: Duration = Duration::from_secs(30);
 /// before it starts evicting peers.
 const INITIAL_EVICTION_WAIT_PERIOD: Duration = Duration::from_secs(2 * 60);
 
+/// Name of the directory of the state sync journal in the network configuration directory.
+const STATE_SYNC_JOURNAL_DIR: &str = "state-sync";
+
 mod rep {
 	use sc_network::ReputationChange as Rep;
 	/// Peer has different genesis.
@@ -396,6 +399,11 @@ where
 			block_downloader,
 			state_request_protocol_name,
 			warp_sync_protocol_name,
+			net_config
+				.network_config
+				.net_config_path
+				.as_ref()
+				.map(|path| path.join(STATE_SYNC_JOURNAL_DIR)),
 		)?;
 
 		let block_announce_protocol_name = block_announce_config.notifications_protocol.clone();
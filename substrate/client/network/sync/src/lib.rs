@@ -83,6 +83,7 @@ use std::{
 	collections::{HashMap, HashSet},
 	iter,
 	ops::Range,
+	path::PathBuf,
 	pin::Pin,
 	sync::Arc,
 };
@@ -328,6 +329,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	state_request_protocol_name: ProtocolName,
 	/// Protocol name used to send out warp sync requests
 	warp_sync_protocol_name: Option<ProtocolName>,
+	/// Directory of the journal allowing an interrupted state download to resume.
+	state_sync_journal_dir: Option<PathBuf>,
 	/// Pending responses
 	pending_responses: HashMap<PeerId, PendingResponse<B>>,
 	/// Handle to import queue.
@@ -586,7 +589,11 @@ where
 						log::debug!(target: LOG_TARGET, "Starting warp state sync.");
 
 						if let Some(config) = self.warp_sync_config.take() {
-							let mut warp_sync = WarpSync::new(self.client.clone(), config);
+							let mut warp_sync = WarpSync::new(
+								self.client.clone(),
+								config,
+								self.state_sync_journal_dir.clone(),
+							);
 							if let Some(header) = self.warp_sync_target_block_header.take() {
 								warp_sync.set_target_block(header);
 							}
@@ -1027,21 +1034,38 @@ where
 				let mut heads: Vec<_> = self.peers.values().map(|peer| peer.best_number).collect();
 				heads.sort();
 				let median = heads[heads.len() / 2];
-				if number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
-					if let Ok(Some(header)) = self.client.header(*hash) {
-						log::debug!(
-							target: LOG_TARGET,
-							"Starting state sync for #{number} ({hash})",
-						);
-						self.state_sync = Some(StateSync::new(
-							self.client.clone(),
-							header,
-							None,
-							None,
-							*skip_proofs,
-						));
-						self.allowed_requests.set_all();
-					}
+				let is_recent = |number: NumberFor<B>| {
+					number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median
+				};
+				// Resume the interrupted state sync of an older block if peers are still likely
+				// to keep its state.
+				let journaled_header = self
+					.state_sync_journal_dir
+					.as_deref()
+					.and_then(state::journaled_target::<B>)
+					.and_then(|target| self.client.header(target).ok().flatten())
+					.filter(|header| is_recent(*header.number()));
+				let header = match journaled_header {
+					Some(header) => Some(header),
+					None if is_recent(number) => self.client.header(*hash).ok().flatten(),
+					None => None,
+				};
+				if let Some(header) = header {
+					log::debug!(
+						target: LOG_TARGET,
+						"Starting state sync for #{} ({})",
+						header.number(),
+						header.hash(),
+					);
+					self.state_sync = Some(StateSync::new(
+						self.client.clone(),
+						header,
+						None,
+						None,
+						*skip_proofs,
+						self.state_sync_journal_dir.as_deref(),
+					));
+					self.allowed_requests.set_all();
 				}
 			}
 		}
@@ -1147,6 +1171,12 @@ where
 		self.peers.remove(who);
 		self.pending_responses.remove(who);
 		self.extra_justifications.peer_disconnected(who);
+		if let Some(state_sync) = &mut self.state_sync {
+			state_sync.peer_disconnected(who);
+		}
+		if let Some(warp_sync) = &mut self.warp_sync {
+			warp_sync.peer_disconnected(who);
+		}
 		self.allowed_requests.set_all();
 		self.fork_targets.retain(|_, target| {
 			target.peers.remove(who);
@@ -1228,6 +1258,7 @@ where
 		block_downloader: Arc<dyn BlockDownloader<B>>,
 		state_request_protocol_name: ProtocolName,
 		warp_sync_protocol_name: Option<ProtocolName>,
+		state_sync_journal_dir: Option<PathBuf>,
 	) -> Result<(Self, NonDefaultSetConfig), ClientError> {
 		let block_announce_config = Self::get_block_announce_proto_config(
 			protocol_id,
@@ -1266,6 +1297,7 @@ where
 			warp_sync_config,
 			warp_sync_target_block_header: None,
 			warp_sync_protocol_name,
+			state_sync_journal_dir,
 			block_announce_protocol_name: block_announce_config
 				.notifications_protocol
 				.clone()
@@ -1668,6 +1700,16 @@ where
 		}
 	}
 
+	/// Release the state range requested from `who` after the request failed.
+	fn state_request_failed(&mut self, who: &PeerId) {
+		if let Some(state_sync) = &mut self.state_sync {
+			state_sync.request_failed(who);
+		}
+		if let Some(warp_sync) = &mut self.warp_sync {
+			warp_sync.request_failed(who);
+		}
+	}
+
 	fn send_warp_sync_request(&mut self, who: PeerId, request: WarpProofRequest<B>) {
 		let (tx, rx) = oneshot::channel();
 
@@ -1787,7 +1829,7 @@ where
 			self.send_block_request(id, request);
 		}
 
-		if let Some((id, request)) = self.state_request() {
+		for (id, request) in self.state_requests() {
 			self.send_state_request(id, request);
 		}
 
@@ -1856,6 +1898,7 @@ where
 									target: LOG_TARGET,
 									"Failed to decode state response from peer {id:?}: {e:?}.",
 								);
+								self.state_request_failed(&id);
 								self.network_service.report_peer(id, rep::BAD_MESSAGE);
 								self.network_service
 									.disconnect_peer(id, self.block_announce_protocol_name.clone());
@@ -1873,6 +1916,9 @@ where
 				},
 				Ok(Err(e)) => {
 					debug!(target: LOG_TARGET, "Request to peer {id:?} failed: {e:?}.");
+					if let PeerRequest::State = request {
+						self.state_request_failed(&id);
+					}
 
 					match e {
 						RequestFailure::Network(OutboundFailure::Timeout) => {
@@ -1895,7 +1941,8 @@ where
 								.disconnect_peer(id, self.block_announce_protocol_name.clone());
 						},
 						RequestFailure::Network(OutboundFailure::ConnectionClosed) |
-						RequestFailure::NotConnected => {
+						RequestFailure::NotConnected |
+						RequestFailure::QuotaExceeded => {
 							self.network_service
 								.disconnect_peer(id, self.block_announce_protocol_name.clone());
 						},
@@ -1916,6 +1963,9 @@ where
 						target: LOG_TARGET,
 						"Request to peer {id:?} failed due to oneshot being canceled.",
 					);
+					if let PeerRequest::State = request {
+						self.state_request_failed(&id);
+					}
 					self.network_service
 						.disconnect_peer(id, self.block_announce_protocol_name.clone());
 				},
@@ -2089,49 +2139,46 @@ where
 		// Box::new(iter)
 	}
 
-	fn state_request(&mut self) -> Option<(PeerId, OpaqueStateRequest)> {
+	/// Get state requests for the ranges of the state not being downloaded yet, one range per
+	/// available peer.
+	fn state_requests(&mut self) -> Vec<(PeerId, OpaqueStateRequest)> {
+		let mut requests = Vec::new();
 		if self.allowed_requests.is_empty() {
-			return None
+			return requests
 		}
-		if (self.state_sync.is_some() || self.warp_sync.is_some()) &&
-			self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState)
-		{
-			// Only one pending state request is allowed.
-			return None
-		}
-		if let Some(sync) = &self.state_sync {
+		if let Some(sync) = &mut self.state_sync {
 			if sync.is_complete() {
-				return None
+				return requests
 			}
 
 			for (id, peer) in self.peers.iter_mut() {
 				if peer.state.is_available() && peer.common_number >= sync.target_block_num() {
-					peer.state = PeerSyncState::DownloadingState;
-					let request = sync.next_request();
+					let Some(request) = sync.next_request(*id) else { break };
 					trace!(target: LOG_TARGET, "New StateRequest for {}: {:?}", id, request);
-					self.allowed_requests.clear();
-					return Some((*id, OpaqueStateRequest(Box::new(request))))
+					peer.state = PeerSyncState::DownloadingState;
+					requests.push((*id, OpaqueStateRequest(Box::new(request))));
 				}
 			}
 		}
-		if let Some(sync) = &self.warp_sync {
+		if let Some(sync) = &mut self.warp_sync {
 			if sync.is_complete() {
-				return None
+				return requests
 			}
-			if let (Some(request), Some(target)) =
-				(sync.next_state_request(), sync.target_block_number())
-			{
+			if let Some(target) = sync.target_block_number() {
 				for (id, peer) in self.peers.iter_mut() {
 					if peer.state.is_available() && peer.best_number >= target {
+						let Some(request) = sync.next_state_request(*id) else { break };
 						trace!(target: LOG_TARGET, "New StateRequest for {id}: {request:?}");
 						peer.state = PeerSyncState::DownloadingState;
-						self.allowed_requests.clear();
-						return Some((*id, OpaqueStateRequest(Box::new(request))))
+						requests.push((*id, OpaqueStateRequest(Box::new(request))));
 					}
 				}
 			}
 		}
-		None
+		if !requests.is_empty() {
+			self.allowed_requests.clear();
+		}
+		requests
 	}
 
 	fn warp_sync_request(&mut self) -> Option<(PeerId, WarpProofRequest<B>)> {
@@ -2170,14 +2217,18 @@ where
 		who: &PeerId,
 		response: OpaqueStateResponse,
 	) -> Result<OnStateData<B>, BadPeer> {
-		let response: Box<StateResponse> = response.0.downcast().map_err(|_error| {
-			error!(
-				target: LOG_TARGET,
-				"Failed to downcast opaque state response, this is an implementation bug."
-			);
+		let response: Box<StateResponse> = match response.0.downcast() {
+			Ok(response) => response,
+			Err(_) => {
+				error!(
+					target: LOG_TARGET,
+					"Failed to downcast opaque state response, this is an implementation bug."
+				);
+				self.state_request_failed(who);
 
-			BadPeer(*who, rep::BAD_RESPONSE)
-		})?;
+				return Err(BadPeer(*who, rep::BAD_RESPONSE))
+			},
+		};
 
 		if let Some(peer) = self.peers.get_mut(who) {
 			if let PeerSyncState::DownloadingState = peer.state {
@@ -2193,7 +2244,7 @@ where
 				response.entries.len(),
 				response.proof.len(),
 			);
-			sync.import(*response)
+			sync.import(who, *response)
 		} else if let Some(sync) = &mut self.warp_sync {
 			debug!(
 				target: LOG_TARGET,
@@ -2202,7 +2253,7 @@ where
 				response.entries.len(),
 				response.proof.len(),
 			);
-			sync.import_state(*response)
+			sync.import_state(who, *response)
 		} else {
 			debug!(target: LOG_TARGET, "Ignored obsolete state response from {who}");
 			return Err(BadPeer(*who, rep::NOT_REQUESTED))
@@ -2840,6 +2891,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -2906,6 +2958,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3080,6 +3133,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3206,6 +3260,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3363,6 +3418,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3505,6 +3561,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3649,6 +3706,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3694,6 +3752,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -3746,6 +3805,7 @@ mod test {
 			Arc::new(MockBlockDownloader::new()),
 			ProtocolName::from("state-request"),
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -17,10 +17,16 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! State sync support.
+//!
+//! The state is split into [`STATE_RANGES`] ranges of top trie keys which are downloaded from
+//! several peers in parallel. The proof of each chunk is verified against the state root of the
+//! target block as it arrives. Verified chunks are appended to a journal on disk, so that an
+//! interrupted state sync resumes where it stopped instead of starting from scratch.
 
 use crate::schema::v1::{StateEntry, StateRequest, StateResponse};
 use codec::{Decode, Encode};
-use log::debug;
+use libp2p::PeerId;
+use log::{debug, warn};
 use sc_client_api::{CompactProof, ProofProvider};
 use sc_consensus::ImportedState;
 use sc_network_common::sync::StateDownloadProgress;
@@ -30,7 +36,190 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{BTreeMap, BTreeSet, HashMap},
+	fs,
+	io::{self, Write},
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// Number of key ranges the state is split into.
+///
+/// Ranges are delimited by the first byte of the top trie keys.
+pub const STATE_RANGES: usize = 16;
+
+/// Name of the state sync journal in the journal directory.
+const JOURNAL_FILE: &str = "state-sync.journal";
+
+/// Key-values of a trie and the storage keys of the top trie pointing to it.
+type TrieState = (BTreeMap<Vec<u8>, Vec<u8>>, BTreeSet<Vec<u8>>);
+
+/// A range of top trie keys downloaded independently of the others.
+#[derive(Debug, Clone)]
+struct StateRange {
+	/// Exclusive upper bound of the range, `None` for the last range.
+	end: Option<Vec<u8>>,
+	/// Position of the last downloaded key, used as the start of the next request.
+	cursor: SmallVec<[Vec<u8>; 2]>,
+	/// First byte of the keys of the range, used to estimate progress.
+	first_byte: u8,
+	/// Whether all keys of the range were downloaded.
+	complete: bool,
+}
+
+impl StateRange {
+	/// Split the top trie key space in `count` ranges.
+	fn split(count: usize) -> Vec<StateRange> {
+		let width = 256 / count;
+		(0..count)
+			.map(|i| {
+				let first_byte = (i * width) as u8;
+				// The start of a request is exclusive, so the range starts right after the
+				// greatest key which can precede its first byte in practice. Keys in between
+				// belong to the previous range, which downloads them anyway.
+				let cursor = if i == 0 {
+					SmallVec::new()
+				} else {
+					let mut start = vec![first_byte - 1];
+					start.extend([0xff; 64]);
+					SmallVec::from_elem(start, 1)
+				};
+				let end = (i + 1 < count).then(|| vec![((i + 1) * width) as u8]);
+				StateRange { end, cursor, first_byte, complete: false }
+			})
+			.collect()
+	}
+
+	/// Whether `cursor` points past the end of the range.
+	fn is_past_end(&self, cursor: &[Vec<u8>]) -> bool {
+		match (&self.end, cursor.first()) {
+			(Some(end), Some(top)) => top >= end,
+			_ => false,
+		}
+	}
+
+	/// Estimated progress of the download of the range, in percent.
+	fn progress(&self) -> u32 {
+		if self.complete {
+			return 100
+		}
+		let end = self.end.as_ref().map_or(256, |end| end[0] as u32);
+		let width = end - self.first_byte as u32;
+		match self.cursor.first().and_then(|top| top.first()) {
+			Some(byte) if *byte as u32 >= self.first_byte as u32 =>
+				((*byte as u32 - self.first_byte as u32) * 100 / width).min(100),
+			_ => 0,
+		}
+	}
+}
+
+/// A verified chunk of state, as imported and written to the journal.
+#[derive(Debug, Encode, Decode)]
+struct Chunk {
+	/// Index of the range the chunk belongs to.
+	range: u32,
+	/// Position of the range after the chunk.
+	cursor: Vec<Vec<u8>>,
+	/// Whether the range is complete after the chunk.
+	complete: bool,
+	/// Key-values per trie root, the top trie having an empty root.
+	key_values: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+	/// Child trie roots along with the storage key of the top trie pointing to them.
+	child_roots: Vec<(Vec<u8>, Vec<u8>)>,
+	/// Size of the proof of the chunk.
+	proof_size: u64,
+}
+
+/// Header of the journal, identifying the state sync it belongs to.
+#[derive(Debug, Encode, Decode)]
+struct JournalHeader<Hash> {
+	target: Hash,
+	ranges: u32,
+}
+
+/// Append-only log of the chunks imported by a state sync.
+struct Journal {
+	path: PathBuf,
+	file: fs::File,
+}
+
+impl Journal {
+	/// Open the journal in `dir` for the state sync of `target`.
+	///
+	/// Returns the chunks imported before if the journal belongs to the same state sync, or
+	/// starts a new journal otherwise.
+	fn open<Hash: Encode + Decode + PartialEq>(
+		dir: &Path,
+		target: Hash,
+		ranges: u32,
+	) -> io::Result<(Self, Vec<Chunk>)> {
+		fs::create_dir_all(dir)?;
+		let path = dir.join(JOURNAL_FILE);
+		let content = match fs::read(&path) {
+			Ok(content) => content,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => return Err(e),
+		};
+
+		let mut input = &content[..];
+		let mut chunks = Vec::new();
+		let valid_len = match JournalHeader::<Hash>::decode(&mut input) {
+			Ok(header) if header.target == target && header.ranges == ranges => {
+				let mut valid_len = content.len() - input.len();
+				while !input.is_empty() {
+					match Chunk::decode(&mut input) {
+						Ok(chunk) => {
+							chunks.push(chunk);
+							valid_len = content.len() - input.len();
+						},
+						Err(e) => {
+							// The node was most likely interrupted while writing the last chunk.
+							debug!(target: "sync", "Truncating state sync journal: {}", e);
+							break
+						},
+					}
+				}
+				valid_len
+			},
+			_ => 0,
+		};
+
+		let file = if valid_len == 0 {
+			let mut file = fs::File::create(&path)?;
+			file.write_all(&JournalHeader { target, ranges }.encode())?;
+			file
+		} else {
+			let file = fs::OpenOptions::new().append(true).open(&path)?;
+			file.set_len(valid_len as u64)?;
+			file
+		};
+
+		Ok((Journal { path, file }, chunks))
+	}
+
+	/// Append an imported chunk.
+	fn append(&mut self, chunk: &Chunk) -> io::Result<()> {
+		self.file.write_all(&chunk.encode())
+	}
+
+	/// Remove the journal once the state sync is complete.
+	fn remove(self) {
+		if let Err(e) = fs::remove_file(&self.path) {
+			debug!(target: "sync", "Failed to remove state sync journal: {}", e);
+		}
+	}
+}
+
+/// Returns the target block of the state sync recorded in the journal in `dir`, if any.
+///
+/// Allows resuming an interrupted state sync of the same block.
+pub fn journaled_target<B: BlockT>(dir: &Path) -> Option<B::Hash> {
+	let content = fs::read(dir.join(JOURNAL_FILE)).ok()?;
+	JournalHeader::<B::Hash>::decode(&mut &content[..])
+		.ok()
+		.map(|header| header.target)
+}
 
 /// State sync state machine. Accumulates partial state data until it
 /// is ready to be imported.
@@ -40,12 +229,15 @@ pub struct StateSync<B: BlockT, Client> {
 	target_root: B::Hash,
 	target_body: Option<Vec<B::Extrinsic>>,
 	target_justifications: Option<Justifications>,
-	last_key: SmallVec<[Vec<u8>; 2]>,
-	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
+	ranges: Vec<StateRange>,
+	/// Index of the range requested from each peer.
+	requests: HashMap<PeerId, usize>,
+	state: BTreeMap<Vec<u8>, TrieState>,
 	complete: bool,
 	client: Arc<Client>,
 	imported_bytes: u64,
 	skip_proof: bool,
+	journal: Option<Journal>,
 }
 
 /// Import state chunk result.
@@ -64,30 +256,66 @@ where
 	Client: ProofProvider<B> + Send + Sync + 'static,
 {
 	///  Create a new instance.
+	///
+	/// If `journal_dir` is given, the imported chunks are recorded there and the download
+	/// resumes from the recorded chunks if they belong to the same target block.
 	pub fn new(
 		client: Arc<Client>,
 		target_header: B::Header,
 		target_body: Option<Vec<B::Extrinsic>>,
 		target_justifications: Option<Justifications>,
 		skip_proof: bool,
+		journal_dir: Option<&Path>,
 	) -> Self {
-		Self {
+		let mut sync = Self {
 			client,
 			target_block: target_header.hash(),
 			target_root: *target_header.state_root(),
 			target_header,
 			target_body,
 			target_justifications,
-			last_key: SmallVec::default(),
-			state: HashMap::default(),
+			ranges: StateRange::split(STATE_RANGES),
+			requests: HashMap::new(),
+			state: BTreeMap::new(),
 			complete: false,
 			imported_bytes: 0,
 			skip_proof,
+			journal: None,
+		};
+
+		if let Some(dir) = journal_dir {
+			match Journal::open(dir, sync.target_block, STATE_RANGES as u32) {
+				Ok((journal, chunks)) => {
+					if !chunks.is_empty() {
+						debug!(
+							target: "sync",
+							"Resuming state sync of {} from {} chunks",
+							sync.target_block,
+							chunks.len(),
+						);
+					}
+					for chunk in chunks {
+						sync.apply(chunk);
+					}
+					sync.journal = Some(journal);
+				},
+				Err(e) => warn!(target: "sync", "Failed to open state sync journal: {}", e),
+			}
 		}
+
+		sync
 	}
 
-	///  Validate and import a state response.
-	pub fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+	/// Validate and import a state response from `who`.
+	pub fn import(&mut self, who: &PeerId, response: StateResponse) -> ImportResult<B> {
+		let Some(index) = self.requests.remove(who) else {
+			debug!(target: "sync", "Unexpected state response from {}", who);
+			return ImportResult::BadResponse
+		};
+		if self.ranges[index].complete {
+			debug!(target: "sync", "Ignoring state response for a complete range from {}", who);
+			return ImportResult::Continue
+		}
 		if response.entries.is_empty() && response.proof.is_empty() {
 			debug!(target: "sync", "Bad state response");
 			return ImportResult::BadResponse
@@ -96,136 +324,45 @@ where
 			debug!(target: "sync", "Missing proof");
 			return ImportResult::BadResponse
 		}
-		let complete = if !self.skip_proof {
-			debug!(target: "sync", "Importing state from {} trie nodes", response.proof.len());
-			let proof_size = response.proof.len() as u64;
-			let proof = match CompactProof::decode(&mut response.proof.as_ref()) {
-				Ok(proof) => proof,
-				Err(e) => {
-					debug!(target: "sync", "Error decoding proof: {:?}", e);
-					return ImportResult::BadResponse
-				},
-			};
-			let (values, completed) = match self.client.verify_range_proof(
-				self.target_root,
-				proof,
-				self.last_key.as_slice(),
-			) {
-				Err(e) => {
-					debug!(
-						target: "sync",
-						"StateResponse failed proof verification: {}",
-						e,
-					);
-					return ImportResult::BadResponse
-				},
-				Ok(values) => values,
-			};
-			debug!(target: "sync", "Imported with {} keys", values.len());
-
-			let complete = completed == 0;
-			if !complete && !values.update_last_key(completed, &mut self.last_key) {
-				debug!(target: "sync", "Error updating key cursor, depth: {}", completed);
-			};
 
-			for values in values.0 {
-				let key_values = if values.state_root.is_empty() {
-					// Read child trie roots.
-					values
-						.key_values
-						.into_iter()
-						.filter(|key_value| {
-							if well_known_keys::is_child_storage_key(key_value.0.as_slice()) {
-								self.state
-									.entry(key_value.1.clone())
-									.or_default()
-									.1
-									.push(key_value.0.clone());
-								false
-							} else {
-								true
-							}
-						})
-						.collect()
-				} else {
-					values.key_values
-				};
-				let entry = self.state.entry(values.state_root).or_default();
-				if entry.0.len() > 0 && entry.1.len() > 1 {
-					// Already imported child_trie with same root.
-					// Warning this will not work with parallel download.
-				} else if entry.0.is_empty() {
-					for (key, _value) in key_values.iter() {
-						self.imported_bytes += key.len() as u64;
-					}
-
-					entry.0 = key_values;
-				} else {
-					for (key, value) in key_values {
-						self.imported_bytes += key.len() as u64;
-						entry.0.push((key, value))
-					}
-				}
+		let chunk = if !self.skip_proof {
+			match self.verify_chunk(index, response) {
+				Some(chunk) => chunk,
+				None => return ImportResult::BadResponse,
 			}
-			self.imported_bytes += proof_size;
-			complete
 		} else {
-			let mut complete = true;
-			// if the trie is a child trie and one of its parent trie is empty,
-			// the parent cursor stays valid.
-			// Empty parent trie content only happens when all the response content
-			// is part of a single child trie.
-			if self.last_key.len() == 2 && response.entries[0].entries.is_empty() {
-				// Do not remove the parent trie position.
-				self.last_key.pop();
-			} else {
-				self.last_key.clear();
-			}
-			for state in response.entries {
-				debug!(
-					target: "sync",
-					"Importing state from {:?} to {:?}",
-					state.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-					state.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-				);
-
-				if !state.complete {
-					if let Some(e) = state.entries.last() {
-						self.last_key.push(e.key.clone());
-					}
-					complete = false;
-				}
-				let is_top = state.state_root.is_empty();
-				let entry = self.state.entry(state.state_root).or_default();
-				if entry.0.len() > 0 && entry.1.len() > 1 {
-					// Already imported child trie with same root.
-				} else {
-					let mut child_roots = Vec::new();
-					for StateEntry { key, value } in state.entries {
-						// Skip all child key root (will be recalculated on import).
-						if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
-							child_roots.push((value, key));
-						} else {
-							self.imported_bytes += key.len() as u64;
-							entry.0.push((key, value))
-						}
-					}
-					for (root, storage_key) in child_roots {
-						self.state.entry(root).or_default().1.push(storage_key);
-					}
-				}
-			}
-			complete
+			self.unverified_chunk(index, response)
 		};
-		if complete {
+
+		if let Some(journal) = &mut self.journal {
+			if let Err(e) = journal.append(&chunk) {
+				warn!(target: "sync", "Failed to write state sync journal: {}", e);
+				self.journal = None;
+			}
+		}
+		self.apply(chunk);
+
+		if self.ranges.iter().all(|range| range.complete) {
 			self.complete = true;
+			self.requests.clear();
+			if let Some(journal) = self.journal.take() {
+				journal.remove();
+			}
+			let state = std::mem::take(&mut self.state).into_iter().map(
+				|(root, (key_values, storage_keys))| {
+					(
+						root,
+						(
+							key_values.into_iter().collect::<Vec<_>>(),
+							storage_keys.into_iter().collect::<Vec<_>>(),
+						),
+					)
+				},
+			);
 			ImportResult::Import(
 				self.target_block,
 				self.target_header.clone(),
-				ImportedState {
-					block: self.target_block,
-					state: std::mem::take(&mut self.state).into(),
-				},
+				ImportedState { block: self.target_block, state: state.into() },
 				self.target_body.clone(),
 				self.target_justifications.clone(),
 			)
@@ -234,13 +371,183 @@ where
 		}
 	}
 
-	/// Produce next state request.
-	pub fn next_request(&self) -> StateRequest {
-		StateRequest {
+	/// Verify the proof of a response for the range at `index` against the target state root.
+	fn verify_chunk(&self, index: usize, response: StateResponse) -> Option<Chunk> {
+		let range = &self.ranges[index];
+		debug!(target: "sync", "Importing state from {} trie nodes", response.proof.len());
+		let proof_size = response.proof.len() as u64;
+		let proof = match CompactProof::decode(&mut response.proof.as_ref()) {
+			Ok(proof) => proof,
+			Err(e) => {
+				debug!(target: "sync", "Error decoding proof: {:?}", e);
+				return None
+			},
+		};
+		let (values, completed) = match self.client.verify_range_proof(
+			self.target_root,
+			proof,
+			range.cursor.as_slice(),
+		) {
+			Err(e) => {
+				debug!(target: "sync", "StateResponse failed proof verification: {}", e);
+				return None
+			},
+			Ok(values) => values,
+		};
+		debug!(target: "sync", "Imported with {} keys", values.len());
+
+		let mut cursor = range.cursor.clone();
+		if completed != 0 && !values.update_last_key(completed, &mut cursor) {
+			debug!(target: "sync", "Error updating key cursor, depth: {}", completed);
+		};
+		let complete = completed == 0 || range.is_past_end(&cursor);
+
+		let mut key_values = Vec::new();
+		let mut child_roots = Vec::new();
+		for values in values.0 {
+			let level_key_values = if values.state_root.is_empty() {
+				// Read child trie roots.
+				values
+					.key_values
+					.into_iter()
+					.filter(|(key, value)| {
+						if well_known_keys::is_child_storage_key(key.as_slice()) {
+							child_roots.push((value.clone(), key.clone()));
+							false
+						} else {
+							true
+						}
+					})
+					.collect()
+			} else {
+				values.key_values
+			};
+			key_values.push((values.state_root, level_key_values));
+		}
+
+		Some(Chunk {
+			range: index as u32,
+			cursor: if complete { Vec::new() } else { cursor.into_vec() },
+			complete,
+			key_values,
+			child_roots,
+			proof_size,
+		})
+	}
+
+	/// Turn a response without proof for the range at `index` into a chunk.
+	fn unverified_chunk(&self, index: usize, response: StateResponse) -> Chunk {
+		let range = &self.ranges[index];
+		let mut cursor = range.cursor.clone();
+		let mut complete = true;
+		// if the trie is a child trie and one of its parent trie is empty,
+		// the parent cursor stays valid.
+		// Empty parent trie content only happens when all the response content
+		// is part of a single child trie.
+		if cursor.len() == 2 &&
+			response.entries.first().map_or(false, |state| state.entries.is_empty())
+		{
+			// Do not remove the parent trie position.
+			cursor.pop();
+		} else {
+			cursor.clear();
+		}
+
+		let mut key_values = Vec::new();
+		let mut child_roots = Vec::new();
+		for state in response.entries {
+			debug!(
+				target: "sync",
+				"Importing state from {:?} to {:?}",
+				state.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+				state.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+			);
+
+			if !state.complete {
+				if let Some(e) = state.entries.last() {
+					cursor.push(e.key.clone());
+				}
+				complete = false;
+			}
+			let is_top = state.state_root.is_empty();
+			let mut level_key_values = Vec::new();
+			for StateEntry { key, value } in state.entries {
+				// Skip all child key root (will be recalculated on import).
+				if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
+					child_roots.push((value, key));
+				} else {
+					level_key_values.push((key, value));
+				}
+			}
+			key_values.push((state.state_root, level_key_values));
+		}
+		let complete = complete || range.is_past_end(&cursor);
+
+		Chunk {
+			range: index as u32,
+			cursor: if complete { Vec::new() } else { cursor.into_vec() },
+			complete,
+			key_values,
+			child_roots,
+			proof_size: 0,
+		}
+	}
+
+	/// Add the content of a chunk to the state and advance its range.
+	///
+	/// Chunks of neighbouring ranges may overlap, so keys already known are skipped.
+	fn apply(&mut self, chunk: Chunk) {
+		let Some(range) = self.ranges.get_mut(chunk.range as usize) else {
+			debug!(target: "sync", "Ignoring state chunk of unknown range {}", chunk.range);
+			return
+		};
+		range.cursor = chunk.cursor.into();
+		range.complete = chunk.complete;
+
+		for (root, key_values) in chunk.key_values {
+			let entry = self.state.entry(root).or_default();
+			for (key, value) in key_values {
+				let key_len = key.len() as u64;
+				if entry.0.insert(key, value).is_none() {
+					self.imported_bytes += key_len;
+				}
+			}
+		}
+		for (root, storage_key) in chunk.child_roots {
+			self.state.entry(root).or_default().1.insert(storage_key);
+		}
+		self.imported_bytes += chunk.proof_size;
+	}
+
+	/// Produce the next state request for `who`.
+	///
+	/// Returns `None` if a request to `who` is already pending or if all the ranges which are
+	/// not complete are being downloaded from other peers.
+	pub fn next_request(&mut self, who: PeerId) -> Option<StateRequest> {
+		if self.complete || self.requests.contains_key(&who) {
+			return None
+		}
+		let index = (0..self.ranges.len()).find(|index| {
+			!self.ranges[*index].complete && !self.requests.values().any(|i| i == index)
+		})?;
+		self.requests.insert(who, index);
+
+		Some(StateRequest {
 			block: self.target_block.encode(),
-			start: self.last_key.clone().into_vec(),
+			start: self.ranges[index].cursor.clone().into_vec(),
 			no_proof: self.skip_proof,
-		}
+		})
+	}
+
+	/// Release the range requested from a peer which disconnected.
+	pub fn peer_disconnected(&mut self, who: &PeerId) {
+		self.requests.remove(who);
+	}
+
+	/// Release the range requested from `who` after the request failed without a response that
+	/// could be imported, so that it can be requested from another peer.
+	pub fn request_failed(&mut self, who: &PeerId) {
+		self.requests.remove(who);
 	}
 
 	/// Check if the state is complete.
@@ -260,8 +567,197 @@ where
 
 	/// Returns state sync estimated progress.
 	pub fn progress(&self) -> StateDownloadProgress {
-		let cursor = *self.last_key.get(0).and_then(|last| last.get(0)).unwrap_or(&0u8);
-		let percent_done = cursor as u32 * 100 / 256;
+		let percent_done =
+			self.ranges.iter().map(StateRange::progress).sum::<u32>() / self.ranges.len() as u32;
 		StateDownloadProgress { percentage: percent_done, size: self.imported_bytes }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_client_api::HeaderBackend;
+	use sp_core::storage::ChildInfo;
+	use substrate_test_runtime_client::{
+		runtime::Block, DefaultTestClientBuilderExt, TestClient, TestClientBuilder,
+		TestClientBuilderExt,
+	};
+
+	const RESPONSE_SIZE: usize = 4 * 1024;
+
+	fn client() -> Arc<TestClient> {
+		let child_info = ChildInfo::new_default(b"child");
+		let builder = (0..64u8).fold(TestClientBuilder::new(), |builder, i| {
+			builder.add_extra_storage(vec![i * 4, i], vec![i; 64]).add_extra_child_storage(
+				&child_info,
+				vec![i],
+				vec![i; 64],
+			)
+		});
+		Arc::new(builder.build())
+	}
+
+	fn respond(client: &TestClient, request: &StateRequest) -> StateResponse {
+		let hash = Decode::decode(&mut request.block.as_ref()).unwrap();
+		let (proof, _) = client.read_proof_collection(hash, &request.start, RESPONSE_SIZE).unwrap();
+		StateResponse { entries: Vec::new(), proof: proof.encode() }
+	}
+
+	fn expected_state(client: &TestClient) -> usize {
+		let hash = client.info().genesis_hash;
+		let mut start: SmallVec<[Vec<u8>; 2]> = SmallVec::new();
+		let mut keys = 0;
+		loop {
+			let (proof, _) = client.read_proof_collection(hash, &start, usize::MAX).unwrap();
+			let header = client.header(hash).unwrap().unwrap();
+			let (values, completed) =
+				client.verify_range_proof(*header.state_root(), proof, &start).unwrap();
+			// Storage keys of child tries are not imported, the child roots are recomputed.
+			keys += values
+				.0
+				.iter()
+				.flat_map(|level| {
+					level.key_values.iter().filter(|(key, _)| {
+						!level.state_root.is_empty() ||
+							!well_known_keys::is_child_storage_key(key.as_slice())
+					})
+				})
+				.count();
+			if completed == 0 {
+				return keys
+			}
+			values.update_last_key(completed, &mut start);
+		}
+	}
+
+	fn imported_keys(result: ImportResult<Block>) -> usize {
+		match result {
+			ImportResult::Import(_, _, state, _, _) => state.state.len(),
+			_ => panic!("State sync is not complete"),
+		}
+	}
+
+	#[test]
+	fn state_is_downloaded_from_several_peers_in_parallel() {
+		let client = client();
+		let header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let mut sync = StateSync::<Block, _>::new(client.clone(), header, None, None, false, None);
+		let peers = [PeerId::random(), PeerId::random(), PeerId::random()];
+
+		loop {
+			let requests = peers
+				.iter()
+				.filter_map(|peer| sync.next_request(*peer).map(|request| (*peer, request)))
+				.collect::<Vec<_>>();
+			assert!(!requests.is_empty());
+			// Each peer downloads a different range.
+			let starts = requests.iter().map(|(_, request)| &request.start).collect::<Vec<_>>();
+			assert!(starts.iter().enumerate().all(|(i, start)| !starts[..i].contains(start)));
+
+			for (peer, request) in requests {
+				match sync.import(&peer, respond(&client, &request)) {
+					ImportResult::Continue => {},
+					result => {
+						assert_eq!(imported_keys(result), expected_state(&client));
+						return
+					},
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn bad_proof_is_rejected() {
+		let client = client();
+		let header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let mut sync = StateSync::<Block, _>::new(client.clone(), header, None, None, false, None);
+		let peer = PeerId::random();
+
+		let mut request = sync.next_request(peer).unwrap();
+		// Proof of another range than the requested one.
+		request.start = vec![vec![0x80]];
+		assert!(matches!(
+			sync.import(&peer, respond(&client, &request)),
+			ImportResult::BadResponse
+		));
+		// Responses from peers which were not asked anything are rejected as well.
+		assert!(matches!(
+			sync.import(&PeerId::random(), respond(&client, &request)),
+			ImportResult::BadResponse
+		));
+	}
+
+	#[test]
+	fn failed_request_releases_its_range() {
+		let client = client();
+		let header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let mut sync = StateSync::<Block, _>::new(client.clone(), header, None, None, false, None);
+		let (failing, other) = (PeerId::random(), PeerId::random());
+
+		let request = sync.next_request(failing).unwrap();
+		// E.g. the response could not be decoded, the peer is still connected.
+		sync.request_failed(&failing);
+
+		// The range is requested from another peer, which can complete it.
+		let retried = sync.next_request(other).unwrap();
+		assert_eq!(retried, request);
+		assert!(matches!(
+			sync.import(&other, respond(&client, &retried)),
+			ImportResult::Continue
+		));
+		// The failing peer is not waiting for a response anymore.
+		assert!(matches!(
+			sync.import(&failing, respond(&client, &request)),
+			ImportResult::BadResponse
+		));
+	}
+
+	#[test]
+	fn interrupted_state_sync_resumes_from_journal() {
+		let dir = tempfile::tempdir().unwrap();
+		let client = client();
+		let header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let peer = PeerId::random();
+
+		let mut sync = StateSync::<Block, _>::new(
+			client.clone(),
+			header.clone(),
+			None,
+			None,
+			false,
+			Some(dir.path()),
+		);
+		for _ in 0..3 {
+			let request = sync.next_request(peer).unwrap();
+			assert!(matches!(
+				sync.import(&peer, respond(&client, &request)),
+				ImportResult::Continue
+			));
+		}
+		let progress = sync.progress();
+		let next_request = sync.next_request(peer).unwrap();
+		drop(sync);
+
+		assert_eq!(journaled_target::<Block>(dir.path()), Some(header.hash()));
+		let mut sync = StateSync::<Block, _>::new(
+			client.clone(),
+			header.clone(),
+			None,
+			None,
+			false,
+			Some(dir.path()),
+		);
+		assert_eq!(sync.progress(), progress);
+		assert_eq!(sync.next_request(peer).unwrap(), next_request);
+
+		let mut request = next_request;
+		let result = loop {
+			match sync.import(&peer, respond(&client, &request)) {
+				ImportResult::Continue => request = sync.next_request(peer).unwrap(),
+				result => break result,
+			}
+		};
+		assert_eq!(imported_keys(result), expected_state(&client));
+		assert_eq!(journaled_target::<Block>(dir.path()), None);
+	}
+}
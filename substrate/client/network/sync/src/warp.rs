@@ -23,6 +23,7 @@ use crate::{
 	state::{ImportResult, StateSync},
 };
 use futures::channel::oneshot;
+use libp2p::PeerId;
 use log::error;
 use sc_client_api::ProofProvider;
 use sc_network_common::sync::{
@@ -35,7 +36,7 @@ use sc_network_common::sync::{
 use sp_blockchain::HeaderBackend;
 use sp_consensus_grandpa::{AuthorityList, SetId};
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor, Zero};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 /// Log target for this file.
 const LOG_TARGET: &'static str = "sync";
@@ -112,6 +113,8 @@ pub struct WarpSync<B: BlockT, Client> {
 	phase: Phase<B, Client>,
 	client: Arc<Client>,
 	total_proof_bytes: u64,
+	/// Directory of the journal of the state download.
+	state_sync_journal_dir: Option<PathBuf>,
 }
 
 impl<B, Client> WarpSync<B, Client>
@@ -122,7 +125,11 @@ where
 	/// Create a new instance. When passing a warp sync provider we will be checking for proof and
 	/// authorities. Alternatively we can pass a target block when we want to skip downloading
 	/// proofs, in this case we will continue polling until the target block is known.
-	pub fn new(client: Arc<Client>, warp_sync_config: WarpSyncConfig<B>) -> Self {
+	pub fn new(
+		client: Arc<Client>,
+		warp_sync_config: WarpSyncConfig<B>,
+		state_sync_journal_dir: Option<PathBuf>,
+	) -> Self {
 		let last_hash = client.hash(Zero::zero()).unwrap().expect("Genesis header always exists");
 		match warp_sync_config {
 			WarpSyncConfig::WithProvider(warp_sync_provider) => {
@@ -132,10 +139,14 @@ where
 					last_hash,
 					warp_sync_provider: warp_sync_provider.clone(),
 				};
-				Self { client, phase, total_proof_bytes: 0 }
+				Self { client, phase, total_proof_bytes: 0, state_sync_journal_dir }
+			},
+			WarpSyncConfig::WaitForTarget => Self {
+				client,
+				phase: Phase::PendingTargetBlock,
+				total_proof_bytes: 0,
+				state_sync_journal_dir,
 			},
-			WarpSyncConfig::WaitForTarget =>
-				Self { client, phase: Phase::PendingTargetBlock, total_proof_bytes: 0 },
 		}
 	}
 
@@ -153,14 +164,14 @@ where
 		self.phase = Phase::TargetBlock(header);
 	}
 
-	///  Validate and import a state response.
-	pub fn import_state(&mut self, response: StateResponse) -> ImportResult<B> {
+	///  Validate and import a state response from `who`.
+	pub fn import_state(&mut self, who: &PeerId, response: StateResponse) -> ImportResult<B> {
 		match &mut self.phase {
 			Phase::WarpProof { .. } | Phase::TargetBlock(_) | Phase::PendingTargetBlock { .. } => {
 				log::debug!(target: "sync", "Unexpected state response");
 				ImportResult::BadResponse
 			},
-			Phase::State(sync) => sync.import(response),
+			Phase::State(sync) => sync.import(who, response),
 		}
 	}
 
@@ -212,6 +223,7 @@ where
 								block.body,
 								block.justifications,
 								false,
+								self.state_sync_journal_dir.as_deref(),
 							);
 							self.phase = Phase::State(state_sync);
 							TargetBlockImportResult::Success
@@ -236,12 +248,26 @@ where
 		}
 	}
 
-	/// Produce next state request.
-	pub fn next_state_request(&self) -> Option<StateRequest> {
-		match &self.phase {
+	/// Produce next state request for `who`.
+	pub fn next_state_request(&mut self, who: PeerId) -> Option<StateRequest> {
+		match &mut self.phase {
 			Phase::WarpProof { .. } | Phase::TargetBlock(_) | Phase::PendingTargetBlock { .. } =>
 				None,
-			Phase::State(sync) => Some(sync.next_request()),
+			Phase::State(sync) => sync.next_request(who),
+		}
+	}
+
+	/// Release the state range requested from a peer which disconnected.
+	pub fn peer_disconnected(&mut self, who: &PeerId) {
+		if let Phase::State(sync) = &mut self.phase {
+			sync.peer_disconnected(who);
+		}
+	}
+
+	/// Release the state range requested from a peer whose request failed.
+	pub fn request_failed(&mut self, who: &PeerId) {
+		if let Phase::State(sync) = &mut self.phase {
+			sync.request_failed(who);
 		}
 	}
 
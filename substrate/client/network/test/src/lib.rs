@@ -26,6 +26,8 @@ mod service;
 #[cfg(test)]
 mod sync;
 
+pub mod mock_backend;
+
 use std::{
 	collections::HashMap,
 	pin::Pin,
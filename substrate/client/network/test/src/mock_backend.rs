@@ -0,0 +1,578 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! In-process networking backend for deterministic tests.
+//!
+//! All the [`MockNetworkBackend`]s of a process form a single network in which nodes are found
+//! by their [`PeerId`], without any transport or discovery. Notification substreams are opened
+//! with the reserved nodes of each protocol once the backend runs, or through the
+//! [`NetworkPeers`] methods of the handle, and requests are forwarded to the inbound queue of
+//! the remote node.
+//!
+//! The block announces protocol isn't handled by the backend, so the syncing engine never sees
+//! any peer.
+
+use futures::{channel::oneshot, prelude::*};
+use libp2p::{identity::SigningError, Multiaddr, PeerId};
+use parking_lot::Mutex;
+use sc_network::{
+	config::{
+		parse_addr, MultiaddrWithPeerId, NonDefaultSetConfig, NotificationHandshake, Params, Role,
+		SetConfig,
+	},
+	error::Error,
+	event::{DhtEvent, Event, SyncEvent},
+	network_state::NetworkState,
+	peer_store::PeerStore,
+	request_responses::{IncomingRequest, ProtocolConfig as RequestResponseConfig},
+	types::ProtocolName,
+	IfDisconnected, KademliaKey, Keypair, NetworkBackend, NetworkDHTProvider, NetworkEventStream,
+	NetworkNotification, NetworkPeers, NetworkRequest, NetworkSigner, NetworkStateInfo,
+	NetworkStatus, NetworkStatusProvider, NotificationSenderError, NotificationSenderReady,
+	NotificationSenderT, ObservedRole, OutboundFailure, ReputationChange, RequestFailure,
+	Signature,
+};
+use sc_network_common::ExHashT;
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedSender};
+use sp_runtime::traits::Block as BlockT;
+
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	marker::PhantomData,
+	pin::Pin,
+	sync::{Arc, Weak},
+	time::Duration,
+};
+
+/// Nodes of the in-process network.
+static NODES: Mutex<BTreeMap<PeerId, Weak<Node>>> = parking_lot::const_mutex(BTreeMap::new());
+
+/// A node of the in-process network.
+struct Node {
+	peer_id: PeerId,
+	keypair: Keypair,
+	role: ObservedRole,
+	listen_addresses: Vec<Multiaddr>,
+	/// Registered notification protocols and their reserved nodes.
+	notification_protocols: HashMap<ProtocolName, Vec<PeerId>>,
+	/// Registered request-response protocols and their inbound queue.
+	request_response_protocols:
+		HashMap<ProtocolName, Option<async_channel::Sender<IncomingRequest>>>,
+	executor: Mutex<Box<dyn Fn(Pin<Box<dyn Future<Output = ()> + Send>>) + Send>>,
+	state: Mutex<NodeState>,
+}
+
+#[derive(Default)]
+struct NodeState {
+	event_streams: Vec<TracingUnboundedSender<Event>>,
+	handshakes: HashMap<ProtocolName, Vec<u8>>,
+	/// Open notification substreams.
+	substreams: HashSet<(PeerId, ProtocolName)>,
+}
+
+impl Node {
+	fn find(peer_id: &PeerId) -> Option<Arc<Node>> {
+		NODES.lock().get(peer_id).and_then(Weak::upgrade)
+	}
+
+	fn emit(&self, event: Event) {
+		self.state
+			.lock()
+			.event_streams
+			.retain(|stream| stream.unbounded_send(event.clone()).is_ok());
+	}
+
+	fn handshake(&self, protocol: &ProtocolName) -> Vec<u8> {
+		self.state.lock().handshakes.get(protocol).cloned().unwrap_or_default()
+	}
+
+	fn is_open(&self, remote: &PeerId, protocol: &ProtocolName) -> bool {
+		self.state.lock().substreams.contains(&(*remote, protocol.clone()))
+	}
+
+	/// Open the substreams of `protocols` with `remote`, on both sides.
+	fn connect(&self, remote: PeerId, protocols: impl IntoIterator<Item = ProtocolName>) {
+		let Some(remote) = Node::find(&remote).filter(|remote| remote.peer_id != self.peer_id)
+		else {
+			return
+		};
+
+		for protocol in protocols {
+			if !self.notification_protocols.contains_key(&protocol) ||
+				!remote.notification_protocols.contains_key(&protocol) ||
+				!self.state.lock().substreams.insert((remote.peer_id, protocol.clone()))
+			{
+				continue
+			}
+			remote.state.lock().substreams.insert((self.peer_id, protocol.clone()));
+
+			self.emit(Event::NotificationStreamOpened {
+				remote: remote.peer_id,
+				protocol: protocol.clone(),
+				negotiated_fallback: None,
+				role: remote.role,
+				received_handshake: remote.handshake(&protocol),
+			});
+			remote.emit(Event::NotificationStreamOpened {
+				remote: self.peer_id,
+				protocol: protocol.clone(),
+				negotiated_fallback: None,
+				role: self.role,
+				received_handshake: self.handshake(&protocol),
+			});
+		}
+	}
+
+	/// Close the substreams of `protocols` with `remote`, on both sides.
+	fn disconnect(&self, remote: PeerId, protocols: impl IntoIterator<Item = ProtocolName>) {
+		for protocol in protocols {
+			if !self.state.lock().substreams.remove(&(remote, protocol.clone())) {
+				continue
+			}
+			self.emit(Event::NotificationStreamClosed { remote, protocol: protocol.clone() });
+
+			if let Some(remote) = Node::find(&remote) {
+				remote.state.lock().substreams.remove(&(self.peer_id, protocol.clone()));
+				remote.emit(Event::NotificationStreamClosed { remote: self.peer_id, protocol });
+			}
+		}
+	}
+
+	/// Deliver a notification to `target`, if a substream is open with it.
+	fn notify(&self, target: PeerId, protocol: ProtocolName, notification: Vec<u8>) -> bool {
+		if !self.is_open(&target, &protocol) {
+			return false
+		}
+		match Node::find(&target) {
+			Some(target) => {
+				target.emit(Event::NotificationsReceived {
+					remote: self.peer_id,
+					messages: vec![(protocol, notification.into())],
+				});
+				true
+			},
+			None => false,
+		}
+	}
+
+	async fn request(
+		&self,
+		target: PeerId,
+		protocol: ProtocolName,
+		payload: Vec<u8>,
+		connect: IfDisconnected,
+	) -> Result<Vec<u8>, RequestFailure> {
+		if !self.request_response_protocols.contains_key(&protocol) {
+			return Err(RequestFailure::UnknownProtocol)
+		}
+		let connected = self.state.lock().substreams.iter().any(|(peer, _)| *peer == target);
+		if !connected && !connect.should_connect() {
+			return Err(RequestFailure::NotConnected)
+		}
+		let Some(remote) = Node::find(&target) else {
+			return Err(RequestFailure::Network(OutboundFailure::DialFailure))
+		};
+		let Some(Some(inbound_queue)) = remote.request_response_protocols.get(&protocol) else {
+			return Err(RequestFailure::Network(OutboundFailure::UnsupportedProtocols))
+		};
+
+		let (pending_response, response) = oneshot::channel();
+		inbound_queue
+			.try_send(IncomingRequest { peer: self.peer_id, payload, pending_response })
+			.map_err(|_| RequestFailure::Refused)?;
+		match response.await {
+			Ok(response) => response.result.map_err(|()| RequestFailure::Refused),
+			Err(_) => Err(RequestFailure::Refused),
+		}
+	}
+
+	fn reserved_peers(peers: HashSet<Multiaddr>) -> Result<Vec<PeerId>, String> {
+		peers
+			.into_iter()
+			.map(|addr| parse_addr(addr).map(|(peer_id, _)| peer_id).map_err(|e| e.to_string()))
+			.collect()
+	}
+}
+
+/// Handle to a [`MockNetworkBackend`].
+#[derive(Clone)]
+pub struct MockNetworkService {
+	node: Arc<Node>,
+}
+
+/// In-process networking backend, see the [module documentation](self).
+pub struct MockNetworkBackend<B: BlockT + 'static, H: ExHashT> {
+	node: Arc<Node>,
+	/// Kept alive so that the syncing engine doesn't see the backend as gone.
+	_sync_events: TracingUnboundedSender<SyncEvent<B>>,
+	_marker: PhantomData<H>,
+}
+
+#[async_trait::async_trait]
+impl<B, H> NetworkBackend<B, H> for MockNetworkBackend<B, H>
+where
+	B: BlockT + 'static,
+	H: ExHashT,
+{
+	type NetworkService = MockNetworkService;
+
+	fn new(params: Params<B>) -> Result<Self, Error> {
+		let network_config = &params.network_config.network_config;
+		let keypair = network_config.node_key.clone().into_keypair()?;
+		let peer_id = keypair.public().to_peer_id();
+
+		let notification_protocols = params
+			.network_config
+			.notification_protocols()
+			.iter()
+			.map(|config| {
+				let reserved_nodes =
+					config.set_config.reserved_nodes.iter().map(|node| node.peer_id).collect();
+				(config.notifications_protocol.clone(), reserved_nodes)
+			})
+			.collect();
+		let handshakes = params
+			.network_config
+			.notification_protocols()
+			.iter()
+			.filter_map(|config| {
+				config
+					.handshake
+					.as_ref()
+					.map(|handshake| (config.notifications_protocol.clone(), handshake.to_vec()))
+			})
+			.collect();
+		let request_response_protocols = params
+			.network_config
+			.request_response_protocols()
+			.iter()
+			.map(|config| (config.name.clone(), config.inbound_queue.clone()))
+			.collect();
+
+		let node = Arc::new(Node {
+			peer_id,
+			keypair,
+			role: match params.role {
+				Role::Full => ObservedRole::Full,
+				Role::Authority => ObservedRole::Authority,
+			},
+			listen_addresses: network_config.listen_addresses.clone(),
+			notification_protocols,
+			request_response_protocols,
+			executor: Mutex::new(params.executor),
+			state: Mutex::new(NodeState { handshakes, ..Default::default() }),
+		});
+		NODES.lock().insert(peer_id, Arc::downgrade(&node));
+
+		Ok(Self { node, _sync_events: params.tx, _marker: PhantomData })
+	}
+
+	fn peer_store(bootnodes: Vec<PeerId>) -> PeerStore {
+		PeerStore::new(bootnodes)
+	}
+
+	fn notification_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_notification_size: u64,
+		handshake: Option<NotificationHandshake>,
+		set_config: SetConfig,
+	) -> NonDefaultSetConfig {
+		NonDefaultSetConfig {
+			notifications_protocol: protocol_name,
+			fallback_names,
+			max_notification_size,
+			handshake,
+			set_config,
+		}
+	}
+
+	fn request_response_config(
+		protocol_name: ProtocolName,
+		fallback_names: Vec<ProtocolName>,
+		max_request_size: u64,
+		max_response_size: u64,
+		request_timeout: Duration,
+		inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
+	) -> RequestResponseConfig {
+		RequestResponseConfig {
+			name: protocol_name,
+			fallback_names,
+			max_request_size,
+			max_response_size,
+			request_timeout,
+			inbound_queue,
+		}
+	}
+
+	fn network_service(&self) -> Self::NetworkService {
+		MockNetworkService { node: self.node.clone() }
+	}
+
+	fn add_known_address(&mut self, _peer_id: PeerId, _addr: Multiaddr) {
+		// Nodes are found by their `PeerId`.
+	}
+
+	async fn run(self) {
+		for (protocol, reserved_nodes) in &self.node.notification_protocols {
+			for peer in reserved_nodes {
+				self.node.connect(*peer, [protocol.clone()]);
+			}
+		}
+
+		// The backend is shut down by dropping it.
+		future::pending::<()>().await
+	}
+}
+
+impl<B: BlockT + 'static, H: ExHashT> Drop for MockNetworkBackend<B, H> {
+	fn drop(&mut self) {
+		let remotes = self.node.state.lock().substreams.iter().cloned().collect::<Vec<_>>();
+		for (remote, protocol) in remotes {
+			self.node.disconnect(remote, [protocol]);
+		}
+		NODES.lock().remove(&self.node.peer_id);
+	}
+}
+
+impl NetworkSigner for MockNetworkService {
+	fn sign_with_local_identity(&self, msg: impl AsRef<[u8]>) -> Result<Signature, SigningError> {
+		Signature::sign_message(msg, &self.node.keypair)
+	}
+}
+
+impl NetworkDHTProvider for MockNetworkService {
+	fn get_value(&self, key: &KademliaKey) {
+		self.node.emit(Event::Dht(DhtEvent::ValueNotFound(key.clone())));
+	}
+
+	fn put_value(&self, key: KademliaKey, _value: Vec<u8>) {
+		self.node.emit(Event::Dht(DhtEvent::ValuePutFailed(key)));
+	}
+}
+
+#[async_trait::async_trait]
+impl NetworkStatusProvider for MockNetworkService {
+	async fn status(&self) -> Result<NetworkStatus, ()> {
+		let mut peers = self
+			.node
+			.state
+			.lock()
+			.substreams
+			.iter()
+			.map(|(peer, _)| *peer)
+			.collect::<Vec<_>>();
+		peers.sort();
+		peers.dedup();
+		Ok(NetworkStatus {
+			num_connected_peers: peers.len(),
+			total_bytes_inbound: 0,
+			total_bytes_outbound: 0,
+		})
+	}
+
+	async fn network_state(&self) -> Result<NetworkState, ()> {
+		Err(())
+	}
+
+	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()> {
+		let mut peers =
+			self.node.notification_protocols.values().flatten().cloned().collect::<Vec<_>>();
+		peers.sort();
+		peers.dedup();
+		Ok(peers)
+	}
+}
+
+impl NetworkPeers for MockNetworkService {
+	fn set_authorized_peers(&self, _peers: HashSet<PeerId>) {}
+
+	fn set_authorized_only(&self, _reserved_only: bool) {}
+
+	fn add_known_address(&self, _peer_id: PeerId, _addr: Multiaddr) {}
+
+	fn report_peer(&self, _who: PeerId, _cost_benefit: ReputationChange) {}
+
+	fn disconnect_peer(&self, who: PeerId, protocol: ProtocolName) {
+		self.node.disconnect(who, [protocol]);
+	}
+
+	fn accept_unreserved_peers(&self) {}
+
+	fn deny_unreserved_peers(&self) {}
+
+	fn add_reserved_peer(&self, peer: MultiaddrWithPeerId) -> Result<(), String> {
+		self.node
+			.connect(peer.peer_id, self.node.notification_protocols.keys().cloned());
+		Ok(())
+	}
+
+	fn remove_reserved_peer(&self, peer_id: PeerId) {
+		self.node.disconnect(peer_id, self.node.notification_protocols.keys().cloned());
+	}
+
+	fn set_reserved_peers(
+		&self,
+		protocol: ProtocolName,
+		peers: HashSet<Multiaddr>,
+	) -> Result<(), String> {
+		self.add_peers_to_reserved_set(protocol, peers)
+	}
+
+	fn add_peers_to_reserved_set(
+		&self,
+		protocol: ProtocolName,
+		peers: HashSet<Multiaddr>,
+	) -> Result<(), String> {
+		if !self.node.notification_protocols.contains_key(&protocol) {
+			return Err(format!("Unknown protocol: {protocol}"))
+		}
+		for peer in Node::reserved_peers(peers)? {
+			self.node.connect(peer, [protocol.clone()]);
+		}
+		Ok(())
+	}
+
+	fn remove_peers_from_reserved_set(
+		&self,
+		protocol: ProtocolName,
+		peers: Vec<PeerId>,
+	) -> Result<(), String> {
+		if !self.node.notification_protocols.contains_key(&protocol) {
+			return Err(format!("Unknown protocol: {protocol}"))
+		}
+		for peer in peers {
+			self.node.disconnect(peer, [protocol.clone()]);
+		}
+		Ok(())
+	}
+
+	fn sync_num_connected(&self) -> usize {
+		0
+	}
+}
+
+impl NetworkEventStream for MockNetworkService {
+	fn event_stream(&self, name: &'static str) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+		let (tx, rx) = tracing_unbounded(name, 100_000);
+		self.node.state.lock().event_streams.push(tx);
+		Box::pin(rx)
+	}
+}
+
+impl NetworkNotification for MockNetworkService {
+	fn write_notification(&self, target: PeerId, protocol: ProtocolName, message: Vec<u8>) {
+		self.node.notify(target, protocol, message);
+	}
+
+	fn notification_sender(
+		&self,
+		target: PeerId,
+		protocol: ProtocolName,
+	) -> Result<Box<dyn NotificationSenderT>, NotificationSenderError> {
+		if !self.node.notification_protocols.contains_key(&protocol) {
+			return Err(NotificationSenderError::BadProtocol)
+		}
+		if !self.node.is_open(&target, &protocol) {
+			return Err(NotificationSenderError::Closed)
+		}
+		Ok(Box::new(MockNotificationSender { node: self.node.clone(), target, protocol }))
+	}
+
+	fn set_notification_handshake(&self, protocol: ProtocolName, handshake: Vec<u8>) {
+		self.node.state.lock().handshakes.insert(protocol, handshake);
+	}
+}
+
+#[async_trait::async_trait]
+impl NetworkRequest for MockNetworkService {
+	async fn request(
+		&self,
+		target: PeerId,
+		protocol: ProtocolName,
+		request: Vec<u8>,
+		connect: IfDisconnected,
+	) -> Result<Vec<u8>, RequestFailure> {
+		self.node.request(target, protocol, request, connect).await
+	}
+
+	fn start_request(
+		&self,
+		target: PeerId,
+		protocol: ProtocolName,
+		request: Vec<u8>,
+		tx: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+		connect: IfDisconnected,
+	) {
+		let node = self.node.clone();
+		(self.node.executor.lock())(Box::pin(async move {
+			let _ = tx.send(node.request(target, protocol, request, connect).await);
+		}));
+	}
+}
+
+impl NetworkStateInfo for MockNetworkService {
+	fn external_addresses(&self) -> Vec<Multiaddr> {
+		Vec::new()
+	}
+
+	fn listen_addresses(&self) -> Vec<Multiaddr> {
+		self.node.listen_addresses.clone()
+	}
+
+	fn local_peer_id(&self) -> PeerId {
+		self.node.peer_id
+	}
+}
+
+/// Sends notifications to a peer of the in-process network.
+struct MockNotificationSender {
+	node: Arc<Node>,
+	target: PeerId,
+	protocol: ProtocolName,
+}
+
+#[async_trait::async_trait]
+impl NotificationSenderT for MockNotificationSender {
+	async fn ready(
+		&self,
+	) -> Result<Box<dyn NotificationSenderReady + '_>, NotificationSenderError> {
+		if !self.node.is_open(&self.target, &self.protocol) {
+			return Err(NotificationSenderError::Closed)
+		}
+		Ok(Box::new(MockNotificationSenderReady { sender: self, sent: false }))
+	}
+}
+
+struct MockNotificationSenderReady<'a> {
+	sender: &'a MockNotificationSender,
+	sent: bool,
+}
+
+impl NotificationSenderReady for MockNotificationSenderReady<'_> {
+	fn send(&mut self, notification: Vec<u8>) -> Result<(), NotificationSenderError> {
+		if std::mem::replace(&mut self.sent, true) {
+			return Err(NotificationSenderError::Closed)
+		}
+		let MockNotificationSender { node, target, protocol } = self.sender;
+		match node.notify(*target, protocol.clone(), notification) {
+			true => Ok(()),
+			false => Err(NotificationSenderError::Closed),
+		}
+	}
+}
@@ -16,6 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::mock_backend::MockNetworkBackend;
+
 use futures::prelude::*;
 use libp2p::{Multiaddr, PeerId};
 
@@ -23,9 +25,8 @@ use sc_consensus::{ImportQueue, Link};
 use sc_network::{
 	config::{self, FullNetworkConfiguration, MultiaddrWithPeerId, ProtocolId, TransportConfig},
 	event::Event,
-	peer_store::PeerStore,
-	NetworkEventStream, NetworkNotification, NetworkPeers, NetworkService, NetworkStateInfo,
-	NetworkWorker,
+	NetworkBackend, NetworkEventStream, NetworkNotification, NetworkPeers, NetworkService,
+	NetworkStateInfo, NetworkWorker,
 };
 use sc_network_common::role::Roles;
 use sc_network_light::light_client_requests::handler::LightClientRequestHandler;
@@ -49,23 +50,23 @@ type TestNetworkService = NetworkService<TestBlock, TestHash>;
 
 const PROTOCOL_NAME: &str = "/foo";
 
-struct TestNetwork {
-	network: TestNetworkWorker,
+struct TestNetwork<N = TestNetworkWorker> {
+	network: N,
 }
 
-impl TestNetwork {
-	pub fn new(network: TestNetworkWorker) -> Self {
+impl<N: NetworkBackend<TestBlock, TestHash>> TestNetwork<N> {
+	pub fn new(network: N) -> Self {
 		Self { network }
 	}
 
 	pub fn start_network(
 		self,
-	) -> (Arc<TestNetworkService>, (impl Stream<Item = Event> + std::marker::Unpin)) {
-		let worker = self.network;
-		let service = worker.service().clone();
+	) -> (N::NetworkService, (impl Stream<Item = Event> + std::marker::Unpin)) {
+		let backend = self.network;
+		let service = backend.network_service();
 		let event_stream = service.event_stream("test");
 
-		tokio::spawn(worker.run());
+		tokio::spawn(backend.run());
 
 		(service, event_stream)
 	}
@@ -116,7 +117,12 @@ impl TestNetworkBuilder {
 		self
 	}
 
-	pub fn build(mut self) -> TestNetwork {
+	pub fn build(self) -> TestNetwork {
+		self.build_backend()
+	}
+
+	/// Builds the node on top of the networking backend `N`.
+	pub fn build_backend<N: NetworkBackend<TestBlock, TestHash>>(mut self) -> TestNetwork<N> {
 		let client = self.client.as_mut().map_or(
 			Arc::new(TestClientBuilder::with_default_backend().build_with_longest_chain().0),
 			|v| v.clone(),
@@ -208,13 +214,13 @@ impl TestNetworkBuilder {
 				full_net_config.add_notification_protocol(config);
 			}
 		} else {
-			full_net_config.add_notification_protocol(config::NonDefaultSetConfig {
-				notifications_protocol: PROTOCOL_NAME.into(),
-				fallback_names: Vec::new(),
-				max_notification_size: 1024 * 1024,
-				handshake: None,
-				set_config: self.set_config.unwrap_or_default(),
-			});
+			full_net_config.add_notification_protocol(N::notification_config(
+				PROTOCOL_NAME.into(),
+				Vec::new(),
+				1024 * 1024,
+				None,
+				self.set_config.unwrap_or_default(),
+			));
 		}
 
 		for config in [
@@ -225,7 +231,7 @@ impl TestNetworkBuilder {
 			full_net_config.add_request_response_protocol(config);
 		}
 
-		let peer_store = PeerStore::new(
+		let peer_store = N::peer_store(
 			network_config.boot_nodes.iter().map(|bootnode| bootnode.peer_id).collect(),
 		);
 		let peer_store_handle = peer_store.handle();
@@ -233,10 +239,7 @@ impl TestNetworkBuilder {
 
 		let genesis_hash =
 			client.hash(Zero::zero()).ok().flatten().expect("Genesis block exists; qed");
-		let worker = NetworkWorker::<
-			substrate_test_runtime_client::runtime::Block,
-			substrate_test_runtime_client::runtime::Hash,
-		>::new(config::Params::<substrate_test_runtime_client::runtime::Block> {
+		let backend = N::new(config::Params::<substrate_test_runtime_client::runtime::Block> {
 			block_announce_config,
 			role: config::Role::Full,
 			executor: Box::new(|f| {
@@ -252,7 +255,7 @@ impl TestNetworkBuilder {
 		})
 		.unwrap();
 
-		let service = worker.service().clone();
+		let service = Arc::new(backend.network_service());
 		tokio::spawn(async move {
 			let _ = chain_sync_network_provider.run(service).await;
 		});
@@ -268,7 +271,7 @@ impl TestNetworkBuilder {
 		});
 		tokio::spawn(engine.run());
 
-		TestNetwork::new(worker)
+		TestNetwork::new(backend)
 	}
 }
 
@@ -823,3 +826,63 @@ async fn ensure_public_addresses_consistent_with_transport_not_memory() {
 		.build()
 		.start_network();
 }
+
+/// Connects two nodes running on the backend `N` and sends a notification from the second node
+/// to the first one, only talking to the nodes through the handles of the backend.
+async fn exchange_notification<N: NetworkBackend<TestBlock, TestHash>>() {
+	let listen_addr = config::build_multiaddr![Memory(rand::random::<u64>())];
+
+	let (node1, mut events_stream1) = TestNetworkBuilder::new()
+		.with_listen_addresses(vec![listen_addr.clone()])
+		.build_backend::<N>()
+		.start_network();
+
+	let (node2, mut events_stream2) = TestNetworkBuilder::new()
+		.with_set_config(config::SetConfig {
+			reserved_nodes: vec![MultiaddrWithPeerId {
+				multiaddr: listen_addr,
+				peer_id: node1.local_peer_id(),
+			}],
+			..Default::default()
+		})
+		.build_backend::<N>()
+		.start_network();
+
+	// Wait for the `NotificationStreamOpened`.
+	loop {
+		match events_stream2.next().await.unwrap() {
+			Event::NotificationStreamOpened { remote, protocol, .. }
+				if protocol == PROTOCOL_NAME.into() =>
+			{
+				assert_eq!(remote, node1.local_peer_id());
+				break
+			},
+			_ => {},
+		};
+	}
+
+	node2.write_notification(node1.local_peer_id(), PROTOCOL_NAME.into(), b"hello".to_vec());
+
+	loop {
+		match events_stream1.next().await.unwrap() {
+			Event::NotificationsReceived { remote, messages } => {
+				assert_eq!(remote, node2.local_peer_id());
+				assert_eq!(messages.len(), 1);
+				assert_eq!(messages[0].0, PROTOCOL_NAME.into());
+				assert_eq!(&messages[0].1[..], b"hello");
+				break
+			},
+			_ => {},
+		};
+	}
+}
+
+#[tokio::test]
+async fn network_backend_exchanges_notifications() {
+	exchange_notification::<TestNetworkWorker>().await;
+}
+
+#[tokio::test]
+async fn mock_network_backend_exchanges_notifications() {
+	exchange_notification::<MockNetworkBackend<TestBlock, TestHash>>().await;
+}
@@ -37,6 +37,26 @@ pub(crate) const MAX_TRANSACTIONS_SIZE: u64 = 16 * 1024 * 1024;
 /// Maximum number of transaction validation request we keep at any moment.
 pub(crate) const MAX_PENDING_TRANSACTIONS: usize = 8192;
 
+/// Maximum number of transactions requested from a peer at once.
+///
+/// Announcements carrying more hashes than this are split into several requests.
+pub(crate) const MAX_TRANSACTIONS_PER_REQUEST: usize = 256;
+
+/// Maximum number of requests for transactions we keep in flight to a single peer.
+pub(crate) const MAX_PENDING_REQUESTS_PER_PEER: usize = 4;
+
+/// Maximum number of requests for transactions a peer may send us per [`PROPAGATE_TIMEOUT`].
+pub(crate) const MAX_INBOUND_REQUESTS_PER_PEER: usize = 16;
+
+/// Maximum number of incoming requests for transactions we queue.
+pub(crate) const MAX_REQUEST_QUEUE: usize = 64;
+
+/// Maximum allowed size for a request for transactions.
+pub(crate) const MAX_REQUEST_SIZE: u64 = 1024 * 1024;
+
+/// Timeout of a request for transactions.
+pub(crate) const REQUEST_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
 /// Result of the transaction import.
 #[derive(Clone, Copy, Debug)]
 pub enum TransactionImport {
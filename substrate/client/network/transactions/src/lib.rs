@@ -23,13 +23,20 @@
 //! - Use [`TransactionsHandlerPrototype::new`] to create a prototype.
 //! - Pass the return value of [`TransactionsHandlerPrototype::set_config`] to the network
 //! configuration as an extra peers set.
+//! - Pass the return value of [`TransactionsHandlerPrototype::request_response_config`] to the
+//! network configuration as a request-response protocol.
 //! - Use [`TransactionsHandlerPrototype::build`] then [`TransactionsHandler::run`] to obtain a
 //! `Future` that processes transactions.
+//!
+//! Peers speaking the current version of the protocol only announce the hashes of their
+//! transactions and pull the transactions they don't know yet through the request-response
+//! protocol. Peers which negotiated one of the fallback protocol names are older nodes and get
+//! the full transactions pushed to them.
 
 use crate::config::*;
 
 use codec::{Decode, Encode};
-use futures::{prelude::*, stream::FuturesUnordered};
+use futures::{channel::oneshot, prelude::*, stream::FuturesUnordered};
 use libp2p::{multiaddr, PeerId};
 use log::{debug, trace, warn};
 
@@ -38,9 +45,11 @@ use sc_network::{
 	config::{NonDefaultSetConfig, NonReservedPeerMode, ProtocolId, SetConfig},
 	error,
 	event::Event,
+	request_responses::{IncomingRequest, OutgoingResponse, ProtocolConfig},
 	types::ProtocolName,
 	utils::{interval, LruHashSet},
-	NetworkEventStream, NetworkNotification, NetworkPeers,
+	IfDisconnected, NetworkBackend, NetworkEventStream, NetworkNotification, NetworkPeers,
+	NetworkRequest, RequestFailure,
 };
 use sc_network_common::{
 	role::ObservedRole,
@@ -51,7 +60,7 @@ use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnbound
 use sp_runtime::traits::Block as BlockT;
 
 use std::{
-	collections::{hash_map::Entry, HashMap},
+	collections::{hash_map::Entry, HashMap, HashSet},
 	iter,
 	num::NonZeroUsize,
 	pin::Pin,
@@ -64,6 +73,9 @@ pub mod config;
 /// A set of transactions.
 pub type Transactions<E> = Vec<E>;
 
+/// A set of transaction hashes, either announced or requested.
+pub type TransactionHashes<H> = Vec<H>;
+
 mod rep {
 	use sc_network::ReputationChange as Rep;
 	/// Reputation change when a peer sends us any transaction.
@@ -77,10 +89,19 @@ mod rep {
 	pub const GOOD_TRANSACTION: Rep = Rep::new(1 << 7, "Good transaction");
 	/// Reputation change when a peer sends us a bad transaction.
 	pub const BAD_TRANSACTION: Rep = Rep::new(-(1 << 12), "Bad transaction");
+	/// Reputation change when a peer sends us a message we fail to decode.
+	pub const BAD_MESSAGE: Rep = Rep::new(-(1 << 12), "Bad transactions message");
+	/// Reputation change when a peer answers a request with transactions we didn't ask for.
+	pub const UNREQUESTED_TRANSACTION: Rep = Rep::new(-(1 << 12), "Unrequested transaction");
+	/// Reputation change when a peer requests transactions more often than allowed.
+	pub const EXCESSIVE_REQUESTS: Rep = Rep::new(-(1 << 10), "Excessive transaction requests");
 }
 
 struct Metrics {
 	propagated_transactions: Counter<U64>,
+	announced_transactions: Counter<U64>,
+	requested_transactions: Counter<U64>,
+	bytes_saved: Counter<U64>,
 }
 
 impl Metrics {
@@ -93,6 +114,27 @@ impl Metrics {
 				)?,
 				r,
 			)?,
+			announced_transactions: register(
+				Counter::new(
+					"substrate_sync_announced_transactions",
+					"Number of transaction hashes announced to peers instead of the transactions",
+				)?,
+				r,
+			)?,
+			requested_transactions: register(
+				Counter::new(
+					"substrate_sync_requested_transactions",
+					"Number of announced transactions requested from peers",
+				)?,
+				r,
+			)?,
+			bytes_saved: register(
+				Counter::new(
+					"substrate_sync_transactions_bytes_saved",
+					"Number of bytes not downloaded because announced transactions were already known",
+				)?,
+				r,
+			)?,
 		})
 	}
 }
@@ -116,49 +158,85 @@ impl<H: ExHashT> Future for PendingTransaction<H> {
 	}
 }
 
+/// Version of the transactions protocol negotiated with a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProtocolVersion {
+	/// Full transactions are pushed to the peer.
+	V1,
+	/// Transaction hashes are announced to the peer, which requests the ones it doesn't know.
+	V2,
+}
+
 /// Prototype for a [`TransactionsHandler`].
 pub struct TransactionsHandlerPrototype {
 	protocol_name: ProtocolName,
-	fallback_protocol_names: Vec<ProtocolName>,
+	request_protocol_name: ProtocolName,
+	request_receiver: async_channel::Receiver<IncomingRequest>,
+	set_config: NonDefaultSetConfig,
+	request_response_config: ProtocolConfig,
 }
 
 impl TransactionsHandlerPrototype {
-	/// Create a new instance.
-	pub fn new<Hash: AsRef<[u8]>>(
+	/// Create a new instance, with the protocol configurations of the networking backend `Net`.
+	pub fn new<Hash: AsRef<[u8]>, B: BlockT, Net: NetworkBackend<B, <B as BlockT>::Hash>>(
 		protocol_id: ProtocolId,
 		genesis_hash: Hash,
 		fork_id: Option<&str>,
 	) -> Self {
-		let genesis_hash = genesis_hash.as_ref();
-		let protocol_name = if let Some(fork_id) = fork_id {
-			format!("/{}/{}/transactions/1", array_bytes::bytes2hex("", genesis_hash), fork_id)
+		let genesis_hash = array_bytes::bytes2hex("", genesis_hash.as_ref());
+		let prefix = if let Some(fork_id) = fork_id {
+			format!("/{}/{}", genesis_hash, fork_id)
 		} else {
-			format!("/{}/transactions/1", array_bytes::bytes2hex("", genesis_hash))
+			format!("/{}", genesis_hash)
 		};
+		let protocol_name: ProtocolName = format!("{}/transactions/2", prefix).into();
+		let v1_protocol_name = format!("{}/transactions/1", prefix);
 		let legacy_protocol_name = format!("/{}/transactions/1", protocol_id.as_ref());
+		let request_protocol_name: ProtocolName =
+			format!("{}/transactions/request/1", prefix).into();
+		let (request_sender, request_receiver) = async_channel::bounded(MAX_REQUEST_QUEUE);
 
-		Self {
-			protocol_name: protocol_name.into(),
-			fallback_protocol_names: iter::once(legacy_protocol_name.into()).collect(),
-		}
-	}
-
-	/// Returns the configuration of the set to put in the network configuration.
-	pub fn set_config(&self) -> NonDefaultSetConfig {
-		NonDefaultSetConfig {
-			notifications_protocol: self.protocol_name.clone(),
-			fallback_names: self.fallback_protocol_names.clone(),
-			max_notification_size: MAX_TRANSACTIONS_SIZE,
-			handshake: None,
-			set_config: SetConfig {
+		let set_config = Net::notification_config(
+			protocol_name.clone(),
+			vec![v1_protocol_name.into(), legacy_protocol_name.into()],
+			MAX_TRANSACTIONS_SIZE,
+			None,
+			SetConfig {
 				in_peers: 0,
 				out_peers: 0,
 				reserved_nodes: Vec::new(),
 				non_reserved_mode: NonReservedPeerMode::Deny,
 			},
+		);
+		let request_response_config = Net::request_response_config(
+			request_protocol_name.clone(),
+			Vec::new(),
+			MAX_REQUEST_SIZE,
+			MAX_TRANSACTIONS_SIZE,
+			REQUEST_TIMEOUT,
+			Some(request_sender),
+		);
+
+		Self {
+			protocol_name,
+			request_protocol_name,
+			request_receiver,
+			set_config,
+			request_response_config,
 		}
 	}
 
+	/// Returns the configuration of the set to put in the network configuration.
+	pub fn set_config(&self) -> NonDefaultSetConfig {
+		self.set_config.clone()
+	}
+
+	/// Returns the configuration of the request-response protocol used to pull announced
+	/// transactions, to put in the network configuration.
+	pub fn request_response_config(&self) -> ProtocolConfig {
+		self.request_response_config.clone()
+	}
+
 	/// Turns the prototype into the actual handler. Returns a controller that allows controlling
 	/// the behaviour of the handler while it's running.
 	///
@@ -166,8 +244,8 @@ impl TransactionsHandlerPrototype {
 	/// Gossiping is enabled when major syncing is done.
 	pub fn build<
 		B: BlockT + 'static,
-		H: ExHashT,
-		N: NetworkPeers + NetworkEventStream + NetworkNotification,
+		H: ExHashT + Encode + Decode,
+		N: NetworkPeers + NetworkEventStream + NetworkNotification + NetworkRequest,
 		S: SyncEventStream + sp_consensus::SyncOracle,
 	>(
 		self,
@@ -182,6 +260,10 @@ impl TransactionsHandlerPrototype {
 
 		let handler = TransactionsHandler {
 			protocol_name: self.protocol_name,
+			request_protocol_name: self.request_protocol_name,
+			request_receiver: self.request_receiver.fuse(),
+			pending_requests: FuturesUnordered::new(),
+			requested_transactions: HashSet::new(),
 			propagate_timeout: (Box::pin(interval(PROPAGATE_TIMEOUT))
 				as Pin<Box<dyn Stream<Item = ()> + Send>>)
 				.fuse(),
@@ -238,11 +320,19 @@ enum ToHandler<H: ExHashT> {
 /// Handler for transactions. Call [`TransactionsHandler::run`] to start the processing.
 pub struct TransactionsHandler<
 	B: BlockT + 'static,
-	H: ExHashT,
-	N: NetworkPeers + NetworkEventStream + NetworkNotification,
+	H: ExHashT + Encode + Decode,
+	N: NetworkPeers + NetworkEventStream + NetworkNotification + NetworkRequest,
 	S: SyncEventStream + sp_consensus::SyncOracle,
 > {
 	protocol_name: ProtocolName,
+	/// Name of the request-response protocol used to pull announced transactions.
+	request_protocol_name: ProtocolName,
+	/// Incoming requests for transactions.
+	request_receiver: stream::Fuse<async_channel::Receiver<IncomingRequest>>,
+	/// Requests for announced transactions sent to peers and not answered yet.
+	pending_requests: FuturesUnordered<PendingRequest<H>>,
+	/// Hashes of the transactions requested from peers and not received yet.
+	requested_transactions: HashSet<H>,
 	/// Interval at which we call `propagate_transactions`.
 	propagate_timeout: stream::Fuse<Pin<Box<dyn Stream<Item = ()> + Send>>>,
 	/// Pending transactions verification tasks.
@@ -274,13 +364,33 @@ struct Peer<H: ExHashT> {
 	/// Holds a set of transactions known to this peer.
 	known_transactions: LruHashSet<H>,
 	role: ObservedRole,
+	/// Version of the protocol negotiated with this peer.
+	version: ProtocolVersion,
+	/// Number of requests for transactions received from this peer since the last propagation.
+	inbound_requests: usize,
+	/// Number of requests for transactions sent to this peer and not answered yet.
+	pending_requests: usize,
 }
 
+/// A request for announced transactions, resolving to the peer it was sent to, the requested
+/// hashes and the response.
+type PendingRequest<H> = Pin<
+	Box<
+		dyn Future<
+				Output = (
+					PeerId,
+					Vec<H>,
+					Result<Result<Vec<u8>, RequestFailure>, oneshot::Canceled>,
+				),
+			> + Send,
+	>,
+>;
+
 impl<B, H, N, S> TransactionsHandler<B, H, N, S>
 where
 	B: BlockT + 'static,
-	H: ExHashT,
-	N: NetworkPeers + NetworkEventStream + NetworkNotification,
+	H: ExHashT + Encode + Decode,
+	N: NetworkPeers + NetworkEventStream + NetworkNotification + NetworkRequest,
 	S: SyncEventStream + sp_consensus::SyncOracle,
 {
 	/// Turns the [`TransactionsHandler`] into a future that should run forever and not be
@@ -298,6 +408,12 @@ where
 						warn!(target: "sub-libp2p", "Inconsistent state, no peers for pending transaction!");
 					}
 				},
+				(who, requested, response) = self.pending_requests.select_next_some() => {
+					self.on_transactions_response(who, requested, response);
+				},
+				request = self.request_receiver.select_next_some() => {
+					self.on_transactions_request(request);
+				},
 				network_event = self.net_event_stream.next() => {
 					if let Some(network_event) = network_event {
 						self.handle_network_event(network_event).await;
@@ -352,9 +468,14 @@ where
 	async fn handle_network_event(&mut self, event: Event) {
 		match event {
 			Event::Dht(_) => {},
-			Event::NotificationStreamOpened { remote, protocol, role, .. }
-				if protocol == self.protocol_name =>
-			{
+			Event::NotificationStreamOpened {
+				remote, protocol, role, negotiated_fallback, ..
+			} if protocol == self.protocol_name => {
+				let version = if negotiated_fallback.is_some() {
+					ProtocolVersion::V1
+				} else {
+					ProtocolVersion::V2
+				};
 				let _was_in = self.peers.insert(
 					remote,
 					Peer {
@@ -362,6 +483,9 @@ where
 							NonZeroUsize::new(MAX_KNOWN_TRANSACTIONS).expect("Constant is nonzero"),
 						),
 						role,
+						version,
+						inbound_requests: 0,
+						pending_requests: 0,
 					},
 				);
 				debug_assert!(_was_in.is_none());
@@ -379,7 +503,17 @@ where
 						continue
 					}
 
-					if let Ok(m) =
+					if self.peers.get(&remote).map(|peer| peer.version) == Some(ProtocolVersion::V2)
+					{
+						if let Ok(m) =
+							<TransactionHashes<H> as Decode>::decode(&mut message.as_ref())
+						{
+							self.on_announcement(remote, m);
+						} else {
+							warn!(target: "sub-libp2p", "Failed to decode transaction hashes list");
+							self.network.report_peer(remote, rep::BAD_MESSAGE);
+						}
+					} else if let Ok(m) =
 						<Transactions<B::Extrinsic> as Decode>::decode(&mut message.as_ref())
 					{
 						self.on_transactions(remote, m);
@@ -435,6 +569,189 @@ where
 		}
 	}
 
+	/// Called when peer announces the hashes of its transactions.
+	///
+	/// The transactions which are neither in the pool nor being imported or requested already
+	/// are requested from the peer.
+	fn on_announcement(&mut self, who: PeerId, hashes: TransactionHashes<H>) {
+		// Accept transactions only when node is not major syncing
+		if self.sync.is_major_syncing() {
+			trace!(target: "sync", "{} Ignoring announced transactions while major syncing", who);
+			return
+		}
+
+		trace!(target: "sync", "Received {} transaction hashes from {}", hashes.len(), who);
+		let Some(peer) = self.peers.get_mut(&who) else { return };
+
+		let mut bytes_saved = 0;
+		let mut to_request = Vec::new();
+		let mut seen = HashSet::new();
+		for hash in hashes {
+			peer.known_transactions.insert(hash.clone());
+
+			if !seen.insert(hash.clone()) ||
+				self.pending_transactions_peers.contains_key(&hash) ||
+				self.requested_transactions.contains(&hash)
+			{
+				continue
+			}
+
+			if let Some(transaction) = self.transaction_pool.transaction(&hash) {
+				bytes_saved += transaction.encoded_size().saturating_sub(hash.encoded_size());
+				continue
+			}
+
+			to_request.push(hash);
+		}
+
+		if let Some(ref metrics) = self.metrics {
+			metrics.bytes_saved.inc_by(bytes_saved as _);
+		}
+
+		for chunk in to_request.chunks(MAX_TRANSACTIONS_PER_REQUEST) {
+			if peer.pending_requests >= MAX_PENDING_REQUESTS_PER_PEER {
+				debug!(
+					target: "sync",
+					"Not requesting announced transactions from {}: too many pending requests",
+					who,
+				);
+				break
+			}
+			if self.pending_transactions.len() + self.requested_transactions.len() >
+				MAX_PENDING_TRANSACTIONS
+			{
+				debug!(
+					target: "sync",
+					"Not requesting announced transactions that exceed `MAX_PENDING_TRANSACTIONS`({}) limit",
+					MAX_PENDING_TRANSACTIONS,
+				);
+				break
+			}
+
+			let requested = chunk.to_vec();
+			peer.pending_requests += 1;
+			self.requested_transactions.extend(requested.iter().cloned());
+			if let Some(ref metrics) = self.metrics {
+				metrics.requested_transactions.inc_by(requested.len() as _);
+			}
+
+			let (tx, rx) = oneshot::channel();
+			self.network.start_request(
+				who,
+				self.request_protocol_name.clone(),
+				requested.encode(),
+				tx,
+				IfDisconnected::ImmediateError,
+			);
+			self.pending_requests.push(async move { (who, requested, rx.await) }.boxed());
+		}
+	}
+
+	/// Called when peer answers a request for announced transactions.
+	fn on_transactions_response(
+		&mut self,
+		who: PeerId,
+		requested: Vec<H>,
+		response: Result<Result<Vec<u8>, RequestFailure>, oneshot::Canceled>,
+	) {
+		for hash in &requested {
+			self.requested_transactions.remove(hash);
+		}
+		if let Some(peer) = self.peers.get_mut(&who) {
+			peer.pending_requests = peer.pending_requests.saturating_sub(1);
+		}
+
+		let response = match response {
+			Ok(Ok(response)) => response,
+			Ok(Err(e)) => {
+				debug!(target: "sync", "Request for transactions to {} failed: {}", who, e);
+				return
+			},
+			Err(_) => {
+				debug!(target: "sync", "Request for transactions to {} was canceled", who);
+				return
+			},
+		};
+
+		let transactions =
+			match <Transactions<B::Extrinsic> as Decode>::decode(&mut response.as_ref()) {
+				Ok(transactions) => transactions,
+				Err(e) => {
+					debug!(target: "sync", "Failed to decode transactions from {}: {}", who, e);
+					self.network.report_peer(who, rep::BAD_MESSAGE);
+					return
+				},
+			};
+
+		let requested = requested.into_iter().collect::<HashSet<_>>();
+		if transactions.len() > requested.len() ||
+			transactions
+				.iter()
+				.any(|t| !requested.contains(&self.transaction_pool.hash_of(t)))
+		{
+			debug!(target: "sync", "{} answered with transactions we didn't request", who);
+			self.network.report_peer(who, rep::UNREQUESTED_TRANSACTION);
+			return
+		}
+
+		self.on_transactions(who, transactions);
+	}
+
+	/// Called when peer requests transactions we announced.
+	fn on_transactions_request(&mut self, request: IncomingRequest) {
+		let IncomingRequest { peer: who, payload, pending_response } = request;
+
+		let (result, reputation_changes) = match self.peers.get_mut(&who) {
+			None => {
+				trace!(target: "sync", "Ignoring request for transactions from unknown peer {}", who);
+				(Err(()), Vec::new())
+			},
+			Some(peer) if peer.inbound_requests >= MAX_INBOUND_REQUESTS_PER_PEER => {
+				debug!(target: "sync", "{} exceeded the limit of requests for transactions", who);
+				(Err(()), vec![rep::EXCESSIVE_REQUESTS])
+			},
+			Some(peer) => {
+				peer.inbound_requests += 1;
+
+				match <TransactionHashes<H> as Decode>::decode(&mut payload.as_ref()) {
+					Ok(hashes) if hashes.len() <= MAX_TRANSACTIONS_PER_REQUEST => {
+						let mut size = 0;
+						let transactions = hashes
+							.iter()
+							.filter_map(|hash| self.transaction_pool.transaction(hash))
+							.take_while(|transaction| {
+								size += transaction.encoded_size();
+								size as u64 <= MAX_TRANSACTIONS_SIZE
+							})
+							.collect::<Transactions<_>>();
+						trace!(
+							target: "sync",
+							"Sending {} of {} requested transactions to {}",
+							transactions.len(),
+							hashes.len(),
+							who,
+						);
+						(Ok(transactions.encode()), Vec::new())
+					},
+					Ok(_) => {
+						debug!(target: "sync", "{} requested too many transactions", who);
+						(Err(()), vec![rep::BAD_MESSAGE])
+					},
+					Err(e) => {
+						debug!(target: "sync", "Failed to decode request from {}: {}", who, e);
+						(Err(()), vec![rep::BAD_MESSAGE])
+					},
+				}
+			},
+		};
+
+		let _ = pending_response.send(OutgoingResponse {
+			result,
+			reputation_changes,
+			sent_feedback: None,
+		});
+	}
+
 	fn on_handle_transaction_import(&mut self, who: PeerId, import: TransactionImport) {
 		match import {
 			TransactionImport::KnownGood =>
@@ -465,6 +782,7 @@ where
 	) -> HashMap<H, Vec<String>> {
 		let mut propagated_to = HashMap::<_, Vec<_>>::new();
 		let mut propagated_transactions = 0;
+		let mut announced_transactions = 0;
 
 		for (who, peer) in self.peers.iter_mut() {
 			// never send transactions to the light node
@@ -481,17 +799,40 @@ where
 			propagated_transactions += hashes.len();
 
 			if !to_send.is_empty() {
+				let announced = hashes.clone();
 				for hash in hashes {
 					propagated_to.entry(hash).or_default().push(who.to_base58());
 				}
-				trace!(target: "sync", "Sending {} transactions to {}", to_send.len(), who);
-				self.network
-					.write_notification(*who, self.protocol_name.clone(), to_send.encode());
+				match peer.version {
+					ProtocolVersion::V1 => {
+						trace!(target: "sync", "Sending {} transactions to {}", to_send.len(), who);
+						self.network.write_notification(
+							*who,
+							self.protocol_name.clone(),
+							to_send.encode(),
+						);
+					},
+					ProtocolVersion::V2 => {
+						trace!(
+							target: "sync",
+							"Announcing {} transactions to {}",
+							announced.len(),
+							who,
+						);
+						announced_transactions += announced.len();
+						self.network.write_notification(
+							*who,
+							self.protocol_name.clone(),
+							announced.encode(),
+						);
+					},
+				}
 			}
 		}
 
 		if let Some(ref metrics) = self.metrics {
-			metrics.propagated_transactions.inc_by(propagated_transactions as _)
+			metrics.propagated_transactions.inc_by(propagated_transactions as _);
+			metrics.announced_transactions.inc_by(announced_transactions as _);
 		}
 
 		propagated_to
@@ -499,6 +840,11 @@ where
 
 	/// Call when we must propagate ready transactions to peers.
 	fn propagate_transactions(&mut self) {
+		// The request budget of every peer is replenished on each propagation.
+		for peer in self.peers.values_mut() {
+			peer.inbound_requests = 0;
+		}
+
 		// Accept transactions only when node is not major syncing
 		if self.sync.is_major_syncing() {
 			return
@@ -510,3 +856,344 @@ where
 		self.transaction_pool.on_broadcasted(propagated_to);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use libp2p::Multiaddr;
+	use sc_network::{
+		config::MultiaddrWithPeerId, NetworkWorker, NotificationSenderError, NotificationSenderT,
+		ReputationChange,
+	};
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper, H256};
+	use std::sync::Mutex;
+
+	type Extrinsic = ExtrinsicWrapper<u64>;
+	type Block = RawBlock<Extrinsic>;
+	type Handler = TransactionsHandler<Block, H256, Arc<TestNetwork>, TestSync>;
+
+	/// Network recording the messages sent by the handler.
+	#[derive(Default)]
+	struct TestNetwork {
+		reports: Mutex<Vec<(PeerId, ReputationChange)>>,
+		notifications: Mutex<Vec<(PeerId, ProtocolName, Vec<u8>)>>,
+		requests: Mutex<Vec<(PeerId, ProtocolName, Vec<u8>)>>,
+	}
+
+	impl NetworkPeers for TestNetwork {
+		fn set_authorized_peers(&self, _: HashSet<PeerId>) {}
+		fn set_authorized_only(&self, _: bool) {}
+		fn add_known_address(&self, _: PeerId, _: Multiaddr) {}
+		fn report_peer(&self, who: PeerId, cost_benefit: ReputationChange) {
+			self.reports.lock().unwrap().push((who, cost_benefit));
+		}
+		fn disconnect_peer(&self, _: PeerId, _: ProtocolName) {}
+		fn accept_unreserved_peers(&self) {}
+		fn deny_unreserved_peers(&self) {}
+		fn add_reserved_peer(&self, _: MultiaddrWithPeerId) -> Result<(), String> {
+			Ok(())
+		}
+		fn remove_reserved_peer(&self, _: PeerId) {}
+		fn set_reserved_peers(&self, _: ProtocolName, _: HashSet<Multiaddr>) -> Result<(), String> {
+			Ok(())
+		}
+		fn add_peers_to_reserved_set(
+			&self,
+			_: ProtocolName,
+			_: HashSet<Multiaddr>,
+		) -> Result<(), String> {
+			Ok(())
+		}
+		fn remove_peers_from_reserved_set(
+			&self,
+			_: ProtocolName,
+			_: Vec<PeerId>,
+		) -> Result<(), String> {
+			Ok(())
+		}
+		fn sync_num_connected(&self) -> usize {
+			0
+		}
+	}
+
+	impl NetworkEventStream for TestNetwork {
+		fn event_stream(&self, _: &'static str) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+			stream::pending().boxed()
+		}
+	}
+
+	impl NetworkNotification for TestNetwork {
+		fn write_notification(&self, target: PeerId, protocol: ProtocolName, message: Vec<u8>) {
+			self.notifications.lock().unwrap().push((target, protocol, message));
+		}
+		fn notification_sender(
+			&self,
+			_: PeerId,
+			_: ProtocolName,
+		) -> Result<Box<dyn NotificationSenderT>, NotificationSenderError> {
+			unimplemented!()
+		}
+		fn set_notification_handshake(&self, _: ProtocolName, _: Vec<u8>) {}
+	}
+
+	#[async_trait::async_trait]
+	impl NetworkRequest for TestNetwork {
+		async fn request(
+			&self,
+			_: PeerId,
+			_: ProtocolName,
+			_: Vec<u8>,
+			_: IfDisconnected,
+		) -> Result<Vec<u8>, RequestFailure> {
+			unimplemented!()
+		}
+		fn start_request(
+			&self,
+			target: PeerId,
+			protocol: ProtocolName,
+			request: Vec<u8>,
+			_: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+			_: IfDisconnected,
+		) {
+			self.requests.lock().unwrap().push((target, protocol, request));
+		}
+	}
+
+	struct TestSync;
+
+	impl SyncEventStream for TestSync {
+		fn event_stream(&self, _: &'static str) -> Pin<Box<dyn Stream<Item = SyncEvent> + Send>> {
+			stream::pending().boxed()
+		}
+	}
+
+	impl sp_consensus::SyncOracle for TestSync {
+		fn is_major_syncing(&self) -> bool {
+			false
+		}
+		fn is_offline(&self) -> bool {
+			false
+		}
+	}
+
+	#[derive(Default)]
+	struct TestPool(Mutex<HashMap<H256, Extrinsic>>);
+
+	impl TestPool {
+		fn with(transactions: impl IntoIterator<Item = u64>) -> Arc<Self> {
+			let pool = Self::default();
+			pool.0
+				.lock()
+				.unwrap()
+				.extend(transactions.into_iter().map(|t| (hash(t), xt(t))));
+			Arc::new(pool)
+		}
+	}
+
+	impl TransactionPool<H256, Block> for TestPool {
+		fn transactions(&self) -> Vec<(H256, Extrinsic)> {
+			self.0.lock().unwrap().iter().map(|(h, t)| (*h, t.clone())).collect()
+		}
+		fn hash_of(&self, transaction: &Extrinsic) -> H256 {
+			hash(**transaction)
+		}
+		fn import(&self, transaction: Extrinsic) -> TransactionImportFuture {
+			let import = match self.0.lock().unwrap().insert(hash(*transaction), transaction) {
+				Some(_) => TransactionImport::KnownGood,
+				None => TransactionImport::NewGood,
+			};
+			Box::pin(future::ready(import))
+		}
+		fn on_broadcasted(&self, _: HashMap<H256, Vec<String>>) {}
+		fn transaction(&self, hash: &H256) -> Option<Extrinsic> {
+			self.0.lock().unwrap().get(hash).cloned()
+		}
+	}
+
+	fn xt(t: u64) -> Extrinsic {
+		t.into()
+	}
+
+	fn hash(t: u64) -> H256 {
+		H256::from_low_u64_be(t)
+	}
+
+	fn handler(network: &Arc<TestNetwork>, pool: &Arc<TestPool>) -> Handler {
+		TransactionsHandlerPrototype::new::<_, Block, NetworkWorker<Block, H256>>(
+			ProtocolId::from("test"),
+			H256::zero(),
+			None,
+		)
+			.build(network.clone(), TestSync, pool.clone(), None)
+			.unwrap()
+			.0
+	}
+
+	fn connect(handler: &mut Handler, remote: PeerId, version: ProtocolVersion) {
+		let negotiated_fallback = match version {
+			ProtocolVersion::V1 => Some(ProtocolName::from("/test/transactions/1")),
+			ProtocolVersion::V2 => None,
+		};
+		block_on(handler.handle_network_event(Event::NotificationStreamOpened {
+			remote,
+			protocol: handler.protocol_name.clone(),
+			negotiated_fallback,
+			role: ObservedRole::Full,
+			received_handshake: Vec::new(),
+		}));
+	}
+
+	fn request(handler: &mut Handler, peer: PeerId, payload: Vec<u8>) -> OutgoingResponse {
+		let (pending_response, response) = oneshot::channel();
+		handler.on_transactions_request(IncomingRequest { peer, payload, pending_response });
+		block_on(response).unwrap()
+	}
+
+	#[test]
+	fn only_unknown_announced_transactions_are_requested() {
+		let network = Arc::new(TestNetwork::default());
+		let mut handler = handler(&network, &TestPool::with([1]));
+		let (alice, bob) = (PeerId::random(), PeerId::random());
+		connect(&mut handler, alice, ProtocolVersion::V2);
+		connect(&mut handler, bob, ProtocolVersion::V2);
+
+		handler.on_announcement(alice, vec![hash(1), hash(2), hash(2)]);
+		handler.on_announcement(bob, vec![hash(1), hash(2)]);
+
+		assert_eq!(
+			*network.requests.lock().unwrap(),
+			vec![(alice, handler.request_protocol_name.clone(), vec![hash(2)].encode())],
+		);
+		assert_eq!(handler.requested_transactions, HashSet::from([hash(2)]));
+		assert_eq!(handler.peers[&alice].pending_requests, 1);
+		assert_eq!(handler.peers[&bob].pending_requests, 0);
+	}
+
+	#[test]
+	fn requested_transactions_are_served_and_imported() {
+		let requester_network = Arc::new(TestNetwork::default());
+		let server_network = Arc::new(TestNetwork::default());
+		let requester_pool = TestPool::with([]);
+		let mut requester = handler(&requester_network, &requester_pool);
+		let mut server = handler(&server_network, &TestPool::with([1, 2]));
+		let (requester_id, server_id) = (PeerId::random(), PeerId::random());
+		connect(&mut requester, server_id, ProtocolVersion::V2);
+		connect(&mut server, requester_id, ProtocolVersion::V2);
+
+		server.propagate_transactions();
+		let (_, _, announcement) = server_network.notifications.lock().unwrap().remove(0);
+		block_on(requester.handle_network_event(Event::NotificationsReceived {
+			remote: server_id,
+			messages: vec![(requester.protocol_name.clone(), announcement.into())],
+		}));
+
+		let (_, _, payload) = requester_network.requests.lock().unwrap().remove(0);
+		let requested = <TransactionHashes<H256>>::decode(&mut payload.as_ref()).unwrap();
+		assert_eq!(requested.iter().collect::<HashSet<_>>(), HashSet::from([&hash(1), &hash(2)]));
+
+		let response = request(&mut server, requester_id, payload);
+		assert!(response.reputation_changes.is_empty());
+		let response = response.result.expect("Announced transactions are served");
+		requester.on_transactions_response(server_id, requested, Ok(Ok(response)));
+		assert!(requester.requested_transactions.is_empty());
+		assert_eq!(requester.peers[&server_id].pending_requests, 0);
+
+		let imported =
+			block_on(requester.pending_transactions.by_ref().take(2).collect::<Vec<_>>());
+		assert!(matches!(
+			imported[..],
+			[(_, TransactionImport::NewGood), (_, TransactionImport::NewGood)]
+		));
+		assert_eq!(requester_pool.transaction(&hash(1)), Some(xt(1)));
+		assert_eq!(requester_pool.transaction(&hash(2)), Some(xt(2)));
+	}
+
+	#[test]
+	fn unrequested_transactions_are_rejected() {
+		let network = Arc::new(TestNetwork::default());
+		let mut handler = handler(&network, &TestPool::with([]));
+		let peer = PeerId::random();
+		connect(&mut handler, peer, ProtocolVersion::V2);
+
+		handler.on_announcement(peer, vec![hash(1)]);
+		handler.on_transactions_response(peer, vec![hash(1)], Ok(Ok(vec![xt(1), xt(2)].encode())));
+
+		assert!(handler.pending_transactions.is_empty());
+		assert_eq!(*network.reports.lock().unwrap(), vec![(peer, rep::UNREQUESTED_TRANSACTION)]);
+	}
+
+	#[test]
+	fn excessive_requests_are_rejected() {
+		let network = Arc::new(TestNetwork::default());
+		let mut handler = handler(&network, &TestPool::with([1]));
+		let peer = PeerId::random();
+		connect(&mut handler, peer, ProtocolVersion::V2);
+
+		for _ in 0..MAX_INBOUND_REQUESTS_PER_PEER {
+			let response = request(&mut handler, peer, vec![hash(1)].encode());
+			assert_eq!(response.result, Ok(vec![xt(1)].encode()));
+		}
+		let response = request(&mut handler, peer, vec![hash(1)].encode());
+		assert_eq!(response.result, Err(()));
+		assert_eq!(response.reputation_changes, vec![rep::EXCESSIVE_REQUESTS]);
+
+		// The budget is replenished on the next propagation.
+		handler.propagate_transactions();
+		let response = request(&mut handler, peer, vec![hash(1)].encode());
+		assert_eq!(response.result, Ok(vec![xt(1)].encode()));
+	}
+
+	#[test]
+	fn oversized_and_unsolicited_requests_are_rejected() {
+		let network = Arc::new(TestNetwork::default());
+		let mut handler = handler(&network, &TestPool::with([1]));
+		let (peer, stranger) = (PeerId::random(), PeerId::random());
+		connect(&mut handler, peer, ProtocolVersion::V2);
+
+		let hashes = (0..=MAX_TRANSACTIONS_PER_REQUEST as u64).map(hash).collect::<Vec<_>>();
+		let response = request(&mut handler, peer, hashes.encode());
+		assert_eq!(response.result, Err(()));
+		assert_eq!(response.reputation_changes, vec![rep::BAD_MESSAGE]);
+
+		let response = request(&mut handler, peer, vec![1u8, 2, 3]);
+		assert_eq!(response.result, Err(()));
+		assert_eq!(response.reputation_changes, vec![rep::BAD_MESSAGE]);
+
+		let response = request(&mut handler, stranger, vec![hash(1)].encode());
+		assert_eq!(response.result, Err(()));
+		assert!(response.reputation_changes.is_empty());
+	}
+
+	#[test]
+	fn v1_peers_exchange_full_transactions() {
+		let network = Arc::new(TestNetwork::default());
+		let pool = TestPool::with([1]);
+		let mut handler = handler(&network, &pool);
+		let (legacy, current) = (PeerId::random(), PeerId::random());
+		connect(&mut handler, legacy, ProtocolVersion::V1);
+		connect(&mut handler, current, ProtocolVersion::V2);
+
+		handler.propagate_transactions();
+		let mut notifications = network.notifications.lock().unwrap().drain(..).collect::<Vec<_>>();
+		notifications.sort_by_key(|(who, _, _)| *who != legacy);
+		assert_eq!(
+			notifications,
+			vec![
+				(legacy, handler.protocol_name.clone(), vec![xt(1)].encode()),
+				(current, handler.protocol_name.clone(), vec![hash(1)].encode()),
+			],
+		);
+
+		block_on(handler.handle_network_event(Event::NotificationsReceived {
+			remote: legacy,
+			messages: vec![(handler.protocol_name.clone(), vec![xt(2)].encode().into())],
+		}));
+		assert!(network.requests.lock().unwrap().is_empty());
+		assert_eq!(
+			block_on(handler.pending_transactions.next()).map(|(hash, _)| hash),
+			Some(hash(2)),
+		);
+		assert_eq!(pool.transaction(&hash(2)), Some(xt(2)));
+	}
+}
@@ -19,10 +19,22 @@
 //! Substrate Statement Store RPC API.
 
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 
 pub mod error;
 
+/// Topic filter of a statement subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TopicFilter {
+	/// Match statements which include all of the given topics. An empty list matches any
+	/// statement.
+	MatchAll(Vec<[u8; 32]>),
+	/// Match statements which include at least one of the given topics.
+	MatchAny(Vec<[u8; 32]>),
+}
+
 /// Substrate statement RPC API
 #[rpc(client, server)]
 pub trait StatementApi {
@@ -57,4 +69,32 @@ pub trait StatementApi {
 	/// Remove a statement from the store.
 	#[method(name = "statement_remove")]
 	fn remove(&self, statement_hash: [u8; 32]) -> RpcResult<()>;
+
+	/// Encrypt `data` to `recipient` and post it on the private channel between the local key
+	/// `sender` and `recipient`. The message expires at `expiry`, in seconds since the UNIX epoch.
+	#[method(name = "statement_postPrivate")]
+	fn post_private(
+		&self,
+		sender: [u8; 32],
+		recipient: [u8; 32],
+		data: Bytes,
+		expiry: u32,
+	) -> RpcResult<()>;
+
+	/// Return the decrypted data of all unexpired messages sent to the local key `recipient` by
+	/// `sender` on their private channel.
+	#[method(name = "statement_privateMessages")]
+	fn private_messages(&self, recipient: [u8; 32], sender: [u8; 32]) -> RpcResult<Vec<Bytes>>;
+
+	/// Subscribe to SCALE-encoded statements which pass `topic_filter` and whose decryption key is
+	/// identified as `dest`, or which have no decryption key if `dest` is `None`.
+	///
+	/// The matching statements already known are sent first, followed by the new ones as they
+	/// arrive. The subscription is closed if the client does not keep up with the statements.
+	#[subscription(
+		name = "statement_subscribeStatement" => "statement_statement",
+		unsubscribe = "statement_unsubscribeStatement",
+		item = Bytes,
+	)]
+	fn subscribe_statement(&self, topic_filter: TopicFilter, dest: Option<[u8; 32]>);
 }
@@ -197,9 +197,10 @@ impl TransactionState {
 			TransactionStatus::Usurped(_) => Some(TransactionEvent::Invalid(TransactionError {
 				error: "Extrinsic was rendered invalid by another extrinsic".into(),
 			})),
-			TransactionStatus::Dropped => Some(TransactionEvent::Invalid(TransactionError {
-				error: "Extrinsic dropped from the pool due to exceeding limits".into(),
-			})),
+			TransactionStatus::Dropped(reason) =>
+				Some(TransactionEvent::Invalid(TransactionError {
+					error: format!("Extrinsic dropped from the pool: {}", reason),
+				})),
 			TransactionStatus::Invalid => Some(TransactionEvent::Invalid(TransactionError {
 				error: "Extrinsic marked as invalid".into(),
 			})),
@@ -18,19 +18,29 @@
 
 //! Substrate statement store API.
 
+use crate::SubscriptionTaskExecutor;
 use codec::{Decode, Encode};
-use jsonrpsee::core::{async_trait, RpcResult};
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	types::SubscriptionResult,
+	SubscriptionSink,
+};
 /// Re-export the API for backward compatibility.
-pub use sc_rpc_api::statement::{error::Error, StatementApiServer};
+pub use sc_rpc_api::statement::{error::Error, StatementApiServer, TopicFilter};
 use sc_rpc_api::DenyUnsafe;
 use sp_core::Bytes;
 use sp_statement_store::{StatementSource, SubmitResult};
 use std::sync::Arc;
 
+/// Number of new statements queued for a subscriber before it is dropped.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
 /// Statement store API
 pub struct StatementStore {
 	store: Arc<dyn sp_statement_store::StatementStore>,
 	deny_unsafe: DenyUnsafe,
+	executor: SubscriptionTaskExecutor,
 }
 
 impl StatementStore {
@@ -38,8 +48,9 @@ impl StatementStore {
 	pub fn new(
 		store: Arc<dyn sp_statement_store::StatementStore>,
 		deny_unsafe: DenyUnsafe,
+		executor: SubscriptionTaskExecutor,
 	) -> Self {
-		StatementStore { store, deny_unsafe }
+		StatementStore { store, deny_unsafe, executor }
 	}
 }
 
@@ -90,19 +101,76 @@ impl StatementApiServer for StatementStore {
 	fn submit(&self, encoded: Bytes) -> RpcResult<()> {
 		let statement = Decode::decode(&mut &*encoded)
 			.map_err(|e| Error::StatementStore(format!("Eror decoding statement: {:?}", e)))?;
-		match self.store.submit(statement, StatementSource::Local) {
-			SubmitResult::New(_) | SubmitResult::Known => Ok(()),
-			// `KnownExpired` should not happen. Expired statements submitted with
-			// `StatementSource::Rpc` should be renewed.
-			SubmitResult::KnownExpired =>
-				Err(Error::StatementStore("Submitted an expired statement.".into()).into()),
-			SubmitResult::Bad(e) => Err(Error::StatementStore(e.into()).into()),
-			SubmitResult::Ignored => Err(Error::StatementStore("Store is full.".into()).into()),
-			SubmitResult::InternalError(e) => Err(Error::StatementStore(e.to_string()).into()),
-		}
+		submit_result(self.store.submit(statement, StatementSource::Local))
 	}
 
 	fn remove(&self, hash: [u8; 32]) -> RpcResult<()> {
 		Ok(self.store.remove(&hash).map_err(|e| Error::StatementStore(e.to_string()))?)
 	}
+
+	fn post_private(
+		&self,
+		sender: [u8; 32],
+		recipient: [u8; 32],
+		data: Bytes,
+		expiry: u32,
+	) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+
+		submit_result(self.store.post_private(sender, recipient, &data, expiry))
+	}
+
+	fn private_messages(&self, recipient: [u8; 32], sender: [u8; 32]) -> RpcResult<Vec<Bytes>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		Ok(self
+			.store
+			.private_messages(recipient, sender)
+			.map_err(|e| Error::StatementStore(e.to_string()))?
+			.into_iter()
+			.map(Into::into)
+			.collect())
+	}
+
+	fn subscribe_statement(
+		&self,
+		mut sink: SubscriptionSink,
+		topic_filter: TopicFilter,
+		dest: Option<[u8; 32]>,
+	) -> SubscriptionResult {
+		let filter = match topic_filter {
+			TopicFilter::MatchAll(topics) => sp_statement_store::TopicFilter::MatchAll(topics),
+			TopicFilter::MatchAny(topics) => sp_statement_store::TopicFilter::MatchAny(topics),
+		};
+		let (existing, new) = match self.store.subscribe(filter, dest, SUBSCRIPTION_BUFFER_SIZE) {
+			Ok(subscription) => subscription,
+			Err(e) => {
+				let _ = sink.reject(JsonRpseeError::from(Error::StatementStore(e.to_string())));
+				return Ok(())
+			},
+		};
+
+		let stream = futures::stream::iter(existing)
+			.chain(new)
+			.map(|statement| Bytes::from(statement.encode()));
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+}
+
+fn submit_result(result: SubmitResult) -> RpcResult<()> {
+	match result {
+		SubmitResult::New(_) | SubmitResult::Known => Ok(()),
+		// `KnownExpired` should not happen. Expired statements submitted with
+		// `StatementSource::Rpc` should be renewed.
+		SubmitResult::KnownExpired =>
+			Err(Error::StatementStore("Submitted an expired statement.".into()).into()),
+		SubmitResult::Bad(e) => Err(Error::StatementStore(e.into()).into()),
+		SubmitResult::Ignored => Err(Error::StatementStore("Store is full.".into()).into()),
+		SubmitResult::InternalError(e) => Err(Error::StatementStore(e.to_string()).into()),
+	}
 }
@@ -73,12 +73,12 @@ use sp_runtime::{
 	Digest, Justification, Justifications, StateVersion,
 };
 use sp_state_machine::{
-	prove_child_read, prove_range_read_with_child_with_size, prove_read,
-	read_range_proof_check_with_child_on_proving_backend, Backend as StateBackend,
-	ChildStorageCollection, KeyValueStates, KeyValueStorageLevel, StorageCollection,
-	MAX_NESTED_TRIE_DEPTH,
+	prove_child_read, prove_closest_merkle_value, prove_range_read_with_child_with_size,
+	prove_range_read_with_limits, prove_read, read_range_proof_check_with_child_on_proving_backend,
+	Backend as StateBackend, ChildStorageCollection, KeyValueStates, KeyValueStorageLevel,
+	StorageCollection, MAX_NESTED_TRIE_DEPTH,
 };
-use sp_trie::{CompactProof, MerkleValue, StorageProof};
+use sp_trie::{proof_size_extension::ProofSizeExt, CompactProof, MerkleValue, StorageProof};
 use std::{
 	collections::{HashMap, HashSet},
 	marker::PhantomData,
@@ -199,6 +199,12 @@ pub struct ClientConfig<Block: BlockT> {
 	/// Map of WASM runtime substitute starting at the child of the given block until the runtime
 	/// version doesn't match anymore.
 	pub wasm_runtime_substitutes: HashMap<NumberFor<Block>, Vec<u8>>,
+	/// Enable recording of storage proofs during block import.
+	///
+	/// This makes the size of the storage proof available to the runtime through the
+	/// [`ProofSizeExt`] while the block is executed, which is required by runtimes that read it
+	/// while building blocks.
+	pub enable_import_proof_recording: bool,
 }
 
 impl<Block: BlockT> Default for ClientConfig<Block> {
@@ -209,6 +215,7 @@ impl<Block: BlockT> Default for ClientConfig<Block> {
 			wasm_runtime_overrides: None,
 			no_genesis: false,
 			wasm_runtime_substitutes: HashMap::new(),
+			enable_import_proof_recording: false,
 		}
 	}
 }
@@ -860,6 +867,14 @@ where
 
 				runtime_api.set_call_context(CallContext::Onchain);
 
+				if self.config.enable_import_proof_recording {
+					runtime_api.record_proof();
+					let recorder = runtime_api
+						.proof_recorder()
+						.expect("Proof recording is enabled in the line above; qed.");
+					runtime_api.register_extension(ProofSizeExt::new(recorder));
+				}
+
 				runtime_api.execute_block(
 					*parent_hash,
 					Block::new(import_block.header.clone(), body.clone()),
@@ -1248,6 +1263,39 @@ where
 		self.executor.prove_execution(hash, method, call_data)
 	}
 
+	fn read_range_proof(
+		&self,
+		hash: Block::Hash,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		start_at: Option<&[u8]>,
+		count: u32,
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)> {
+		self.state_at(hash).and_then(|state| {
+			prove_range_read_with_limits(
+				state,
+				child_info,
+				prefix,
+				Some(count),
+				size_limit,
+				start_at,
+			)
+			.map_err(Into::into)
+		})
+	}
+
+	fn closest_merkle_value_proof(
+		&self,
+		hash: Block::Hash,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> sp_blockchain::Result<StorageProof> {
+		self.state_at(hash).and_then(|state| {
+			prove_closest_merkle_value(state, child_info, key).map_err(Into::into)
+		})
+	}
+
 	fn read_proof_collection(
 		&self,
 		hash: Block::Hash,
@@ -42,7 +42,8 @@ use jsonrpsee::{core::Error as JsonRpseeError, RpcModule};
 use log::{debug, error, warn};
 use sc_client_api::{blockchain::HeaderBackend, BlockBackend, BlockchainEvents, ProofProvider};
 use sc_network::{
-	config::MultiaddrWithPeerId, NetworkBlock, NetworkPeers, NetworkStateInfo, PeerId,
+	config::MultiaddrWithPeerId, NetworkBackend, NetworkBlock, NetworkPeers, NetworkStateInfo,
+	NetworkStatusProvider, PeerId,
 };
 use sc_network_sync::SyncingService;
 use sc_utils::mpsc::TracingUnboundedReceiver;
@@ -55,10 +56,11 @@ use sp_runtime::{
 
 pub use self::{
 	builder::{
-		build_network, new_client, new_db_backend, new_full_client, new_full_parts,
-		new_full_parts_with_genesis_builder, new_native_or_wasm_executor, new_wasm_executor,
-		spawn_tasks, BuildNetworkParams, KeystoreContainer, NetworkStarter, SpawnTasksParams,
-		TFullBackend, TFullCallExecutor, TFullClient,
+		build_network, build_network_with_backend, new_client, new_db_backend, new_full_client,
+		new_full_parts, new_full_parts_record_import, new_full_parts_with_genesis_builder,
+		new_native_or_wasm_executor, new_wasm_executor, spawn_tasks, BuildNetworkParams,
+		KeystoreContainer, NetworkStarter, SpawnTasksParams, TFullBackend, TFullCallExecutor,
+		TFullClient,
 	},
 	client::{ClientConfig, LocalCallExecutor},
 	error::Error,
@@ -155,9 +157,9 @@ async fn build_network_future<
 		+ Send
 		+ Sync
 		+ 'static,
-	H: sc_network_common::ExHashT,
+	N: NetworkBackend<B, <B as BlockT>::Hash>,
 >(
-	network: sc_network::NetworkWorker<B, H>,
+	network: N,
 	client: Arc<C>,
 	sync_service: Arc<SyncingService<B>>,
 	announce_imported_blocks: bool,
@@ -201,9 +203,9 @@ async fn build_network_future<
 				sync_service.on_block_finalized(notification.hash, notification.header);
 			}
 
-			// Drive the network. Shut down the network future if `NetworkWorker` has terminated.
+			// Drive the network. Shut down the network future if the backend has terminated.
 			_ = network_run => {
-				debug!("`NetworkWorker` has terminated, shutting down the network future.");
+				debug!("Network backend has terminated, shutting down the network future.");
 				return
 			}
 		}
@@ -221,10 +223,10 @@ pub async fn build_system_rpc_future<
 		+ Send
 		+ Sync
 		+ 'static,
-	H: sc_network_common::ExHashT,
+	N: NetworkStatusProvider + NetworkPeers + NetworkStateInfo,
 >(
 	role: Role,
-	network_service: Arc<sc_network::NetworkService<B, H>>,
+	network_service: N,
 	sync_service: Arc<SyncingService<B>>,
 	client: Arc<C>,
 	mut rpc_rx: TracingUnboundedReceiver<sc_rpc::system::Request<B>>,
@@ -43,6 +43,12 @@
 //! explicitly with the `remove` function) the statement is marked as expired. Expired statements
 //! can't be added to the store for `Options::purge_after_sec` seconds. This is to prevent old
 //! statements from being propagated on the network.
+//!
+//! Subscriptions.
+//!
+//! Subscribers receive new statements which pass their topic filter and decryption key as they are
+//! inserted. Each subscriber has a bounded queue. A subscriber that does not keep up is dropped,
+//! which terminates its stream, so that slow consumers never block statement submission.
 
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
@@ -51,8 +57,9 @@ mod metrics;
 
 pub use sp_statement_store::{Error, StatementStore, MAX_TOPICS};
 
+use futures::channel::mpsc;
 use metrics::MetricsLink as PrometheusMetrics;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_keystore::LocalKeystore;
 use sp_api::ProvideRuntimeApi;
@@ -63,8 +70,8 @@ use sp_statement_store::{
 	runtime_api::{
 		InvalidStatement, StatementSource, StatementStoreExt, ValidStatement, ValidateStatement,
 	},
-	AccountId, BlockHash, Channel, DecryptionKey, Hash, NetworkPriority, Proof, Result, Statement,
-	SubmitResult, Topic,
+	AccountId, BlockHash, Channel, DecryptionKey, Hash, NetworkPriority, PrivateChannel, Proof,
+	Result, Statement, StatementStream, SubmitResult, Topic, TopicFilter,
 };
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
@@ -203,11 +210,19 @@ pub struct Store {
 			+ Sync,
 	>,
 	keystore: Arc<LocalKeystore>,
+	subscribers: Mutex<Vec<Subscriber>>,
 	// Used for testing
 	time_override: Option<u64>,
 	metrics: PrometheusMetrics,
 }
 
+/// A statement subscription.
+struct Subscriber {
+	filter: TopicFilter,
+	key: Option<DecryptionKey>,
+	sender: mpsc::Sender<Statement>,
+}
+
 enum IndexQuery {
 	Unknown,
 	Exists,
@@ -305,6 +320,41 @@ impl Index {
 		Ok(())
 	}
 
+	fn iterate_with_any(
+		&self,
+		key: Option<DecryptionKey>,
+		match_any_topics: &[Topic],
+		mut f: impl FnMut(&Hash) -> Result<()>,
+	) -> Result<()> {
+		let Some(key_set) = self.by_dec_key.get(&key) else { return Ok(()) };
+		let mut visited = HashSet::new();
+		for topic in match_any_topics {
+			for item in self.by_topic.get(topic).into_iter().flatten() {
+				if key_set.contains(item) && visited.insert(*item) {
+					log::trace!(
+						target: LOG_TARGET,
+						"Iterating by any topic/key: statement {:?}",
+						HexDisplay::from(item)
+					);
+					f(item)?
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn iterate_filtered(
+		&self,
+		key: Option<DecryptionKey>,
+		filter: &TopicFilter,
+		f: impl FnMut(&Hash) -> Result<()>,
+	) -> Result<()> {
+		match filter {
+			TopicFilter::MatchAll(topics) => self.iterate_with(key, topics, f),
+			TopicFilter::MatchAny(topics) => self.iterate_with_any(key, topics, f),
+		}
+	}
+
 	fn maintain(&mut self, current_time: u64) -> Vec<Hash> {
 		// Purge previously expired messages.
 		let mut purged = Vec::new();
@@ -571,6 +621,7 @@ impl Store {
 			index: RwLock::new(Index::new(options)),
 			validate_fn,
 			keystore,
+			subscribers: Default::default(),
 			time_override: None,
 			metrics: PrometheusMetrics::new(prometheus),
 		};
@@ -639,35 +690,63 @@ impl Store {
 		let mut result = Vec::new();
 		let index = self.index.read();
 		index.iterate_with(key, match_all_topics, |hash| {
-			match self.db.get(col::STATEMENTS, hash).map_err(|e| Error::Db(e.to_string()))? {
-				Some(entry) => {
-					if let Ok(statement) = Statement::decode(&mut entry.as_slice()) {
-						if let Some(data) = f(statement) {
-							result.push(data);
-						}
-					} else {
-						// DB inconsistency
-						log::warn!(
-							target: LOG_TARGET,
-							"Corrupt statement {:?}",
-							HexDisplay::from(hash)
-						);
-					}
-				},
-				None => {
-					// DB inconsistency
-					log::warn!(
-						target: LOG_TARGET,
-						"Missing statement {:?}",
-						HexDisplay::from(hash)
-					);
-				},
+			if let Some(data) = self.read_statement(hash)?.and_then(&mut f) {
+				result.push(data);
 			}
 			Ok(())
 		})?;
 		Ok(result)
 	}
 
+	/// Read an indexed statement from the database.
+	fn read_statement(&self, hash: &Hash) -> Result<Option<Statement>> {
+		match self.db.get(col::STATEMENTS, hash).map_err(|e| Error::Db(e.to_string()))? {
+			Some(entry) => {
+				if let Ok(statement) = Statement::decode(&mut entry.as_slice()) {
+					return Ok(Some(statement))
+				}
+				// DB inconsistency
+				log::warn!(target: LOG_TARGET, "Corrupt statement {:?}", HexDisplay::from(hash));
+			},
+			None => {
+				// DB inconsistency
+				log::warn!(target: LOG_TARGET, "Missing statement {:?}", HexDisplay::from(hash));
+			},
+		}
+		Ok(None)
+	}
+
+	/// Send a newly inserted statement to the interested subscribers.
+	///
+	/// Must be called with the index write lock held, so that the statement is either replayed to a
+	/// new subscriber or sent to it, but not both.
+	fn notify_subscribers(&self, statement: &Statement) {
+		let mut subscribers = self.subscribers.lock();
+		if subscribers.is_empty() {
+			return
+		}
+		let key = statement.decryption_key();
+		subscribers.retain_mut(|subscriber| {
+			if subscriber.sender.is_closed() {
+				return false
+			}
+			if subscriber.key != key || !subscriber.filter.matches(statement) {
+				return true
+			}
+			match subscriber.sender.try_send(statement.clone()) {
+				Ok(()) => true,
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Dropping statement subscriber: {:?}",
+						e.into_send_error()
+					);
+					false
+				},
+			}
+		});
+	}
+
 	/// Perform periodic store maintenance
 	pub fn maintain(&self) {
 		log::trace!(target: LOG_TARGET, "Started store maintenance");
@@ -698,6 +777,48 @@ impl Store {
 		})
 	}
 
+	/// Get the statement key pair of `public` from the keystore.
+	fn key_pair(&self, public: [u8; 32]) -> Result<sp_core::ed25519::Pair> {
+		let key: sp_core::ed25519::Public = UncheckedFrom::unchecked_from(public);
+		let key: sp_statement_store::ed25519::Public = key.into();
+		match self.keystore.key_pair::<sp_statement_store::ed25519::Pair>(&key) {
+			Ok(Some(pair)) => Ok(pair.into_inner()),
+			Ok(None) => Err(Error::InvalidKey(format!(
+				"Keystore is missing key {:?}",
+				HexDisplay::from(&public)
+			))),
+			Err(e) => Err(Error::InvalidKey(e.to_string())),
+		}
+	}
+
+	/// Remove the expired messages of `channel` sent to any of `keys`.
+	fn remove_expired_messages(&self, channel: &PrivateChannel, keys: [[u8; 32]; 2]) -> Result<()> {
+		let current_time = self.timestamp();
+		let mut expired = Vec::new();
+		{
+			let index = self.index.read();
+			for key in keys {
+				index.iterate_with(Some(key), &[channel.topic()], |hash| {
+					if let Some((_, priority, _)) = index.entries.get(hash) {
+						if u64::from(priority.0) <= current_time {
+							expired.push(*hash);
+						}
+					}
+					Ok(())
+				})?;
+			}
+		} // Release index lock
+		for hash in expired {
+			log::trace!(
+				target: LOG_TARGET,
+				"Removing expired channel message {:?}",
+				HexDisplay::from(&hash)
+			);
+			self.remove(&hash)?;
+		}
+		Ok(())
+	}
+
 	#[cfg(test)]
 	fn set_time(&mut self, time: u64) {
 		self.time_override = Some(time);
@@ -895,6 +1016,7 @@ impl StatementStore for Store {
 				);
 				return SubmitResult::InternalError(Error::Db(e.to_string()))
 			}
+			self.notify_subscribers(&statement);
 		} // Release index lock
 		self.metrics.report(|metrics| metrics.submitted_statements.inc());
 		let network_priority = NetworkPriority::High;
@@ -925,6 +1047,78 @@ impl StatementStore for Store {
 		}
 		Ok(())
 	}
+
+	/// Post `data` on the private channel between the local key `sender` and `recipient`.
+	fn post_private(
+		&self,
+		sender: [u8; 32],
+		recipient: [u8; 32],
+		data: &[u8],
+		expiry: u32,
+	) -> SubmitResult {
+		if u64::from(expiry) <= self.timestamp() {
+			return SubmitResult::Bad("Message is already expired")
+		}
+		let pair = match self.key_pair(sender) {
+			Ok(pair) => pair,
+			Err(e) => return SubmitResult::InternalError(e),
+		};
+		let recipient_key: sp_core::ed25519::Public = UncheckedFrom::unchecked_from(recipient);
+		let Ok(channel) = PrivateChannel::between(&pair, &recipient_key) else {
+			return SubmitResult::Bad("Invalid recipient key")
+		};
+		if let Err(e) = self.remove_expired_messages(&channel, [sender, recipient]) {
+			return SubmitResult::InternalError(e)
+		}
+		let Ok(mut statement) = channel.message(data, &recipient_key, expiry) else {
+			return SubmitResult::Bad("Invalid recipient key")
+		};
+		statement.sign_ed25519_private(&pair);
+		self.submit(statement, StatementSource::Local)
+	}
+
+	/// Return the decrypted data of all unexpired messages sent to the local key `recipient` by
+	/// `sender`.
+	fn private_messages(&self, recipient: [u8; 32], sender: [u8; 32]) -> Result<Vec<Vec<u8>>> {
+		let pair = self.key_pair(recipient)?;
+		let sender_key: sp_core::ed25519::Public = UncheckedFrom::unchecked_from(sender);
+		let channel = PrivateChannel::between(&pair, &sender_key)
+			.map_err(|e| Error::InvalidKey(e.to_string()))?;
+		self.remove_expired_messages(&channel, [sender, recipient])?;
+		self.collect_statements(Some(recipient), &[channel.topic()], |statement| {
+			// Anyone knowing the topic can post on the channel, so only trust messages signed by
+			// the other party.
+			match statement.proof() {
+				Some(Proof::Ed25519 { signer, .. }) if *signer == sender =>
+					statement.decrypt_private(&pair).ok().flatten(),
+				_ => None,
+			}
+		})
+	}
+
+	/// Subscribe to statements which pass `filter` and whose decryption key is `dest`.
+	fn subscribe(
+		&self,
+		filter: TopicFilter,
+		dest: Option<[u8; 32]>,
+		buffer: usize,
+	) -> Result<(Vec<Statement>, StatementStream)> {
+		let index = self.index.read();
+		let mut existing = Vec::new();
+		index.iterate_filtered(dest, &filter, |hash| {
+			existing.extend(self.read_statement(hash)?);
+			Ok(())
+		})?;
+		// The sender adds a slot of its own to the channel capacity.
+		let (sender, receiver) = mpsc::channel(buffer.saturating_sub(1));
+		self.subscribers.lock().push(Subscriber { filter, key: dest, sender });
+		log::trace!(
+			target: LOG_TARGET,
+			"New subscription, replaying {} statements",
+			existing.len()
+		);
+		Ok((existing, receiver))
+	}
 }
 
 #[cfg(test)]
@@ -935,7 +1129,7 @@ mod tests {
 	use sp_statement_store::{
 		runtime_api::{InvalidStatement, ValidStatement, ValidateStatement},
 		AccountId, Channel, DecryptionKey, NetworkPriority, Proof, SignatureVerificationResult,
-		Statement, StatementSource, StatementStore, SubmitResult, Topic,
+		Statement, StatementSource, StatementStore, SubmitResult, Topic, TopicFilter,
 	};
 
 	type Extrinsic = sp_runtime::OpaqueExtrinsic;
@@ -1293,4 +1487,97 @@ mod tests {
 		let posted_clear = store.posted_clear(&[], public.into()).unwrap();
 		assert_eq!(posted_clear, vec![plain]);
 	}
+
+	#[test]
+	fn private_channel_messages_expire() {
+		let (mut store, _temp) = test_store();
+		let alice = store
+			.keystore
+			.ed25519_generate_new(sp_core::crypto::key_types::STATEMENT, None)
+			.unwrap();
+		let bob = store
+			.keystore
+			.ed25519_generate_new(sp_core::crypto::key_types::STATEMENT, None)
+			.unwrap();
+		store.set_time(10);
+
+		assert_eq!(
+			store.post_private(alice.0, bob.0, b"first", 20),
+			SubmitResult::New(NetworkPriority::High)
+		);
+		assert_eq!(
+			store.post_private(alice.0, bob.0, b"second", 30),
+			SubmitResult::New(NetworkPriority::High)
+		);
+		assert_eq!(
+			store.post_private(bob.0, alice.0, b"reply", 30),
+			SubmitResult::New(NetworkPriority::High)
+		);
+		assert_eq!(
+			store.post_private(alice.0, bob.0, b"late", 10),
+			SubmitResult::Bad("Message is already expired")
+		);
+		// Messages are only readable by their recipient.
+		let mut messages = store.private_messages(bob.0, alice.0).unwrap();
+		messages.sort();
+		assert_eq!(messages, vec![b"first".to_vec(), b"second".to_vec()]);
+		assert_eq!(store.private_messages(alice.0, bob.0).unwrap(), vec![b"reply".to_vec()]);
+		assert!(store.broadcasts(&[]).unwrap().is_empty());
+
+		store.set_time(20);
+		assert_eq!(store.private_messages(bob.0, alice.0).unwrap(), vec![b"second".to_vec()]);
+		assert_eq!(store.statements().unwrap().len(), 2);
+		assert!(matches!(
+			store.private_messages([0u8; 32], alice.0),
+			Err(sp_statement_store::Error::InvalidKey(_))
+		));
+	}
+
+	#[test]
+	fn subscriptions_replay_and_stream_statements() {
+		let (store, _temp) = test_store();
+		let statement0 = signed_statement_with_topics(0, &[topic(0), topic(1)], None);
+		let statement1 = signed_statement_with_topics(1, &[topic(2)], None);
+		let statement2 = signed_statement_with_topics(2, &[topic(1)], Some(dec_key(1)));
+		for s in [&statement0, &statement1, &statement2] {
+			store.submit(s.clone(), StatementSource::Network);
+		}
+		let data = |statements: Vec<Statement>| {
+			let mut data: Vec<_> = statements.into_iter().map(|s| s.data().unwrap()[0]).collect();
+			data.sort();
+			data
+		};
+
+		let (existing, mut any) = store
+			.subscribe(TopicFilter::MatchAny(vec![topic(1), topic(2)]), None, 4)
+			.unwrap();
+		assert_eq!(data(existing), vec![0, 1]);
+		let (existing, mut all) = store
+			.subscribe(TopicFilter::MatchAll(vec![topic(1)]), Some(dec_key(1)), 4)
+			.unwrap();
+		assert_eq!(data(existing), vec![2]);
+		let (existing, mut lagging) =
+			store.subscribe(TopicFilter::MatchAll(vec![]), None, 1).unwrap();
+		assert_eq!(data(existing), vec![0, 1]);
+
+		let statement3 = signed_statement_with_topics(3, &[topic(2), topic(3)], None);
+		let statement4 = signed_statement_with_topics(4, &[topic(1)], Some(dec_key(1)));
+		let statement5 = signed_statement_with_topics(5, &[topic(4)], None);
+		for s in [&statement3, &statement4, &statement5] {
+			store.submit(s.clone(), StatementSource::Network);
+		}
+
+		assert_eq!(any.try_next().unwrap(), Some(statement3.clone()));
+		assert!(any.try_next().is_err());
+		assert_eq!(all.try_next().unwrap(), Some(statement4));
+		assert!(all.try_next().is_err());
+		// The lagging subscriber is dropped once its queue is full.
+		assert_eq!(lagging.try_next().unwrap(), Some(statement3));
+		assert_eq!(lagging.try_next().unwrap(), None);
+		assert_eq!(store.subscribers.lock().len(), 2);
+
+		drop(any);
+		store.submit(signed_statement_with_topics(6, &[topic(2)], None), StatementSource::Network);
+		assert_eq!(store.subscribers.lock().len(), 1);
+	}
 }
@@ -104,8 +104,15 @@ impl PoolStatus {
 /// within 512 blocks. This either indicates that finality is not available for your chain,
 /// or that finality gadget is lagging behind. If you choose to wait for finality longer, you can
 /// re-subscribe for a particular transaction hash manually again.
+///
+/// The reason of `Dropped` is not part of the serialized form, which stays compatible with the
+/// JSON emitted by earlier versions.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(
+	from = "LegacyTransactionStatus<Hash, BlockHash>",
+	into = "LegacyTransactionStatus<Hash, BlockHash>",
+	bound(serialize = "Hash: Serialize + Clone, BlockHash: Serialize + Clone")
+)]
 pub enum TransactionStatus<Hash, BlockHash> {
 	/// Transaction is part of the future queue.
 	Future,
@@ -115,7 +122,6 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	Broadcast(Vec<String>),
 	/// Transaction has been included in block with given hash
 	/// at the given position.
-	#[serde(with = "v1_compatible")]
 	InBlock((BlockHash, TxIndex)),
 	/// The block this transaction was included in has been retracted.
 	Retracted(BlockHash),
@@ -123,17 +129,102 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	/// old watchers are being removed.
 	FinalityTimeout(BlockHash),
 	/// Transaction has been finalized by a finality-gadget, e.g GRANDPA.
-	#[serde(with = "v1_compatible")]
 	Finalized((BlockHash, TxIndex)),
 	/// Transaction has been replaced in the pool, by another transaction
 	/// that provides the same tags. (e.g. same (sender, nonce)).
 	Usurped(Hash),
-	/// Transaction has been dropped from the pool because of the limit.
-	Dropped,
+	/// Transaction has been dropped from the pool for the given reason.
+	Dropped(DroppedReason),
 	/// Transaction is no longer valid in the current state.
 	Invalid,
 }
 
+/// The serialized form of [`TransactionStatus`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum LegacyTransactionStatus<Hash, BlockHash> {
+	Future,
+	Ready,
+	Broadcast(Vec<String>),
+	#[serde(with = "v1_compatible")]
+	InBlock((BlockHash, TxIndex)),
+	Retracted(BlockHash),
+	FinalityTimeout(BlockHash),
+	#[serde(with = "v1_compatible")]
+	Finalized((BlockHash, TxIndex)),
+	Usurped(Hash),
+	Dropped,
+	Invalid,
+}
+
+impl<Hash, BlockHash> From<TransactionStatus<Hash, BlockHash>>
+	for LegacyTransactionStatus<Hash, BlockHash>
+{
+	fn from(status: TransactionStatus<Hash, BlockHash>) -> Self {
+		match status {
+			TransactionStatus::Future => Self::Future,
+			TransactionStatus::Ready => Self::Ready,
+			TransactionStatus::Broadcast(peers) => Self::Broadcast(peers),
+			TransactionStatus::InBlock(at) => Self::InBlock(at),
+			TransactionStatus::Retracted(hash) => Self::Retracted(hash),
+			TransactionStatus::FinalityTimeout(hash) => Self::FinalityTimeout(hash),
+			TransactionStatus::Finalized(at) => Self::Finalized(at),
+			TransactionStatus::Usurped(by) => Self::Usurped(by),
+			TransactionStatus::Dropped(_) => Self::Dropped,
+			TransactionStatus::Invalid => Self::Invalid,
+		}
+	}
+}
+
+impl<Hash, BlockHash> From<LegacyTransactionStatus<Hash, BlockHash>>
+	for TransactionStatus<Hash, BlockHash>
+{
+	fn from(status: LegacyTransactionStatus<Hash, BlockHash>) -> Self {
+		match status {
+			LegacyTransactionStatus::Future => Self::Future,
+			LegacyTransactionStatus::Ready => Self::Ready,
+			LegacyTransactionStatus::Broadcast(peers) => Self::Broadcast(peers),
+			LegacyTransactionStatus::InBlock(at) => Self::InBlock(at),
+			LegacyTransactionStatus::Retracted(hash) => Self::Retracted(hash),
+			LegacyTransactionStatus::FinalityTimeout(hash) => Self::FinalityTimeout(hash),
+			LegacyTransactionStatus::Finalized(at) => Self::Finalized(at),
+			LegacyTransactionStatus::Usurped(by) => Self::Usurped(by),
+			// The serialized form doesn't carry the reason, the pool limits are the historical one.
+			LegacyTransactionStatus::Dropped => Self::Dropped(DroppedReason::LimitsEnforced),
+			LegacyTransactionStatus::Invalid => Self::Invalid,
+		}
+	}
+}
+
+/// The reason why a transaction was dropped from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DroppedReason {
+	/// The pool-wide limits of the ready or the future queue were exceeded.
+	LimitsEnforced,
+	/// The sender of the transaction exceeded its quota in the ready or the future queue.
+	SenderLimitsEnforced,
+	/// The transaction was replaced by another one providing the same tags while being
+	/// resubmitted to the pool.
+	Replaced,
+	/// The transaction could not be promoted to the ready queue.
+	PromotionFailed,
+	/// The transaction was in the future queue of a pool that rejects future transactions.
+	FutureRejected,
+}
+
+impl std::fmt::Display for DroppedReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let reason = match self {
+			Self::LimitsEnforced => "pool limits exceeded",
+			Self::SenderLimitsEnforced => "sender limits exceeded",
+			Self::Replaced => "replaced on resubmission",
+			Self::PromotionFailed => "promotion to the ready queue failed",
+			Self::FutureRejected => "future transactions are rejected",
+		};
+		f.write_str(reason)
+	}
+}
+
 /// The stream of transaction events.
 pub type TransactionStatusStream<Hash, BlockHash> =
 	dyn Stream<Item = TransactionStatus<Hash, BlockHash>> + Send;
@@ -240,6 +331,25 @@ pub trait TransactionPool: Send + Sync {
 		>,
 	>;
 
+	/// Get an iterator for ready transactions of the fork ending at the block with the given
+	/// `hash` and `number`.
+	///
+	/// Pools keeping a separate view per fork leaf return the ready transactions of the view at
+	/// `hash`. By default this is the same as [`Self::ready_at`] with `number`.
+	fn ready_at_block(
+		&self,
+		_hash: <Self::Block as BlockT>::Hash,
+		number: NumberFor<Self::Block>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		self.ready_at(number)
+	}
+
 	/// Get an iterator for ready transactions ordered by priority.
 	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
 
@@ -522,5 +632,15 @@ mod tests {
 
 		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, TransactionStatus::Finalized((1, 0)));
+
+		let event: TransactionStatus<u8, u8> =
+			TransactionStatus::Dropped(DroppedReason::SenderLimitsEnforced);
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#""dropped""#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, TransactionStatus::Dropped(DroppedReason::LimitsEnforced));
 	}
 }
@@ -20,7 +20,12 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc};
+use std::{
+	cmp::Ordering,
+	collections::{hash_map::Entry, HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+};
 
 use crate::LOG_TARGET;
 use log::{debug, trace, warn};
@@ -104,6 +109,11 @@ pub struct Transaction<Hash, Extrinsic> {
 	pub propagate: bool,
 	/// Source of that transaction.
 	pub source: Source,
+	/// Encoded sender of that transaction, if known.
+	///
+	/// Transactions without a sender only count towards the global limits of the pool, see
+	/// [`BasePool::enforce_sender_limits`].
+	pub sender: Option<Vec<u8>>,
 }
 
 impl<Hash, Extrinsic> AsRef<Extrinsic> for Transaction<Hash, Extrinsic> {
@@ -162,6 +172,7 @@ impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
 			requires: self.requires.clone(),
 			provides: self.provides.clone(),
 			propagate: self.propagate,
+			sender: self.sender.clone(),
 		}
 	}
 }
@@ -197,6 +208,53 @@ where
 /// Store last pruned tags for given number of invocations.
 const RECENTLY_PRUNED_TAGS: usize = 2;
 
+/// One of the two queues of the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Queue {
+	Ready,
+	Future,
+}
+
+/// Transactions of a single sender currently in the pool.
+#[derive(Debug, Clone)]
+struct SenderTransactions<Hash> {
+	ready: HashSet<Hash>,
+	ready_bytes: usize,
+	future: HashSet<Hash>,
+	future_bytes: usize,
+}
+
+impl<Hash> Default for SenderTransactions<Hash> {
+	fn default() -> Self {
+		Self {
+			ready: Default::default(),
+			ready_bytes: 0,
+			future: Default::default(),
+			future_bytes: 0,
+		}
+	}
+}
+
+impl<Hash> SenderTransactions<Hash> {
+	fn queue(&self, queue: Queue) -> (&HashSet<Hash>, usize) {
+		match queue {
+			Queue::Ready => (&self.ready, self.ready_bytes),
+			Queue::Future => (&self.future, self.future_bytes),
+		}
+	}
+
+	fn queue_mut(&mut self, queue: Queue) -> (&mut HashSet<Hash>, &mut usize) {
+		match queue {
+			Queue::Ready => (&mut self.ready, &mut self.ready_bytes),
+			Queue::Future => (&mut self.future, &mut self.future_bytes),
+		}
+	}
+
+	fn is_empty(&self) -> bool {
+		self.ready.is_empty() && self.future.is_empty()
+	}
+}
+
 /// Transaction pool.
 ///
 /// Builds a dependency graph for all transactions in the pool and returns
@@ -218,6 +276,22 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Transactions in the pool grouped by their sender, see [`Transaction::sender`].
+	by_sender: HashMap<Vec<u8>, SenderTransactions<Hash>>,
+}
+
+impl<Hash: hash::Hash + Eq + Clone, Ex> Clone for BasePool<Hash, Ex> {
+	/// Clones the queues of the pool; the transactions themselves are shared with the original.
+	fn clone(&self) -> Self {
+		Self {
+			reject_future_transactions: self.reject_future_transactions,
+			future: self.future.clone(),
+			ready: self.ready.clone(),
+			recently_pruned: self.recently_pruned.clone(),
+			recently_pruned_index: self.recently_pruned_index,
+			by_sender: self.by_sender.clone(),
+		}
+	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
@@ -235,6 +309,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			by_sender: Default::default(),
 		}
 	}
 
@@ -287,6 +362,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			self.track(&tx.transaction, Queue::Future);
 			self.future.import(tx);
 			return Ok(Imported::Future { hash })
 		}
@@ -312,12 +388,21 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		// take first transaction from the list
 		while let Some(tx) = to_import.pop() {
 			// find transactions in Future that it unlocks
-			to_import.append(&mut self.future.satisfy_tags(&tx.transaction.provides));
+			let mut unlocked = self.future.satisfy_tags(&tx.transaction.provides);
+			for tx in &unlocked {
+				self.untrack(&tx.transaction, Queue::Future);
+			}
+			to_import.append(&mut unlocked);
 
 			// import this transaction
 			let current_hash = tx.transaction.hash.clone();
+			let transaction = tx.transaction.clone();
 			match self.ready.import(tx) {
 				Ok(mut replaced) => {
+					self.track(&transaction, Queue::Ready);
+					for tx in &replaced {
+						self.untrack(tx, Queue::Ready);
+					}
 					if !first {
 						promoted.push(current_hash);
 					}
@@ -345,7 +430,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		if removed.iter().any(|tx| tx.hash == hash) {
 			// We still need to remove all transactions that we promoted
 			// since they depend on each other and will never get to the best iterator.
-			self.ready.remove_subtree(&promoted);
+			for tx in self.ready.remove_subtree(&promoted) {
+				self.untrack(&tx, Queue::Ready);
+			}
 
 			debug!(target: LOG_TARGET, "[{:?}] Cycle detected, bailing.", hash);
 			return Err(error::Error::CycleDetected)
@@ -384,8 +471,10 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// set. We use a simplified approach: transactions of the sender with the most transactions
+	/// in the queue are removed first, see [`Transaction::sender`]. Among those, the ones with the
+	/// lowest priority go first and, in case priority is the same, the ones that occupy the pool
+	/// for the longest time.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
@@ -394,46 +483,18 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		let mut removed = vec![];
 
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
-			// find the worst transaction
-			let worst = self.ready.fold::<TransactionRef<Hash, Ex>, _>(|worst, current| {
-				let transaction = &current.transaction;
-				worst
-					.map(|worst| {
-						// Here we don't use `TransactionRef`'s ordering implementation because
-						// while it prefers priority like need here, it also prefers older
-						// transactions for inclusion purposes and limit enforcement needs to prefer
-						// newer transactions instead and drop the older ones.
-						match worst.transaction.priority.cmp(&transaction.transaction.priority) {
-							Ordering::Less => worst,
-							Ordering::Equal =>
-								if worst.insertion_id > transaction.insertion_id {
-									transaction.clone()
-								} else {
-									worst
-								},
-							Ordering::Greater => transaction.clone(),
-						}
-					})
-					.or_else(|| Some(transaction.clone()))
-			});
-
-			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+			let sender = self.heaviest_sender(Queue::Ready);
+			if let Some(worst) = self.worst_ready(sender.as_deref()) {
+				removed.append(&mut self.remove_subtree(&[worst]))
 			} else {
 				break
 			}
 		}
 
 		while future.is_exceeded(self.future.len(), self.future.bytes()) {
-			// find the worst transaction
-			let worst = self.future.fold(|worst, current| match worst {
-				None => Some(current.clone()),
-				Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
-				other => other,
-			});
-
-			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+			let sender = self.heaviest_sender(Queue::Future);
+			if let Some(worst) = self.worst_future(sender.as_deref()) {
+				removed.append(&mut self.remove_subtree(&[worst]))
 			} else {
 				break
 			}
@@ -442,6 +503,119 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Makes sure that the senders of the `imported` transactions stay within the per-sender
+	/// limits.
+	///
+	/// Removes and returns the worst transactions of every such sender exceeding its quota in one
+	/// of the queues and all transactions that depend on them.
+	pub fn enforce_sender_limits(
+		&mut self,
+		imported: &[Hash],
+		ready: &Limit,
+		future: &Limit,
+	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		let senders = self
+			.by_hashes(imported)
+			.into_iter()
+			.flatten()
+			.filter_map(|tx| tx.sender.clone())
+			.collect::<HashSet<_>>();
+		let mut removed = vec![];
+
+		for sender in senders {
+			while self.sender_exceeds(&sender, Queue::Ready, ready) {
+				if let Some(worst) = self.worst_ready(Some(&sender)) {
+					removed.append(&mut self.remove_subtree(&[worst]))
+				} else {
+					break
+				}
+			}
+
+			while self.sender_exceeds(&sender, Queue::Future, future) {
+				if let Some(worst) = self.worst_future(Some(&sender)) {
+					removed.append(&mut self.remove_subtree(&[worst]))
+				} else {
+					break
+				}
+			}
+		}
+
+		removed
+	}
+
+	/// Accounts the transaction entering the given queue to its sender.
+	fn track(&mut self, transaction: &Transaction<Hash, Ex>, queue: Queue) {
+		let Some(sender) = &transaction.sender else { return };
+		let (hashes, bytes) = self.by_sender.entry(sender.clone()).or_default().queue_mut(queue);
+		if hashes.insert(transaction.hash.clone()) {
+			*bytes += transaction.bytes;
+		}
+	}
+
+	/// Releases the transaction leaving the given queue from the account of its sender.
+	fn untrack(&mut self, transaction: &Transaction<Hash, Ex>, queue: Queue) {
+		let Some(sender) = &transaction.sender else { return };
+		if let Entry::Occupied(mut entry) = self.by_sender.entry(sender.clone()) {
+			let (hashes, bytes) = entry.get_mut().queue_mut(queue);
+			if hashes.remove(&transaction.hash) {
+				*bytes -= transaction.bytes;
+			}
+			if entry.get().is_empty() {
+				entry.remove();
+			}
+		}
+	}
+
+	/// Returns true if the transactions of `sender` in the given queue exceed the limit.
+	fn sender_exceeds(&self, sender: &[u8], queue: Queue, limit: &Limit) -> bool {
+		self.by_sender.get(sender).map_or(false, |transactions| {
+			let (hashes, bytes) = transactions.queue(queue);
+			limit.is_exceeded(hashes.len(), bytes)
+		})
+	}
+
+	/// Returns the sender with the most (and at least two) transactions in the given queue.
+	fn heaviest_sender(&self, queue: Queue) -> Option<Vec<u8>> {
+		self.by_sender
+			.iter()
+			.map(|(sender, transactions)| (transactions.queue(queue).0.len(), sender))
+			.filter(|(count, _)| *count > 1)
+			.max_by_key(|(count, _)| *count)
+			.map(|(_, sender)| sender.clone())
+	}
+
+	/// Finds the worst ready transaction, optionally only among the ones of `sender`.
+	fn worst_ready(&mut self, sender: Option<&[u8]>) -> Option<Hash> {
+		let worst = match sender {
+			Some(sender) => self.by_sender.get(sender).and_then(|transactions| {
+				transactions
+					.ready
+					.iter()
+					.filter_map(|hash| self.ready.ref_by_hash(hash))
+					.fold(None, |worst, current| worse_ready(worst, &current))
+			}),
+			None => self.ready.fold(|worst, current| worse_ready(worst, &current.transaction)),
+		};
+
+		worst.map(|worst| worst.transaction.hash.clone())
+	}
+
+	/// Finds the worst future transaction, optionally only among the ones of `sender`.
+	fn worst_future(&mut self, sender: Option<&[u8]>) -> Option<Hash> {
+		let worst = match sender {
+			Some(sender) => self.by_sender.get(sender).and_then(|transactions| {
+				transactions
+					.future
+					.iter()
+					.filter_map(|hash| self.future.get(hash))
+					.fold(None, worse_future)
+			}),
+			None => self.future.fold(worse_future),
+		};
+
+		worst.map(|worst| worst.transaction.hash.clone())
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -452,13 +626,23 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	/// and you don't want them to be stored in the pool use `prune_tags` method.
 	pub fn remove_subtree(&mut self, hashes: &[Hash]) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = self.ready.remove_subtree(hashes);
-		removed.extend(self.future.remove(hashes));
+		for tx in &removed {
+			self.untrack(tx, Queue::Ready);
+		}
+		for tx in self.future.remove(hashes) {
+			self.untrack(&tx, Queue::Future);
+			removed.push(tx);
+		}
 		removed
 	}
 
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		self.future.clear()
+		let removed = self.future.clear();
+		for tx in &removed {
+			self.untrack(tx, Queue::Future);
+		}
+		removed
 	}
 
 	/// Prunes transactions that provide given list of tags.
@@ -483,6 +667,13 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			recently_pruned.insert(tag);
 		}
 
+		for tx in &to_import {
+			self.untrack(&tx.transaction, Queue::Future);
+		}
+		for tx in &pruned {
+			self.untrack(tx, Queue::Ready);
+		}
+
 		let mut promoted = vec![];
 		let mut failed = vec![];
 		for tx in to_import {
@@ -513,6 +704,46 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	}
 }
 
+/// Picks the ready transaction to be dropped first when enforcing limits.
+///
+/// Here we don't use `TransactionRef`'s ordering implementation because while it prefers
+/// priority like need here, it also prefers older transactions for inclusion purposes and limit
+/// enforcement needs to prefer newer transactions instead and drop the older ones.
+fn worse_ready<Hash, Ex>(
+	worst: Option<TransactionRef<Hash, Ex>>,
+	current: &TransactionRef<Hash, Ex>,
+) -> Option<TransactionRef<Hash, Ex>> {
+	worst
+		.map(|worst| match worst.transaction.priority.cmp(&current.transaction.priority) {
+			Ordering::Less => worst,
+			Ordering::Equal =>
+				if worst.insertion_id > current.insertion_id {
+					current.clone()
+				} else {
+					worst
+				},
+			Ordering::Greater => current.clone(),
+		})
+		.or_else(|| Some(current.clone()))
+}
+
+/// Picks the future transaction to be dropped first when enforcing limits.
+///
+/// These are the ones with the lowest priority and, among those, the ones waiting the longest.
+fn worse_future<Hash, Ex>(
+	worst: Option<WaitingTransaction<Hash, Ex>>,
+	current: &WaitingTransaction<Hash, Ex>,
+) -> Option<WaitingTransaction<Hash, Ex>> {
+	match worst {
+		None => Some(current.clone()),
+		Some(worst) => match worst.transaction.priority.cmp(&current.transaction.priority) {
+			Ordering::Less => Some(worst),
+			Ordering::Equal if worst.imported_at <= current.imported_at => Some(worst),
+			_ => Some(current.clone()),
+		},
+	}
+}
+
 /// Queue limits
 #[derive(Debug, Clone)]
 pub struct Limit {
@@ -549,6 +780,7 @@ mod tests {
 		provides: vec![],
 		propagate: true,
 		source: Source::External,
+		sender: None,
 	};
 
 	#[test]
@@ -1042,4 +1274,107 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	fn future_tx_of(sender: u8, nonce: u8) -> Transaction<Hash, Vec<u8>> {
+		Transaction {
+			data: vec![sender, nonce],
+			hash: (sender as u64) << 8 | nonce as u64,
+			requires: vec![vec![sender, nonce - 1]],
+			provides: vec![vec![sender, nonce]],
+			sender: Some(vec![sender]),
+			..DEFAULT_TX.clone()
+		}
+	}
+
+	#[test]
+	fn transactions_without_sender_are_not_tracked() {
+		// given
+		let mut pool = pool();
+
+		// when
+		pool.import(Transaction { hash: 1, provides: vec![vec![9, 3]], ..DEFAULT_TX.clone() })
+			.unwrap();
+		pool.import(future_tx_of(7, 5)).unwrap();
+
+		// then
+		assert_eq!(pool.by_sender.len(), 1);
+		assert_eq!(pool.by_sender[&vec![7u8]].future.len(), 1);
+	}
+
+	#[test]
+	fn should_enforce_sender_limits() {
+		// given
+		let mut pool = pool();
+		for nonce in 5..10 {
+			pool.import(future_tx_of(7, nonce)).unwrap();
+		}
+		pool.import(future_tx_of(9, 4)).unwrap();
+		assert_eq!(pool.future.len(), 6);
+
+		// when
+		let limit = Limit { count: 2, total_bytes: 1000 };
+		let imported = [future_tx_of(7, 9).hash, future_tx_of(9, 4).hash];
+		let removed = pool.enforce_sender_limits(&imported, &limit, &limit);
+
+		// then
+		assert_eq!(removed.len(), 3);
+		assert!(removed.iter().all(|tx| tx.data[0] == 7));
+		assert_eq!(pool.futures().filter(|tx| tx.data[0] == 7).count(), 2);
+		assert!(pool.is_imported(&future_tx_of(9, 4).hash));
+		assert_eq!(pool.by_sender[&vec![7u8]].future_bytes, 2);
+	}
+
+	#[test]
+	fn should_evict_transactions_of_heaviest_sender_first() {
+		// given
+		let mut pool = pool();
+		for nonce in 5..9 {
+			pool.import(future_tx_of(7, nonce)).unwrap();
+		}
+		pool.import(Transaction { priority: 1, ..future_tx_of(9, 4) }).unwrap();
+		assert_eq!(pool.future.len(), 5);
+
+		// when
+		let ready = Limit { count: 10, total_bytes: 1000 };
+		let future = Limit { count: 3, total_bytes: 1000 };
+		let removed = pool.enforce_limits(&ready, &future);
+
+		// then
+		assert_eq!(removed.len(), 2);
+		assert!(removed.iter().all(|tx| tx.data[0] == 7));
+		assert!(pool.is_imported(&future_tx_of(9, 4).hash));
+	}
+
+	#[test]
+	fn should_track_sender_usage_across_queues() {
+		// given
+		let mut pool = pool();
+		for nonce in 1..4 {
+			pool.import(future_tx_of(7, nonce)).unwrap();
+		}
+		assert_eq!(pool.by_sender[&vec![7u8]].future.len(), 3);
+
+		// when
+		pool.import(Transaction {
+			hash: 1,
+			provides: vec![vec![7, 0]],
+			sender: Some(vec![7]),
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// then
+		let usage = &pool.by_sender[&vec![7u8]];
+		assert_eq!((usage.ready.len(), usage.ready_bytes), (4, 4));
+		assert_eq!((usage.future.len(), usage.future_bytes), (0, 0));
+		assert_eq!(pool.future.bytes(), 0);
+
+		// when
+		pool.prune_tags(vec![vec![7, 0], vec![7, 1]]);
+		assert_eq!(pool.by_sender[&vec![7u8]].ready.len(), 2);
+		pool.remove_subtree(&[future_tx_of(7, 2).hash]);
+
+		// then
+		assert!(pool.by_sender.is_empty());
+	}
 }
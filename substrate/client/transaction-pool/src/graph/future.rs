@@ -113,11 +113,23 @@ pub struct FutureTransactions<Hash: hash::Hash + Eq, Ex> {
 	wanted_tags: HashMap<Tag, HashSet<Hash>>,
 	/// Transactions waiting for a particular other transaction
 	waiting: HashMap<Hash, WaitingTransaction<Hash, Ex>>,
+	/// Sum of encoding lengths of all transactions in `waiting`.
+	bytes: usize,
 }
 
 impl<Hash: hash::Hash + Eq, Ex> Default for FutureTransactions<Hash, Ex> {
 	fn default() -> Self {
-		Self { wanted_tags: Default::default(), waiting: Default::default() }
+		Self { wanted_tags: Default::default(), waiting: Default::default(), bytes: 0 }
+	}
+}
+
+impl<Hash: hash::Hash + Eq + Clone, Ex> Clone for FutureTransactions<Hash, Ex> {
+	fn clone(&self) -> Self {
+		Self {
+			wanted_tags: self.wanted_tags.clone(),
+			waiting: self.waiting.clone(),
+			bytes: self.bytes,
+		}
 	}
 }
 
@@ -149,6 +161,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		}
 
 		// Add the transaction to a by-hash waiting map
+		self.bytes += tx.transaction.bytes;
 		self.waiting.insert(tx.transaction.hash.clone(), tx);
 	}
 
@@ -157,6 +170,11 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		self.waiting.contains_key(hash)
 	}
 
+	/// Returns the waiting transaction of the given hash, if it's part of the queue.
+	pub fn get(&self, hash: &Hash) -> Option<&WaitingTransaction<Hash, Ex>> {
+		self.waiting.get(hash)
+	}
+
 	/// Returns a list of known transactions
 	pub fn by_hashes(&self, hashes: &[Hash]) -> Vec<Option<Arc<Transaction<Hash, Ex>>>> {
 		hashes
@@ -186,6 +204,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 
 					if is_ready {
 						let tx = self.waiting.remove(&hash).expect(WAITING_PROOF);
+						self.bytes -= tx.transaction.bytes;
 						became_ready.push(tx);
 					}
 				}
@@ -202,6 +221,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		let mut removed = vec![];
 		for hash in hashes {
 			if let Some(waiting_tx) = self.waiting.remove(hash) {
+				self.bytes -= waiting_tx.transaction.bytes;
 				// remove from wanted_tags as well
 				for tag in waiting_tx.missing_tags {
 					let remove = if let Some(wanted) = self.wanted_tags.get_mut(&tag) {
@@ -237,6 +257,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
+		self.bytes = 0;
 		self.waiting.drain().map(|(_, tx)| tx.transaction).collect()
 	}
 
@@ -247,6 +268,6 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 
 	/// Returns sum of encoding lengths of all transactions in this queue.
 	pub fn bytes(&self) -> usize {
-		self.waiting.values().fold(0, |acc, tx| acc + tx.transaction.bytes)
+		self.bytes
 	}
 }
@@ -21,6 +21,7 @@ use std::{collections::HashMap, fmt::Debug, hash};
 use crate::LOG_TARGET;
 use linked_hash_map::LinkedHashMap;
 use log::{debug, trace};
+use sc_transaction_pool_api::DroppedReason;
 use serde::Serialize;
 use sp_runtime::traits;
 
@@ -87,13 +88,16 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		self.fire(tx, |watcher| watcher.future());
 	}
 
-	/// Transaction was dropped from the pool because of the limit.
-	pub fn dropped(&mut self, tx: &H, by: Option<&H>) {
-		trace!(target: LOG_TARGET, "[{:?}] Dropped (replaced with {:?})", tx, by);
-		self.fire(tx, |watcher| match by {
-			Some(t) => watcher.usurped(t.clone()),
-			None => watcher.dropped(),
-		})
+	/// Transaction was replaced in the pool by another one.
+	pub fn usurped(&mut self, tx: &H, by: &H) {
+		trace!(target: LOG_TARGET, "[{:?}] Usurped by {:?}", tx, by);
+		self.fire(tx, |watcher| watcher.usurped(by.clone()))
+	}
+
+	/// Transaction was dropped from the pool for the given `reason`.
+	pub fn dropped(&mut self, tx: &H, reason: DroppedReason) {
+		trace!(target: LOG_TARGET, "[{:?}] Dropped ({:?})", tx, reason);
+		self.fire(tx, |watcher| watcher.dropped(reason))
 	}
 
 	/// Transaction was removed as invalid.
@@ -122,6 +126,16 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		}
 	}
 
+	/// Transaction was included in the given block.
+	///
+	/// Unlike [`Self::pruned`] this does nothing if the inclusion was already reported.
+	pub fn included(&mut self, block_hash: BlockHash<C>, tx: &H) {
+		if self.finality_watchers.get(&block_hash).map_or(false, |txs| txs.contains(tx)) {
+			return
+		}
+		self.pruned(block_hash, tx)
+	}
+
 	/// The block this transaction was included in has been retracted.
 	pub fn retracted(&mut self, block_hash: BlockHash<C>) {
 		if let Some(hashes) = self.finality_watchers.remove(&block_hash) {
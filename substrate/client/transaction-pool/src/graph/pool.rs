@@ -19,6 +19,7 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::LOG_TARGET;
+use codec::{Compact, Decode, Encode};
 use futures::{channel::mpsc::Receiver, Future};
 use sc_transaction_pool_api::error;
 use sp_blockchain::TreeRoute;
@@ -108,6 +109,44 @@ pub trait ChainApi: Send + Sync {
 	) -> Result<TreeRoute<Self::Block>, Self::Error>;
 }
 
+/// A closure that returns the encoded sender of the given encoded transaction, if it has one.
+#[derive(Clone)]
+pub struct SenderOf(Arc<dyn Fn(&[u8]) -> Option<Vec<u8>> + Send + Sync>);
+
+impl SenderOf {
+	/// Create a new instance from the given closure.
+	pub fn new(sender_of: impl Fn(&[u8]) -> Option<Vec<u8>> + Send + Sync + 'static) -> Self {
+		Self(Arc::new(sender_of))
+	}
+
+	/// Create a new instance that extracts the `Address` of signed
+	/// [`UncheckedExtrinsic`](sp_runtime::generic::UncheckedExtrinsic)s.
+	///
+	/// Only the length prefix, the version byte and the address are decoded, so this works for
+	/// any runtime using the standard extrinsic format, whatever its calls and signed extensions.
+	pub fn unchecked_extrinsic<Address: Decode + Encode + 'static>() -> Self {
+		Self::new(|mut encoded| {
+			let _length = Compact::<u32>::decode(&mut encoded).ok()?;
+			let version = u8::decode(&mut encoded).ok()?;
+			if version & 0b1000_0000 == 0 {
+				return None
+			}
+			Address::decode(&mut encoded).ok().map(|address| address.encode())
+		})
+	}
+
+	/// Returns the encoded sender of the given encoded transaction.
+	pub fn sender(&self, encoded: &[u8]) -> Option<Vec<u8>> {
+		(self.0)(encoded)
+	}
+}
+
+impl std::fmt::Debug for SenderOf {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(fmt, "SenderOf")
+	}
+}
+
 /// Pool configuration options.
 #[derive(Debug, Clone)]
 pub struct Options {
@@ -115,6 +154,15 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Ready queue limits of every single sender, see [`Options::sender_of`].
+	pub ready_per_sender: base::Limit,
+	/// Future queue limits of every single sender, see [`Options::sender_of`].
+	pub future_per_sender: base::Limit,
+	/// Extracts the sender of a transaction.
+	///
+	/// The per-sender limits are only enforced if it is set, as the pool itself can't tell the
+	/// sender of a transaction.
+	pub sender_of: Option<SenderOf>,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
@@ -126,6 +174,9 @@ impl Default for Options {
 		Self {
 			ready: base::Limit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
+			ready_per_sender: base::Limit { count: 1024, total_bytes: 2 * 1024 * 1024 },
+			future_per_sender: base::Limit { count: 64, total_bytes: 256 * 1024 },
+			sender_of: None,
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
 		}
@@ -423,6 +474,7 @@ impl<B: ChainApi> Pool<B> {
 				if validity.provides.is_empty() {
 					ValidatedTransaction::Invalid(hash, error::Error::NoTagsProvided.into())
 				} else {
+					let sender = self.validated_pool.sender_of(&xt);
 					ValidatedTransaction::valid_at(
 						block_number.saturated_into::<u64>(),
 						hash,
@@ -430,6 +482,7 @@ impl<B: ChainApi> Pool<B> {
 						xt,
 						bytes,
 						validity,
+						sender,
 					)
 				},
 			Err(TransactionValidityError::Invalid(e)) =>
@@ -441,6 +494,13 @@ impl<B: ChainApi> Pool<B> {
 		(hash, validity)
 	}
 
+	/// Creates a new pool with a copy of the transactions of this one.
+	///
+	/// See [`ValidatedPool::deep_clone`].
+	pub fn deep_clone(&self) -> Self {
+		Self { validated_pool: Arc::new(self.validated_pool.deep_clone()) }
+	}
+
 	/// get a reference to the underlying validated pool.
 	pub fn validated_pool(&self) -> &ValidatedPool<B> {
 		&self.validated_pool
@@ -458,9 +518,10 @@ mod tests {
 	use super::{super::base_pool::Limit, *};
 	use crate::tests::{pool, uxt, TestApi, INVALID_NONCE};
 	use assert_matches::assert_matches;
+	use codec::{Decode, Encode};
 	use futures::executor::block_on;
 	use parking_lot::Mutex;
-	use sc_transaction_pool_api::TransactionStatus;
+	use sc_transaction_pool_api::{DroppedReason, TransactionStatus};
 	use sp_runtime::transaction_validity::TransactionSource;
 	use std::{collections::HashMap, time::Instant};
 	use substrate_test_runtime::{AccountId, ExtrinsicBuilder, Transfer, H256};
@@ -587,6 +648,27 @@ mod tests {
 		assert_eq!(it.next(), None);
 	}
 
+	#[test]
+	fn should_notify_all_listeners_about_invalid_transactions() {
+		// given
+		let pool = pool();
+		let closed = pool.validated_pool().invalid_notification_stream();
+		let first = pool.validated_pool().invalid_notification_stream();
+		let second = pool.validated_pool().invalid_notification_stream();
+		drop(closed);
+		let hashes = [H256::from_low_u64_be(1), H256::from_low_u64_be(2)];
+
+		// when
+		pool.validated_pool().remove_invalid(&hashes);
+
+		// then
+		for stream in [first, second] {
+			let mut it = futures::executor::block_on_stream(stream);
+			assert_eq!(it.next(), Some(hashes[0]));
+			assert_eq!(it.next(), Some(hashes[1]));
+		}
+	}
+
 	#[test]
 	fn should_clear_stale_transactions() {
 		// given
@@ -660,7 +742,6 @@ mod tests {
 		// then
 		assert!(pool.validated_pool.is_banned(&hash1));
 	}
-	use codec::Encode;
 
 	#[test]
 	fn should_limit_futures() {
@@ -702,6 +783,66 @@ mod tests {
 		assert!(!pool.validated_pool.is_banned(&hash2));
 	}
 
+	#[test]
+	fn should_enforce_sender_limits() {
+		// given
+		let sender_of = SenderOf::new(|encoded| {
+			let xt = substrate_test_runtime::Extrinsic::decode(&mut &encoded[..]).ok()?;
+			let transfer = substrate_test_runtime::TransferData::try_from(&xt).ok()?;
+			Some(transfer.from.encode())
+		});
+		let options = Options {
+			future_per_sender: Limit { count: 2, total_bytes: 1000 },
+			sender_of: Some(sender_of),
+			..Default::default()
+		};
+		let pool = Pool::new(options, true.into(), TestApi::default().into());
+		let transfer = |from: AccountId, nonce| {
+			uxt(Transfer {
+				from,
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce,
+			})
+		};
+
+		// when
+		let bob = block_on(pool.submit_one(&BlockId::Number(0), SOURCE, transfer(Bob.into(), 10)))
+			.unwrap();
+		for nonce in 1..4 {
+			let _ = block_on(pool.submit_one(
+				&BlockId::Number(0),
+				SOURCE,
+				transfer(Alice.into(), nonce),
+			));
+		}
+
+		// then
+		assert_eq!(pool.validated_pool().status().future, 3);
+		assert!(pool.validated_pool().ready_by_hash(&bob).is_none());
+		assert!(!pool.validated_pool.is_banned(&bob));
+		assert_eq!(
+			pool.validated_pool().pool.read().by_hashes(&[bob])[0].as_ref().unwrap().sender,
+			Some(AccountId::from(Bob).encode()),
+		);
+	}
+
+	#[test]
+	fn unchecked_extrinsic_sender_of_should_return_the_signer() {
+		let sender_of = SenderOf::unchecked_extrinsic::<substrate_test_runtime::Address>();
+		let signed = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+		let unsigned = ExtrinsicBuilder::new_include_data(vec![42]).unsigned().build();
+
+		assert_eq!(sender_of.sender(&signed.encode()), Some(AccountId::from(Alice).encode()));
+		assert_eq!(sender_of.sender(&unsigned.encode()), None);
+		assert_eq!(sender_of.sender(&[]), None);
+	}
+
 	#[test]
 	fn should_error_if_reject_immediately() {
 		// given
@@ -940,7 +1081,10 @@ mod tests {
 			// then
 			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
 			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
-			assert_eq!(stream.next(), Some(TransactionStatus::Dropped));
+			assert_eq!(
+				stream.next(),
+				Some(TransactionStatus::Dropped(DroppedReason::LimitsEnforced))
+			);
 		}
 
 		#[test]
@@ -1010,7 +1154,10 @@ mod tests {
 				// then
 				let mut stream = futures::executor::block_on_stream(watcher.into_stream());
 				assert_eq!(stream.next(), Some(TransactionStatus::Ready));
-				assert_eq!(stream.next(), Some(TransactionStatus::Dropped));
+				assert_eq!(
+					stream.next(),
+					Some(TransactionStatus::Dropped(DroppedReason::LimitsEnforced))
+				);
 			}
 		}
 
@@ -126,6 +126,27 @@ impl<Hash, Ex> tracked_map::Size for ReadyTx<Hash, Ex> {
 	}
 }
 
+impl<Hash: Clone, Ex> Clone for ReadyTx<Hash, Ex> {
+	fn clone(&self) -> Self {
+		Self {
+			transaction: self.transaction.clone(),
+			unlocks: self.unlocks.clone(),
+			requires_offset: self.requires_offset,
+		}
+	}
+}
+
+impl<Hash: hash::Hash + Eq + Clone, Ex> Clone for ReadyTransactions<Hash, Ex> {
+	fn clone(&self) -> Self {
+		Self {
+			insertion_id: self.insertion_id,
+			provided_tags: self.provided_tags.clone(),
+			ready: self.ready.clone(),
+			best: self.best.clone(),
+		}
+	}
+}
+
 impl<Hash: hash::Hash + Eq, Ex> Default for ReadyTransactions<Hash, Ex> {
 	fn default() -> Self {
 		Self {
@@ -250,6 +271,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		self.by_hashes(&[hash.clone()]).into_iter().next().unwrap_or(None)
 	}
 
+	/// Retrieve the transaction reference, including its insertion id, by hash
+	pub fn ref_by_hash(&self, hash: &Hash) -> Option<TransactionRef<Hash, Ex>> {
+		self.ready.read().get(hash).map(|x| x.transaction.clone())
+	}
+
 	/// Retrieve transactions by hash
 	pub fn by_hashes(&self, hashes: &[Hash]) -> Vec<Option<Arc<Transaction<Hash, Ex>>>> {
 		let ready = self.ready.read();
@@ -602,6 +628,7 @@ mod tests {
 			provides: vec![vec![3], vec![4]],
 			propagate: true,
 			source: Source::External,
+			sender: None,
 		}
 	}
 
@@ -712,6 +739,7 @@ mod tests {
 			provides: vec![],
 			propagate: true,
 			source: Source::External,
+			sender: None,
 		};
 
 		// when
@@ -50,6 +50,12 @@ impl<Hash: hash::Hash + Eq> Default for PoolRotator<Hash> {
 	}
 }
 
+impl<Hash: hash::Hash + Eq + Clone> Clone for PoolRotator<Hash> {
+	fn clone(&self) -> Self {
+		Self { ban_time: self.ban_time, banned_until: self.banned_until.read().clone().into() }
+	}
+}
+
 impl<Hash: hash::Hash + Eq + Clone> PoolRotator<Hash> {
 	/// New rotator instance with specified ban time.
 	pub fn new(ban_time: Duration) -> Self {
@@ -127,6 +133,7 @@ mod tests {
 			provides: vec![],
 			propagate: true,
 			source: TransactionSource::External,
+			sender: None,
 		};
 
 		(hash, tx)
@@ -193,6 +200,7 @@ mod tests {
 				provides: vec![],
 				propagate: true,
 				source: TransactionSource::External,
+				sender: None,
 			}
 		}
 
@@ -79,6 +79,17 @@ impl<K: Clone, V: Clone> TrackedMap<K, V> {
 	}
 }
 
+impl<K: Clone, V: Clone> Clone for TrackedMap<K, V> {
+	/// Clones the content; unlike the iterators, the copy doesn't share the map with the original.
+	fn clone(&self) -> Self {
+		Self {
+			index: Arc::new(self.clone_map().into()),
+			bytes: self.bytes.load(AtomicOrdering::Relaxed).into(),
+			length: self.length.load(AtomicOrdering::Relaxed).into(),
+		}
+	}
+}
+
 pub struct TrackedMapReadAccess<'a, K, V> {
 	inner_guard: RwLockReadGuard<'a, HashMap<K, V>>,
 }
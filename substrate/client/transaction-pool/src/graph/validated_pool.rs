@@ -23,9 +23,10 @@ use std::{
 };
 
 use crate::LOG_TARGET;
+use codec::Encode;
 use futures::channel::mpsc::{channel, Sender};
 use parking_lot::{Mutex, RwLock};
-use sc_transaction_pool_api::{error, PoolStatus, ReadyTransactions};
+use sc_transaction_pool_api::{error, DroppedReason, PoolStatus, ReadyTransactions};
 use serde::Serialize;
 use sp_runtime::{
 	generic::BlockId,
@@ -66,6 +67,7 @@ impl<Hash, Ex, Error> ValidatedTransaction<Hash, Ex, Error> {
 		data: Ex,
 		bytes: usize,
 		validity: ValidTransaction,
+		sender: Option<Vec<u8>>,
 	) -> Self {
 		Self::Valid(base::Transaction {
 			data,
@@ -77,6 +79,7 @@ impl<Hash, Ex, Error> ValidatedTransaction<Hash, Ex, Error> {
 			provides: validity.provides,
 			propagate: validity.propagate,
 			valid_till: at.saturated_into::<u64>().saturating_add(validity.longevity),
+			sender,
 		})
 	}
 }
@@ -86,17 +89,18 @@ pub type ValidatedTransactionFor<B> =
 	ValidatedTransaction<ExtrinsicHash<B>, ExtrinsicFor<B>, <B as ChainApi>::Error>;
 
 /// A closure that returns true if the local node is a validator that can author blocks.
-pub struct IsValidator(Box<dyn Fn() -> bool + Send + Sync>);
+#[derive(Clone)]
+pub struct IsValidator(Arc<dyn Fn() -> bool + Send + Sync>);
 
 impl From<bool> for IsValidator {
 	fn from(is_validator: bool) -> Self {
-		Self(Box::new(move || is_validator))
+		Self(Arc::new(move || is_validator))
 	}
 }
 
 impl From<Box<dyn Fn() -> bool + Send + Sync>> for IsValidator {
 	fn from(is_validator: Box<dyn Fn() -> bool + Send + Sync>) -> Self {
-		Self(is_validator)
+		Self(is_validator.into())
 	}
 }
 
@@ -108,6 +112,7 @@ pub struct ValidatedPool<B: ChainApi> {
 	listener: RwLock<Listener<ExtrinsicHash<B>, B>>,
 	pub(crate) pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
+	invalid_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
 }
 
@@ -123,10 +128,28 @@ impl<B: ChainApi> ValidatedPool<B> {
 			api,
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
+			invalid_notification_sinks: Default::default(),
 			rotator: PoolRotator::new(ban_time),
 		}
 	}
 
+	/// Creates a new pool with a copy of the transactions and the bans of this one.
+	///
+	/// The transactions themselves are shared with this pool, only the queues are copied.
+	/// Watchers and notification streams are not carried over.
+	pub fn deep_clone(&self) -> Self {
+		Self {
+			is_validator: self.is_validator.clone(),
+			options: self.options.clone(),
+			listener: Default::default(),
+			api: self.api.clone(),
+			pool: RwLock::new(self.pool.read().clone()),
+			import_notification_sinks: Default::default(),
+			invalid_notification_sinks: Default::default(),
+			rotator: self.rotator.clone(),
+		}
+	}
+
 	/// Bans given set of hashes.
 	pub fn ban(&self, now: &Instant, hashes: impl IntoIterator<Item = ExtrinsicHash<B>>) {
 		self.rotator.ban(now, hashes)
@@ -168,7 +191,10 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 		// only enforce limits if there is at least one imported transaction
 		let removed = if results.iter().any(|res| res.is_ok()) {
-			self.enforce_limits()
+			let mut removed =
+				self.enforce_sender_limits(results.iter().filter_map(|res| res.as_ref().ok()));
+			removed.extend(self.enforce_limits());
+			removed
 		} else {
 			Default::default()
 		};
@@ -226,6 +252,51 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Returns the encoded sender of the transaction, see [`Options::sender_of`].
+	pub fn sender_of(&self, xt: &ExtrinsicFor<B>) -> Option<Vec<u8>> {
+		self.options.sender_of.as_ref().and_then(|sender_of| sender_of.sender(&xt.encode()))
+	}
+
+	fn enforce_sender_limits<'a>(
+		&self,
+		imported: impl Iterator<Item = &'a ExtrinsicHash<B>>,
+	) -> HashSet<ExtrinsicHash<B>> {
+		let ready_limit = &self.options.ready_per_sender;
+		let future_limit = &self.options.future_per_sender;
+
+		let removed = {
+			let mut pool = self.pool.write();
+			let imported = imported.cloned().collect::<Vec<_>>();
+			let removed = pool
+				.enforce_sender_limits(&imported, ready_limit, future_limit)
+				.into_iter()
+				.map(|x| x.hash)
+				.collect::<HashSet<_>>();
+			// ban all removed transactions
+			self.rotator.ban(&Instant::now(), removed.iter().copied());
+			removed
+		};
+		if !removed.is_empty() {
+			log::debug!(
+				target: LOG_TARGET,
+				"Enforcing sender limits ({}/{}kB ready, {}/{}kB future): {} dropped",
+				ready_limit.count,
+				ready_limit.total_bytes / 1024,
+				future_limit.count,
+				future_limit.total_bytes / 1024,
+				removed.len(),
+			);
+		}
+
+		// run notifications
+		let mut listener = self.listener.write();
+		for h in &removed {
+			listener.dropped(h, DroppedReason::SenderLimitsEnforced);
+		}
+
+		removed
+	}
+
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
 		let status = self.pool.read().status();
 		let ready_limit = &self.options.ready;
@@ -263,7 +334,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			// run notifications
 			let mut listener = self.listener.write();
 			for h in &removed {
-				listener.dropped(h, None);
+				listener.dropped(h, DroppedReason::LimitsEnforced);
 			}
 
 			removed
@@ -307,7 +378,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			Future,
 			Ready,
 			Failed,
-			Dropped,
+			Dropped(DroppedReason),
 		}
 
 		let (mut initial_statuses, final_statuses) = {
@@ -375,7 +446,10 @@ impl<B: ChainApi> ValidatedPool<B> {
 										final_statuses.insert(hash, Status::Failed);
 									}
 									for tx in removed {
-										final_statuses.insert(tx.hash, Status::Dropped);
+										final_statuses.insert(
+											tx.hash,
+											Status::Dropped(DroppedReason::Replaced),
+										);
 									}
 								},
 								base::Imported::Future { .. } => {
@@ -407,7 +481,8 @@ impl<B: ChainApi> ValidatedPool<B> {
 				// queue, updating final statuses as required
 				if reject_future_transactions {
 					for future_tx in pool.clear_future() {
-						final_statuses.insert(future_tx.hash, Status::Dropped);
+						final_statuses
+							.insert(future_tx.hash, Status::Dropped(DroppedReason::FutureRejected));
 					}
 				}
 
@@ -423,7 +498,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 				match final_status {
 					Status::Future => listener.future(&hash),
 					Status::Ready => listener.ready(&hash, None),
-					Status::Dropped => listener.dropped(&hash, None),
+					Status::Dropped(reason) => listener.dropped(&hash, reason),
 					Status::Failed => listener.invalid(&hash),
 				}
 			}
@@ -462,7 +537,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 				fire_events(&mut *listener, promoted);
 			}
 			for f in &status.failed {
-				listener.dropped(f, None);
+				listener.dropped(f, DroppedReason::PromotionFailed);
 			}
 		}
 
@@ -578,6 +653,17 @@ impl<B: ChainApi> ValidatedPool<B> {
 		stream
 	}
 
+	/// Return an event stream of transactions removed from the pool as invalid.
+	///
+	/// Only the transactions found invalid are reported, not the ones depending on them.
+	pub fn invalid_notification_stream(&self) -> EventStream<ExtrinsicHash<B>> {
+		const CHANNEL_BUFFER_SIZE: usize = 1024;
+
+		let (sink, stream) = channel(CHANNEL_BUFFER_SIZE);
+		self.invalid_notification_sinks.lock().push(sink);
+		stream
+	}
+
 	/// Invoked when extrinsics are broadcasted.
 	pub fn on_broadcasted(&self, propagated: HashMap<ExtrinsicHash<B>, Vec<String>>) {
 		let mut listener = self.listener.write();
@@ -607,6 +693,31 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 		log::debug!(target: LOG_TARGET, "Removed invalid transactions: {:?}", invalid);
 
+		let mut sinks = self.invalid_notification_sinks.lock();
+		let connected = sinks
+			.iter_mut()
+			.map(|sink| {
+				let mut connected = true;
+				for hash in hashes {
+					if let Err(e) = sink.try_send(*hash) {
+						if e.is_full() {
+							log::warn!(
+								target: LOG_TARGET,
+								"[{:?}] Trying to notify an invalid transaction but the channel is full",
+								hash,
+							);
+						} else {
+							connected = false;
+						}
+					}
+				}
+				connected
+			})
+			.collect::<Vec<_>>();
+		let mut connected = connected.into_iter();
+		sinks.retain(|_| connected.next().unwrap_or(false));
+		drop(sinks);
+
 		let mut listener = self.listener.write();
 		for tx in &invalid {
 			listener.invalid(&tx.hash);
@@ -641,6 +752,16 @@ impl<B: ChainApi> ValidatedPool<B> {
 		Ok(())
 	}
 
+	/// Notify the watchers about transactions included in the block with given hash.
+	///
+	/// Inclusions which were already reported for that block are not reported again.
+	pub fn on_block_included(&self, block_hash: BlockHash<B>, hashes: &[ExtrinsicHash<B>]) {
+		let mut listener = self.listener.write();
+		for hash in hashes {
+			listener.included(block_hash, hash);
+		}
+	}
+
 	/// Notify the listener of retracted blocks
 	pub fn on_block_retracted(&self, block_hash: BlockHash<B>) {
 		self.listener.write().retracted(block_hash)
@@ -656,7 +777,7 @@ where
 		base::Imported::Ready { ref promoted, ref failed, ref removed, ref hash } => {
 			listener.ready(hash, None);
 			failed.iter().for_each(|f| listener.invalid(f));
-			removed.iter().for_each(|r| listener.dropped(&r.hash, Some(hash)));
+			removed.iter().for_each(|r| listener.usurped(&r.hash, hash));
 			promoted.iter().for_each(|p| listener.ready(p, None));
 		},
 		base::Imported::Future { ref hash } => listener.future(hash),
@@ -19,7 +19,7 @@
 //! Extrinsics status updates.
 
 use futures::Stream;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{DroppedReason, TransactionStatus};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 
 /// Extrinsic watcher.
@@ -112,9 +112,9 @@ impl<H: Clone, BH: Clone> Sender<H, BH> {
 		self.is_finalized = true;
 	}
 
-	/// Transaction has been dropped from the pool because of the limit.
-	pub fn dropped(&mut self) {
-		self.send(TransactionStatus::Dropped);
+	/// Transaction has been dropped from the pool for the given `reason`.
+	pub fn dropped(&mut self, reason: DroppedReason) {
+		self.send(TransactionStatus::Dropped(reason));
 		self.is_finalized = true;
 	}
 
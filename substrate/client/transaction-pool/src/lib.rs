@@ -30,17 +30,19 @@ mod metrics;
 mod revalidation;
 #[cfg(test)]
 mod tests;
+mod view;
 
 pub use crate::api::FullChainApi;
 use async_trait::async_trait;
 use enactment_state::{EnactmentAction, EnactmentState};
 use futures::{
-	channel::oneshot,
-	future::{self, ready},
+	channel::{mpsc, oneshot},
+	future::{self, ready, BoxFuture, Either},
 	prelude::*,
 };
 pub use graph::{
-	base_pool::Limit as PoolLimit, ChainApi, Options, Pool, Transaction, ValidatedTransaction,
+	base_pool::Limit as PoolLimit, ChainApi, Options, Pool, SenderOf, Transaction,
+	ValidatedTransaction,
 };
 use parking_lot::Mutex;
 use std::{
@@ -60,7 +62,7 @@ use sp_runtime::{
 	generic::BlockId,
 	traits::{AtLeast32Bit, Block as BlockT, Extrinsic, Header as HeaderT, NumberFor, Zero},
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::metrics::MetricsLink as PrometheusMetrics;
 use prometheus_endpoint::Registry as PrometheusRegistry;
@@ -69,6 +71,13 @@ use sp_blockchain::{HashAndNumber, TreeRoute};
 
 pub(crate) const LOG_TARGET: &str = "txpool";
 
+/// How long [`TransactionPool::ready_at_block`] waits for a view to be built.
+///
+/// The ready set is requested within the deadline of block authoring. If the view can't be
+/// built in time, it is finished in the background and the ready set of the closest view is
+/// returned instead.
+const READY_AT_BLOCK_TIMEOUT: Duration = Duration::from_millis(200);
+
 type BoxedReadyIterator<Hash, Data> =
 	Box<dyn ReadyTransactions<Item = Arc<graph::base_pool::Transaction<Hash, Data>>> + Send>;
 
@@ -93,6 +102,10 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	options: graph::Options,
+	views: Arc<Mutex<view::ViewStore<PoolApi>>>,
+	view_builds: mpsc::UnboundedSender<BoxFuture<'static, ()>>,
+	invalid_transactions: Mutex<graph::EventStream<ExtrinsicHash<PoolApi>>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -166,8 +179,11 @@ where
 		finalized_hash: Block::Hash,
 	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
 		let pool = Arc::new(graph::Pool::new(Default::default(), true.into(), pool_api.clone()));
-		let (revalidation_queue, background_task) =
+		let (revalidation_queue, revalidation_task) =
 			revalidation::RevalidationQueue::new_background(pool_api.clone(), pool.clone());
+		let (view_builds, view_builds_task) = view_builds_task();
+		let background_task = future::join(revalidation_task, view_builds_task).map(|_| ()).boxed();
+		let invalid_transactions = Mutex::new(pool.validated_pool().invalid_notification_stream());
 		(
 			Self {
 				api: pool_api,
@@ -180,6 +196,10 @@ where
 					best_block_hash,
 					finalized_hash,
 				))),
+				options: Default::default(),
+				views: Default::default(),
+				view_builds,
+				invalid_transactions,
 			},
 			background_task,
 		)
@@ -198,7 +218,7 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
-		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
+		let pool = Arc::new(graph::Pool::new(options.clone(), is_validator, pool_api.clone()));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light =>
 				(revalidation::RevalidationQueue::new(pool_api.clone(), pool.clone()), None),
@@ -212,6 +232,9 @@ where
 		if let Some(background_task) = background_task {
 			spawner.spawn_essential("txpool-background", Some("transaction-pool"), background_task);
 		}
+		let (view_builds, view_builds_task) = view_builds_task();
+		spawner.spawn_essential("txpool-views", Some("transaction-pool"), view_builds_task);
+		let invalid_transactions = Mutex::new(pool.validated_pool().invalid_notification_stream());
 
 		Self {
 			api: pool_api,
@@ -228,6 +251,10 @@ where
 				best_block_hash,
 				finalized_hash,
 			))),
+			options,
+			views: Default::default(),
+			view_builds,
+			invalid_transactions,
 		}
 	}
 
@@ -259,12 +286,20 @@ where
 		xts: Vec<TransactionFor<Self>>,
 	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
 		let pool = self.pool.clone();
+		let views = self.views.clone();
 		let at = *at;
 
 		self.metrics
 			.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
 
-		async move { pool.submit_at(&at, source, xts).await }.boxed()
+		async move {
+			let results = pool.submit_at(&at, source, xts).await?;
+			let accepted =
+				results.iter().filter_map(|result| result.as_ref().ok().copied()).collect::<Vec<_>>();
+			import_to_views(&pool, &views, &accepted);
+			Ok(results)
+		}
+		.boxed()
 	}
 
 	fn submit_one(
@@ -274,11 +309,17 @@ where
 		xt: TransactionFor<Self>,
 	) -> PoolFuture<TxHash<Self>, Self::Error> {
 		let pool = self.pool.clone();
+		let views = self.views.clone();
 		let at = *at;
 
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
-		async move { pool.submit_one(&at, source, xt).await }.boxed()
+		async move {
+			let hash = pool.submit_one(&at, source, xt).await?;
+			import_to_views(&pool, &views, &[hash]);
+			Ok(hash)
+		}
+		.boxed()
 	}
 
 	fn submit_and_watch(
@@ -289,11 +330,13 @@ where
 	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
 		let at = *at;
 		let pool = self.pool.clone();
+		let views = self.views.clone();
 
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
 		async move {
 			let watcher = pool.submit_and_watch(&at, source, xt).await?;
+			import_to_views(&pool, &views, &[*watcher.hash()]);
 
 			Ok(watcher.into_stream().boxed())
 		}
@@ -302,6 +345,7 @@ where
 
 	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
 		let removed = self.pool.validated_pool().remove_invalid(hashes);
+		self.views.lock().remove_invalid(hashes);
 		self.metrics
 			.report(|metrics| metrics.validations_invalid.inc_by(removed.len() as u64));
 		removed
@@ -355,6 +399,56 @@ where
 			.boxed()
 	}
 
+	fn ready_at_block(
+		&self,
+		hash: <Self::Block as BlockT>::Hash,
+		number: NumberFor<Self::Block>,
+	) -> PolledIterator<PoolApi> {
+		if let Some(view) = self.views.lock().get(&hash) {
+			let iterator: ReadyIteratorFor<PoolApi> = Box::new(view.pool.validated_pool().ready());
+			return async move { iterator }.boxed()
+		}
+
+		// There is no view at blocks which were never the best one, e.g. when authoring on top
+		// of another fork. Build it on demand, but don't wait for it longer than the timeout.
+		let api = self.api.clone();
+		let options = self.options.clone();
+		let views = self.views.clone();
+		let pool = self.pool.clone();
+		let view_builds = self.view_builds.clone();
+		let finalized = self.enactment_state.lock().recent_finalized_block();
+		async move {
+			let at = HashAndNumber { hash, number };
+			let build = {
+				let (api, views, pool) = (api.clone(), views.clone(), pool.clone());
+				async move { view::update_view(api, options, &views, &pool, finalized, at).await }
+					.boxed()
+			};
+
+			let view = match future::select(build, futures_timer::Delay::new(READY_AT_BLOCK_TIMEOUT))
+				.await
+			{
+				Either::Left((view, _)) => Some(view),
+				Either::Right((_, build)) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"[{:?}] Building the view timed out, finishing it in the background",
+						hash,
+					);
+					let _ = view_builds.unbounded_send(build.map(|_| ()).boxed());
+					views.lock().closest(&*api, hash).map(|(view, _)| view)
+				},
+			};
+
+			let iterator: ReadyIteratorFor<PoolApi> = match view {
+				Some(view) => Box::new(view.pool.validated_pool().ready()),
+				None => Box::new(pool.validated_pool().ready()),
+			};
+			iterator
+		}
+		.boxed()
+	}
+
 	fn ready(&self) -> ReadyIteratorFor<PoolApi> {
 		Box::new(self.pool.validated_pool().ready())
 	}
@@ -446,6 +540,7 @@ where
 			})?;
 
 		let (hash, bytes) = self.pool.validated_pool().api().hash_and_length(&xt);
+		let sender = self.pool.validated_pool().sender_of(&xt);
 		let block_number = self
 			.api
 			.block_id_to_number(&BlockId::hash(at))?
@@ -458,6 +553,7 @@ where
 			xt,
 			bytes,
 			validity,
+			sender,
 		);
 
 		self.pool.validated_pool().submit(vec![validated]).remove(0)
@@ -546,6 +642,51 @@ impl<N: Clone + Copy + AtLeast32Bit> RevalidationStatus<N> {
 	}
 }
 
+/// Imports the transactions accepted by the main pool into all views.
+///
+/// The transactions are not validated again, the views take over the validity computed by the
+/// main pool.
+fn import_to_views<Api: graph::ChainApi + 'static>(
+	pool: &graph::Pool<Api>,
+	views: &Mutex<view::ViewStore<Api>>,
+	hashes: &[ExtrinsicHash<Api>],
+) {
+	if hashes.is_empty() {
+		return
+	}
+
+	let transactions =
+		pool.validated_pool().pool.read().by_hashes(hashes).into_iter().flatten().collect::<Vec<_>>();
+	for view in views.lock().iter() {
+		let transactions = transactions
+			.iter()
+			.filter(|tx| !view.is_included(&tx.hash))
+			.collect::<Vec<_>>();
+		let results = view.pool.validated_pool().submit(
+			transactions.iter().map(|tx| ValidatedTransaction::Valid(tx.duplicate())),
+		);
+		for (tx, result) in transactions.iter().zip(results) {
+			if let Err(e) = result {
+				log::trace!(
+					target: LOG_TARGET,
+					"[{:?}] Error importing transaction {:?} to view: {}",
+					view.at.hash,
+					tx.hash,
+					e,
+				);
+			}
+		}
+	}
+}
+
+/// Returns the sender of views to finish building in the background, along with the task
+/// driving them.
+fn view_builds_task(
+) -> (mpsc::UnboundedSender<BoxFuture<'static, ()>>, Pin<Box<dyn Future<Output = ()> + Send>>) {
+	let (sender, receiver) = mpsc::unbounded::<BoxFuture<'static, ()>>();
+	(sender, receiver.for_each_concurrent(None, |build| build).boxed())
+}
+
 /// Prune the known txs for the given block.
 async fn prune_known_txs_for_block<Block: BlockT, Api: graph::ChainApi<Block = Block>>(
 	block_hash: Block::Hash,
@@ -722,6 +863,72 @@ where
 			self.revalidation_strategy.lock().clear();
 		}
 	}
+
+	/// Builds the view at the new leaf `hash`, unless there already is one.
+	async fn update_view(&self, hash: Block::Hash) {
+		let number = match self.api.block_id_to_number(&BlockId::Hash(hash)) {
+			Ok(Some(number)) => number,
+			_ => {
+				log::debug!(target: LOG_TARGET, "[{:?}] Could not find number for view.", hash);
+				return
+			},
+		};
+
+		let finalized = self.enactment_state.lock().recent_finalized_block();
+		view::update_view(
+			self.api.clone(),
+			self.options.clone(),
+			&self.views,
+			&self.pool,
+			finalized,
+			HashAndNumber { hash, number },
+		)
+		.await;
+	}
+
+	/// Removes the transactions the main pool found invalid, e.g. during revalidation, from
+	/// the views.
+	fn remove_invalid_from_views(&self) {
+		let mut invalid = Vec::new();
+		{
+			let mut stream = self.invalid_transactions.lock();
+			while let Ok(Some(hash)) = stream.try_next() {
+				invalid.push(hash);
+			}
+		}
+		self.views.lock().remove_invalid(&invalid);
+	}
+
+	/// Notifies the watchers about the finalization of the given blocks.
+	///
+	/// The transactions included in the finalized blocks are taken from the view that reaches
+	/// finality, so that inclusions on forks the main pool never enacted are reported as well.
+	async fn handle_finalized(&self, hash: Block::Hash, tree_route: &[Block::Hash]) {
+		let finalized_view = self.views.lock().finalized_view(&*self.api, hash);
+
+		for hash in tree_route.iter().chain(std::iter::once(&hash)) {
+			if let Some(included) = finalized_view.as_ref().and_then(|view| view.included(hash)) {
+				self.pool.validated_pool().on_block_included(*hash, included);
+			}
+			if let Err(e) = self.pool.validated_pool().on_block_finalized(*hash).await {
+				log::warn!(
+					target: LOG_TARGET,
+					"Error occurred while attempting to notify watchers about finalization {}: {}",
+					hash, e
+				)
+			}
+		}
+
+		match self.api.block_id_to_number(&BlockId::Hash(hash)) {
+			Ok(Some(number)) =>
+				self.views.lock().prune_finalized(&*self.api, &HashAndNumber { hash, number }),
+			_ => log::debug!(
+				target: LOG_TARGET,
+				"[{:?}] Could not find number of finalized block.",
+				hash
+			),
+		}
+	}
 }
 
 #[async_trait]
@@ -749,18 +956,26 @@ where
 				.lock()
 				.update(&event, &compute_tree_route, &block_id_to_number);
 
-		match result {
+		// The view at the notified block is built whenever the main pool is moved to it.
+		let update_view = match result {
 			Err(msg) => {
 				log::debug!(target: LOG_TARGET, "{msg}");
 				self.enactment_state.lock().force_update(&event);
+				true
 			},
 			Ok(EnactmentAction::Skip) => return,
-			Ok(EnactmentAction::HandleFinalization) => {},
+			Ok(EnactmentAction::HandleFinalization) => false,
 			Ok(EnactmentAction::HandleEnactment(tree_route)) => {
 				self.handle_enactment(tree_route).await;
+				true
 			},
 		};
 
+		self.remove_invalid_from_views();
+		if update_view {
+			self.update_view(event.hash()).await;
+		}
+
 		if let ChainEvent::Finalized { hash, tree_route } = event {
 			log::trace!(
 				target: LOG_TARGET,
@@ -768,15 +983,7 @@ where
 				{prev_finalized_block:?}",
 			);
 
-			for hash in tree_route.iter().chain(std::iter::once(&hash)) {
-				if let Err(e) = self.pool.validated_pool().on_block_finalized(*hash).await {
-					log::warn!(
-						target: LOG_TARGET,
-						"Error occurred while attempting to notify watchers about finalization {}: {}",
-						hash, e
-					)
-				}
-			}
+			self.handle_finalized(hash, &tree_route).await;
 		}
 	}
 }
@@ -0,0 +1,402 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Views of the transaction pool at fork leaves.
+//!
+//! The main pool follows the best block. On chains with frequent forks the ready set of the best
+//! block is often not the one a block author needs, because it builds on top of another leaf.
+//! A [`View`] holds the ready and future transactions as validated at a single leaf, and the
+//! [`ViewStore`] keeps one view for every fork leaf the pool was notified about, or asked to
+//! author on, until the fork is abandoned by finality or too many views pile up.
+//!
+//! A view is derived from the closest existing view: its transactions are carried over without
+//! being validated again, the transactions of the retracted blocks are resubmitted and the ones
+//! of the enacted blocks are pruned. Only when there is no view close enough, the view is built
+//! from the transactions of the main pool.
+//!
+//! Transactions submitted to the pool are validated by the main pool only. The views import them
+//! with the validity computed there.
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
+
+use crate::{graph, prune_known_txs_for_block, LOG_TARGET};
+use parking_lot::Mutex;
+use sc_transaction_pool_api::TransactionSource;
+use sp_blockchain::{HashAndNumber, TreeRoute};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Extrinsic, Zero},
+};
+
+use graph::{BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, NumberFor};
+
+/// Maximal number of blocks between a new view and the view it is derived from.
+///
+/// Views further apart are built from the main pool instead.
+const MAX_VIEW_DISTANCE: usize = 20;
+
+/// Maximal number of views kept at the same time.
+///
+/// Views are only dropped on finality otherwise, so they would pile up while finality stalls.
+/// The views at the lowest blocks are dropped first.
+const MAX_VIEWS: usize = 16;
+
+/// The transactions of the pool as seen at a single block.
+pub(crate) struct View<PoolApi: ChainApi> {
+	/// The block the transactions of this view were validated at.
+	pub(crate) at: HashAndNumber<PoolApi::Block>,
+	/// The transactions valid at [`Self::at`].
+	pub(crate) pool: graph::Pool<PoolApi>,
+	/// The transactions included in the blocks of the fork of this view, which were not yet
+	/// finalized when the view was built.
+	included: HashMap<BlockHash<PoolApi>, (NumberFor<PoolApi>, Vec<ExtrinsicHash<PoolApi>>)>,
+}
+
+impl<PoolApi> View<PoolApi>
+where
+	PoolApi: ChainApi + 'static,
+{
+	/// Builds the view at `at` out of the given transactions.
+	///
+	/// The transactions are validated at `at`. Those included in the blocks enacted between
+	/// the `finalized` block and `at` are pruned afterwards.
+	async fn build(
+		api: Arc<PoolApi>,
+		options: graph::Options,
+		finalized: BlockHash<PoolApi>,
+		at: HashAndNumber<PoolApi::Block>,
+		xts: Vec<(TransactionSource, ExtrinsicFor<PoolApi>)>,
+	) -> Self {
+		// Only transactions which were already accepted by the main pool end up here, so the
+		// view doesn't have to care about whether the node is a validator.
+		let pool = graph::Pool::new(options, true.into(), api.clone());
+
+		for source in
+			[TransactionSource::InBlock, TransactionSource::Local, TransactionSource::External]
+		{
+			let xts = xts
+				.iter()
+				.filter(|(s, _)| *s == source)
+				.map(|(_, xt)| xt.clone())
+				.collect::<Vec<_>>();
+			if xts.is_empty() {
+				continue
+			}
+
+			if let Err(e) = pool.submit_at(&BlockId::Hash(at.hash), source, xts).await {
+				log::debug!(
+					target: LOG_TARGET,
+					"[{:?}] Error submitting transactions to view: {}",
+					at.hash,
+					e,
+				);
+			}
+		}
+
+		let mut view = Self { at, pool, included: Default::default() };
+		match api.tree_route(finalized, view.at.hash) {
+			Ok(tree_route) => {
+				view.enact(&api, &tree_route).await;
+			},
+			Err(e) => log::debug!(
+				target: LOG_TARGET,
+				"[{:?}] Error computing tree route from {:?} for view: {}",
+				view.at.hash,
+				finalized,
+				e,
+			),
+		}
+		view
+	}
+
+	/// Derives the view at `at` from the view at `base`.
+	///
+	/// The transactions of `base` are carried over without being validated again. The ones of
+	/// the blocks retracted on the way from `base` to `at` are resubmitted and the ones of the
+	/// enacted blocks are pruned.
+	async fn derive(
+		api: Arc<PoolApi>,
+		base: &Self,
+		tree_route: &TreeRoute<PoolApi::Block>,
+		finalized: NumberFor<PoolApi>,
+		at: HashAndNumber<PoolApi::Block>,
+	) -> Self {
+		let retracted = tree_route.retracted().iter().map(|b| b.hash).collect::<HashSet<_>>();
+		let included = base
+			.included
+			.iter()
+			.filter(|(hash, (number, _))| *number > finalized && !retracted.contains(hash))
+			.map(|(hash, included)| (*hash, included.clone()))
+			.collect();
+		let mut view = Self { at, pool: base.pool.deep_clone(), included };
+
+		let pruned = view.enact(&api, tree_route).await;
+
+		let mut resubmit = Vec::new();
+		for block in tree_route.retracted() {
+			let block_transactions = api
+				.block_body(block.hash)
+				.await
+				.unwrap_or_else(|e| {
+					log::warn!(target: LOG_TARGET, "Failed to fetch block body: {}", e);
+					None
+				})
+				.unwrap_or_default()
+				.into_iter()
+				.filter(|tx| tx.is_signed().unwrap_or(true))
+				.filter(|tx| !pruned.contains(&view.pool.hash_of(tx)));
+			resubmit.extend(block_transactions);
+		}
+
+		if !resubmit.is_empty() {
+			// These transactions are coming from retracted blocks, we should simply consider
+			// them external.
+			if let Err(e) = view
+				.pool
+				.resubmit_at(&BlockId::Hash(view.at.hash), TransactionSource::External, resubmit)
+				.await
+			{
+				log::debug!(
+					target: LOG_TARGET,
+					"[{:?}] Error re-submitting transactions to view: {}",
+					view.at.hash,
+					e,
+				);
+			}
+		}
+
+		view
+	}
+
+	/// Prunes the transactions of the blocks enacted by `tree_route`, in order.
+	///
+	/// Returns the hashes of all pruned transactions.
+	async fn enact(
+		&mut self,
+		api: &PoolApi,
+		tree_route: &TreeRoute<PoolApi::Block>,
+	) -> HashSet<ExtrinsicHash<PoolApi>> {
+		let mut pruned = HashSet::new();
+		for enacted in tree_route.enacted() {
+			let hashes = prune_known_txs_for_block(enacted.hash, api, &self.pool).await;
+			pruned.extend(hashes.iter().copied());
+			self.included.insert(enacted.hash, (enacted.number, hashes));
+		}
+		pruned
+	}
+
+	/// Returns true if the transaction was included in the fork of this view after the block
+	/// finalized when the view was built.
+	pub(crate) fn is_included(&self, hash: &ExtrinsicHash<PoolApi>) -> bool {
+		self.included.values().any(|(_, hashes)| hashes.contains(hash))
+	}
+
+	/// Returns the transactions included in the block with the given hash, if it is part of
+	/// the fork of this view and was not finalized when the view was built.
+	pub(crate) fn included(&self, block: &BlockHash<PoolApi>) -> Option<&[ExtrinsicHash<PoolApi>]> {
+		self.included.get(block).map(|(_, hashes)| &hashes[..])
+	}
+}
+
+/// The views of the pool at the fork leaves.
+pub(crate) struct ViewStore<PoolApi: ChainApi> {
+	views: HashMap<BlockHash<PoolApi>, Arc<View<PoolApi>>>,
+}
+
+impl<PoolApi: ChainApi> Default for ViewStore<PoolApi> {
+	fn default() -> Self {
+		Self { views: Default::default() }
+	}
+}
+
+impl<PoolApi> ViewStore<PoolApi>
+where
+	PoolApi: ChainApi + 'static,
+{
+	/// Returns the view at the given block, if any.
+	pub(crate) fn get(&self, at: &BlockHash<PoolApi>) -> Option<Arc<View<PoolApi>>> {
+		self.views.get(at).cloned()
+	}
+
+	/// Returns an iterator over all views.
+	pub(crate) fn iter(&self) -> impl Iterator<Item = &Arc<View<PoolApi>>> {
+		self.views.values()
+	}
+
+	/// Returns the number of views.
+	pub(crate) fn len(&self) -> usize {
+		self.views.len()
+	}
+
+	/// Returns the view closest to `at` along with the tree route from it to `at`.
+	///
+	/// Views further than [`MAX_VIEW_DISTANCE`] blocks away are not considered.
+	pub(crate) fn closest(
+		&self,
+		api: &PoolApi,
+		at: BlockHash<PoolApi>,
+	) -> Option<(Arc<View<PoolApi>>, TreeRoute<PoolApi::Block>)> {
+		self.views
+			.values()
+			.filter_map(|view| {
+				let tree_route = api.tree_route(view.at.hash, at).ok()?;
+				let distance = tree_route.retracted().len() + tree_route.enacted().len();
+				(distance <= MAX_VIEW_DISTANCE).then(|| (distance, view.clone(), tree_route))
+			})
+			.min_by_key(|(distance, _, _)| *distance)
+			.map(|(_, view, tree_route)| (view, tree_route))
+	}
+
+	/// Returns the view the transactions of the `finalized` block are taken from.
+	///
+	/// This is the view at the `finalized` block or, if there is none, the view at its closest
+	/// descendant.
+	pub(crate) fn finalized_view(
+		&self,
+		api: &PoolApi,
+		finalized: BlockHash<PoolApi>,
+	) -> Option<Arc<View<PoolApi>>> {
+		if let Some(view) = self.get(&finalized) {
+			return Some(view)
+		}
+
+		self.views
+			.values()
+			.filter(|view| {
+				api.tree_route(finalized, view.at.hash)
+					.map(|tree_route| tree_route.retracted().is_empty())
+					.unwrap_or(false)
+			})
+			.min_by_key(|view| view.at.number)
+			.cloned()
+	}
+
+	/// Inserts the view at a new leaf, unless there already is one, and returns the view stored
+	/// at the leaf.
+	///
+	/// The view at `replaced`, if any, is removed, as its block is not a leaf anymore. If there
+	/// are more than [`MAX_VIEWS`] views afterwards, the ones at the lowest blocks are removed.
+	fn insert(
+		&mut self,
+		view: View<PoolApi>,
+		replaced: Option<BlockHash<PoolApi>>,
+	) -> Arc<View<PoolApi>> {
+		if let Some(view) = self.get(&view.at.hash) {
+			return view
+		}
+
+		if let Some(replaced) = replaced {
+			self.views.remove(&replaced);
+		}
+		let view = Arc::new(view);
+		self.views.insert(view.at.hash, view.clone());
+
+		while self.views.len() > MAX_VIEWS {
+			let Some(lowest) = self
+				.views
+				.values()
+				.filter(|v| v.at.hash != view.at.hash)
+				.min_by_key(|v| v.at.number)
+				.map(|v| v.at.hash)
+			else {
+				break
+			};
+			log::trace!(target: LOG_TARGET, "[{:?}] Pruning view over the limit", lowest);
+			self.views.remove(&lowest);
+		}
+
+		view
+	}
+
+	/// Removes the given invalid transactions from all views.
+	pub(crate) fn remove_invalid(&self, hashes: &[ExtrinsicHash<PoolApi>]) {
+		for view in self.views.values() {
+			view.pool.validated_pool().remove_invalid(hashes);
+		}
+	}
+
+	/// Removes the views which are not descendants of the `finalized` block.
+	pub(crate) fn prune_finalized(
+		&mut self,
+		api: &PoolApi,
+		finalized: &HashAndNumber<PoolApi::Block>,
+	) {
+		self.views.retain(|hash, view| {
+			let keep = view.at.number >= finalized.number &&
+				api.tree_route(finalized.hash, *hash)
+					.map(|tree_route| tree_route.retracted().is_empty())
+					.unwrap_or(false);
+			if !keep {
+				log::trace!(target: LOG_TARGET, "[{:?}] Pruning view on finalization", hash);
+			}
+			keep
+		});
+	}
+}
+
+/// Returns the view at `at`, building it if there is none yet.
+///
+/// The view is derived from the closest existing view or, if there is none, built out of the
+/// transactions of the `main` pool.
+pub(crate) async fn update_view<PoolApi>(
+	api: Arc<PoolApi>,
+	options: graph::Options,
+	views: &Mutex<ViewStore<PoolApi>>,
+	main: &graph::Pool<PoolApi>,
+	finalized: BlockHash<PoolApi>,
+	at: HashAndNumber<PoolApi::Block>,
+) -> Arc<View<PoolApi>>
+where
+	PoolApi: ChainApi + 'static,
+{
+	if let Some(view) = views.lock().get(&at.hash) {
+		return view
+	}
+
+	let hash = at.hash;
+	let closest = views.lock().closest(&*api, hash);
+	let (view, replaced) = match closest {
+		Some((base, tree_route)) => {
+			let finalized = api
+				.block_id_to_number(&BlockId::Hash(finalized))
+				.ok()
+				.flatten()
+				.unwrap_or_else(Zero::zero);
+			let replaced = tree_route.retracted().is_empty().then_some(base.at.hash);
+			(View::derive(api, &base, &tree_route, finalized, at).await, replaced)
+		},
+		None => {
+			let xts = {
+				let pool = main.validated_pool().pool.read();
+				pool.ready()
+					.map(|tx| (tx.source, tx.data.clone()))
+					.chain(pool.futures().map(|tx| (tx.source, tx.data.clone())))
+					.collect::<Vec<_>>()
+			};
+			(View::build(api, options, finalized, at, xts).await, None)
+		},
+	};
+
+	let mut views = views.lock();
+	let view = views.insert(view, replaced);
+	log::debug!(target: LOG_TARGET, "[{:?}] Built view, {} views active", hash, views.len());
+	view
+}
@@ -1567,3 +1567,125 @@ fn best_block_after_finalization_does_not_retract() {
 		assert_eq!(stream.next(), None);
 	}
 }
+
+#[test]
+fn ready_at_block_returns_transactions_of_the_fork() {
+	sp_tracing::try_init_simple();
+	let api = TestApi::empty();
+	// starting block A1
+	let a_header = api.push_block(1, vec![], true);
+
+	let pool = create_basic_pool(api);
+
+	let xt = uxt(Alice, 0);
+	block_on(pool.submit_one(&BlockId::number(1), SOURCE, xt.clone())).expect("1. Imported");
+
+	// B1 includes the transaction, B2 doesn't
+	let b1_header = pool.api().push_block_with_parent(a_header.hash(), vec![xt.clone()], true);
+	let b2_header = pool.api().push_block_with_parent(a_header.hash(), vec![], true);
+
+	block_on(pool.maintain(ChainEvent::NewBestBlock { hash: b1_header.hash(), tree_route: None }));
+	block_on(pool.maintain(ChainEvent::NewBestBlock { hash: b2_header.hash(), tree_route: None }));
+
+	let ready_at = |header: &Header| {
+		block_on(pool.ready_at_block(header.hash(), header.number))
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>()
+	};
+	assert!(ready_at(&b1_header).is_empty());
+	assert_eq!(ready_at(&b2_header), vec![pool.hash_of(&xt)]);
+}
+
+#[test]
+fn views_are_derived_across_fork_switches() {
+	sp_tracing::try_init_simple();
+	let api = TestApi::empty();
+	// starting block A1
+	let a_header = api.push_block(1, vec![], true);
+
+	let pool = create_basic_pool(api);
+
+	let xt0 = uxt(Alice, 0);
+	let xt1 = uxt(Bob, 0);
+	block_on(pool.submit_at(&BlockId::number(1), SOURCE, vec![xt0.clone(), xt1.clone()]))
+		.expect("1. Imported");
+
+	// B1 includes the first transaction, B2 the second one
+	let b1_header = pool.api().push_block_with_parent(a_header.hash(), vec![xt0.clone()], true);
+	let b2_header = pool.api().push_block_with_parent(a_header.hash(), vec![xt1.clone()], true);
+
+	let ready_at = |header: &Header| {
+		block_on(pool.ready_at_block(header.hash(), header.number))
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>()
+	};
+
+	// the view at B2 is built on demand, although B2 never was the best block
+	block_on(pool.maintain(block_event(b1_header.clone())));
+	assert_eq!(ready_at(&b1_header), vec![pool.hash_of(&xt1)]);
+	assert_eq!(ready_at(&b2_header), vec![pool.hash_of(&xt0)]);
+
+	// switching to the fork of B2 keeps the view at B1
+	let c2_header = pool.api().push_block_with_parent(b2_header.hash(), vec![], true);
+	block_on(pool.maintain(block_event(c2_header.clone())));
+	assert_eq!(ready_at(&c2_header), vec![pool.hash_of(&xt0)]);
+	assert_eq!(ready_at(&b1_header), vec![pool.hash_of(&xt1)]);
+}
+
+#[test]
+fn submitted_transactions_are_validated_once_for_all_views() {
+	sp_tracing::try_init_simple();
+	let api = TestApi::empty();
+	// starting block A1
+	let a_header = api.push_block(1, vec![], true);
+
+	let pool = create_basic_pool(api);
+
+	// views at B1 and B2
+	let b1_header = pool.api().push_block_with_parent(a_header.hash(), vec![], true);
+	let b2_header = pool.api().push_block_with_parent(a_header.hash(), vec![], true);
+	block_on(pool.maintain(block_event(b1_header.clone())));
+	assert_eq!(block_on(pool.ready_at_block(b2_header.hash(), b2_header.number)).count(), 0);
+
+	let xt = uxt(Alice, 0);
+	block_on(pool.submit_one(&BlockId::number(1), SOURCE, xt.clone())).expect("1. Imported");
+
+	let ready_at = |header: &Header| {
+		block_on(pool.ready_at_block(header.hash(), header.number))
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(ready_at(&b1_header), vec![pool.hash_of(&xt)]);
+	assert_eq!(ready_at(&b2_header), vec![pool.hash_of(&xt)]);
+	assert_eq!(pool.api().validation_requests().iter().filter(|v| **v == xt).count(), 1);
+}
+
+#[test]
+fn finality_is_reported_once_for_the_fork_reaching_finality() {
+	sp_tracing::try_init_simple();
+	let api = TestApi::empty();
+	// starting block A1
+	let a_header = api.push_block(1, vec![], true);
+
+	let pool = create_basic_pool(api);
+
+	let xt = uxt(Alice, 0);
+	let watcher = block_on(pool.submit_and_watch(&BlockId::number(1), SOURCE, xt.clone()))
+		.expect("1. Imported");
+
+	// B1 includes the transaction but is never the best block
+	let b1_header = pool.api().push_block_with_parent(a_header.hash(), vec![xt.clone()], true);
+	let b2_header = pool.api().push_block_with_parent(a_header.hash(), vec![], true);
+	block_on(pool.maintain(block_event(b2_header.clone())));
+	assert_eq!(block_on(pool.ready_at_block(b1_header.hash(), b1_header.number)).count(), 0);
+
+	let event = ChainEvent::Finalized { hash: b1_header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+
+	let mut stream = futures::executor::block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b1_header.hash(), 0))));
+	assert_eq!(stream.next(), Some(TransactionStatus::Finalized((b1_header.hash(), 0))));
+	assert_eq!(stream.next(), None);
+	assert_eq!(pool.status().ready, 0);
+}
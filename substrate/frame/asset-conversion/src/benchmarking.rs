@@ -18,7 +18,7 @@
 //! Asset Conversion pallet benchmarking.
 
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{benchmarks, whitelisted_caller, BenchmarkError};
 use frame_support::{
 	assert_ok,
 	storage::bounded_vec::BoundedVec,
@@ -28,8 +28,12 @@ use frame_support::{
 	},
 };
 use frame_system::RawOrigin as SystemOrigin;
+use sp_arithmetic::Permill;
 use sp_core::Get;
-use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_runtime::{
+	traits::{Bounded, One, StaticLookup},
+	Saturating,
+};
 use sp_std::{ops::Div, prelude::*};
 
 use crate::Pallet as AssetConversion;
@@ -88,6 +92,106 @@ where
 	(lp_token, caller, caller_lookup)
 }
 
+/// Fills the price history of the pool of `asset1` and `asset2` and moves past its observation
+/// period, so the next update of its oracle accrues prices into an existing accumulator and
+/// rotates a new observation into a full ring.
+fn fill_price_oracle<T: Config>(asset1: &T::MultiAssetId, asset2: &T::MultiAssetId) {
+	let pool_id = AssetConversion::<T>::get_pool_id(asset1.clone(), asset2.clone());
+	let now = frame_system::Pallet::<T>::block_number();
+	let observation = PriceObservation { block: now, ..Default::default() };
+	let observations = vec![observation.clone(); T::MaxPriceObservations::get() as usize];
+	PriceAccumulators::<T>::insert(&pool_id, observation);
+	PriceObservations::<T>::insert(
+		&pool_id,
+		BoundedVec::try_from(observations).expect("as many observations as the bound; qed"),
+	);
+	frame_system::Pallet::<T>::set_block_number(
+		now.saturating_add(T::PriceObservationPeriod::get()).saturating_add(One::one()),
+	);
+}
+
+/// The amount of `asset` each pool of a swap path is seeded with.
+fn pool_liquidity<T: Config>(asset: &T::MultiAssetId) -> u128
+where
+	T::Balance: Into<u128>,
+{
+	if T::MultiAssetIdConverter::is_native(asset) {
+		let ed: u128 = T::Currency::minimum_balance().into();
+		1000 * ed
+	} else {
+		1_000_000
+	}
+}
+
+fn balance<T: Config>(asset: &T::MultiAssetId, who: &T::AccountId) -> u128
+where
+	T::AssetBalance: Into<u128>,
+	T::Balance: Into<u128>,
+{
+	match T::MultiAssetIdConverter::try_convert(asset) {
+		MultiAssetIdConversionResult::Converted(asset_id) =>
+			T::Assets::balance(asset_id, who).into(),
+		_ => T::Currency::balance(who).into(),
+	}
+}
+
+/// Creates a swap path of `n` assets, or the longest one there can be if shorter, with a pool
+/// holding some liquidity and a full price history between every two of them.
+///
+/// Without multi-asset pools every pool has to contain the native asset, so the longest path is
+/// `asset1-native-asset2`.
+fn create_swap_path<T: Config>(n: u32) -> (T::AccountId, Vec<T::MultiAssetId>)
+where
+	T::AssetBalance: From<u128>,
+	T::Currency: Unbalanced<T::AccountId>,
+	T::Balance: Into<u128>,
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let native = T::MultiAssetIdConverter::get_native();
+	let mut path = if T::AllowMultiAssetPools::get() {
+		sp_std::iter::once(native)
+			.chain((1..n).map(T::BenchmarkHelper::multiasset_id))
+			.collect::<Vec<_>>()
+	} else {
+		vec![T::BenchmarkHelper::multiasset_id(1), native, T::BenchmarkHelper::multiasset_id(2)]
+	};
+	path.truncate(n as usize);
+
+	let caller: T::AccountId = whitelisted_caller();
+	for asset in &path {
+		let (_, _) = create_asset::<T>(asset);
+	}
+	for assets_pair in path.windows(2) {
+		if let [asset1, asset2] = assets_pair {
+			assert_ok!(AssetConversion::<T>::create_pool(
+				SystemOrigin::Signed(caller.clone()).into(),
+				asset1.clone(),
+				asset2.clone()
+			));
+			assert_ok!(AssetConversion::<T>::add_liquidity(
+				SystemOrigin::Signed(caller.clone()).into(),
+				asset1.clone(),
+				asset2.clone(),
+				pool_liquidity::<T>(asset1).into(),
+				pool_liquidity::<T>(asset2).into(),
+				0.into(),
+				0.into(),
+				caller.clone(),
+			));
+			fill_price_oracle::<T>(asset1, asset2);
+		}
+	}
+	(caller, path)
+}
+
+fn max_fee_tiers<T: Config>() -> BoundedVec<Permill, T::MaxFeeTiers> {
+	(1..=T::MaxFeeTiers::get())
+		.map(Permill::from_parts)
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("as many fee tiers as the bound; qed")
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -119,6 +223,8 @@ benchmarks! {
 			pool_account: AssetConversion::<T>::get_pool_account(&pool_id),
 			pool_id,
 			lp_token,
+			fee: AssetConversion::<T>::default_fee(),
+			curve: PoolCurve::ConstantProduct,
 		}.into());
 	}
 
@@ -126,6 +232,7 @@ benchmarks! {
 		let asset1 = T::MultiAssetIdConverter::get_native();
 		let asset2 = T::BenchmarkHelper::multiasset_id(0);
 		let (lp_token, caller, _) = create_asset_and_pool::<T>(&asset1, &asset2);
+		fill_price_oracle::<T>(&asset1, &asset2);
 		let ed: u128 = T::Currency::minimum_balance().into();
 		let add_amount = 1000 + ed;
 	}: _(SystemOrigin::Signed(caller.clone()), asset1.clone(), asset2.clone(), add_amount.into(), 1000.into(), 0.into(), 0.into(), caller.clone())
@@ -165,6 +272,7 @@ benchmarks! {
 			0.into(),
 			caller.clone(),
 		)?;
+		fill_price_oracle::<T>(&asset1, &asset2);
 		let total_supply = <T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token.clone());
 	}: _(SystemOrigin::Signed(caller.clone()), asset1, asset2, remove_lp_amount.into(), 0.into(), 0.into(), caller.clone())
 	verify {
@@ -176,162 +284,61 @@ benchmarks! {
 	}
 
 	swap_exact_tokens_for_tokens {
-		let native = T::MultiAssetIdConverter::get_native();
-		let asset1 = T::BenchmarkHelper::multiasset_id(1);
-		let asset2 = T::BenchmarkHelper::multiasset_id(2);
-		let (_, caller, _) = create_asset_and_pool::<T>(&native, &asset1);
-		let (_, _) = create_asset::<T>(&asset2);
-		let ed: u128 = T::Currency::minimum_balance().into();
-
-		AssetConversion::<T>::add_liquidity(
-			SystemOrigin::Signed(caller.clone()).into(),
-			native.clone(),
-			asset1.clone(),
-			(100 * ed).into(),
-			200.into(),
-			0.into(),
-			0.into(),
-			caller.clone(),
-		)?;
-
-		let path;
-		let swap_amount;
-		// if we only allow the native-asset pools, then the worst case scenario would be to swap
-		// asset1-native-asset2
-		if !T::AllowMultiAssetPools::get() {
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), native.clone(), asset2.clone())?;
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				native.clone(),
-				asset2.clone(),
-				(500 * ed).into(),
-				1000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			path = vec![asset1.clone(), native.clone(), asset2.clone()];
-			swap_amount = 100.into();
-		} else {
-			let asset3 = T::BenchmarkHelper::multiasset_id(3);
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), asset1.clone(), asset2.clone())?;
-			let (_, _) = create_asset::<T>(&asset3);
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), asset2.clone(), asset3.clone())?;
-
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				asset1.clone(),
-				asset2.clone(),
-				200.into(),
-				2000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				asset2.clone(),
-				asset3.clone(),
-				2000.into(),
-				2000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			path = vec![native.clone(), asset1.clone(), asset2.clone(), asset3.clone()];
-			swap_amount = ed.into();
-		}
-
+		let n in 2 .. T::MaxSwapPathLength::get();
+		let (caller, path) = create_swap_path::<T>(n);
+		let asset_in = path.first().cloned().unwrap();
+		let swap_amount = pool_liquidity::<T>(&asset_in) / 1000;
+		let balance_in = balance::<T>(&asset_in, &caller);
 		let path: BoundedVec<_, T::MaxSwapPathLength> = BoundedVec::try_from(path).unwrap();
-		let native_balance = T::Currency::balance(&caller);
-		let asset1_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(1), &caller);
-	}: _(SystemOrigin::Signed(caller.clone()), path, swap_amount, 1.into(), caller.clone(), false)
+	}: _(SystemOrigin::Signed(caller.clone()), path, swap_amount.into(), 1.into(), caller.clone(), false)
 	verify {
-		if !T::AllowMultiAssetPools::get() {
-			let new_asset1_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(1), &caller);
-			assert_eq!(new_asset1_balance, asset1_balance - 100.into());
-		} else {
-			let new_native_balance = T::Currency::balance(&caller);
-			assert_eq!(new_native_balance, native_balance - ed.into());
-		}
+		assert_eq!(balance::<T>(&asset_in, &caller), balance_in - swap_amount);
 	}
 
 	swap_tokens_for_exact_tokens {
-		let native = T::MultiAssetIdConverter::get_native();
-		let asset1 = T::BenchmarkHelper::multiasset_id(1);
-		let asset2 = T::BenchmarkHelper::multiasset_id(2);
-		let (_, caller, _) = create_asset_and_pool::<T>(&native, &asset1);
-		let (_, _) = create_asset::<T>(&asset2);
-		let ed: u128 = T::Currency::minimum_balance().into();
-
-		AssetConversion::<T>::add_liquidity(
-			SystemOrigin::Signed(caller.clone()).into(),
-			native.clone(),
-			asset1.clone(),
-			(1000 * ed).into(),
-			500.into(),
-			0.into(),
-			0.into(),
-			caller.clone(),
-		)?;
-
-		let path;
-		// if we only allow the native-asset pools, then the worst case scenario would be to swap
-		// asset1-native-asset2
-		if !T::AllowMultiAssetPools::get() {
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), native.clone(), asset2.clone())?;
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				native.clone(),
-				asset2.clone(),
-				(500 * ed).into(),
-				1000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			path = vec![asset1.clone(), native.clone(), asset2.clone()];
-		} else {
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), asset1.clone(), asset2.clone())?;
-			let asset3 = T::BenchmarkHelper::multiasset_id(3);
-			let (_, _) = create_asset::<T>(&asset3);
-			AssetConversion::<T>::create_pool(SystemOrigin::Signed(caller.clone()).into(), asset2.clone(), asset3.clone())?;
+		let n in 2 .. T::MaxSwapPathLength::get();
+		let (caller, path) = create_swap_path::<T>(n);
+		let asset_in = path.first().cloned().unwrap();
+		let asset_out = path.last().cloned().unwrap();
+		let amount_in_max = pool_liquidity::<T>(&asset_in);
+		let swap_amount = pool_liquidity::<T>(&asset_out) / 1000;
+		let balance_out = balance::<T>(&asset_out, &caller);
+		let path: BoundedVec<_, T::MaxSwapPathLength> = BoundedVec::try_from(path).unwrap();
+	}: _(SystemOrigin::Signed(caller.clone()), path, swap_amount.into(), amount_in_max.into(), caller.clone(), false)
+	verify {
+		assert_eq!(balance::<T>(&asset_out, &caller), balance_out + swap_amount);
+	}
 
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				asset1.clone(),
-				asset2.clone(),
-				2000.into(),
-				2000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			AssetConversion::<T>::add_liquidity(
-				SystemOrigin::Signed(caller.clone()).into(),
-				asset2.clone(),
-				asset3.clone(),
-				2000.into(),
-				2000.into(),
-				0.into(),
-				0.into(),
-				caller.clone(),
-			)?;
-			path = vec![native.clone(), asset1.clone(), asset2.clone(), asset3.clone()];
-		}
+	create_custom_pool {
+		let asset1 = T::MultiAssetIdConverter::get_native();
+		let asset2 = T::BenchmarkHelper::multiasset_id(0);
+		let (caller, _) = create_asset::<T>(&asset2);
+		// the fee is the last of the most tiers there can be.
+		let fee_tiers = max_fee_tiers::<T>();
+		let fee = fee_tiers.last().copied().unwrap_or_else(AssetConversion::<T>::default_fee);
+		FeeTiers::<T>::put(fee_tiers);
+		let curve = PoolCurve::StableSwap { amplification: MAX_AMPLIFICATION };
+	}: _(SystemOrigin::Signed(caller.clone()), asset1.clone(), asset2.clone(), fee, curve)
+	verify {
+		let lp_token = get_lp_token_id::<T>();
+		let pool_id = (asset1.clone(), asset2.clone());
+		assert_last_event::<T>(Event::PoolCreated {
+			creator: caller.clone(),
+			pool_account: AssetConversion::<T>::get_pool_account(&pool_id),
+			pool_id,
+			lp_token,
+			fee,
+			curve,
+		}.into());
+	}
 
-		let path: BoundedVec<_, T::MaxSwapPathLength> = BoundedVec::try_from(path).unwrap();
-		let asset2_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(2), &caller);
-		let asset3_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(3), &caller);
-	}: _(SystemOrigin::Signed(caller.clone()), path.clone(), 100.into(), (1000 * ed).into(), caller.clone(), false)
+	set_fee_tiers {
+		let origin =
+			T::FeeTiersOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let fee_tiers = max_fee_tiers::<T>();
+	}: _<T::RuntimeOrigin>(origin, fee_tiers.clone())
 	verify {
-		if !T::AllowMultiAssetPools::get() {
-			let new_asset2_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(2), &caller);
-			assert_eq!(new_asset2_balance, asset2_balance + 100.into());
-		} else {
-			let new_asset3_balance = T::Assets::balance(T::BenchmarkHelper::asset_id(3), &caller);
-			assert_eq!(new_asset3_balance, asset3_balance + 100.into());
-		}
+		assert_last_event::<T>(Event::FeeTiersSet { fee_tiers }.into());
 	}
 
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
@@ -24,6 +24,8 @@
 //! This pallet allows you to:
 //!
 //!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool with a custom fee tier and curve](`Pallet::create_custom_pool()`),
+//!    e.g. a StableSwap pool for two stablecoins
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -51,6 +53,24 @@
 //! http://localhost:9933/
 //! ```
 //! (This can be run against the kitchen sync node in the `node` folder of this repo.)
+//!
+//! ## Fee tiers and curves
+//!
+//! Pools created with [`Pallet::create_pool()`] charge the default [`Config::LPFee`] and price
+//! swaps against the constant product curve. [`Pallet::create_custom_pool()`] lets the creator pick
+//! any fee from the governance approved [`FeeTiers`] instead, and optionally the
+//! [StableSwap](https://curve.fi/files/stableswap-paper.pdf) curve. Both are fixed for the lifetime
+//! of the pool, and swaps, quotes and multi-hop routes price every hop with the fee and curve of
+//! the pool it goes through.
+//!
+//! ## Price oracle
+//!
+//! Every pool accumulates its spot prices, weighted by the number of blocks they held for, right
+//! before its reserves change. Snapshots of the accumulators are kept for the last
+//! [`Config::MaxPriceObservations`] periods of [`Config::PriceObservationPeriod`] blocks, so other
+//! pallets can read a manipulation resistant
+//! [time weighted average price](`PriceOracle::time_weighted_average_price`) over any window they
+//! cover, as can clients via [`AssetConversionApi::time_weighted_average_price`].
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::traits::{DefensiveOption, Incrementable};
@@ -58,6 +78,8 @@ use frame_support::traits::{DefensiveOption, Incrementable};
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migration;
+mod oracle;
 mod types;
 pub mod weights;
 
@@ -88,6 +110,11 @@ use sp_std::prelude::*;
 pub use types::*;
 pub use weights::WeightInfo;
 
+const LOG_TARGET: &str = "runtime::asset-conversion";
+
+/// The maximum number of Newton's method iterations when solving the StableSwap invariant.
+const STABLE_SWAP_MAX_ITERATIONS: u32 = 255;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -105,13 +132,17 @@ pub mod pallet {
 		},
 		BoundedBTreeSet, PalletId,
 	};
-	use sp_arithmetic::Permill;
+	use sp_arithmetic::{PerThing, Permill};
 	use sp_runtime::{
 		traits::{IntegerSquareRoot, One, Zero},
 		Saturating,
 	};
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -133,6 +164,7 @@ pub mod pallet {
 		type HigherPrecisionBalance: IntegerSquareRoot
 			+ One
 			+ Ensure
+			+ Clone
 			+ Unsigned
 			+ From<u32>
 			+ From<Self::AssetBalance>
@@ -172,9 +204,28 @@ pub mod pallet {
 			+ AccountTouch<Self::PoolAssetId, Self::AccountId>;
 
 		/// A % the liquidity providers will take of every swap. Represents 10ths of a percent.
+		///
+		/// This is the fee of pools created with [`Pallet::create_pool`], which is always allowed
+		/// in addition to the [`FeeTiers`].
 		#[pallet::constant]
 		type LPFee: Get<u32>;
 
+		/// The origin that can set the [`FeeTiers`] pools may be created with.
+		type FeeTiersOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of [`FeeTiers`].
+		#[pallet::constant]
+		type MaxFeeTiers: Get<u32>;
+
+		/// The maximum number of [`PriceObservations`] kept per pool, which bounds the longest
+		/// window a time weighted average price can be read over.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
+		/// The minimum number of blocks between two [`PriceObservations`] of a pool.
+		#[pallet::constant]
+		type PriceObservationPeriod: Get<BlockNumberFor<Self>>;
+
 		/// A one-time fee to setup the pool.
 		#[pallet::constant]
 		type PoolSetupFee: Get<Self::Balance>;
@@ -221,6 +272,31 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+	/// The cumulative prices of every pool, as of the last time its reserves changed.
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		PoolIdOf<T>,
+		PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Snapshots of the cumulative prices of every pool, oldest first, at most one per
+	/// `PriceObservationPeriod`.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		PoolIdOf<T>,
+		BoundedVec<PriceObservation<BlockNumberFor<T>>, T::MaxPriceObservations>,
+		ValueQuery,
+	>;
+
+	/// The fees, besides the default `LPFee`, that new pools may be created with.
+	#[pallet::storage]
+	pub type FeeTiers<T: Config> = StorageValue<_, BoundedVec<Permill, T::MaxFeeTiers>, ValueQuery>;
+
 	// Pallet's events.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -237,6 +313,10 @@ pub mod pallet {
 			/// The id of the liquidity tokens that will be minted when assets are added to this
 			/// pool.
 			lp_token: T::PoolAssetId,
+			/// The share of every swap the liquidity providers of the pool take.
+			fee: Permill,
+			/// The invariant the pool prices its swaps against.
+			curve: PoolCurve,
 		},
 
 		/// A successful call of the `AddLiquidity` extrinsic will create this event.
@@ -302,6 +382,11 @@ pub mod pallet {
 			/// The amount of the asset that was transferred.
 			amount: T::AssetBalance,
 		},
+		/// The fee tiers new pools may be created with have been set.
+		FeeTiersSet {
+			/// The new fee tiers.
+			fee_tiers: BoundedVec<Permill, T::MaxFeeTiers>,
+		},
 	}
 
 	#[pallet::error]
@@ -365,6 +450,14 @@ pub mod pallet {
 		/// with another. For example, an array of assets constituting a `path` should have a
 		/// corresponding array of `amounts` along the path.
 		CorrespondenceError,
+		/// The fee is neither the default `LPFee` nor one of the `FeeTiers`.
+		FeeTierNotAllowed,
+		/// A fee tier must be less than 100%.
+		InvalidFeeTier,
+		/// The curve parameters are out of bounds.
+		InvalidCurve,
+		/// The StableSwap invariant did not converge.
+		CurveDidNotConverge,
 	}
 
 	#[pallet::hooks]
@@ -374,6 +467,7 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(T::LPFee::get() < 1000, "the `LPFee` should be less than 100%");
 		}
 	}
 
@@ -392,65 +486,13 @@ pub mod pallet {
 			asset2: T::MultiAssetId,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::EqualAssets);
-
-			// prepare pool_id
-			let pool_id = Self::get_pool_id(asset1, asset2);
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-			let (asset1, asset2) = &pool_id;
-			if !T::AllowMultiAssetPools::get() && !T::MultiAssetIdConverter::is_native(asset1) {
-				Err(Error::<T>::PoolMustContainNativeCurrency)?;
-			}
-
-			let pool_account = Self::get_pool_account(&pool_id);
-			frame_system::Pallet::<T>::inc_providers(&pool_account);
-
-			// pay the setup fee
-			T::Currency::transfer(
-				&sender,
-				&T::PoolSetupFeeReceiver::get(),
-				T::PoolSetupFee::get(),
-				Preserve,
-			)?;
-
-			// try to convert both assets
-			match T::MultiAssetIdConverter::try_convert(asset1) {
-				MultiAssetIdConversionResult::Converted(asset) =>
-					if !T::Assets::contains(&asset, &pool_account) {
-						T::Assets::touch(asset, pool_account.clone(), sender.clone())?
-					},
-				MultiAssetIdConversionResult::Unsupported(_) => Err(Error::<T>::UnsupportedAsset)?,
-				MultiAssetIdConversionResult::Native => (),
-			}
-			match T::MultiAssetIdConverter::try_convert(asset2) {
-				MultiAssetIdConversionResult::Converted(asset) =>
-					if !T::Assets::contains(&asset, &pool_account) {
-						T::Assets::touch(asset, pool_account.clone(), sender.clone())?
-					},
-				MultiAssetIdConversionResult::Unsupported(_) => Err(Error::<T>::UnsupportedAsset)?,
-				MultiAssetIdConversionResult::Native => (),
-			}
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			T::PoolAssets::touch(lp_token.clone(), pool_account.clone(), sender.clone())?;
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
-
-			Ok(())
+			Self::do_create_pool(
+				sender,
+				asset1,
+				asset2,
+				Self::default_fee(),
+				PoolCurve::ConstantProduct,
+			)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -491,6 +533,7 @@ pub mod pallet {
 
 			let maybe_pool = Pools::<T>::get(&pool_id);
 			let pool = maybe_pool.as_ref().ok_or(Error::<T>::PoolNotFound)?;
+			Self::update_price_oracle(&pool_id, pool.curve);
 			let pool_account = Self::get_pool_account(&pool_id);
 
 			let (asset1, asset2) = &pool_id;
@@ -600,6 +643,7 @@ pub mod pallet {
 
 			let maybe_pool = Pools::<T>::get(&pool_id);
 			let pool = maybe_pool.as_ref().ok_or(Error::<T>::PoolNotFound)?;
+			Self::update_price_oracle(&pool_id, pool.curve);
 
 			let pool_account = Self::get_pool_account(&pool_id);
 			let reserve1 = Self::get_balance(&pool_account, &asset1)?;
@@ -654,7 +698,7 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens())]
+		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>,
@@ -682,7 +726,7 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens())]
+		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>,
@@ -702,9 +746,123 @@ pub mod pallet {
 			)?;
 			Ok(())
 		}
+
+		/// Creates an empty liquidity pool like [`Pallet::create_pool`], but charging `fee` on
+		/// every swap and pricing swaps against `curve`.
+		///
+		/// `fee` must be either the default `LPFee` or one of the [`FeeTiers`]. Neither can be
+		/// changed once the pool exists.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_custom_pool())]
+		pub fn create_custom_pool(
+			origin: OriginFor<T>,
+			asset1: T::MultiAssetId,
+			asset2: T::MultiAssetId,
+			fee: Permill,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, asset1, asset2, fee, curve)
+		}
+
+		/// Set the fees, besides the default `LPFee`, that new pools may be created with.
+		///
+		/// Pools that already exist keep their fee.
+		///
+		/// Must be called by `FeeTiersOrigin`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_fee_tiers())]
+		pub fn set_fee_tiers(
+			origin: OriginFor<T>,
+			fee_tiers: BoundedVec<Permill, T::MaxFeeTiers>,
+		) -> DispatchResult {
+			T::FeeTiersOrigin::ensure_origin(origin)?;
+			ensure!(fee_tiers.iter().all(|fee| *fee < Permill::one()), Error::<T>::InvalidFeeTier);
+
+			FeeTiers::<T>::put(&fee_tiers);
+			Self::deposit_event(Event::FeeTiersSet { fee_tiers });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Create an empty pool of `asset1` and `asset2` on behalf of `sender`, charging `fee` on
+		/// every swap and pricing swaps against `curve`.
+		pub(crate) fn do_create_pool(
+			sender: T::AccountId,
+			asset1: T::MultiAssetId,
+			asset2: T::MultiAssetId,
+			fee: Permill,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::EqualAssets);
+			ensure!(
+				fee == Self::default_fee() || FeeTiers::<T>::get().contains(&fee),
+				Error::<T>::FeeTierNotAllowed
+			);
+			ensure!(curve.is_valid(), Error::<T>::InvalidCurve);
+
+			// prepare pool_id
+			let pool_id = Self::get_pool_id(asset1, asset2);
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+			let (asset1, asset2) = &pool_id;
+			if !T::AllowMultiAssetPools::get() && !T::MultiAssetIdConverter::is_native(asset1) {
+				Err(Error::<T>::PoolMustContainNativeCurrency)?;
+			}
+
+			let pool_account = Self::get_pool_account(&pool_id);
+			frame_system::Pallet::<T>::inc_providers(&pool_account);
+
+			// pay the setup fee
+			T::Currency::transfer(
+				&sender,
+				&T::PoolSetupFeeReceiver::get(),
+				T::PoolSetupFee::get(),
+				Preserve,
+			)?;
+
+			// try to convert both assets
+			match T::MultiAssetIdConverter::try_convert(asset1) {
+				MultiAssetIdConversionResult::Converted(asset) =>
+					if !T::Assets::contains(&asset, &pool_account) {
+						T::Assets::touch(asset, pool_account.clone(), sender.clone())?
+					},
+				MultiAssetIdConversionResult::Unsupported(_) => Err(Error::<T>::UnsupportedAsset)?,
+				MultiAssetIdConversionResult::Native => (),
+			}
+			match T::MultiAssetIdConverter::try_convert(asset2) {
+				MultiAssetIdConversionResult::Converted(asset) =>
+					if !T::Assets::contains(&asset, &pool_account) {
+						T::Assets::touch(asset, pool_account.clone(), sender.clone())?
+					},
+				MultiAssetIdConversionResult::Unsupported(_) => Err(Error::<T>::UnsupportedAsset)?,
+				MultiAssetIdConversionResult::Native => (),
+			}
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			T::PoolAssets::touch(lp_token.clone(), pool_account.clone(), sender.clone())?;
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone(), fee, curve };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id,
+				pool_account,
+				lp_token,
+				fee,
+				curve,
+			});
+
+			Ok(())
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -854,6 +1012,16 @@ pub mod pallet {
 			keep_alive: bool,
 		) -> Result<(), DispatchError> {
 			ensure!(amounts.len() > 1, Error::<T>::CorrespondenceError);
+			// bring the price oracles up to date before the reserves of any pool change.
+			for assets_pair in path.windows(2) {
+				if let [asset1, asset2] = assets_pair {
+					let pool = Self::get_pool_info(asset1, asset2)?;
+					Self::update_price_oracle(
+						&Self::get_pool_id(asset1.clone(), asset2.clone()),
+						pool.curve,
+					);
+				}
+			}
 			if let Some([asset1, asset2]) = &path.get(0..2) {
 				let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
 				let pool_account = Self::get_pool_account(&pool_id);
@@ -977,6 +1145,20 @@ pub mod pallet {
 			Ok((balance1, balance2))
 		}
 
+		/// The fee of pools created without an explicit fee tier.
+		pub fn default_fee() -> Permill {
+			Permill::from_perthousand(T::LPFee::get())
+		}
+
+		/// Returns the info of the pool of `asset1` and `asset2`.
+		pub(crate) fn get_pool_info(
+			asset1: &T::MultiAssetId,
+			asset2: &T::MultiAssetId,
+		) -> Result<PoolInfo<T::PoolAssetId>, Error<T>> {
+			Pools::<T>::get(Self::get_pool_id(asset1.clone(), asset2.clone()))
+				.ok_or(Error::<T>::PoolNotFound)
+		}
+
 		/// Leading to an amount at the end of a `path`, get the required amounts in.
 		pub(crate) fn get_amounts_in(
 			amount_out: &T::AssetBalance,
@@ -986,9 +1168,16 @@ pub mod pallet {
 
 			for assets_pair in path.windows(2).rev() {
 				if let [asset1, asset2] = assets_pair {
+					let pool = Self::get_pool_info(asset1, asset2)?;
 					let (reserve_in, reserve_out) = Self::get_reserves(asset1, asset2)?;
 					let prev_amount = amounts.last().expect("Always has at least one element");
-					let amount_in = Self::get_amount_in(prev_amount, &reserve_in, &reserve_out)?;
+					let amount_in = Self::get_amount_in(
+						prev_amount,
+						&reserve_in,
+						&reserve_out,
+						pool.fee,
+						pool.curve,
+					)?;
 					amounts.push(amount_in);
 				}
 			}
@@ -1006,9 +1195,16 @@ pub mod pallet {
 
 			for assets_pair in path.windows(2) {
 				if let [asset1, asset2] = assets_pair {
+					let pool = Self::get_pool_info(asset1, asset2)?;
 					let (reserve_in, reserve_out) = Self::get_reserves(asset1, asset2)?;
 					let prev_amount = amounts.last().expect("Always has at least one element");
-					let amount_out = Self::get_amount_out(prev_amount, &reserve_in, &reserve_out)?;
+					let amount_out = Self::get_amount_out(
+						prev_amount,
+						&reserve_in,
+						&reserve_out,
+						pool.fee,
+						pool.curve,
+					)?;
 					amounts.push(amount_out);
 				}
 			}
@@ -1017,22 +1213,30 @@ pub mod pallet {
 		}
 
 		/// Used by the RPC service to provide current prices.
+		///
+		/// Without `include_fee`, constant product pools quote the spot price, while StableSwap
+		/// pools quote a swap along their curve with no fee charged.
 		pub fn quote_price_exact_tokens_for_tokens(
 			asset1: T::MultiAssetId,
 			asset2: T::MultiAssetId,
 			amount: T::AssetBalance,
 			include_fee: bool,
 		) -> Option<T::AssetBalance> {
+			let pool = Self::get_pool_info(&asset1, &asset2).ok()?;
 			let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
 			let pool_account = Self::get_pool_account(&pool_id);
 
 			let balance1 = Self::get_balance(&pool_account, &asset1).ok()?;
 			let balance2 = Self::get_balance(&pool_account, &asset2).ok()?;
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+				match (include_fee, pool.curve) {
+					(true, curve) =>
+						Self::get_amount_out(&amount, &balance1, &balance2, pool.fee, curve).ok(),
+					(false, PoolCurve::ConstantProduct) =>
+						Self::quote(&amount, &balance1, &balance2).ok(),
+					(false, curve) =>
+						Self::get_amount_out(&amount, &balance1, &balance2, Zero::zero(), curve)
+							.ok(),
 				}
 			} else {
 				None
@@ -1040,22 +1244,30 @@ pub mod pallet {
 		}
 
 		/// Used by the RPC service to provide current prices.
+		///
+		/// Without `include_fee`, constant product pools quote the spot price, while StableSwap
+		/// pools quote a swap along their curve with no fee charged.
 		pub fn quote_price_tokens_for_exact_tokens(
 			asset1: T::MultiAssetId,
 			asset2: T::MultiAssetId,
 			amount: T::AssetBalance,
 			include_fee: bool,
 		) -> Option<T::AssetBalance> {
+			let pool = Self::get_pool_info(&asset1, &asset2).ok()?;
 			let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
 			let pool_account = Self::get_pool_account(&pool_id);
 
 			let balance1 = Self::get_balance(&pool_account, &asset1).ok()?;
 			let balance2 = Self::get_balance(&pool_account, &asset2).ok()?;
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+				match (include_fee, pool.curve) {
+					(true, curve) =>
+						Self::get_amount_in(&amount, &balance1, &balance2, pool.fee, curve).ok(),
+					(false, PoolCurve::ConstantProduct) =>
+						Self::quote(&amount, &balance2, &balance1).ok(),
+					(false, curve) =>
+						Self::get_amount_in(&amount, &balance1, &balance2, Zero::zero(), curve)
+							.ok(),
 				}
 			} else {
 				None
@@ -1110,11 +1322,13 @@ pub mod pallet {
 		/// Calculates amount out.
 		///
 		/// Given an input amount of an asset and pair reserves, returns the maximum output amount
-		/// of the other asset.
+		/// of the other asset for a pool charging `fee` and pricing against `curve`.
 		pub fn get_amount_out(
 			amount_in: &T::AssetBalance,
 			reserve_in: &T::AssetBalance,
 			reserve_out: &T::AssetBalance,
+			fee: Permill,
+			curve: PoolCurve,
 		) -> Result<T::AssetBalance, Error<T>> {
 			let amount_in = T::HigherPrecisionBalance::from(*amount_in);
 			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
@@ -1124,20 +1338,44 @@ pub mod pallet {
 				return Err(Error::<T>::ZeroLiquidity.into())
 			}
 
-			let amount_in_with_fee = amount_in
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - (T::LPFee::get().into())))
-				.ok_or(Error::<T>::Overflow)?;
+			let amount_in_with_fee =
+				amount_in.checked_mul(&Self::fee_complement(fee)).ok_or(Error::<T>::Overflow)?;
 
-			let numerator =
-				amount_in_with_fee.checked_mul(&reserve_out).ok_or(Error::<T>::Overflow)?;
+			let result = match curve {
+				PoolCurve::ConstantProduct => {
+					let numerator =
+						amount_in_with_fee.checked_mul(&reserve_out).ok_or(Error::<T>::Overflow)?;
 
-			let denominator = reserve_in
-				.checked_mul(&1000u32.into())
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&amount_in_with_fee)
-				.ok_or(Error::<T>::Overflow)?;
+					let denominator = reserve_in
+						.checked_mul(&Self::fee_denominator())
+						.ok_or(Error::<T>::Overflow)?
+						.checked_add(&amount_in_with_fee)
+						.ok_or(Error::<T>::Overflow)?;
 
-			let result = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+					numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?
+				},
+				PoolCurve::StableSwap { amplification } => {
+					let invariant =
+						Self::get_stable_swap_invariant(&reserve_in, &reserve_out, amplification)?;
+
+					let new_reserve_in = amount_in_with_fee
+						.checked_div(&Self::fee_denominator())
+						.ok_or(Error::<T>::Overflow)?
+						.checked_add(&reserve_in)
+						.ok_or(Error::<T>::Overflow)?;
+					let new_reserve_out =
+						Self::get_stable_swap_reserve(&new_reserve_in, &invariant, amplification)?;
+
+					// round down by one unit in favour of the pool.
+					let new_reserve_out =
+						new_reserve_out.checked_add(&One::one()).ok_or(Error::<T>::Overflow)?;
+					if new_reserve_out >= reserve_out {
+						Zero::zero()
+					} else {
+						reserve_out.checked_sub(&new_reserve_out).ok_or(Error::<T>::Overflow)?
+					}
+				},
+			};
 
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
@@ -1145,11 +1383,13 @@ pub mod pallet {
 		/// Calculates amount in.
 		///
 		/// Given an output amount of an asset and pair reserves, returns a required input amount
-		/// of the other asset.
+		/// of the other asset for a pool charging `fee` and pricing against `curve`.
 		pub fn get_amount_in(
 			amount_out: &T::AssetBalance,
 			reserve_in: &T::AssetBalance,
 			reserve_out: &T::AssetBalance,
+			fee: Permill,
+			curve: PoolCurve,
 		) -> Result<T::AssetBalance, Error<T>> {
 			let amount_out = T::HigherPrecisionBalance::from(*amount_out);
 			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
@@ -1163,25 +1403,183 @@ pub mod pallet {
 				Err(Error::<T>::AmountOutTooHigh.into())?
 			}
 
-			let numerator = reserve_in
-				.checked_mul(&amount_out)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&1000u32.into())
+			let result = match curve {
+				PoolCurve::ConstantProduct => {
+					let numerator = reserve_in
+						.checked_mul(&amount_out)
+						.ok_or(Error::<T>::Overflow)?
+						.checked_mul(&Self::fee_denominator())
+						.ok_or(Error::<T>::Overflow)?;
+
+					let denominator = reserve_out
+						.checked_sub(&amount_out)
+						.ok_or(Error::<T>::Overflow)?
+						.checked_mul(&Self::fee_complement(fee))
+						.ok_or(Error::<T>::Overflow)?;
+
+					numerator
+						.checked_div(&denominator)
+						.ok_or(Error::<T>::Overflow)?
+						.checked_add(&One::one())
+						.ok_or(Error::<T>::Overflow)?
+				},
+				PoolCurve::StableSwap { amplification } => {
+					let invariant =
+						Self::get_stable_swap_invariant(&reserve_in, &reserve_out, amplification)?;
+
+					let new_reserve_out =
+						reserve_out.checked_sub(&amount_out).ok_or(Error::<T>::Overflow)?;
+					let new_reserve_in =
+						Self::get_stable_swap_reserve(&new_reserve_out, &invariant, amplification)?;
+
+					// round up by one unit in favour of the pool, both before and after the fee.
+					let amount_in_with_fee = new_reserve_in
+						.checked_sub(&reserve_in)
+						.ok_or(Error::<T>::Overflow)?
+						.checked_add(&One::one())
+						.ok_or(Error::<T>::Overflow)?;
+
+					amount_in_with_fee
+						.checked_mul(&Self::fee_denominator())
+						.ok_or(Error::<T>::Overflow)?
+						.checked_div(&Self::fee_complement(fee))
+						.ok_or(Error::<T>::Overflow)?
+						.checked_add(&One::one())
+						.ok_or(Error::<T>::Overflow)?
+				},
+			};
+
+			result.try_into().map_err(|_| Error::<T>::Overflow)
+		}
+
+		/// The denominator fees are expressed over, i.e. the parts of a `Permill`.
+		fn fee_denominator() -> T::HigherPrecisionBalance {
+			Permill::ACCURACY.into()
+		}
+
+		/// The share of a swap left after the `fee`, over [`Self::fee_denominator`].
+		fn fee_complement(fee: Permill) -> T::HigherPrecisionBalance {
+			Permill::one().saturating_sub(fee).deconstruct().into()
+		}
+
+		/// Calculates the StableSwap invariant `D` of a pool with reserves `x` and `y`.
+		///
+		/// `D` is the solution of `4A(x + y) + D = 4AD + D^3 / 4xy`, found with Newton's method.
+		pub(crate) fn get_stable_swap_invariant(
+			x: &T::HigherPrecisionBalance,
+			y: &T::HigherPrecisionBalance,
+			amplification: u32,
+		) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			let two = T::HigherPrecisionBalance::from(2u32);
+			let ann = T::HigherPrecisionBalance::from(amplification)
+				.checked_mul(&4u32.into())
+				.ok_or(Error::<T>::Overflow)?;
+			let sum = x.checked_add(y).ok_or(Error::<T>::Overflow)?;
+			let double_x = x.checked_mul(&two).ok_or(Error::<T>::Overflow)?;
+			let double_y = y.checked_mul(&two).ok_or(Error::<T>::Overflow)?;
+
+			let mut d = sum.clone();
+			for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+				// d_p = d^3 / 4xy
+				let d_p = d
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_div(&double_x)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_div(&double_y)
+					.ok_or(Error::<T>::Overflow)?;
+
+				// d = (ann * sum + 2 * d_p) * d / ((ann - 1) * d + 3 * d_p)
+				let numerator = ann
+					.checked_mul(&sum)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&d_p.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?;
+				let denominator = ann
+					.checked_sub(&One::one())
+					.ok_or(Error::<T>::Overflow)?
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&d_p.checked_mul(&3u32.into()).ok_or(Error::<T>::Overflow)?)
+					.ok_or(Error::<T>::Overflow)?;
+				let next_d = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+
+				if Self::differ_by_at_most_one(&next_d, &d) {
+					return Ok(next_d)
+				}
+				d = next_d;
+			}
+			Err(Error::<T>::CurveDidNotConverge)
+		}
+
+		/// Calculates the reserve `y` that keeps the StableSwap invariant at `d` given the other
+		/// reserve `x`, found with Newton's method.
+		fn get_stable_swap_reserve(
+			x: &T::HigherPrecisionBalance,
+			d: &T::HigherPrecisionBalance,
+			amplification: u32,
+		) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			let two = T::HigherPrecisionBalance::from(2u32);
+			let ann = T::HigherPrecisionBalance::from(amplification)
+				.checked_mul(&4u32.into())
 				.ok_or(Error::<T>::Overflow)?;
 
-			let denominator = reserve_out
-				.checked_sub(&amount_out)
+			// c = d^3 / 4x * ann
+			let c = d
+				.checked_mul(d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&x.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
 				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - T::LPFee::get().into()))
+				.checked_mul(d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&ann.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
 				.ok_or(Error::<T>::Overflow)?;
-
-			let result = numerator
-				.checked_div(&denominator)
+			// b = x + d / ann
+			let b = d
+				.checked_div(&ann)
 				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&One::one())
+				.checked_add(x)
 				.ok_or(Error::<T>::Overflow)?;
 
-			result.try_into().map_err(|_| Error::<T>::Overflow)
+			let mut y = d.clone();
+			for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+				// y = (y^2 + c) / (2y + b - d)
+				let numerator = y
+					.checked_mul(&y)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&c)
+					.ok_or(Error::<T>::Overflow)?;
+				let denominator = y
+					.checked_mul(&two)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&b)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_sub(d)
+					.ok_or(Error::<T>::Overflow)?;
+				let next_y = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+
+				if Self::differ_by_at_most_one(&next_y, &y) {
+					return Ok(next_y)
+				}
+				y = next_y;
+			}
+			Err(Error::<T>::CurveDidNotConverge)
+		}
+
+		/// Whether `a` and `b` are at most one unit apart, the precision Newton's method converges
+		/// to.
+		fn differ_by_at_most_one(
+			a: &T::HigherPrecisionBalance,
+			b: &T::HigherPrecisionBalance,
+		) -> bool {
+			match if a > b { a.checked_sub(b) } else { b.checked_sub(a) } {
+				Some(diff) => diff <= One::one(),
+				None => false,
+			}
 		}
 
 		/// Ensure that a `value` meets the minimum balance requirements of an `asset` class.
@@ -1285,6 +1683,7 @@ impl<T: Config> Swap<T::AccountId, T::HigherPrecisionBalance, T::MultiAssetId> f
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
+	#[api_version(2)]
 	pub trait AssetConversionApi<Balance, AssetBalance, AssetId> where
 		Balance: Codec + MaybeDisplay,
 		AssetBalance: frame_support::traits::tokens::Balance,
@@ -1304,6 +1703,11 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Returns the price of `base` in units of `quote`, averaged over at least the last
+		/// `window` blocks, or `None` if the pool has no price history that far back.
+		#[api_version(2)]
+		fn time_weighted_average_price(base: AssetId, quote: AssetId, window: sp_runtime::traits::NumberFor<Block>) -> Option<sp_arithmetic::FixedU128>;
 	}
 }
 
@@ -27,7 +27,7 @@ use frame_support::{
 	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64},
 	PalletId,
 };
-use frame_system::{EnsureSigned, EnsureSignedBy};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
 use sp_arithmetic::Permill;
 use sp_core::H256;
 use sp_runtime::{
@@ -106,6 +106,10 @@ impl pallet_assets::Config<Instance1> for Test {
 	type ApprovalDeposit = ConstU128<1>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type WeightInfo = ();
 	type CallbackHandle = ();
@@ -131,6 +135,10 @@ impl pallet_assets::Config<Instance2> for Test {
 	type ApprovalDeposit = ConstU128<0>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type WeightInfo = ();
 	type CallbackHandle = ();
@@ -160,6 +168,10 @@ impl Config for Test {
 	type PalletId = AssetConversionPalletId;
 	type WeightInfo = ();
 	type LPFee = ConstU32<3>; // means 0.3%
+	type FeeTiersOrigin = EnsureRoot<u128>;
+	type MaxFeeTiers = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<3>;
+	type PriceObservationPeriod = ConstU64<5>;
 	type PoolSetupFee = ConstU128<100>; // should be more or equal to the existential deposit
 	type PoolSetupFeeReceiver = AssetConversionOrigin;
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
@@ -0,0 +1,151 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time weighted average prices of the pools, in the manner of Uniswap V2.
+//!
+//! Every pool keeps a [`PriceObservation`] accumulating its spot prices, weighted by the number of
+//! blocks they held for. The accumulator is brought up to date right before the reserves of the
+//! pool change, i.e. on swaps and liquidity changes, so a price only starts counting from the
+//! block after it was set. Snapshots of the accumulator are kept in a bounded ring, at most one
+//! per `PriceObservationPeriod`, and the average price over a window is the difference between
+//! the current accumulator and the snapshot at the start of the window, over the blocks between
+//! them.
+
+use super::*;
+use frame_support::traits::Get;
+use sp_arithmetic::{traits::Zero, FixedPointNumber, FixedU128};
+use sp_runtime::{traits::CheckedSub, SaturatedConversion, Saturating};
+
+impl<T: Config> Pallet<T> {
+	/// Accrue the current prices of the pool into its accumulator, and record a snapshot of it if
+	/// `PriceObservationPeriod` has passed since the last one.
+	///
+	/// Must be called before the reserves of the pool change.
+	pub(crate) fn update_price_oracle(pool_id: &PoolIdOf<T>, curve: PoolCurve) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let accumulator = Self::current_price_accumulator(pool_id, curve, now);
+		PriceAccumulators::<T>::insert(pool_id, &accumulator);
+
+		PriceObservations::<T>::mutate(pool_id, |observations| {
+			let due = match observations.last() {
+				Some(last) => now.saturating_sub(last.block) >= T::PriceObservationPeriod::get(),
+				None => true,
+			};
+			if due {
+				let _ = observations.force_insert_keep_right(observations.len(), accumulator);
+			}
+		});
+	}
+
+	/// The accumulator of the pool as it would be if it was brought up to date at block `now`.
+	fn current_price_accumulator(
+		pool_id: &PoolIdOf<T>,
+		curve: PoolCurve,
+		now: BlockNumberFor<T>,
+	) -> PriceObservation<BlockNumberFor<T>> {
+		let mut accumulator = PriceAccumulators::<T>::get(pool_id)
+			.unwrap_or(PriceObservation { block: now, ..Default::default() });
+
+		let elapsed: u128 = now.saturating_sub(accumulator.block).saturated_into();
+		if !elapsed.is_zero() {
+			if let Ok((reserve1, reserve2)) = Self::get_reserves(&pool_id.0, &pool_id.1) {
+				let price1 = Self::spot_price(reserve1, reserve2, curve).unwrap_or_default();
+				let price2 = Self::spot_price(reserve2, reserve1, curve).unwrap_or_default();
+				accumulator.price1_cumulative = accumulator
+					.price1_cumulative
+					.wrapping_add(price1.into_inner().wrapping_mul(elapsed));
+				accumulator.price2_cumulative = accumulator
+					.price2_cumulative
+					.wrapping_add(price2.into_inner().wrapping_mul(elapsed));
+			}
+		}
+		accumulator.block = now;
+		accumulator
+	}
+
+	/// The marginal price of the asset with `reserve_base` in units of the asset with
+	/// `reserve_quote`, for a pool pricing against `curve`.
+	pub(crate) fn spot_price(
+		reserve_base: T::AssetBalance,
+		reserve_quote: T::AssetBalance,
+		curve: PoolCurve,
+	) -> Option<FixedU128> {
+		let base: u128 = reserve_base.saturated_into();
+		let quote: u128 = reserve_quote.saturated_into();
+		match curve {
+			PoolCurve::ConstantProduct => FixedU128::checked_from_rational(quote, base),
+			PoolCurve::StableSwap { amplification } => {
+				// `-dy/dx` along the invariant, which is `(ann + 2u^2v) / (ann + 2uv^2)` with
+				// `u = D / 2x` and `v = D / 2y`.
+				let d: T::AssetBalance = Self::get_stable_swap_invariant(
+					&reserve_base.into(),
+					&reserve_quote.into(),
+					amplification,
+				)
+				.ok()?
+				.try_into()
+				.ok()?;
+				let d: u128 = d.saturated_into();
+				let u = FixedU128::checked_from_rational(d, base.checked_mul(2)?)?;
+				let v = FixedU128::checked_from_rational(d, quote.checked_mul(2)?)?;
+				let ann = FixedU128::saturating_from_integer(amplification.saturating_mul(4));
+				let two_uv =
+					u.saturating_mul(v).saturating_mul(FixedU128::saturating_from_integer(2));
+				ann.saturating_add(two_uv.saturating_mul(u))
+					.checked_div(&ann.saturating_add(two_uv.saturating_mul(v)))
+			},
+		}
+	}
+
+	/// Returns the price of `base` in units of `quote`, averaged over at least the last `window`
+	/// blocks. See [`PriceOracle::time_weighted_average_price`].
+	pub fn time_weighted_average_price(
+		base: T::MultiAssetId,
+		quote: T::MultiAssetId,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		let pool = Self::get_pool_info(&base, &quote).ok()?;
+		let pool_id = Self::get_pool_id(base.clone(), quote);
+		let now = frame_system::Pallet::<T>::block_number();
+		let since = now.checked_sub(&window)?;
+
+		let observations = PriceObservations::<T>::get(&pool_id);
+		let start = observations.iter().rev().find(|observation| observation.block <= since)?;
+		let current = Self::current_price_accumulator(&pool_id, pool.curve, now);
+
+		let elapsed: u128 = now.saturating_sub(start.block).saturated_into();
+		if elapsed.is_zero() {
+			return None
+		}
+		let accrued = if base == pool_id.0 {
+			current.price1_cumulative.wrapping_sub(start.price1_cumulative)
+		} else {
+			current.price2_cumulative.wrapping_sub(start.price2_cumulative)
+		};
+		Some(FixedU128::from_inner(accrued / elapsed))
+	}
+}
+
+impl<T: Config> PriceOracle<T::MultiAssetId, BlockNumberFor<T>> for Pallet<T> {
+	fn time_weighted_average_price(
+		base: T::MultiAssetId,
+		quote: T::MultiAssetId,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		Self::time_weighted_average_price(base, quote, window)
+	}
+}
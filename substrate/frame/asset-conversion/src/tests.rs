@@ -21,7 +21,7 @@ use frame_support::{
 	instances::Instance1,
 	traits::{fungible::Inspect, fungibles::InspectEnumerable, Get},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{FixedPointNumber, FixedU128, Permill};
 use sp_runtime::{DispatchError, TokenError};
 
 fn events() -> Vec<Event<Test>> {
@@ -121,11 +121,27 @@ fn check_max_numbers() {
 		assert_eq!(AssetConversion::quote(&u128::MAX, &u128::MAX, &1u128).ok().unwrap(), 1);
 
 		assert_eq!(
-			AssetConversion::get_amount_out(&100u128, &u128::MAX, &u128::MAX).ok().unwrap(),
+			AssetConversion::get_amount_out(
+				&100u128,
+				&u128::MAX,
+				&u128::MAX,
+				AssetConversion::default_fee(),
+				PoolCurve::ConstantProduct
+			)
+			.ok()
+			.unwrap(),
 			99
 		);
 		assert_eq!(
-			AssetConversion::get_amount_in(&100u128, &u128::MAX, &u128::MAX).ok().unwrap(),
+			AssetConversion::get_amount_in(
+				&100u128,
+				&u128::MAX,
+				&u128::MAX,
+				AssetConversion::default_fee(),
+				PoolCurve::ConstantProduct
+			)
+			.ok()
+			.unwrap(),
 			101
 		);
 	});
@@ -162,7 +178,9 @@ fn can_create_pool() {
 				creator: user,
 				pool_id,
 				pool_account: AssetConversion::get_pool_account(&pool_id),
-				lp_token
+				lp_token,
+				fee: AssetConversion::default_fee(),
+				curve: PoolCurve::ConstantProduct,
 			}]
 		);
 		assert_eq!(pools(), vec![pool_id]);
@@ -244,7 +262,9 @@ fn different_pools_should_have_different_lp_tokens() {
 				creator: user,
 				pool_id: pool_id_1_2,
 				pool_account: AssetConversion::get_pool_account(&pool_id_1_2),
-				lp_token: lp_token2_1
+				lp_token: lp_token2_1,
+				fee: AssetConversion::default_fee(),
+				curve: PoolCurve::ConstantProduct,
 			}]
 		);
 
@@ -256,6 +276,8 @@ fn different_pools_should_have_different_lp_tokens() {
 				pool_id: pool_id_1_3,
 				pool_account: AssetConversion::get_pool_account(&pool_id_1_3),
 				lp_token: lp_token3_1,
+				fee: AssetConversion::default_fee(),
+				curve: PoolCurve::ConstantProduct,
 			}]
 		);
 
@@ -885,10 +907,15 @@ fn can_swap_with_native() {
 		));
 
 		let input_amount = 100;
-		let expect_receive =
-			AssetConversion::get_amount_out(&input_amount, &liquidity2, &liquidity1)
-				.ok()
-				.unwrap();
+		let expect_receive = AssetConversion::get_amount_out(
+			&input_amount,
+			&liquidity2,
+			&liquidity1,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
 
 		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
 			RuntimeOrigin::signed(user),
@@ -1068,9 +1095,15 @@ fn check_no_panic_when_try_swap_close_to_empty_pool() {
 
 		// The price for the last tokens should be very high
 		assert_eq!(
-			AssetConversion::get_amount_in(&(token_1_left - 1), &token_2_left, &token_1_left)
-				.ok()
-				.unwrap(),
+			AssetConversion::get_amount_in(
+				&(token_1_left - 1),
+				&token_2_left,
+				&token_1_left,
+				AssetConversion::default_fee(),
+				PoolCurve::ConstantProduct
+			)
+			.ok()
+			.unwrap(),
 			10625
 		);
 
@@ -1178,9 +1211,15 @@ fn can_swap_tokens_for_exact_tokens() {
 		));
 
 		let exchange_out = 50;
-		let expect_in = AssetConversion::get_amount_in(&exchange_out, &liquidity1, &liquidity2)
-			.ok()
-			.unwrap();
+		let expect_in = AssetConversion::get_amount_in(
+			&exchange_out,
+			&liquidity1,
+			&liquidity2,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
 
 		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
 			RuntimeOrigin::signed(user),
@@ -1248,9 +1287,15 @@ fn can_swap_tokens_for_exact_tokens_when_not_liquidity_provider() {
 		assert_eq!(balance(user, token_2), 0);
 
 		let exchange_out = 50;
-		let expect_in = AssetConversion::get_amount_in(&exchange_out, &liquidity1, &liquidity2)
-			.ok()
-			.unwrap();
+		let expect_in = AssetConversion::get_amount_in(
+			&exchange_out,
+			&liquidity1,
+			&liquidity2,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
 
 		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
 			RuntimeOrigin::signed(user),
@@ -1434,12 +1479,24 @@ fn swap_exact_tokens_for_tokens_in_multi_hops() {
 		));
 
 		let input_amount = 500;
-		let expect_out2 = AssetConversion::get_amount_out(&input_amount, &liquidity1, &liquidity2)
-			.ok()
-			.unwrap();
-		let expect_out3 = AssetConversion::get_amount_out(&expect_out2, &liquidity2, &liquidity3)
-			.ok()
-			.unwrap();
+		let expect_out2 = AssetConversion::get_amount_out(
+			&input_amount,
+			&liquidity1,
+			&liquidity2,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
+		let expect_out3 = AssetConversion::get_amount_out(
+			&expect_out2,
+			&liquidity2,
+			&liquidity3,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
 
 		assert_noop!(
 			AssetConversion::swap_exact_tokens_for_tokens(
@@ -1533,12 +1590,24 @@ fn swap_tokens_for_exact_tokens_in_multi_hops() {
 		));
 
 		let exchange_out3 = 100;
-		let expect_in2 = AssetConversion::get_amount_in(&exchange_out3, &liquidity2, &liquidity3)
-			.ok()
-			.unwrap();
-		let expect_in1 = AssetConversion::get_amount_in(&expect_in2, &liquidity1, &liquidity2)
-			.ok()
-			.unwrap();
+		let expect_in2 = AssetConversion::get_amount_in(
+			&exchange_out3,
+			&liquidity2,
+			&liquidity3,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
+		let expect_in1 = AssetConversion::get_amount_in(
+			&expect_in2,
+			&liquidity1,
+			&liquidity2,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.ok()
+		.unwrap();
 
 		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
 			RuntimeOrigin::signed(user),
@@ -1685,3 +1754,516 @@ fn cannot_block_pool_creation() {
 		));
 	});
 }
+
+#[test]
+fn can_create_custom_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+		let pool_id = (token_1, token_2);
+		let fee = Permill::from_percent(1);
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2, token_3]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		assert_noop!(
+			AssetConversion::create_custom_pool(
+				RuntimeOrigin::signed(user),
+				token_2,
+				token_1,
+				fee,
+				curve
+			),
+			Error::<Test>::FeeTierNotAllowed
+		);
+
+		assert_noop!(
+			AssetConversion::set_fee_tiers(RuntimeOrigin::signed(user), bvec![fee]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AssetConversion::set_fee_tiers(RuntimeOrigin::root(), bvec![fee, Permill::one()]),
+			Error::<Test>::InvalidFeeTier
+		);
+		assert_ok!(AssetConversion::set_fee_tiers(RuntimeOrigin::root(), bvec![fee]));
+		assert_eq!(events(), [Event::<Test>::FeeTiersSet { fee_tiers: bvec![fee] }]);
+
+		for amplification in [0, MAX_AMPLIFICATION + 1] {
+			assert_noop!(
+				AssetConversion::create_custom_pool(
+					RuntimeOrigin::signed(user),
+					token_2,
+					token_1,
+					fee,
+					PoolCurve::StableSwap { amplification }
+				),
+				Error::<Test>::InvalidCurve
+			);
+		}
+
+		let lp_token = AssetConversion::get_next_pool_asset_id();
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_2,
+			token_1,
+			fee,
+			curve
+		));
+		assert_eq!(
+			events(),
+			[Event::<Test>::PoolCreated {
+				creator: user,
+				pool_id,
+				pool_account: AssetConversion::get_pool_account(&pool_id),
+				lp_token,
+				fee,
+				curve,
+			}]
+		);
+		assert_eq!(Pools::<Test>::get(pool_id), Some(PoolInfo { lp_token, fee, curve }));
+
+		// the default fee is always allowed.
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_3,
+			token_1,
+			AssetConversion::default_fee(),
+			curve
+		));
+		assert_eq!(pools(), vec![pool_id, (token_1, token_3)]);
+	});
+}
+
+#[test]
+fn pool_fee_tier_is_charged() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+		let fee = Permill::from_percent(1);
+
+		create_tokens(user, vec![token_2, token_3]);
+		assert_ok!(AssetConversion::set_fee_tiers(RuntimeOrigin::root(), bvec![fee]));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_3,
+			fee,
+			PoolCurve::ConstantProduct
+		));
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 100000));
+		for token in [token_2, token_3] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				token_1,
+				token,
+				10000,
+				10000,
+				1,
+				1,
+				user,
+			));
+		}
+
+		// the same reserves, but the 1% pool pays out less than the 0.3% one.
+		let amount = 1000;
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_2, token_1, amount, true),
+			Some(906)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_3, token_1, amount, true),
+			Some(900)
+		);
+
+		let prior_dot_balance = balance(user, token_1);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_3, token_1],
+			amount,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_1), prior_dot_balance + 900);
+	});
+}
+
+#[test]
+fn stable_swap_pool_has_less_slippage() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+
+		create_tokens(user, vec![token_2, token_3]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_3,
+			AssetConversion::default_fee(),
+			PoolCurve::StableSwap { amplification: 100 }
+		));
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 100000));
+		for token in [token_2, token_3] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				token_1,
+				token,
+				10000,
+				10000,
+				1,
+				1,
+				user,
+			));
+		}
+
+		// swapping 10% of the reserves stays close to par on the stable pool.
+		let amount = 1000;
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_2, token_1, amount, true),
+			Some(906)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_3, token_1, amount, true),
+			Some(996)
+		);
+		// without the fee the stable pool quotes along its curve rather than the spot price.
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_2, token_1, amount, false),
+			Some(1000)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(token_3, token_1, amount, false),
+			Some(999)
+		);
+
+		let prior_dot_balance = balance(user, token_1);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_3, token_1],
+			amount,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_1), prior_dot_balance + 996);
+
+		let amount_out = 500;
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_3, token_1, amount_out, true
+			),
+			Some(503)
+		);
+
+		let prior_token_3_balance = balance(user, token_3);
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_3, token_1],
+			amount_out,
+			1000,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_3), prior_token_3_balance - 503);
+	});
+}
+
+#[test]
+fn stable_swap_rounds_in_favour_of_the_pool() {
+	new_test_ext().execute_with(|| {
+		let fee = AssetConversion::default_fee();
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+		let (reserve_in, reserve_out) = (10000u128, 10000u128);
+
+		let amount_out =
+			AssetConversion::get_amount_out(&1000, &reserve_in, &reserve_out, fee, curve).unwrap();
+		assert_eq!(amount_out, 996);
+		assert_eq!(
+			AssetConversion::get_amount_in(&amount_out, &reserve_in, &reserve_out, fee, curve),
+			Ok(1001)
+		);
+
+		let amount_in =
+			AssetConversion::get_amount_in(&500, &reserve_in, &reserve_out, fee, curve).unwrap();
+		assert_eq!(amount_in, 503);
+		assert_eq!(
+			AssetConversion::get_amount_out(&amount_in, &reserve_in, &reserve_out, fee, curve),
+			Ok(500)
+		);
+
+		assert_eq!(
+			AssetConversion::get_amount_in(&reserve_out, &reserve_in, &reserve_out, fee, curve),
+			Err(Error::<Test>::AmountOutTooHigh)
+		);
+	});
+}
+
+#[test]
+fn multi_hop_swap_respects_each_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let token_3 = NativeOrAssetId::Asset(3);
+		let fee = Permill::from_percent(1);
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2, token_3]);
+		assert_ok!(AssetConversion::set_fee_tiers(RuntimeOrigin::root(), bvec![fee]));
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_3,
+			fee,
+			curve
+		));
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 100000));
+		for token in [token_2, token_3] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				token_1,
+				token,
+				10000,
+				10000,
+				1,
+				1,
+				user,
+			));
+		}
+
+		let amount = 1000;
+		let expect_out2 = AssetConversion::get_amount_out(
+			&amount,
+			&10000,
+			&10000,
+			AssetConversion::default_fee(),
+			PoolCurve::ConstantProduct,
+		)
+		.unwrap();
+		let expect_out3 =
+			AssetConversion::get_amount_out(&expect_out2, &10000, &10000, fee, curve).unwrap();
+		assert_eq!((expect_out2, expect_out3), (906, 895));
+
+		let prior_token_3_balance = balance(user, token_3);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2, token_1, token_3],
+			amount,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_3), prior_token_3_balance + expect_out3);
+	});
+}
+
+#[test]
+fn migration_to_v1_works() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let pool_id = (NativeOrAssetId::Native, NativeOrAssetId::Asset(2));
+		let lp_token = 7u32;
+		// a v0 `PoolInfo` only held the lp token.
+		frame_support::storage::unhashed::put(&Pools::<Test>::hashed_key_for(&pool_id), &lp_token);
+		StorageVersion::new(0).put::<AssetConversion>();
+
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			Pools::<Test>::get(pool_id),
+			Some(PoolInfo {
+				lp_token,
+				fee: AssetConversion::default_fee(),
+				curve: PoolCurve::ConstantProduct,
+			})
+		);
+		assert_eq!(StorageVersion::get::<AssetConversion>(), 1);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+
+		create_tokens(user, vec![token_2]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			10000,
+			20000,
+			1,
+			1,
+			user,
+		));
+		let spot_price = |base, quote| {
+			let (reserve_base, reserve_quote) =
+				AssetConversion::get_reserves(&base, &quote).unwrap();
+			FixedU128::checked_from_rational(reserve_quote, reserve_base).unwrap()
+		};
+		let old_price = spot_price(token_1, token_2);
+		assert_eq!(old_price, FixedU128::saturating_from_integer(2));
+
+		// nothing has accrued yet, and swaps only count from the block after them.
+		assert_eq!(AssetConversion::time_weighted_average_price(token_1, token_2, 0), None);
+		System::set_block_number(11);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1, token_2],
+			1000,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1, token_2, 10),
+			Some(old_price)
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_2, token_1, 10),
+			Some(FixedU128::from_rational(1, 2))
+		);
+		let new_price = spot_price(token_1, token_2);
+		assert!(new_price < old_price);
+
+		System::set_block_number(21);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1, token_2, 10),
+			Some(new_price)
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_2, token_1, 10),
+			Some(spot_price(token_2, token_1))
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1, token_2, 20),
+			Some(FixedU128::from_inner((old_price.into_inner() + new_price.into_inner()) / 2))
+		);
+		// there is no price history before the liquidity was added.
+		assert_eq!(AssetConversion::time_weighted_average_price(token_1, token_2, 21), None);
+		assert_eq!(AssetConversion::time_weighted_average_price(token_1, token_2, 100), None);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1, NativeOrAssetId::Asset(3), 10),
+			None
+		);
+	});
+}
+
+#[test]
+fn price_observations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+		let pool_id = (token_1, token_2);
+
+		create_tokens(user, vec![token_2]);
+		assert_ok!(AssetConversion::create_pool(RuntimeOrigin::signed(user), token_1, token_2));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			10000,
+			10000,
+			1,
+			1,
+			user,
+		));
+
+		let observed_blocks = || {
+			PriceObservations::<Test>::get(&pool_id)
+				.iter()
+				.map(|observation| observation.block)
+				.collect::<Vec<_>>()
+		};
+		for block in [6, 9, 11, 16] {
+			System::set_block_number(block);
+			assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user),
+				bvec![token_1, token_2],
+				10,
+				1,
+				user,
+				false,
+			));
+		}
+		// block 9 is within the `PriceObservationPeriod` of block 6, and block 1 fell out of the
+		// `MaxPriceObservations` ring.
+		assert_eq!(observed_blocks(), vec![6, 11, 16]);
+		assert_eq!(PriceAccumulators::<Test>::get(&pool_id).unwrap().block, 16);
+
+		System::set_block_number(20);
+		assert!(AssetConversion::time_weighted_average_price(token_1, token_2, 14).is_some());
+		assert_eq!(AssetConversion::time_weighted_average_price(token_1, token_2, 15), None);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_of_balanced_stable_pool_is_par() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrAssetId::Native;
+		let token_2 = NativeOrAssetId::Asset(2);
+
+		create_tokens(user, vec![token_2]);
+		assert_ok!(AssetConversion::create_custom_pool(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			AssetConversion::default_fee(),
+			PoolCurve::StableSwap { amplification: 100 }
+		));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			token_1,
+			token_2,
+			10000,
+			10000,
+			1,
+			1,
+			user,
+		));
+
+		System::set_block_number(11);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1, token_2, 10),
+			Some(FixedU128::from_u32(1))
+		);
+		assert_eq!(
+			<AssetConversion as PriceOracle<_, _>>::time_weighted_average_price(
+				token_2, token_1, 10
+			),
+			Some(FixedU128::from_u32(1))
+		);
+	});
+}
@@ -19,6 +19,8 @@ use super::*;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_arithmetic::{FixedU128, Permill};
+use sp_runtime::RuntimeDebug;
 use sp_std::{cmp::Ordering, marker::PhantomData};
 
 /// Pool ID.
@@ -27,11 +29,50 @@ use sp_std::{cmp::Ordering, marker::PhantomData};
 /// migration.
 pub(super) type PoolIdOf<T> = (<T as Config>::MultiAssetId, <T as Config>::MultiAssetId);
 
-/// Stores the lp_token asset id a particular pool has been assigned.
-#[derive(Decode, Encode, Default, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+/// The maximum amplification coefficient a [`PoolCurve::StableSwap`] pool can be created with.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The invariant a pool prices its swaps against.
+#[derive(
+	Decode, Encode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum PoolCurve {
+	/// The constant product invariant `x * y = k` of Uniswap V2. Suits pairs of uncorrelated
+	/// assets.
+	#[default]
+	ConstantProduct,
+	/// The StableSwap invariant of Curve, which flattens the constant product curve around the
+	/// balanced point. Suits pairs of assets expected to trade at par, e.g. two stablecoins.
+	///
+	/// The higher the `amplification`, the closer the curve is to a constant sum around the
+	/// balanced point. Must be within `1..=MAX_AMPLIFICATION`.
+	StableSwap {
+		/// The amplification coefficient `A` of the invariant.
+		amplification: u32,
+	},
+}
+
+impl PoolCurve {
+	/// Whether the curve parameters are within their permitted bounds.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			PoolCurve::ConstantProduct => true,
+			PoolCurve::StableSwap { amplification } =>
+				(1..=MAX_AMPLIFICATION).contains(amplification),
+		}
+	}
+}
+
+/// Stores the lp_token asset id a particular pool has been assigned, along with the fee and curve
+/// it was created with.
+#[derive(Decode, Encode, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct PoolInfo<PoolAssetId> {
 	/// Liquidity pool asset
 	pub lp_token: PoolAssetId,
+	/// The share of every swap the liquidity providers of this pool take.
+	pub fee: Permill,
+	/// The invariant the pool prices its swaps against.
+	pub curve: PoolCurve,
 }
 
 /// A trait that converts between a MultiAssetId and either the native currency or an AssetId.
@@ -120,6 +161,38 @@ pub trait Swap<AccountId, Balance, MultiAssetId> {
 	) -> Result<Balance, DispatchError>;
 }
 
+/// Trait for reading manipulation resistant prices off the pools.
+pub trait PriceOracle<MultiAssetId, BlockNumber> {
+	/// Returns the price of `base` in units of `quote`, averaged over at least the last `window`
+	/// blocks.
+	///
+	/// The average is taken since the latest recorded observation at least `window` blocks old, so
+	/// it may span up to one observation period more than `window`. Prices set within the current
+	/// block are not included. Returns `None` if there is no such pool, or its history does not go
+	/// back far enough.
+	fn time_weighted_average_price(
+		base: MultiAssetId,
+		quote: MultiAssetId,
+		window: BlockNumber,
+	) -> Option<FixedU128>;
+}
+
+/// A snapshot of the cumulative prices of a pool.
+///
+/// The cumulative prices are the sums, over every block since the pool first received liquidity,
+/// of the spot price of one asset of the pool in units of the other, stored as the inner value of
+/// a `FixedU128`. They wrap around on overflow, so only the difference between two snapshots is
+/// meaningful.
+#[derive(Decode, Encode, Default, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block the snapshot was taken at.
+	pub block: BlockNumber,
+	/// The cumulative price of the first asset of the pool in units of the second.
+	pub price1_cumulative: u128,
+	/// The cumulative price of the second asset of the pool in units of the first.
+	pub price2_cumulative: u128,
+}
+
 /// An implementation of MultiAssetId that can be either Native or an asset.
 #[derive(Decode, Encode, Default, MaxEncodedLen, TypeInfo, Clone, Copy, Debug)]
 pub enum NativeOrAssetId<AssetId>
@@ -20,12 +20,16 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
+use codec::Decode;
 use frame_benchmarking::v1::{
 	account, benchmarks_instance_pallet, whitelist_account, whitelisted_caller, BenchmarkError,
 };
-use frame_support::traits::{EnsureOrigin, Get, UnfilteredDispatchable};
+use frame_support::{
+	traits::{EnsureOrigin, Get, UnfilteredDispatchable},
+	BoundedVec,
+};
 use frame_system::RawOrigin as SystemOrigin;
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
 use sp_std::prelude::*;
 
 use crate::Pallet as Assets;
@@ -125,6 +129,34 @@ fn add_approvals<T: Config<I>, I: 'static>(minter: T::AccountId, n: u32) {
 	}
 }
 
+/// A hold or freeze reason, decoded from the first variant that exists.
+fn any_reason<R: Decode>() -> R {
+	(0..=u8::MAX)
+		.find_map(|variant| R::decode(&mut TrailingZeroInput::new(&[variant])).ok())
+		.expect("reasons have at least one variant")
+}
+
+/// Puts the maximum number of holds and freezes on the default asset-account of `who`, which are
+/// read to find out how much of its balance can be debited.
+///
+/// Returns the balance of `who` that can still be debited.
+fn add_holds_and_freezes<T: Config<I>, I: 'static>(who: &T::AccountId) -> T::Balance {
+	let asset_id: T::AssetId = default_asset_id::<T, I>().into();
+	let holds: BoundedVec<_, T::MaxHolds> = (0..T::MaxHolds::get())
+		.map(|_| IdAmount { id: any_reason::<T::RuntimeHoldReason>(), amount: Zero::zero() })
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("at most `MaxHolds` holds");
+	Holds::<T, I>::insert(&asset_id, who, holds);
+	let freezes: BoundedVec<_, T::MaxFreezes> = (0..T::MaxFreezes::get())
+		.map(|_| IdAmount { id: any_reason::<T::RuntimeFreezeReason>(), amount: Zero::zero() })
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("at most `MaxFreezes` freezes");
+	Freezes::<T, I>::insert(&asset_id, who, freezes);
+	Assets::<T, I>::reducible_balance(asset_id, who, false).expect("the account exists")
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
@@ -226,16 +258,16 @@ benchmarks_instance_pallet! {
 	}
 
 	burn {
-		let amount = T::Balance::from(100u32);
-		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, amount);
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 100u32.into());
+		let amount = add_holds_and_freezes::<T, I>(&caller);
 	}: _(SystemOrigin::Signed(caller.clone()), asset_id, caller_lookup, amount)
 	verify {
 		assert_last_event::<T, I>(Event::Burned { asset_id: asset_id.into(), owner: caller, balance: amount }.into());
 	}
 
 	transfer {
-		let amount = T::Balance::from(100u32);
-		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, amount);
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 100u32.into());
+		let amount = add_holds_and_freezes::<T, I>(&caller);
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
 	}: _(SystemOrigin::Signed(caller.clone()), asset_id, target_lookup, amount)
@@ -247,6 +279,7 @@ benchmarks_instance_pallet! {
 		let mint_amount = T::Balance::from(200u32);
 		let amount = T::Balance::from(100u32);
 		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, mint_amount);
+		add_holds_and_freezes::<T, I>(&caller);
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
 	}: _(SystemOrigin::Signed(caller.clone()), asset_id, target_lookup, amount)
@@ -256,8 +289,8 @@ benchmarks_instance_pallet! {
 	}
 
 	force_transfer {
-		let amount = T::Balance::from(100u32);
-		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, amount);
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 100u32.into());
+		let amount = add_holds_and_freezes::<T, I>(&caller);
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
 	}: _(SystemOrigin::Signed(caller.clone()), asset_id, caller_lookup, target_lookup, amount)
@@ -432,7 +465,7 @@ benchmarks_instance_pallet! {
 		let delegate: T::AccountId = account("delegate", 0, SEED);
 		whitelist_account!(delegate);
 		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
-		let amount = 100u32.into();
+		let amount = add_holds_and_freezes::<T, I>(&owner);
 		let origin = SystemOrigin::Signed(owner.clone()).into();
 		Assets::<T, I>::approve_transfer(origin, asset_id, delegate_lookup, amount)?;
 
@@ -53,6 +53,16 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Account::<T, I>::get(id, who.borrow()).map(|a| a.balance)
 	}
 
+	/// Get the asset `id` balance of `who` that is on hold, for all reasons.
+	pub fn held_balance(
+		id: T::AssetId,
+		who: impl sp_std::borrow::Borrow<T::AccountId>,
+	) -> T::Balance {
+		Holds::<T, I>::get(id, who.borrow())
+			.iter()
+			.fold(Zero::zero(), |total: T::Balance, hold| total.saturating_add(hold.amount))
+	}
+
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
 		Self::maybe_total_supply(id).unwrap_or_default()
@@ -186,7 +196,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Frozen
 		}
 		if let Some(rest) = account.balance.checked_sub(&amount) {
-			if let Some(frozen) = T::Freezer::frozen_balance(id.clone(), who) {
+			if let Some(frozen) = Self::frozen_balance(id.clone(), who) {
 				match frozen.checked_add(&details.min_balance) {
 					Some(required) if rest < required => return Frozen,
 					None => return Overflow,
@@ -208,6 +218,27 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// The amount of the asset `id` balance of `who` which must not be debited, if any.
+	///
+	/// This accounts for `T::Freezer` as well as for the freezes and holds on the asset-account.
+	/// Freezes apply to the total balance, so funds on hold count towards them. If `Some`, the
+	/// asset-account must not be removed.
+	pub(super) fn frozen_balance(id: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+		let freezes = Freezes::<T, I>::get(&id, who);
+		let held = Self::held_balance(id.clone(), who);
+		let maybe_frozen = T::Freezer::frozen_balance(id, who);
+		if freezes.is_empty() && held.is_zero() {
+			return maybe_frozen
+		}
+		let frozen = freezes
+			.iter()
+			.map(|freeze| freeze.amount)
+			.max()
+			.unwrap_or_else(Zero::zero)
+			.saturating_sub(held);
+		Some(maybe_frozen.map_or(frozen, |f| f.max(frozen)))
+	}
+
 	// Maximum `amount` that can be passed into `can_withdraw` to result in a `WithdrawConsequence`
 	// of `Success`.
 	pub(super) fn reducible_balance(
@@ -221,7 +252,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let account = Account::<T, I>::get(&id, who).ok_or(Error::<T, I>::NoAccount)?;
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 
-		let amount = if let Some(frozen) = T::Freezer::frozen_balance(id, who) {
+		let amount = if let Some(frozen) = Self::frozen_balance(id, who) {
 			// Frozen balance: account CANNOT be deleted
 			let required =
 				frozen.checked_add(&details.min_balance).ok_or(ArithmeticError::Overflow)?;
@@ -349,6 +380,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let mut details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
 		ensure!(matches!(details.status, Live | Frozen), Error::<T, I>::IncorrectStatus);
 		ensure!(account.balance.is_zero() || allow_burn, Error::<T, I>::WouldBurn);
+		ensure!(!Holds::<T, I>::contains_key(&id, &who), Error::<T, I>::ContainsHolds);
+		ensure!(!Freezes::<T, I>::contains_key(&id, &who), Error::<T, I>::ContainsFreezes);
 
 		if let Some(deposit) = account.reason.take_deposit() {
 			T::Currency::unreserve(&who, deposit);
@@ -382,6 +415,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 		ensure!(caller == &depositor || caller == &details.admin, Error::<T, I>::NoPermission);
 		ensure!(account.balance.is_zero(), Error::<T, I>::WouldBurn);
+		ensure!(!Holds::<T, I>::contains_key(&id, &who), Error::<T, I>::ContainsHolds);
+		ensure!(!Freezes::<T, I>::contains_key(&id, &who), Error::<T, I>::ContainsFreezes);
 
 		T::Currency::unreserve(&depositor, deposit);
 
@@ -769,6 +804,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					}
 					if let Remove = Self::dead_account(&who, &mut details, &v.reason, false) {
 						Account::<T, I>::remove(&id, &who);
+						Holds::<T, I>::remove(&id, &who);
+						Freezes::<T, I>::remove(&id, &who);
 						dead_accounts.push(who);
 					} else {
 						// deposit may have been released, need to update `Account`
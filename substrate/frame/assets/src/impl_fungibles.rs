@@ -46,7 +46,8 @@ impl<T: Config<I>, I: 'static> fungibles::Inspect<<T as SystemConfig>::AccountId
 	}
 
 	fn total_balance(asset: Self::AssetId, who: &<T as SystemConfig>::AccountId) -> Self::Balance {
-		Pallet::<T, I>::balance(asset, who)
+		Pallet::<T, I>::balance(asset.clone(), who)
+			.saturating_add(Pallet::<T, I>::held_balance(asset, who))
 	}
 
 	fn reducible_balance(
@@ -167,6 +168,131 @@ impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T,
 	// TODO: #13196 implement deactivate/reactivate once we have inactive balance tracking.
 }
 
+impl<T: Config<I>, I: 'static> fungibles::InspectHold<T::AccountId> for Pallet<T, I> {
+	type Reason = T::RuntimeHoldReason;
+
+	fn total_balance_on_hold(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Pallet::<T, I>::held_balance(asset, who)
+	}
+
+	fn balance_on_hold(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		Holds::<T, I>::get(asset, who)
+			.iter()
+			.find(|x| &x.id == reason)
+			.map_or_else(Zero::zero, |x| x.amount)
+	}
+
+	fn hold_available(asset: T::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		if !Account::<T, I>::contains_key(&asset, who) {
+			return false
+		}
+		let holds = Holds::<T, I>::get(asset, who);
+		!holds.is_full() || holds.iter().any(|x| &x.id == reason)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::UnbalancedHold<T::AccountId> for Pallet<T, I> {
+	fn set_balance_on_hold(
+		asset: T::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		ensure!(Account::<T, I>::contains_key(&asset, who), Error::<T, I>::NoAccount);
+		let mut holds = Holds::<T, I>::get(&asset, who);
+		if let Some(item) = holds.iter_mut().find(|x| &x.id == reason) {
+			item.amount = amount;
+			holds.retain(|x| !x.amount.is_zero());
+		} else if !amount.is_zero() {
+			holds
+				.try_push(IdAmount { id: *reason, amount })
+				.map_err(|_| Error::<T, I>::TooManyHolds)?;
+		}
+
+		if holds.is_empty() {
+			Holds::<T, I>::remove(asset, who);
+		} else {
+			Holds::<T, I>::insert(asset, who, holds);
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::MutateHold<T::AccountId> for Pallet<T, I> {}
+
+impl<T: Config<I>, I: 'static> fungibles::BalancedHold<T::AccountId> for Pallet<T, I> {}
+
+impl<T: Config<I>, I: 'static> fungibles::InspectFreeze<T::AccountId> for Pallet<T, I> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> T::Balance {
+		Freezes::<T, I>::get(asset, who)
+			.iter()
+			.find(|x| &x.id == id)
+			.map_or_else(Zero::zero, |x| x.amount)
+	}
+
+	fn can_freeze(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		!freezes.is_full() || freezes.iter().any(|x| &x.id == id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungibles::MutateFreeze<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Self::thaw(asset, id, who)
+		}
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(item) = freezes.iter_mut().find(|x| &x.id == id) {
+			item.amount = amount;
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Freezes::<T, I>::insert(asset, who, freezes);
+		Ok(())
+	}
+
+	fn extend_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
+		}
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(item) = freezes.iter_mut().find(|x| &x.id == id) {
+			item.amount = item.amount.max(amount);
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Freezes::<T, I>::insert(asset, who, freezes);
+		Ok(())
+	}
+
+	fn thaw(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		freezes.retain(|x| &x.id != id);
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(asset, who);
+		} else {
+			Freezes::<T, I>::insert(asset, who, freezes);
+		}
+		Ok(())
+	}
+}
+
 impl<T: Config<I>, I: 'static> fungibles::Create<T::AccountId> for Pallet<T, I> {
 	fn create(
 		id: T::AssetId,
@@ -310,6 +310,20 @@ pub mod pallet {
 		/// respected in all permissionless operations.
 		type Freezer: FrozenBalance<Self::AssetId, Self::AccountId, Self::Balance>;
 
+		/// The overarching hold reason.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of holds that can exist on an asset account at any time.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+
+		/// The maximum number of freezes that can exist on an asset account at any time.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
 		/// Additional data to be stored with an account's asset balance.
 		type Extra: Member + Parameter + Default + MaxEncodedLen;
 
@@ -344,6 +358,30 @@ pub mod pallet {
 		AssetAccountOf<T, I>,
 	>;
 
+	#[pallet::storage]
+	/// Holds on the balance of a specific account for a specific asset.
+	pub(super) type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::RuntimeHoldReason, T::Balance>, T::MaxHolds>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// Freezes on the balance of a specific account for a specific asset.
+	pub(super) type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::RuntimeFreezeReason, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	/// Approved balance transfers. First balance is the amount approved for transfer. Second
 	/// is the amount of `T::Currency` reserved for storing this.
@@ -571,6 +609,14 @@ pub mod pallet {
 		NotFrozen,
 		/// Callback action resulted in error
 		CallbackFailed,
+		/// Number of holds on an asset account exceeds `MaxHolds`.
+		TooManyHolds,
+		/// Number of freezes on an asset account exceeds `MaxFreezes`.
+		TooManyFreezes,
+		/// The asset account still has funds on hold.
+		ContainsHolds,
+		/// The asset account still has freezes in place.
+		ContainsFreezes,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
@@ -20,7 +20,7 @@
 use super::*;
 use crate as pallet_assets;
 
-use codec::Encode;
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64},
@@ -146,6 +146,10 @@ impl Config for Test {
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = TestFreezer;
+	type RuntimeHoldReason = TestId;
+	type RuntimeFreezeReason = TestId;
+	type MaxHolds = ConstU32<2>;
+	type MaxFreezes = ConstU32<2>;
 	type WeightInfo = ();
 	type CallbackHandle = AssetsCallbackHandle;
 	type Extra = ();
@@ -154,6 +158,16 @@ impl Config for Test {
 	type BenchmarkHelper = ();
 }
 
+/// Reasons for holds and freezes in tests.
+#[derive(
+	Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, MaxEncodedLen, TypeInfo, Debug,
+)]
+pub enum TestId {
+	Foo,
+	Bar,
+	Baz,
+}
+
 use std::collections::HashMap;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -22,7 +22,16 @@ use crate::{mock::*, Error};
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::GetDispatchInfo,
-	traits::{fungibles::InspectEnumerable, tokens::Preservation::Protect, Currency},
+	traits::{
+		fungibles::InspectEnumerable,
+		tokens::{
+			Fortitude::Polite,
+			Precision::Exact,
+			Preservation::{Expendable, Protect},
+			Restriction::{Free, OnHold},
+		},
+		Currency,
+	},
 };
 use pallet_balances::Error as BalancesError;
 use sp_io::storage;
@@ -1775,3 +1784,328 @@ fn asset_destroy_refund_existence_deposit() {
 		assert_eq!(Balances::reserved_balance(&admin), 0);
 	});
 }
+
+#[test]
+fn holds_work() {
+	use fungibles::{InspectHold, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		// Funds on hold are still part of the total balance and the supply.
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 30));
+		assert_eq!(Assets::balance(0, 1), 70);
+		assert_eq!(Assets::balance_on_hold(0, &Foo, &1), 30);
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 30);
+		assert_eq!(<Assets as fungibles::Inspect<_>>::total_balance(0, &1), 100);
+		assert_eq!(Assets::total_supply(0), 100);
+
+		// The account cannot be reaped while it has funds on hold.
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Expendable, Polite),
+			60
+		);
+		assert_noop!(
+			<Assets as fungibles::Mutate<_>>::transfer(0, &1, &2, 70, Expendable),
+			TokenError::Frozen
+		);
+		assert_noop!(
+			<Assets as MutateHold<_>>::hold(0, &Bar, &1, 61),
+			TokenError::FundsUnavailable
+		);
+
+		assert_ok!(<Assets as MutateHold<_>>::release(0, &Foo, &1, 10, Exact));
+		assert_eq!(Assets::balance(0, 1), 80);
+		assert_eq!(Assets::balance_on_hold(0, &Foo, &1), 20);
+
+		assert_ok!(<Assets as MutateHold<_>>::burn_all_held(0, &Foo, &1, Exact, Polite));
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 0);
+		assert_eq!(Assets::total_supply(0), 80);
+		assert!(!Holds::<Test>::contains_key(0, 1));
+
+		// Without holds the account may be reaped again.
+		assert_ok!(<Assets as fungibles::Mutate<_>>::transfer(0, &1, &2, 80, Expendable));
+		assert!(Account::<Test>::get(0, 1).is_none());
+	});
+}
+
+#[test]
+fn holds_are_limited() {
+	use fungibles::{InspectHold, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert!(!Assets::hold_available(0, &Foo, &2));
+		assert_noop!(<Assets as MutateHold<_>>::hold(0, &Foo, &2, 1), TokenError::CannotCreateHold);
+
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 10));
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Bar, &1, 10));
+		assert_noop!(
+			<Assets as MutateHold<_>>::hold(0, &Baz, &1, 10),
+			TokenError::CannotCreateHold
+		);
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 10));
+		assert_eq!(Assets::balance_on_hold(0, &Foo, &1), 20);
+	});
+}
+
+#[test]
+fn transfer_on_hold_works() {
+	use fungibles::{InspectHold, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 50));
+
+		assert_ok!(<Assets as MutateHold<_>>::transfer_on_hold(
+			0, &Foo, &1, &2, 20, Exact, OnHold, Polite
+		));
+		assert_eq!(Assets::balance_on_hold(0, &Foo, &1), 30);
+		assert_eq!(Assets::balance_on_hold(0, &Foo, &2), 20);
+		assert_eq!(Assets::balance(0, 2), 100);
+
+		assert_ok!(<Assets as MutateHold<_>>::transfer_on_hold(
+			0, &Foo, &1, &2, 30, Exact, Free, Polite
+		));
+		assert_eq!(Assets::total_balance_on_hold(0, &1), 0);
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 130);
+		assert_eq!(Assets::total_supply(0), 200);
+	});
+}
+
+#[test]
+fn freezes_work() {
+	use fungibles::{InspectFreeze, MutateFreeze, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert_ok!(Assets::set_freeze(0, &Foo, &1, 30));
+		assert_eq!(Assets::balance_frozen(0, &Foo, &1), 30);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Expendable, Polite),
+			60
+		);
+		assert_noop!(
+			<Assets as fungibles::Mutate<_>>::transfer(0, &1, &2, 61, Expendable),
+			TokenError::Frozen
+		);
+
+		// Overlapping freezes: only the largest one applies.
+		assert_ok!(Assets::extend_freeze(0, &Bar, &1, 50));
+		assert_ok!(Assets::extend_freeze(0, &Bar, &1, 40));
+		assert_eq!(Assets::balance_frozen(0, &Bar, &1), 50);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Expendable, Polite),
+			40
+		);
+		assert!(!Assets::can_freeze(0, &Baz, &1));
+		assert_noop!(Assets::set_freeze(0, &Baz, &1, 10), Error::<Test>::TooManyFreezes);
+
+		// Freezes apply to the total balance, so funds on hold count towards them.
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 20));
+		assert_eq!(Assets::balance(0, 1), 80);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Expendable, Polite),
+			40
+		);
+
+		assert_ok!(Assets::thaw(0, &Foo, &1));
+		assert_ok!(Assets::set_freeze(0, &Bar, &1, 0));
+		assert!(!Freezes::<Test>::contains_key(0, 1));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::reducible_balance(0, &1, Expendable, Polite),
+			70
+		);
+	});
+}
+
+#[test]
+fn refund_fails_with_holds_or_freezes() {
+	use fungibles::{MutateFreeze, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 1));
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 10));
+		assert_noop!(
+			Assets::refund(RuntimeOrigin::signed(1), 0, true),
+			Error::<Test>::ContainsHolds
+		);
+		assert_ok!(<Assets as MutateHold<_>>::release(0, &Foo, &1, 10, Exact));
+
+		assert_ok!(Assets::set_freeze(0, &Foo, &1, 10));
+		assert_noop!(
+			Assets::refund(RuntimeOrigin::signed(1), 0, true),
+			Error::<Test>::ContainsFreezes
+		);
+		assert_ok!(Assets::thaw(0, &Foo, &1));
+		assert_ok!(Assets::refund(RuntimeOrigin::signed(1), 0, true));
+	});
+}
+
+#[test]
+fn refund_other_fails_with_holds() {
+	use fungibles::MutateHold;
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::touch_other(RuntimeOrigin::signed(1), 0, 2));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 10));
+
+		// All funds are on hold, so the free balance is zero.
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &2, 10));
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert_noop!(
+			Assets::refund_other(RuntimeOrigin::signed(1), 0, 2),
+			Error::<Test>::ContainsHolds
+		);
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert!(Account::<Test>::contains_key(0, &2));
+	});
+}
+
+#[test]
+fn destroy_clears_holds_and_freezes() {
+	use fungibles::{MutateFreeze, MutateHold};
+	use TestId::*;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(<Assets as MutateHold<_>>::hold(0, &Foo, &1, 10));
+		assert_ok!(Assets::set_freeze(0, &Foo, &1, 10));
+
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(RuntimeOrigin::signed(1), 0));
+		assert!(!Holds::<Test>::contains_key(0, 1));
+		assert!(!Freezes::<Test>::contains_key(0, 1));
+	});
+}
+
+#[test]
+fn union_of_native_and_assets_works() {
+	use frame_support::traits::{
+		fungible::{NativeFromLeft, NativeOrWithId, UnionOf},
+		fungibles::{Balanced, Inspect, Mutate},
+	};
+	type NativeAndAssets = UnionOf<Balances, Assets, NativeFromLeft, NativeOrWithId<u32>, u64>;
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		let native = NativeOrWithId::Native;
+		let asset = NativeOrWithId::WithId(0);
+
+		assert_ok!(NativeAndAssets::mint_into(native, &1, 100));
+		assert_ok!(NativeAndAssets::mint_into(asset, &1, 50));
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(NativeAndAssets::balance(native, &1), 100);
+		assert_eq!(NativeAndAssets::total_issuance(asset), 50);
+
+		assert_ok!(NativeAndAssets::transfer(native, &1, &2, 30, Expendable));
+		assert_ok!(NativeAndAssets::transfer(asset, &1, &2, 20, Expendable));
+		assert_eq!(Balances::free_balance(2), 30);
+		assert_eq!(Assets::balance(0, 2), 20);
+
+		let credit = NativeAndAssets::withdraw(native, &1, 10, Exact, Expendable, Polite).unwrap();
+		assert_eq!(credit.asset(), native);
+		assert_ok!(NativeAndAssets::resolve(&2, credit));
+		assert_eq!(Balances::free_balance(1), 60);
+		assert_eq!(Balances::free_balance(2), 40);
+
+		// Dropped imbalances are handled by the implementation their asset belongs to.
+		let native_issuance = Balances::total_issuance();
+		drop(NativeAndAssets::issue(native, 5));
+		drop(NativeAndAssets::issue(asset, 5));
+		assert_eq!(Balances::total_issuance(), native_issuance);
+		assert_eq!(Assets::total_supply(0), 50);
+
+		assert_noop!(
+			<NativeAndAssets as fungibles::Create<u64>>::create(native, 1, true, 1),
+			TokenError::Unsupported
+		);
+	});
+}
+
+mod fungibles_conformance_tests {
+	use super::*;
+	use frame_support::traits::fungibles::conformance_tests;
+
+	macro_rules! run_tests {
+		($path:path, $min_balance:expr, $($name:ident),*) => {
+			$(
+				#[test]
+				fn $name() {
+					new_test_ext().execute_with(|| {
+						assert_ok!(Assets::force_create(
+							RuntimeOrigin::root(),
+							0,
+							1,
+							true,
+							$min_balance
+						));
+						$path::$name::<Assets, <Test as frame_system::Config>::AccountId>(0, None);
+					});
+				}
+			)*
+		};
+		($path:path, $min_balance:expr) => {
+			// `transfer_expendable_dust` is not run since this pallet leaves the dust of a reaped
+			// account in the supply rather than burning it.
+			run_tests!(
+				$path,
+				$min_balance,
+				mint_into_success,
+				mint_into_overflow,
+				mint_into_below_minimum,
+				burn_from_exact_success,
+				burn_from_best_effort_success,
+				burn_from_exact_insufficient_funds,
+				restore_success,
+				restore_overflow,
+				restore_below_minimum,
+				shelve_success,
+				shelve_insufficient_funds,
+				transfer_success,
+				transfer_expendable_all,
+				transfer_protect_preserve,
+				set_balance_mint_success,
+				set_balance_burn_success,
+				can_deposit_success,
+				can_deposit_below_minimum,
+				can_deposit_overflow,
+				can_withdraw_success,
+				can_withdraw_reduced_to_zero,
+				can_withdraw_balance_low,
+				reducible_balance_expendable,
+				reducible_balance_protect_preserve
+			);
+		};
+	}
+
+	// Depositing nothing is a no-op in this pallet, so the minimum balance must be at least two
+	// for the `_below_minimum` tests to be meaningful.
+	mod min_balance_2 {
+		use super::*;
+		run_tests!(conformance_tests::inspect_mutate, 2);
+	}
+
+	mod min_balance_5 {
+		use super::*;
+		run_tests!(conformance_tests::inspect_mutate, 5);
+	}
+
+	mod min_balance_1000 {
+		use super::*;
+		run_tests!(conformance_tests::inspect_mutate, 1000);
+	}
+}
@@ -130,6 +130,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1000 w:1000)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:0 w:1000)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:0 w:1000)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	/// The range of component `c` is `[0, 1000]`.
 	fn destroy_accounts(c: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -142,7 +146,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
-			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(c.into())))
 			.saturating_add(Weight::from_parts(0, 2609).saturating_mul(c.into()))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -194,13 +198,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
 	/// Storage: Assets Account (r:1 w:1)
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn burn() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `459`
 		//  Estimated: `3675`
 		// Minimum execution time: 33_625_000 picoseconds.
 		Weight::from_parts(34_474_000, 3675)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -209,13 +217,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 47_609_000 picoseconds.
 		Weight::from_parts(48_476_000, 6208)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -224,13 +236,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer_keep_alive() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 41_625_000 picoseconds.
 		Weight::from_parts(43_030_000, 6208)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -239,13 +255,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn force_transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 47_661_000 picoseconds.
 		Weight::from_parts(48_469_000, 6208)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:0)
@@ -410,13 +430,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer_approved() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `668`
 		//  Estimated: `6208`
 		// Minimum execution time: 67_712_000 picoseconds.
 		Weight::from_parts(69_946_000, 6208)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -490,26 +514,34 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn refund() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `579`
 		//  Estimated: `3675`
 		// Minimum execution time: 32_898_000 picoseconds.
 		Weight::from_parts(33_489_000, 3675)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: Assets Account (r:1 w:1)
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: Assets Asset (r:1 w:1)
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn refund_other() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `510`
 		//  Estimated: `3675`
 		// Minimum execution time: 31_243_000 picoseconds.
 		Weight::from_parts(31_909_000, 3675)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:0)
@@ -570,6 +602,10 @@ impl WeightInfo for () {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1000 w:1000)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:0 w:1000)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:0 w:1000)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	/// The range of component `c` is `[0, 1000]`.
 	fn destroy_accounts(c: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -582,7 +618,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(c.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
-			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(c.into())))
 			.saturating_add(Weight::from_parts(0, 2609).saturating_mul(c.into()))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -634,13 +670,17 @@ impl WeightInfo for () {
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
 	/// Storage: Assets Account (r:1 w:1)
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn burn() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `459`
 		//  Estimated: `3675`
 		// Minimum execution time: 33_625_000 picoseconds.
 		Weight::from_parts(34_474_000, 3675)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -649,13 +689,17 @@ impl WeightInfo for () {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 47_609_000 picoseconds.
 		Weight::from_parts(48_476_000, 6208)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -664,13 +708,17 @@ impl WeightInfo for () {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer_keep_alive() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 41_625_000 picoseconds.
 		Weight::from_parts(43_030_000, 6208)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -679,13 +727,17 @@ impl WeightInfo for () {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn force_transfer() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `498`
 		//  Estimated: `6208`
 		// Minimum execution time: 47_661_000 picoseconds.
 		Weight::from_parts(48_469_000, 6208)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:0)
@@ -850,13 +902,17 @@ impl WeightInfo for () {
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn transfer_approved() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `668`
 		//  Estimated: `6208`
 		// Minimum execution time: 67_712_000 picoseconds.
 		Weight::from_parts(69_946_000, 6208)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:1)
@@ -930,26 +986,34 @@ impl WeightInfo for () {
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn refund() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `579`
 		//  Estimated: `3675`
 		// Minimum execution time: 32_898_000 picoseconds.
 		Weight::from_parts(33_489_000, 3675)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: Assets Account (r:1 w:1)
 	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
 	/// Storage: Assets Asset (r:1 w:1)
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Holds (r:1 w:0)
+	/// Proof: Assets Holds (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
+	/// Storage: Assets Freezes (r:1 w:0)
+	/// Proof: Assets Freezes (max_values: None, max_size: Some(69), added: 2544, mode: MaxEncodedLen)
 	fn refund_other() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `510`
 		//  Estimated: `3675`
 		// Minimum execution time: 31_243_000 picoseconds.
 		Weight::from_parts(31_909_000, 3675)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: Assets Asset (r:1 w:0)
@@ -161,6 +161,13 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `MultiStepMigrator`: Drives the multi-block migrations. While they are ongoing, it is stepped
+///   once the inherents of a block are applied, transactions are rejected and `on_idle` is not
+///   called. Once they are stuck, operational transactions are accepted again.
+/// - `IdleScheduler`: Hands the weight left at the end of every block to the `on_idle` hooks of
+///   `AllPalletsWithSystem`. `()` calls them in turn, while a
+///   [`FairIdleScheduler`](frame_support::traits::FairIdleScheduler) reserves a share of it for
+///   each pallet.
 pub struct Executive<
 	System,
 	Block,
@@ -168,6 +175,8 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	MultiStepMigrator = (),
+	IdleScheduler = (),
 >(
 	PhantomData<(
 		System,
@@ -176,6 +185,8 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		MultiStepMigrator,
+		IdleScheduler,
 	)>,
 );
 
@@ -193,8 +204,19 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		IdleScheduler: frame_support::traits::IdleScheduler<BlockNumberFor<System>, AllPalletsWithSystem>,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		IdleScheduler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -211,6 +233,8 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			MultiStepMigrator,
+			IdleScheduler,
 		>::execute_block(block);
 	}
 }
@@ -231,7 +255,19 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ frame_support::traits::TryState<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		IdleScheduler: frame_support::traits::IdleScheduler<BlockNumberFor<System>, AllPalletsWithSystem>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		IdleScheduler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -282,6 +318,12 @@ where
 			<frame_system::Pallet<System>>::note_extrinsic(encoded);
 
 			let dispatch_info = xt.get_dispatch_info();
+			if dispatch_info.class != DispatchClass::Mandatory &&
+				!<frame_system::Pallet<System>>::inherents_applied()
+			{
+				Self::inherents_applied();
+			}
+
 			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 			<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
@@ -313,6 +355,10 @@ where
 			log::error!(target: LOG_TARGET, "failure: {:?}", e);
 			e
 		})?;
+		<IdleScheduler as frame_support::traits::IdleScheduler<
+			BlockNumberFor<System>,
+			AllPalletsWithSystem,
+		>>::try_state()?;
 		drop(_guard);
 
 		// do some of the checks that would normally happen in `final_checks`, but perhaps skip
@@ -371,6 +417,10 @@ where
 				checks.pre_and_post(),
 			)?;
 
+		// There are no further blocks in which multi-block migrations could progress.
+		let weight =
+			weight.saturating_add(MultiStepMigrator::try_run_to_completion(checks.pre_and_post())?);
+
 		if checks.try_state() {
 			let _guard = frame_support::StorageNoopGuard::default();
 			<AllPalletsWithSystem as frame_support::traits::TryState<
@@ -379,6 +429,10 @@ where
 				frame_system::Pallet::<System>::block_number(),
 				frame_try_runtime::TryStateSelect::All,
 			)?;
+			<IdleScheduler as frame_support::traits::IdleScheduler<
+				BlockNumberFor<System>,
+				AllPalletsWithSystem,
+			>>::try_state()?;
 		}
 
 		Ok(weight)
@@ -399,7 +453,19 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+		IdleScheduler: frame_support::traits::IdleScheduler<BlockNumberFor<System>, AllPalletsWithSystem>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+		IdleScheduler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -545,15 +611,22 @@ where
 	}
 
 	fn idle_and_finalize_hook(block_number: NumberFor<Block>) {
+		// Blocks without transactions only contain inherents.
+		if !<frame_system::Pallet<System>>::inherents_applied() {
+			Self::inherents_applied();
+		}
+
 		let weight = <frame_system::Pallet<System>>::block_weight();
 		let max_weight = <System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
 		let remaining_weight = max_weight.saturating_sub(weight.total());
 
-		if remaining_weight.all_gt(Weight::zero()) {
-			let used_weight = <AllPalletsWithSystem as OnIdle<BlockNumberFor<System>>>::on_idle(
-				block_number,
-				remaining_weight,
-			);
+		// The idle weight of the block belongs to the multi-block migrations while they are
+		// ongoing.
+		if remaining_weight.all_gt(Weight::zero()) && !MultiStepMigrator::ongoing() {
+			let used_weight = <IdleScheduler as frame_support::traits::IdleScheduler<
+				BlockNumberFor<System>,
+				AllPalletsWithSystem,
+			>>::on_idle(block_number, remaining_weight);
 			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
 				used_weight,
 				DispatchClass::Mandatory,
@@ -563,6 +636,36 @@ where
 		<AllPalletsWithSystem as OnFinalize<BlockNumberFor<System>>>::on_finalize(block_number);
 	}
 
+	/// Called once all inherents of the block have been applied.
+	///
+	/// Steps the multi-block migrations, so that they observe the state set up by the inherents.
+	/// Invoked right before the first transaction of the block is applied or, if there is none,
+	/// before `on_idle`.
+	pub fn inherents_applied() {
+		<frame_system::Pallet<System>>::note_inherents_applied();
+
+		if MultiStepMigrator::ongoing() {
+			let used_weight = MultiStepMigrator::step();
+			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+		}
+	}
+
+	/// Whether extrinsics of `class` may be applied with regard to the multi-block migrations.
+	///
+	/// Only inherents are applied while the migrations are ongoing. Once they are stuck, operational
+	/// extrinsics are applied as well, so that governance can recover the chain.
+	fn migrations_allow(class: DispatchClass) -> bool {
+		match class {
+			DispatchClass::Mandatory => true,
+			_ if !MultiStepMigrator::ongoing() => true,
+			DispatchClass::Operational => MultiStepMigrator::stuck(),
+			DispatchClass::Normal => false,
+		}
+	}
+
 	/// Apply extrinsic outside of the block execution function.
 	///
 	/// This doesn't attempt to validate anything regarding the block, but it builds a list of uxt
@@ -585,6 +688,17 @@ where
 
 		// Decode parameters and dispatch
 		let dispatch_info = xt.get_dispatch_info();
+
+		if dispatch_info.class != DispatchClass::Mandatory {
+			if !<frame_system::Pallet<System>>::inherents_applied() {
+				Self::inherents_applied();
+			}
+
+			if !Self::migrations_allow(dispatch_info.class) {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+		}
+
 		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 		// Mandatory(inherents) are not allowed to fail.
@@ -665,6 +779,11 @@ where
 			return Err(InvalidTransaction::MandatoryValidation.into())
 		}
 
+		// Transactions could not be included before the multi-block migrations completed.
+		if !Self::migrations_allow(dispatch_info.class) {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
+
 		within_span! {
 			sp_tracing::Level::TRACE, "validate";
 			xt.validate::<UnsignedValidator>(source, &dispatch_info, encoded_len)
@@ -963,6 +1082,73 @@ mod tests {
 		CustomOnRuntimeUpgrade,
 	>;
 
+	parameter_types! {
+		pub static MbmOngoing: bool = false;
+		pub static MbmStuck: bool = false;
+		pub static MbmSteps: u32 = 0;
+	}
+
+	struct MockedMultiStepMigrator;
+	impl frame_support::migrations::MultiStepMigrator for MockedMultiStepMigrator {
+		fn ongoing() -> bool {
+			MbmOngoing::get()
+		}
+
+		fn stuck() -> bool {
+			MbmStuck::get()
+		}
+
+		fn step() -> Weight {
+			MbmSteps::mutate(|s| *s += 1);
+			Weight::from_parts(50, 0)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_run_to_completion(_checks: bool) -> Result<Weight, TryRuntimeError> {
+			MbmOngoing::set(false);
+			Ok(Weight::zero())
+		}
+	}
+
+	type MbmExecutive = super::Executive<
+		Runtime,
+		Block<TestXt>,
+		ChainContext<Runtime>,
+		Runtime,
+		AllPalletsWithSystem,
+		CustomOnRuntimeUpgrade,
+		MockedMultiStepMigrator,
+	>;
+
+	parameter_types! {
+		pub IdleShares: Vec<(&'static str, sp_runtime::Perbill)> =
+			vec![("Custom", sp_runtime::Perbill::from_percent(50))];
+		pub static IdleOffered: Vec<(&'static str, Weight, Weight)> = Default::default();
+		pub static IdleUnused: Weight = Weight::zero();
+	}
+
+	struct MockedIdleMetrics;
+	impl frame_support::traits::IdleMetrics for MockedIdleMetrics {
+		fn on_pallet_idle(pallet: &'static str, offered: Weight, used: Weight) {
+			IdleOffered::mutate(|o| o.push((pallet, offered, used)));
+		}
+
+		fn on_unused_idle(unused: Weight) {
+			IdleUnused::set(unused);
+		}
+	}
+
+	type FairIdleExecutive = super::Executive<
+		Runtime,
+		Block<TestXt>,
+		ChainContext<Runtime>,
+		Runtime,
+		AllPalletsWithSystem,
+		CustomOnRuntimeUpgrade,
+		(),
+		frame_support::traits::FairIdleScheduler<IdleShares, MockedIdleMetrics>,
+	>;
+
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
 		(
 			frame_system::CheckEra::from(Era::Immortal),
@@ -1307,6 +1493,28 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn idle_scheduler_hands_out_idle_weight() {
+		new_test_ext(1).execute_with(|| {
+			FairIdleExecutive::initialize_block(&Header::new_from_number(1));
+			FairIdleExecutive::finalize_block();
+
+			// The pallets take turns in rotation, and only `Custom` uses any idle weight.
+			let offered = IdleOffered::take();
+			assert_eq!(
+				offered.iter().map(|(pallet, _, _)| *pallet).collect::<Vec<_>>(),
+				["Balances", "TransactionPayment", "Custom", "System"],
+			);
+			assert_eq!(offered[2].2, Weight::from_parts(175, 0));
+			// `System` runs last and is offered everything that is left.
+			assert_eq!(IdleUnused::get(), offered[3].1);
+			assert_eq!(
+				<frame_system::Pallet<Runtime>>::block_weight().total(),
+				Weight::from_parts(175 + 175 + 10, 0)
+			);
+		})
+	}
+
 	#[test]
 	fn runtime_upgraded_should_work() {
 		new_test_ext(1).execute_with(|| {
@@ -1642,4 +1850,119 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn ongoing_migrations_suspend_transactions() {
+		let xt1 = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+		let xt2 = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+		let header =
+			Header::new(1, H256::default(), H256::default(), [69u8; 32].into(), Digest::default());
+
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(true);
+			MbmSteps::set(0);
+
+			// Migrations are only stepped once the inherents are applied.
+			MbmExecutive::initialize_block(&header);
+			MbmExecutive::apply_extrinsic(xt1.clone()).unwrap().unwrap();
+			assert_eq!(MbmSteps::get(), 0);
+
+			// Transactions are not applied, but the migrations are stepped before them.
+			assert_eq!(
+				MbmExecutive::apply_extrinsic(xt2.clone()).unwrap_err(),
+				InvalidTransaction::ExhaustsResources.into()
+			);
+			assert_eq!(MbmSteps::get(), 1);
+
+			MbmExecutive::finalize_block();
+			assert_eq!(MbmSteps::get(), 1);
+		});
+
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(true);
+
+			assert_eq!(
+				MbmExecutive::validate_transaction(
+					TransactionSource::External,
+					xt2.clone(),
+					Default::default(),
+				),
+				Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+		});
+
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(false);
+			MbmSteps::set(0);
+
+			MbmExecutive::initialize_block(&header);
+			MbmExecutive::apply_extrinsic(xt1).unwrap().unwrap();
+			MbmExecutive::apply_extrinsic(xt2).unwrap().unwrap();
+			MbmExecutive::finalize_block();
+			assert_eq!(MbmSteps::get(), 0);
+		});
+	}
+
+	#[test]
+	fn stuck_migrations_accept_operational_transactions() {
+		let xt1 = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+		let xt2 = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+		let xt3 =
+			TestXt::new(RuntimeCall::Custom(custom::Call::some_root_operation {}), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(true);
+			MbmStuck::set(false);
+
+			// Operational transactions are rejected while the migrations are making progress.
+			assert_eq!(
+				MbmExecutive::validate_transaction(
+					TransactionSource::External,
+					xt3.clone(),
+					Default::default(),
+				),
+				Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+
+			MbmStuck::set(true);
+			MbmExecutive::initialize_block(&Header::new_from_number(1));
+			MbmExecutive::apply_extrinsic(xt1).unwrap().unwrap();
+			assert_eq!(
+				MbmExecutive::apply_extrinsic(xt2).unwrap_err(),
+				InvalidTransaction::ExhaustsResources.into()
+			);
+			// Dispatched, but fails since the origin is not root.
+			assert!(MbmExecutive::apply_extrinsic(xt3).unwrap().is_err());
+		});
+	}
+
+	#[test]
+	fn ongoing_migrations_suspend_on_idle() {
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(true);
+			MbmSteps::set(0);
+
+			// Blocks without transactions step the migrations at the end.
+			MbmExecutive::initialize_block(&Header::new_from_number(1));
+			MbmExecutive::finalize_block();
+			assert_eq!(MbmSteps::get(), 1);
+
+			// `on_initialize`, the migration step and the base weight, but not `on_idle`.
+			assert_eq!(
+				<frame_system::Pallet<Runtime>>::block_weight().total(),
+				Weight::from_parts(175 + 50 + 10, 0)
+			);
+		});
+
+		new_test_ext(1).execute_with(|| {
+			MbmOngoing::set(false);
+
+			MbmExecutive::initialize_block(&Header::new_from_number(1));
+			MbmExecutive::finalize_block();
+			assert_eq!(
+				<frame_system::Pallet<Runtime>>::block_weight().total(),
+				Weight::from_parts(175 + 175 + 10, 0)
+			);
+		});
+	}
 }
@@ -0,0 +1,114 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Groth16 verifier pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+
+use crate::Pallet as Groth16Verifier;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+/// Registers a verifying key on `curve` for `n` public inputs, returning its id, a valid proof
+/// and its public inputs.
+fn register_key<T: Config>(
+	curve: Curve,
+	n: u32,
+) -> Result<(KeyId, ProofOf, BoundedVec<PublicInput, T::MaxPublicInputs>), BenchmarkError> {
+	let (key, proof, public_inputs) = groth16::fixture(curve, n);
+	let key_id = NextKeyId::<T>::get();
+	VerifyingKeys::<T>::insert(
+		key_id,
+		VerifyingKey {
+			curve,
+			public_inputs: n,
+			key: key.try_into().map_err(|_| BenchmarkError::Weightless)?,
+		},
+	);
+	NextKeyId::<T>::put(key_id + 1);
+
+	let public_inputs = public_inputs
+		.into_iter()
+		.map(PublicInput::try_from)
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	Ok((
+		key_id,
+		proof.try_into().map_err(|_| BenchmarkError::Weightless)?,
+		public_inputs.try_into().map_err(|_| BenchmarkError::Weightless)?,
+	))
+}
+
+benchmarks! {
+	register_verifying_key {
+		// decoding BW6-761 points is the most expensive.
+		let n in 0 .. T::MaxPublicInputs::get();
+		let origin = T::KeyOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (key, _, _) = groth16::fixture(Curve::BW6_761, n);
+		let key: BoundedVec<_, _> = key.try_into().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, Curve::BW6_761, key)
+	verify {
+		assert_last_event::<T>(Event::VerifyingKeyRegistered { key_id: 0, curve: Curve::BW6_761 }.into());
+	}
+
+	remove_verifying_key {
+		let origin = T::KeyOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (key_id, _, _) = register_key::<T>(Curve::BW6_761, T::MaxPublicInputs::get())?;
+	}: _<T::RuntimeOrigin>(origin, key_id)
+	verify {
+		assert_last_event::<T>(Event::VerifyingKeyRemoved { key_id }.into());
+	}
+
+	verify_proof_bls12_381 {
+		let n in 0 .. T::MaxPublicInputs::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (key_id, proof, public_inputs) = register_key::<T>(Curve::Bls12_381, n)?;
+	}: verify_proof(RawOrigin::Signed(caller.clone()), key_id, proof, public_inputs)
+	verify {
+		assert_last_event::<T>(Event::ProofVerified { key_id, who: caller }.into());
+	}
+
+	verify_proof_bls12_377 {
+		let n in 0 .. T::MaxPublicInputs::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (key_id, proof, public_inputs) = register_key::<T>(Curve::Bls12_377, n)?;
+	}: verify_proof(RawOrigin::Signed(caller.clone()), key_id, proof, public_inputs)
+	verify {
+		assert_last_event::<T>(Event::ProofVerified { key_id, who: caller }.into());
+	}
+
+	verify_proof_bw6_761 {
+		let n in 0 .. T::MaxPublicInputs::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (key_id, proof, public_inputs) = register_key::<T>(Curve::BW6_761, n)?;
+	}: verify_proof(RawOrigin::Signed(caller.clone()), key_id, proof, public_inputs)
+	verify {
+		assert_last_event::<T>(Event::ProofVerified { key_id, who: caller }.into());
+	}
+
+	impl_benchmark_test_suite!(Groth16Verifier, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,284 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Groth16 verification over the pairing-friendly curves of `sp-crypto-ec-utils`.
+//!
+//! The curves are the `sp-ark-*` models, which hand their multi scalar multiplications, Miller
+//! loops and final exponentiations to the host through [`HostCalls`]. Keys, proofs and public
+//! inputs are the compressed `ark-serialize` encodings, laid out as `ark-groth16` writes them.
+
+use crate::Curve;
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_serialize::CanonicalDeserialize;
+use ark_std::Zero;
+use sp_crypto_ec_utils::elliptic_curves;
+use sp_std::prelude::*;
+
+/// Routes the curve operations of the `sp-ark-*` models through the `sp-crypto-ec-utils` host
+/// functions.
+#[derive(PartialEq, Eq)]
+pub struct HostCalls;
+
+impl sp_ark_bls12_381::HostFunctions for HostCalls {
+	fn bls12_381_multi_miller_loop(a: Vec<u8>, b: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_multi_miller_loop(a, b)
+	}
+	fn bls12_381_final_exponentiation(f12: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_final_exponentiation(f12)
+	}
+	fn bls12_381_msm_g1(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_msm_g1(bases, bigints)
+	}
+	fn bls12_381_msm_g2(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_msm_g2(bases, bigints)
+	}
+	fn bls12_381_mul_projective_g1(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_mul_projective_g1(base, scalar)
+	}
+	fn bls12_381_mul_projective_g2(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_381_mul_projective_g2(base, scalar)
+	}
+}
+
+impl sp_ark_bls12_377::HostFunctions for HostCalls {
+	fn bls12_377_multi_miller_loop(a: Vec<u8>, b: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_multi_miller_loop(a, b)
+	}
+	fn bls12_377_final_exponentiation(f12: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_final_exponentiation(f12)
+	}
+	fn bls12_377_msm_g1(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_msm_g1(bases, bigints)
+	}
+	fn bls12_377_msm_g2(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_msm_g2(bases, bigints)
+	}
+	fn bls12_377_mul_projective_g1(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_mul_projective_g1(base, scalar)
+	}
+	fn bls12_377_mul_projective_g2(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bls12_377_mul_projective_g2(base, scalar)
+	}
+}
+
+impl sp_ark_bw6_761::HostFunctions for HostCalls {
+	fn bw6_761_multi_miller_loop(a: Vec<u8>, b: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_multi_miller_loop(a, b)
+	}
+	fn bw6_761_final_exponentiation(f12: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_final_exponentiation(f12)
+	}
+	fn bw6_761_msm_g1(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_msm_g1(bases, bigints)
+	}
+	fn bw6_761_msm_g2(bases: Vec<u8>, bigints: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_msm_g2(bases, bigints)
+	}
+	fn bw6_761_mul_projective_g1(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_mul_projective_g1(base, scalar)
+	}
+	fn bw6_761_mul_projective_g2(base: Vec<u8>, scalar: Vec<u8>) -> Result<Vec<u8>, ()> {
+		elliptic_curves::bw6_761_mul_projective_g2(base, scalar)
+	}
+}
+
+/// BLS12-381, computed by the host.
+pub type Bls12_381 = sp_ark_bls12_381::Bls12_381<HostCalls>;
+/// BLS12-377, computed by the host.
+pub type Bls12_377 = sp_ark_bls12_377::Bls12_377<HostCalls>;
+/// BW6-761, computed by the host.
+pub type BW6_761 = sp_ark_bw6_761::BW6_761<HostCalls>;
+
+/// Why a proof could not be verified.
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyError {
+	/// The verifying key does not decode to valid curve points.
+	MalformedVerifyingKey,
+	/// The proof does not decode to valid curve points.
+	MalformedProof,
+	/// A public input is not the canonical encoding of a scalar.
+	MalformedPublicInput,
+	/// The number of public inputs differs from what the verifying key expects.
+	WrongPublicInputCount,
+	/// The pairing check failed.
+	InvalidProof,
+}
+
+/// A Groth16 verifying key.
+struct VerifyingKey<E: Pairing> {
+	alpha_g1: E::G1Affine,
+	beta_g2: E::G2Affine,
+	gamma_g2: E::G2Affine,
+	delta_g2: E::G2Affine,
+	gamma_abc_g1: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> VerifyingKey<E> {
+	fn decode(mut bytes: &[u8]) -> Result<Self, VerifyError> {
+		let key = Self {
+			alpha_g1: read(&mut bytes).ok_or(VerifyError::MalformedVerifyingKey)?,
+			beta_g2: read(&mut bytes).ok_or(VerifyError::MalformedVerifyingKey)?,
+			gamma_g2: read(&mut bytes).ok_or(VerifyError::MalformedVerifyingKey)?,
+			delta_g2: read(&mut bytes).ok_or(VerifyError::MalformedVerifyingKey)?,
+			gamma_abc_g1: read(&mut bytes).ok_or(VerifyError::MalformedVerifyingKey)?,
+		};
+		if !bytes.is_empty() || key.gamma_abc_g1.is_empty() {
+			return Err(VerifyError::MalformedVerifyingKey)
+		}
+		Ok(key)
+	}
+
+	fn public_inputs(&self) -> u32 {
+		self.gamma_abc_g1.len().saturating_sub(1) as u32
+	}
+}
+
+/// A Groth16 proof.
+struct Proof<E: Pairing> {
+	a: E::G1Affine,
+	b: E::G2Affine,
+	c: E::G1Affine,
+}
+
+impl<E: Pairing> Proof<E> {
+	fn decode(mut bytes: &[u8]) -> Result<Self, VerifyError> {
+		let proof = Self {
+			a: read(&mut bytes).ok_or(VerifyError::MalformedProof)?,
+			b: read(&mut bytes).ok_or(VerifyError::MalformedProof)?,
+			c: read(&mut bytes).ok_or(VerifyError::MalformedProof)?,
+		};
+		if !bytes.is_empty() {
+			return Err(VerifyError::MalformedProof)
+		}
+		Ok(proof)
+	}
+}
+
+/// Reads a value off the front of `bytes`, checking that it is valid.
+fn read<T: CanonicalDeserialize>(bytes: &mut &[u8]) -> Option<T> {
+	T::deserialize_compressed(bytes).ok()
+}
+
+/// Returns the number of public inputs taken by the verifying key `key` on `curve`.
+pub fn public_inputs(curve: Curve, key: &[u8]) -> Result<u32, VerifyError> {
+	match curve {
+		Curve::Bls12_381 => VerifyingKey::<Bls12_381>::decode(key).map(|key| key.public_inputs()),
+		Curve::Bls12_377 => VerifyingKey::<Bls12_377>::decode(key).map(|key| key.public_inputs()),
+		Curve::BW6_761 => VerifyingKey::<BW6_761>::decode(key).map(|key| key.public_inputs()),
+	}
+}
+
+/// Verifies `proof` of `public_inputs` against the verifying key `key` on `curve`.
+pub fn verify<I: AsRef<[u8]>>(
+	curve: Curve,
+	key: &[u8],
+	proof: &[u8],
+	public_inputs: &[I],
+) -> Result<(), VerifyError> {
+	match curve {
+		Curve::Bls12_381 => verify_on::<Bls12_381, _>(key, proof, public_inputs),
+		Curve::Bls12_377 => verify_on::<Bls12_377, _>(key, proof, public_inputs),
+		Curve::BW6_761 => verify_on::<BW6_761, _>(key, proof, public_inputs),
+	}
+}
+
+fn verify_on<E: Pairing, I: AsRef<[u8]>>(
+	key: &[u8],
+	proof: &[u8],
+	public_inputs: &[I],
+) -> Result<(), VerifyError> {
+	let key = VerifyingKey::<E>::decode(key)?;
+	let proof = Proof::<E>::decode(proof)?;
+	let public_inputs = public_inputs
+		.iter()
+		.map(|input| {
+			let mut bytes = input.as_ref();
+			read::<E::ScalarField>(&mut bytes)
+				.filter(|_| bytes.is_empty())
+				.ok_or(VerifyError::MalformedPublicInput)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if public_inputs.len() + 1 != key.gamma_abc_g1.len() {
+		return Err(VerifyError::WrongPublicInputCount)
+	}
+
+	let prepared_inputs = E::G1::msm(&key.gamma_abc_g1[1..], &public_inputs)
+		.map_err(|_| VerifyError::WrongPublicInputCount)? +
+		key.gamma_abc_g1[0];
+	// e(A, B) = e(alpha, beta) * e(inputs, gamma) * e(C, delta)
+	let check = E::multi_pairing(
+		[proof.a, (-prepared_inputs).into_affine(), -proof.c, -key.alpha_g1],
+		[proof.b, key.gamma_g2, key.delta_g2, key.beta_g2],
+	);
+	if !check.is_zero() {
+		return Err(VerifyError::InvalidProof)
+	}
+	Ok(())
+}
+
+/// A verifying key, a proof and its public inputs, for benchmarks and tests.
+///
+/// The proof is forged from the known toxic waste of the key rather than proven for a circuit, and
+/// the values are deterministic so the same works in a runtime.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn fixture(curve: Curve, public_inputs: u32) -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+	match curve {
+		Curve::Bls12_381 => fixture_on::<Bls12_381>(public_inputs),
+		Curve::Bls12_377 => fixture_on::<Bls12_377>(public_inputs),
+		Curve::BW6_761 => fixture_on::<BW6_761>(public_inputs),
+	}
+}
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+fn fixture_on<E: Pairing>(public_inputs: u32) -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+	use ark_ec::Group;
+	use ark_ff::Field;
+	use ark_serialize::CanonicalSerialize;
+
+	fn encode<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		value.serialize_compressed(&mut bytes).expect("writing to a vec can't fail");
+		bytes
+	}
+	let scalar = |n: u64| E::ScalarField::from(n);
+	let g1 = |s: E::ScalarField| (E::G1::generator() * s).into_affine();
+	let g2 = |s: E::ScalarField| (E::G2::generator() * s).into_affine();
+
+	let (alpha, beta, gamma, delta) = (scalar(2), scalar(3), scalar(5), scalar(7));
+	let gamma_abc: Vec<_> = (0..=public_inputs as u64).map(|i| scalar(11 + i)).collect();
+	let inputs: Vec<_> = (0..public_inputs as u64).map(|i| scalar(1_000 + i)).collect();
+	let prepared_inputs = gamma_abc[0] +
+		gamma_abc[1..].iter().zip(&inputs).map(|(g, x)| *g * x).sum::<E::ScalarField>();
+	let (a, b) = (scalar(13), scalar(17));
+	// pick `c` such that `a * b = alpha * beta + prepared_inputs * gamma + c * delta`.
+	let c = (a * b - alpha * beta - prepared_inputs * gamma) *
+		delta.inverse().expect("delta is not zero; qed");
+
+	let mut key = Vec::new();
+	key.extend(encode(&g1(alpha)));
+	key.extend(encode(&g2(beta)));
+	key.extend(encode(&g2(gamma)));
+	key.extend(encode(&g2(delta)));
+	key.extend(encode(&gamma_abc.into_iter().map(g1).collect::<Vec<_>>()));
+
+	let mut proof = Vec::new();
+	proof.extend(encode(&g1(a)));
+	proof.extend(encode(&g2(b)));
+	proof.extend(encode(&g1(c)));
+
+	(key, proof, inputs.iter().map(encode).collect())
+}
@@ -0,0 +1,303 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Groth16 Verifier Pallet
+//!
+//! Stores Groth16 verifying keys and checks proofs against them.
+//!
+//! ## Overview
+//!
+//! Verifying keys are registered by [`Config::KeyOrigin`] on one of the pairing-friendly
+//! [`Curve`]s, and anyone may then submit a proof with its public inputs to
+//! [`Pallet::verify_proof`]. Other pallets can check proofs directly through the [`Verify`]
+//! trait.
+//!
+//! The curve arithmetic is not done in the runtime but by the host functions of
+//! `sp-crypto-ec-utils`, so the executor of any node running this pallet has to be extended with
+//! `sp_crypto_ec_utils::elliptic_curves::HostFunctions`.
+//!
+//! Keys, proofs and public inputs are the compressed `ark-serialize` encodings of their curve
+//! points and scalars, as written by `ark-groth16`.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod groth16;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{ConstU32, Get},
+	weights::Weight,
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use groth16::VerifyError;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The longest encoding of a proof, which is the length of one on BW6-761.
+pub const MAX_PROOF_LEN: u32 = 288;
+
+/// The longest encoding of a public input, which is the length of a BW6-761 scalar.
+pub const MAX_PUBLIC_INPUT_LEN: u32 = 48;
+
+/// Identifies a registered verifying key.
+pub type KeyId = u32;
+
+/// An encoded proof.
+pub type ProofOf = BoundedVec<u8, ConstU32<MAX_PROOF_LEN>>;
+
+/// An encoded public input.
+pub type PublicInput = BoundedVec<u8, ConstU32<MAX_PUBLIC_INPUT_LEN>>;
+
+/// A pairing-friendly curve proofs can be verified on.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Curve {
+	/// BLS12-381.
+	Bls12_381,
+	/// BLS12-377.
+	Bls12_377,
+	/// BW6-761, whose scalar field is the base field of BLS12-377.
+	BW6_761,
+}
+
+/// A registered verifying key.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxKeyLen))]
+#[codec(mel_bound())]
+pub struct VerifyingKey<MaxKeyLen: Get<u32>> {
+	/// The curve the key is on.
+	pub curve: Curve,
+	/// The number of public inputs proofs for the key take.
+	pub public_inputs: u32,
+	/// The encoded key.
+	pub key: BoundedVec<u8, MaxKeyLen>,
+}
+
+/// Checks proofs against registered verifying keys.
+pub trait Verify {
+	/// Identifies a verifying key.
+	type KeyId;
+
+	/// Verify `proof` of `public_inputs` against the verifying key `key`.
+	fn verify(key: Self::KeyId, proof: &[u8], public_inputs: &[Vec<u8>]) -> DispatchResult;
+
+	/// The worst case weight of [`Self::verify`] for a proof of `public_inputs` inputs.
+	fn verify_weight(public_inputs: u32) -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may register and remove verifying keys.
+		type KeyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of public inputs of a verifying key.
+		#[pallet::constant]
+		type MaxPublicInputs: Get<u32>;
+
+		/// The maximum length of an encoded verifying key.
+		#[pallet::constant]
+		type MaxKeyLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The id the next registered verifying key gets.
+	#[pallet::storage]
+	pub type NextKeyId<T> = StorageValue<_, KeyId, ValueQuery>;
+
+	/// The registered verifying keys.
+	#[pallet::storage]
+	pub type VerifyingKeys<T: Config> =
+		StorageMap<_, Twox64Concat, KeyId, VerifyingKey<T::MaxKeyLen>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A verifying key was registered.
+		VerifyingKeyRegistered { key_id: KeyId, curve: Curve },
+		/// A verifying key was removed.
+		VerifyingKeyRemoved { key_id: KeyId },
+		/// A proof was verified.
+		ProofVerified { key_id: KeyId, who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no verifying key with the given id.
+		UnknownVerifyingKey,
+		/// The verifying key does not decode to valid points of its curve.
+		MalformedVerifyingKey,
+		/// The verifying key takes more than `MaxPublicInputs` public inputs.
+		TooManyPublicInputs,
+		/// The proof does not decode to valid points of the curve of the key.
+		MalformedProof,
+		/// A public input is not the canonical encoding of a scalar of the curve of the key.
+		MalformedPublicInput,
+		/// The number of public inputs differs from what the verifying key expects.
+		WrongPublicInputCount,
+		/// The proof is not valid for the verifying key and public inputs.
+		InvalidProof,
+		/// No more verifying keys can be registered.
+		NoAvailableKeyId,
+	}
+
+	impl<T> From<VerifyError> for Error<T> {
+		fn from(error: VerifyError) -> Self {
+			match error {
+				VerifyError::MalformedVerifyingKey => Error::<T>::MalformedVerifyingKey,
+				VerifyError::MalformedProof => Error::<T>::MalformedProof,
+				VerifyError::MalformedPublicInput => Error::<T>::MalformedPublicInput,
+				VerifyError::WrongPublicInputCount => Error::<T>::WrongPublicInputCount,
+				VerifyError::InvalidProof => Error::<T>::InvalidProof,
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the verifying key `key` on `curve`.
+		///
+		/// The origin must be `KeyOrigin`. Emits `VerifyingKeyRegistered` with the id the key is
+		/// referred to by.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_verifying_key(T::MaxPublicInputs::get()))]
+		pub fn register_verifying_key(
+			origin: OriginFor<T>,
+			curve: Curve,
+			key: BoundedVec<u8, T::MaxKeyLen>,
+		) -> DispatchResultWithPostInfo {
+			T::KeyOrigin::ensure_origin(origin)?;
+			let public_inputs =
+				groth16::public_inputs(curve, &key).map_err(Error::<T>::from)?;
+			ensure!(public_inputs <= T::MaxPublicInputs::get(), Error::<T>::TooManyPublicInputs);
+
+			let key_id = NextKeyId::<T>::get();
+			NextKeyId::<T>::put(key_id.checked_add(1).ok_or(Error::<T>::NoAvailableKeyId)?);
+			VerifyingKeys::<T>::insert(key_id, VerifyingKey { curve, public_inputs, key });
+
+			Self::deposit_event(Event::VerifyingKeyRegistered { key_id, curve });
+			Ok(Some(T::WeightInfo::register_verifying_key(public_inputs)).into())
+		}
+
+		/// Remove the verifying key `key_id`.
+		///
+		/// The origin must be `KeyOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_verifying_key())]
+		pub fn remove_verifying_key(origin: OriginFor<T>, key_id: KeyId) -> DispatchResult {
+			T::KeyOrigin::ensure_origin(origin)?;
+			VerifyingKeys::<T>::take(key_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+			Self::deposit_event(Event::VerifyingKeyRemoved { key_id });
+			Ok(())
+		}
+
+		/// Verify `proof` of `public_inputs` against the verifying key `key_id`.
+		///
+		/// The origin must be signed. Emits `ProofVerified` if the proof is valid, and fails
+		/// otherwise.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Pallet::<T>::verify_weight(public_inputs.len() as u32))]
+		pub fn verify_proof(
+			origin: OriginFor<T>,
+			key_id: KeyId,
+			proof: ProofOf,
+			public_inputs: BoundedVec<PublicInput, T::MaxPublicInputs>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let curve = Self::do_verify(key_id, &proof, public_inputs.as_slice())?;
+			Self::deposit_event(Event::ProofVerified { key_id, who });
+			Ok(Some(Self::curve_verify_weight(curve, public_inputs.len() as u32)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Verify `proof` of `public_inputs` against the verifying key `key_id`, returning the curve
+	/// of the key.
+	fn do_verify<I: AsRef<[u8]>>(
+		key_id: KeyId,
+		proof: &[u8],
+		public_inputs: &[I],
+	) -> Result<Curve, Error<T>> {
+		let key = VerifyingKeys::<T>::get(key_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+		if public_inputs.len() != key.public_inputs as usize {
+			return Err(Error::<T>::WrongPublicInputCount)
+		}
+		groth16::verify(key.curve, &key.key, proof, public_inputs)?;
+		Ok(key.curve)
+	}
+
+	/// The weight of verifying a proof of `public_inputs` inputs on `curve`.
+	fn curve_verify_weight(curve: Curve, public_inputs: u32) -> Weight {
+		match curve {
+			Curve::Bls12_381 => T::WeightInfo::verify_proof_bls12_381(public_inputs),
+			Curve::Bls12_377 => T::WeightInfo::verify_proof_bls12_377(public_inputs),
+			Curve::BW6_761 => T::WeightInfo::verify_proof_bw6_761(public_inputs),
+		}
+	}
+}
+
+impl<T: Config> Verify for Pallet<T> {
+	type KeyId = KeyId;
+
+	fn verify(key: KeyId, proof: &[u8], public_inputs: &[Vec<u8>]) -> DispatchResult {
+		Self::do_verify(key, proof, public_inputs)?;
+		Ok(())
+	}
+
+	fn verify_weight(public_inputs: u32) -> Weight {
+		[Curve::Bls12_381, Curve::Bls12_377, Curve::BW6_761]
+			.into_iter()
+			.map(|curve| Self::curve_verify_weight(curve, public_inputs))
+			.fold(Weight::zero(), |worst, weight| worst.max(weight))
+	}
+}
@@ -0,0 +1,249 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Groth16 verifier pallet.
+
+use crate::{groth16::fixture, mock::*, *};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::DispatchError;
+
+const CURVES: [Curve; 3] = [Curve::Bls12_381, Curve::Bls12_377, Curve::BW6_761];
+
+fn bounded<S: Get<u32>>(bytes: Vec<u8>) -> BoundedVec<u8, S> {
+	bytes.try_into().unwrap()
+}
+
+fn public_inputs(inputs: Vec<Vec<u8>>) -> BoundedVec<PublicInput, ConstU32<4>> {
+	inputs.into_iter().map(bounded).collect::<Vec<_>>().try_into().unwrap()
+}
+
+#[test]
+fn verifies_proofs_on_every_curve() {
+	new_test_ext().execute_with(|| {
+		for (key_id, curve) in CURVES.into_iter().enumerate() {
+			let key_id = key_id as KeyId;
+			let (key, proof, inputs) = fixture(curve, 2);
+			assert_ok!(Groth16Verifier::register_verifying_key(
+				RuntimeOrigin::root(),
+				curve,
+				bounded(key.clone())
+			));
+			System::assert_last_event(Event::VerifyingKeyRegistered { key_id, curve }.into());
+			assert_eq!(
+				VerifyingKeys::<Test>::get(key_id),
+				Some(VerifyingKey { curve, public_inputs: 2, key: bounded(key) })
+			);
+
+			assert_ok!(Groth16Verifier::verify_proof(
+				RuntimeOrigin::signed(1),
+				key_id,
+				bounded(proof),
+				public_inputs(inputs)
+			));
+			System::assert_last_event(Event::ProofVerified { key_id, who: 1 }.into());
+		}
+		assert_eq!(NextKeyId::<Test>::get(), 3);
+	});
+}
+
+#[test]
+fn verifies_proofs_without_public_inputs() {
+	new_test_ext().execute_with(|| {
+		let (key, proof, _) = fixture(Curve::Bls12_381, 0);
+		assert_ok!(Groth16Verifier::register_verifying_key(
+			RuntimeOrigin::root(),
+			Curve::Bls12_381,
+			bounded(key)
+		));
+		assert_ok!(Groth16Verifier::verify_proof(
+			RuntimeOrigin::signed(1),
+			0,
+			bounded(proof),
+			public_inputs(vec![])
+		));
+	});
+}
+
+#[test]
+fn rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		let (key, proof, inputs) = fixture(Curve::Bls12_381, 2);
+		assert_ok!(Groth16Verifier::register_verifying_key(
+			RuntimeOrigin::root(),
+			Curve::Bls12_381,
+			bounded(key)
+		));
+		let verify = |proof: Vec<u8>, inputs: Vec<Vec<u8>>| {
+			Groth16Verifier::verify_proof(
+				RuntimeOrigin::signed(1),
+				0,
+				bounded(proof),
+				public_inputs(inputs),
+			)
+		};
+
+		// the proof is for different public inputs.
+		let swapped = vec![inputs[1].clone(), inputs[0].clone()];
+		assert_noop!(verify(proof.clone(), swapped), Error::<Test>::InvalidProof);
+		// the proof is not for this key.
+		let (_, other_proof, _) = fixture(Curve::Bls12_381, 3);
+		assert_noop!(verify(other_proof, inputs.clone()), Error::<Test>::InvalidProof);
+		assert_noop!(
+			verify(proof.clone(), inputs[..1].to_vec()),
+			Error::<Test>::WrongPublicInputCount
+		);
+		assert_noop!(
+			verify(proof[..proof.len() - 1].to_vec(), inputs.clone()),
+			Error::<Test>::MalformedProof
+		);
+		// a scalar larger than the modulus.
+		assert_noop!(
+			verify(proof.clone(), vec![inputs[0].clone(), vec![0xff; 32]]),
+			Error::<Test>::MalformedPublicInput
+		);
+		assert_noop!(
+			Groth16Verifier::verify_proof(
+				RuntimeOrigin::signed(1),
+				1,
+				bounded(proof),
+				public_inputs(inputs)
+			),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn registering_keys_is_checked() {
+	new_test_ext().execute_with(|| {
+		let (key, _, _) = fixture(Curve::Bls12_377, 2);
+		assert_noop!(
+			Groth16Verifier::register_verifying_key(
+				RuntimeOrigin::signed(1),
+				Curve::Bls12_377,
+				bounded(key.clone())
+			),
+			DispatchError::BadOrigin
+		);
+		// the key is on another curve.
+		assert_noop!(
+			Groth16Verifier::register_verifying_key(
+				RuntimeOrigin::root(),
+				Curve::BW6_761,
+				bounded(key.clone())
+			),
+			Error::<Test>::MalformedVerifyingKey
+		);
+		assert_noop!(
+			Groth16Verifier::register_verifying_key(
+				RuntimeOrigin::root(),
+				Curve::Bls12_377,
+				bounded(key[..key.len() - 1].to_vec())
+			),
+			Error::<Test>::MalformedVerifyingKey
+		);
+		let (key, _, _) = fixture(Curve::Bls12_377, 5);
+		assert_noop!(
+			Groth16Verifier::register_verifying_key(
+				RuntimeOrigin::root(),
+				Curve::Bls12_377,
+				bounded(key)
+			),
+			Error::<Test>::TooManyPublicInputs
+		);
+	});
+}
+
+#[test]
+fn removing_keys_works() {
+	new_test_ext().execute_with(|| {
+		let (key, proof, inputs) = fixture(Curve::BW6_761, 1);
+		assert_ok!(Groth16Verifier::register_verifying_key(
+			RuntimeOrigin::root(),
+			Curve::BW6_761,
+			bounded(key)
+		));
+
+		assert_noop!(
+			Groth16Verifier::remove_verifying_key(RuntimeOrigin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Groth16Verifier::remove_verifying_key(RuntimeOrigin::root(), 1),
+			Error::<Test>::UnknownVerifyingKey
+		);
+		assert_ok!(Groth16Verifier::remove_verifying_key(RuntimeOrigin::root(), 0));
+		System::assert_last_event(Event::VerifyingKeyRemoved { key_id: 0 }.into());
+		assert_eq!(VerifyingKeys::<Test>::get(0), None);
+
+		assert_noop!(
+			Groth16Verifier::verify_proof(
+				RuntimeOrigin::signed(1),
+				0,
+				bounded(proof),
+				public_inputs(inputs)
+			),
+			Error::<Test>::UnknownVerifyingKey
+		);
+	});
+}
+
+#[test]
+fn verify_trait_works() {
+	new_test_ext().execute_with(|| {
+		let (key, proof, inputs) = fixture(Curve::Bls12_381, 1);
+		assert_ok!(Groth16Verifier::register_verifying_key(
+			RuntimeOrigin::root(),
+			Curve::Bls12_381,
+			bounded(key)
+		));
+
+		assert_ok!(<Groth16Verifier as Verify>::verify(0, &proof, &inputs));
+		assert_eq!(
+			<Groth16Verifier as Verify>::verify(0, &proof, &[vec![0; 32]]),
+			Err(Error::<Test>::InvalidProof.into())
+		);
+		assert_eq!(
+			<Groth16Verifier as Verify>::verify(1, &proof, &inputs),
+			Err(Error::<Test>::UnknownVerifyingKey.into())
+		);
+		assert_eq!(
+			<Groth16Verifier as Verify>::verify_weight(1),
+			<() as WeightInfo>::verify_proof_bw6_761(1)
+		);
+	});
+}
+
+#[test]
+fn placeholder_weights_fit_into_a_block() {
+	use crate::weights::SubstrateWeight;
+	use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
+
+	// The maximal block weight of the Substrate node, with the maximal number of public inputs
+	// covered by the benchmarks.
+	let max_block = Weight::from_parts(2 * WEIGHT_REF_TIME_PER_SECOND, u64::MAX);
+	let n = 32;
+	for weight in [
+		SubstrateWeight::<Test>::register_verifying_key(n),
+		SubstrateWeight::<Test>::remove_verifying_key(),
+		SubstrateWeight::<Test>::verify_proof_bls12_381(n),
+		SubstrateWeight::<Test>::verify_proof_bls12_377(n),
+		SubstrateWeight::<Test>::verify_proof_bw6_761(n),
+	] {
+		assert!(weight.all_lt(max_block), "{:?} exceeds the block weight", weight);
+	}
+}
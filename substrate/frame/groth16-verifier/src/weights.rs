@@ -0,0 +1,147 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_groth16_verifier`
+//!
+//! PENDING GENERATION: the benchmark CLI has not been run for this pallet yet, so the weights
+//! below are conservative estimates. The command below rewrites this file, including
+//! the standard header, from the benchmarks on reference hardware.
+
+// Generate with:
+// ./target/production/substrate
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_groth16_verifier
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/groth16-verifier/src/weights.rs
+// --header=./HEADER-APACHE2
+// --template=./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_groth16_verifier`.
+pub trait WeightInfo {
+	fn register_verifying_key(n: u32, ) -> Weight;
+	fn remove_verifying_key() -> Weight;
+	fn verify_proof_bls12_381(n: u32, ) -> Weight;
+	fn verify_proof_bls12_377(n: u32, ) -> Weight;
+	fn verify_proof_bw6_761(n: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_groth16_verifier` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Groth16Verifier::NextKeyId` (r:1 w:1)
+	/// Proof: `Groth16Verifier::NextKeyId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:0 w:1)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn register_verifying_key(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000_000, 1489)
+			.saturating_add(Weight::from_parts(1_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:1)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	fn remove_verifying_key() -> Weight {
+		Weight::from_parts(15_635_000, 7580)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bls12_381(n: u32, ) -> Weight {
+		Weight::from_parts(200_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(5_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bls12_377(n: u32, ) -> Weight {
+		Weight::from_parts(220_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(5_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bw6_761(n: u32, ) -> Weight {
+		Weight::from_parts(600_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(15_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `Groth16Verifier::NextKeyId` (r:1 w:1)
+	/// Proof: `Groth16Verifier::NextKeyId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:0 w:1)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn register_verifying_key(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000_000, 1489)
+			.saturating_add(Weight::from_parts(1_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:1)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	fn remove_verifying_key() -> Weight {
+		Weight::from_parts(15_635_000, 7580)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bls12_381(n: u32, ) -> Weight {
+		Weight::from_parts(200_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(5_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bls12_377(n: u32, ) -> Weight {
+		Weight::from_parts(220_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(5_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Groth16Verifier::VerifyingKeys` (r:1 w:0)
+	/// Proof: `Groth16Verifier::VerifyingKeys` (`max_values`: None, `max_size`: Some(4115), added: 6590, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn verify_proof_bw6_761(n: u32, ) -> Weight {
+		Weight::from_parts(600_000_000_000, 7580)
+			.saturating_add(Weight::from_parts(15_000_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
@@ -0,0 +1,118 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::{Pallet as Migrations, *};
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	/// Onboarding new migrations in `on_runtime_upgrade`.
+	#[benchmark]
+	fn onboard_new_mbms() {
+		#[block]
+		{
+			Migrations::<T>::onboard_new_mbms();
+		}
+	}
+
+	/// Stepping without ongoing migrations.
+	#[benchmark]
+	fn progress_mbms_none() {
+		#[block]
+		{
+			Migrations::<T>::progress_mbms();
+		}
+	}
+
+	/// Reaching the end of the migrations.
+	#[benchmark]
+	fn exec_migration_completed() {
+		let cursor = ActiveCursor {
+			index: T::Migrations::len(),
+			inner_cursor: None,
+			started_at: 0u32.into(),
+		};
+		Cursor::<T>::set(Some(cursor.clone().into()));
+		let mut meter = WeightMeter::with_limit(Migrations::<T>::exec_migration_max_weight());
+
+		#[block]
+		{
+			Migrations::<T>::exec_migration(cursor, false, &mut meter);
+		}
+
+		assert!(Cursor::<T>::get().is_none());
+	}
+
+	/// Skipping a migration that already completed.
+	#[benchmark]
+	fn exec_migration_skipped_historic() -> Result<(), BenchmarkError> {
+		let id = T::Migrations::nth_id(0).ok_or(BenchmarkError::Weightless)?;
+		let id: IdentifierOf<T> = id.try_into().map_err(|_| "Identifier too long")?;
+		Historic::<T>::insert(&id, ());
+		let cursor = ActiveCursor { index: 0, inner_cursor: None, started_at: 0u32.into() };
+		let mut meter = WeightMeter::with_limit(Migrations::<T>::exec_migration_max_weight());
+
+		#[block]
+		{
+			Migrations::<T>::exec_migration(cursor, false, &mut meter);
+		}
+
+		Ok(())
+	}
+
+	/// Forcing the cursor.
+	#[benchmark]
+	fn force_set_cursor() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Cursor::<T>::set(Some(MigrationCursor::Stuck));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, None);
+
+		assert!(Cursor::<T>::get().is_none());
+		Ok(())
+	}
+
+	/// Clearing `n` completed migrations.
+	#[benchmark]
+	fn clear_historic(n: Linear<0, 256>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let ids = (0..n)
+			.map(|i| {
+				let id: IdentifierOf<T> = i.encode().try_into().expect("IdentifierMaxLen >= 4");
+				Historic::<T>::insert(&id, ());
+				id
+			})
+			.collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, ids);
+
+		assert_eq!(Historic::<T>::iter().count(), 0);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Migrations, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,555 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-Block Migrations Pallet
+//!
+//! Executes [`SteppedMigration`](frame_support::migrations::SteppedMigration)s across multiple
+//! blocks.
+//!
+//! ## Overview
+//!
+//! Migrations that touch too much storage to fit into a single block are written as stepped
+//! migrations: each step does a bounded amount of work and returns a cursor to continue from. This
+//! pallet keeps track of the active migration and its cursor and executes the steps within a
+//! weight budget of [`Config::MaxServiceWeight`] per block.
+//!
+//! The migrations of [`Config::Migrations`] are onboarded in `on_runtime_upgrade`. From then on,
+//! `frame-executive` steps them once the inherents of every block are applied, through the
+//! [`MultiStepMigrator`](frame_support::migrations::MultiStepMigrator) implementation of this
+//! pallet. It suspends all transactions and `on_idle` until they are completed. Only inherents are
+//! applied in the meantime, since transactions could observe or create inconsistent state.
+//!
+//! Completed migrations are recorded in [`Historic`] and are never executed again, even if they
+//! are still part of [`Config::Migrations`] in a later runtime upgrade.
+//!
+//! ### Failure handling
+//!
+//! A migration fails if a step returns an error, if it takes longer than its
+//! [`max_steps`](frame_support::migrations::SteppedMigration::max_steps) or if the runtime is
+//! upgraded while migrations are still ongoing. The storage changes of the failing step are
+//! rolled back and [`Config::FailedMigrationHandler`] decides how to proceed: keep the migrations
+//! stuck, resume normal operation (possibly in safe mode) or skip the migration.
+//!
+//! Stuck migrations are still considered ongoing, so normal transactions stay suspended. Only
+//! operational transactions are applied again, so that governance can recover through
+//! [`Pallet::force_set_cursor`] and [`Pallet::clear_historic`], which are operational themselves.
+//!
+//! ### Try-runtime
+//!
+//! Since `try-runtime` only executes a single runtime upgrade, the pallet drives the onboarded
+//! migrations to completion in
+//! [`MultiStepMigrator::try_run_to_completion`](frame_support::migrations::MultiStepMigrator),
+//! including the pre- and post-upgrade checks of every migration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::ops::ControlFlow;
+use frame_support::{
+	defensive, defensive_assert,
+	migrations::{
+		FailedMigrationHandler, FailedMigrationHandling, MultiStepMigrator, SteppedMigrationError,
+		SteppedMigrations,
+	},
+	traits::Get,
+	weights::{Weight, WeightMeter},
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, Pallet as System};
+use sp_runtime::{traits::One, Saturating};
+use sp_std::vec::Vec;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// Points to the next migration to execute.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+pub enum MigrationCursor<Cursor, BlockNumber> {
+	/// Points to the currently active migration and its inner cursor.
+	Active(ActiveCursor<Cursor, BlockNumber>),
+	/// A migration failed and the chain is frozen until governance intervenes.
+	Stuck,
+}
+
+impl<Cursor, BlockNumber> MigrationCursor<Cursor, BlockNumber> {
+	/// The active cursor, if any.
+	pub fn as_active(&self) -> Option<&ActiveCursor<Cursor, BlockNumber>> {
+		match self {
+			MigrationCursor::Active(active) => Some(active),
+			MigrationCursor::Stuck => None,
+		}
+	}
+}
+
+impl<Cursor, BlockNumber> From<ActiveCursor<Cursor, BlockNumber>>
+	for MigrationCursor<Cursor, BlockNumber>
+{
+	fn from(active: ActiveCursor<Cursor, BlockNumber>) -> Self {
+		MigrationCursor::Active(active)
+	}
+}
+
+/// Points to the currently active migration and its inner cursor.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct ActiveCursor<Cursor, BlockNumber> {
+	/// The index of the migration in [`Config::Migrations`].
+	pub index: u32,
+	/// The cursor of the migration at `index`.
+	///
+	/// `None` if the migration did not do any step yet.
+	pub inner_cursor: Option<Cursor>,
+	/// The block in which the migration started.
+	pub started_at: BlockNumber,
+}
+
+impl<Cursor, BlockNumber> ActiveCursor<Cursor, BlockNumber> {
+	/// Advance to the next migration, starting in `current_block`.
+	pub(crate) fn goto_next_migration(&mut self, current_block: BlockNumber) {
+		self.index.saturating_inc();
+		self.inner_cursor = None;
+		self.started_at = current_block;
+	}
+}
+
+/// The encoded cursor of a migration.
+pub type RawCursorOf<T> = BoundedVec<u8, <T as Config>::CursorMaxLen>;
+
+/// The encoded identifier of a migration.
+pub type IdentifierOf<T> = BoundedVec<u8, <T as Config>::IdentifierMaxLen>;
+
+/// The cursor stored by the pallet.
+pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// The active cursor stored by the pallet.
+pub type ActiveCursorOf<T> = ActiveCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The multi-block migrations to execute after a runtime upgrade.
+		///
+		/// Should only be changed in a runtime upgrade once all previous migrations completed, that
+		/// is when [`Cursor`] is `None`.
+		type Migrations: SteppedMigrations;
+
+		/// The maximal length of an encoded cursor.
+		#[pallet::constant]
+		type CursorMaxLen: Get<u32>;
+
+		/// The maximal length of an encoded migration identifier.
+		#[pallet::constant]
+		type IdentifierMaxLen: Get<u32>;
+
+		/// Decides how to proceed after a migration failed.
+		type FailedMigrationHandler: FailedMigrationHandler;
+
+		/// The maximal weight to spend on migrations in each block.
+		#[pallet::constant]
+		type MaxServiceWeight: Get<Weight>;
+
+		/// The origin that may force the migration state.
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics and hooks in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The active migration and its cursor.
+	///
+	/// `None` if no migrations are ongoing.
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// The identifiers of all completed migrations.
+	///
+	/// Migrations in this set are skipped, even if they are still part of [`Config::Migrations`].
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A runtime upgrade onboarded `migrations` new migrations.
+		UpgradeStarted { migrations: u32 },
+		/// All migrations of the current runtime upgrade completed.
+		UpgradeCompleted,
+		/// A migration failed and the failure was handled by [`Config::FailedMigrationHandler`].
+		UpgradeFailed,
+		/// The migration at `index` was skipped, since it already completed in the past.
+		MigrationSkipped { index: u32 },
+		/// The migration at `index` did a step after `took` blocks.
+		MigrationAdvanced { index: u32, took: BlockNumberFor<T> },
+		/// The migration at `index` completed after `took` blocks.
+		MigrationCompleted { index: u32, took: BlockNumberFor<T> },
+		/// The migration at `index` failed after `took` blocks.
+		MigrationFailed { index: u32, took: BlockNumberFor<T> },
+		/// `count` identifiers were removed from [`Historic`].
+		HistoricCleared { count: u32 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// `frame-executive` checks whether migrations are ongoing before every extrinsic. Only
+			// the first check of a block reads `Cursor` from the database.
+			T::WeightInfo::progress_mbms_none()
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			Self::onboard_new_mbms()
+		}
+
+		#[cfg(feature = "std")]
+		fn integrity_test() {
+			let max_block = T::BlockWeights::get().max_block;
+			assert!(
+				T::MaxServiceWeight::get().all_lte(max_block),
+				"`MaxServiceWeight` must not exceed the maximal block weight",
+			);
+
+			for n in 0..T::Migrations::len() {
+				let id = T::Migrations::nth_id(n).expect("`nth_id` is defined up to `len`");
+				assert!(
+					id.len() <= T::IdentifierMaxLen::get() as usize,
+					"The identifier of migration {} exceeds `IdentifierMaxLen`",
+					n,
+				);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the migration cursor, to force start, stop or skip migrations.
+		///
+		/// Only meant as an emergency measure. Does not emit the events of the affected
+		/// migrations.
+		#[pallet::call_index(0)]
+		#[pallet::weight((T::WeightInfo::force_set_cursor(), DispatchClass::Operational))]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<CursorOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Cursor::<T>::set(cursor);
+
+			Ok(())
+		}
+
+		/// Remove `ids` from the set of completed migrations, so that they can be executed again.
+		#[pallet::call_index(1)]
+		#[pallet::weight((
+			T::WeightInfo::clear_historic(ids.len() as u32),
+			DispatchClass::Operational,
+		))]
+		pub fn clear_historic(origin: OriginFor<T>, ids: Vec<IdentifierOf<T>>) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let mut count = 0u32;
+			for id in ids {
+				if Historic::<T>::take(id).is_some() {
+					count.saturating_inc();
+				}
+			}
+			Self::deposit_event(Event::HistoricCleared { count });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Start executing the migrations of [`Config::Migrations`].
+	fn onboard_new_mbms() -> Weight {
+		if let Some(cursor) = Cursor::<T>::get() {
+			log::error!(target: LOG_TARGET, "Runtime upgraded while migrations are ongoing");
+			Self::upgrade_failed(cursor.as_active().map(|active| active.index));
+			return T::WeightInfo::onboard_new_mbms()
+		}
+
+		let migrations = T::Migrations::len();
+		log::debug!(target: LOG_TARGET, "Onboarding {} new migrations", migrations);
+
+		if migrations > 0 {
+			Cursor::<T>::set(Some(
+				ActiveCursor { index: 0, inner_cursor: None, started_at: Self::next_block() }
+					.into(),
+			));
+			Self::deposit_event(Event::UpgradeStarted { migrations });
+		}
+
+		T::WeightInfo::onboard_new_mbms()
+	}
+
+	/// Execute the active migrations within the budget of [`Config::MaxServiceWeight`].
+	fn progress_mbms() -> Weight {
+		let mut meter = WeightMeter::with_limit(T::MaxServiceWeight::get());
+		meter.consume(T::WeightInfo::progress_mbms_none());
+
+		let mut cursor = match Cursor::<T>::get() {
+			None => return meter.consumed(),
+			Some(MigrationCursor::Active(cursor)) => cursor,
+			Some(MigrationCursor::Stuck) => {
+				log::error!(target: LOG_TARGET, "Migrations are stuck");
+				return meter.consumed()
+			},
+		};
+
+		// Bounded to guard against an endless loop. Migrations are expected to take multiple blocks
+		// each, so completing a few per block is plenty.
+		for i in 0..8 {
+			match Self::exec_migration(cursor, i == 0, &mut meter) {
+				None => return meter.consumed(),
+				Some(ControlFlow::Continue(next_cursor)) => cursor = next_cursor,
+				Some(ControlFlow::Break(last_cursor)) => {
+					cursor = last_cursor;
+					break
+				},
+			}
+		}
+
+		Cursor::<T>::set(Some(cursor.into()));
+		meter.consumed()
+	}
+
+	/// Execute the next action of the migration that `cursor` points to.
+	///
+	/// Returns `None` if the cursor was already updated in storage because all migrations
+	/// completed or a migration failed. Otherwise the new cursor, which may be stepped further in
+	/// this block on `Continue`.
+	fn exec_migration(
+		mut cursor: ActiveCursorOf<T>,
+		is_first: bool,
+		meter: &mut WeightMeter,
+	) -> Option<ControlFlow<ActiveCursorOf<T>, ActiveCursorOf<T>>> {
+		// The bookkeeping of all outcomes costs about the same, so the maximum is consumed upfront.
+		if meter.try_consume(Self::exec_migration_max_weight()).is_err() {
+			defensive_assert!(!is_first, "`MaxServiceWeight` must cover at least one migration");
+			return Some(ControlFlow::Break(cursor))
+		}
+
+		let Some(id) = T::Migrations::nth_id(cursor.index) else {
+			// No more migrations to execute.
+			defensive_assert!(cursor.index == T::Migrations::len(), "Inconsistent migrations");
+			Cursor::<T>::kill();
+			Self::deposit_event(Event::UpgradeCompleted);
+			return None
+		};
+
+		let Ok(id): Result<IdentifierOf<T>, _> = id.try_into() else {
+			defensive!("`integrity_test` ensures that all identifiers fit `IdentifierMaxLen`");
+			Self::upgrade_failed(Some(cursor.index));
+			return None
+		};
+
+		if Historic::<T>::contains_key(&id) {
+			Self::deposit_event(Event::MigrationSkipped { index: cursor.index });
+			cursor.goto_next_migration(System::<T>::block_number());
+			return Some(ControlFlow::Continue(cursor))
+		}
+
+		let max_steps = T::Migrations::nth_max_steps(cursor.index);
+		let next_cursor = T::Migrations::nth_transactional_step(
+			cursor.index,
+			cursor.inner_cursor.clone().map(|c| c.into_inner()),
+			meter,
+		);
+		let Some((max_steps, next_cursor)) = max_steps.zip(next_cursor) else {
+			defensive!("The migration exists, since `nth_id` returned its identifier");
+			Self::upgrade_failed(Some(cursor.index));
+			return None
+		};
+
+		let took = System::<T>::block_number().saturating_sub(cursor.started_at);
+		match next_cursor {
+			Ok(Some(next_cursor)) => {
+				let Ok(next_cursor) = next_cursor.try_into() else {
+					log::error!(
+						target: LOG_TARGET,
+						"Cursor of migration {} exceeds `CursorMaxLen`",
+						cursor.index,
+					);
+					Self::migration_failed(cursor.index, took);
+					return None
+				};
+				Self::deposit_event(Event::MigrationAdvanced { index: cursor.index, took });
+				cursor.inner_cursor = Some(next_cursor);
+
+				if max_steps.map_or(false, |max| took > max.into()) {
+					Self::migration_failed(cursor.index, took);
+					None
+				} else {
+					// A migration does at most one step per block.
+					Some(ControlFlow::Break(cursor))
+				}
+			},
+			Ok(None) => {
+				Self::deposit_event(Event::MigrationCompleted { index: cursor.index, took });
+				Historic::<T>::insert(&id, ());
+				cursor.goto_next_migration(System::<T>::block_number());
+				Some(ControlFlow::Continue(cursor))
+			},
+			Err(SteppedMigrationError::InsufficientWeight { required }) => {
+				if is_first || required.any_gt(meter.limit()) {
+					// Not even the whole budget is enough, so the migration can never progress.
+					Self::migration_failed(cursor.index, took);
+					None
+				} else {
+					// Retry with a fresh budget in the next block.
+					Some(ControlFlow::Break(cursor))
+				}
+			},
+			Err(SteppedMigrationError::InvalidCursor | SteppedMigrationError::Failed) => {
+				Self::migration_failed(cursor.index, took);
+				None
+			},
+		}
+	}
+
+	/// The migration at `index` failed after `took` blocks.
+	fn migration_failed(index: u32, took: BlockNumberFor<T>) {
+		log::error!(target: LOG_TARGET, "Migration {} failed after {:?} blocks", index, took);
+		Self::deposit_event(Event::MigrationFailed { index, took });
+		Self::upgrade_failed(Some(index));
+	}
+
+	/// Let [`Config::FailedMigrationHandler`] decide how to proceed after a migration failed.
+	fn upgrade_failed(migration: Option<u32>) {
+		Self::deposit_event(Event::UpgradeFailed);
+
+		match T::FailedMigrationHandler::failed(migration) {
+			FailedMigrationHandling::KeepStuck => Cursor::<T>::set(Some(MigrationCursor::Stuck)),
+			FailedMigrationHandling::ForceUnstuck => Cursor::<T>::kill(),
+			FailedMigrationHandling::Ignore => match migration {
+				Some(index) => Cursor::<T>::set(Some(
+					ActiveCursor {
+						index: index.saturating_add(1),
+						inner_cursor: None,
+						started_at: Self::next_block(),
+					}
+					.into(),
+				)),
+				None => Cursor::<T>::kill(),
+			},
+		}
+	}
+
+	/// The block in which a migration that is set up now does its first step.
+	///
+	/// This is also the case for `on_runtime_upgrade`, which runs before the number of the new
+	/// block is set.
+	fn next_block() -> BlockNumberFor<T> {
+		System::<T>::block_number().saturating_add(One::one())
+	}
+
+	/// The maximal bookkeeping weight of [`Self::exec_migration`].
+	pub fn exec_migration_max_weight() -> Weight {
+		// Advancing, completing or failing a migration does the reads of skipping it, plus the
+		// writes of `Historic` and `Cursor`.
+		T::WeightInfo::exec_migration_skipped_historic()
+			.max(T::WeightInfo::exec_migration_completed())
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+
+	/// Run all onboarded migrations to completion, giving each step a full budget.
+	#[cfg(feature = "try-runtime")]
+	fn run_to_completion(checks: bool) -> Result<Weight, sp_runtime::TryRuntimeError> {
+		let mut weight = Weight::zero();
+		let mut pre_upgrade_state: Option<(u32, Vec<u8>)> = None;
+
+		loop {
+			let cursor = match Cursor::<T>::get() {
+				None => break,
+				Some(MigrationCursor::Active(cursor)) => cursor,
+				Some(MigrationCursor::Stuck) => return Err("Migrations are stuck".into()),
+			};
+			let index = cursor.index;
+			let completed = || {
+				T::Migrations::nth_id(index)
+					.and_then(|id| IdentifierOf::<T>::try_from(id).ok())
+					.map_or(false, Historic::<T>::contains_key)
+			};
+
+			let started = pre_upgrade_state.as_ref().map_or(false, |(i, _)| *i == index);
+			if checks && !started && !completed() {
+				pre_upgrade_state =
+					T::Migrations::nth_pre_upgrade(index).transpose()?.map(|state| (index, state));
+			}
+
+			let mut meter = WeightMeter::with_limit(T::MaxServiceWeight::get());
+			match Self::exec_migration(cursor, true, &mut meter) {
+				None => (),
+				Some(ControlFlow::Continue(next) | ControlFlow::Break(next)) =>
+					Cursor::<T>::set(Some(next.into())),
+			}
+			weight.saturating_accrue(meter.consumed());
+
+			// Moving past the last index is the completion of all migrations, not of a single one.
+			let moved_on =
+				Cursor::<T>::get().and_then(|c| c.as_active().map(|c| c.index)) != Some(index);
+			if moved_on && index < T::Migrations::len() {
+				if !completed() {
+					return Err("Migration failed".into())
+				}
+				if let Some((_, state)) = pre_upgrade_state.take().filter(|(i, _)| *i == index) {
+					T::Migrations::nth_post_upgrade(index, state).transpose()?;
+				}
+			}
+		}
+
+		Ok(weight)
+	}
+}
+
+impl<T: Config> MultiStepMigrator for Pallet<T> {
+	fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	fn stuck() -> bool {
+		matches!(Cursor::<T>::get(), Some(MigrationCursor::Stuck))
+	}
+
+	fn step() -> Weight {
+		Self::progress_mbms()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn try_run_to_completion(checks: bool) -> Result<Weight, sp_runtime::TryRuntimeError> {
+		Self::run_to_completion(checks)
+	}
+}
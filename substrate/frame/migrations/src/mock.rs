@@ -0,0 +1,199 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the migrations pallet.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_migrations;
+
+use frame_support::{
+	migrations::{FailedMigrationHandler, FailedMigrationHandling, SteppedMigrations},
+	parameter_types,
+	traits::{ConstU32, ConstU64, Everything, OnFinalize, OnInitialize},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Migrations: pallet_migrations,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// What a mocked migration does once it did its number of steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockedMigrationKind {
+	/// Complete.
+	SucceedAfter,
+	/// Fail.
+	FailAfter,
+	/// Keep going, until it exceeds its maximal number of steps.
+	TimeoutAfter,
+	/// Require more weight than is available.
+	HighWeightAfter(Weight),
+}
+pub use MockedMigrationKind::*;
+
+parameter_types! {
+	/// The migrations of [`MockedMigrations`], with their number of steps.
+	pub static MockedMigrationList: Vec<(MockedMigrationKind, u32)> = vec![];
+	/// How [`MockedFailedMigrationHandler`] handles failed migrations.
+	pub static FailedMigrationResponse: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
+	pub const MaxServiceWeight: Weight = Weight::from_parts(1_000_000_000, 1_000_000);
+}
+
+/// Migrations that are configured at runtime through [`MockedMigrationList`].
+pub struct MockedMigrations;
+impl SteppedMigrations for MockedMigrations {
+	fn len() -> u32 {
+		MockedMigrationList::get().len() as u32
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		MockedMigrationList::get()
+			.get(n as usize)
+			.map(|(kind, steps)| format!("{:?}({})@{}", kind, steps, n).into_bytes())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		MockedMigrationList::get().get(n as usize).map(|(kind, steps)| match kind {
+			TimeoutAfter => Some(*steps),
+			_ => None,
+		})
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		_meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let (kind, steps) = MockedMigrationList::get().get(n as usize).copied()?;
+		let count: u32 = match cursor {
+			Some(cursor) => match Decode::decode(&mut &cursor[..]) {
+				Ok(count) => count,
+				Err(_) => return Some(Err(SteppedMigrationError::InvalidCursor)),
+			},
+			None => 0,
+		};
+
+		if count < steps || kind == TimeoutAfter {
+			return Some(Ok(Some((count + 1).encode())))
+		}
+
+		Some(match kind {
+			SucceedAfter => Ok(None),
+			FailAfter => Err(SteppedMigrationError::Failed),
+			HighWeightAfter(required) => Err(SteppedMigrationError::InsufficientWeight { required }),
+			TimeoutAfter => unreachable!(),
+		})
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		MockedMigrationList::get().get(n as usize).map(|_| Ok(n.encode()))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		MockedMigrationList::get().get(n as usize).map(|_| {
+			frame_support::ensure!(state == n.encode(), "Unexpected pre-upgrade state");
+			Ok(())
+		})
+	}
+}
+
+/// Handles failed migrations as configured through [`FailedMigrationResponse`].
+pub struct MockedFailedMigrationHandler;
+impl FailedMigrationHandler for MockedFailedMigrationHandler {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationResponse::get()
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = MockedMigrations;
+	type CursorMaxLen = ConstU32<64>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type FailedMigrationHandler = MockedFailedMigrationHandler;
+	type MaxServiceWeight = MaxServiceWeight;
+	type ControlOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run a runtime upgrade with the given migrations.
+pub fn upgrade_with(migrations: Vec<(MockedMigrationKind, u32)>) {
+	MockedMigrationList::set(migrations);
+	<Migrations as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+}
+
+/// Produce blocks until `n`, stepping the migrations like `frame-executive` does.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		if System::block_number() > 1 {
+			AllPalletsWithSystem::on_finalize(System::block_number());
+		}
+		System::set_block_number(System::block_number() + 1);
+		AllPalletsWithSystem::on_initialize(System::block_number());
+		if Migrations::ongoing() {
+			Migrations::step();
+		}
+	}
+}
@@ -0,0 +1,252 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the migrations pallet.
+
+#![cfg(test)]
+
+use crate::{
+	mock::{MockedMigrationKind::*, *},
+	Cursor, Event, FailedMigrationHandling, Historic, MigrationCursor,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, GetDispatchInfo},
+	migrations::{MultiStepMigrator, SteppedMigrations},
+	BoundedVec,
+};
+use sp_runtime::traits::BadOrigin;
+
+fn events() -> Vec<Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let RuntimeEvent::Migrations(e) = r.event { Some(e) } else { None })
+		.collect()
+}
+
+#[test]
+fn basic_works() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 0), (SucceedAfter, 1), (SucceedAfter, 2)]);
+		assert!(Migrations::ongoing());
+
+		run_to_block(10);
+		assert!(!Migrations::ongoing());
+
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 3 },
+				Event::MigrationCompleted { index: 0, took: 0 },
+				Event::MigrationAdvanced { index: 1, took: 0 },
+				Event::MigrationCompleted { index: 1, took: 1 },
+				Event::MigrationAdvanced { index: 2, took: 0 },
+				Event::MigrationAdvanced { index: 2, took: 1 },
+				Event::MigrationCompleted { index: 2, took: 2 },
+				Event::UpgradeCompleted,
+			]
+		);
+		assert_eq!(Historic::<Test>::iter().count(), 3);
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 0)]);
+		run_to_block(5);
+		System::reset_events();
+
+		upgrade_with(vec![(SucceedAfter, 0), (SucceedAfter, 1)]);
+		run_to_block(10);
+
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationSkipped { index: 0 },
+				Event::MigrationAdvanced { index: 1, took: 0 },
+				Event::MigrationCompleted { index: 1, took: 1 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_keeps_chain_stuck() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 0), (FailAfter, 1), (SucceedAfter, 0)]);
+		run_to_block(10);
+
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		// Only operational transactions resume, so that governance can recover.
+		assert!(Migrations::ongoing());
+		assert!(Migrations::stuck());
+		let call = crate::Call::<Test>::force_set_cursor { cursor: None };
+		assert_eq!(call.get_dispatch_info().class, DispatchClass::Operational);
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 3 },
+				Event::MigrationCompleted { index: 0, took: 0 },
+				Event::MigrationAdvanced { index: 1, took: 0 },
+				Event::MigrationFailed { index: 1, took: 1 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_can_force_unstuck() {
+	new_test_ext().execute_with(|| {
+		FailedMigrationResponse::set(FailedMigrationHandling::ForceUnstuck);
+		upgrade_with(vec![(FailAfter, 0), (SucceedAfter, 0)]);
+		run_to_block(10);
+
+		assert!(!Migrations::ongoing());
+		assert_eq!(Historic::<Test>::iter().count(), 0);
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_can_be_ignored() {
+	new_test_ext().execute_with(|| {
+		FailedMigrationResponse::set(FailedMigrationHandling::Ignore);
+		upgrade_with(vec![(FailAfter, 0), (SucceedAfter, 0)]);
+		run_to_block(10);
+
+		assert!(!Migrations::ongoing());
+		assert_eq!(Historic::<Test>::iter().count(), 1);
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 2 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+				Event::MigrationCompleted { index: 1, took: 0 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn migration_exceeding_max_steps_fails() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(TimeoutAfter, 2)]);
+		run_to_block(10);
+
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 1 },
+				Event::MigrationAdvanced { index: 0, took: 0 },
+				Event::MigrationAdvanced { index: 0, took: 1 },
+				Event::MigrationAdvanced { index: 0, took: 2 },
+				Event::MigrationAdvanced { index: 0, took: 3 },
+				Event::MigrationFailed { index: 0, took: 3 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn migration_exceeding_service_weight_fails() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(HighWeightAfter(MaxServiceWeight::get() * 2), 0)]);
+		run_to_block(10);
+
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			events(),
+			vec![
+				Event::UpgradeStarted { migrations: 1 },
+				Event::MigrationFailed { index: 0, took: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+	});
+}
+
+#[test]
+fn upgrade_during_migrations_fails() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 5)]);
+		run_to_block(2);
+
+		upgrade_with(vec![(SucceedAfter, 0)]);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn force_set_cursor_works() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(FailAfter, 0)]);
+		run_to_block(2);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+
+		assert_noop!(Migrations::force_set_cursor(RuntimeOrigin::signed(1), None), BadOrigin);
+		assert_ok!(Migrations::force_set_cursor(RuntimeOrigin::root(), None));
+		assert!(!Migrations::ongoing());
+	});
+}
+
+#[test]
+fn clear_historic_works() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 0), (SucceedAfter, 0)]);
+		run_to_block(2);
+		assert_eq!(Historic::<Test>::iter().count(), 2);
+
+		let id: BoundedVec<_, _> = MockedMigrations::nth_id(0).unwrap().try_into().unwrap();
+		assert_noop!(
+			Migrations::clear_historic(RuntimeOrigin::signed(1), vec![id.clone()]),
+			BadOrigin
+		);
+		assert_ok!(Migrations::clear_historic(RuntimeOrigin::root(), vec![id.clone(), id]));
+
+		assert_eq!(Historic::<Test>::iter().count(), 1);
+		System::assert_last_event(Event::HistoricCleared { count: 1 }.into());
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn try_run_to_completion_works() {
+	new_test_ext().execute_with(|| {
+		upgrade_with(vec![(SucceedAfter, 0), (SucceedAfter, 3)]);
+		assert_ok!(Migrations::try_run_to_completion(true));
+		assert!(!Migrations::ongoing());
+		assert_eq!(Historic::<Test>::iter().count(), 2);
+
+		upgrade_with(vec![(FailAfter, 1)]);
+		assert!(Migrations::try_run_to_completion(true).is_err());
+	});
+}
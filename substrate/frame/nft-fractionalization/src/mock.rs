@@ -107,6 +107,10 @@ impl pallet_assets::Config for Test {
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = ();
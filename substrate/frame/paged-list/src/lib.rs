@@ -51,6 +51,15 @@
 //! 5. **Draining** elements happens through the [`Pallet::drain`] iterator. Note that even
 //! *peeking* a value will already remove it.
 #![doc = docify::embed!("src/tests.rs", drain_works)]
+//! 6. **Random access** to a value by its index is provided by [`Pallet::get`]:
+#![doc = docify::embed!("src/tests.rs", get_works)]
+//! 7. **Removing** a range of values happens with [`Pallet::remove_range`]. It re-writes all
+//! values behind the range, so it should only be used close to the end of the list:
+#![doc = docify::embed!("src/tests.rs", remove_range_works)]
+//! 8. **Clearing** a long list can be spread over many blocks with [`Pallet::clear_within`], which
+//! removes as many pages as the given weight allows. The [`migrations::ClearList`] migration does
+//! this until the list is empty.
+#![doc = docify::embed!("src/tests.rs", clear_within_works)]
 //!
 //! ## Pallet API
 //!
@@ -65,16 +74,19 @@
 
 pub use pallet::*;
 
+pub mod migrations;
 pub mod mock;
 mod paged_list;
 mod tests;
 
 use codec::FullCodec;
+use core::ops::Range;
 use frame_support::{
 	pallet_prelude::StorageList,
 	traits::{PalletInfoAccess, StorageInstance},
+	weights::{Weight, WeightMeter},
 };
-pub use paged_list::StoragePagedList;
+pub use paged_list::{PageIndex, StoragePagedList, ValueIndex};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -124,6 +136,23 @@ impl<T: Config<I>, I: 'static> StorageList<T::Value> for Pallet<T, I> {
 	}
 }
 
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The value at `index`, see [`StoragePagedList::get`].
+	pub fn get(index: ValueIndex) -> Option<T::Value> {
+		List::<T, I>::get(index)
+	}
+
+	/// Remove the values in `range`, see [`StoragePagedList::remove_range`].
+	pub fn remove_range(range: Range<ValueIndex>) -> ValueIndex {
+		List::<T, I>::remove_range(range)
+	}
+
+	/// Remove pages from the front of the list, see [`StoragePagedList::clear_within`].
+	pub fn clear_within(meter: &mut WeightMeter, weight_per_page: Weight) -> bool {
+		List::<T, I>::clear_within(meter, weight_per_page)
+	}
+}
+
 /// Generates a unique storage prefix for each instance of the pallet.
 pub struct ListPrefix<T, I>(core::marker::PhantomData<(T, I)>);
 
@@ -0,0 +1,61 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migrations for the paged list pallet.
+
+use crate::{Config, Pallet};
+use codec::Encode;
+use core::marker::PhantomData;
+use frame_support::{
+	migrations::{SteppedMigration, SteppedMigrationError},
+	traits::{Get, PalletInfoAccess},
+	weights::WeightMeter,
+};
+
+/// Clears the list of an instance of the pallet, over as many blocks as it takes.
+///
+/// Every step removes as many pages as its weight allows, so lists of any length can be cleared.
+pub struct ClearList<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> SteppedMigration for ClearList<T, I> {
+	type Cursor = ();
+	type Identifier = [u8; 32];
+
+	fn id() -> Self::Identifier {
+		(b"PagedListClearList", Pallet::<T, I>::name()).using_encoded(sp_io::hashing::blake2_256)
+	}
+
+	fn step(
+		_cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let weight_per_page = T::DbWeight::get().writes(1);
+		// Reading the metadata and the first page, and storing the metadata.
+		let overhead = T::DbWeight::get().reads_writes(2, 1);
+		let required = overhead.saturating_add(weight_per_page);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+		meter.consume(overhead);
+
+		if Pallet::<T, I>::clear_within(meter, weight_per_page) {
+			Ok(None)
+		} else {
+			Ok(Some(()))
+		}
+	}
+}
@@ -24,17 +24,20 @@
 #![deny(unsafe_code)]
 
 use codec::{Decode, Encode, EncodeLike, FullCodec};
-use core::marker::PhantomData;
+use core::{marker::PhantomData, ops::Range};
 use frame_support::{
 	defensive,
-	storage::StoragePrefixedContainer,
+	storage::{StorageDecodeLength, StoragePrefixedContainer},
 	traits::{Get, StorageInstance},
+	weights::{Weight, WeightMeter},
 	CloneNoBound, DebugNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound,
 };
 use sp_runtime::traits::Saturating;
 use sp_std::prelude::*;
 
+/// The index of a page in a [`StoragePagedList`].
 pub type PageIndex = u32;
+/// The index of a value inside a page, or in a whole [`StoragePagedList`].
 pub type ValueIndex = u32;
 
 /// A paginated storage list.
@@ -216,6 +219,13 @@ pub(crate) fn delete_page<Prefix: StorageInstance>(index: PageIndex) {
 	sp_io::storage::clear(&key);
 }
 
+/// The number of values stored in the page with `index`, including already drained ones.
+// Does not live under `Page` since it only decodes the length of the page.
+pub(crate) fn page_len<Prefix: StorageInstance, Value: Encode>(index: PageIndex) -> Option<u32> {
+	<Vec<Value> as StorageDecodeLength>::decode_len(&page_key::<Prefix>(index))
+		.map(|len| len as u32)
+}
+
 /// Storage key of a page with `index`.
 // Does not live under `Page` since it does not require the `Value` generic.
 pub(crate) fn page_key<Prefix: StorageInstance>(index: PageIndex) -> Vec<u8> {
@@ -352,6 +362,116 @@ where
 		Self::read_meta()
 	}
 
+	/// The value at `index`, counting from the first value of the list.
+	///
+	/// Only the lengths of the pages in front of the value are read, and the page that holds it.
+	pub fn get(index: ValueIndex) -> Option<Value> {
+		let meta = Self::read_meta();
+		let (page, offset) = Self::locate(&meta, index)?;
+		Page::<Value>::from_storage::<Prefix>(page, offset)?.next()
+	}
+
+	/// Remove the values in `range`, counting from the first value of the list.
+	///
+	/// Returns the number of removed values. All values behind the start of the range are re-written
+	/// to close the gap, so this should only be used on a short tail of the list. Use
+	/// [`Self::clear_within`] to remove the list as a whole.
+	pub fn remove_range(range: Range<ValueIndex>) -> ValueIndex {
+		if range.is_empty() {
+			return 0
+		}
+		let mut meta = Self::read_meta();
+		let (start_page, start_offset) = match Self::locate(&meta, range.start) {
+			Some(location) => location,
+			None => return 0,
+		};
+
+		let mut values = Vec::new();
+		for index in start_page..=meta.last_page {
+			match Page::<Value>::from_storage::<Prefix>(index, 0) {
+				Some(page) => values.extend(page),
+				None => break,
+			}
+			delete_page::<Prefix>(index);
+		}
+
+		let start = start_offset as usize;
+		let end = start.saturating_add(range.len()).min(values.len());
+		let removed = values.drain(start..end).count() as ValueIndex;
+		if start_page == meta.first_page {
+			// Drop the values that were already drained, the re-written page starts with the first
+			// value of the list.
+			values.drain(..meta.first_value_offset as usize);
+			meta.first_value_offset = 0;
+		}
+
+		if values.is_empty() {
+			if start_page == meta.first_page {
+				meta.reset();
+				return removed
+			}
+			meta.last_page = start_page.saturating_sub(1);
+			meta.last_page_len = page_len::<Prefix, Value>(meta.last_page).unwrap_or_default();
+		}
+		let values_per_page = ValuesPerNewPage::get().max(1) as usize;
+		for (index, chunk) in (start_page..).zip(values.chunks(values_per_page)) {
+			sp_io::storage::set(&page_key::<Prefix>(index), &chunk.encode());
+			meta.last_page = index;
+			meta.last_page_len = chunk.len() as ValueIndex;
+		}
+		meta.store();
+
+		removed
+	}
+
+	/// Remove whole pages from the front of the list for as long as `meter` can pay
+	/// `weight_per_page` for each of them.
+	///
+	/// Returns whether the list is empty afterwards. This allows to remove a list of any length
+	/// over as many blocks as needed.
+	pub fn clear_within(meter: &mut WeightMeter, weight_per_page: Weight) -> bool {
+		let mut meta = Self::read_meta();
+		if page_len::<Prefix, Value>(meta.first_page).is_none() {
+			meta.reset();
+			return true
+		}
+
+		let first_page = meta.first_page;
+		loop {
+			if meter.try_consume(weight_per_page).is_err() {
+				if meta.first_page != first_page {
+					meta.store();
+				}
+				return false
+			}
+			delete_page::<Prefix>(meta.first_page);
+			if meta.first_page >= meta.last_page {
+				meta.reset();
+				return true
+			}
+			meta.first_page.saturating_inc();
+			meta.first_value_offset = 0;
+		}
+	}
+
+	/// The page and the offset inside of it of the value at `index`.
+	fn locate(
+		meta: &StoragePagedListMeta<Prefix, Value, ValuesPerNewPage>,
+		index: ValueIndex,
+	) -> Option<(PageIndex, ValueIndex)> {
+		let mut remaining = index;
+		let mut offset = meta.first_value_offset;
+		for page in meta.first_page..=meta.last_page {
+			let available = page_len::<Prefix, Value>(page)?.saturating_sub(offset);
+			if remaining < available {
+				return Some((page, offset.saturating_add(remaining)))
+			}
+			remaining.saturating_reduce(available);
+			offset = 0;
+		}
+		None
+	}
+
 	/// Return the elements of the list.
 	#[cfg(test)]
 	fn as_vec() -> Vec<Value> {
@@ -567,6 +687,106 @@ mod tests {
 		assert_eq!(got, want);
 	}
 
+	#[test]
+	fn get_works() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(List::get(0), None);
+			List::append_many(0..23);
+
+			for i in 0..23 {
+				assert_eq!(List::get(i), Some(i));
+			}
+			assert_eq!(List::get(23), None);
+
+			// Indices start at the first value that was not drained.
+			assert_eq!(List::drain().take(7).count(), 7);
+			assert_eq!(List::get(0), Some(7));
+			assert_eq!(List::get(15), Some(22));
+			assert_eq!(List::get(16), None);
+		});
+	}
+
+	#[test]
+	fn remove_range_works() {
+		TestExternalities::default().execute_with(|| {
+			List::append_many(0..23);
+			assert_eq!(List::drain().take(2).count(), 2);
+
+			// Inside of a page.
+			assert_eq!(List::remove_range(1..3), 2);
+			let mut expected = (2..23).collect::<Vec<_>>();
+			expected.drain(1..3);
+			assert_eq!(List::as_vec(), expected);
+
+			// Across pages.
+			assert_eq!(List::remove_range(4..12), 8);
+			expected.drain(4..12);
+			assert_eq!(List::as_vec(), expected);
+
+			// The range is cut off at the end of the list.
+			assert_eq!(List::remove_range(8..100), 3);
+			expected.truncate(8);
+			assert_eq!(List::as_vec(), expected);
+			assert_eq!(List::remove_range(8..100), 0);
+			assert_eq!(List::remove_range(3..3), 0);
+
+			// Appending continues behind the remaining values.
+			List::append_many(100..103);
+			expected.extend(100..103);
+			assert_eq!(List::as_vec(), expected);
+			assert_eq!(List::get(10), Some(102));
+
+			// Removing everything empties the list.
+			assert_eq!(List::remove_range(0..11), 11);
+			assert_eq!(List::as_vec(), Vec::<u32>::new());
+			assert_eq!(List::read_meta(), Default::default());
+		});
+	}
+
+	#[test]
+	fn remove_range_of_whole_tail_pages_works() {
+		TestExternalities::default().execute_with(|| {
+			List::append_many(0..15);
+
+			assert_eq!(List::remove_range(10..15), 5);
+			assert_eq!(List::as_vec(), (0..10).collect::<Vec<_>>());
+			let meta = List::read_meta();
+			assert_eq!((meta.last_page, meta.last_page_len), (1, 5));
+			assert!(!sp_io::storage::exists(&page_key::<Prefix>(2)));
+
+			List::append_one(10);
+			assert_eq!(List::as_vec(), (0..11).collect::<Vec<_>>());
+		});
+	}
+
+	#[test]
+	fn clear_within_works() {
+		TestExternalities::default().execute_with(|| {
+			let _g = StorageNoopGuard::default();
+			List::append_many(0..23);
+			let per_page = Weight::from_parts(1, 1);
+
+			// Two out of the five pages are removed.
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(2, 10));
+			assert!(!List::clear_within(&mut meter, per_page));
+			assert_eq!(List::as_vec(), (10..23).collect::<Vec<_>>());
+			assert!(!sp_io::storage::exists(&page_key::<Prefix>(1)));
+
+			// Nothing happens without weight.
+			let mut meter = WeightMeter::with_limit(Weight::zero());
+			assert!(!List::clear_within(&mut meter, per_page));
+			assert_eq!(List::as_vec(), (10..23).collect::<Vec<_>>());
+
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(3, 10));
+			assert!(List::clear_within(&mut meter, per_page));
+			assert_eq!(List::as_vec(), Vec::<u32>::new());
+			assert_eq!(meter.consumed(), Weight::from_parts(3, 3));
+
+			// Clearing an empty list is a no-op.
+			assert!(List::clear_within(&mut meter, per_page));
+		});
+	}
+
 	#[test]
 	fn peekable_drain_also_deletes() {
 		TestExternalities::default().execute_with(|| {
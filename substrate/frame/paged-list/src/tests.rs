@@ -83,6 +83,67 @@ fn drain_works() {
 	});
 }
 
+#[docify::export]
+#[test]
+fn get_works() {
+	test_closure(|| {
+		PagedList::append_many(0..10);
+
+		assert_eq!(PagedList::get(3), Some(3));
+		assert_eq!(PagedList::get(10), None);
+	});
+}
+
+#[docify::export]
+#[test]
+fn remove_range_works() {
+	test_closure(|| {
+		PagedList::append_many(0..10);
+
+		assert_eq!(PagedList::remove_range(2..8), 6);
+		assert_eq!(PagedList::iter().collect::<Vec<_>>(), vec![0, 1, 8, 9]);
+	});
+}
+
+#[docify::export]
+#[test]
+fn clear_within_works() {
+	use frame_support::weights::{Weight, WeightMeter};
+	test_closure(|| {
+		PagedList::append_many(0..100); // 20 pages with 5 values each.
+		let per_page = Weight::from_parts(1, 0);
+
+		// Only 8 pages fit into the first block.
+		let mut meter = WeightMeter::with_limit(Weight::from_parts(8, 0));
+		assert!(!PagedList::clear_within(&mut meter, per_page), "Not empty yet");
+		assert_eq!(PagedList::get(0), Some(40));
+
+		// The rest is removed in the next block.
+		let mut meter = WeightMeter::with_limit(Weight::from_parts(100, 0));
+		assert!(PagedList::clear_within(&mut meter, per_page), "Empty now");
+		assert_eq!(PagedList::iter().count(), 0);
+	});
+}
+
+#[test]
+fn clear_list_migration_works() {
+	use crate::migrations::ClearList;
+	use frame_support::{migrations::SteppedMigration, weights::WeightMeter};
+	test_closure(|| {
+		PagedList::append_many(0..100);
+		PagedList2::append_many(0..100);
+
+		// The mock has no database weights, so a single step clears the list.
+		let mut meter = WeightMeter::new();
+		assert_eq!(ClearList::<Test>::step(None, &mut meter), Ok(None));
+		assert_eq!(PagedList::iter().count(), 0);
+		// Other instances are not affected.
+		assert_eq!(PagedList2::iter().count(), 100);
+
+		assert_ne!(ClearList::<Test>::id(), ClearList::<Test, crate::Instance2>::id());
+	});
+}
+
 #[test]
 fn iter_independent_works() {
 	test_closure(|| {
@@ -0,0 +1,50 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the parameters pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_system::{EventRecord, Pallet as System};
+
+use crate::Pallet as Parameters;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	set_parameter {
+		let key_value = T::BenchmarkHelper::key_value();
+		let (key, new_value) = key_value.clone().into_parts();
+		let origin = T::AdminOrigin::try_successful_origin(&key)
+			.map_err(|_| BenchmarkError::Weightless)?;
+		// replacing a value decodes the old one and deposits it in the event as well.
+		let old_value = new_value.clone();
+		pallet::Parameters::<T>::set(&key, old_value.clone());
+	}: _<T::RuntimeOrigin>(origin, key_value)
+	verify {
+		assert_last_event::<T>(Event::Updated { key, old_value, new_value }.into());
+	}
+
+	impl_benchmark_test_suite!(Parameters, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Parameters Pallet
+//!
+//! Stores the values of runtime parameters, so they can be changed without a runtime upgrade.
+//!
+//! ## Overview
+//!
+//! The parameters are declared in groups with [`frame_support::dynamic_params`], each with a type
+//! and a default, and their aggregated key-value type is [`Config::RuntimeParameters`]. Every
+//! parameter implements [`Get`](frame_support::traits::Get) for its value, so it can configure
+//! other pallets in place of a constant declared with `parameter_types!`.
+//!
+//! A parameter is set, or reset to its default, with [`Pallet::set_parameter`]. The origin is
+//! checked by [`Config::AdminOrigin`] against the key of the parameter, so every group can have its
+//! own origin.
+//!
+//! The types of all parameters and their values are part of the metadata, through the call and the
+//! storage of this pallet.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use frame_support::traits::{
+	dynamic_params::{AggregatedKeyValue, RuntimeParameterStore},
+	EnsureOriginWithArg,
+};
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The key of a parameter of the runtime.
+pub type KeyOf<T> = <<T as Config>::RuntimeParameters as AggregatedKeyValue>::Key;
+
+/// The value of a parameter of the runtime.
+pub type ValueOf<T> = <<T as Config>::RuntimeParameters as AggregatedKeyValue>::Value;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The parameters of the runtime, usually declared with
+		/// [`frame_support::dynamic_params`].
+		type RuntimeParameters: AggregatedKeyValue;
+
+		/// The origin which may set the parameter with the given key.
+		type AdminOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, KeyOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for creating a parameter to set in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::RuntimeParameters>;
+	}
+
+	/// Creates the parameter set in benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<RuntimeParameters> {
+		/// A parameter together with a new value for it.
+		///
+		/// This should be the parameter with the largest encoded key and value, set to `Some` value,
+		/// as the weight of `set_parameter` is benchmarked with it.
+		fn key_value() -> RuntimeParameters;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The values of the parameters which have been set.
+	#[pallet::storage]
+	pub type Parameters<T: Config> =
+		StorageMap<_, Blake2_128Concat, KeyOf<T>, ValueOf<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A parameter was set, or reset to its default if `new_value` is `None`.
+		Updated { key: KeyOf<T>, old_value: Option<ValueOf<T>>, new_value: Option<ValueOf<T>> },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set a parameter to a new value, or reset it to its default if the value is `None`.
+		///
+		/// The origin must be `AdminOrigin` for the key of the parameter. Emits `Updated`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		pub fn set_parameter(
+			origin: OriginFor<T>,
+			key_value: T::RuntimeParameters,
+		) -> DispatchResult {
+			let (key, new_value) = key_value.into_parts();
+			T::AdminOrigin::ensure_origin(origin, &key)?;
+
+			let old_value = Parameters::<T>::mutate(&key, |value| {
+				sp_std::mem::replace(value, new_value.clone())
+			});
+
+			Self::deposit_event(Event::Updated { key, old_value, new_value });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> RuntimeParameterStore for Pallet<T> {
+	type AggregatedKeyValue = T::RuntimeParameters;
+
+	fn get(key: KeyOf<T>) -> Option<ValueOf<T>> {
+		Parameters::<T>::get(key)
+	}
+}
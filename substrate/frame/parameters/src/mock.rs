@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the parameters pallet.
+
+use crate as pallet_parameters;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, EnsureOrigin, EnsureOriginWithArg};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+pub type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		ModuleParameters: pallet_parameters::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+frame_support::dynamic_params! {
+	/// The parameters of the test runtime.
+	pub enum RuntimeParameters stored in crate::Pallet<Test> {
+		/// Parameters which only root may set.
+		pub mod pallet1: Pallet1 {
+			pub static Key1: u64 = 0;
+			pub static Key2: u32 = 1;
+		}
+		/// Parameters which any signed origin may set.
+		pub mod pallet2: Pallet2 {
+			pub static Key3: u128 = 2;
+		}
+	}
+}
+
+/// Root for the parameters of `pallet1`, any signed origin for those of `pallet2`.
+pub struct ParametersAdminOrigin;
+impl EnsureOriginWithArg<RuntimeOrigin, RuntimeParametersKey> for ParametersAdminOrigin {
+	type Success = ();
+
+	fn try_origin(
+		origin: RuntimeOrigin,
+		key: &RuntimeParametersKey,
+	) -> Result<Self::Success, RuntimeOrigin> {
+		match key {
+			RuntimeParametersKey::Pallet1(_) => EnsureRoot::<u64>::try_origin(origin),
+			RuntimeParametersKey::Pallet2(_) => EnsureSigned::<u64>::try_origin(origin).map(|_| ()),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(key: &RuntimeParametersKey) -> Result<RuntimeOrigin, ()> {
+		match key {
+			RuntimeParametersKey::Pallet1(_) => EnsureRoot::<u64>::try_successful_origin(),
+			RuntimeParametersKey::Pallet2(_) => EnsureSigned::<u64>::try_successful_origin(),
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_parameters::BenchmarkHelper<RuntimeParameters> for BenchmarkHelper {
+	fn key_value() -> RuntimeParameters {
+		pallet2::Parameters::Key3(pallet2::Key3, Some(u128::MAX)).into()
+	}
+}
+
+impl pallet_parameters::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeParameters = RuntimeParameters;
+	type AdminOrigin = ParametersAdminOrigin;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = RuntimeGenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the parameters pallet.
+
+use crate::{mock::*, Event};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::DispatchError;
+
+#[test]
+fn parameters_default_until_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(pallet1::Key1::get(), 0);
+		assert_eq!(pallet1::Key2::get(), 1);
+		assert_eq!(pallet2::Key3::get(), 2);
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::root(),
+			pallet1::Parameters::Key1(pallet1::Key1, Some(10)).into()
+		));
+		assert_eq!(pallet1::Key1::get(), 10);
+		// Other parameters are unaffected.
+		assert_eq!(pallet1::Key2::get(), 1);
+		assert_eq!(pallet2::Key3::get(), 2);
+	});
+}
+
+#[test]
+fn set_parameter_updates_and_resets() {
+	new_test_ext().execute_with(|| {
+		let key: RuntimeParametersKey = pallet1::ParametersKey::Key2(pallet1::Key2).into();
+		let value = |v| Some(RuntimeParametersValue::Pallet1(pallet1::ParametersValue::Key2(v)));
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::root(),
+			pallet1::Parameters::Key2(pallet1::Key2, Some(5)).into()
+		));
+		System::assert_last_event(
+			Event::Updated { key: key.clone(), old_value: None, new_value: value(5) }.into(),
+		);
+		assert_eq!(pallet1::Key2::get(), 5);
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::root(),
+			pallet1::Parameters::Key2(pallet1::Key2, Some(7)).into()
+		));
+		System::assert_last_event(
+			Event::Updated { key: key.clone(), old_value: value(5), new_value: value(7) }.into(),
+		);
+		assert_eq!(pallet1::Key2::get(), 7);
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::root(),
+			pallet1::Parameters::Key2(pallet1::Key2, None).into()
+		));
+		System::assert_last_event(
+			Event::Updated { key: key.clone(), old_value: value(7), new_value: None }.into(),
+		);
+		assert_eq!(pallet1::Key2::get(), 1);
+		assert_eq!(crate::Parameters::<Test>::get(key), None);
+	});
+}
+
+#[test]
+fn set_parameter_checks_origin_of_group() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ModuleParameters::set_parameter(
+				RuntimeOrigin::signed(1),
+				pallet1::Parameters::Key1(pallet1::Key1, Some(10)).into()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ModuleParameters::set_parameter(
+				RuntimeOrigin::none(),
+				pallet2::Parameters::Key3(pallet2::Key3, Some(10)).into()
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::signed(1),
+			pallet2::Parameters::Key3(pallet2::Key3, Some(10)).into()
+		));
+		assert_eq!(pallet2::Key3::get(), 10);
+	});
+}
@@ -16,13 +16,18 @@
 // limitations under the License.
 
 use crate::{
-	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, StorageVersion},
-	weights::{RuntimeDbWeight, Weight},
+	defensive,
+	storage::transactional::with_transaction,
+	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, SafeMode, StorageVersion},
+	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
+use scale_info::TypeInfo;
 use sp_core::Get;
 use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_std::marker::PhantomData;
+use sp_runtime::{traits::Zero, DispatchError, TransactionOutcome};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 /// Handles storage migration pallet versioning.
 ///
@@ -344,3 +349,337 @@ impl<P: Get<&'static str>, DbWeight: Get<RuntimeDbWeight>> frame_support::traits
 		Ok(())
 	}
 }
+
+/// A migration that can run over multiple blocks.
+///
+/// Each call to [`Self::step`] does a bounded amount of work and returns a cursor that is passed
+/// back in on the next call, possibly in a later block. The migration is done once `step` returns
+/// `Ok(None)`.
+pub trait SteppedMigration {
+	/// The cursor type that stores the progress of the migration between steps.
+	type Cursor: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of the migration.
+	///
+	/// It is recorded once the migration completed, so that it is never run twice.
+	type Identifier: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of this migration.
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of blocks this migration may take to complete.
+	///
+	/// The migration is considered failed once it takes longer. `None` means no limit.
+	fn max_steps() -> Option<u32> {
+		None
+	}
+
+	/// Do the next step of the migration, starting at `cursor`.
+	///
+	/// `cursor` is `None` on the first step. Returns the cursor to continue from, or `None` once
+	/// the migration completed. Must not consume more weight than `meter` allows; if not even a
+	/// single step fits, `InsufficientWeight` must be returned without doing any changes.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError>;
+
+	/// Same as [`Self::step`], but rolls back all storage changes of the step if it fails.
+	fn transactional_step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		// The outer error only signals that the transactional layer limit was reached.
+		with_transaction::<_, DispatchError, _>(move || match Self::step(cursor, meter) {
+			Ok(new_cursor) => TransactionOutcome::Commit(Ok(Ok(new_cursor))),
+			Err(err) => TransactionOutcome::Rollback(Ok(Err(err))),
+		})
+		.unwrap_or(Err(SteppedMigrationError::Failed))
+	}
+
+	/// Hook for checking the state before the migration starts.
+	///
+	/// The returned state is passed to [`Self::post_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	/// Hook for checking the state after the migration completed.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		Ok(())
+	}
+}
+
+/// Error that can occur in a [`SteppedMigration`] step.
+#[derive(Debug, Encode, Decode, MaxEncodedLen, PartialEq, Eq, Clone, TypeInfo)]
+pub enum SteppedMigrationError {
+	/// Not enough weight left to do a single step.
+	///
+	/// The step is retried in the next block, unless `required` exceeds the whole budget, in which
+	/// case the migration can never progress and is considered failed.
+	InsufficientWeight {
+		/// Weight needed for a single step.
+		required: Weight,
+	},
+	/// The cursor could not be decoded or does not point to valid progress.
+	InvalidCursor,
+	/// The migration failed for a migration specific reason.
+	Failed,
+}
+
+/// A list of [`SteppedMigration`]s, addressed by their index.
+///
+/// Implemented for single migrations and tuples of them. Cursors and identifiers are passed around
+/// in their encoded form, so that migrations with different types can be aggregated.
+pub trait SteppedMigrations {
+	/// The number of migrations.
+	fn len() -> u32;
+
+	/// The encoded identifier of the `n`th migration.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// The maximum number of blocks the `n`th migration may take.
+	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
+
+	/// Do a transactional step of the `n`th migration, starting at the encoded `cursor`.
+	///
+	/// Returns `None` if there is no `n`th migration.
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
+
+	/// Call [`SteppedMigration::pre_upgrade`] of the `n`th migration.
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>>;
+
+	/// Call [`SteppedMigration::post_upgrade`] of the `n`th migration.
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>>;
+}
+
+impl<T: SteppedMigration> SteppedMigrations for T {
+	fn len() -> u32 {
+		1
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		n.is_zero().then(|| T::id().encode())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		n.is_zero().then(T::max_steps)
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		if !n.is_zero() {
+			defensive!("nth_transactional_step should only be called with n==0");
+			return None
+		}
+
+		let cursor = match cursor {
+			Some(cursor) => match T::Cursor::decode(&mut &cursor[..]) {
+				Ok(cursor) => Some(cursor),
+				Err(_) => return Some(Err(SteppedMigrationError::InvalidCursor)),
+			},
+			None => None,
+		};
+
+		Some(T::transactional_step(cursor, meter).map(|cursor| cursor.map(|c| c.encode())))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		n.is_zero().then(T::pre_upgrade)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		n.is_zero().then(|| T::post_upgrade(state))
+	}
+}
+
+#[impl_for_tuples(1, 30)]
+impl SteppedMigrations for Tuple {
+	fn len() -> u32 {
+		for_tuples!( #( Tuple::len() )+* )
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_id(n - i)
+			}
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_max_steps(n - i)
+			}
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_transactional_step(n - i, cursor, meter)
+			}
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_pre_upgrade(n - i)
+			}
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_post_upgrade(n - i, state)
+			}
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+}
+
+/// Drives the multi-block migrations of the runtime.
+///
+/// Used by `frame-executive` to step the migrations once the inherents of a block are applied and
+/// to suspend normal transactions and `on_idle` while they are ongoing.
+pub trait MultiStepMigrator {
+	/// Whether migrations are ongoing, including stuck ones.
+	///
+	/// Transactions must not be processed while this returns `true`, since they could observe or
+	/// create inconsistent state. It is queried for every extrinsic, so implementations should
+	/// account for the weight of the query once per block.
+	fn ongoing() -> bool;
+
+	/// Whether the ongoing migrations are stuck after a failure.
+	///
+	/// Stuck migrations are no longer stepped. Operational transactions are processed again, so
+	/// that governance can recover the chain.
+	fn stuck() -> bool {
+		false
+	}
+
+	/// Do the next steps of the ongoing migrations and return the consumed weight.
+	///
+	/// Must respect its own weight budget.
+	fn step() -> Weight;
+
+	/// Run the ongoing migrations to completion, as if enough blocks were produced.
+	///
+	/// Runs the `pre_upgrade` and `post_upgrade` checks of every migration if `checks` is set.
+	#[cfg(feature = "try-runtime")]
+	fn try_run_to_completion(checks: bool) -> Result<Weight, sp_runtime::TryRuntimeError>;
+}
+
+impl MultiStepMigrator for () {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn step() -> Weight {
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn try_run_to_completion(_checks: bool) -> Result<Weight, sp_runtime::TryRuntimeError> {
+		Ok(Weight::zero())
+	}
+}
+
+/// How to proceed after a multi-block migration failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedMigrationHandling {
+	/// Stop the migrations and resume normal operation.
+	///
+	/// The storage may be left in an inconsistent state, so this should be combined with other
+	/// means of protection, like safe mode.
+	ForceUnstuck,
+	/// Keep the chain frozen in the failed migration, without executing any further step.
+	///
+	/// Only inherents and operational transactions are applied until governance resolves this, for
+	/// example by forcing the migration cursor or via a runtime upgrade.
+	KeepStuck,
+	/// Skip the failed migration and continue with the next one.
+	Ignore,
+}
+
+/// Decides how to proceed after a multi-block migration failed.
+pub trait FailedMigrationHandler {
+	/// Called when a multi-block migration failed.
+	///
+	/// `migration` is the index of the failed migration, if known.
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling;
+}
+
+/// Freezes the chain after a failed migration.
+///
+/// Normal transactions stay suspended, only inherents and operational transactions are applied
+/// until governance intervenes. See [`FailedMigrationHandling::KeepStuck`].
+pub struct FreezeChainOnFailedMigration;
+
+impl FailedMigrationHandler for FreezeChainOnFailedMigration {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationHandling::KeepStuck
+	}
+}
+
+/// Enters safe mode for `Duration` blocks after a failed migration and resumes normal operation.
+///
+/// Falls back to `Else` if safe mode could not be entered.
+pub struct EnterSafeModeOnFailedMigration<SM, Duration, Else>(PhantomData<(SM, Duration, Else)>);
+
+impl<SM: SafeMode, Duration: Get<SM::BlockNumber>, Else: FailedMigrationHandler>
+	FailedMigrationHandler for EnterSafeModeOnFailedMigration<SM, Duration, Else>
+{
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling {
+		match SM::enter(Duration::get()) {
+			Ok(()) => FailedMigrationHandling::ForceUnstuck,
+			Err(_) => Else::failed(migration),
+		}
+	}
+}
@@ -23,6 +23,7 @@ use crate::{
 		EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, KeyGenerator,
 		ReversibleKeyGenerator, TupleToEncodedIter,
 	},
+	weights::{Weight, WeightMeter},
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, FullEncode};
 use sp_core::storage::ChildInfo;
@@ -1014,6 +1015,30 @@ impl<T, OnRemoval> PrefixIterator<T, OnRemoval> {
 	}
 }
 
+impl<T, OnRemoval: PrefixIteratorOnRemoval> PrefixIterator<T, OnRemoval> {
+	/// Take the next items for as long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the items together with the cursor to resume after them, which is the raw key to
+	/// pass to [`Self::set_last_raw_key`] or to `iter_from`. The cursor is `None` once there are no
+	/// more items.
+	pub fn next_page(
+		&mut self,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<T>, Option<Vec<u8>>) {
+		let mut items = Vec::new();
+		loop {
+			if meter.try_consume(weight_per_item).is_err() {
+				return (items, Some(self.previous_key.clone()))
+			}
+			match self.next() {
+				Some(item) => items.push(item),
+				None => return (items, None),
+			}
+		}
+	}
+}
+
 impl<T, OnRemoval: PrefixIteratorOnRemoval> Iterator for PrefixIterator<T, OnRemoval> {
 	type Item = T;
 
@@ -1110,6 +1135,30 @@ impl<T> KeyPrefixIterator<T> {
 	}
 }
 
+impl<T> KeyPrefixIterator<T> {
+	/// Take the next keys for as long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the keys together with the cursor to resume after them, which is the raw key to
+	/// pass to [`Self::set_last_raw_key`] or to `iter_keys_from`. The cursor is `None` once there
+	/// are no more keys.
+	pub fn next_page(
+		&mut self,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<T>, Option<Vec<u8>>) {
+		let mut keys = Vec::new();
+		loop {
+			if meter.try_consume(weight_per_item).is_err() {
+				return (keys, Some(self.previous_key.clone()))
+			}
+			match self.next() {
+				Some(key) => keys.push(key),
+				None => return (keys, None),
+			}
+		}
+	}
+}
+
 impl<T> Iterator for KeyPrefixIterator<T> {
 	type Item = T;
 
@@ -1841,6 +1890,51 @@ mod test {
 		});
 	}
 
+	#[test]
+	fn prefix_iterator_next_page_works() {
+		TestExternalities::default().execute_with(|| {
+			use crate::{hash::Identity, storage::generator::map::StorageMap};
+			#[crate::storage_alias]
+			type MyStorageMap = StorageMap<MyModule, Identity, u64, u64>;
+
+			for i in 1..=5 {
+				MyStorageMap::insert(i, i * 10);
+			}
+			let per_item = Weight::from_parts(10, 1);
+
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(25, 10));
+			let (items, cursor) = MyStorageMap::iter().next_page(&mut meter, per_item);
+			assert_eq!(items, vec![(1, 10), (2, 20)]);
+			assert_eq!(cursor, Some(MyStorageMap::storage_map_final_key(2)));
+			assert_eq!(meter.consumed(), Weight::from_parts(20, 2));
+
+			// Resume where the last page ended.
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(30, 10));
+			let (keys, cursor) =
+				MyStorageMap::iter_keys_from(cursor.unwrap()).next_page(&mut meter, per_item);
+			assert_eq!(keys, vec![3, 4, 5]);
+			assert_eq!(cursor, Some(MyStorageMap::storage_map_final_key(5)));
+
+			// The last page is empty and ends the iteration.
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(30, 10));
+			let (items, cursor) =
+				MyStorageMap::iter_from(cursor.unwrap()).next_page(&mut meter, per_item);
+			assert!(items.is_empty());
+			assert_eq!(cursor, None);
+			assert_eq!(meter.consumed(), per_item);
+
+			// Nothing fits into an empty budget.
+			let mut meter = WeightMeter::with_limit(Weight::zero());
+			let (items, cursor) = MyStorageMap::iter().next_page(&mut meter, per_item);
+			assert!(items.is_empty());
+			let mut meter = WeightMeter::new();
+			let (items, cursor) =
+				MyStorageMap::iter_from(cursor.unwrap()).next_page(&mut meter, per_item);
+			assert_eq!(items.len(), 5);
+			assert_eq!(cursor, None);
+		});
+	}
+
 	#[test]
 	fn child_trie_prefixed_map_works() {
 		TestExternalities::default().execute_with(|| {
@@ -27,6 +27,7 @@ use crate::{
 		StorageAppend, StorageDecodeLength, StorageTryAppend,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInfoTrait, StorageInstance},
+	weights::{Weight, WeightMeter},
 	Never,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
@@ -440,6 +441,26 @@ where
 		<Self as MapWrapper>::Map::iter_from(starting_raw_key).convert_on_removal()
 	}
 
+	/// Enumerate the elements in the map after `cursor`, or from the start if it is `None`, for as
+	/// long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the elements together with the cursor to continue from, or `None` once all elements
+	/// have been enumerated. The cursor is a raw storage key, so a large map can be read over many
+	/// blocks or calls.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<(Key, Value)>, Option<Vec<u8>>) {
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		};
+		iter.next_page(meter, weight_per_item)
+	}
+
 	/// Enumerate all keys in the counted map.
 	///
 	/// If you alter the map while doing this, you'll get undefined results.
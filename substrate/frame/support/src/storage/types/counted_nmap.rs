@@ -26,6 +26,7 @@ use crate::{
 		KeyGenerator, PrefixIterator, StorageAppend, StorageDecodeLength,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 	Never,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
@@ -537,6 +538,26 @@ where
 		<Self as MapWrapper>::Map::iter_from(starting_raw_key).convert_on_removal()
 	}
 
+	/// Enumerate the elements in the map after `cursor`, or from the start if it is `None`, for as
+	/// long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the elements together with the cursor to continue from, or `None` once all elements
+	/// have been enumerated. The cursor is a raw storage key, so a large map can be read over many
+	/// blocks or calls.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<(Key::Key, Value)>, Option<Vec<u8>>) {
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		};
+		iter.next_page(meter, weight_per_item)
+	}
+
 	/// Enumerate all keys in the map in no particular order.
 	///
 	/// If you add or remove values to the map while doing this, you'll get undefined results.
@@ -1424,4 +1445,42 @@ mod test {
 			assert_eq!(A::count(), 5);
 		});
 	}
+
+	#[test]
+	fn iter_paged_resumes_from_cursor() {
+		type A = CountedStorageNMap<
+			Prefix,
+			(NMapKey<Twox64Concat, u16>, NMapKey<Twox64Concat, u16>),
+			u32,
+			OptionQuery,
+		>;
+
+		TestExternalities::default().execute_with(|| {
+			for i in 0..10u16 {
+				A::insert((i, i), i as u32);
+			}
+			let per_item = Weight::from_parts(1, 1);
+
+			let mut items = Vec::new();
+			let mut cursor = None;
+			let mut pages = 0;
+			loop {
+				let mut meter = WeightMeter::with_limit(Weight::from_parts(4, 4));
+				let (page, next_cursor) = A::iter_paged(cursor, &mut meter, per_item);
+				assert!(page.len() <= 4);
+				items.extend(page);
+				pages += 1;
+				match next_cursor {
+					Some(next_cursor) => cursor = Some(next_cursor),
+					None => break,
+				}
+			}
+
+			// Every element is returned exactly once.
+			items.sort();
+			assert_eq!(items, (0..10u16).map(|i| ((i, i), i as u32)).collect::<Vec<_>>());
+			assert_eq!(pages, 3);
+			assert_eq!(A::count(), 10);
+		});
+	}
 }
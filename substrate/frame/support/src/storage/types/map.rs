@@ -24,6 +24,7 @@ use crate::{
 		KeyLenOf, StorageAppend, StorageDecodeLength, StoragePrefixedMap, StorageTryAppend,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 	StorageHasher, Twox128,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
@@ -384,6 +385,26 @@ where
 		Self::iter_from(Self::hashed_key_for(starting_key))
 	}
 
+	/// Enumerate the elements in the map after `cursor`, or from the start if it is `None`, for as
+	/// long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the elements together with the cursor to continue from, or `None` once all elements
+	/// have been enumerated. The cursor is a raw storage key, so a large map can be read over many
+	/// blocks or calls.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<(Key, Value)>, Option<Vec<u8>>) {
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		};
+		iter.next_page(meter, weight_per_item)
+	}
+
 	/// Enumerate all keys in the map in no particular order.
 	///
 	/// If you alter the map while doing this, you'll get undefined results.
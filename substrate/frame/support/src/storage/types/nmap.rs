@@ -27,6 +27,7 @@ use crate::{
 		KeyGenerator, PrefixIterator, StorageAppend, StorageDecodeLength, StoragePrefixedMap,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
 use sp_metadata_ir::{StorageEntryMetadataIR, StorageEntryTypeIR};
@@ -507,6 +508,26 @@ where
 		<Self as crate::storage::IterableStorageNMap<Key, Value>>::iter_from(starting_raw_key)
 	}
 
+	/// Enumerate the elements in the map after `cursor`, or from the start if it is `None`, for as
+	/// long as `meter` can pay `weight_per_item` for each of them.
+	///
+	/// Returns the elements together with the cursor to continue from, or `None` once all elements
+	/// have been enumerated. The cursor is a raw storage key, so a large map can be read over many
+	/// blocks or calls.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> (Vec<(Key::Key, Value)>, Option<Vec<u8>>) {
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		};
+		iter.next_page(meter, weight_per_item)
+	}
+
 	/// Enumerate all keys in the map in no particular order.
 	///
 	/// If you add or remove values to the map while doing this, you'll get undefined results.
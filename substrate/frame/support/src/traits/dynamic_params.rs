@@ -0,0 +1,301 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for runtime parameters which can be changed without a runtime upgrade.
+//!
+//! The parameters of a runtime are declared with [`dynamic_params`](crate::dynamic_params), which
+//! aggregates them into a single key-value type implementing [`AggregatedKeyValue`], and are read
+//! from a [`RuntimeParameterStore`].
+
+use crate::dispatch::Parameter;
+use codec::MaxEncodedLen;
+
+/// The key of a parameter in the store of `S`.
+pub type KeyOf<S> =
+	<<S as RuntimeParameterStore>::AggregatedKeyValue as AggregatedKeyValue>::Key;
+
+/// The value of a parameter in the store of `S`.
+pub type ValueOf<S> =
+	<<S as RuntimeParameterStore>::AggregatedKeyValue as AggregatedKeyValue>::Value;
+
+/// The key of a parameter together with its new value, or with `None` to reset it to its default.
+pub trait AggregatedKeyValue: Parameter {
+	/// The key of a parameter.
+	type Key: Parameter + MaxEncodedLen;
+
+	/// The value of a parameter.
+	type Value: Parameter + MaxEncodedLen;
+
+	/// Split into the key and the new value.
+	fn into_parts(self) -> (Self::Key, Option<Self::Value>);
+}
+
+/// A store of the values of runtime parameters.
+pub trait RuntimeParameterStore {
+	/// The parameters in the store.
+	type AggregatedKeyValue: AggregatedKeyValue;
+
+	/// The value of the parameter `key`, or `None` if it has not been set.
+	fn get(key: KeyOf<Self>) -> Option<ValueOf<Self>>;
+}
+
+/// Declare runtime parameters which are kept in a [`RuntimeParameterStore`].
+///
+/// Parameters are declared in groups, each in its own module. Every parameter is a type
+/// implementing [`Get`](crate::traits::Get) for its value, which is the one in the store or the
+/// default if it has not been set, so it can be used in the configuration of a pallet like a
+/// constant declared with [`parameter_types`](crate::parameter_types).
+///
+/// ```ignore
+/// frame_support::dynamic_params! {
+/// 	/// The parameters of the runtime.
+/// 	pub enum RuntimeParameters stored in pallet_parameters::Pallet<Runtime> {
+/// 		/// Parameters of the treasury.
+/// 		pub mod treasury: Treasury {
+/// 			/// The portion of the treasury burnt every spend period.
+/// 			pub static BurnPortion: Permill = Permill::from_percent(1);
+/// 		}
+/// 	}
+/// }
+///
+/// impl pallet_treasury::Config for Runtime {
+/// 	type Burn = treasury::BurnPortion;
+/// 	// ...
+/// }
+/// ```
+///
+/// Every group module contains the parameter types and:
+/// - `Parameters`: a parameter of the group together with its new value.
+/// - `ParametersKey`: a parameter of the group.
+/// - `ParametersValue`: the value of a parameter of the group.
+///
+/// The enum `RuntimeParameters` implements [`AggregatedKeyValue`] with one variant for each group,
+/// whose keys and values are the enums `RuntimeParametersKey` and `RuntimeParametersValue`. They
+/// are the types the store of the parameters has to be configured with, and they tell the origin
+/// allowed to set a parameter which group it belongs to.
+#[macro_export]
+macro_rules! dynamic_params {
+	(
+		$(#[$attr:meta])*
+		$vis:vis enum $name:ident stored in $store:ty {
+			$(
+				$(#[$group_attr:meta])*
+				$group_vis:vis mod $group:ident : $variant:ident {
+					$(
+						$(#[$param_attr:meta])*
+						pub static $param:ident : $param_ty:ty = $default:expr;
+					)*
+				}
+			)*
+		}
+	) => {
+		$crate::__private::paste::paste! {
+			$(
+				$(#[$group_attr])*
+				$group_vis mod $group {
+					use super::*;
+					use $crate::traits::dynamic_params::{AggregatedKeyValue, RuntimeParameterStore};
+
+					$(
+						$(#[$param_attr])*
+						#[derive(
+							Clone,
+							PartialEq,
+							Eq,
+							$crate::__private::codec::Encode,
+							$crate::__private::codec::Decode,
+							$crate::__private::codec::MaxEncodedLen,
+							$crate::__private::scale_info::TypeInfo,
+							$crate::__private::RuntimeDebug,
+						)]
+						pub struct $param;
+
+						impl $crate::traits::Get<$param_ty> for $param {
+							fn get() -> $param_ty {
+								let key = [<$name Key>]::$variant(ParametersKey::$param($param));
+								match <$store as RuntimeParameterStore>::get(key) {
+									Some([<$name Value>]::$variant(
+										ParametersValue::$param(value),
+									)) => value,
+									_ => $default,
+								}
+							}
+						}
+					)*
+
+					#[doc = "A parameter of this group with its new value, or `None` to reset it."]
+					#[derive(
+						Clone,
+						PartialEq,
+						Eq,
+						$crate::__private::codec::Encode,
+						$crate::__private::codec::Decode,
+						$crate::__private::codec::MaxEncodedLen,
+						$crate::__private::scale_info::TypeInfo,
+						$crate::__private::RuntimeDebug,
+					)]
+					pub enum Parameters {
+						$(
+							$(#[$param_attr])*
+							$param($param, Option<$param_ty>),
+						)*
+					}
+
+					#[doc = "A parameter of this group."]
+					#[derive(
+						Clone,
+						PartialEq,
+						Eq,
+						$crate::__private::codec::Encode,
+						$crate::__private::codec::Decode,
+						$crate::__private::codec::MaxEncodedLen,
+						$crate::__private::scale_info::TypeInfo,
+						$crate::__private::RuntimeDebug,
+					)]
+					pub enum ParametersKey {
+						$(
+							$(#[$param_attr])*
+							$param($param),
+						)*
+					}
+
+					#[doc = "The value of a parameter of this group."]
+					#[derive(
+						Clone,
+						PartialEq,
+						Eq,
+						$crate::__private::codec::Encode,
+						$crate::__private::codec::Decode,
+						$crate::__private::codec::MaxEncodedLen,
+						$crate::__private::scale_info::TypeInfo,
+						$crate::__private::RuntimeDebug,
+					)]
+					pub enum ParametersValue {
+						$(
+							$(#[$param_attr])*
+							$param($param_ty),
+						)*
+					}
+
+					impl AggregatedKeyValue for Parameters {
+						type Key = ParametersKey;
+						type Value = ParametersValue;
+
+						fn into_parts(self) -> (ParametersKey, Option<ParametersValue>) {
+							match self {
+								$(
+									Parameters::$param(key, value) => (
+										ParametersKey::$param(key),
+										value.map(ParametersValue::$param),
+									),
+								)*
+							}
+						}
+					}
+				}
+			)*
+
+			$(#[$attr])*
+			#[derive(
+				Clone,
+				PartialEq,
+				Eq,
+				$crate::__private::codec::Encode,
+				$crate::__private::codec::Decode,
+				$crate::__private::codec::MaxEncodedLen,
+				$crate::__private::scale_info::TypeInfo,
+				$crate::__private::RuntimeDebug,
+			)]
+			$vis enum $name {
+				$(
+					$(#[$group_attr])*
+					$variant($group::Parameters),
+				)*
+			}
+
+			#[doc = "A parameter of any group."]
+			#[derive(
+				Clone,
+				PartialEq,
+				Eq,
+				$crate::__private::codec::Encode,
+				$crate::__private::codec::Decode,
+				$crate::__private::codec::MaxEncodedLen,
+				$crate::__private::scale_info::TypeInfo,
+				$crate::__private::RuntimeDebug,
+			)]
+			$vis enum [<$name Key>] {
+				$(
+					$(#[$group_attr])*
+					$variant($group::ParametersKey),
+				)*
+			}
+
+			#[doc = "The value of a parameter of any group."]
+			#[derive(
+				Clone,
+				PartialEq,
+				Eq,
+				$crate::__private::codec::Encode,
+				$crate::__private::codec::Decode,
+				$crate::__private::codec::MaxEncodedLen,
+				$crate::__private::scale_info::TypeInfo,
+				$crate::__private::RuntimeDebug,
+			)]
+			$vis enum [<$name Value>] {
+				$(
+					$(#[$group_attr])*
+					$variant($group::ParametersValue),
+				)*
+			}
+
+			impl $crate::traits::dynamic_params::AggregatedKeyValue for $name {
+				type Key = [<$name Key>];
+				type Value = [<$name Value>];
+
+				fn into_parts(self) -> ([<$name Key>], Option<[<$name Value>]>) {
+					match self {
+						$(
+							$name::$variant(parameter) => {
+								let (key, value) =
+									$crate::traits::dynamic_params::AggregatedKeyValue::into_parts(
+										parameter,
+									);
+								let value = value.map([<$name Value>]::$variant);
+								([<$name Key>]::$variant(key), value)
+							},
+						)*
+					}
+				}
+			}
+
+			$(
+				impl From<$group::Parameters> for $name {
+					fn from(parameter: $group::Parameters) -> Self {
+						$name::$variant(parameter)
+					}
+				}
+
+				impl From<$group::ParametersKey> for [<$name Key>] {
+					fn from(key: $group::ParametersKey) -> Self {
+						[<$name Key>]::$variant(key)
+					}
+				}
+			)*
+		}
+	};
+}
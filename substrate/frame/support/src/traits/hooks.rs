@@ -21,9 +21,10 @@
 
 #![deny(missing_docs)]
 
-use crate::weights::Weight;
+use crate::{traits::Get, weights::Weight};
 use impl_trait_for_tuples::impl_for_tuples;
-use sp_runtime::traits::AtLeast32BitUnsigned;
+use sp_arithmetic::traits::Saturating;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill};
 use sp_std::prelude::*;
 
 #[cfg(feature = "try-runtime")]
@@ -86,6 +87,194 @@ impl<BlockNumber: Copy + AtLeast32BitUnsigned> OnIdle<BlockNumber> for Tuple {
 	}
 }
 
+/// The `on_idle` hooks of a tuple of pallets, each with the name of its pallet.
+///
+/// Used by [`IdleScheduler`]s that need to tell the pallets apart.
+pub trait PalletsOnIdle<BlockNumber> {
+	/// The name and `on_idle` hook of every pallet, in order.
+	fn on_idle_hooks() -> Vec<(&'static str, fn(BlockNumber, Weight) -> Weight)>;
+}
+
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(feature = "tuples-128", impl_for_tuples(128))]
+impl<BlockNumber> PalletsOnIdle<BlockNumber> for Tuple {
+	for_tuples!( where #( Tuple: crate::traits::PalletInfoAccess + OnIdle<BlockNumber> )* );
+	fn on_idle_hooks() -> Vec<(&'static str, fn(BlockNumber, Weight) -> Weight)> {
+		sp_std::vec![for_tuples!(
+			#( (
+				<Tuple as crate::traits::PalletInfoAccess>::name(),
+				<Tuple as OnIdle<BlockNumber>>::on_idle as fn(BlockNumber, Weight) -> Weight,
+			) ),*
+		)]
+	}
+}
+
+/// Hands the weight left at the end of a block to the `on_idle` hooks of `Pallets`.
+///
+/// `()` calls the hooks in turn, as the [`OnIdle`] implementation for tuples does, so the first
+/// pallet may use up all of the weight. [`FairIdleScheduler`] reserves a share of it for each
+/// pallet instead.
+pub trait IdleScheduler<BlockNumber, Pallets> {
+	/// Run the `on_idle` hooks of `Pallets` with at most `remaining_weight`, returning the weight
+	/// they used.
+	fn on_idle(n: BlockNumber, remaining_weight: Weight) -> Weight;
+
+	/// Check the configuration of the scheduler, warning about pallets which never get any idle
+	/// weight.
+	#[cfg(feature = "try-runtime")]
+	fn try_state() -> Result<(), TryRuntimeError> {
+		Ok(())
+	}
+}
+
+impl<BlockNumber, Pallets: OnIdle<BlockNumber>> IdleScheduler<BlockNumber, Pallets> for () {
+	fn on_idle(n: BlockNumber, remaining_weight: Weight) -> Weight {
+		Pallets::on_idle(n, remaining_weight)
+	}
+}
+
+/// Is told how an [`IdleScheduler`] handed out the idle weight of a block.
+pub trait IdleMetrics {
+	/// `pallet` was offered `offered` idle weight and used `used` of it.
+	fn on_pallet_idle(_pallet: &'static str, _offered: Weight, _used: Weight) {}
+
+	/// `unused` idle weight was left over after all pallets had run.
+	fn on_unused_idle(_unused: Weight) {}
+}
+
+impl IdleMetrics for () {}
+
+/// [`IdleMetrics`] which logs the idle weight offered to and used by every pallet.
+pub struct LogIdleMetrics;
+
+impl IdleMetrics for LogIdleMetrics {
+	fn on_pallet_idle(pallet: &'static str, offered: Weight, used: Weight) {
+		log::debug!(
+			target: crate::LOG_TARGET,
+			"{} used {:?} of {:?} idle weight",
+			pallet,
+			used,
+			offered,
+		);
+	}
+
+	fn on_unused_idle(unused: Weight) {
+		log::debug!(target: crate::LOG_TARGET, "{:?} idle weight left unused", unused);
+	}
+}
+
+/// An [`IdleScheduler`] which reserves a share of the idle weight of every block for each pallet.
+///
+/// `Shares` maps pallet names to the share of the idle weight reserved for them, and the pallets
+/// not in it split what is left equally. A pallet may use any idle weight which is not reserved for
+/// the pallets running after it, so weight left unused by one pallet goes to the next. The pallet
+/// running first rotates with the block number, as with [`OnIdle`] for tuples.
+///
+/// `Metrics` is told how much weight each pallet was offered and used.
+pub struct FairIdleScheduler<Shares, Metrics = ()>(sp_std::marker::PhantomData<(Shares, Metrics)>);
+
+impl<Shares: Get<Vec<(&'static str, Perbill)>>, Metrics> FairIdleScheduler<Shares, Metrics> {
+	/// The share of the idle weight reserved for each of `pallets`.
+	fn shares(pallets: &[&'static str]) -> Vec<Perbill> {
+		let configured = Shares::get();
+		let share_of = |pallet: &str| {
+			configured.iter().find(|(name, _)| *name == pallet).map(|(_, share)| *share)
+		};
+
+		let (allotted, unlisted) =
+			pallets.iter().fold((Perbill::zero(), 0u32), |(allotted, unlisted), pallet| {
+				match share_of(pallet) {
+					Some(share) => (allotted.saturating_add(share), unlisted),
+					None => (allotted, unlisted + 1),
+				}
+			});
+		let rest = Perbill::one().saturating_sub(allotted);
+		let default_share = Perbill::from_parts(rest.deconstruct() / unlisted.max(1));
+
+		pallets.iter().map(|pallet| share_of(pallet).unwrap_or(default_share)).collect()
+	}
+}
+
+impl<BlockNumber, Pallets, Shares, Metrics> IdleScheduler<BlockNumber, Pallets>
+	for FairIdleScheduler<Shares, Metrics>
+where
+	BlockNumber: Copy + AtLeast32BitUnsigned,
+	Pallets: PalletsOnIdle<BlockNumber>,
+	Shares: Get<Vec<(&'static str, Perbill)>>,
+	Metrics: IdleMetrics,
+{
+	fn on_idle(n: BlockNumber, remaining_weight: Weight) -> Weight {
+		let hooks = Pallets::on_idle_hooks();
+		let len = hooks.len();
+		if len == 0 {
+			return Weight::zero()
+		}
+
+		let names: Vec<_> = hooks.iter().map(|(name, _)| *name).collect();
+		let reserved: Vec<_> = Self::shares(&names)
+			.into_iter()
+			.map(|share| {
+				Weight::from_parts(
+					share.mul_floor(remaining_weight.ref_time()),
+					share.mul_floor(remaining_weight.proof_size()),
+				)
+			})
+			.collect();
+		let mut still_reserved = reserved
+			.iter()
+			.fold(Weight::zero(), |total, weight| total.saturating_add(*weight));
+
+		let start_index = n % (len as u32).into();
+		let start_index: usize = start_index.try_into().ok().expect(
+			"`start_index % len` always fits into `usize`, because `len` can be in maximum `usize::MAX`; qed"
+		);
+		let mut used = Weight::zero();
+		for index in (0..len).map(|i| (start_index + i) % len) {
+			let (pallet, on_idle) = hooks[index];
+			still_reserved = still_reserved.saturating_sub(reserved[index]);
+			let offered = remaining_weight.saturating_sub(used).saturating_sub(still_reserved);
+			let consumed = on_idle(n, offered);
+			Metrics::on_pallet_idle(pallet, offered, consumed);
+			used = used.saturating_add(consumed);
+		}
+
+		Metrics::on_unused_idle(remaining_weight.saturating_sub(used));
+		used
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn try_state() -> Result<(), TryRuntimeError> {
+		let names: Vec<_> = Pallets::on_idle_hooks().into_iter().map(|(name, _)| name).collect();
+		let configured = Shares::get();
+
+		for (pallet, _) in configured.iter().filter(|(pallet, _)| !names.contains(pallet)) {
+			log::warn!(
+				target: crate::LOG_TARGET,
+				"idle weight is reserved for {}, which is not part of the runtime",
+				pallet,
+			);
+		}
+		let allotted: u64 = configured.iter().map(|(_, share)| share.deconstruct() as u64).sum();
+		if allotted > Perbill::one().deconstruct() as u64 {
+			log::warn!(
+				target: crate::LOG_TARGET,
+				"more than all of the idle weight is reserved; pallets running first may get none",
+			);
+		}
+		for (pallet, share) in names.iter().zip(Self::shares(&names)) {
+			if share.is_zero() {
+				log::warn!(
+					target: crate::LOG_TARGET,
+					"{} has no share of the idle weight and may never get any",
+					pallet,
+				);
+			}
+		}
+		Ok(())
+	}
+}
+
 /// A trait that will be called at genesis.
 ///
 /// Implementing this trait for a pallet let's you express operations that should
@@ -624,4 +813,82 @@ mod tests {
 			ON_IDLE_INVOCATION_ORDER.clear();
 		}
 	}
+
+	#[test]
+	fn fair_idle_scheduler_reserves_shares() {
+		crate::parameter_types! {
+			pub static Offered: Vec<(&'static str, Weight)> = Default::default();
+			pub static Unused: Weight = Weight::zero();
+			pub Shares: Vec<(&'static str, Perbill)> =
+				sp_std::vec![("Greedy", Perbill::from_percent(50))];
+		}
+
+		// Each pallet uses all the weight it is offered, up to `$limit`.
+		macro_rules! impl_test_type {
+			($name:ident, $limit:expr) => {
+				struct $name;
+				impl OnIdle<u32> for $name {
+					fn on_idle(_n: u32, remaining_weight: Weight) -> Weight {
+						Offered::mutate(|o| o.push((stringify!($name), remaining_weight)));
+						remaining_weight.min(Weight::from_parts($limit, 0))
+					}
+				}
+				impl crate::traits::PalletInfoAccess for $name {
+					fn index() -> usize {
+						0
+					}
+					fn name() -> &'static str {
+						stringify!($name)
+					}
+					fn module_name() -> &'static str {
+						stringify!($name)
+					}
+					fn crate_version() -> crate::traits::CrateVersion {
+						crate::traits::CrateVersion::new(1, 0, 0)
+					}
+				}
+			};
+		}
+
+		impl_test_type!(Greedy, u64::MAX);
+		impl_test_type!(Modest, 10);
+		impl_test_type!(Idle, 0);
+
+		struct Metrics;
+		impl IdleMetrics for Metrics {
+			fn on_unused_idle(unused: Weight) {
+				Unused::set(unused);
+			}
+		}
+
+		type Scheduler = FairIdleScheduler<Shares, Metrics>;
+		type Pallets = (Greedy, Modest, Idle);
+		let weight = |ref_time| Weight::from_parts(ref_time, 0);
+
+		// `Greedy` runs first and may only use what is not reserved for the others.
+		let used = <Scheduler as IdleScheduler<u32, Pallets>>::on_idle(0, weight(100));
+		assert_eq!(used, weight(60));
+		assert_eq!(
+			Offered::take(),
+			[("Greedy", weight(50)), ("Modest", weight(25)), ("Idle", weight(40))]
+		);
+		assert_eq!(Unused::get(), weight(40));
+
+		// `Greedy` runs last and gets what the others leave.
+		let used = <Scheduler as IdleScheduler<u32, Pallets>>::on_idle(1, weight(100));
+		assert_eq!(used, weight(100));
+		assert_eq!(
+			Offered::take(),
+			[("Modest", weight(25)), ("Idle", weight(40)), ("Greedy", weight(90))]
+		);
+		assert_eq!(Unused::get(), weight(0));
+
+		// The default scheduler lets the first pallet use up all of the weight.
+		let used = <() as IdleScheduler<u32, Pallets>>::on_idle(0, weight(100));
+		assert_eq!(used, weight(100));
+		assert_eq!(
+			Offered::take(),
+			[("Greedy", weight(100)), ("Modest", weight(0)), ("Idle", weight(0))]
+		);
+	}
 }
@@ -44,6 +44,7 @@ pub mod hold;
 mod imbalance;
 mod item_of;
 mod regular;
+mod union_of;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support_procedural::{CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
@@ -64,6 +65,7 @@ pub use item_of::ItemOf;
 pub use regular::{
 	Balanced, DecreaseIssuance, Dust, IncreaseIssuance, Inspect, Mutate, Unbalanced,
 };
+pub use union_of::{NativeFromLeft, NativeOrWithId, UnionOf};
 use sp_arithmetic::traits::Zero;
 use sp_core::Get;
 use sp_runtime::{traits::Convert, DispatchError};
@@ -0,0 +1,863 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Adapter to use a `fungible::*` and a `fungibles::*` implementation together as one
+//! `fungibles::*` implementation.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::Convert, DispatchError, DispatchResult, Either, RuntimeDebug, TokenError,
+};
+
+use crate::traits::tokens::{
+	fungible, fungibles, AssetId, Balance, DepositConsequence, Fortitude, Imbalance as ImbalanceT,
+	Precision, Preservation, Provenance, Restriction, WithdrawConsequence,
+};
+
+/// An asset which is either the native asset of the chain or the asset with the given id.
+#[derive(
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Default,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+)]
+pub enum NativeOrWithId<AssetId> {
+	/// The native asset.
+	#[default]
+	Native,
+	/// The asset with the given id.
+	WithId(AssetId),
+}
+
+impl<AssetId> From<AssetId> for NativeOrWithId<AssetId> {
+	fn from(asset: AssetId) -> Self {
+		Self::WithId(asset)
+	}
+}
+
+/// Criterion for [`UnionOf`] which takes [`NativeOrWithId::Native`] from the `fungible` and
+/// everything else from the `fungibles` implementation.
+pub struct NativeFromLeft;
+
+impl<AssetId> Convert<NativeOrWithId<AssetId>, Either<(), AssetId>> for NativeFromLeft {
+	fn convert(asset: NativeOrWithId<AssetId>) -> Either<(), AssetId> {
+		match asset {
+			NativeOrWithId::Native => Either::Left(()),
+			NativeOrWithId::WithId(id) => Either::Right(id),
+		}
+	}
+}
+
+/// Combine a `fungible` trait implementation `Left` and a `fungibles` trait implementation `Right`
+/// into a single `fungibles` trait implementation over the assets `AssetKind`.
+///
+/// `Criterion` tells for each asset whether it is the asset of `Left` or which asset of `Right` it
+/// is. Both implementations must use the same balance type and, where holds and freezes are
+/// supported, the same hold reasons and freeze ids.
+pub struct UnionOf<Left, Right, Criterion, AssetKind, AccountId>(
+	sp_std::marker::PhantomData<(Left, Right, Criterion, AssetKind, AccountId)>,
+);
+
+impl<
+		Left: fungible::Inspect<AccountId>,
+		Right: fungibles::Inspect<AccountId, Balance = Left::Balance>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::Inspect<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	type AssetId = AssetKind;
+	type Balance = Left::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::total_issuance(),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::total_issuance(a),
+		}
+	}
+	fn active_issuance(asset: Self::AssetId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::active_issuance(),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::active_issuance(a),
+		}
+	}
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::minimum_balance(),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::minimum_balance(a),
+		}
+	}
+	fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::balance(who),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::balance(a, who),
+		}
+	}
+	fn total_balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::total_balance(who),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::total_balance(a, who),
+		}
+	}
+	fn reducible_balance(
+		asset: Self::AssetId,
+		who: &AccountId,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Inspect<AccountId>>::reducible_balance(who, preservation, force),
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::reducible_balance(
+				a,
+				who,
+				preservation,
+				force,
+			),
+		}
+	}
+	fn can_deposit(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Inspect<AccountId>>::can_deposit(who, amount, provenance),
+			Either::Right(a) =>
+				<Right as fungibles::Inspect<AccountId>>::can_deposit(a, who, amount, provenance),
+		}
+	}
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Inspect<AccountId>>::can_withdraw(who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::Inspect<AccountId>>::can_withdraw(a, who, amount),
+		}
+	}
+	fn asset_exists(asset: Self::AssetId) -> bool {
+		match Criterion::convert(asset) {
+			Either::Left(()) => true,
+			Either::Right(a) => <Right as fungibles::Inspect<AccountId>>::asset_exists(a),
+		}
+	}
+}
+
+impl<
+		Left: fungible::InspectHold<AccountId>,
+		Right: fungibles::InspectHold<AccountId, Balance = Left::Balance, Reason = Left::Reason>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::InspectHold<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	type Reason = Left::Reason;
+
+	fn reducible_total_balance_on_hold(
+		asset: Self::AssetId,
+		who: &AccountId,
+		force: Fortitude,
+	) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectHold<AccountId>>::reducible_total_balance_on_hold(
+					who, force,
+				),
+			Either::Right(a) =>
+				<Right as fungibles::InspectHold<AccountId>>::reducible_total_balance_on_hold(
+					a, who, force,
+				),
+		}
+	}
+	fn hold_available(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId) -> bool {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectHold<AccountId>>::hold_available(reason, who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectHold<AccountId>>::hold_available(a, reason, who),
+		}
+	}
+	fn total_balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectHold<AccountId>>::total_balance_on_hold(who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectHold<AccountId>>::total_balance_on_hold(a, who),
+		}
+	}
+	fn balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+	) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectHold<AccountId>>::balance_on_hold(reason, who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectHold<AccountId>>::balance_on_hold(a, reason, who),
+		}
+	}
+	fn can_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> bool {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectHold<AccountId>>::can_hold(reason, who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::InspectHold<AccountId>>::can_hold(a, reason, who, amount),
+		}
+	}
+}
+
+impl<
+		Left: fungible::InspectFreeze<AccountId>,
+		Right: fungibles::InspectFreeze<AccountId, Balance = Left::Balance, Id = Left::Id>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::InspectFreeze<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	type Id = Left::Id;
+
+	fn balance_frozen(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectFreeze<AccountId>>::balance_frozen(id, who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectFreeze<AccountId>>::balance_frozen(a, id, who),
+		}
+	}
+	fn balance_freezable(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::InspectFreeze<AccountId>>::balance_freezable(who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectFreeze<AccountId>>::balance_freezable(a, who),
+		}
+	}
+	fn can_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> bool {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::InspectFreeze<AccountId>>::can_freeze(id, who),
+			Either::Right(a) =>
+				<Right as fungibles::InspectFreeze<AccountId>>::can_freeze(a, id, who),
+		}
+	}
+}
+
+impl<
+		Left: fungible::Unbalanced<AccountId>,
+		Right: fungibles::Unbalanced<AccountId, Balance = Left::Balance>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::Unbalanced<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn handle_dust(dust: fungibles::Dust<AccountId, Self>)
+	where
+		Self: Sized,
+	{
+		match Criterion::convert(dust.0) {
+			Either::Left(()) =>
+				<Left as fungible::Unbalanced<AccountId>>::handle_dust(fungible::Dust(dust.1)),
+			Either::Right(a) =>
+				<Right as fungibles::Unbalanced<AccountId>>::handle_dust(fungibles::Dust(
+					a, dust.1,
+				)),
+		}
+	}
+	fn write_balance(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Option<Self::Balance>, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Unbalanced<AccountId>>::write_balance(who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::Unbalanced<AccountId>>::write_balance(a, who, amount),
+		}
+	}
+	fn set_total_issuance(asset: Self::AssetId, amount: Self::Balance) {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Unbalanced<AccountId>>::set_total_issuance(amount),
+			Either::Right(a) =>
+				<Right as fungibles::Unbalanced<AccountId>>::set_total_issuance(a, amount),
+		}
+	}
+	fn decrease_balance(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Unbalanced<AccountId>>::decrease_balance(
+				who,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+			Either::Right(a) => <Right as fungibles::Unbalanced<AccountId>>::decrease_balance(
+				a,
+				who,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+		}
+	}
+	fn increase_balance(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Unbalanced<AccountId>>::increase_balance(who, amount, precision),
+			Either::Right(a) => <Right as fungibles::Unbalanced<AccountId>>::increase_balance(
+				a, who, amount, precision,
+			),
+		}
+	}
+	fn deactivate(asset: Self::AssetId, amount: Self::Balance) {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Unbalanced<AccountId>>::deactivate(amount),
+			Either::Right(a) => <Right as fungibles::Unbalanced<AccountId>>::deactivate(a, amount),
+		}
+	}
+	fn reactivate(asset: Self::AssetId, amount: Self::Balance) {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Unbalanced<AccountId>>::reactivate(amount),
+			Either::Right(a) => <Right as fungibles::Unbalanced<AccountId>>::reactivate(a, amount),
+		}
+	}
+}
+
+impl<
+		Left: fungible::UnbalancedHold<AccountId>,
+		Right: fungibles::UnbalancedHold<AccountId, Balance = Left::Balance, Reason = Left::Reason>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::UnbalancedHold<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn set_balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::UnbalancedHold<AccountId>>::set_balance_on_hold(
+				reason, who, amount,
+			),
+			Either::Right(a) =>
+				<Right as fungibles::UnbalancedHold<AccountId>>::set_balance_on_hold(
+					a, reason, who, amount,
+				),
+		}
+	}
+	fn decrease_balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::UnbalancedHold<AccountId>>::decrease_balance_on_hold(
+					reason, who, amount, precision,
+				),
+			Either::Right(a) =>
+				<Right as fungibles::UnbalancedHold<AccountId>>::decrease_balance_on_hold(
+					a, reason, who, amount, precision,
+				),
+		}
+	}
+	fn increase_balance_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::UnbalancedHold<AccountId>>::increase_balance_on_hold(
+					reason, who, amount, precision,
+				),
+			Either::Right(a) =>
+				<Right as fungibles::UnbalancedHold<AccountId>>::increase_balance_on_hold(
+					a, reason, who, amount, precision,
+				),
+		}
+	}
+}
+
+impl<
+		Left: fungible::Mutate<AccountId>,
+		Right: fungibles::Mutate<AccountId, Balance = Left::Balance>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::Mutate<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn mint_into(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Mutate<AccountId>>::mint_into(who, amount),
+			Either::Right(a) => <Right as fungibles::Mutate<AccountId>>::mint_into(a, who, amount),
+		}
+	}
+	fn burn_from(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Mutate<AccountId>>::burn_from(who, amount, precision, force),
+			Either::Right(a) =>
+				<Right as fungibles::Mutate<AccountId>>::burn_from(
+					a, who, amount, precision, force,
+				),
+		}
+	}
+	fn shelve(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Mutate<AccountId>>::shelve(who, amount),
+			Either::Right(a) => <Right as fungibles::Mutate<AccountId>>::shelve(a, who, amount),
+		}
+	}
+	fn restore(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Mutate<AccountId>>::restore(who, amount),
+			Either::Right(a) => <Right as fungibles::Mutate<AccountId>>::restore(a, who, amount),
+		}
+	}
+	fn transfer(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::Mutate<AccountId>>::transfer(source, dest, amount, preservation),
+			Either::Right(a) => <Right as fungibles::Mutate<AccountId>>::transfer(
+				a,
+				source,
+				dest,
+				amount,
+				preservation,
+			),
+		}
+	}
+	fn set_balance(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::Mutate<AccountId>>::set_balance(who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::Mutate<AccountId>>::set_balance(a, who, amount),
+		}
+	}
+}
+
+impl<
+		Left: fungible::MutateHold<AccountId>,
+		Right: fungibles::MutateHold<AccountId, Balance = Left::Balance, Reason = Left::Reason>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::MutateHold<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::MutateHold<AccountId>>::hold(reason, who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::MutateHold<AccountId>>::hold(a, reason, who, amount),
+		}
+	}
+	fn release(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::MutateHold<AccountId>>::release(reason, who, amount, precision),
+			Either::Right(a) => <Right as fungibles::MutateHold<AccountId>>::release(
+				a, reason, who, amount, precision,
+			),
+		}
+	}
+	fn burn_held(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::MutateHold<AccountId>>::burn_held(
+				reason, who, amount, precision, force,
+			),
+			Either::Right(a) => <Right as fungibles::MutateHold<AccountId>>::burn_held(
+				a, reason, who, amount, precision, force,
+			),
+		}
+	}
+	fn transfer_on_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		mode: Restriction,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::MutateHold<AccountId>>::transfer_on_hold(
+				reason, source, dest, amount, precision, mode, force,
+			),
+			Either::Right(a) => <Right as fungibles::MutateHold<AccountId>>::transfer_on_hold(
+				a, reason, source, dest, amount, precision, mode, force,
+			),
+		}
+	}
+	fn transfer_and_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::MutateHold<AccountId>>::transfer_and_hold(
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+			Either::Right(a) => <Right as fungibles::MutateHold<AccountId>>::transfer_and_hold(
+				a,
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+		}
+	}
+}
+
+impl<
+		Left: fungible::MutateFreeze<AccountId>,
+		Right: fungibles::MutateFreeze<AccountId, Balance = Left::Balance, Id = Left::Id>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::MutateFreeze<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn set_freeze(
+		asset: Self::AssetId,
+		id: &Self::Id,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::MutateFreeze<AccountId>>::set_freeze(id, who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::MutateFreeze<AccountId>>::set_freeze(a, id, who, amount),
+		}
+	}
+	fn extend_freeze(
+		asset: Self::AssetId,
+		id: &Self::Id,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match Criterion::convert(asset) {
+			Either::Left(()) =>
+				<Left as fungible::MutateFreeze<AccountId>>::extend_freeze(id, who, amount),
+			Either::Right(a) =>
+				<Right as fungibles::MutateFreeze<AccountId>>::extend_freeze(a, id, who, amount),
+		}
+	}
+	fn thaw(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> DispatchResult {
+		match Criterion::convert(asset) {
+			Either::Left(()) => <Left as fungible::MutateFreeze<AccountId>>::thaw(id, who),
+			Either::Right(a) => <Right as fungibles::MutateFreeze<AccountId>>::thaw(a, id, who),
+		}
+	}
+}
+
+/// Hands a dropped imbalance of [`UnionOf`] to the drop handler of the implementation its asset
+/// belongs to.
+pub struct ConvertUnionImbalanceDropHandler<LeftHandler, RightHandler, Criterion, RightAssetId>(
+	sp_std::marker::PhantomData<(LeftHandler, RightHandler, Criterion, RightAssetId)>,
+);
+
+impl<
+		AssetKind,
+		B,
+		LeftHandler: fungible::HandleImbalanceDrop<B>,
+		RightHandler: fungibles::HandleImbalanceDrop<RightAssetId, B>,
+		Criterion: Convert<AssetKind, Either<(), RightAssetId>>,
+		RightAssetId,
+	> fungibles::HandleImbalanceDrop<AssetKind, B>
+	for ConvertUnionImbalanceDropHandler<LeftHandler, RightHandler, Criterion, RightAssetId>
+{
+	fn handle(asset: AssetKind, amount: B) {
+		match Criterion::convert(asset) {
+			Either::Left(()) => LeftHandler::handle(amount),
+			Either::Right(a) => RightHandler::handle(a, amount),
+		}
+	}
+}
+
+/// Turn `imbalance` into an imbalance of `asset` without running the drop handler of `imbalance`.
+fn from_fungible<
+	A: AssetId,
+	B: Balance,
+	OnDrop: fungible::HandleImbalanceDrop<B>,
+	OppositeOnDrop: fungible::HandleImbalanceDrop<B>,
+	NewOnDrop: fungibles::HandleImbalanceDrop<A, B>,
+	NewOppositeOnDrop: fungibles::HandleImbalanceDrop<A, B>,
+>(
+	imbalance: fungible::Imbalance<B, OnDrop, OppositeOnDrop>,
+	asset: A,
+) -> fungibles::Imbalance<A, B, NewOnDrop, NewOppositeOnDrop> {
+	let amount = imbalance.peek();
+	sp_std::mem::forget(imbalance);
+	fungibles::Imbalance::new(asset, amount)
+}
+
+/// Turn `imbalance` into an imbalance of `asset` without running the drop handler of `imbalance`.
+fn from_fungibles<
+	A: AssetId,
+	B: Balance,
+	OnDrop: fungibles::HandleImbalanceDrop<A, B>,
+	OppositeOnDrop: fungibles::HandleImbalanceDrop<A, B>,
+	NewA: AssetId,
+	NewOnDrop: fungibles::HandleImbalanceDrop<NewA, B>,
+	NewOppositeOnDrop: fungibles::HandleImbalanceDrop<NewA, B>,
+>(
+	imbalance: fungibles::Imbalance<A, B, OnDrop, OppositeOnDrop>,
+	asset: NewA,
+) -> fungibles::Imbalance<NewA, B, NewOnDrop, NewOppositeOnDrop> {
+	let amount = imbalance.peek();
+	sp_std::mem::forget(imbalance);
+	fungibles::Imbalance::new(asset, amount)
+}
+
+impl<
+		Left: fungible::Balanced<AccountId>,
+		Right: fungibles::Balanced<AccountId, Balance = Left::Balance>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::Balanced<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	type OnDropDebt = ConvertUnionImbalanceDropHandler<
+		Left::OnDropDebt,
+		Right::OnDropDebt,
+		Criterion,
+		Right::AssetId,
+	>;
+	type OnDropCredit = ConvertUnionImbalanceDropHandler<
+		Left::OnDropCredit,
+		Right::OnDropCredit,
+		Criterion,
+		Right::AssetId,
+	>;
+
+	fn rescind(asset: Self::AssetId, amount: Self::Balance) -> fungibles::Debt<AccountId, Self> {
+		match Criterion::convert(asset.clone()) {
+			Either::Left(()) =>
+				from_fungible(<Left as fungible::Balanced<AccountId>>::rescind(amount), asset),
+			Either::Right(a) => {
+				let debt = <Right as fungibles::Balanced<AccountId>>::rescind(a, amount);
+				from_fungibles(debt, asset)
+			},
+		}
+	}
+	fn issue(asset: Self::AssetId, amount: Self::Balance) -> fungibles::Credit<AccountId, Self> {
+		match Criterion::convert(asset.clone()) {
+			Either::Left(()) =>
+				from_fungible(<Left as fungible::Balanced<AccountId>>::issue(amount), asset),
+			Either::Right(a) =>
+				from_fungibles(<Right as fungibles::Balanced<AccountId>>::issue(a, amount), asset),
+		}
+	}
+	fn deposit(
+		asset: Self::AssetId,
+		who: &AccountId,
+		value: Self::Balance,
+		precision: Precision,
+	) -> Result<fungibles::Debt<AccountId, Self>, DispatchError> {
+		match Criterion::convert(asset.clone()) {
+			Either::Left(()) =>
+				<Left as fungible::Balanced<AccountId>>::deposit(who, value, precision)
+					.map(|debt| from_fungible(debt, asset)),
+			Either::Right(a) =>
+				<Right as fungibles::Balanced<AccountId>>::deposit(a, who, value, precision)
+					.map(|debt| from_fungibles(debt, asset)),
+		}
+	}
+	fn withdraw(
+		asset: Self::AssetId,
+		who: &AccountId,
+		value: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<fungibles::Credit<AccountId, Self>, DispatchError> {
+		match Criterion::convert(asset.clone()) {
+			Either::Left(()) => <Left as fungible::Balanced<AccountId>>::withdraw(
+				who,
+				value,
+				precision,
+				preservation,
+				force,
+			)
+			.map(|credit| from_fungible(credit, asset)),
+			Either::Right(a) => <Right as fungibles::Balanced<AccountId>>::withdraw(
+				a,
+				who,
+				value,
+				precision,
+				preservation,
+				force,
+			)
+			.map(|credit| from_fungibles(credit, asset)),
+		}
+	}
+}
+
+impl<
+		Left: fungible::BalancedHold<AccountId>,
+		Right: fungibles::BalancedHold<AccountId, Balance = Left::Balance, Reason = Left::Reason>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::BalancedHold<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn slash(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> (fungibles::Credit<AccountId, Self>, Self::Balance) {
+		match Criterion::convert(asset.clone()) {
+			Either::Left(()) => {
+				let (credit, amount) =
+					<Left as fungible::BalancedHold<AccountId>>::slash(reason, who, amount);
+				(from_fungible(credit, asset), amount)
+			},
+			Either::Right(a) => {
+				let (credit, amount) =
+					<Right as fungibles::BalancedHold<AccountId>>::slash(a, reason, who, amount);
+				(from_fungibles(credit, asset), amount)
+			},
+		}
+	}
+}
+
+impl<
+		Left: fungible::Inspect<AccountId>,
+		Right: fungibles::Create<AccountId, Balance = Left::Balance>,
+		Criterion: Convert<AssetKind, Either<(), Right::AssetId>>,
+		AssetKind: AssetId,
+		AccountId,
+	> fungibles::Create<AccountId> for UnionOf<Left, Right, Criterion, AssetKind, AccountId>
+{
+	fn create(
+		asset: Self::AssetId,
+		admin: AccountId,
+		is_sufficient: bool,
+		min_balance: Self::Balance,
+	) -> DispatchResult {
+		match Criterion::convert(asset) {
+			// The asset of `Left` always exists.
+			Either::Left(()) => Err(TokenError::Unsupported.into()),
+			Either::Right(a) =>
+				<Right as fungibles::Create<AccountId>>::create(
+					a,
+					admin,
+					is_sufficient,
+					min_balance,
+				),
+		}
+	}
+}
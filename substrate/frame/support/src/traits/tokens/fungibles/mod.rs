@@ -18,6 +18,7 @@
 //! The traits for sets of fungible tokens and any associated types.
 
 pub mod approvals;
+pub mod conformance_tests;
 mod enumerable;
 pub mod freeze;
 pub mod hold;
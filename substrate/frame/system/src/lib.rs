@@ -673,6 +673,11 @@ pub mod pallet {
 	#[pallet::whitelist_storage]
 	pub(super) type ExecutionPhase<T: Config> = StorageValue<_, Phase>;
 
+	/// Whether all inherents of the current block have been applied.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub(super) type InherentsApplied<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[derive(frame_support::DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -1386,6 +1391,7 @@ impl<T: Config> Pallet<T> {
 
 		// Remove previous block data from storage
 		BlockWeight::<T>::kill();
+		InherentsApplied::<T>::kill();
 	}
 
 	/// Remove temporary "environment" entries in storage, compute the storage root and return the
@@ -1428,6 +1434,7 @@ impl<T: Config> Pallet<T> {
 		);
 		ExecutionPhase::<T>::kill();
 		AllExtrinsicsLen::<T>::kill();
+		InherentsApplied::<T>::kill();
 		storage::unhashed::kill(well_known_keys::INTRABLOCK_ENTROPY);
 
 		// The following fields
@@ -1648,6 +1655,16 @@ impl<T: Config> Pallet<T> {
 		ExecutionPhase::<T>::put(Phase::ApplyExtrinsic(0))
 	}
 
+	/// To be called once all inherents of the block have been applied.
+	pub fn note_inherents_applied() {
+		InherentsApplied::<T>::put(true);
+	}
+
+	/// Whether all inherents of the current block have been applied.
+	pub fn inherents_applied() -> bool {
+		InherentsApplied::<T>::get()
+	}
+
 	/// An account is being created.
 	pub fn on_created_account(who: T::AccountId, _a: &mut AccountInfo<T::Nonce, T::AccountData>) {
 		T::OnNewAccount::on_new_account(&who);
@@ -187,6 +187,10 @@ impl pallet_assets::Config for Runtime {
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<20>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = ();
@@ -212,6 +216,10 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type WeightInfo = ();
 	type CallbackHandle = ();
@@ -243,6 +251,10 @@ impl pallet_asset_conversion::Config for Runtime {
 	type PalletId = AssetConversionPalletId;
 	type WeightInfo = ();
 	type LPFee = ConstU32<3>; // means 0.3%
+	type FeeTiersOrigin = EnsureRoot<AccountId>;
+	type MaxFeeTiers = ConstU32<10>;
+	type MaxPriceObservations = ConstU32<3>;
+	type PriceObservationPeriod = ConstU64<5>;
 	type PoolSetupFee = ConstU64<100>; // should be more or equal to the existential deposit
 	type PoolSetupFeeReceiver = AssetConversionOrigin;
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
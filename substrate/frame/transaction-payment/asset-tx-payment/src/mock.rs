@@ -160,6 +160,10 @@ impl pallet_assets::Config for Runtime {
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<20>;
 	type Freezer = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = ();
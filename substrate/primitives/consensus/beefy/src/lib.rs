@@ -150,7 +150,7 @@ pub const GENESIS_AUTHORITY_SET_ID: u64 = 0;
 pub type ValidatorSetId = u64;
 
 /// A set of BEEFY authorities, a.k.a. validators.
-#[derive(Decode, Encode, Debug, PartialEq, Clone, TypeInfo)]
+#[derive(Decode, Encode, Debug, PartialEq, Eq, Clone, TypeInfo)]
 pub struct ValidatorSet<AuthorityId> {
 	/// Public keys of the validator set elements
 	validators: Vec<AuthorityId>,
@@ -168,7 +168,7 @@ mod execution {
 		traits::{CallContext, CodeExecutor, RuntimeCode},
 	};
 	use sp_externalities::Extensions;
-	use sp_trie::PrefixedMemoryDB;
+	use sp_trie::{MerkleValue, PrefixedMemoryDB};
 	use std::collections::{HashMap, HashSet};
 
 	pub(crate) type CallResult<E> = Result<Vec<u8>, E>;
@@ -716,6 +716,58 @@ mod execution {
 		size_limit: usize,
 		start_at: Option<&[u8]>,
 	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		prove_range_read_with_limits_on_trie_backend(
+			trie_backend,
+			child_info,
+			prefix,
+			None,
+			size_limit,
+			start_at,
+		)
+	}
+
+	/// Generate range storage read proof of at most `count_limit` keys.
+	///
+	/// Like `prove_range_read_with_size`, the proof always includes at least one key.
+	pub fn prove_range_read_with_limits<B, H>(
+		backend: B,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		count_limit: Option<u32>,
+		size_limit: usize,
+		start_at: Option<&[u8]>,
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		B: AsTrieBackend<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let trie_backend = backend.as_trie_backend();
+		prove_range_read_with_limits_on_trie_backend(
+			trie_backend,
+			child_info,
+			prefix,
+			count_limit,
+			size_limit,
+			start_at,
+		)
+	}
+
+	/// Generate range storage read proof of at most `count_limit` keys on an existing trie
+	/// backend.
+	pub fn prove_range_read_with_limits_on_trie_backend<S, H>(
+		trie_backend: &TrieBackend<S, H>,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		count_limit: Option<u32>,
+		size_limit: usize,
+		start_at: Option<&[u8]>,
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
 	where
 		S: trie_backend_essence::TrieBackendStorage<H>,
 		H: Hasher,
@@ -739,7 +791,10 @@ mod execution {
 
 		for item in iter {
 			item.map_err(|e| Box::new(e) as Box<dyn Error>)?;
-			if count == 0 || recorder.estimate_encoded_size() <= size_limit {
+			if count == 0 ||
+				(recorder.estimate_encoded_size() <= size_limit &&
+					count_limit.map_or(true, |limit| count < limit))
+			{
 				count += 1;
 			} else {
 				break
@@ -752,6 +807,46 @@ mod execution {
 		Ok((proof, count))
 	}
 
+	/// Generate a proof of the closest merkle value of `key` in the top trie, or in the child
+	/// trie `child_info`.
+	pub fn prove_closest_merkle_value<B, H>(
+		backend: B,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> Result<StorageProof, Box<dyn Error>>
+	where
+		B: AsTrieBackend<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let trie_backend = backend.as_trie_backend();
+		prove_closest_merkle_value_on_trie_backend(trie_backend, child_info, key)
+	}
+
+	/// Generate a proof of the closest merkle value of `key` on an existing trie backend.
+	pub fn prove_closest_merkle_value_on_trie_backend<S, H>(
+		trie_backend: &TrieBackend<S, H>,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> Result<StorageProof, Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend =
+			TrieBackendBuilder::wrap(trie_backend).with_recorder(Default::default()).build();
+		match child_info {
+			Some(child_info) => proving_backend.child_closest_merkle_value(child_info, key),
+			None => proving_backend.closest_merkle_value(key),
+		}
+		.map_err(|e| Box::new(e) as Box<dyn Error>)?;
+
+		Ok(proving_backend
+			.extract_proof()
+			.expect("A recorder was set and thus, a storage proof can be extracted; qed"))
+	}
+
 	/// Generate child storage read proof.
 	pub fn prove_child_read<B, H, I>(
 		backend: B,
@@ -882,6 +977,25 @@ mod execution {
 		)
 	}
 
+	/// Check closest merkle value proof, generated by `prove_closest_merkle_value` call.
+	pub fn read_closest_merkle_value_proof_check<H>(
+		root: H::Out,
+		proof: StorageProof,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> Result<Option<MerkleValue<H::Out>>, Box<dyn Error>>
+	where
+		H: Hasher + 'static,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend = create_proof_check_backend::<H>(root, proof)?;
+		match child_info {
+			Some(child_info) => proving_backend.child_closest_merkle_value(child_info, key),
+			None => proving_backend.closest_merkle_value(key),
+		}
+		.map_err(|e| Box::new(e) as Box<dyn Error>)
+	}
+
 	/// Check child storage read proof, generated by `prove_child_read` call.
 	pub fn read_child_proof_check<H, I>(
 		root: H::Out,
@@ -1747,6 +1861,68 @@ mod tests {
 		assert_eq!(count, 3);
 	}
 
+	#[test]
+	fn prove_read_with_count_limit_works() {
+		let state_version = StateVersion::V0;
+		let remote_backend = trie_backend::tests::test_trie(state_version, None, None);
+		let remote_root = remote_backend.storage_root(::std::iter::empty(), state_version).0;
+		let (proof, count) =
+			prove_range_read_with_limits(remote_backend, None, None, Some(10), 50000, None)
+				.unwrap();
+		assert_eq!(count, 10);
+
+		let (results, completed) = read_range_proof_check::<BlakeTwo256>(
+			remote_root,
+			proof,
+			None,
+			None,
+			Some(count),
+			None,
+		)
+		.unwrap();
+		assert_eq!(results.len() as u32, count);
+		assert_eq!(completed, false);
+	}
+
+	#[test]
+	fn prove_closest_merkle_value_works() {
+		let state_version = StateVersion::V1;
+		let child_info = ChildInfo::new_default(b"sub1");
+		let remote_backend = trie_backend::tests::test_trie(state_version, None, None);
+		let remote_root = remote_backend.storage_root(::std::iter::empty(), state_version).0;
+		let expected = remote_backend.closest_merkle_value(b"value").unwrap();
+		let expected_child =
+			remote_backend.child_closest_merkle_value(&child_info, b"value").unwrap();
+		assert!(expected.is_some());
+		assert!(expected_child.is_some());
+
+		let proof =
+			prove_closest_merkle_value_on_trie_backend(&remote_backend, None, b"value").unwrap();
+		let local_result = read_closest_merkle_value_proof_check::<BlakeTwo256>(
+			remote_root,
+			proof,
+			None,
+			b"value",
+		)
+		.unwrap();
+		assert_eq!(local_result, expected);
+
+		let proof = prove_closest_merkle_value_on_trie_backend(
+			&remote_backend,
+			Some(&child_info),
+			b"value",
+		)
+		.unwrap();
+		let local_result = read_closest_merkle_value_proof_check::<BlakeTwo256>(
+			remote_root,
+			proof,
+			Some(&child_info),
+			b"value",
+		)
+		.unwrap();
+		assert_eq!(local_result, expected_child);
+	}
+
 	#[test]
 	fn inner_state_versioning_switch_proofs() {
 		let mut state_version = StateVersion::V0;
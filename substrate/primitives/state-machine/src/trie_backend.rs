@@ -37,7 +37,9 @@ use sp_trie::{
 	MemoryDB, StorageProof,
 };
 #[cfg(not(feature = "std"))]
-use sp_trie::{Error, NodeCodec};
+use sp_std::boxed::Box;
+#[cfg(not(feature = "std"))]
+use sp_trie::{Error, NodeCodec, TrieRecorder};
 use sp_trie::{MerkleValue, PrefixedMemoryDB};
 use trie_db::TrieCache as TrieCacheT;
 #[cfg(not(feature = "std"))]
@@ -66,6 +68,19 @@ pub trait TrieCacheProvider<H: Hasher> {
 	/// This must be used for the cache returned by [`Self::as_trie_db_mut_cache`] as otherwise the
 	/// cached data is just thrown away.
 	fn merge<'a>(&'a self, other: Self::Cache<'a>, new_root: H::Out);
+
+	/// Returns a recorder for the accesses to the trie with the given `storage_root`.
+	///
+	/// The backend has no recorder of its own in `no_std`, so this allows a runtime to keep track
+	/// of the trie nodes it accesses, e.g. to know the size of the storage proof while validating
+	/// a parachain block. Nothing is recorded by default.
+	#[cfg(not(feature = "std"))]
+	fn as_trie_db_recorder(
+		&self,
+		_storage_root: H::Out,
+	) -> Option<Box<dyn TrieRecorder<H::Out> + '_>> {
+		None
+	}
 }
 
 #[cfg(feature = "std")]
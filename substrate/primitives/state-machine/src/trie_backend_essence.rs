@@ -318,7 +318,13 @@ impl<S: TrieBackendStorage<H>, H: Hasher, C: TrieCacheProvider<H>> TrieBackendEs
 
 		#[cfg(not(feature = "std"))]
 		{
-			callback(None, cache)
+			let mut recorder =
+				self.trie_node_cache.as_ref().and_then(|c| c.as_trie_db_recorder(storage_root));
+			let recorder = match recorder.as_mut() {
+				Some(recorder) => Some(&mut **recorder as &mut dyn TrieRecorder<H::Out>),
+				None => None,
+			};
+			callback(recorder, cache)
 		}
 	}
 
@@ -365,16 +371,23 @@ impl<S: TrieBackendStorage<H>, H: Hasher, C: TrieCacheProvider<H>> TrieBackendEs
 	#[cfg(not(feature = "std"))]
 	fn with_recorder_and_cache_for_storage_root<R>(
 		&self,
-		_storage_root: Option<H::Out>,
+		storage_root: Option<H::Out>,
 		callback: impl FnOnce(
 			Option<&mut dyn TrieRecorder<H::Out>>,
 			Option<&mut dyn TrieCache<NodeCodec<H>>>,
 		) -> (Option<H::Out>, R),
 	) -> R {
 		if let Some(local_cache) = self.trie_node_cache.as_ref() {
+			let storage_root = storage_root.unwrap_or_else(|| self.root);
+			let mut recorder = local_cache.as_trie_db_recorder(storage_root);
+			let recorder = match recorder.as_mut() {
+				Some(recorder) => Some(&mut **recorder as &mut dyn TrieRecorder<H::Out>),
+				None => None,
+			};
+
 			let mut cache = local_cache.as_trie_db_mut_cache();
 
-			let (new_root, r) = callback(None, Some(&mut cache));
+			let (new_root, r) = callback(recorder, Some(&mut cache));
 
 			if let Some(new_root) = new_root {
 				local_cache.merge(cache, new_root);
@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Private channels between two accounts.
+//!
+//! A channel is identified by a topic derived from a secret shared by both accounts, so that only
+//! the two parties can tell which statements belong to it. Message bodies are encrypted to the
+//! recipient's key and carry their expiry timestamp as priority: once the store is full, the
+//! messages expiring first are evicted first, and expired messages can be removed by either
+//! party.
+
+use crate::{ecies, Statement, Topic, TopicFilter};
+
+/// Context used to derive the channel topic from the shared secret.
+const TOPIC_CONTEXT: &[u8] = b"statement-store-private-channel";
+
+/// Private channel between two accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivateChannel {
+	topic: Topic,
+}
+
+impl PrivateChannel {
+	/// Create the channel identified by `shared_secret`.
+	pub fn new(shared_secret: &[u8]) -> Self {
+		let mut material = TOPIC_CONTEXT.to_vec();
+		material.extend_from_slice(shared_secret);
+		Self { topic: sp_core::hashing::blake2_256(&material) }
+	}
+
+	/// Create the channel between the owner of `local` and the owner of `remote`. Both parties end
+	/// up with the same channel.
+	pub fn between(
+		local: &sp_core::ed25519::Pair,
+		remote: &sp_core::ed25519::Public,
+	) -> core::result::Result<Self, ecies::Error> {
+		Ok(Self::new(&ecies::shared_secret_ed25519(local, remote)?))
+	}
+
+	/// Topic of the statements of the channel.
+	pub fn topic(&self) -> Topic {
+		self.topic
+	}
+
+	/// Filter selecting the statements of the channel.
+	pub fn filter(&self) -> TopicFilter {
+		TopicFilter::MatchAll(vec![self.topic])
+	}
+
+	/// Create an unsigned statement carrying `data` encrypted to `recipient`, which expires at
+	/// `expiry`, in seconds since the UNIX epoch.
+	pub fn message(
+		&self,
+		data: &[u8],
+		recipient: &sp_core::ed25519::Public,
+		expiry: u32,
+	) -> core::result::Result<Statement, ecies::Error> {
+		let mut statement = Statement::new();
+		statement.set_topic(0, self.topic);
+		statement.set_priority(expiry);
+		statement.encrypt(data, recipient)?;
+		Ok(statement)
+	}
+
+	/// Check if `statement` is a message of the channel.
+	pub fn contains(&self, statement: &Statement) -> bool {
+		self.filter().matches(statement)
+	}
+}
+
+/// Check if the channel message `statement` is expired at `now`, in seconds since the UNIX epoch.
+pub fn is_expired(statement: &Statement, now: u64) -> bool {
+	statement.priority().map_or(true, |expiry| u64::from(expiry) <= now)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sp_core::Pair;
+
+	#[test]
+	fn both_parties_read_the_same_channel() {
+		let (alice, _) = sp_core::ed25519::Pair::generate();
+		let (bob, _) = sp_core::ed25519::Pair::generate();
+		let (charlie, _) = sp_core::ed25519::Pair::generate();
+
+		let channel = PrivateChannel::between(&alice, &bob.public()).unwrap();
+		assert_eq!(channel, PrivateChannel::between(&bob, &alice.public()).unwrap());
+		assert_ne!(channel, PrivateChannel::between(&charlie, &bob.public()).unwrap());
+
+		let message = channel.message(b"hello", &bob.public(), 100).unwrap();
+		assert!(channel.contains(&message));
+		assert_eq!(message.decrypt_private(&bob).unwrap(), Some(b"hello".to_vec()));
+		assert!(message.decrypt_private(&charlie).is_err());
+
+		assert!(!is_expired(&message, 99));
+		assert!(is_expired(&message, 100));
+	}
+}
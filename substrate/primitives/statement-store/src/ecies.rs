@@ -90,13 +90,24 @@ pub fn encrypt_x25519(pk: &PublicKey, plaintext: &[u8]) -> Result<Vec<u8>, Error
 	Ok(out)
 }
 
+fn ed25519_to_x25519_public(pk: &sp_core::ed25519::Public) -> Result<PublicKey, Error> {
+	let ed25519 = curve25519_dalek::edwards::CompressedEdwardsY(pk.0);
+	let x25519 = ed25519.decompress().ok_or(Error::BadData)?.to_montgomery();
+	Ok(x25519_dalek::PublicKey::from(x25519.to_bytes()))
+}
+
+fn ed25519_to_x25519_secret(pair: &sp_core::ed25519::Pair) -> Result<SecretKey, Error> {
+	let raw = pair.to_raw_vec();
+	let hash: [u8; 32] = sha2::Sha512::digest(&raw).as_slice()[..32]
+		.try_into()
+		.map_err(|_| Error::Decryption)?;
+	Ok(x25519_dalek::StaticSecret::from(hash))
+}
+
 /// Encrypt `plaintext` with the given ed25519 public key. Decryption can be performed with the
 /// matching secret key.
 pub fn encrypt_ed25519(pk: &sp_core::ed25519::Public, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
-	let ed25519 = curve25519_dalek::edwards::CompressedEdwardsY(pk.0);
-	let x25519 = ed25519.decompress().ok_or(Error::BadData)?.to_montgomery();
-	let montgomery = x25519_dalek::PublicKey::from(x25519.to_bytes());
-	encrypt_x25519(&montgomery, plaintext)
+	encrypt_x25519(&ed25519_to_x25519_public(pk)?, plaintext)
 }
 
 /// Decrypt with the given x25519 secret key.
@@ -119,12 +130,17 @@ pub fn decrypt_x25519(sk: &SecretKey, encrypted: &[u8]) -> Result<Vec<u8>, Error
 
 /// Decrypt with the given ed25519 key pair.
 pub fn decrypt_ed25519(pair: &sp_core::ed25519::Pair, encrypted: &[u8]) -> Result<Vec<u8>, Error> {
-	let raw = pair.to_raw_vec();
-	let hash: [u8; 32] = sha2::Sha512::digest(&raw).as_slice()[..32]
-		.try_into()
-		.map_err(|_| Error::Decryption)?;
-	let secret = x25519_dalek::StaticSecret::from(hash);
-	decrypt_x25519(&secret, encrypted)
+	decrypt_x25519(&ed25519_to_x25519_secret(pair)?, encrypted)
+}
+
+/// Compute the x25519 shared secret of the given ed25519 key pair and the public key of the other
+/// party. Both parties derive the same secret.
+pub fn shared_secret_ed25519(
+	pair: &sp_core::ed25519::Pair,
+	pk: &sp_core::ed25519::Public,
+) -> Result<[u8; 32], Error> {
+	let secret = ed25519_to_x25519_secret(pair)?;
+	Ok(secret.diffie_hellman(&ed25519_to_x25519_public(pk)?).to_bytes())
 }
 
 #[cfg(test)]
@@ -157,6 +173,17 @@ mod test {
 		assert_eq!(plain_message, decrypted.as_slice());
 	}
 
+	#[test]
+	fn ed25519_shared_secret_is_symmetric() {
+		let (alice, _) = sp_core::ed25519::Pair::generate();
+		let (bob, _) = sp_core::ed25519::Pair::generate();
+
+		assert_eq!(
+			shared_secret_ed25519(&alice, &bob.public()).unwrap(),
+			shared_secret_ed25519(&bob, &alice.public()).unwrap(),
+		);
+	}
+
 	#[test]
 	fn fails_on_bad_data() {
 		let sk = SecretKey::random_from_rng(OsRng);
@@ -46,9 +46,15 @@ pub const MAX_TOPICS: usize = 4;
 
 #[cfg(feature = "std")]
 pub use store_api::{
-	Error, NetworkPriority, Result, StatementSource, StatementStore, SubmitResult,
+	Error, NetworkPriority, Result, StatementSource, StatementStore, StatementStream, SubmitResult,
+	TopicFilter,
 };
 
+#[cfg(feature = "std")]
+pub use channel::PrivateChannel;
+
+#[cfg(feature = "std")]
+pub mod channel;
 #[cfg(feature = "std")]
 mod ecies;
 pub mod runtime_api;
@@ -16,7 +16,7 @@
 // limitations under the License.
 
 pub use crate::runtime_api::StatementSource;
-use crate::{Hash, Statement, Topic};
+use crate::{Hash, Statement, Topic, MAX_TOPICS};
 
 /// Statement store error.
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
@@ -30,6 +30,9 @@ pub enum Error {
 	/// Error making runtime call.
 	#[error("Error calling into the runtime")]
 	Runtime,
+	/// Key is missing from the keystore or invalid.
+	#[error("Invalid key: {0:?}")]
+	InvalidKey(String),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +64,31 @@ pub enum SubmitResult {
 /// Result type for `Error`
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Topic filter used to select statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicFilter {
+	/// Match statements which include all of the given topics. An empty list matches any
+	/// statement.
+	MatchAll(Vec<Topic>),
+	/// Match statements which include at least one of the given topics.
+	MatchAny(Vec<Topic>),
+}
+
+impl TopicFilter {
+	/// Check if `statement` passes the filter.
+	pub fn matches(&self, statement: &Statement) -> bool {
+		let has_topic =
+			|topic: &Topic| (0..MAX_TOPICS).any(|i| statement.topic(i).as_ref() == Some(topic));
+		match self {
+			TopicFilter::MatchAll(topics) => topics.iter().all(has_topic),
+			TopicFilter::MatchAny(topics) => topics.iter().any(has_topic),
+		}
+	}
+}
+
+/// Stream of new statements returned by [`StatementStore::subscribe`].
+pub type StatementStream = futures::channel::mpsc::Receiver<Statement>;
+
 /// Statement store API.
 pub trait StatementStore: Send + Sync {
 	/// Return all statements.
@@ -87,4 +115,35 @@ pub trait StatementStore: Send + Sync {
 
 	/// Remove a statement from the store.
 	fn remove(&self, hash: &Hash) -> Result<()>;
+
+	/// Post `data` on the private channel between the local key `sender` and `recipient`.
+	///
+	/// The data is encrypted to `recipient` and the statement is signed with the `sender` key,
+	/// which must be available to the client. The message expires at `expiry`, in seconds since
+	/// the UNIX epoch. Expired messages of the channel are removed from the store.
+	fn post_private(
+		&self,
+		sender: [u8; 32],
+		recipient: [u8; 32],
+		data: &[u8],
+		expiry: u32,
+	) -> SubmitResult;
+
+	/// Return the decrypted data of all unexpired messages sent to the local key `recipient` on
+	/// the private channel between `recipient` and `sender`. The `recipient` key must be available
+	/// to the client. Expired messages of the channel are removed from the store.
+	fn private_messages(&self, recipient: [u8; 32], sender: [u8; 32]) -> Result<Vec<Vec<u8>>>;
+
+	/// Subscribe to statements which pass `filter` and whose decryption key is `dest`. Statements
+	/// without a decryption key are selected if `dest` is `None`.
+	///
+	/// Returns the matching statements which are currently in the store, along with a stream of
+	/// the matching statements submitted afterwards. At most `buffer` statements are queued for
+	/// the subscriber. The stream is terminated if the subscriber falls further behind.
+	fn subscribe(
+		&self,
+		filter: TopicFilter,
+		dest: Option<[u8; 32]>,
+		buffer: usize,
+	) -> Result<(Vec<Statement>, StatementStream)>;
 }
@@ -25,6 +25,8 @@ mod error;
 mod node_codec;
 mod node_header;
 #[cfg(feature = "std")]
+pub mod proof_size_extension;
+#[cfg(feature = "std")]
 pub mod recorder;
 mod storage_proof;
 mod trie_codec;
@@ -56,6 +58,12 @@ pub use trie_db::{proof::VerifyError, MerkleValue};
 /// The Substrate format implementation of `TrieStream`.
 pub use trie_stream::TrieStream;
 
+/// Provides the size of the storage proof recorded so far.
+pub trait ProofSizeProvider {
+	/// Returns the storage proof size.
+	fn estimate_encoded_size(&self) -> usize;
+}
+
 /// substrate trie layout
 pub struct LayoutV0<H>(PhantomData<H>);
 